@@ -0,0 +1,188 @@
+// apps/cli/src/bin/mock_mcp_server.rs
+//! # Mock MCP Server (stdio)
+//!
+//! 用于测试 MCP 客户端的最小 stdio 服务器，声明工具、资源 (可订阅) 与提示词能力。
+//! - `add_tool` 工具: 新增一个工具并发出 `notifications/tools/list_changed`
+//! - `resources/subscribe`: 立即发出一次 `notifications/resources/updated`
+//! - `pid` 工具返回进程号，`sleep` 工具延迟响应，`crash` 工具立即退出进程
+//!
+//! 每个请求在独立线程中处理 (可并发)。动态新增的工具写入 `MOCK_MCP_STATE` 指向的文件，
+//! 进程重启后仍然可见。
+
+use serde_json::{Value, json};
+use std::io::{BufRead, Write};
+use std::sync::{Arc, Mutex};
+
+const STATE_ENV: &str = "MOCK_MCP_STATE";
+
+fn main() -> anyhow::Result<()> {
+    let stdin = std::io::stdin();
+    let stdout = Arc::new(Mutex::new(std::io::stdout()));
+    for line in stdin.lock().lines() {
+        let line = line?;
+        let Ok(msg) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        // 通知与客户端对服务器请求的应答无需处理
+        let (Some(id), Some(method)) = (
+            msg.get("id").cloned(),
+            msg.get("method")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        ) else {
+            continue;
+        };
+        let params = msg.get("params").cloned().unwrap_or(Value::Null);
+        let stdout = stdout.clone();
+        std::thread::spawn(move || {
+            let mut out = Vec::new();
+            let reply = handle(&method, &params, &mut out);
+            out.push(match reply {
+                Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                Err((code, message)) => json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": code, "message": message }
+                }),
+            });
+            let mut stdout = stdout.lock().unwrap_or_else(|e| e.into_inner());
+            for msg in out {
+                let _ = writeln!(stdout, "{}", msg);
+            }
+            let _ = stdout.flush();
+        });
+    }
+    Ok(())
+}
+
+/// 处理请求；需在响应前发送的通知写入 `out`
+fn handle(method: &str, params: &Value, out: &mut Vec<Value>) -> Result<Value, (i64, String)> {
+    match method {
+        "initialize" => Ok(json!({
+            "protocolVersion": "2025-06-18",
+            "serverInfo": { "name": "mock-mcp", "version": "0.1.0" },
+            "capabilities": {
+                "tools": { "listChanged": true },
+                "resources": { "subscribe": true, "listChanged": true },
+                "prompts": { "listChanged": true }
+            }
+        })),
+        "tools/list" => {
+            let mut tools = vec![json!({
+                "name": "echo",
+                "description": "Echo the text argument",
+                "inputSchema": { "type": "object", "properties": { "text": { "type": "string" } } }
+            })];
+            tools.extend(
+                extra_tools()
+                    .into_iter()
+                    .map(|name| json!({ "name": name, "inputSchema": { "type": "object" } })),
+            );
+            Ok(json!({ "tools": tools }))
+        }
+        "tools/call" => {
+            let name = params.get("name").and_then(Value::as_str).unwrap_or("");
+            let args = params.get("arguments").cloned().unwrap_or(Value::Null);
+            match name {
+                "echo" => {
+                    let text = args.get("text").and_then(Value::as_str).unwrap_or("");
+                    Ok(text_result(text))
+                }
+                "add_tool" => {
+                    let tool = args
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .ok_or((-32602, "missing name".to_string()))?;
+                    add_extra_tool(tool).map_err(|e| (-32603, e.to_string()))?;
+                    out.push(notification("notifications/tools/list_changed", None));
+                    Ok(text_result(&format!("added {}", tool)))
+                }
+                "pid" => Ok(text_result(&std::process::id().to_string())),
+                "sleep" => {
+                    let ms = args.get("ms").and_then(Value::as_u64).unwrap_or(100);
+                    std::thread::sleep(std::time::Duration::from_millis(ms));
+                    Ok(text_result("slept"))
+                }
+                "crash" => std::process::exit(3),
+                other => Err((-32602, format!("Unknown tool: {}", other))),
+            }
+        }
+        "resources/list" => Ok(json!({
+            "resources": [{
+                "uri": "mock://readme",
+                "name": "README",
+                "description": "Mock readme",
+                "mimeType": "text/plain"
+            }]
+        })),
+        "resources/read" => {
+            let uri = params.get("uri").and_then(Value::as_str).unwrap_or("");
+            if uri != "mock://readme" {
+                return Err((-32002, format!("Resource not found: {}", uri)));
+            }
+            Ok(json!({
+                "contents": [{ "uri": uri, "mimeType": "text/plain", "text": "Mock readme text" }]
+            }))
+        }
+        "resources/subscribe" => {
+            let uri = params.get("uri").cloned().unwrap_or(Value::Null);
+            out.push(notification(
+                "notifications/resources/updated",
+                Some(json!({ "uri": uri })),
+            ));
+            Ok(json!({}))
+        }
+        "resources/unsubscribe" => Ok(json!({})),
+        "prompts/list" => Ok(json!({
+            "prompts": [{
+                "name": "review",
+                "description": "Review a topic",
+                "arguments": [{ "name": "topic", "required": true }]
+            }]
+        })),
+        "prompts/get" => {
+            let topic = params
+                .pointer("/arguments/topic")
+                .and_then(Value::as_str)
+                .ok_or((-32602, "missing argument: topic".to_string()))?;
+            Ok(json!({
+                "description": "Review a topic",
+                "messages": [{
+                    "role": "user",
+                    "content": { "type": "text", "text": format!("Please review {}", topic) }
+                }]
+            }))
+        }
+        "ping" => Ok(json!({})),
+        other => Err((-32601, format!("Method not found: {}", other))),
+    }
+}
+
+fn text_result(text: &str) -> Value {
+    json!({ "content": [{ "type": "text", "text": text }], "isError": false })
+}
+
+fn notification(method: &str, params: Option<Value>) -> Value {
+    match params {
+        Some(params) => json!({ "jsonrpc": "2.0", "method": method, "params": params }),
+        None => json!({ "jsonrpc": "2.0", "method": method }),
+    }
+}
+
+fn extra_tools() -> Vec<String> {
+    std::env::var(STATE_ENV)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|s| s.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn add_extra_tool(name: &str) -> anyhow::Result<()> {
+    let path = std::env::var(STATE_ENV)?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", name)?;
+    Ok(())
+}
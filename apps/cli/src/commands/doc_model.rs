@@ -0,0 +1,43 @@
+// apps/cli/src/commands/doc_model.rs
+//! # 文档模型命令
+//!
+//! 查看或切换仓库的文档模型 (`text` / `crdt`)。切换为 `crdt` 时立即迁移已有文档：
+//! 为每个非空文档写入承载当前文本的初始 CRDT 插入，之后的编辑均以 CRDT 操作记录。
+//! 迁移应在一台设备上完成后再同步到其他设备。需在服务未运行时执行。
+
+use anyhow::{Context, Result};
+use deve_core::config::DocModel;
+use deve_core::ledger::RepoManager;
+use std::path::Path;
+
+pub fn run(
+    ledger_dir: &Path,
+    vault_path: &Path,
+    model: Option<String>,
+    snapshot_depth: usize,
+) -> Result<()> {
+    let repo = RepoManager::init(ledger_dir, snapshot_depth, None, None)
+        .context("Failed to open ledger (is `deve serve` running?)")?;
+    let Some(model) = model else {
+        println!("Document model: {:?}", repo.doc_model());
+        return Ok(());
+    };
+    let model: DocModel = model
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Unknown document model: {} (expected text|crdt)", model))?;
+
+    repo.set_doc_model(model)?;
+    if model == DocModel::Crdt {
+        let deve_dir = vault_path.join(".deve");
+        std::fs::create_dir_all(&deve_dir)?;
+        let identity = crate::server::security::load_or_generate_identity_key(&deve_dir)?;
+        let migrated = repo.migrate_to_crdt(&identity.peer_id())?;
+        println!(
+            "Document model set to crdt ({} documents migrated)",
+            migrated
+        );
+    } else {
+        println!("Document model set to text (migrated documents stay crdt)");
+    }
+    Ok(())
+}
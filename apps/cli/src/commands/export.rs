@@ -3,18 +3,18 @@ use anyhow::Result;
 use deve_core::ledger::RepoManager;
 use deve_core::ledger::listing::RepoListing;
 use deve_core::models::{DocId, LedgerEntry, RepoType};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 
 /// 导出条目结构
-/// 用于序列化为 JSON 格式。
-#[derive(Serialize)]
-struct ExportEntry {
-    doc_id: DocId,
-    path: String,
-    ops: Vec<LedgerEntry>,
+/// 用于序列化为 JSON 格式 (`import` 命令反序列化同一结构)。
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ExportEntry {
+    pub doc_id: DocId,
+    pub path: String,
+    pub ops: Vec<LedgerEntry>,
 }
 
 /// 导出命令
@@ -0,0 +1,196 @@
+// apps/cli/src/commands/import/apply.rs
+//! # 执行导入计划
+
+use anyhow::Result;
+use deve_core::ledger::RepoManager;
+use deve_core::models::{LedgerEntry, Op, PeerId};
+use deve_core::state::reconstruct_content;
+use deve_core::vfs::Vfs;
+use std::path::Path;
+
+use super::plan::{ImportPlan, Payload};
+
+pub struct ImportReport {
+    pub docs: usize,
+    pub ops: usize,
+}
+
+/// 按计划写入 Ledger 并落盘
+///
+/// # 前置条件
+/// - `plan` 由同一 `repo` 生成，期间无其他写入者
+///
+/// # 后置条件
+/// - JSONL 操作原样保留 `peer_id` / `seq` / `timestamp`
+/// - Markdown 文档以 `local_peer` 身份生成一个全文插入操作
+/// - 每个文档在 Vault 中有对应文件 (避免下次扫描视为幽灵条目)
+pub fn apply(
+    repo: &RepoManager,
+    vault: &Path,
+    local_peer: &PeerId,
+    plan: &ImportPlan,
+) -> Result<ImportReport> {
+    let vfs = Vfs::new(vault);
+    let mut report = ImportReport { docs: 0, ops: 0 };
+
+    for doc in &plan.docs {
+        repo.register_docid(&doc.target_path, doc.doc_id)?;
+
+        let content = match &doc.payload {
+            Payload::Ops(ops) => {
+                for entry in ops {
+                    repo.append_local_op(entry)?;
+                }
+                report.ops += ops.len();
+                reconstruct_content(ops)
+            }
+            Payload::Markdown {
+                content,
+                modified_ms,
+            } => {
+                if !content.is_empty() {
+                    repo.append_generated_op(doc.doc_id, local_peer.clone(), |seq| LedgerEntry {
+                        doc_id: doc.doc_id,
+                        op: Op::Insert {
+                            pos: 0,
+                            content: content.as_str().into(),
+                        },
+                        timestamp: *modified_ms,
+                        peer_id: local_peer.clone(),
+                        seq,
+                    })?;
+                    report.ops += 1;
+                }
+                content.clone()
+            }
+        };
+
+        let file_path = vault.join(&doc.target_path);
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&file_path, content)?;
+        if let Ok(Some(inode)) = vfs.get_inode(&doc.target_path) {
+            let _ = repo.bind_inode(&inode, doc.doc_id);
+        }
+        report.docs += 1;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::plan::{plan_jsonl, plan_markdown};
+    use super::*;
+    use crate::commands::export::ExportEntry;
+    use deve_core::models::DocId;
+
+    fn entry(doc_id: DocId, text: &str, peer: &str, seq: u64) -> LedgerEntry {
+        LedgerEntry {
+            doc_id,
+            op: Op::Insert {
+                pos: 0,
+                content: text.into(),
+            },
+            timestamp: 1_600_000_000_000 + seq as i64,
+            peer_id: PeerId::new(peer),
+            seq,
+        }
+    }
+
+    #[test]
+    fn test_jsonl_replay_remaps_colliding_doc_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path().join("vault");
+        std::fs::create_dir_all(&vault).unwrap();
+        let repo = RepoManager::init(dir.path().join("ledger"), 100, None, None).unwrap();
+
+        // 本地已有文档占用了导出中的 DocId
+        let existing = repo.create_docid("notes/a.md").unwrap();
+
+        let export = [
+            ExportEntry {
+                doc_id: existing,
+                path: "notes/a.md".into(),
+                ops: vec![
+                    entry(existing, "world", "peer-x", 1),
+                    entry(existing, "hello ", "peer-x", 2),
+                ],
+            },
+            ExportEntry {
+                doc_id: DocId::new(),
+                path: "../escape.md".into(),
+                ops: vec![],
+            },
+        ];
+        let jsonl: String = export
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap() + "\n")
+            .collect();
+        let source = dir.path().join("export.jsonl");
+        std::fs::write(&source, jsonl).unwrap();
+
+        let plan = plan_jsonl(&repo, &vault, &source).unwrap();
+        assert_eq!(plan.docs.len(), 1);
+        assert_eq!(plan.skipped.len(), 1);
+        let doc = &plan.docs[0];
+        assert!(doc.remapped);
+        assert_ne!(doc.doc_id, existing);
+        assert_eq!(doc.target_path, "notes/a (imported).md");
+
+        let report = apply(&repo, &vault, &PeerId::new("local"), &plan).unwrap();
+        assert_eq!((report.docs, report.ops), (1, 2));
+
+        let ops = repo.get_local_ops(doc.doc_id).unwrap();
+        assert_eq!(ops.len(), 2);
+        assert_eq!(ops[1].1.peer_id.as_str(), "peer-x");
+        assert_eq!(ops[1].1.seq, 2);
+        assert_eq!(ops[1].1.timestamp, 1_600_000_000_002);
+        assert_eq!(
+            std::fs::read_to_string(vault.join("notes/a (imported).md")).unwrap(),
+            "hello world"
+        );
+    }
+
+    #[test]
+    fn test_markdown_ingest_skips_hidden_and_non_markdown() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path().join("vault");
+        let source = dir.path().join("obsidian");
+        std::fs::create_dir_all(vault.join("inbox")).unwrap();
+        std::fs::create_dir_all(source.join(".obsidian")).unwrap();
+        std::fs::create_dir_all(source.join("daily")).unwrap();
+        std::fs::write(source.join(".obsidian/app.md"), "config").unwrap();
+        std::fs::write(source.join("daily/today.md"), "# Today\r\n").unwrap();
+        std::fs::write(source.join("image.png"), [0u8; 4]).unwrap();
+        std::fs::write(vault.join("inbox/readme.md"), "mine").unwrap();
+        std::fs::write(source.join("readme.md"), "theirs").unwrap();
+        let repo = RepoManager::init(dir.path().join("ledger"), 100, None, None).unwrap();
+
+        let plan = plan_markdown(&repo, &vault, &source, "inbox/").unwrap();
+        let targets: Vec<&str> = plan.docs.iter().map(|d| d.target_path.as_str()).collect();
+        assert_eq!(
+            targets,
+            ["inbox/daily/today.md", "inbox/readme (imported).md"]
+        );
+        assert_eq!(plan.skipped.len(), 1);
+
+        apply(&repo, &vault, &PeerId::new("local"), &plan).unwrap();
+        let id = repo.get_docid("inbox/daily/today.md").unwrap().unwrap();
+        assert_eq!(
+            reconstruct_content(
+                &repo
+                    .get_local_ops(id)
+                    .unwrap()
+                    .into_iter()
+                    .map(|(_, e)| e)
+                    .collect::<Vec<_>>()
+            ),
+            "# Today\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(vault.join("inbox/readme.md")).unwrap(),
+            "mine"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+// apps/cli/src/commands/import/mod.rs
+//! # 导入命令 (Import)
+//!
+//! `export` 的逆操作，并支持摄取外部 Markdown 目录 (如 Obsidian Vault)。
+//!
+//! ## 来源
+//! - `*.jsonl`: `deve export` 的输出，逐条回放操作，保留 `peer_id` / `seq` / `timestamp`
+//! - 目录: 递归收集 `.md` 文件，为每个文件创建文档与节点，首个操作时间取文件修改时间
+//!
+//! ## 流程
+//! 1. `plan`: 只读分析，解决路径与 DocId 冲突
+//! 2. 打印摘要 (dry-run 到此结束)
+//! 3. 确认后 `apply`: 写入 Ledger 并落盘到 Vault
+//!
+//! ## Invariants
+//! - 已存在的文档与文件永不覆盖：路径冲突时改名为 `name (imported).md` / `name (imported 2).md`
+//! - DocId 冲突时分配新 DocId，并改写该文档全部操作的 `doc_id`
+
+mod apply;
+mod plan;
+
+use anyhow::Result;
+use deve_core::ledger::RepoManager;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+pub use plan::{ImportPlan, PlannedDoc};
+
+/// 导入命令入口
+///
+/// * `prefix`: Markdown 导入的目标文件夹 (相对 Vault 根目录)
+/// * `dry_run`: 仅打印摘要
+/// * `yes`: 跳过确认提示
+pub fn run(
+    ledger_dir: &PathBuf,
+    vault_path: &Path,
+    source: PathBuf,
+    prefix: Option<String>,
+    dry_run: bool,
+    yes: bool,
+    snapshot_depth: usize,
+) -> Result<()> {
+    let repo = RepoManager::init(ledger_dir, snapshot_depth, None, None)?;
+
+    let plan = if source.is_dir() {
+        plan::plan_markdown(&repo, vault_path, &source, prefix.as_deref().unwrap_or(""))?
+    } else {
+        plan::plan_jsonl(&repo, vault_path, &source)?
+    };
+
+    print_summary(&plan);
+    if dry_run || plan.docs.is_empty() {
+        return Ok(());
+    }
+    if !yes && !confirm("Proceed with import?")? {
+        println!("Import cancelled.");
+        return Ok(());
+    }
+
+    let deve_dir = vault_path.join(".deve");
+    std::fs::create_dir_all(&deve_dir)?;
+    let identity = crate::server::security::load_or_generate_identity_key(&deve_dir)?;
+
+    let report = apply::apply(&repo, vault_path, &identity.peer_id(), &plan)?;
+    println!("Imported {} documents ({} ops).", report.docs, report.ops);
+    Ok(())
+}
+
+fn print_summary(plan: &ImportPlan) {
+    let ops: usize = plan.docs.iter().map(PlannedDoc::op_count).sum();
+    let renamed = plan
+        .docs
+        .iter()
+        .filter(|d| d.renamed_from.is_some())
+        .count();
+    let remapped = plan.docs.iter().filter(|d| d.remapped).count();
+
+    println!("Import summary ({}):", plan.source);
+    println!("  documents to import: {}", plan.docs.len());
+    println!("  operations:          {}", ops);
+    println!("  renamed (path taken):   {}", renamed);
+    println!("  new DocId (id taken):   {}", remapped);
+    println!("  skipped:                {}", plan.skipped.len());
+
+    for doc in &plan.docs {
+        if let Some(original) = &doc.renamed_from {
+            println!("    {} -> {}", original, doc.target_path);
+        }
+    }
+    for (path, reason) in &plan.skipped {
+        println!("    skip {}: {}", path, reason);
+    }
+}
+
+pub(crate) fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} [y/N] ", prompt);
+    std::io::stdout().flush()?;
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line)?;
+    Ok(matches!(line.trim(), "y" | "Y" | "yes"))
+}
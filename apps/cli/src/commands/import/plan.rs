@@ -0,0 +1,228 @@
+// apps/cli/src/commands/import/plan.rs
+//! # 导入计划 (只读分析)
+
+use anyhow::{Context, Result};
+use deve_core::ledger::RepoManager;
+use deve_core::models::{DocId, LedgerEntry};
+use deve_core::utils::path::{path_to_forward_slash, to_forward_slash};
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::path::Path;
+
+use crate::commands::export::ExportEntry;
+
+/// 单个待导入文档的内容来源
+pub enum Payload {
+    /// 回放导出的操作 (doc_id 已改写为目标 DocId)
+    Ops(Vec<LedgerEntry>),
+    /// Markdown 全文，`modified_ms` 为源文件修改时间
+    Markdown { content: String, modified_ms: i64 },
+}
+
+pub struct PlannedDoc {
+    pub doc_id: DocId,
+    /// Vault 内的目标路径 (正斜杠)
+    pub target_path: String,
+    /// 因路径冲突而改名时的原路径
+    pub renamed_from: Option<String>,
+    /// DocId 已被占用，分配了新 DocId
+    pub remapped: bool,
+    pub payload: Payload,
+}
+
+impl PlannedDoc {
+    pub fn op_count(&self) -> usize {
+        match &self.payload {
+            Payload::Ops(ops) => ops.len(),
+            Payload::Markdown { content, .. } => usize::from(!content.is_empty()),
+        }
+    }
+}
+
+pub struct ImportPlan {
+    pub source: String,
+    pub docs: Vec<PlannedDoc>,
+    /// (路径, 原因)
+    pub skipped: Vec<(String, String)>,
+}
+
+/// 分析导出 JSONL
+pub fn plan_jsonl(repo: &RepoManager, vault: &Path, source: &Path) -> Result<ImportPlan> {
+    let file = std::fs::File::open(source).with_context(|| format!("Cannot open {:?}", source))?;
+    let mut claims = Claims::default();
+    let mut plan = ImportPlan {
+        source: source.display().to_string(),
+        docs: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    for (line_no, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: ExportEntry = serde_json::from_str(&line)
+            .with_context(|| format!("Invalid export entry at line {}", line_no + 1))?;
+        let path = to_forward_slash(&entry.path);
+        if !is_safe_relative(&path) {
+            plan.skipped.push((path, "path escapes the vault".into()));
+            continue;
+        }
+
+        // 同一文档重复导入: DocId 与路径都一致且本地操作不少于导出
+        if repo.get_docid(&path)? == Some(entry.doc_id)
+            && repo.get_local_ops(entry.doc_id)?.len() >= entry.ops.len()
+        {
+            plan.skipped.push((path, "already imported".into()));
+            continue;
+        }
+
+        let remapped = !claims.claim_id(repo, entry.doc_id)?;
+        let doc_id = if remapped {
+            claims.fresh_id(repo)?
+        } else {
+            entry.doc_id
+        };
+        let target_path = claims.claim_path(repo, vault, &path)?;
+
+        let ops = entry
+            .ops
+            .into_iter()
+            .map(|op| LedgerEntry { doc_id, ..op })
+            .collect();
+        plan.docs.push(PlannedDoc {
+            doc_id,
+            renamed_from: (target_path != path).then_some(path),
+            target_path,
+            remapped,
+            payload: Payload::Ops(ops),
+        });
+    }
+    Ok(plan)
+}
+
+/// 分析外部 Markdown 目录 (跳过隐藏目录，如 `.obsidian` / `.git`)
+pub fn plan_markdown(
+    repo: &RepoManager,
+    vault: &Path,
+    source: &Path,
+    prefix: &str,
+) -> Result<ImportPlan> {
+    let prefix = to_forward_slash(prefix).trim_matches('/').to_string();
+    let mut claims = Claims::default();
+    let mut plan = ImportPlan {
+        source: source.display().to_string(),
+        docs: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    let walker = walkdir::WalkDir::new(source)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'));
+    for entry in walker {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = path_to_forward_slash(entry.path().strip_prefix(source)?);
+        if entry.path().extension().is_none_or(|ext| ext != "md") {
+            plan.skipped.push((rel, "not a Markdown file".into()));
+            continue;
+        }
+        let content = match std::fs::read_to_string(entry.path()) {
+            Ok(c) => c,
+            Err(e) => {
+                plan.skipped.push((rel, format!("unreadable: {}", e)));
+                continue;
+            }
+        };
+        let modified_ms = entry
+            .metadata()
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .map(|t| chrono::DateTime::<chrono::Utc>::from(t).timestamp_millis())
+            .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+
+        let path = if prefix.is_empty() {
+            rel
+        } else {
+            format!("{}/{}", prefix, rel)
+        };
+        let target_path = claims.claim_path(repo, vault, &path)?;
+        plan.docs.push(PlannedDoc {
+            doc_id: claims.fresh_id(repo)?,
+            renamed_from: (target_path != path).then_some(path),
+            target_path,
+            remapped: false,
+            payload: Payload::Markdown {
+                content: content.replace("\r\n", "\n"),
+                modified_ms,
+            },
+        });
+    }
+    Ok(plan)
+}
+
+/// 拒绝绝对路径与 `..` 段，避免写出 Vault
+fn is_safe_relative(path: &str) -> bool {
+    !path.is_empty()
+        && !path.starts_with('/')
+        && !path.contains(':')
+        && path
+            .split('/')
+            .all(|seg| !seg.is_empty() && seg != "." && seg != "..")
+}
+
+/// 本批次已占用的路径与 DocId (与 Ledger / 磁盘一起判定冲突)
+#[derive(Default)]
+struct Claims {
+    paths: HashSet<String>,
+    ids: HashSet<DocId>,
+}
+
+impl Claims {
+    /// 占用 DocId，已被占用返回 false
+    fn claim_id(&mut self, repo: &RepoManager, id: DocId) -> Result<bool> {
+        if self.ids.contains(&id) || repo.get_path_by_docid(id)?.is_some() {
+            return Ok(false);
+        }
+        self.ids.insert(id);
+        Ok(true)
+    }
+
+    fn fresh_id(&mut self, repo: &RepoManager) -> Result<DocId> {
+        loop {
+            let id = DocId::new();
+            if self.claim_id(repo, id)? {
+                return Ok(id);
+            }
+        }
+    }
+
+    /// 占用路径，冲突时追加 ` (imported N)` 后缀
+    fn claim_path(&mut self, repo: &RepoManager, vault: &Path, path: &str) -> Result<String> {
+        let (stem, ext) = match path.rsplit_once('.') {
+            Some((stem, ext)) if !stem.is_empty() && !ext.contains('/') => {
+                (stem.to_string(), format!(".{}", ext))
+            }
+            _ => (path.to_string(), String::new()),
+        };
+        let mut candidate = path.to_string();
+        let mut n = 1;
+        while self.paths.contains(&candidate)
+            || repo.get_docid(&candidate)?.is_some()
+            || vault.join(&candidate).exists()
+        {
+            let suffix = if n == 1 {
+                " (imported)".to_string()
+            } else {
+                format!(" (imported {})", n)
+            };
+            candidate = format!("{}{}{}", stem, suffix, ext);
+            n += 1;
+        }
+        self.paths.insert(candidate.clone());
+        Ok(candidate)
+    }
+}
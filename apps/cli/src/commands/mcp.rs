@@ -0,0 +1,95 @@
+// apps/cli/src/commands/mcp.rs
+//! # MCP stdio 服务命令
+//!
+//! 供以子进程方式接入的 AI Agent 使用: 逐行读取 stdin 上的 JSON-RPC 消息，
+//! 转发到运行中服务的 `POST /mcp`，响应逐行写回 stdout。
+//! 所有工具与资源均由服务端执行，因此认证与权限规则与 HTTP 接入完全一致。
+//!
+//! ## Invariants
+//! - stdout 只输出 JSON-RPC 消息 (日志写 stderr，见 `main`)
+//! - 转发失败时对带 id 的请求回写 JSON-RPC 错误，Agent 不会无限等待
+
+use crate::server::source_control_proxy::loopback_client;
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+/// 访问令牌环境变量 (登录后 `token` Cookie 中的 JWT)
+const TOKEN_ENV: &str = "DEVE_MCP_TOKEN";
+
+/// 服务端不可达或拒绝请求 (实现自定义错误码)
+const RELAY_ERROR: i64 = -32000;
+
+pub async fn run(url: String, token: Option<String>) -> anyhow::Result<()> {
+    let endpoint = format!("{}/mcp", url.trim_end_matches('/'));
+    let token = token.or_else(|| std::env::var(TOKEN_ENV).ok());
+    let client = loopback_client(&url);
+    tracing::info!("MCP stdio relay -> {}", endpoint);
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = relay(&client, &endpoint, token.as_deref(), &line).await {
+            let mut out = serde_json::to_string(&response)?;
+            out.push('\n');
+            stdout.write_all(out.as_bytes()).await?;
+            stdout.flush().await?;
+        }
+    }
+    Ok(())
+}
+
+/// 转发单条消息，返回需写回的响应 (通知返回 None)
+async fn relay(
+    client: &reqwest::Client,
+    endpoint: &str,
+    token: Option<&str>,
+    line: &str,
+) -> Option<Value> {
+    let mut request = client
+        .post(endpoint)
+        .header("content-type", "application/json")
+        .header("accept", "application/json")
+        .body(line.to_string());
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+
+    let failure = match request.send().await {
+        Ok(resp) if resp.status() == reqwest::StatusCode::ACCEPTED => return None,
+        Ok(resp) if resp.status().is_success() => match resp.json::<Value>().await {
+            Ok(value) => return Some(value),
+            Err(e) => format!("Invalid response from server: {}", e),
+        },
+        Ok(resp) if resp.status() == reqwest::StatusCode::UNAUTHORIZED => {
+            format!("Unauthorized: set {} to a valid login token", TOKEN_ENV)
+        }
+        Ok(resp) => format!("Server returned {}", resp.status()),
+        Err(e) => format!("Server unreachable ({}): {}", endpoint, e),
+    };
+    tracing::warn!("MCP relay failed: {}", failure);
+    error_for(line, &failure)
+}
+
+/// 为原始消息中带 id 的请求构造错误响应
+fn error_for(line: &str, message: &str) -> Option<Value> {
+    let error = |id: &Value| {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": RELAY_ERROR, "message": message },
+        })
+    };
+    match serde_json::from_str::<Value>(line).ok()? {
+        Value::Array(batch) => {
+            let errors: Vec<Value> = batch
+                .iter()
+                .filter_map(|m| m.get("id").map(error))
+                .collect();
+            (!errors.is_empty()).then_some(Value::Array(errors))
+        }
+        msg => msg.get("id").map(error),
+    }
+}
@@ -2,10 +2,15 @@
 //! CLI 子命令模块
 //!
 //! 包含所有 CLI 支持的子命令实现。
+pub mod doc_model;
 pub mod dump;
 pub mod export;
+pub mod import;
 pub mod init;
+pub mod mcp;
 pub mod node_check;
+pub mod plugin;
+pub mod reindex;
 pub mod scan;
 pub mod seed;
 pub mod serve;
@@ -0,0 +1,171 @@
+// apps/cli/src/commands/plugin.rs
+//! # 插件管理命令 (Plugin)
+//!
+//! - `install <archive>`: 校验签名 (`.deve/trusted-publishers.json`)，展示请求的能力并确认后安装
+//! - `uninstall` / `enable` / `disable <id>`: 修改插件目录
+//! - `list`: 列出插件、启用状态与发布者
+//! - `pack <dir> --key <file>`: 以发布者密钥签名打包 (密钥不存在时生成)
+//!
+//! 运行中的 `deve serve` 监听插件目录，上述修改无需重启即生效。
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use deve_core::plugin::install::PluginInstaller;
+use deve_core::plugin::manifest::Capability;
+use deve_core::plugin::package::{PluginPackage, TrustedPublishers};
+use deve_core::security::IdentityKeyPair;
+use std::path::{Path, PathBuf};
+
+#[derive(Subcommand, Debug)]
+pub enum PluginCommand {
+    /// Install a signed plugin package (.tar.gz)
+    Install {
+        archive: PathBuf,
+        /// Approve the requested capabilities without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Remove an installed plugin
+    Uninstall { id: String },
+    /// List plugins in the plugin directory
+    List,
+    /// Enable a disabled plugin
+    Enable { id: String },
+    /// Disable a plugin without removing it
+    Disable { id: String },
+    /// Sign and pack a plugin directory into a package
+    Pack {
+        dir: PathBuf,
+        /// Publisher key file (32-byte Ed25519 seed, generated if missing)
+        #[arg(long)]
+        key: PathBuf,
+        /// Output archive (default: `<id>-<version>.tar.gz`)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+pub fn run(vault_path: &Path, command: PluginCommand) -> Result<()> {
+    let installer = PluginInstaller::new(super::serve::plugin_dir());
+    match command {
+        PluginCommand::Install { archive, yes } => install(vault_path, &installer, &archive, yes)?,
+        PluginCommand::Uninstall { id } => {
+            installer.uninstall(&id)?;
+            println!("Uninstalled {}", id);
+        }
+        PluginCommand::List => list(&installer)?,
+        PluginCommand::Enable { id } => {
+            installer.set_enabled(&id, true)?;
+            println!("Enabled {}", id);
+        }
+        PluginCommand::Disable { id } => {
+            installer.set_enabled(&id, false)?;
+            println!("Disabled {}", id);
+        }
+        PluginCommand::Pack { dir, key, output } => pack(&dir, &key, output)?,
+    }
+    Ok(())
+}
+
+fn install(
+    vault_path: &Path,
+    installer: &PluginInstaller,
+    archive: &Path,
+    yes: bool,
+) -> Result<()> {
+    let package = PluginPackage::read(archive)?;
+    let trusted = TrustedPublishers::load(&vault_path.join(".deve"))?;
+    let publisher = package.verify(&trusted)?;
+    let manifest = &package.manifest;
+
+    println!(
+        "{} {} ({}) signed by {}",
+        manifest.name, manifest.version, manifest.id, publisher.name
+    );
+    print_capabilities(&manifest.capabilities);
+    if !yes && !super::import::confirm("Grant these capabilities and install?")? {
+        println!("Install cancelled.");
+        return Ok(());
+    }
+
+    installer.install(&package, &publisher)?;
+    println!("Installed {}", manifest.id);
+    Ok(())
+}
+
+fn print_capabilities(caps: &Capability) {
+    let mut lines = Vec::new();
+    for domain in &caps.allow_net {
+        lines.push(format!("network: {}", domain));
+    }
+    for path in &caps.allow_fs_read {
+        lines.push(format!("read files: {}", path.display()));
+    }
+    for path in &caps.allow_fs_write {
+        lines.push(format!("write files: {}", path.display()));
+    }
+    for key in &caps.allow_env {
+        lines.push(format!("environment variable: {}", key));
+    }
+    if caps.allow_source_control {
+        lines.push("repository and version control access".to_string());
+    }
+    if caps.allow_doc_edit {
+        lines.push("read and edit notes".to_string());
+    }
+    if lines.is_empty() {
+        println!("Requested capabilities: none");
+    } else {
+        println!("Requested capabilities:");
+        for line in lines {
+            println!("  - {}", line);
+        }
+    }
+}
+
+fn list(installer: &PluginInstaller) -> Result<()> {
+    let plugins = installer.list()?;
+    if plugins.is_empty() {
+        println!("No plugins installed.");
+    }
+    for p in plugins {
+        println!(
+            "{:<24} {:<10} {:<9} {}",
+            p.manifest.id,
+            p.manifest.version,
+            if p.enabled { "enabled" } else { "disabled" },
+            p.record
+                .map(|r| r.publisher)
+                .unwrap_or_else(|| "(local)".into())
+        );
+    }
+    Ok(())
+}
+
+fn pack(dir: &Path, key_path: &Path, output: Option<PathBuf>) -> Result<()> {
+    let key = if key_path.exists() {
+        let bytes = std::fs::read(key_path)?;
+        IdentityKeyPair::from_bytes(&bytes)
+            .with_context(|| format!("Invalid key file {:?}", key_path))?
+    } else {
+        let key = IdentityKeyPair::generate();
+        crate::server::security::write_key_file(key_path, &key.to_bytes())?;
+        println!("Generated publisher key {:?}", key_path);
+        key
+    };
+
+    let mut package = PluginPackage::from_dir(dir)?;
+    package.sign(&key);
+    let output = output.unwrap_or_else(|| {
+        PathBuf::from(format!(
+            "{}-{}.tar.gz",
+            package.manifest.id, package.manifest.version
+        ))
+    });
+    package.write(&output)?;
+    println!("Packed {:?}", output);
+    if let Some(signature) = &package.signature {
+        println!("Publisher public key: {}", signature.publisher);
+    }
+    Ok(())
+}
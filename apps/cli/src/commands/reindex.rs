@@ -0,0 +1,40 @@
+// apps/cli/src/commands/reindex.rs
+//! # 搜索索引重建命令
+//!
+//! 从 Ledger 全量重建 `.deve_search_index` (单次提交)，并同步历史提交与影子库版本。
+//! 需在服务未运行时执行：服务运行期间 Ledger 与索引写锁均被占用，
+//! 且服务启动时本身会自动重建一次。
+
+use std::path::Path;
+
+#[cfg(feature = "search")]
+pub fn run(ledger_dir: &Path, vault_path: &Path, snapshot_depth: usize) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use deve_core::ledger::RepoManager;
+    use deve_core::search::{SearchConfig, SearchService, archive, indexer};
+
+    let repo = RepoManager::init(ledger_dir, snapshot_depth, None, None)
+        .context("Failed to open ledger (is `deve serve` running?)")?;
+    let index_path = vault_path.join(".deve_search_index");
+    let config = SearchConfig::load(&vault_path.join(".deve"));
+    let service = SearchService::new_on_disk(&index_path, config.tokenizer)
+        .context("Failed to open search index (is `deve serve` running?)")?;
+
+    let started = std::time::Instant::now();
+    let count = indexer::rebuild_index(&repo, &service)?;
+    let archived = archive::sync_archive(&repo, &service)?;
+    println!(
+        "Reindexed {} documents ({} history/peer updates) into {:?} ({:?} tokenizer) in {:.2?}",
+        count,
+        archived,
+        index_path,
+        config.tokenizer,
+        started.elapsed()
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "search"))]
+pub fn run(_ledger_dir: &Path, _vault_path: &Path, _snapshot_depth: usize) -> anyhow::Result<()> {
+    anyhow::bail!("Search feature not enabled (rebuild with `--features search`)")
+}
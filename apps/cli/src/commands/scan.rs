@@ -6,7 +6,7 @@ use std::path::PathBuf;
 /// 扫描命令
 ///
 /// **功能**:
-/// 遍历 `vault` 目录，将所有 Markdown 文件注册到 `ledger` 中。
+/// 遍历 `vault` 目录，将所有 Markdown 文件注册到 `ledger` 中，非 Markdown 文件存为附件。
 /// 使用 `Vfs` 模块执行扫描操作。
 pub fn run(
     ledger_dir: &PathBuf,
@@ -18,6 +18,9 @@ pub fn run(
     let vfs = Vfs::new(vault_path);
     println!("Scanning vault at {:?}...", vault_path);
     let count = vfs.scan(&repo)?;
-    println!("Scanned. Registered {} new documents.", count);
+    println!(
+        "Scanned. Registered {} new documents or changed attachments.",
+        count
+    );
     Ok(())
 }
@@ -1,9 +1,9 @@
 // apps\cli\src\commands
 use crate::server;
+use deve_core::config::AppProfile;
 use deve_core::ledger::RepoManager;
-use deve_core::plugin::loader::PluginLoader;
+use deve_core::plugin::registry::PluginRegistry;
 use deve_core::plugin::runtime::host;
-use reqwest::Client;
 use std::net::TcpListener;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -12,15 +12,17 @@ use tokio::time::{Duration, timeout};
 /// 启动后端服务器
 ///
 /// **功能**:
-/// 1. 初始化 `RepoManager` (Store B/C Access)
+/// 1. 初始化 `RepoManager` (Store B/C Access)，按运行模式设定文档状态缓存预算
 /// 2. 启动 `SyncManager` 进行初始扫描
 /// 3. 加载本地插件
-/// 4. 启动 WebSocket 服务监听端口
+/// 4. 启动 WebSocket 服务监听端口 (可选内置 TLS)
 pub async fn run(
     ledger_dir: &PathBuf,
     vault_path: PathBuf,
     port: u16,
     snapshot_depth: usize,
+    profile: AppProfile,
+    tls: server::tls::TlsOptions,
 ) -> anyhow::Result<()> {
     let bind_addr = format!("0.0.0.0:{}", port);
     if TcpListener::bind(&bind_addr).is_err() {
@@ -38,6 +40,7 @@ pub async fn run(
             return Err(e);
         }
     };
+    repo.set_profile(profile);
     let repo_arc = Arc::new(repo);
 
     // 启动时通过 SyncManager 自动扫描
@@ -50,18 +53,17 @@ pub async fn run(
     // 2. 加载插件 (Plugins)
     let plugins = load_plugins();
 
-    server::start_server(repo_arc, vault_path, port, plugins).await?;
+    server::start_server(repo_arc, vault_path, port, plugins, tls).await?;
     Ok(())
 }
 
 /// 代理模式: 检测已运行的主进程并以 plugin-host 方式启动
 async fn start_proxy_mode(port: u16) -> anyhow::Result<()> {
-    let main_port = detect_main_port(port).await;
+    let (main_port, base_url) = detect_main_port(port).await;
     tracing::info!(
         "Main process detected on port {}. Switching to client proxy mode...",
         main_port
     );
-    let base_url = format!("http://127.0.0.1:{}", main_port);
     let remote =
         Arc::new(crate::server::source_control_proxy::RemoteSourceControlApi::new(base_url));
     let repo_api: Arc<dyn deve_core::ledger::traits::Repository> = remote;
@@ -79,23 +81,25 @@ async fn start_proxy_mode(port: u16) -> anyhow::Result<()> {
     server::start_plugin_host_only(plugins, plugin_port).await
 }
 
-/// 加载 `plugins/` 目录下的所有 Rhai 插件
-fn load_plugins() -> Vec<Box<dyn deve_core::plugin::runtime::PluginRuntime>> {
-    let plugin_dir = PathBuf::from("plugins");
-    let loader = PluginLoader::new(plugin_dir);
-    match loader.load_all() {
-        Ok(p) => {
-            tracing::info!("Loaded {} plugins.", p.len());
-            p
-        }
-        Err(e) => {
-            tracing::warn!("Failed to load plugins: {}", e);
-            vec![]
-        }
+/// 插件目录 (`deve plugin` 子命令与服务共用)
+pub fn plugin_dir() -> PathBuf {
+    PathBuf::from("plugins")
+}
+
+/// 加载插件目录下的所有插件，并监听目录变化热重载
+fn load_plugins() -> Arc<PluginRegistry> {
+    let registry = Arc::new(PluginRegistry::load(plugin_dir()));
+    tracing::info!("Loaded {} plugins.", registry.snapshot().len());
+    if let Err(e) = registry.watch() {
+        tracing::warn!("Plugin hot reload disabled: {}", e);
     }
+    registry
 }
 
-async fn detect_main_port(port: u16) -> u16 {
+/// 探测主进程端口，返回 (端口, base URL)
+///
+/// 主进程可能启用了内置 TLS，因此依次尝试 `http` 与 `https`。
+async fn detect_main_port(port: u16) -> (u16, String) {
     let mut ports = vec![port];
     for p in port.saturating_sub(2)..=port + 4 {
         if !ports.contains(&p) {
@@ -103,19 +107,21 @@ async fn detect_main_port(port: u16) -> u16 {
         }
     }
 
-    let client = Client::new();
     for p in ports {
-        let url = format!("http://127.0.0.1:{}/api/repo/docs", p);
-        let req = client.get(&url);
-        let is_ok = matches!(
-            timeout(Duration::from_millis(300), req.send()).await,
-            Ok(Ok(resp)) if resp.status().is_success()
-        );
-        if is_ok {
-            return p;
+        for scheme in ["http", "https"] {
+            let base_url = format!("{}://127.0.0.1:{}", scheme, p);
+            let client = crate::server::source_control_proxy::loopback_client(&base_url);
+            let req = client.get(format!("{}/api/repo/docs", base_url));
+            let is_ok = matches!(
+                timeout(Duration::from_millis(300), req.send()).await,
+                Ok(Ok(resp)) if resp.status().is_success()
+            );
+            if is_ok {
+                return (p, base_url);
+            }
         }
     }
-    port
+    (port, format!("http://127.0.0.1:{}", port))
 }
 
 fn find_free_port(start: u16, span: u16) -> Option<u16> {
@@ -12,6 +12,11 @@
 //! - `watch`: 监控文件系统变更 (Watcher Service)
 //! - `dump`: 调试工具，用于检查 ops 记录
 //! - `serve`: 启动 WebSocket 后端服务器 (Backend Architecture)
+//! - `export` / `import`: Ledger JSONL 导出与导入 (含外部 Markdown 目录摄取)
+//! - `reindex`: 从 Ledger 全量重建搜索索引 (需 `search` 特性)
+//! - `doc-model`: 查看或切换文档模型 (切换为 `crdt` 时迁移已有文档)
+//! - `plugin`: 插件包的安装、卸载、启停与签名打包
+//! - `mcp`: MCP stdio 服务 (中继到运行中服务的 `/mcp`)
 
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
@@ -46,12 +51,35 @@ enum Commands {
     Serve {
         #[arg(short, long, default_value_t = 3001)]
         port: u16,
+        /// PEM certificate chain for HTTPS/WSS
+        #[arg(long, requires = "tls_key")]
+        tls_cert: Option<PathBuf>,
+        /// PEM private key for HTTPS/WSS
+        #[arg(long, requires = "tls_cert")]
+        tls_key: Option<PathBuf>,
+        /// Serve HTTPS/WSS with a generated self-signed certificate
+        #[arg(long, conflicts_with = "tls_cert")]
+        tls_self_signed: bool,
     },
     /// Export ledger to JSONL
     Export {
         #[arg(short, long)]
         output: Option<String>,
     },
+    /// Import an export JSONL or a folder of Markdown files
+    Import {
+        /// `.jsonl` file produced by `export`, or a Markdown directory
+        source: PathBuf,
+        /// Target folder inside the vault (Markdown import only)
+        #[arg(long)]
+        prefix: Option<String>,
+        /// Only print the summary
+        #[arg(long)]
+        dry_run: bool,
+        /// Apply without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
     /// Verify P2P Sync Logic (Simulation)
     VerifyP2P,
     /// Seed a shadow repo with local data
@@ -64,14 +92,41 @@ enum Commands {
         #[arg(long)]
         repair: bool,
     },
+    /// Rebuild the full-text search index from the ledger
+    Reindex,
+    /// Show or set the document model (text | crdt)
+    DocModel {
+        /// New model; switching to `crdt` migrates existing documents
+        model: Option<String>,
+    },
+    /// Install, remove, enable or disable plugins
+    Plugin {
+        #[command(subcommand)]
+        action: commands::plugin::PluginCommand,
+    },
+    /// Serve MCP over stdio by relaying to a running `deve serve`
+    Mcp {
+        /// Base URL of the running server
+        #[arg(long, default_value = "http://127.0.0.1:3001")]
+        url: String,
+        /// Login token (JWT); defaults to $DEVE_MCP_TOKEN
+        #[arg(long)]
+        token: Option<String>,
+    },
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
 
-    // Initialize logging
-    tracing_subscriber::fmt::init();
+    // Initialize logging (MCP stdio 模式下 stdout 只承载协议消息)
+    if matches!(args.command, Some(Commands::Mcp { .. })) {
+        tracing_subscriber::fmt()
+            .with_writer(std::io::stderr)
+            .init();
+    } else {
+        tracing_subscriber::fmt::init();
+    }
 
     // Initialize configuration from Env
     let config = deve_core::config::Config::load();
@@ -95,12 +150,44 @@ async fn main() -> anyhow::Result<()> {
         Some(Commands::Dump { path }) => {
             commands::dump::run(&ledger_dir, path, config.snapshot_depth)?
         }
-        Some(Commands::Serve { port }) => {
-            commands::serve::run(&ledger_dir, vault_path, port, config.snapshot_depth).await?
+        Some(Commands::Serve {
+            port,
+            tls_cert,
+            tls_key,
+            tls_self_signed,
+        }) => {
+            let tls = server::tls::TlsOptions {
+                cert: tls_cert,
+                key: tls_key,
+                self_signed: tls_self_signed,
+            };
+            commands::serve::run(
+                &ledger_dir,
+                vault_path,
+                port,
+                config.snapshot_depth,
+                config.profile,
+                tls,
+            )
+            .await?
         }
         Some(Commands::Export { output }) => {
             commands::export::run(&ledger_dir, output, config.snapshot_depth)?
         }
+        Some(Commands::Import {
+            source,
+            prefix,
+            dry_run,
+            yes,
+        }) => commands::import::run(
+            &ledger_dir,
+            &vault_path,
+            source,
+            prefix,
+            dry_run,
+            yes,
+            config.snapshot_depth,
+        )?,
         Some(Commands::VerifyP2P) => commands::verify_p2p::run(config.snapshot_depth)?,
         Some(Commands::Seed { peer }) => {
             commands::seed::run(&ledger_dir, peer, config.snapshot_depth)?
@@ -108,6 +195,14 @@ async fn main() -> anyhow::Result<()> {
         Some(Commands::NodeCheck { repair }) => {
             commands::node_check::run(&ledger_dir, config.snapshot_depth, repair)?
         }
+        Some(Commands::Reindex) => {
+            commands::reindex::run(&ledger_dir, &vault_path, config.snapshot_depth)?
+        }
+        Some(Commands::DocModel { model }) => {
+            commands::doc_model::run(&ledger_dir, &vault_path, model, config.snapshot_depth)?
+        }
+        Some(Commands::Plugin { action }) => commands::plugin::run(&vault_path, action)?,
+        Some(Commands::Mcp { url, token }) => commands::mcp::run(url, token).await?,
         None => tracing::info!("请提供子命令，使用 --help 查看帮助。"),
     }
 
@@ -0,0 +1,169 @@
+// apps/cli/src/server/agent/mod.rs
+//! # Server Agent (服务端智能体)
+//!
+//! 以 Rust 实现的聊天智能体循环，取代 ai-chat 插件中的 Rhai 工具循环。
+//!
+//! ## 架构
+//! ```text
+//! ClientMessage::ChatSend ──► handlers::agent ──► AgentService::begin
+//!                                                   │
+//!                                                   ▼
+//!                               run_agent: 流式补全 ⇄ 并行工具 (builtin / MCP)
+//!                                   │                     │
+//!                                   ▼                     ▼
+//!                     ChatChunk / ChatToolCallDelta   ConversationStore (redb)
+//! ```
+//!
+//! **模块结构**:
+//! - `tools`: 工具目录与并行执行
+//! - `run`: 单次运行的循环
+//!
+//! ## Invariants
+//! - 同一会话同一时刻至多一个运行 (`begin` 拒绝并发写入同一会话)
+//! - `RunGuard` 析构时注销运行，`ChatCancel` 之后的取消请求为空操作
+//! - 只有发起运行的 WebSocket 会话可以取消该运行
+
+pub mod run;
+pub mod tools;
+
+use anyhow::{Result, anyhow, bail};
+use deve_core::agent::ConversationStore;
+use deve_core::plugin::runtime::cancel::CancelToken;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+struct ActiveRun {
+    conversation_id: String,
+    /// 发起运行的会话 (`WsSession::session_id`)
+    session_id: String,
+    cancel: CancelToken,
+}
+
+/// 会话存储与运行注册表
+pub struct AgentService {
+    store: Option<Arc<ConversationStore>>,
+    runs: Mutex<HashMap<String, ActiveRun>>,
+}
+
+impl AgentService {
+    pub fn new(store: Option<Arc<ConversationStore>>) -> Self {
+        Self {
+            store,
+            runs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 打开会话存储 (失败时智能体不可用，其余功能照常)
+    pub fn open(path: &Path) -> Self {
+        match ConversationStore::open(path) {
+            Ok(store) => Self::new(Some(Arc::new(store))),
+            Err(e) => {
+                tracing::warn!("Conversation storage unavailable: {}", e);
+                Self::new(None)
+            }
+        }
+    }
+
+    pub fn store(&self) -> Result<Arc<ConversationStore>> {
+        self.store
+            .clone()
+            .ok_or_else(|| anyhow!("Conversation storage unavailable"))
+    }
+
+    /// 登记一次运行，返回其取消令牌与注销守卫
+    pub fn begin(
+        self: &Arc<Self>,
+        req_id: &str,
+        conversation_id: &str,
+        session_id: &str,
+    ) -> Result<RunGuard> {
+        let mut runs = self.runs.lock().unwrap_or_else(|e| e.into_inner());
+        if runs.contains_key(req_id) {
+            bail!("Request {} is already running", req_id);
+        }
+        if runs.values().any(|r| r.conversation_id == conversation_id) {
+            bail!("Conversation is busy");
+        }
+        let cancel = CancelToken::new();
+        runs.insert(
+            req_id.to_string(),
+            ActiveRun {
+                conversation_id: conversation_id.to_string(),
+                session_id: session_id.to_string(),
+                cancel: cancel.clone(),
+            },
+        );
+        Ok(RunGuard {
+            service: self.clone(),
+            req_id: req_id.to_string(),
+            cancel,
+        })
+    }
+
+    /// 取消该会话发起的运行，返回运行是否存在
+    pub fn cancel(&self, req_id: &str, session_id: &str) -> bool {
+        let runs = self.runs.lock().unwrap_or_else(|e| e.into_inner());
+        match runs.get(req_id).filter(|run| run.session_id == session_id) {
+            Some(run) => {
+                run.cancel.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 会话是否有进行中的运行
+    pub fn is_running(&self, conversation_id: &str) -> bool {
+        let runs = self.runs.lock().unwrap_or_else(|e| e.into_inner());
+        runs.values().any(|r| r.conversation_id == conversation_id)
+    }
+}
+
+/// RAII guard: 析构时注销运行
+pub struct RunGuard {
+    service: Arc<AgentService>,
+    req_id: String,
+    cancel: CancelToken,
+}
+
+impl RunGuard {
+    pub fn cancel_token(&self) -> &CancelToken {
+        &self.cancel
+    }
+}
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        let mut runs = self.service.runs.lock().unwrap_or_else(|e| e.into_inner());
+        runs.remove(&self.req_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runs_are_exclusive_per_conversation() {
+        let service = Arc::new(AgentService::new(None));
+        assert!(service.store().is_err());
+
+        let guard = service.begin("r1", "c1", "s1").unwrap();
+        assert!(service.begin("r1", "c2", "s1").is_err());
+        assert!(service.begin("r2", "c1", "s1").is_err());
+        assert!(service.is_running("c1"));
+
+        // 其他会话不能取消
+        assert!(!service.cancel("r1", "s2"));
+        assert!(!guard.cancel_token().is_cancelled());
+
+        assert!(service.cancel("r1", "s1"));
+        assert!(guard.cancel_token().is_cancelled());
+        drop(guard);
+
+        assert!(!service.cancel("r1", "s1"));
+        assert!(!service.is_running("c1"));
+        assert!(service.begin("r2", "c1", "s1").is_ok());
+    }
+}
@@ -0,0 +1,621 @@
+// apps/cli/src/server/agent/run.rs
+//! # Agent Loop (智能体循环)
+//!
+//! 一次运行: 追加用户消息 → 流式请求模型 → 并行执行工具 → 回填结果 → 再请求，
+//! 直到模型不再调用工具、达到 `max_tool_rounds`、被取消或出错。
+//!
+//! ## Invariants
+//! - 每条消息生成后立即写入会话存储，中途取消或出错时已完成的部分仍可恢复
+//! - 会话存储 (redb) 的同步读写在阻塞线程池中执行，不占用异步工作线程
+//! - 持久化的 `assistant` 工具调用总有对应的 `tool` 结果 (取消时记为错误)，
+//!   回放历史时缺失的结果以 `interrupted` 补齐，保证请求合法
+
+use super::tools::AgentTools;
+use crate::server::ai_chat::config::ChatConfig;
+use crate::server::ai_chat::stream::stream_completion;
+use crate::server::ai_chat::types::ParsedSseEvent;
+use deve_core::agent::ConversationStore;
+use deve_core::plugin::runtime::cancel::CancelToken;
+use deve_core::protocol::{AgentMessage, AgentToolCall, ServerMessage};
+use serde_json::{Value, json};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// 默认系统提示词
+const SYSTEM_PROMPT: &str = "You are a helpful AI assistant for Deve-Notebook. \
+You can read files, check git status, view diffs, stage and commit changes, \
+and call tools provided by connected MCP servers (named mcp__<server>__<tool>). \
+Independent tool calls may be issued together; they run in parallel. \
+Be concise and precise.";
+
+/// 运行结束原因 (`ChatDone.reason`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunOutcome {
+    Stop,
+    MaxRounds,
+    Cancelled,
+    Error(String),
+}
+
+impl RunOutcome {
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::Stop => "stop",
+            Self::MaxRounds => "max_rounds",
+            Self::Cancelled => "cancelled",
+            Self::Error(_) => "error",
+        }
+    }
+
+    pub fn error(&self) -> Option<String> {
+        match self {
+            Self::Error(e) => Some(e.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// 一次运行的输入
+pub struct AgentRun {
+    pub req_id: String,
+    pub conversation_id: String,
+    pub user_message: AgentMessage,
+    pub current_file: Option<String>,
+}
+
+/// 执行智能体循环，增量事件经 `emit` 推送
+pub async fn run_agent(
+    config: &ChatConfig,
+    store: &Arc<ConversationStore>,
+    tools: &AgentTools,
+    run: AgentRun,
+    cancel: &CancelToken,
+    emit: &(dyn Fn(ServerMessage) + Send + Sync),
+) -> RunOutcome {
+    match run_rounds(config, store, tools, &run, cancel, emit).await {
+        Ok(outcome) => outcome,
+        Err(e) => RunOutcome::Error(e.to_string()),
+    }
+}
+
+async fn run_rounds(
+    config: &ChatConfig,
+    store: &Arc<ConversationStore>,
+    tools: &AgentTools,
+    run: &AgentRun,
+    cancel: &CancelToken,
+    emit: &(dyn Fn(ServerMessage) + Send + Sync),
+) -> anyhow::Result<RunOutcome> {
+    config.validate().map_err(anyhow::Error::msg)?;
+    let (reader, id) = (store.clone(), run.conversation_id.clone());
+    let mut history = tokio::task::spawn_blocking(move || reader.get(&id))
+        .await??
+        .map(|c| c.messages)
+        .unwrap_or_default();
+    append(store, &run.conversation_id, vec![run.user_message.clone()]).await?;
+    history.push(run.user_message.clone());
+
+    let mut system = SYSTEM_PROMPT.to_string();
+    if let Some(file) = run.current_file.as_deref().filter(|f| !f.is_empty()) {
+        system.push_str(&format!("\n\nThe user is currently editing: {}", file));
+    }
+
+    for round in 1..=config.max_tool_rounds {
+        let mut body = json!({
+            "model": config.model,
+            "messages": openai_messages(&system, &history),
+            "stream": true,
+            "max_tokens": config.max_tokens,
+        });
+        if !tools.definitions().is_empty() {
+            body["tools"] = Value::Array(tools.definitions().to_vec());
+        }
+
+        let mut partial = String::new();
+        let completion = stream_completion(
+            &config.endpoint(),
+            &config.api_key,
+            &config.headers,
+            body,
+            Some(cancel),
+            |event| match event {
+                ParsedSseEvent::ContentDelta(text) => {
+                    partial.push_str(text);
+                    emit(ServerMessage::ChatChunk {
+                        req_id: run.req_id.clone(),
+                        delta: Some(text.clone()),
+                        finish_reason: None,
+                    });
+                }
+                ParsedSseEvent::ToolCallDelta {
+                    index,
+                    id,
+                    name,
+                    arguments,
+                } => emit(ServerMessage::ChatToolCallDelta {
+                    req_id: run.req_id.clone(),
+                    round,
+                    index: *index as u32,
+                    id: id.clone(),
+                    name: name.clone(),
+                    arguments: arguments.clone(),
+                }),
+                _ => {}
+            },
+        )
+        .await?;
+
+        let Some(completion) = completion else {
+            // 取消时保留已生成的文本
+            if !partial.is_empty() {
+                append(
+                    store,
+                    &run.conversation_id,
+                    vec![AgentMessage::assistant(partial, Vec::new(), now_ms())],
+                )
+                .await?;
+            }
+            return Ok(RunOutcome::Cancelled);
+        };
+
+        let calls: Vec<AgentToolCall> = completion
+            .tool_calls
+            .into_iter()
+            .map(|c| AgentToolCall {
+                id: c.id,
+                name: c.name,
+                arguments: c.arguments,
+            })
+            .collect();
+        let assistant = AgentMessage::assistant(completion.content, calls.clone(), now_ms());
+        append(store, &run.conversation_id, vec![assistant.clone()]).await?;
+        history.push(assistant);
+
+        if calls.is_empty() {
+            return Ok(RunOutcome::Stop);
+        }
+
+        tracing::info!(
+            "Agent: req_id={} round {} executing {} tool call(s)",
+            run.req_id,
+            round,
+            calls.len()
+        );
+        let outputs = tools.execute_all(&calls, cancel).await;
+        let results: Vec<AgentMessage> = calls
+            .iter()
+            .zip(outputs)
+            .map(|(call, output)| {
+                emit(ServerMessage::ChatToolResult {
+                    req_id: run.req_id.clone(),
+                    call_id: call.id.clone(),
+                    name: call.name.clone(),
+                    content: output.content.clone(),
+                    is_error: output.is_error,
+                });
+                AgentMessage::tool(call.id.clone(), output.content, output.is_error, now_ms())
+            })
+            .collect();
+        append(store, &run.conversation_id, results.clone()).await?;
+        history.extend(results);
+
+        if cancel.is_cancelled() {
+            return Ok(RunOutcome::Cancelled);
+        }
+    }
+
+    Ok(RunOutcome::MaxRounds)
+}
+
+/// 在阻塞线程池中追加消息
+async fn append(
+    store: &Arc<ConversationStore>,
+    conversation_id: &str,
+    messages: Vec<AgentMessage>,
+) -> anyhow::Result<()> {
+    let (store, id) = (store.clone(), conversation_id.to_string());
+    tokio::task::spawn_blocking(move || store.append(&id, &messages, now_ms())).await??;
+    Ok(())
+}
+
+/// 会话历史转为 OpenAI `messages`
+pub fn openai_messages(system: &str, history: &[AgentMessage]) -> Vec<Value> {
+    let answered: HashSet<&str> = history
+        .iter()
+        .filter_map(|m| m.tool_call_id.as_deref())
+        .collect();
+    let mut out = vec![json!({ "role": "system", "content": system })];
+    for message in history {
+        match message.role.as_str() {
+            "assistant" if !message.tool_calls.is_empty() => {
+                let calls: Vec<Value> = message
+                    .tool_calls
+                    .iter()
+                    .map(|c| {
+                        json!({
+                            "id": c.id,
+                            "type": "function",
+                            "function": { "name": c.name, "arguments": c.arguments },
+                        })
+                    })
+                    .collect();
+                let content = if message.content.is_empty() {
+                    Value::Null
+                } else {
+                    Value::String(message.content.clone())
+                };
+                out.push(json!({ "role": "assistant", "content": content, "tool_calls": calls }));
+                for call in &message.tool_calls {
+                    if !answered.contains(call.id.as_str()) {
+                        out.push(json!({
+                            "role": "tool",
+                            "tool_call_id": call.id,
+                            "content": "Error: interrupted",
+                        }));
+                    }
+                }
+            }
+            "tool" => out.push(json!({
+                "role": "tool",
+                "tool_call_id": message.tool_call_id.clone().unwrap_or_default(),
+                "content": message.content,
+            })),
+            role => out.push(json!({ "role": role, "content": message.content })),
+        }
+    }
+    out
+}
+
+pub(crate) fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::agent::tools::AgentTools;
+    use axum::Router;
+    use axum::body::Body;
+    use axum::extract::State;
+    use axum::response::Response;
+    use axum::routing::post;
+    use deve_core::mcp::{McpExecutor, McpManager, McpToolSpec};
+    use deve_core::plugin::manifest::PluginManifest;
+    use deve_core::plugin::runtime::{PluginRuntime, RhaiRuntime};
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// 模拟 MCP 服务器: `echo` 工具回显 `text`
+    struct EchoExecutor;
+
+    impl McpExecutor for EchoExecutor {
+        fn request(&self, method: &str, params: Option<Value>) -> anyhow::Result<Value> {
+            match method {
+                "tools/call" => {
+                    let text = params.unwrap_or_default()["arguments"]["text"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string();
+                    Ok(json!({ "content": [{ "type": "text", "text": format!("echo {}", text) }] }))
+                }
+                other => anyhow::bail!("unexpected {}", other),
+            }
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    enum Mode {
+        /// 首轮并行调用两个工具，收到结果后回复文本
+        Tools,
+        /// 每轮都调用工具
+        Loop,
+        /// 缓慢输出文本 (用于取消)
+        Slow,
+    }
+
+    #[derive(Clone)]
+    struct Mock {
+        mode: Mode,
+        requests: Arc<Mutex<Vec<Value>>>,
+    }
+
+    fn chunk(delta: Value, finish: Option<&str>) -> String {
+        let payload = json!({ "choices": [{ "delta": delta, "finish_reason": finish }] });
+        format!("data: {}\n\n", payload)
+    }
+
+    fn tool_call_chunks(calls: &[(&str, &str, &str)]) -> String {
+        let mut body = String::new();
+        for (index, (id, name, args)) in calls.iter().enumerate() {
+            body += &chunk(
+                json!({ "tool_calls": [{ "index": index, "id": id,
+                    "function": { "name": name, "arguments": "" } }] }),
+                None,
+            );
+            // 参数分两段流出
+            let (a, b) = args.split_at(args.len() / 2);
+            for part in [a, b] {
+                body += &chunk(
+                    json!({ "tool_calls": [{ "index": index, "function": { "arguments": part } }] }),
+                    None,
+                );
+            }
+        }
+        body.push_str(&chunk(json!({}), Some("tool_calls")));
+        body + "data: [DONE]\n\n"
+    }
+
+    async fn completions(State(mock): State<Mock>, body: String) -> Response {
+        let request: Value = serde_json::from_str(&body).unwrap();
+        let answered = request["messages"]
+            .as_array()
+            .unwrap()
+            .last()
+            .is_some_and(|m| m["role"] == "tool");
+        mock.requests.lock().unwrap().push(request);
+
+        let body = match mock.mode {
+            Mode::Tools if answered => Body::from(
+                [
+                    chunk(json!({ "content": "do" }), None),
+                    chunk(json!({ "content": "ne" }), None),
+                    chunk(json!({}), Some("stop")),
+                    "data: [DONE]\n\n".to_string(),
+                ]
+                .concat(),
+            ),
+            Mode::Tools => Body::from(tool_call_chunks(&[
+                ("call_1", "read_file", r#"{"path":"notes/a.md"}"#),
+                ("call_2", "mcp__stub__echo", r#"{"text":"hi"}"#),
+            ])),
+            Mode::Loop => Body::from(tool_call_chunks(&[(
+                "call_x",
+                "mcp__stub__echo",
+                r#"{"text":"again"}"#,
+            )])),
+            Mode::Slow => Body::from_stream(futures::stream::unfold(0, |n| async move {
+                if n >= 50 {
+                    return None;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                let data = chunk(json!({ "content": "x" }), None);
+                Some((Ok::<_, std::io::Error>(data), n + 1))
+            })),
+        };
+        Response::builder()
+            .header("content-type", "text/event-stream")
+            .body(body)
+            .unwrap()
+    }
+
+    /// 内置工具插件替身: 回显工具名与参数
+    fn builtin_plugin() -> Arc<dyn PluginRuntime> {
+        let manifest = PluginManifest {
+            id: "ai-chat".into(),
+            name: "AI Chat".into(),
+            version: "0.1".into(),
+            entry: "main.rhai".into(),
+            engine: Default::default(),
+            events: Vec::new(),
+            limits: Default::default(),
+            ui: Default::default(),
+            capabilities: Default::default(),
+        };
+        let mut rt = RhaiRuntime::new(manifest.clone(), PathBuf::from("."));
+        rt.load(
+            manifest,
+            r#"fn run_tool(name, arguments) { "ran " + name + " " + arguments }"#,
+        )
+        .unwrap();
+        Arc::new(rt)
+    }
+
+    struct Harness {
+        config: ChatConfig,
+        store: Arc<ConversationStore>,
+        tools: AgentTools,
+        requests: Arc<Mutex<Vec<Value>>>,
+        events: Arc<Mutex<Vec<ServerMessage>>>,
+        conversation_id: String,
+        _dir: tempfile::TempDir,
+    }
+
+    async fn harness(mode: Mode, max_tool_rounds: u32) -> Harness {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let app = Router::new()
+            .route("/chat/completions", post(completions))
+            .with_state(Mock {
+                mode,
+                requests: requests.clone(),
+            });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let mut mcp = McpManager::new();
+        mcp.register_executor("stub", Arc::new(EchoExecutor));
+        mcp.set_tools(
+            "stub",
+            vec![McpToolSpec {
+                name: "echo".into(),
+                description: Some("Echo text".into()),
+                input_schema: json!({ "type": "object", "properties": { "text": { "type": "string" } } }),
+            }],
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let store =
+            Arc::new(ConversationStore::open(&dir.path().join("conversations.redb")).unwrap());
+        let conversation_id = store.create("test", now_ms()).unwrap().id;
+        Harness {
+            config: ChatConfig {
+                base_url: format!("http://{}", addr),
+                api_key: "test-key".into(),
+                model: "mock".into(),
+                max_tokens: 128,
+                headers: HashMap::new(),
+                max_tool_rounds,
+            },
+            store,
+            tools: AgentTools::new(Some(builtin_plugin()), Some(Arc::new(mcp))),
+            requests,
+            events: Arc::new(Mutex::new(Vec::new())),
+            conversation_id,
+            _dir: dir,
+        }
+    }
+
+    impl Harness {
+        async fn run(&self, message: &str, cancel: &CancelToken) -> RunOutcome {
+            let events = self.events.clone();
+            let emit = move |msg: ServerMessage| events.lock().unwrap().push(msg);
+            let run = AgentRun {
+                req_id: "r1".into(),
+                conversation_id: self.conversation_id.clone(),
+                user_message: AgentMessage::user(message.into(), None, now_ms()),
+                current_file: Some("notes/a.md".into()),
+            };
+            run_agent(&self.config, &self.store, &self.tools, run, cancel, &emit).await
+        }
+
+        fn messages(&self) -> Vec<AgentMessage> {
+            self.store
+                .get(&self.conversation_id)
+                .unwrap()
+                .unwrap()
+                .messages
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_tool_round_then_text_reply() {
+        let h = harness(Mode::Tools, 4).await;
+        let outcome = h.run("inspect", &CancelToken::new()).await;
+        assert_eq!(outcome, RunOutcome::Stop);
+
+        // 第二次请求携带两个工具结果，工具定义包含内置与 MCP 工具
+        let requests = h.requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 2);
+        let names: Vec<&str> = requests[0]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|t| t["function"]["name"].as_str())
+            .collect();
+        assert!(names.contains(&"read_file") && names.contains(&"mcp__stub__echo"));
+        let system = requests[0]["messages"][0]["content"].as_str().unwrap();
+        assert!(system.contains("notes/a.md"));
+        let tool_messages: Vec<&Value> = requests[1]["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|m| m["role"] == "tool")
+            .collect();
+        assert_eq!(tool_messages.len(), 2);
+
+        let messages = h.messages();
+        let roles: Vec<&str> = messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(
+            roles,
+            vec!["user", "assistant", "tool", "tool", "assistant"]
+        );
+        assert_eq!(messages[1].tool_calls.len(), 2);
+        assert_eq!(
+            messages[2].content,
+            r#"ran read_file {"path":"notes/a.md"}"#
+        );
+        assert!(!messages[2].is_error);
+        assert_eq!(messages[3].content, "echo hi");
+        assert_eq!(messages[3].tool_call_id.as_deref(), Some("call_2"));
+        assert_eq!(messages[4].content, "done");
+
+        let events = h.events.lock().unwrap();
+        let deltas = events
+            .iter()
+            .filter(|e| matches!(e, ServerMessage::ChatToolCallDelta { round: 1, .. }))
+            .count();
+        assert_eq!(deltas, 6);
+        let results = events
+            .iter()
+            .filter(|e| matches!(e, ServerMessage::ChatToolResult { .. }))
+            .count();
+        assert_eq!(results, 2);
+        let text: String = events
+            .iter()
+            .filter_map(|e| match e {
+                ServerMessage::ChatChunk { delta, .. } => delta.clone(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, "done");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_round_limit_stops_loop() {
+        let h = harness(Mode::Loop, 2).await;
+        let outcome = h.run("loop", &CancelToken::new()).await;
+        assert_eq!(outcome, RunOutcome::MaxRounds);
+        assert_eq!(h.requests.lock().unwrap().len(), 2);
+        // 每轮的工具结果都已持久化，历史可直接续聊
+        let roles: Vec<String> = h.messages().into_iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            vec!["user", "assistant", "tool", "assistant", "tool"]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cancel_keeps_partial_reply() {
+        let h = harness(Mode::Slow, 4).await;
+        let cancel = CancelToken::new();
+        let trigger = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(400)).await;
+            trigger.cancel();
+        });
+        let started = std::time::Instant::now();
+        let outcome = h.run("slow", &cancel).await;
+        assert_eq!(outcome, RunOutcome::Cancelled);
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let messages = h.messages();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1].role, "assistant");
+        assert!(messages[1].content.starts_with('x'));
+    }
+
+    #[tokio::test]
+    async fn test_missing_api_key_is_error() {
+        let mut h = harness(Mode::Tools, 1).await;
+        h.config.api_key = String::new();
+        let outcome = h.run("hi", &CancelToken::new()).await;
+        assert!(matches!(outcome, RunOutcome::Error(e) if e.contains("API key")));
+        assert!(h.requests.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_openai_messages_fill_interrupted_results() {
+        let call = |id: &str| AgentToolCall {
+            id: id.into(),
+            name: "git_status".into(),
+            arguments: "{}".into(),
+        };
+        let history = vec![
+            AgentMessage::user("hi".into(), Some("shown".into()), 1),
+            AgentMessage::assistant(String::new(), vec![call("a"), call("b")], 2),
+            AgentMessage::tool("a".into(), "ok".into(), false, 3),
+        ];
+        let messages = openai_messages("sys", &history);
+        assert_eq!(messages.len(), 5);
+        assert_eq!(messages[1]["content"], "hi");
+        assert!(messages[2]["content"].is_null());
+        assert_eq!(messages[3]["tool_call_id"], "b");
+        assert_eq!(messages[3]["content"], "Error: interrupted");
+        assert_eq!(messages[4]["tool_call_id"], "a");
+    }
+}
@@ -0,0 +1,271 @@
+// apps/cli/src/server/agent/tools.rs
+//! # Agent Tools (智能体工具目录)
+//!
+//! 向模型暴露内置工具 (`builtin_tools()`，经 ai-chat 插件的 `run_tool` 在沙箱中执行)
+//! 与已连接 MCP 服务器的工具 (命名为 `mcp__{server}__{tool}`)。
+//!
+//! ## Invariants
+//! - 同一批工具调用并行执行，结果按调用顺序返回
+//! - 取消后不再等待未完成的调用，其结果记为 `Error: cancelled`
+//! - 工具输出超过 `MAX_OUTPUT_CHARS` 时截断
+
+use deve_core::mcp::McpManager;
+use deve_core::plugin::runtime::PluginRuntime;
+use deve_core::plugin::runtime::cancel::{CancelScope, CancelToken};
+use deve_core::plugin::runtime::tools::builtin_tools;
+use deve_core::protocol::AgentToolCall;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 单个工具输出的最大字符数
+pub const MAX_OUTPUT_CHARS: usize = 32_000;
+/// OpenAI 工具名的最大长度
+const MAX_TOOL_NAME: usize = 64;
+
+/// 工具执行结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolOutput {
+    pub content: String,
+    pub is_error: bool,
+}
+
+impl ToolOutput {
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            content: message.into(),
+            is_error: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ToolRoute {
+    Builtin,
+    Mcp { server: String, tool: String },
+}
+
+/// 单次运行可用的工具集合 (运行开始时快照)
+pub struct AgentTools {
+    builtin: Option<Arc<dyn PluginRuntime>>,
+    mcp: Option<Arc<McpManager>>,
+    definitions: Vec<Value>,
+    routes: HashMap<String, ToolRoute>,
+}
+
+impl AgentTools {
+    /// `builtin` 为 ai-chat 插件 (缺失时不提供内置工具)
+    pub fn new(builtin: Option<Arc<dyn PluginRuntime>>, mcp: Option<Arc<McpManager>>) -> Self {
+        let mut definitions = Vec::new();
+        let mut routes = HashMap::new();
+        if builtin.is_some() {
+            for tool in builtin_tools() {
+                routes.insert(tool.function.name.clone(), ToolRoute::Builtin);
+                definitions.push(serde_json::to_value(&tool).unwrap_or(Value::Null));
+            }
+        }
+        for entry in mcp.iter().flat_map(|m| m.list_all_tools()) {
+            let name = mcp_tool_name(&entry.server, &entry.tool.name);
+            if routes.contains_key(&name) {
+                tracing::warn!("Agent: duplicate tool name {}, skipped", name);
+                continue;
+            }
+            let parameters = if entry.tool.input_schema.is_object() {
+                entry.tool.input_schema.clone()
+            } else {
+                json!({ "type": "object", "properties": {} })
+            };
+            definitions.push(json!({
+                "type": "function",
+                "function": {
+                    "name": name,
+                    "description": entry.tool.description.clone().unwrap_or_default(),
+                    "parameters": parameters,
+                }
+            }));
+            routes.insert(
+                name,
+                ToolRoute::Mcp {
+                    server: entry.server,
+                    tool: entry.tool.name,
+                },
+            );
+        }
+        Self {
+            builtin,
+            mcp,
+            definitions,
+            routes,
+        }
+    }
+
+    /// OpenAI `tools` 参数
+    pub fn definitions(&self) -> &[Value] {
+        &self.definitions
+    }
+
+    /// 并行执行一批工具调用
+    pub async fn execute_all(
+        &self,
+        calls: &[AgentToolCall],
+        cancel: &CancelToken,
+    ) -> Vec<ToolOutput> {
+        let tasks: Vec<_> = calls
+            .iter()
+            .map(|call| {
+                let route = self.routes.get(&call.name).cloned();
+                let builtin = self.builtin.clone();
+                let mcp = self.mcp.clone();
+                let call = call.clone();
+                let cancel = cancel.clone();
+                tokio::task::spawn_blocking(move || match route {
+                    Some(ToolRoute::Builtin) => run_builtin(builtin, &call, cancel),
+                    Some(ToolRoute::Mcp { server, tool }) => run_mcp(mcp, &server, &tool, &call),
+                    None => ToolOutput::error(format!("Error: unknown tool '{}'", call.name)),
+                })
+            })
+            .collect();
+
+        let results = tokio::select! {
+            results = futures::future::join_all(tasks) => results,
+            _ = cancel.cancelled() => {
+                return calls.iter().map(|_| ToolOutput::error("Error: cancelled")).collect();
+            }
+        };
+        results
+            .into_iter()
+            .map(|r| match r {
+                Ok(output) => truncate(output),
+                Err(e) => ToolOutput::error(format!("Error: tool task failed: {}", e)),
+            })
+            .collect()
+    }
+}
+
+/// MCP 工具暴露给模型的名称 (仅保留 `[A-Za-z0-9_-]`)
+pub fn mcp_tool_name(server: &str, tool: &str) -> String {
+    let sanitize = |s: &str| -> String {
+        s.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    };
+    let mut name = format!("mcp__{}__{}", sanitize(server), sanitize(tool));
+    name.truncate(MAX_TOOL_NAME);
+    name
+}
+
+fn run_builtin(
+    plugin: Option<Arc<dyn PluginRuntime>>,
+    call: &AgentToolCall,
+    cancel: CancelToken,
+) -> ToolOutput {
+    let Some(plugin) = plugin else {
+        return ToolOutput::error("Error: builtin tools unavailable");
+    };
+    let _cancel = CancelScope::new(cancel);
+    match plugin.call(
+        "run_tool",
+        vec![call.name.clone().into(), call.arguments.clone().into()],
+    ) {
+        Ok(result) => {
+            let content = result
+                .into_string()
+                .unwrap_or_else(|t| format!("Error: tool returned {}", t));
+            ToolOutput {
+                is_error: content.starts_with("Error"),
+                content,
+            }
+        }
+        Err(e) => ToolOutput::error(format!("Error: {}", e)),
+    }
+}
+
+fn run_mcp(
+    mcp: Option<Arc<McpManager>>,
+    server: &str,
+    tool: &str,
+    call: &AgentToolCall,
+) -> ToolOutput {
+    let Some(mcp) = mcp else {
+        return ToolOutput::error("Error: MCP unavailable");
+    };
+    let args = if call.arguments.trim().is_empty() {
+        json!({})
+    } else {
+        match serde_json::from_str::<Value>(&call.arguments) {
+            Ok(args) => args,
+            Err(e) => return ToolOutput::error(format!("Error: invalid arguments: {}", e)),
+        }
+    };
+    match mcp.call_tool(server, tool, args) {
+        Ok(result) => call_result_output(&result.content),
+        Err(e) => ToolOutput::error(format!("Error: {}", e)),
+    }
+}
+
+/// `tools/call` 结果: 拼接文本内容，`isError` 标记失败
+fn call_result_output(result: &Value) -> ToolOutput {
+    let content = result["content"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|c| {
+            c["text"]
+                .as_str()
+                .or_else(|| c["resource"]["text"].as_str())
+                .map(str::to_string)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let content = if content.is_empty() {
+        result
+            .get("structuredContent")
+            .map(Value::to_string)
+            .unwrap_or_default()
+    } else {
+        content
+    };
+    ToolOutput {
+        content,
+        is_error: result["isError"].as_bool().unwrap_or(false),
+    }
+}
+
+fn truncate(mut output: ToolOutput) -> ToolOutput {
+    if let Some((idx, _)) = output.content.char_indices().nth(MAX_OUTPUT_CHARS) {
+        output.content.truncate(idx);
+        output.content.push_str("\n…[truncated]");
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mcp_tool_name_sanitized() {
+        assert_eq!(mcp_tool_name("notes", "search"), "mcp__notes__search");
+        assert_eq!(mcp_tool_name("my server", "a.b/c"), "mcp__my_server__a_b_c");
+        assert_eq!(mcp_tool_name("s", &"x".repeat(100)).len(), MAX_TOOL_NAME);
+    }
+
+    #[test]
+    fn test_call_result_output() {
+        let output = call_result_output(&json!({
+            "content": [{ "type": "text", "text": "a" }, { "type": "text", "text": "b" }],
+            "isError": true
+        }));
+        assert_eq!(output.content, "a\nb");
+        assert!(output.is_error);
+        let output = call_result_output(&json!({ "content": [], "structuredContent": { "n": 1 } }));
+        assert_eq!(output.content, r#"{"n":1}"#);
+        assert!(!output.is_error);
+    }
+}
@@ -18,9 +18,13 @@
 //! ## Invariants
 //! 1. 子进程生命周期严格受 `handle_agent_chat` 管控，函数退出即回收
 //! 2. 任何 spawn 失败不会 panic，只返回错误到前端
+//! 3. 上下文中的 MCP 提示词与附加资源在启动 CLI 前展开为纯文本查询
 
 use crate::server::channel::DualChannel;
+use deve_core::mcp::{McpManager, McpPromptResult};
+use deve_core::plugin::runtime::host;
 use deve_core::protocol::ServerMessage;
+use serde_json::Value;
 
 /// 默认 CLI 工具名 (可通过 `AGENT_CLI_PATH` 环境变量覆盖)
 const DEFAULT_CLI: &str = "opencode";
@@ -35,6 +39,27 @@ pub async fn handle_agent_chat(ch: &DualChannel, req_id: String, args: Vec<serde
         send_error(ch, &req_id, "No user message provided");
         return;
     }
+    let user_message = match (args.get(2).cloned(), host::mcp_manager()) {
+        (Some(context), Some(mcp)) => {
+            // 提示词与资源读取可能启动 stdio 服务器进程
+            let expanded = tokio::task::spawn_blocking(move || {
+                expand_mcp_context(&mcp, &user_message, &context)
+            })
+            .await;
+            match expanded {
+                Ok(Ok(query)) => query,
+                Ok(Err(e)) => {
+                    send_error(ch, &req_id, &format!("MCP context error: {}", e));
+                    return;
+                }
+                Err(e) => {
+                    send_error(ch, &req_id, &format!("MCP context error: {}", e));
+                    return;
+                }
+            }
+        }
+        _ => user_message,
+    };
 
     let cli_path = std::env::var("AGENT_CLI_PATH").unwrap_or_else(|_| DEFAULT_CLI.to_string());
 
@@ -82,6 +107,55 @@ fn extract_user_message(args: &[serde_json::Value]) -> String {
     String::new()
 }
 
+/// 展开聊天上下文: `prompt` 替换用户消息为提示词文本，`resources` 追加为资源块
+pub(crate) fn expand_mcp_context(
+    mcp: &McpManager,
+    message: &str,
+    context: &Value,
+) -> anyhow::Result<String> {
+    let mut query = match context.get("prompt") {
+        Some(prompt) => {
+            let server = prompt["server"].as_str().unwrap_or_default();
+            let name = prompt["name"].as_str().unwrap_or_default();
+            let args = prompt.get("arguments").cloned().unwrap_or(Value::Null);
+            prompt_text(&mcp.get_prompt(server, name, args)?)
+        }
+        None => message.to_string(),
+    };
+    for resource in context
+        .get("resources")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let server = resource["server"].as_str().unwrap_or_default();
+        let uri = resource["uri"].as_str().unwrap_or_default();
+        for content in mcp.read_resource(server, uri)? {
+            if let Some(text) = content.text {
+                query.push_str(&format!(
+                    "\n\n<resource uri=\"{}\">\n{}\n</resource>",
+                    content.uri, text
+                ));
+            }
+        }
+    }
+    Ok(query)
+}
+
+/// 提示词消息中的文本内容 (内嵌资源取其文本)，按消息顺序拼接
+fn prompt_text(result: &McpPromptResult) -> String {
+    result
+        .messages
+        .iter()
+        .filter_map(|m| {
+            m.content["text"]
+                .as_str()
+                .or_else(|| m.content["resource"]["text"].as_str())
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
 /// 启动外部 CLI 并将 stdout 流式推送到前端。
 async fn spawn_and_stream(
     cli_path: &str,
@@ -181,3 +255,28 @@ fn send_error(ch: &DualChannel, req_id: &str, message: &str) {
         error: Some(message.to_string()),
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_expand_mcp_context() {
+        let result: McpPromptResult = serde_json::from_value(json!({
+            "messages": [
+                { "role": "user", "content": { "type": "text", "text": "Review this" } },
+                { "role": "user", "content": { "type": "image", "data": "..." } },
+                { "role": "user", "content": { "type": "resource", "resource": { "uri": "x://a", "text": "body" } } }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(prompt_text(&result), "Review this\n\nbody");
+
+        let mcp = McpManager::new();
+        let plain = expand_mcp_context(&mcp, "hello", &json!({ "current_file": "a.md" }));
+        assert_eq!(plain.unwrap(), "hello");
+        let missing = json!({ "prompt": { "server": "none", "name": "p", "arguments": {} } });
+        assert!(expand_mcp_context(&mcp, "/p", &missing).is_err());
+    }
+}
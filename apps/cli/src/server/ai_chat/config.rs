@@ -7,7 +7,7 @@ use serde::Deserialize;
 use std::collections::HashMap;
 
 /// AI 聊天配置 (强类型)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ChatConfig {
     pub base_url: String,
     pub api_key: String,
@@ -16,13 +16,51 @@ pub struct ChatConfig {
     pub max_tokens: u32,
     #[serde(default)]
     pub headers: HashMap<String, String>,
+    /// 服务端智能体单次运行的最大模型轮数 (每轮可包含一批并行工具调用)
+    #[serde(default = "default_max_tool_rounds")]
+    pub max_tool_rounds: u32,
 }
 
 fn default_max_tokens() -> u32 {
     4096
 }
 
+fn default_max_tool_rounds() -> u32 {
+    8
+}
+
+/// `max_tool_rounds` 上限
+pub const MAX_TOOL_ROUNDS_LIMIT: u32 = 64;
+
+/// 读取非空环境变量
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
 impl ChatConfig {
+    /// 从环境变量读取配置
+    ///
+    /// `AI_BASE_URL` / `AI_API_KEY` (回退 `OPENAI_API_KEY`, `ANTHROPIC_API_KEY`) /
+    /// `AI_MODEL` / `AI_MAX_TOKENS` / `AI_MAX_TOOL_ROUNDS`
+    pub fn from_env() -> Self {
+        Self {
+            base_url: env_var("AI_BASE_URL").unwrap_or_else(|| "https://api.openai.com/v1".into()),
+            api_key: env_var("AI_API_KEY")
+                .or_else(|| env_var("OPENAI_API_KEY"))
+                .or_else(|| env_var("ANTHROPIC_API_KEY"))
+                .unwrap_or_default(),
+            model: env_var("AI_MODEL").unwrap_or_else(|| "gpt-4o-mini".into()),
+            max_tokens: env_var("AI_MAX_TOKENS")
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or_else(default_max_tokens),
+            headers: HashMap::new(),
+            max_tool_rounds: env_var("AI_MAX_TOOL_ROUNDS")
+                .and_then(|v| v.trim().parse::<u32>().ok())
+                .unwrap_or_else(default_max_tool_rounds)
+                .clamp(1, MAX_TOOL_ROUNDS_LIMIT),
+        }
+    }
+
     /// 构建 API endpoint URL
     pub fn endpoint(&self) -> String {
         format!("{}/chat/completions", self.base_url.trim_end_matches('/'))
@@ -7,16 +7,16 @@
 //! - `config`: 配置结构
 //! - `types`: SSE 响应数据类型
 //! - `sse_parser`: SSE 消息解析与工具调用构建
-//! - `stream`: 流式请求执行
+//! - `stream`: 流式请求执行 (服务端智能体 `server::agent` 亦复用)
 //!
 //! **优化**:
 //! - 全局 HTTP 客户端单例 (复用 TCP 连接池)
 //! - 强类型 SSE 解析 (避免 serde_json::Value)
 
-mod config;
+pub(crate) mod config;
 mod sse_parser;
-mod stream;
-mod types;
+pub(crate) mod stream;
+pub(crate) mod types;
 
 use anyhow::{Result, anyhow};
 use config::ChatConfig;
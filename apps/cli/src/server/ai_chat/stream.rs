@@ -2,15 +2,18 @@
 //! # SSE 流式请求执行器
 //!
 //! **功能**: 执行 OpenAI 兼容的 SSE 流式 HTTP 请求。
+//! `stream_completion` 为通用的单轮补全 (支持取消)，插件与服务端智能体共用。
 
 use super::sse_parser::{ToolCallBuilder, parse_sse_message};
 use super::types::ParsedSseEvent;
 use anyhow::{Result, anyhow};
-use deve_core::plugin::runtime::chat_stream::{ChatStreamResponse, ChatStreamSink};
+use deve_core::plugin::runtime::cancel::CancelToken;
+use deve_core::plugin::runtime::chat_stream::{ChatStreamResponse, ChatStreamSink, ToolCallInfo};
 use futures::StreamExt;
 use reqwest_eventsource::{Error as EventSourceError, Event, EventSource};
 use std::collections::HashMap;
 use std::sync::OnceLock;
+use std::time::Instant;
 
 /// 全局 HTTP 客户端单例
 static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
@@ -24,7 +27,15 @@ pub fn get_http_client() -> &'static reqwest::Client {
     })
 }
 
-/// 执行流式请求
+/// 一轮流式补全的结果
+#[derive(Debug, Default)]
+pub struct Completion {
+    pub content: String,
+    pub tool_calls: Vec<ToolCallInfo>,
+    pub finish_reason: Option<String>,
+}
+
+/// 执行流式请求 (插件 `ai_chat_stream_with_tools` 路径)
 pub async fn execute_stream(
     req_id: &str,
     endpoint: &str,
@@ -33,6 +44,44 @@ pub async fn execute_stream(
     body: serde_json::Value,
     sink: &ChatStreamSink,
 ) -> Result<ChatStreamResponse> {
+    let completion = stream_completion(endpoint, api_key, headers, body, None, |event| {
+        if let ParsedSseEvent::ContentDelta(content) = event {
+            sink.send_chunk(req_id, Some(content.clone()), None);
+        }
+    })
+    .await?
+    .unwrap_or_default();
+
+    // 发送结束信号
+    if let Some(reason) = completion.finish_reason {
+        sink.send_chunk(req_id, None, Some(reason));
+    }
+
+    if !completion.tool_calls.is_empty() {
+        Ok(ChatStreamResponse::ToolCalls {
+            calls: completion.tool_calls,
+        })
+    } else {
+        Ok(ChatStreamResponse::Text {
+            content: completion.content,
+        })
+    }
+}
+
+/// 流式请求一轮补全
+///
+/// 每个内容/工具调用增量都会回调 `on_event`。
+/// `cancel` 被触发后断开连接并返回 `Ok(None)`。
+pub async fn stream_completion(
+    endpoint: &str,
+    api_key: &str,
+    headers: &HashMap<String, String>,
+    body: serde_json::Value,
+    cancel: Option<&CancelToken>,
+    mut on_event: impl FnMut(&ParsedSseEvent),
+) -> Result<Option<Completion>> {
+    let started = Instant::now();
+    let mut first_token_seen = false;
     let client = get_http_client();
     let mut req = client.post(endpoint).bearer_auth(api_key).json(&body);
 
@@ -47,7 +96,24 @@ pub async fn execute_stream(
     let mut tool_builder = ToolCallBuilder::new();
     let mut finish_reason: Option<String> = None;
 
-    while let Some(event) = stream.next().await {
+    let cancelled = async {
+        match cancel {
+            Some(token) => token.cancelled().await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(cancelled);
+    loop {
+        let event = tokio::select! {
+            event = stream.next() => match event {
+                Some(event) => event,
+                None => break,
+            },
+            _ = &mut cancelled => {
+                stream.close();
+                return Ok(None);
+            }
+        };
         match event {
             Ok(Event::Open) => {}
             Ok(Event::Message(message)) => {
@@ -56,10 +122,21 @@ pub async fn execute_stream(
                     break;
                 }
 
-                match parse_sse_message(&message.data).map_err(|e| anyhow!("{}", e))? {
+                let parsed = parse_sse_message(&message.data).map_err(|e| anyhow!("{}", e))?;
+                if !first_token_seen
+                    && matches!(
+                        parsed,
+                        ParsedSseEvent::ContentDelta(_) | ParsedSseEvent::ToolCallDelta { .. }
+                    )
+                {
+                    first_token_seen = true;
+                    deve_core::metrics::AI_TTFT_SECONDS.observe(started.elapsed().as_secs_f64());
+                }
+                on_event(&parsed);
+
+                match parsed {
                     ParsedSseEvent::ContentDelta(content) => {
                         output.push_str(&content);
-                        sink.send_chunk(req_id, Some(content), None);
                     }
                     ParsedSseEvent::ToolCallDelta {
                         index,
@@ -80,16 +157,11 @@ pub async fn execute_stream(
             Err(err) => return Err(anyhow!("SSE stream error: {}", err)),
         }
     }
+    stream.close();
 
-    // 发送结束信号
-    if let Some(reason) = finish_reason {
-        sink.send_chunk(req_id, None, Some(reason));
-    }
-
-    let tool_calls = tool_builder.build();
-    if !tool_calls.is_empty() {
-        Ok(ChatStreamResponse::ToolCalls { calls: tool_calls })
-    } else {
-        Ok(ChatStreamResponse::Text { content: output })
-    }
+    Ok(Some(Completion {
+        content: output,
+        tool_calls: tool_builder.build(),
+        finish_reason,
+    }))
 }
@@ -6,6 +6,9 @@
 //! | POST   | /api/auth/login    | No   | 登录，返回 JWT Cookie |
 //! | POST   | /api/auth/logout   | Yes  | 清除 Cookie          |
 //! | GET    | /api/auth/me       | Yes  | 返回当前用户信息      |
+//!
+//! 启用 TOTP 后，密码校验通过只签发挑战 Cookie，需再调用
+//! `POST /api/auth/login/totp` 完成第二步 (见 `totp` 模块)。
 
 use axum::{Extension, Json, extract::ConnectInfo, http::StatusCode, response::IntoResponse};
 use axum_extra::extract::cookie::{Cookie, SameSite};
@@ -13,11 +16,13 @@ use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use deve_core::security::MfaStore;
 use deve_core::security::auth::{config::AuthConfig, jwt, password};
 
 use super::brute_force::BruteForceGuard;
 
 const COOKIE_NAME: &str = "token";
+pub(super) const CHALLENGE_COOKIE_NAME: &str = "mfa_challenge";
 
 #[derive(Deserialize)]
 pub struct LoginRequest {
@@ -30,11 +35,36 @@ pub struct LoginResponse {
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// 密码正确但需要提交 TOTP 口令
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub mfa_required: bool,
+}
+
+impl LoginResponse {
+    pub(super) fn ok() -> Self {
+        Self {
+            success: true,
+            error: None,
+            mfa_required: false,
+        }
+    }
+
+    pub(super) fn err(msg: &str) -> Self {
+        Self {
+            success: false,
+            error: Some(msg.into()),
+            mfa_required: false,
+        }
+    }
 }
 
 #[derive(Serialize)]
 pub struct MeResponse {
     pub username: String,
+    /// 是否已启用 TOTP
+    pub totp_enabled: bool,
+    /// 当前会话的第二因素是否仍在新鲜度窗口内
+    pub mfa_verified: bool,
 }
 
 /// POST /api/auth/login
@@ -43,6 +73,7 @@ pub struct MeResponse {
 pub async fn login(
     Extension(config): Extension<Arc<AuthConfig>>,
     Extension(guard): Extension<Arc<BruteForceGuard>>,
+    Extension(mfa): Extension<Arc<MfaStore>>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Json(body): Json<LoginRequest>,
 ) -> impl IntoResponse {
@@ -54,10 +85,7 @@ pub async fn login(
         return (
             StatusCode::TOO_MANY_REQUESTS,
             build_empty_cookie(),
-            Json(LoginResponse {
-                success: false,
-                error: Some("Too many attempts. Try again later.".into()),
-            }),
+            Json(LoginResponse::err("Too many attempts. Try again later.")),
         );
     }
 
@@ -68,10 +96,7 @@ pub async fn login(
         return (
             StatusCode::UNAUTHORIZED,
             build_empty_cookie(),
-            Json(LoginResponse {
-                success: false,
-                error: Some("Invalid credentials".into()),
-            }),
+            Json(LoginResponse::err("Invalid credentials")),
         );
     }
 
@@ -84,87 +109,113 @@ pub async fn login(
         return (
             StatusCode::UNAUTHORIZED,
             build_empty_cookie(),
-            Json(LoginResponse {
-                success: false,
-                error: Some("Invalid credentials".into()),
-            }),
+            Json(LoginResponse::err("Invalid credentials")),
         );
     }
 
+    // 已启用 TOTP: 仅签发挑战 Cookie，等待第二步
+    if mfa.is_enabled() {
+        tracing::info!(user = %body.username, ip = %ip, "Password accepted, TOTP required");
+        return match jwt::issue_challenge(&config.secret, config.token_version) {
+            Ok(challenge) => (
+                StatusCode::OK,
+                build_cookie(CHALLENGE_COOKIE_NAME, &challenge),
+                Json(LoginResponse {
+                    success: false,
+                    error: None,
+                    mfa_required: true,
+                }),
+            ),
+            Err(e) => internal_error(e),
+        };
+    }
+
     // 签发 JWT
     guard.record_success(&ip);
     log_login(true, &ip, &body.username);
 
     match jwt::issue_token(&config.secret, config.token_version) {
-        Ok(token) => {
-            let cookie = build_auth_cookie(&token);
-            (
-                StatusCode::OK,
-                cookie,
-                Json(LoginResponse {
-                    success: true,
-                    error: None,
-                }),
-            )
-        }
-        Err(e) => {
-            tracing::error!("JWT issue failed: {:?}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                build_empty_cookie(),
-                Json(LoginResponse {
-                    success: false,
-                    error: Some("Internal error".into()),
-                }),
-            )
-        }
+        Ok(token) => (
+            StatusCode::OK,
+            build_auth_cookie(&token),
+            Json(LoginResponse::ok()),
+        ),
+        Err(e) => internal_error(e),
     }
 }
 
+pub(super) fn internal_error(
+    e: anyhow::Error,
+) -> (StatusCode, [(String, String); 1], Json<LoginResponse>) {
+    tracing::error!("JWT issue failed: {:?}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        build_empty_cookie(),
+        Json(LoginResponse::err("Internal error")),
+    )
+}
+
 /// POST /api/auth/logout — 清除 Cookie
 pub async fn logout() -> impl IntoResponse {
     (
         StatusCode::OK,
         build_removal_cookie(),
-        Json(LoginResponse {
-            success: true,
-            error: None,
-        }),
+        Json(LoginResponse::ok()),
     )
 }
 
 /// GET /api/auth/me — 返回已认证用户 (受中间件保护)
-pub async fn me(Extension(claims): Extension<deve_core::security::Claims>) -> impl IntoResponse {
+pub async fn me(
+    Extension(claims): Extension<deve_core::security::Claims>,
+    Extension(mfa): Extension<Arc<MfaStore>>,
+) -> impl IntoResponse {
+    let now = chrono::Utc::now().timestamp();
     Json(MeResponse {
+        mfa_verified: claims.has_recent_mfa(now),
+        totp_enabled: mfa.is_enabled(),
         username: claims.sub,
     })
 }
 
-fn build_auth_cookie(token: &str) -> [(String, String); 1] {
-    let cookie = Cookie::build((COOKIE_NAME, token.to_string()))
+pub(super) fn build_auth_cookie(token: &str) -> [(String, String); 1] {
+    build_cookie(COOKIE_NAME, token)
+}
+
+fn build_cookie(name: &'static str, token: &str) -> [(String, String); 1] {
+    let cookie = Cookie::build((name, token.to_string()))
         .path("/")
         .http_only(true)
         .same_site(SameSite::Strict)
-        .secure(false) // Nginx 终止 TLS 后内部 HTTP
+        // 内置 TLS 时标记 Secure；反向代理终止 TLS 时内部为 HTTP
+        .secure(crate::server::tls::is_active())
         .build();
     [("Set-Cookie".into(), cookie.to_string())]
 }
 
 fn build_removal_cookie() -> [(String, String); 1] {
-    // max-age=0 立即过期，清除 Cookie
+    [build_cleared_cookie(COOKIE_NAME)]
+}
+
+/// max-age=0 立即过期，清除指定 Cookie
+pub(super) fn build_cleared_cookie(name: &str) -> (String, String) {
+    let secure = if crate::server::tls::is_active() {
+        "; Secure"
+    } else {
+        ""
+    };
     let value = format!(
-        "{}=; Path=/; HttpOnly; SameSite=Strict; Max-Age=0",
-        COOKIE_NAME
+        "{}=; Path=/; HttpOnly; SameSite=Strict; Max-Age=0{}",
+        name, secure
     );
-    [("Set-Cookie".into(), value)]
+    ("Set-Cookie".into(), value)
 }
 
-fn build_empty_cookie() -> [(String, String); 1] {
+pub(super) fn build_empty_cookie() -> [(String, String); 1] {
     [("X-No-Op".into(), "1".into())]
 }
 
 /// 审计日志 (09_auth.md: 登录事件 MUST 记录)
-fn log_login(success: bool, ip: &std::net::IpAddr, user: &str) {
+pub(super) fn log_login(success: bool, ip: &std::net::IpAddr, user: &str) {
     if success {
         tracing::info!(user = user, ip = %ip, "Login success");
     } else {
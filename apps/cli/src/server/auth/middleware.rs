@@ -1,7 +1,8 @@
 // apps/cli/src/server/auth/middleware.rs
 //! # JWT Cookie 认证中间件
 //!
-//! 从 HttpOnly Cookie 中提取 JWT，验证后将 Claims 注入请求 Extension。
+//! 从 HttpOnly Cookie (或非浏览器客户端的 `Authorization: Bearer`) 中提取 JWT，
+//! 验证后将 Claims 注入请求 Extension。
 //!
 //! ## Invariants
 //! - 未认证请求返回 401 Unauthorized
@@ -26,7 +27,7 @@ const COOKIE_NAME: &str = "token";
 ///
 /// 工作流程:
 /// 1. 检查 localhost 免密策略
-/// 2. 从 Cookie 提取 JWT
+/// 2. 从 Cookie 提取 JWT (缺省时读取 Bearer 头)
 /// 3. 验证 JWT 签名 + 有效期 + 版本号
 /// 4. 注入 Claims 到 Extension
 pub async fn auth_middleware(
@@ -42,13 +43,14 @@ pub async fn auth_middleware(
             iat: 0,
             exp: i64::MAX,
             ver: config.token_version,
+            mfa_at: None,
         };
         req.extensions_mut().insert(anonymous_claims);
         return next.run(req).await;
     }
 
     // 提取 Cookie
-    let token = extract_cookie_token(&req);
+    let token = extract_cookie_token(&req).or_else(|| extract_bearer_token(&req));
     let token = match token {
         Some(t) => t,
         None => return unauthorized("Missing auth token"),
@@ -88,6 +90,13 @@ fn extract_cookie_token(req: &Request<Body>) -> Option<String> {
     None
 }
 
+/// 非浏览器客户端 (如 MCP Agent) 以 `Authorization: Bearer <JWT>` 携带同一令牌
+fn extract_bearer_token(req: &Request<Body>) -> Option<String> {
+    let header = req.headers().get("authorization")?.to_str().ok()?;
+    let token = header.strip_prefix("Bearer ")?.trim();
+    (!token.is_empty()).then(|| token.to_string())
+}
+
 fn unauthorized(msg: &str) -> Response {
     (StatusCode::UNAUTHORIZED, msg.to_string()).into_response()
 }
@@ -6,8 +6,10 @@
 //! - `middleware`: JWT Cookie 提取 + 验证中间件
 //! - `brute_force`: IP 级暴力破解防护 (5 次/15 分钟)
 //! - `headers`: 安全响应头中间件
+//! - `totp`: TOTP 双因素登录、启用/关闭与重新校验端点
 
 pub mod brute_force;
 pub mod handlers;
 pub mod headers;
 pub mod middleware;
+pub mod totp;
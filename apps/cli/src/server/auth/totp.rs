@@ -0,0 +1,245 @@
+// apps/cli/src/server/auth/totp.rs
+//! # TOTP 双因素端点
+//!
+//! | Method | Path                    | Auth      | Description                     |
+//! |--------|-------------------------|-----------|---------------------------------|
+//! | POST   | /api/auth/login/totp    | Challenge | 登录第二步，校验口令后签发 JWT    |
+//! | POST   | /api/auth/totp/setup    | Yes       | 生成候选密钥与 `otpauth://` URI  |
+//! | POST   | /api/auth/totp/enable   | Yes       | 校验首个口令并启用，返回恢复码    |
+//! | POST   | /api/auth/totp/disable  | Yes       | 校验口令后关闭 TOTP             |
+//! | POST   | /api/auth/totp/verify   | Yes       | 重新校验第二因素 (敏感操作前)     |
+//!
+//! ## Invariants
+//! - 口令错误 (含启用确认) 与密码错误共用同一 `BruteForceGuard` 计数
+//! - 校验成功后重新签发 JWT，`mfa_at` 更新为当前时间
+
+use axum::{
+    Extension, Json,
+    extract::ConnectInfo,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use deve_core::security::auth::{config::AuthConfig, jwt};
+use deve_core::security::{Claims, MfaStore};
+
+use super::brute_force::BruteForceGuard;
+use super::handlers::{
+    CHALLENGE_COOKIE_NAME, LoginResponse, build_auth_cookie, build_cleared_cookie,
+    build_empty_cookie, internal_error, log_login,
+};
+
+#[derive(Deserialize)]
+pub struct CodeRequest {
+    /// 6 位 TOTP 口令或恢复码
+    pub code: String,
+}
+
+#[derive(Serialize)]
+pub struct EnableResponse {
+    pub success: bool,
+    /// 恢复码明文 (仅返回这一次)
+    pub recovery_codes: Vec<String>,
+}
+
+/// POST /api/auth/login/totp
+///
+/// 校验挑战 Cookie 与 TOTP 口令，成功后签发带 `mfa_at` 的 JWT。
+pub async fn login_totp(
+    Extension(config): Extension<Arc<AuthConfig>>,
+    Extension(guard): Extension<Arc<BruteForceGuard>>,
+    Extension(mfa): Extension<Arc<MfaStore>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(body): Json<CodeRequest>,
+) -> Response {
+    let ip = addr.ip();
+    if guard.is_blocked(&ip) {
+        tracing::warn!(ip = %ip, "TOTP login blocked (brute force)");
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            build_empty_cookie(),
+            Json(LoginResponse::err("Too many attempts. Try again later.")),
+        )
+            .into_response();
+    }
+
+    let challenge = extract_cookie(&headers, CHALLENGE_COOKIE_NAME)
+        .and_then(|t| jwt::validate_challenge(&config.secret, &t, config.token_version).ok());
+    let Some(challenge) = challenge else {
+        return (
+            StatusCode::UNAUTHORIZED,
+            build_empty_cookie(),
+            Json(LoginResponse::err("Login challenge missing or expired")),
+        )
+            .into_response();
+    };
+
+    let now = chrono::Utc::now().timestamp();
+    if !mfa.verify(&body.code, now) {
+        guard.record_failure(&ip);
+        log_login(false, &ip, &challenge.sub);
+        return (
+            StatusCode::UNAUTHORIZED,
+            build_empty_cookie(),
+            Json(LoginResponse::err("Invalid code")),
+        )
+            .into_response();
+    }
+
+    guard.record_success(&ip);
+    log_login(true, &ip, &challenge.sub);
+
+    match jwt::issue_token_with_mfa(&config.secret, config.token_version, Some(now)) {
+        Ok(token) => {
+            let [auth] = build_auth_cookie(&token);
+            let clear = build_cleared_cookie(CHALLENGE_COOKIE_NAME);
+            (StatusCode::OK, [auth, clear], Json(LoginResponse::ok())).into_response()
+        }
+        Err(e) => internal_error(e).into_response(),
+    }
+}
+
+/// POST /api/auth/totp/setup — 开始启用流程
+pub async fn setup(
+    Extension(claims): Extension<Claims>,
+    Extension(mfa): Extension<Arc<MfaStore>>,
+) -> Response {
+    match mfa.begin_enrolment(&claims.sub) {
+        Ok(enrolment) => Json(enrolment).into_response(),
+        Err(e) => (
+            StatusCode::CONFLICT,
+            Json(LoginResponse::err(&e.to_string())),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /api/auth/totp/enable — 确认启用，返回恢复码
+pub async fn enable(
+    Extension(config): Extension<Arc<AuthConfig>>,
+    Extension(guard): Extension<Arc<BruteForceGuard>>,
+    Extension(mfa): Extension<Arc<MfaStore>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Json(body): Json<CodeRequest>,
+) -> Response {
+    let ip = addr.ip();
+    if guard.is_blocked(&ip) {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(LoginResponse::err("Too many attempts. Try again later.")),
+        )
+            .into_response();
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let codes = match mfa.confirm_enrolment(&body.code, now) {
+        Ok(codes) => codes,
+        Err(e) => {
+            guard.record_failure(&ip);
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(LoginResponse::err(&e.to_string())),
+            )
+                .into_response();
+        }
+    };
+    guard.record_success(&ip);
+    tracing::info!("TOTP enabled");
+
+    match jwt::issue_token_with_mfa(&config.secret, config.token_version, Some(now)) {
+        Ok(token) => (
+            StatusCode::OK,
+            build_auth_cookie(&token),
+            Json(EnableResponse {
+                success: true,
+                recovery_codes: codes,
+            }),
+        )
+            .into_response(),
+        Err(e) => internal_error(e).into_response(),
+    }
+}
+
+/// POST /api/auth/totp/disable — 关闭 TOTP
+pub async fn disable(
+    Extension(guard): Extension<Arc<BruteForceGuard>>,
+    Extension(mfa): Extension<Arc<MfaStore>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Json(body): Json<CodeRequest>,
+) -> Response {
+    let ip = addr.ip();
+    if guard.is_blocked(&ip) {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(LoginResponse::err("Too many attempts. Try again later.")),
+        )
+            .into_response();
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    match mfa.disable(&body.code, now) {
+        Ok(()) => {
+            tracing::warn!(ip = %ip, "TOTP disabled");
+            Json(LoginResponse::ok()).into_response()
+        }
+        Err(e) => {
+            guard.record_failure(&ip);
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(LoginResponse::err(&e.to_string())),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// POST /api/auth/totp/verify — 重新校验第二因素并刷新 `mfa_at`
+pub async fn verify(
+    Extension(config): Extension<Arc<AuthConfig>>,
+    Extension(guard): Extension<Arc<BruteForceGuard>>,
+    Extension(mfa): Extension<Arc<MfaStore>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Json(body): Json<CodeRequest>,
+) -> Response {
+    let ip = addr.ip();
+    if guard.is_blocked(&ip) {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(LoginResponse::err("Too many attempts. Try again later.")),
+        )
+            .into_response();
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    if !mfa.verify(&body.code, now) {
+        guard.record_failure(&ip);
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(LoginResponse::err("Invalid code")),
+        )
+            .into_response();
+    }
+    guard.record_success(&ip);
+
+    match jwt::issue_token_with_mfa(&config.secret, config.token_version, Some(now)) {
+        Ok(token) => (
+            StatusCode::OK,
+            build_auth_cookie(&token),
+            Json(LoginResponse::ok()),
+        )
+            .into_response(),
+        Err(e) => internal_error(e).into_response(),
+    }
+}
+
+fn extract_cookie(headers: &HeaderMap, name: &str) -> Option<String> {
+    let header = headers.get("cookie")?.to_str().ok()?;
+    header.split(';').find_map(|pair| {
+        let (key, value) = pair.trim().split_once('=')?;
+        (key == name && !value.is_empty()).then(|| value.to_string())
+    })
+}
@@ -73,6 +73,12 @@ impl DualChannel {
         Self { broadcast, unicast }
     }
 
+    /// 仅广播的通道 (无发起客户端，如插件宿主函数)，单播消息被丢弃
+    pub fn broadcast_only(broadcast: broadcast::Sender<ServerMessage>) -> Self {
+        let (unicast, _) = mpsc::channel(1);
+        Self { broadcast, unicast }
+    }
+
     /// 广播消息 (全局事件)
     pub fn broadcast(&self, msg: ServerMessage) {
         let _ = self.broadcast.send(msg);
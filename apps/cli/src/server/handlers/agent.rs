@@ -0,0 +1,214 @@
+// apps/cli/src/server/handlers/agent.rs
+//! # 智能体处理器 (Agent Handler)
+//!
+//! 处理聊天发送、取消与会话的列出、读取、删除。
+//! 运行在独立任务中执行，WebSocket 处理循环不等待；
+//! 连接断开时 (会话令牌取消) 立即取消本连接发起的运行，取消请求只对本会话发起的运行生效。
+
+use crate::server::AppState;
+use crate::server::agent::run::{AgentRun, RunOutcome, now_ms, run_agent};
+use crate::server::agent::tools::AgentTools;
+use crate::server::ai_chat::config::ChatConfig;
+use crate::server::channel::DualChannel;
+use crate::server::session::WsSession;
+use deve_core::agent::ConversationStore;
+use deve_core::protocol::{AgentMessage, ServerMessage};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// 提供内置工具的插件
+const BUILTIN_TOOLS_PLUGIN: &str = "ai-chat";
+
+fn send_done(ch: &DualChannel, req_id: String, conversation_id: String, outcome: &RunOutcome) {
+    ch.unicast(ServerMessage::ChatDone {
+        req_id,
+        conversation_id,
+        reason: outcome.reason().to_string(),
+        error: outcome.error(),
+    });
+}
+
+/// 处理聊天发送: 准备会话后在后台启动智能体循环
+pub async fn handle_chat_send(
+    state: &Arc<AppState>,
+    ch: &DualChannel,
+    session: &WsSession,
+    req_id: String,
+    conversation_id: Option<String>,
+    message: String,
+    context: Value,
+) {
+    let fail = |conversation_id: String, error: String| {
+        send_done(
+            ch,
+            req_id.clone(),
+            conversation_id,
+            &RunOutcome::Error(error),
+        );
+    };
+    let store = match state.agent.store() {
+        Ok(store) => store,
+        Err(e) => return fail(conversation_id.unwrap_or_default(), e.to_string()),
+    };
+
+    // 提示词与资源读取可能启动 stdio 服务器进程
+    let needs_expand = context.get("prompt").is_some() || context.get("resources").is_some();
+    let content = if needs_expand {
+        let mcp = state.mcp.clone();
+        let (text, ctx) = (message.clone(), context.clone());
+        match tokio::task::spawn_blocking(move || {
+            crate::server::agent_bridge::expand_mcp_context(&mcp, &text, &ctx)
+        })
+        .await
+        {
+            Ok(Ok(content)) => content,
+            Ok(Err(e)) => {
+                return fail(
+                    conversation_id.unwrap_or_default(),
+                    format!("MCP context error: {}", e),
+                );
+            }
+            Err(e) => {
+                return fail(
+                    conversation_id.unwrap_or_default(),
+                    format!("MCP context error: {}", e),
+                );
+            }
+        }
+    } else {
+        message.clone()
+    };
+    if content.trim().is_empty() {
+        return fail(
+            conversation_id.unwrap_or_default(),
+            "No user message provided".into(),
+        );
+    }
+
+    // 会话存储为同步 redb 读写，在阻塞线程池中执行
+    let conversation_id = match conversation_id {
+        Some(id) => {
+            let (reader, key) = (store.clone(), id.clone());
+            match tokio::task::spawn_blocking(move || reader.get(&key)).await {
+                Ok(Ok(Some(_))) => id,
+                Ok(Ok(None)) => {
+                    return fail(id.clone(), format!("Conversation not found: {}", id));
+                }
+                Ok(Err(e)) => return fail(id, e.to_string()),
+                Err(e) => return fail(id, e.to_string()),
+            }
+        }
+        None => {
+            let title = if message.trim().is_empty() {
+                content.clone()
+            } else {
+                message.clone()
+            };
+            let writer = store.clone();
+            match tokio::task::spawn_blocking(move || writer.create(&title, now_ms())).await {
+                Ok(Ok(summary)) => summary.id,
+                Ok(Err(e)) => return fail(String::new(), e.to_string()),
+                Err(e) => return fail(String::new(), e.to_string()),
+            }
+        }
+    };
+
+    let guard = match state
+        .agent
+        .begin(&req_id, &conversation_id, &session.session_id)
+    {
+        Ok(guard) => guard,
+        Err(e) => return fail(conversation_id, e.to_string()),
+    };
+    ch.unicast(ServerMessage::ChatStarted {
+        req_id: req_id.clone(),
+        conversation_id: conversation_id.clone(),
+    });
+
+    let builtin = state
+        .plugins
+        .snapshot()
+        .iter()
+        .find(|p| p.manifest().id == BUILTIN_TOOLS_PLUGIN)
+        .cloned();
+    let tools = AgentTools::new(builtin, Some(state.mcp.clone()));
+    let config = ChatConfig::from_env();
+    let display = (message != content).then_some(message);
+    let run = AgentRun {
+        req_id: req_id.clone(),
+        conversation_id: conversation_id.clone(),
+        user_message: AgentMessage::user(content, display, now_ms()),
+        current_file: context
+            .get("current_file")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    };
+
+    // 连接断开时取消运行 (运行结束后令牌亦被取消，监视任务随之退出)
+    let run_cancel = guard.cancel_token().clone();
+    let session_cancel = session.plugin_cancel.clone();
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = session_cancel.cancelled() => run_cancel.cancel(),
+            _ = run_cancel.cancelled() => {}
+        }
+    });
+
+    let ch = ch.clone();
+    tokio::spawn(async move {
+        let emit_ch = ch.clone();
+        let emit = move |msg: ServerMessage| emit_ch.unicast(msg);
+        let outcome = run_agent(&config, &store, &tools, run, guard.cancel_token(), &emit).await;
+        tracing::info!("Agent: req_id={} finished ({})", req_id, outcome.reason());
+        guard.cancel_token().cancel();
+        drop(guard);
+        send_done(&ch, req_id, conversation_id, &outcome);
+    });
+}
+
+/// 取消运行 (运行结束后或其他会话发起的运行的取消请求忽略)
+pub fn handle_chat_cancel(state: &Arc<AppState>, session: &WsSession, req_id: String) {
+    if !state.agent.cancel(&req_id, &session.session_id) {
+        tracing::debug!("Agent: cancel for unknown req_id={}", req_id);
+    }
+}
+
+/// 在阻塞线程池中访问会话存储 (redb 读写不占用异步工作线程)
+async fn with_store<R: Send + 'static>(
+    state: &Arc<AppState>,
+    f: impl FnOnce(&ConversationStore) -> anyhow::Result<R> + Send + 'static,
+) -> anyhow::Result<R> {
+    let store = state.agent.store()?;
+    tokio::task::spawn_blocking(move || f(&store)).await?
+}
+
+/// 会话列表
+pub async fn handle_list_conversations(state: &Arc<AppState>, ch: &DualChannel) {
+    match with_store(state, |s| s.list()).await {
+        Ok(conversations) => ch.unicast(ServerMessage::ConversationList { conversations }),
+        Err(e) => ch.send_error(format!("List conversations failed: {}", e)),
+    }
+}
+
+/// 读取完整会话
+pub async fn handle_get_conversation(state: &Arc<AppState>, ch: &DualChannel, id: String) {
+    let key = id.clone();
+    match with_store(state, move |s| s.get(&key)).await {
+        Ok(Some(conversation)) => ch.unicast(ServerMessage::ConversationData { conversation }),
+        Ok(None) => ch.send_error(format!("Conversation not found: {}", id)),
+        Err(e) => ch.send_error(format!("Get conversation failed: {}", e)),
+    }
+}
+
+/// 删除会话 (运行中的会话拒绝删除)
+pub async fn handle_delete_conversation(state: &Arc<AppState>, ch: &DualChannel, id: String) {
+    if state.agent.is_running(&id) {
+        ch.send_error("Conversation is busy".into());
+        return;
+    }
+    let key = id.clone();
+    match with_store(state, move |s| s.delete(&key)).await {
+        Ok(_) => ch.unicast(ServerMessage::ConversationDeleted { id }),
+        Err(e) => ch.send_error(format!("Delete conversation failed: {}", e)),
+    }
+}
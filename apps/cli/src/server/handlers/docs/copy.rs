@@ -8,6 +8,7 @@ use super::validate_path;
 use crate::server::AppState;
 use crate::server::channel::DualChannel;
 use crate::server::handlers::docs::node_helpers::{broadcast_dir_chain, broadcast_parent_dirs};
+use crate::server::handlers::indexing::reconcile_index;
 use crate::server::handlers::listing::handle_list_docs;
 use crate::server::session::WsSession;
 use anyhow::anyhow;
@@ -129,6 +130,8 @@ pub async fn handle_copy_doc(
         }
     }
 
+    reconcile_index(state);
+
     handle_list_docs(state, ch, session).await;
 }
 
@@ -5,11 +5,13 @@ use super::validate_path;
 use crate::server::AppState;
 use crate::server::channel::DualChannel;
 use crate::server::handlers::docs::node_helpers::{broadcast_dir_chain, broadcast_parent_dirs};
+use crate::server::handlers::indexing::reconcile_index;
 use crate::server::handlers::listing::handle_list_docs;
 use crate::server::session::WsSession;
 use anyhow::anyhow;
 use deve_core::ledger::node_meta;
 use deve_core::models::NodeId;
+use deve_core::plugin::events::PluginEvent;
 use deve_core::protocol::ServerMessage;
 use deve_core::utils::path::join_normalized;
 use std::sync::Arc;
@@ -138,12 +140,16 @@ pub async fn handle_create_doc(
             ch.broadcast(ServerMessage::TreeUpdate(delta));
         }
         // 刷新文档列表
+        reconcile_index(state);
         handle_list_docs(state, ch, session).await;
     } else if let Err(e) = std::fs::write(&path, "") {
         tracing::error!("创建文件失败: {:?}", e);
         ch.send_error(format!("Failed to create file: {}", e));
     } else if let Ok(doc_id) = state.repo.create_docid(&filename) {
         tracing::info!("已创建文档: {} ({})", filename, doc_id);
+        state.events.emit(PluginEvent::FileCreated {
+            path: filename.clone(),
+        });
         let node_id = NodeId::from_doc_id(doc_id);
         if let Ok(meta) = state
             .repo
@@ -165,6 +171,7 @@ pub async fn handle_create_doc(
             ch.broadcast(ServerMessage::TreeUpdate(delta));
         }
         // 刷新文档列表
+        reconcile_index(state);
         handle_list_docs(state, ch, session).await;
     }
 }
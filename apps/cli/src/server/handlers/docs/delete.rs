@@ -3,6 +3,7 @@
 
 use crate::server::AppState;
 use crate::server::channel::DualChannel;
+use crate::server::handlers::indexing::reconcile_index;
 use crate::server::handlers::listing::handle_list_docs;
 use crate::server::session::WsSession;
 use deve_core::ledger::node_meta;
@@ -67,6 +68,10 @@ pub async fn handle_delete_doc(
             Ok(count) => tracing::info!("已从 Ledger 删除 {} 个文档 (文件夹: {})", count, path),
             Err(e) => tracing::error!("Ledger 文件夹删除失败: {:?}", e),
         }
+    } else if matches!(state.repo.get_attachment(&path), Ok(Some(_))) {
+        if let Err(e) = state.repo.remove_attachment(&path) {
+            tracing::error!("Ledger 附件删除失败: {:?}", e);
+        }
     } else if let Err(e) = state.repo.delete_doc(&path) {
         tracing::error!("Ledger 文档删除失败: {:?}", e);
     }
@@ -82,5 +87,6 @@ pub async fn handle_delete_doc(
     }
 
     // 5. 刷新文档列表
+    reconcile_index(state);
     handle_list_docs(state, ch, session).await;
 }
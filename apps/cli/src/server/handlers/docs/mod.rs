@@ -13,7 +13,7 @@ mod copy;
 mod copy_utils;
 mod create;
 mod delete;
-mod node_helpers;
+pub(crate) mod node_helpers;
 mod rename;
 
 pub use copy::handle_copy_doc;
@@ -32,23 +32,32 @@ pub const MAX_DEPTH: usize = 10;
 /// - 不包含 `..` (目录遍历)
 /// - 不以 `/` 或 `\` 开头 (绝对路径)
 /// - 目录深度不超过 `MAX_DEPTH`
-pub fn validate_path(path: &str, ch: &DualChannel) -> bool {
+pub fn check_path(path: &str) -> Result<(), String> {
     // 防止目录遍历攻击
     if path.contains("..") || path.starts_with('/') || path.starts_with('\\') {
         tracing::error!("路径校验失败 (遍历攻击): {}", path);
-        ch.send_error(format!("Invalid path: {}", path));
-        return false;
+        return Err(format!("Invalid path: {}", path));
     }
 
     // 检查目录深度
     if std::path::Path::new(path).components().count() > MAX_DEPTH {
         tracing::error!("路径校验失败 (深度超限): {}", path);
-        ch.send_error(format!(
+        return Err(format!(
             "Directory depth limit exceeded (max {})",
             MAX_DEPTH
         ));
-        return false;
     }
 
-    true
+    Ok(())
+}
+
+/// `check_path` 失败时向客户端发送错误
+pub fn validate_path(path: &str, ch: &DualChannel) -> bool {
+    match check_path(path) {
+        Ok(()) => true,
+        Err(e) => {
+            ch.send_error(e);
+            false
+        }
+    }
 }
@@ -5,10 +5,13 @@ use super::validate_path;
 use crate::server::AppState;
 use crate::server::channel::DualChannel;
 use crate::server::handlers::docs::node_helpers::broadcast_parent_dirs;
+use crate::server::handlers::indexing::reconcile_index;
+use crate::server::handlers::links::offer_link_rewrite;
 use crate::server::handlers::listing::handle_list_docs;
 use crate::server::session::WsSession;
 use anyhow::anyhow;
 use deve_core::ledger::node_meta;
+use deve_core::plugin::events::PluginEvent;
 use deve_core::protocol::ServerMessage;
 use deve_core::utils::path::join_normalized;
 use std::sync::Arc;
@@ -20,6 +23,7 @@ use std::sync::Arc;
 /// 2. 执行文件系统重命名
 /// 3. 更新 Ledger 中的路径映射
 /// 4. 更新 TreeManager 并广播 TreeDelta
+/// 5. 存在受影响的链接时发送 `LinkRewriteOffer`
 pub async fn handle_rename_doc(
     state: &Arc<AppState>,
     ch: &DualChannel,
@@ -72,14 +76,26 @@ pub async fn handle_rename_doc(
             ch.send_error(format!("Failed to rename: {}", e));
         } else {
             tracing::info!("已重命名 {} -> {}", old_path, dst_name);
+            state.events.emit(PluginEvent::FileRenamed {
+                from: old_path.clone(),
+                to: dst_name.clone(),
+            });
 
-            // 4. 更新 Ledger
+            // 4. 更新 Ledger (文档/文件夹成功后检查入链)
             if dst.is_dir() {
-                if let Err(e) = state.repo.rename_folder(&old_path, &dst_name) {
-                    tracing::error!("Ledger 文件夹重命名失败: {:?}", e);
+                match state.repo.rename_folder(&old_path, &dst_name) {
+                    Ok(()) => offer_link_rewrite(state, ch, &old_path, &dst_name),
+                    Err(e) => tracing::error!("Ledger 文件夹重命名失败: {:?}", e),
+                }
+            } else if matches!(state.repo.get_attachment(&old_path), Ok(Some(_))) {
+                if let Err(e) = state.repo.rename_attachment(&old_path, &dst_name) {
+                    tracing::error!("Ledger 附件重命名失败: {:?}", e);
+                }
+            } else {
+                match state.repo.rename_doc(&old_path, &dst_name) {
+                    Ok(()) => offer_link_rewrite(state, ch, &old_path, &dst_name),
+                    Err(e) => tracing::error!("Ledger 文档重命名失败: {:?}", e),
                 }
-            } else if let Err(e) = state.repo.rename_doc(&old_path, &dst_name) {
-                tracing::error!("Ledger 文档重命名失败: {:?}", e);
             }
 
             // 5. 更新 TreeManager 并广播 Delta
@@ -111,6 +127,7 @@ pub async fn handle_rename_doc(
             }
 
             // 6. 刷新文档列表
+            reconcile_index(state);
             handle_list_docs(state, ch, session).await;
         }
     } else {
@@ -6,7 +6,9 @@
 use crate::server::AppState;
 use crate::server::channel::DualChannel;
 use crate::server::session::WsSession;
-use deve_core::models::{LedgerEntry, Op};
+use deve_core::ledger::MergedOp;
+use deve_core::models::{DocId, LedgerEntry, Op, PeerId};
+use deve_core::plugin::events::PluginEvent;
 use deve_core::protocol::ServerMessage;
 use std::sync::Arc;
 use std::time::Instant;
@@ -16,6 +18,9 @@ type SnapshotPayload = (String, u64, Vec<(u64, Op)>, u64);
 
 /// 处理编辑请求
 ///
+/// 操作基于客户端的 `base_seq` 生成，先变换到其后的并发操作之上再写入 (OT rebase)，
+/// 每段写入结果以 NewOp 广播，最后广播携带变换结果的 Ack。
+///
 /// **只读模式处理**:
 /// 当 session 处于只读模式 (remotes 分支) 时，静默忽略编辑请求。
 /// // TODO: Frontend will hide edit buttons when readonly
@@ -25,6 +30,7 @@ pub async fn handle_edit(
     session: &WsSession,
     doc_id: deve_core::models::DocId,
     op: deve_core::models::Op,
+    base_seq: u64,
     client_id: u64,
 ) {
     // 只读模式检查: 静默忽略编辑请求
@@ -33,49 +39,138 @@ pub async fn handle_edit(
         return;
     }
 
-    // 获取本地 Peer ID
     let local_peer_id = state.identity_key.peer_id();
+    let guard = state.repo.lock_edits();
 
-    // 2. 构造并追加操作 (Atomic Generation & Persist)
-    // 使用 sync_manager.apply_local_op 自动处理序号生成和持久化
-    let op_clone = op.clone();
-
-    // 我们需要克隆 peer_id 用于构建 closure
-    let peer_id_clone = local_peer_id.clone();
-
-    match state.sync_manager.apply_local_op(
+    let (seq, ops) = match append_op_locked(
+        state,
+        &ch.broadcast,
         doc_id,
-        local_peer_id.clone(),
-        move |seq| LedgerEntry {
-            doc_id,
-            op: op_clone.clone(),
-            timestamp: chrono::Utc::now().timestamp_millis(),
-            peer_id: peer_id_clone.clone(),
-            seq,
-        },
-        true, // 自动写入 Vault
+        &local_peer_id,
+        base_seq,
+        &op,
+        client_id,
     ) {
-        Ok((_global_seq, local_seq)) => {
-            // 3. 广播新操作给所有连接的客户端
-            // BUG FIX: 必须广播 Local Seq (CrDT Version)，而不是 Global Seq
-            ch.broadcast(ServerMessage::NewOp {
-                doc_id,
-                op,
-                seq: local_seq,
-                client_id,
-            });
-
-            // 4. 发送 Ack
-            ch.unicast(ServerMessage::Ack {
-                doc_id,
-                seq: local_seq,
-            });
-        }
+        Ok(result) => result,
         Err(e) => {
             tracing::error!("Failed to persist op: {:?}", e);
             ch.send_error(format!("Failed to persist operation: {}", e));
+            return;
         }
+    };
+
+    // 广播 Ack (与 NewOp 同一通道，保证发起方先收到其前的全部并发操作)
+    ch.broadcast(ServerMessage::Ack {
+        doc_id,
+        seq,
+        client_id,
+        ops: ops.clone(),
+    });
+    drop(guard);
+
+    // 写入 Vault 并更新搜索索引 (后台防抖合并)
+    if !ops.is_empty()
+        && let Err(e) = finish_edit(state, doc_id)
+    {
+        tracing::error!("Failed to persist doc {}: {:?}", doc_id, e);
+        ch.send_error(format!("Failed to persist operation: {}", e));
+    }
+}
+
+/// 将基于 `base_seq` 的操作变换后逐段写入 Ledger，并以 NewOp 广播
+///
+/// 返回最后的全局序号 (与 Snapshot 版本一致) 与变换后的操作。
+///
+/// # 前置条件
+/// - 调用方持有 `state.repo.lock_edits()`
+pub(crate) fn append_op_locked(
+    state: &AppState,
+    broadcast: &tokio::sync::broadcast::Sender<ServerMessage>,
+    doc_id: DocId,
+    peer_id: &PeerId,
+    base_seq: u64,
+    op: &Op,
+    client_id: u64,
+) -> anyhow::Result<(u64, Vec<Op>)> {
+    // 1. 变换到 base_seq 之后的并发操作之上
+    let ops = state.repo.rebase_local_op(doc_id, base_seq, op)?;
+
+    // 2. 逐段追加 (原子生成序号)
+    let mut seq = base_seq;
+    for op in &ops {
+        let entry_op = op.clone();
+        let entry_peer = peer_id.clone();
+        let (global_seq, _) = state.sync_manager.apply_local_op(
+            doc_id,
+            peer_id.clone(),
+            move |local_seq| LedgerEntry {
+                doc_id,
+                op: entry_op.clone(),
+                timestamp: chrono::Utc::now().timestamp_millis(),
+                peer_id: entry_peer.clone(),
+                seq: local_seq,
+            },
+            false,
+        )?;
+        seq = global_seq;
+        let _ = broadcast.send(ServerMessage::NewOp {
+            doc_id,
+            op: op.clone(),
+            seq,
+            client_id,
+        });
     }
+    Ok((seq, ops))
+}
+
+/// 编辑写入后: 落盘到 Vault、触发 `doc_saved` 并更新索引
+pub(crate) fn finish_edit(state: &AppState, doc_id: DocId) -> anyhow::Result<()> {
+    let persisted = state.sync_manager.persist_doc(doc_id);
+    if persisted.is_ok()
+        && let Ok(Some(path)) = state.repo.get_path_by_docid(doc_id)
+    {
+        state.events.emit(PluginEvent::DocSaved { doc_id, path });
+    }
+    super::indexing::touch_index(state, doc_id);
+    persisted
+}
+
+/// 合并远端 CRDT 操作并推送到打开该文档的编辑器
+///
+/// `merge` 在编辑定序锁内执行，写入的操作按序以 NewOp (client_id 0) 广播：
+/// 单个位置效果直接下发；多个效果无法共用一个序号，改为下发 CRDT 操作本身，
+/// 编辑器收到后重新打开文档。随后写入 Vault 并更新索引。
+/// 返回写入的操作数 (`None` 表示不适用 CRDT 合并)。
+pub(crate) fn publish_crdt_merge(
+    state: &Arc<AppState>,
+    ch: &DualChannel,
+    doc_id: deve_core::models::DocId,
+    merge: impl FnOnce() -> anyhow::Result<Option<Vec<MergedOp>>>,
+) -> anyhow::Result<Option<usize>> {
+    let guard = state.repo.lock_edits();
+    let Some(merged) = merge()? else {
+        return Ok(None);
+    };
+    for MergedOp { seq, op, effects } in &merged {
+        let op = match effects.as_slice() {
+            [] => continue,
+            [effect] => effect,
+            _ => op,
+        };
+        ch.broadcast(ServerMessage::NewOp {
+            doc_id,
+            op: op.clone(),
+            seq: *seq,
+            client_id: 0,
+        });
+    }
+    drop(guard);
+
+    if !merged.is_empty() {
+        state.sync_manager.persist_doc(doc_id)?;
+        super::indexing::touch_index(state, doc_id);
+    }
+    Ok(Some(merged.len()))
 }
 
 /// 处理历史记录请求
@@ -86,10 +181,8 @@ pub async fn handle_request_history(
     doc_id: deve_core::models::DocId,
 ) {
     if let Ok(entries) = state.repo.get_local_ops(doc_id) {
-        let ops: Vec<(u64, deve_core::models::Op)> = entries
-            .into_iter()
-            .map(|(seq, entry)| (seq, entry.op))
-            .collect();
+        // CRDT 操作以其对文本的位置效果下发
+        let ops = deve_core::state::text_ops_after(&entries, 0);
 
         // 单播历史记录给请求者
         ch.unicast(ServerMessage::History { doc_id, ops });
@@ -103,7 +196,7 @@ pub async fn handle_request_history(
 ///
 /// **逻辑**:
 /// 使用 session 中锁定的 active_db 直接读取操作日志，
-/// 支持本地和远程分支的统一读取。
+/// 支持本地和远程分支的统一读取；主库经 `RepoManager` 的文档状态缓存读取。
 pub async fn handle_open_doc(
     state: &Arc<AppState>,
     ch: &DualChannel,
@@ -120,39 +213,35 @@ pub async fn handle_open_doc(
     let start = Instant::now();
 
     // 优先使用 session 锁定的数据库
-    let (snapshot_content, base_seq, delta_ops, version) = if let Some(handle) =
-        session.get_active_db()
-    {
-        // 直接从锁定的数据库读取
-        match build_snapshot_payload(&handle.db, doc_id, state.repo.snapshot_depth) {
-            Ok(payload) => payload,
-            Err(e) => {
-                tracing::error!("Failed to build snapshot from active_db: {:?}", e);
-                (String::new(), 0, Vec::new(), 0)
+    let (snapshot_content, base_seq, delta_ops, version) =
+        if let Some(handle) = session.get_active_db() {
+            // 直接从锁定的数据库读取
+            match build_snapshot_payload(&handle.db, doc_id, state.repo.snapshot_depth) {
+                Ok(payload) => payload,
+                Err(e) => {
+                    tracing::error!("Failed to build snapshot from active_db: {:?}", e);
+                    (String::new(), 0, Vec::new(), 0)
+                }
             }
-        }
-    } else {
-        // 回退: 使用默认本地库
-        tracing::warn!("No active_db in session, falling back to main local repo");
-        let repo_name = state.repo.local_repo_name();
-
-        // Reconcile logic for main repo
-        if let Err(e) = state.sync_manager.reconcile_doc(doc_id) {
-            tracing::error!("SyncManager reconcile failed: {:?}", e);
-        }
+        } else {
+            // 回退: 使用默认本地库
+            tracing::warn!("No active_db in session, falling back to main local repo");
+            let repo_name = state.repo.local_repo_name();
 
-        let res: anyhow::Result<SnapshotPayload> = state.repo.run_on_local_repo(repo_name, |db| {
-            build_snapshot_payload(db, doc_id, state.repo.snapshot_depth)
-        });
+            // Reconcile logic for main repo
+            if let Err(e) = state.sync_manager.reconcile_doc(doc_id) {
+                tracing::error!("SyncManager reconcile failed: {:?}", e);
+            }
 
-        match res {
-            Ok(payload) => payload,
-            Err(e) => {
-                tracing::error!("Failed to read snapshot from repo {}: {:?}", repo_name, e);
-                (String::new(), 0, Vec::new(), 0)
+            // 主库经文档状态缓存读取完整内容，无需下发增量
+            match state.repo.get_local_state(doc_id) {
+                Ok((content, version)) => (content, version, Vec::new(), version),
+                Err(e) => {
+                    tracing::error!("Failed to read snapshot from repo {}: {:?}", repo_name, e);
+                    (String::new(), 0, Vec::new(), 0)
+                }
             }
-        }
-    };
+        };
 
     tracing::info!(
         "OpenDoc Prepared: doc={}, base_seq={}, version={}, pending_ops={}, elapsed_ms={}",
@@ -171,6 +260,10 @@ pub async fn handle_open_doc(
         version,
         delta_ops,
     });
+
+    if let Ok(Some(path)) = state.repo.get_path_by_docid(doc_id) {
+        state.events.emit(PluginEvent::DocOpened { doc_id, path });
+    }
 }
 
 fn build_snapshot_payload(
@@ -183,6 +276,18 @@ fn build_snapshot_payload(
     let (base_seq, content) = snapshot.unwrap_or((0, String::new()));
 
     let delta_entries = deve_core::ledger::ops::get_ops_from_db_after(db, doc_id, base_seq)?;
+    if delta_entries
+        .iter()
+        .any(|(_, entry)| matches!(entry.op, deve_core::models::Op::Crdt(_)))
+    {
+        // 快照文本不含 CRDT 字符标识，增量无法在其上定位；
+        // 该库无文档状态缓存，重放一次直接下发完整内容 (不再另算位置效果)
+        let full_entries = deve_core::ledger::ops::get_ops_from_db(db, doc_id)?;
+        let full_version = full_entries.last().map(|(seq, _)| *seq).unwrap_or(0);
+        let ops: Vec<LedgerEntry> = full_entries.into_iter().map(|(_, entry)| entry).collect();
+        let full_content = deve_core::state::reconstruct_content(&ops);
+        return Ok((full_content, full_version, Vec::new(), full_version));
+    }
     let mut version = base_seq;
     let mut delta_ops = Vec::new();
     for (seq, entry) in delta_entries {
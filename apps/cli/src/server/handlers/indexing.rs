@@ -0,0 +1,36 @@
+// apps/cli/src/server/handlers/indexing.rs
+//! # 索引通知 (Indexing Notifications)
+//!
+//! 向后台索引器 (属性索引、全文索引) 转发文档变更通知。
+//! 全文索引在 `search` 特性关闭时跳过，调用方无需条件编译。
+
+use crate::server::AppState;
+use deve_core::models::DocId;
+
+/// 文档内容或路径变化后通知索引器
+pub fn touch_index(state: &AppState, doc_id: DocId) {
+    state.meta_indexer.touch(doc_id);
+    #[cfg(feature = "search")]
+    if let Some(indexer) = &state.search_indexer {
+        indexer.touch(doc_id);
+    }
+}
+
+/// 目录级变更 (新建/重命名/删除/复制) 后让索引器对齐文档集合
+pub fn reconcile_index(state: &AppState) {
+    state.meta_indexer.reconcile();
+    #[cfg(feature = "search")]
+    if let Some(indexer) = &state.search_indexer {
+        indexer.reconcile();
+    }
+}
+
+/// 新提交后让全文索引收录历史版本
+pub fn archive_index(state: &AppState) {
+    #[cfg(feature = "search")]
+    if let Some(indexer) = &state.search_indexer {
+        indexer.archive();
+    }
+    #[cfg(not(feature = "search"))]
+    let _ = state;
+}
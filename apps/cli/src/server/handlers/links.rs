@@ -0,0 +1,133 @@
+// apps/cli/src/server/handlers/links.rs
+//! # 链接图处理器 (Links Handler)
+//!
+//! 反链查询、断链报告、知识图谱，以及重命名/移动后的入链改写。
+//!
+//! 改写流程: 重命名完成后 `offer_link_rewrite` 计算受影响的链接并发送
+//! `LinkRewriteOffer`；客户端确认后发送 `RewriteLinks`，由 `handle_rewrite_links`
+//! 重新计算计划并以普通编辑操作写入 Ledger 与 Vault。
+
+use crate::server::AppState;
+use crate::server::channel::DualChannel;
+use crate::server::handlers::indexing::touch_index;
+use crate::server::session::WsSession;
+use deve_core::models::{DocId, LedgerEntry};
+use deve_core::protocol::ServerMessage;
+use std::sync::Arc;
+
+pub async fn handle_get_backlinks(state: &Arc<AppState>, ch: &DualChannel, doc_id: DocId) {
+    match state.repo.get_backlinks(doc_id) {
+        Ok(links) => ch.unicast(ServerMessage::Backlinks { doc_id, links }),
+        Err(e) => ch.send_error(format!("Failed to get backlinks: {}", e)),
+    }
+}
+
+pub async fn handle_get_unresolved_links(state: &Arc<AppState>, ch: &DualChannel) {
+    match state.repo.unresolved_links() {
+        Ok(links) => ch.unicast(ServerMessage::UnresolvedLinks { links }),
+        Err(e) => ch.send_error(format!("Failed to list unresolved links: {}", e)),
+    }
+}
+
+pub async fn handle_get_graph(
+    state: &Arc<AppState>,
+    ch: &DualChannel,
+    root: Option<DocId>,
+    depth: u32,
+) {
+    match state.repo.get_graph(root, depth) {
+        Ok(graph) => ch.unicast(ServerMessage::Graph { root, graph }),
+        Err(e) => ch.send_error(format!("Failed to build graph: {}", e)),
+    }
+}
+
+/// 重命名/移动完成后，若存在需改写的链接则询问客户端
+pub fn offer_link_rewrite(state: &AppState, ch: &DualChannel, old_path: &str, new_path: &str) {
+    match state.repo.plan_link_rewrites(old_path, new_path) {
+        Ok(plans) if !plans.is_empty() => ch.unicast(ServerMessage::LinkRewriteOffer {
+            old_path: old_path.to_string(),
+            new_path: new_path.to_string(),
+            docs: plans.len() as u32,
+            links: plans.iter().map(|p| p.links as u32).sum(),
+        }),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Link rewrite planning failed: {:?}", e),
+    }
+}
+
+/// 执行入链改写
+///
+/// **只读模式处理**: 与编辑一致，静默忽略。
+pub async fn handle_rewrite_links(
+    state: &Arc<AppState>,
+    ch: &DualChannel,
+    session: &WsSession,
+    old_path: String,
+    new_path: String,
+) {
+    if session.is_readonly() {
+        tracing::debug!("RewriteLinks ignored: session is readonly (remote branch)");
+        return;
+    }
+    // 改写基于当前内容计算，期间不接受并发编辑
+    let _guard = state.repo.lock_edits();
+    let plans = match state.repo.plan_link_rewrites(&old_path, &new_path) {
+        Ok(plans) => plans,
+        Err(e) => {
+            ch.send_error(format!("Failed to plan link rewrite: {}", e));
+            return;
+        }
+    };
+
+    let peer_id = state.identity_key.peer_id();
+    let mut links = 0;
+    for plan in &plans {
+        let doc_id = plan.doc_id;
+        for op in deve_core::state::compute_diff(&plan.content, &plan.new_content) {
+            let entry_op = op.clone();
+            let entry_peer = peer_id.clone();
+            match state.sync_manager.apply_local_op(
+                doc_id,
+                peer_id.clone(),
+                move |seq| LedgerEntry {
+                    doc_id,
+                    op: entry_op.clone(),
+                    timestamp: chrono::Utc::now().timestamp_millis(),
+                    peer_id: entry_peer.clone(),
+                    seq,
+                },
+                false, // 每个文档改写完成后一次性持久化
+            ) {
+                // client_id 0: 服务端发起，所有编辑器都应用
+                Ok((seq, _)) => ch.broadcast(ServerMessage::NewOp {
+                    doc_id,
+                    op,
+                    seq,
+                    client_id: 0,
+                }),
+                Err(e) => {
+                    tracing::error!("Failed to apply link rewrite op: {:?}", e);
+                    ch.send_error(format!("Failed to rewrite links in {}: {}", plan.path, e));
+                    return;
+                }
+            }
+        }
+        if let Err(e) = state.sync_manager.persist_doc(doc_id) {
+            tracing::error!("Failed to persist link rewrite: {:?}", e);
+        }
+        touch_index(state, doc_id);
+        links += plan.links;
+    }
+
+    tracing::info!(
+        "Rewrote {} links in {} docs ({} -> {})",
+        links,
+        plans.len(),
+        old_path,
+        new_path
+    );
+    ch.unicast(ServerMessage::LinksRewritten {
+        docs: plans.len() as u32,
+        links: links as u32,
+    });
+}
@@ -7,6 +7,7 @@
 use crate::server::AppState;
 use crate::server::channel::DualChannel;
 use deve_core::config::SyncMode;
+use deve_core::plugin::events::PluginEvent;
 use deve_core::protocol::ServerMessage;
 use std::sync::Arc;
 
@@ -128,14 +129,35 @@ pub async fn handle_merge_peer(
 
     let pid = deve_core::models::PeerId::new(peer_id);
 
-    // 2. Perform Merge
+    // 2. CRDT 文档: 操作并集合并，无冲突
+    let crdt = super::document::publish_crdt_merge(state, ch, doc_id, || {
+        repo.merge_crdt_ops(&pid, &repo_id, doc_id)
+    });
+    match crdt {
+        Ok(Some(_)) => {
+            tracing::info!("CRDT merge for doc {}", doc_id);
+            state.events.emit(PluginEvent::PeerMerged {
+                peer_id: pid.to_string(),
+                doc_id,
+            });
+            ch.broadcast(ServerMessage::MergeComplete { merged_count: 1 });
+            return;
+        }
+        Ok(None) => {}
+        Err(e) => {
+            ch.send_error(format!("Merge failed: {}", e));
+            return;
+        }
+    }
+
+    // 3. Perform Merge
     let result = repo.merge_peer(&pid, &repo_id, doc_id);
 
     match result {
         Ok(merge_res) => {
             match merge_res {
                 deve_core::ledger::merge::MergeResult::Success(content) => {
-                    // 3. 成功: 写入文件系统
+                    // 4. 成功: 写入文件系统
                     if let Some(path_str) = repo.get_path_by_docid(doc_id).unwrap_or(None) {
                         let abs_path = state.vault_path.join(&path_str);
 
@@ -144,14 +166,20 @@ pub async fn handle_merge_peer(
                             return;
                         }
 
+                        // 合并结果也会经 Watcher 回流，这里提前通知索引器
+                        super::indexing::touch_index(state, doc_id);
                         tracing::info!("Merge Success for doc {} ({})", doc_id, path_str);
+                        state.events.emit(PluginEvent::PeerMerged {
+                            peer_id: pid.to_string(),
+                            doc_id,
+                        });
                         ch.broadcast(ServerMessage::MergeComplete { merged_count: 1 });
                     } else {
                         ch.send_error("Doc path not found for merged document".to_string());
                     }
                 }
                 deve_core::ledger::merge::MergeResult::Conflict { local, remote, .. } => {
-                    // 4. 冲突: 通知前端
+                    // 5. 冲突: 通知前端
                     tracing::warn!("Merge Conflict detected for doc {}", doc_id);
 
                     if let Some(path) = repo.get_path_by_docid(doc_id).unwrap_or(None) {
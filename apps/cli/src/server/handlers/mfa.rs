@@ -0,0 +1,56 @@
+// apps/cli/src/server/handlers/mfa.rs
+//! # 第二因素校验处理器
+//!
+//! 为敏感 WS 操作 (RequestKey, DeletePeer) 提供第二因素新鲜度检查，
+//! 并处理会话内的 `VerifyTotp` 重新校验。
+//!
+//! **Invariant**: 未启用 TOTP 时不拦截任何操作。
+
+use crate::server::AppState;
+use crate::server::channel::DualChannel;
+use crate::server::session::WsSession;
+use deve_core::protocol::ServerMessage;
+use std::sync::Arc;
+
+/// 检查会话是否满足敏感操作的第二因素要求
+///
+/// **Post-condition**: 不满足时单播 `MfaRequired` 并返回 false。
+pub fn ensure_recent_mfa(
+    state: &Arc<AppState>,
+    ch: &DualChannel,
+    session: &WsSession,
+    action: &str,
+) -> bool {
+    if !state.mfa.is_enabled() || session.has_recent_mfa(chrono::Utc::now().timestamp()) {
+        return true;
+    }
+    tracing::warn!("{} rejected: second factor required", action);
+    ch.unicast(ServerMessage::MfaRequired {
+        action: action.to_string(),
+    });
+    false
+}
+
+/// 处理会话内的 TOTP 重新校验
+///
+/// **Post-condition**: 单播 `MfaVerified`；连续失败达到上限后会话内不再接受校验。
+pub async fn handle_verify_totp(
+    state: &Arc<AppState>,
+    ch: &DualChannel,
+    session: &mut WsSession,
+    code: String,
+) {
+    if session.mfa_locked() {
+        tracing::warn!("VerifyTotp rejected: too many failures in session");
+        ch.unicast(ServerMessage::MfaVerified { success: false });
+        return;
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let success = state.mfa.verify(&code, now);
+    session.record_mfa(success, now);
+    if !success {
+        tracing::warn!("VerifyTotp failed ({} in session)", session.mfa_failures);
+    }
+    ch.unicast(ServerMessage::MfaVerified { success });
+}
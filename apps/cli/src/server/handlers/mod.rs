@@ -2,12 +2,17 @@
 //! 消息处理器模块
 //!
 //! 包含各类 ClientMessage 的处理逻辑，按功能领域划分。
+pub mod agent;
 pub mod docs;
 pub mod document;
+pub mod indexing;
 pub mod key_exchange;
+pub mod links;
 pub mod listing;
 pub mod merge;
+pub mod mfa;
 pub mod plugin;
+pub mod query;
 pub mod repo;
 pub mod search;
 pub mod source_control;
@@ -1,30 +1,48 @@
 // apps/cli/src/server/handlers/plugin.rs
 //! # 插件处理器 (Plugin Handler)
 //!
-//! 处理来自客户端的插件调用请求 (RPC)
+//! 处理来自客户端的插件调用请求 (RPC)，以及插件 UI 贡献与设置值的查询、保存。
 
 use crate::server::AppState;
 use crate::server::channel::DualChannel;
+use deve_core::mcp::McpManager;
+use deve_core::plugin::registry::PluginSet;
+use deve_core::plugin::runtime::cancel::{CancelScope, CancelToken};
 use deve_core::plugin::runtime::chat_stream::{ChatStreamScope, ChatStreamSink};
+use deve_core::plugin::ui::PluginInfo;
 use deve_core::protocol::ServerMessage;
 use std::sync::Arc;
-use tokio::task::block_in_place;
 
 /// 处理插件调用
 pub async fn handle_plugin_call(
     state: &Arc<AppState>,
     ch: &DualChannel,
+    cancel: CancelToken,
     req_id: String,
     plugin_id: String,
     fn_name: String,
     args: Vec<serde_json::Value>,
 ) {
-    handle_plugin_call_with_plugins(&state.plugins, ch, req_id, plugin_id, fn_name, args).await
+    handle_plugin_call_with_plugins(
+        &state.plugins.snapshot(),
+        ch,
+        cancel,
+        req_id,
+        plugin_id,
+        fn_name,
+        args,
+    )
+    .await
 }
 
+/// 在阻塞线程池中执行插件调用，立即返回
+///
+/// 调用结果以 PluginResponse 单播；WebSocket 处理循环不等待插件，
+/// 连接断开时 `cancel` 被触发，执行中的脚本在下一次进度检查时终止。
 pub async fn handle_plugin_call_with_plugins(
-    plugins: &[Box<dyn deve_core::plugin::runtime::PluginRuntime>],
+    plugins: &PluginSet,
     ch: &DualChannel,
+    cancel: CancelToken,
     req_id: String,
     plugin_id: String,
     fn_name: String,
@@ -36,20 +54,30 @@ pub async fn handle_plugin_call_with_plugins(
         return;
     }
 
-    let plugin = plugins.iter().find(|p| p.manifest().id == plugin_id);
+    let Some(index) = plugins.iter().position(|p| p.manifest().id == plugin_id) else {
+        ch.unicast(ServerMessage::PluginResponse {
+            req_id,
+            result: None,
+            error: Some(format!("Plugin '{}' not found", plugin_id)),
+        });
+        return;
+    };
 
-    if let Some(plugin) = plugin {
-        let rhai_args: Vec<rhai::Dynamic> = args
-            .into_iter()
-            .map(|v| rhai::serde::to_dynamic(&v).unwrap_or(rhai::Dynamic::UNIT))
-            .collect();
+    let rhai_args: Vec<rhai::Dynamic> = args
+        .into_iter()
+        .map(|v| rhai::serde::to_dynamic(&v).unwrap_or(rhai::Dynamic::UNIT))
+        .collect();
 
+    let plugins = plugins.clone();
+    let ch = ch.clone();
+    tokio::task::spawn_blocking(move || {
         let ch_for_stream = ch.clone();
         let stream_sink = ChatStreamSink::new(move |msg| ch_for_stream.unicast(msg));
-        let call_result = block_in_place(|| {
+        let call_result = {
             let _scope = ChatStreamScope::new(stream_sink);
-            plugin.call(&fn_name, rhai_args)
-        });
+            let _cancel = CancelScope::new(cancel);
+            plugins[index].call(&fn_name, rhai_args)
+        };
 
         match call_result {
             Ok(result) => {
@@ -69,11 +97,66 @@ pub async fn handle_plugin_call_with_plugins(
                 });
             }
         }
-    } else {
-        ch.unicast(ServerMessage::PluginResponse {
-            req_id,
-            result: None,
-            error: Some(format!("Plugin '{}' not found", plugin_id)),
-        });
+    });
+}
+
+/// 已加载插件的摘要 (清单 UI + 生效设置值)
+pub(crate) fn plugin_list(state: &AppState) -> Vec<PluginInfo> {
+    state
+        .plugins
+        .snapshot()
+        .iter()
+        .map(|p| {
+            let m = p.manifest();
+            PluginInfo {
+                id: m.id.clone(),
+                name: m.name.clone(),
+                version: m.version.clone(),
+                ui: m.ui.clone(),
+                settings: state.plugin_settings.effective(&m.id, &m.ui),
+            }
+        })
+        .collect()
+}
+
+/// 处理插件列表请求
+pub fn handle_list_plugins(state: &Arc<AppState>, ch: &DualChannel) {
+    ch.unicast(ServerMessage::PluginList {
+        plugins: plugin_list(state),
+    });
+}
+
+/// 当前 MCP 提示词与资源目录
+pub fn mcp_catalog(mcp: &McpManager) -> ServerMessage {
+    ServerMessage::McpCatalog {
+        prompts: mcp.list_all_prompts(),
+        resources: mcp.list_all_resources(),
+    }
+}
+
+/// 处理 MCP 目录请求
+pub fn handle_list_mcp_catalog(state: &Arc<AppState>, ch: &DualChannel) {
+    ch.unicast(mcp_catalog(&state.mcp));
+}
+
+/// 保存插件设置项，成功后向所有客户端广播新的插件列表
+pub fn handle_set_plugin_setting(
+    state: &Arc<AppState>,
+    ch: &DualChannel,
+    plugin_id: String,
+    key: String,
+    value: serde_json::Value,
+) {
+    let plugins = state.plugins.snapshot();
+    let Some(plugin) = plugins.iter().find(|p| p.manifest().id == plugin_id) else {
+        ch.send_error(format!("Plugin '{}' not found", plugin_id));
+        return;
+    };
+    let ui = &plugin.manifest().ui;
+    match state.plugin_settings.set(&plugin_id, ui, &key, value) {
+        Ok(()) => ch.broadcast(ServerMessage::PluginList {
+            plugins: plugin_list(state),
+        }),
+        Err(e) => ch.send_error(format!("Failed to save plugin setting: {}", e)),
     }
 }
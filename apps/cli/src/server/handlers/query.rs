@@ -0,0 +1,21 @@
+// apps/cli/src/server/handlers/query.rs
+//! # 属性查询处理器 (QueryDocs Handler)
+//!
+//! 基于本地库的属性/标签索引执行 `DocQuery`，结果单播给请求者。
+
+use crate::server::AppState;
+use crate::server::channel::DualChannel;
+use deve_core::protocol::{DocQuery, ServerMessage};
+use std::sync::Arc;
+
+pub async fn handle_query_docs(
+    state: &Arc<AppState>,
+    ch: &DualChannel,
+    req_id: String,
+    query: DocQuery,
+) {
+    match state.repo.query_docs(&query) {
+        Ok(rows) => ch.unicast(ServerMessage::QueryDocsResult { req_id, rows }),
+        Err(e) => ch.send_error(format!("Query failed: {}", e)),
+    }
+}
@@ -3,7 +3,7 @@
 
 use axum::Json;
 use axum::extract::{Query, State};
-use axum::http::StatusCode;
+use axum::http::{StatusCode, header};
 use axum::response::IntoResponse;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -15,7 +15,11 @@ use deve_core::ledger::listing::RepoListing;
 use deve_core::models::DocId;
 use deve_core::models::RepoType;
 use deve_core::plugin::runtime::host;
-use deve_core::state::reconstruct_content;
+
+#[derive(Deserialize)]
+pub struct AttachmentQuery {
+    pub path: String,
+}
 
 #[derive(Deserialize)]
 pub struct DocQuery {
@@ -51,12 +55,8 @@ pub async fn doc_content(
         Err(_) => return (StatusCode::BAD_REQUEST, "invalid doc_id").into_response(),
     };
     let doc_id = DocId(uuid);
-    match state.repo.get_local_ops(doc_id) {
-        Ok(ops) => {
-            let entries: Vec<_> = ops.into_iter().map(|(_, e)| e).collect();
-            let content = reconstruct_content(&entries);
-            content.into_response()
-        }
+    match state.repo.get_local_content(doc_id) {
+        Ok(content) => content.into_response(),
         Err(_) => (StatusCode::NOT_FOUND, "doc not found").into_response(),
     }
 }
@@ -78,3 +78,71 @@ pub async fn doc_content_plugin_host(
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
+
+/// 属性查询 (供插件宿主进程经 `RemoteSourceControlApi` 调用)
+pub async fn query_docs(
+    State(state): State<Arc<AppState>>,
+    Json(query): Json<deve_core::protocol::DocQuery>,
+) -> impl IntoResponse {
+    match state.repo.query_docs(&query) {
+        Ok(rows) => Json(rows).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+pub async fn query_docs_plugin_host(
+    State(_state): State<Arc<PluginHostState>>,
+    Json(query): Json<deve_core::protocol::DocQuery>,
+) -> impl IntoResponse {
+    match host::repository() {
+        Ok(repo) => match repo.query_docs(&query) {
+            Ok(rows) => Json(rows).into_response(),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        },
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 读取附件内容 (供预览中的图片链接使用)
+///
+/// SVG 等可执行内容通过 `Content-Security-Policy: sandbox` 隔离。
+pub async fn attachment(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<AttachmentQuery>,
+) -> impl IntoResponse {
+    match state.repo.read_attachment(&q.path) {
+        Ok(Some(bytes)) => (
+            [
+                (header::CONTENT_TYPE, attachment_mime(&q.path)),
+                (header::CONTENT_SECURITY_POLICY, "sandbox"),
+                (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+            ],
+            bytes,
+        )
+            .into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "attachment not found").into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+fn attachment_mime(path: &str) -> &'static str {
+    let ext = path
+        .rsplit_once('.')
+        .map(|(_, e)| e.to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        "pdf" => "application/pdf",
+        "txt" | "csv" => "text/plain; charset=utf-8",
+        "json" => "application/json",
+        "mp3" => "audio/mpeg",
+        "mp4" => "video/mp4",
+        _ => "application/octet-stream",
+    }
+}
@@ -1,32 +1,27 @@
-﻿// apps/cli/src/server/handlers/search.rs
+// apps/cli/src/server/handlers/search.rs
 //! # 搜索处理器 (Search Handler)
 //!
-//! 处理来自客户端的搜索请求
+//! 处理来自客户端的全文搜索请求。
+//! 查询包含 `in:history` / `in:peers` 时先同步历史提交与影子库版本 (无变化时开销很小)。
 
 use crate::server::AppState;
 use crate::server::channel::DualChannel;
+use deve_core::protocol::SearchHit;
 #[cfg(feature = "search")]
 use deve_core::protocol::ServerMessage;
 use std::sync::Arc;
 
 #[cfg(feature = "search")]
 pub async fn handle_search(state: &Arc<AppState>, ch: &DualChannel, query: String, limit: u32) {
-    if let Some(ref search_service) = state.search_service {
-        match search_service.search(&query, limit as usize) {
-            Ok(results) => {
-                let results: Vec<(String, String, f32)> = results
-                    .into_iter()
-                    .map(|r| (r.doc_id, r.path, r.score))
-                    .collect();
-                // 单播搜索结果给请求者
-                ch.unicast(ServerMessage::SearchResults { results });
-            }
-            Err(e) => {
-                ch.send_error(format!("Search failed: {}", e));
-            }
-        }
-    } else {
-        ch.send_error("Search feature not enabled".to_string());
+    // 归档同步会重放 Ledger 并写入 tantivy，不能阻塞 async 运行时
+    let state = state.clone();
+    let result = tokio::task::spawn_blocking(move || run_search(&state, &query, limit as usize))
+        .await
+        .unwrap_or_else(|e| Err(format!("Search task failed: {}", e)));
+    match result {
+        // 单播搜索结果给请求者
+        Ok(results) => ch.unicast(ServerMessage::SearchResults { results }),
+        Err(e) => ch.send_error(e),
     }
 }
 
@@ -34,3 +29,33 @@ pub async fn handle_search(state: &Arc<AppState>, ch: &DualChannel, query: Strin
 pub async fn handle_search(_state: &Arc<AppState>, ch: &DualChannel, _query: String, _limit: u32) {
     ch.send_error("Search feature not enabled".to_string());
 }
+
+/// 执行搜索 (WS 与 MCP 共用)，失败时返回面向客户端的错误信息
+///
+/// 同步阻塞：调用方须在 `spawn_blocking` 中执行。
+#[cfg(feature = "search")]
+pub fn run_search(state: &AppState, query: &str, limit: usize) -> Result<Vec<SearchHit>, String> {
+    use deve_core::search::{Scope, archive, query::parse};
+
+    let Some(ref search_service) = state.search_service else {
+        return Err("Search feature not enabled".to_string());
+    };
+    let archived = parse(query)
+        .map(|q| q.scopes().iter().any(|s| *s != Scope::Local))
+        .unwrap_or(false);
+    if archived && let Err(e) = archive::sync_archive(&state.repo, search_service) {
+        tracing::warn!("Search archive sync failed: {:?}", e);
+    }
+    search_service
+        .search(query, limit)
+        .map_err(|e| format!("Search failed: {}", e))
+}
+
+#[cfg(not(feature = "search"))]
+pub fn run_search(
+    _state: &AppState,
+    _query: &str,
+    _limit: usize,
+) -> Result<Vec<SearchHit>, String> {
+    Err("Search feature not enabled".to_string())
+}
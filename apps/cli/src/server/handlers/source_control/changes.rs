@@ -70,11 +70,8 @@ fn detect_unstaged_changes(state: &Arc<AppState>, session: &WsSession) -> Vec<Ch
             continue;
         }
 
-        let current = match state.repo.get_local_ops(doc_id) {
-            Ok(ops) => {
-                let entries: Vec<_> = ops.iter().map(|(_, e)| e.clone()).collect();
-                Some(deve_core::state::reconstruct_content(&entries))
-            }
+        let current = match state.repo.get_local_content(doc_id) {
+            Ok(content) => Some(content),
             Err(e) => {
                 tracing::error!("Failed to get local ops for {}: {:?}", path, e);
                 // On error, we shouldn't treat it as "empty/deleted", better to skip detecting change for this file
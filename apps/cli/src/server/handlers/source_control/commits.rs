@@ -1,16 +1,50 @@
 use crate::server::AppState;
 use crate::server::channel::DualChannel;
+use deve_core::plugin::events::{PluginEvent, Rejection};
 use deve_core::protocol::ServerMessage;
+use deve_core::source_control::CommitInfo;
 use std::sync::Arc;
 
+/// 提交前分发 `before_commit`，任一插件拒绝则返回其拒绝
+///
+/// 处理函数在阻塞线程池中同步执行，不占用异步工作线程。
+pub(crate) async fn check_commit(state: &Arc<AppState>, message: &str) -> Result<(), Rejection> {
+    let event = PluginEvent::BeforeCommit {
+        message: message.to_string(),
+    };
+    let events = state.events.clone();
+    let rejection = tokio::task::spawn_blocking(move || events.check(&event))
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("before_commit dispatch failed, allowing: {}", e);
+            None
+        });
+    match rejection {
+        Some(rejection) => Err(rejection),
+        None => Ok(()),
+    }
+}
+
+/// 提交成功后通知插件
+pub(crate) fn notify_committed(state: &Arc<AppState>, info: &CommitInfo) {
+    state.events.emit(PluginEvent::CommitCreated {
+        commit_id: info.id.clone(),
+        message: info.message.clone(),
+    });
+}
+
 /// 创建提交 (保存快照)
 pub async fn handle_commit(state: &Arc<AppState>, ch: &DualChannel, message: String) {
+    if let Err(rejection) = check_commit(state, &message).await {
+        tracing::info!("Commit vetoed: {}", rejection);
+        ch.send_error(rejection.to_string());
+        return;
+    }
+
     let get_content = |path: &str| -> Option<(deve_core::models::DocId, String)> {
         let normalized = deve_core::utils::path::to_forward_slash(path);
         let doc_id = state.repo.get_docid(&normalized).ok()??;
-        let ops = state.repo.get_local_ops(doc_id).ok()?;
-        let entries: Vec<_> = ops.iter().map(|(_, e)| e.clone()).collect();
-        let content = deve_core::state::reconstruct_content(&entries);
+        let content = state.repo.get_local_content(doc_id).ok()?;
         Some((doc_id, content))
     };
 
@@ -20,6 +54,8 @@ pub async fn handle_commit(state: &Arc<AppState>, ch: &DualChannel, message: Str
     {
         Ok(info) => {
             tracing::info!("Created commit: {} - {}", info.id, info.message);
+            crate::server::handlers::indexing::archive_index(state);
+            notify_committed(state, &info);
             // 广播提交成功 (其他标签页需要更新)
             ch.broadcast(ServerMessage::CommitAck {
                 commit_id: info.id,
@@ -1,6 +1,7 @@
 use crate::server::AppState;
 use crate::server::channel::DualChannel;
 use crate::server::session::WsSession;
+use deve_core::models::DocId;
 use deve_core::protocol::ServerMessage;
 use std::sync::Arc;
 
@@ -40,15 +41,7 @@ pub async fn handle_get_doc_diff(
         .flatten()
         .unwrap_or_default();
 
-    let new_content = state
-        .repo
-        .get_local_ops(doc_id)
-        .ok()
-        .map(|ops| {
-            let entries: Vec<_> = ops.iter().map(|(_, e)| e.clone()).collect();
-            deve_core::state::reconstruct_content(&entries)
-        })
-        .unwrap_or_default();
+    let new_content = state.repo.get_local_content(doc_id).unwrap_or_default();
 
     ch.unicast(ServerMessage::DocDiff {
         path,
@@ -57,6 +50,44 @@ pub async fn handle_get_doc_diff(
     });
 }
 
+/// 获取文档在指定提交时的版本 (左) 与当前版本 (右) 的 Diff
+///
+/// 用于历史搜索命中: 历史内容由 Ledger 重放到提交时的 `ledger_seq` 得到。
+pub async fn handle_get_historical_diff(
+    state: &Arc<AppState>,
+    ch: &DualChannel,
+    commit_id: String,
+    doc_id: DocId,
+) {
+    let commit = match state.repo.get_commit(&commit_id) {
+        Ok(Some(commit)) => commit,
+        Ok(None) => {
+            ch.send_error(format!("Commit not found: {}", commit_id));
+            return;
+        }
+        Err(e) => {
+            ch.send_error(e.to_string());
+            return;
+        }
+    };
+
+    let path = state
+        .repo
+        .get_path_by_docid(doc_id)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| doc_id.to_string());
+    let content_at = |seq| state.repo.get_content_at_seq(doc_id, seq);
+    match (content_at(commit.ledger_seq), content_at(u64::MAX)) {
+        (Ok(old_content), Ok(new_content)) => ch.unicast(ServerMessage::DocDiff {
+            path,
+            old_content,
+            new_content,
+        }),
+        (Err(e), _) | (_, Err(e)) => ch.send_error(e.to_string()),
+    }
+}
+
 /// Remote 分支的跨分支 Diff
 ///
 /// **左侧 (old)**: Local 分支对应文档 (需匹配 Repo URL)
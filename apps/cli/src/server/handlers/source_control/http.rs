@@ -95,8 +95,14 @@ pub async fn commit(
     State(state): State<Arc<AppState>>,
     Json(payload): Json<CommitPayload>,
 ) -> impl IntoResponse {
+    if let Err(rejection) = super::check_commit(&state, &payload.message).await {
+        return (StatusCode::BAD_REQUEST, rejection.to_string()).into_response();
+    }
     match state.repo.commit_staged(&payload.message) {
-        Ok(info) => Json::<CommitInfo>(info).into_response(),
+        Ok(info) => {
+            super::notify_committed(&state, &info);
+            Json::<CommitInfo>(info).into_response()
+        }
         Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
     }
 }
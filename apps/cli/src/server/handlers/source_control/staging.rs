@@ -1,6 +1,8 @@
 use crate::server::AppState;
 use crate::server::channel::DualChannel;
+use crate::server::handlers::document::append_op_locked;
 use crate::server::session::WsSession;
+use deve_core::models::{DocId, PeerId};
 use deve_core::protocol::ServerMessage;
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -106,51 +108,24 @@ pub async fn handle_discard_file(
         .flatten()
         .unwrap_or_default();
 
-    // 实际恢复逻辑:
+    // 实际恢复逻辑 (持编辑定序锁，与客户端编辑同路径写入并广播):
     // 1. 获取当前内容
     // 2. 计算差异 (current -> committed)
     // 3. 应用差异操作
-    let current_content = state
-        .repo
-        .get_local_ops(doc_id)
-        .ok()
-        .map(|ops| {
-            let entries: Vec<_> = ops.iter().map(|(_, e)| e.clone()).collect();
-            deve_core::state::reconstruct_content(&entries)
-        })
-        .unwrap_or_default();
-
-    if current_content == committed_content {
-        tracing::info!("Discard file: {} - already matches committed state", path);
-        ch.unicast(ServerMessage::DiscardAck { path: path.clone() });
-        super::changes::handle_get_changes(state, ch, session).await;
-        return;
-    }
-
-    // 计算差异并生成反向操作
-    let ops = deve_core::state::compute_diff(&current_content, &committed_content);
-
-    // 应用操作到 Ledger
-    for op in ops {
-        let peer_id = deve_core::models::PeerId::new("local");
-        // 使用 SyncManager 应用 Op，但不每次都持久化 (为了性能)
-        if let Err(e) = state.sync_manager.apply_local_op(
-            doc_id,
-            peer_id.clone(),
-            move |seq| deve_core::models::LedgerEntry {
-                doc_id,
-                peer_id: deve_core::models::PeerId::new("local"),
-                seq,
-                op: op.clone(),
-                timestamp: chrono::Utc::now().timestamp_millis(),
-            },
-            false, // 稍后一次性持久化
-        ) {
+    let current_content = match discard_locked(state, doc_id, &committed_content) {
+        Ok(Some(content)) => content,
+        Ok(None) => {
+            tracing::info!("Discard file: {} - already matches committed state", path);
+            ch.unicast(ServerMessage::DiscardAck { path: path.clone() });
+            super::changes::handle_get_changes(state, ch, session).await;
+            return;
+        }
+        Err(e) => {
             tracing::error!("Failed to apply discard op: {:?}", e);
             ch.send_error(format!("Failed to discard: {}", e));
             return;
         }
-    }
+    };
 
     // 统一持久化到 Vault
     if let Err(e) = state.sync_manager.persist_doc(doc_id) {
@@ -170,3 +145,21 @@ pub async fn handle_discard_file(
     // 刷新 Changes 列表
     super::changes::handle_get_changes(state, ch, session).await;
 }
+
+/// 在编辑定序锁内将文档改写为 `committed`，返回改写前的内容 (已一致时为 `None`)
+fn discard_locked(
+    state: &AppState,
+    doc_id: DocId,
+    committed: &str,
+) -> anyhow::Result<Option<String>> {
+    let _guard = state.repo.lock_edits();
+    let (current, mut version) = state.repo.get_local_state(doc_id)?;
+    if current == committed {
+        return Ok(None);
+    }
+    let peer_id = PeerId::new("local");
+    for op in deve_core::state::compute_diff(&current, committed) {
+        (version, _) = append_op_locked(state, &state.tx, doc_id, &peer_id, version, &op, 0)?;
+    }
+    Ok(Some(current))
+}
@@ -1,13 +1,15 @@
 // apps/cli/src/server/handlers/sync.rs
 //! # P2P 同步消息处理器
 //!
-//! 处理 P2P 同步相关的消息: SyncHello, SyncRequest, SyncPush
+//! 处理 P2P 同步相关的消息: SyncHello, SyncRequest, SyncPush, 附件清单与 Blob 分块
 
 #![allow(dead_code)] // P2P 同步功能尚未完全集成，预留接口
 
 use crate::server::AppState;
 use crate::server::channel::DualChannel;
-use deve_core::models::PeerId;
+use deve_core::config::SyncMode;
+use deve_core::models::{DocId, PeerId};
+use deve_core::plugin::events::PluginEvent;
 use deve_core::protocol::ServerMessage;
 use deve_core::sync::protocol as sync_proto;
 use std::sync::Arc;
@@ -92,6 +94,19 @@ pub async fn handle_sync_hello(
         let push_msg = ServerMessage::SyncPush { ops: ops_to_push };
         ch.unicast(push_msg);
     }
+
+    // 6. 发送附件清单 (对方据此请求缺失的 Blob)
+    match engine.attachment_manifest() {
+        Ok(entries) if !entries.is_empty() => {
+            ch.unicast(ServerMessage::SyncAttachmentManifest {
+                peer_id: engine.local_peer_id.clone(),
+                repo_id: super::get_repo_id(state),
+                entries,
+            });
+        }
+        Ok(_) => {}
+        Err(e) => tracing::error!("Failed to build attachment manifest: {:?}", e),
+    }
 }
 
 /// 处理数据请求 (对方想要数据)
@@ -132,6 +147,9 @@ pub async fn handle_sync_push(
     let mut engine = state.sync_engine.write().unwrap_or_else(|e| e.into_inner());
 
     let repo_id = super::get_repo_id(state);
+    let mut doc_ids: Vec<DocId> = ops.iter().map(|op| op.doc_id).collect();
+    doc_ids.sort_by_key(|id| id.as_u128());
+    doc_ids.dedup();
     let response = sync_proto::SyncResponse {
         peer_id: peer_id.clone(),
         repo_id,
@@ -146,6 +164,29 @@ pub async fn handle_sync_push(
             tracing::error!("Failed to apply ops from {}: {:?}", peer_id, e);
             // 使用单播发送错误给当前客户端
             ch.send_error(format!("Failed to apply sync ops from {}: {}", peer_id, e));
+            return;
+        }
+    }
+    let auto = engine.sync_mode() == SyncMode::Auto;
+    drop(engine);
+
+    // Auto 模式: CRDT 文档直接取操作并集合并 (基线不一致的文档留待手动合并)
+    if auto {
+        for doc_id in doc_ids {
+            let merged = super::document::publish_crdt_merge(state, ch, doc_id, || {
+                state.repo.merge_crdt_ops(&peer_id, &repo_id, doc_id)
+            });
+            match merged {
+                Ok(Some(count)) if count > 0 => {
+                    tracing::info!("Merged {} CRDT ops from {} into {}", count, peer_id, doc_id);
+                    state.events.emit(PluginEvent::PeerMerged {
+                        peer_id: peer_id.to_string(),
+                        doc_id,
+                    });
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("CRDT merge failed for {}: {:?}", doc_id, e),
+            }
         }
     }
 }
@@ -218,6 +259,77 @@ pub async fn handle_sync_push_snapshot(
     }
 }
 
+/// 处理附件清单 (对方持有的附件)，请求本地缺失的 Blob
+pub async fn handle_sync_attachment_manifest(
+    state: &Arc<AppState>,
+    ch: &DualChannel,
+    peer_id: PeerId,
+    repo_id: deve_core::models::RepoId,
+    entries: Vec<(String, String, u64)>,
+) {
+    let engine = state.sync_engine.read().unwrap_or_else(|e| e.into_inner());
+    tracing::info!(
+        "Handling AttachmentManifest from {} ({} entries)",
+        peer_id,
+        entries.len()
+    );
+
+    match engine.apply_attachment_manifest(&peer_id, &repo_id, entries) {
+        Ok(hashes) if !hashes.is_empty() => {
+            ch.unicast(ServerMessage::SyncBlobRequest { hashes });
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!(
+                "Failed to apply attachment manifest from {}: {:?}",
+                peer_id,
+                e
+            );
+            ch.send_error(format!("Failed to apply attachment manifest: {}", e));
+        }
+    }
+}
+
+/// 处理 Blob 请求: 逐块加密发送
+pub async fn handle_sync_blob_request(
+    state: &Arc<AppState>,
+    ch: &DualChannel,
+    hashes: Vec<String>,
+) {
+    let engine = state.sync_engine.read().unwrap_or_else(|e| e.into_inner());
+    for hash in hashes {
+        match engine.get_blob_chunks_for_sync(&hash) {
+            Ok(chunks) => {
+                for chunk in chunks {
+                    ch.unicast(ServerMessage::SyncBlobChunk {
+                        peer_id: engine.local_peer_id.clone(),
+                        chunk,
+                    });
+                }
+            }
+            Err(e) => tracing::warn!("Cannot send blob {}: {:?}", hash, e),
+        }
+    }
+}
+
+/// 处理 Blob 分块推送
+pub async fn handle_sync_blob_chunk(
+    state: &Arc<AppState>,
+    ch: &DualChannel,
+    peer_id: PeerId,
+    chunk: deve_core::security::EncryptedChunk,
+) {
+    let engine = state.sync_engine.read().unwrap_or_else(|e| e.into_inner());
+    match engine.apply_blob_chunk(&peer_id, &chunk) {
+        Ok(true) => tracing::info!("Received blob {} from {}", chunk.hash, peer_id),
+        Ok(false) => {}
+        Err(e) => {
+            tracing::error!("Failed to apply blob chunk from {}: {:?}", peer_id, e);
+            ch.send_error(format!("Failed to apply blob chunk: {}", e));
+        }
+    }
+}
+
 /// 处理删除 Peer 请求 (物理删除远端分支)
 pub async fn handle_delete_peer(state: &Arc<AppState>, ch: &DualChannel, peer_id_str: String) {
     let peer_id = PeerId::new(peer_id_str.clone());
@@ -1,12 +1,18 @@
 // apps/cli/src/server/mcp/http.rs
 //! # MCP HTTP Executor
+//!
+//! Streamable HTTP 传输: 响应可为 JSON 或 SSE 流，握手返回的
+//! `Mcp-Session-Id` 会附加到后续请求。流中的通知缓存到下次 `take_notifications`。
 
-use super::parse_tools;
-use super::protocol::{JsonRpcRequest, JsonRpcResponse};
+use super::protocol::{JsonRpcNotification, JsonRpcRequest, sort_message, sse_data};
 use anyhow::{Result, anyhow};
-use deve_core::mcp::{McpCallResult, McpExecutor, McpToolSpec};
+use deve_core::mcp::{McpExecutor, McpNotification};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const SESSION_HEADER: &str = "mcp-session-id";
 
 pub struct HttpExecutor {
     url: String,
@@ -15,6 +21,9 @@ pub struct HttpExecutor {
     timeout_ms: u64,
     retries: u32,
     backoff_ms: u64,
+    next_id: AtomicU64,
+    session: Mutex<Option<String>>,
+    notifications: Mutex<Vec<McpNotification>>,
 }
 
 impl HttpExecutor {
@@ -32,70 +41,120 @@ impl HttpExecutor {
             timeout_ms,
             retries,
             backoff_ms,
+            next_id: AtomicU64::new(1),
+            session: Mutex::new(None),
+            notifications: Mutex::new(Vec::new()),
         }
     }
 
-    fn call_rpc(&self, method: &str, params: Option<Value>) -> Result<Value> {
-        let req = JsonRpcRequest {
-            jsonrpc: "2.0".to_string(),
-            id: 1,
-            method: method.to_string(),
-            params,
-        };
-
+    /// 发送一条消息，返回响应状态、Content-Type 与正文
+    fn post(&self, body: &Value) -> Result<(reqwest::StatusCode, String, String)> {
         let mut attempt = 0;
         loop {
             attempt += 1;
-            let mut request = self.client.post(&self.url).json(&req);
+            let mut request = self
+                .client
+                .post(&self.url)
+                .header("accept", "application/json, text/event-stream")
+                .json(body);
+            if let Some(session) = self.session.lock().ok().and_then(|s| s.clone()) {
+                request = request.header(SESSION_HEADER, session);
+            }
             for (k, v) in &self.headers {
                 request = request.header(k, v);
             }
             let resp = tokio::runtime::Handle::current().block_on(async {
-                tokio::time::timeout(
-                    std::time::Duration::from_millis(self.timeout_ms),
-                    request.send(),
-                )
+                tokio::time::timeout(std::time::Duration::from_millis(self.timeout_ms), async {
+                    let resp = request.send().await?;
+                    let status = resp.status();
+                    let session = resp
+                        .headers()
+                        .get(SESSION_HEADER)
+                        .and_then(|v| v.to_str().ok())
+                        .map(str::to_string);
+                    let content_type = resp
+                        .headers()
+                        .get(reqwest::header::CONTENT_TYPE)
+                        .and_then(|v| v.to_str().ok())
+                        .unwrap_or_default()
+                        .to_string();
+                    let text = resp.text().await?;
+                    Ok::<_, reqwest::Error>((status, session, content_type, text))
+                })
                 .await
             });
 
-            let resp = match resp {
-                Ok(Ok(r)) => r,
-                Ok(Err(e)) => {
-                    if attempt <= self.retries {
-                        std::thread::sleep(std::time::Duration::from_millis(self.backoff_ms));
-                        continue;
+            let error = match resp {
+                Ok(Ok((status, session, content_type, text))) => {
+                    if let Some(session) = session
+                        && let Ok(mut current) = self.session.lock()
+                    {
+                        *current = Some(session);
                     }
-                    return Err(anyhow!("MCP http error: {}", e));
-                }
-                Err(_) => {
-                    if attempt <= self.retries {
-                        std::thread::sleep(std::time::Duration::from_millis(self.backoff_ms));
-                        continue;
-                    }
-                    return Err(anyhow!("MCP http timeout"));
+                    return Ok((status, content_type, text));
                 }
+                Ok(Err(e)) => anyhow!("MCP http error: {}", e),
+                Err(_) => anyhow!("MCP http timeout"),
             };
+            if attempt <= self.retries {
+                std::thread::sleep(std::time::Duration::from_millis(self.backoff_ms));
+                continue;
+            }
+            return Err(error);
+        }
+    }
 
-            let val: JsonRpcResponse =
-                tokio::runtime::Handle::current().block_on(async { resp.json().await })?;
+    fn call_rpc(&self, method: &str, params: Option<Value>) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let req = serde_json::to_value(JsonRpcRequest::new(id, method, params))?;
+        let (status, content_type, text) = self.post(&req)?;
+        if !status.is_success() {
+            return Err(anyhow!("MCP http status {}", status));
+        }
+
+        let messages: Vec<Value> = if content_type.starts_with("text/event-stream") {
+            sse_data(&text)
+                .iter()
+                .filter_map(|data| serde_json::from_str(data).ok())
+                .collect()
+        } else {
+            vec![serde_json::from_str(&text)?]
+        };
 
-            if let Some(err) = val.error {
-                return Err(anyhow!("MCP error: {}", err));
+        let mut notes = Vec::new();
+        let mut response = None;
+        for msg in messages {
+            if let Some(resp) = sort_message(msg, id, &mut notes) {
+                response = Some(resp);
             }
-            return val.result.ok_or_else(|| anyhow!("Missing MCP result"));
         }
+        if let Ok(mut pending) = self.notifications.lock() {
+            pending.extend(notes);
+        }
+        response
+            .ok_or_else(|| anyhow!("Missing MCP response"))?
+            .into_result()
     }
 }
 
 impl McpExecutor for HttpExecutor {
-    fn list_tools(&self) -> Result<Vec<McpToolSpec>> {
-        let result = self.call_rpc("tools/list", None)?;
-        parse_tools(result)
+    fn request(&self, method: &str, params: Option<Value>) -> Result<Value> {
+        self.call_rpc(method, params)
+    }
+
+    fn notify(&self, method: &str, params: Option<Value>) -> Result<()> {
+        let note = serde_json::to_value(JsonRpcNotification::new(method, params))?;
+        let (status, _, _) = self.post(&note)?;
+        if !status.is_success() {
+            return Err(anyhow!("MCP http status {}", status));
+        }
+        Ok(())
     }
 
-    fn call_tool(&self, name: &str, args: Value) -> Result<McpCallResult> {
-        let params = serde_json::json!({ "name": name, "arguments": args });
-        let result = self.call_rpc("tools/call", Some(params))?;
-        Ok(McpCallResult { content: result })
+    fn take_notifications(&self) -> Vec<McpNotification> {
+        self.notifications
+            .lock()
+            .map(|mut pending| std::mem::take(&mut *pending))
+            .unwrap_or_default()
     }
 }
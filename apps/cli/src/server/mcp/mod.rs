@@ -2,18 +2,26 @@
 //! # MCP Client Executors (CLI)
 //!
 //! 轻量实现：
-//! - Local: stdio JSON-RPC
-//! - Remote: HTTP JSON-RPC
+//! - Local: stdio JSON-RPC (受监管的常驻子进程)
+//! - Remote: Streamable HTTP JSON-RPC
+//! - RemoteSse: SSE JSON-RPC
+//!
+//! 注册后立即握手并拉取目录；之后的刷新由 `McpManager` 按通知驱动，
+//! `spawn_supervisor` 定期处理通知并做健康检查。
 
 mod http;
 mod protocol;
 mod sse;
 mod stdio;
 
-use anyhow::{Result, anyhow};
-use deve_core::mcp::{McpExecutor, McpManager, McpServerConfig, McpServerStatus, McpToolSpec};
-use serde_json::Value;
-use std::sync::Arc;
+use deve_core::mcp::{McpExecutor, McpManager, McpServerConfig};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+/// 监管循环的节拍 (处理服务器主动通知)
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// 每隔多少个节拍做一次健康检查
+const HEALTH_EVERY_TICKS: u32 = 10;
 
 pub fn register_mcp_servers(manager: &mut McpManager, configs: Vec<McpServerConfig>) {
     for cfg in configs {
@@ -24,12 +32,14 @@ pub fn register_mcp_servers(manager: &mut McpManager, configs: Vec<McpServerConf
             McpServerConfig::Local {
                 command, args, env, ..
             } => Arc::new(stdio::StdioExecutor::new(
+                name.clone(),
                 command.clone(),
                 args.clone(),
                 env.clone(),
                 cfg.timeout_ms(8000),
                 retries,
                 backoff_ms,
+                cfg.max_in_flight(4),
             )),
             McpServerConfig::Remote { url, headers, .. } => Arc::new(http::HttpExecutor::new(
                 url.clone(),
@@ -48,52 +58,39 @@ pub fn register_mcp_servers(manager: &mut McpManager, configs: Vec<McpServerConf
         };
 
         manager.register_server(cfg);
-        manager.register_executor(&name, exec.clone());
+        manager.register_executor(&name, exec);
+    }
 
-        match exec.list_tools() {
-            Ok(tools) => {
-                manager.set_tools(&name, tools);
-                manager.set_status(&name, McpServerStatus::Connected);
-            }
-            Err(err) => {
-                manager.set_status(
-                    &name,
-                    McpServerStatus::Failed {
-                        reason: err.to_string(),
-                    },
-                );
-                tracing::warn!("MCP list_tools failed for {}: {:?}", name, err);
-            }
+    // 握手失败或工具列表失败时状态记为 Failed，不影响其他服务器
+    for name in manager.list_servers() {
+        if let Err(err) = manager.connect(&name) {
+            tracing::warn!("MCP connect failed for {}: {:?}", name, err);
         }
     }
 }
 
-pub(crate) fn parse_tools(value: Value) -> Result<Vec<McpToolSpec>> {
-    let tools_val = value.get("tools").cloned().unwrap_or(value);
-    let arr = tools_val
-        .as_array()
-        .ok_or_else(|| anyhow!("Invalid MCP tools"))?;
-
-    let mut tools = Vec::new();
-    for t in arr {
-        let name = t.get("name").and_then(|v| v.as_str()).unwrap_or("");
-        if name.is_empty() {
-            continue;
+/// 启动监管线程: 定期处理通知并做健康检查，管理器释放后退出
+///
+/// 须在 Tokio 运行时内调用 (HTTP 传输依赖当前运行时句柄)。
+pub fn spawn_supervisor(manager: Weak<McpManager>) {
+    let runtime = tokio::runtime::Handle::current();
+    std::thread::spawn(move || {
+        let _guard = runtime.enter();
+        let mut tick: u32 = 0;
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let Some(manager) = manager.upgrade() else {
+                break;
+            };
+            tick = tick.wrapping_add(1);
+            let check = tick.is_multiple_of(HEALTH_EVERY_TICKS);
+            for name in manager.list_servers() {
+                if !check {
+                    manager.poll_notifications(&name);
+                } else if let Err(err) = manager.check_health(&name) {
+                    tracing::debug!("MCP health check failed for {}: {}", name, err);
+                }
+            }
         }
-        let desc = t
-            .get("description")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
-        let schema = t
-            .get("inputSchema")
-            .or_else(|| t.get("input_schema"))
-            .cloned()
-            .unwrap_or_else(|| serde_json::json!({"type":"object"}));
-        tools.push(McpToolSpec {
-            name: name.to_string(),
-            description: desc,
-            input_schema: schema,
-        });
-    }
-    Ok(tools)
+    });
 }
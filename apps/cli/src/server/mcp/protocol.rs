@@ -1,6 +1,8 @@
 // apps/cli/src/server/mcp/protocol.rs
 //! # JSON-RPC 2.0 Protocol Types
 
+use anyhow::{Result, anyhow};
+use deve_core::mcp::McpNotification;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -13,8 +15,129 @@ pub struct JsonRpcRequest {
     pub params: Option<Value>,
 }
 
+impl JsonRpcRequest {
+    pub fn new(id: u64, method: &str, params: Option<Value>) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id,
+            method: method.to_string(),
+            params,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonRpcNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+impl JsonRpcNotification {
+    pub fn new(method: &str, params: Option<Value>) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct JsonRpcResponse {
+    #[serde(default)]
+    pub id: Option<Value>,
     pub result: Option<Value>,
     pub error: Option<Value>,
 }
+
+impl JsonRpcResponse {
+    pub fn into_result(self) -> Result<Value> {
+        if let Some(err) = self.error {
+            return Err(anyhow!("MCP error: {}", err));
+        }
+        self.result.ok_or_else(|| anyhow!("Missing MCP result"))
+    }
+}
+
+/// 分拣服务器发来的消息 (可为批量): 通知收集到 `notes`，返回 id 匹配的响应
+///
+/// 服务器发起的请求 (如 `ping`、`roots/list`) 当前不处理，直接忽略。
+pub fn sort_message(
+    msg: Value,
+    id: u64,
+    notes: &mut Vec<McpNotification>,
+) -> Option<JsonRpcResponse> {
+    if let Value::Array(batch) = msg {
+        let mut found = None;
+        for item in batch {
+            if let Some(resp) = sort_message(item, id, notes) {
+                found = Some(resp);
+            }
+        }
+        return found;
+    }
+    if let Some(note) = McpNotification::from_message(&msg) {
+        notes.push(note);
+        return None;
+    }
+    if msg.get("method").is_some() {
+        return None;
+    }
+    let resp: JsonRpcResponse = serde_json::from_value(msg).ok()?;
+    (resp.id.as_ref().and_then(Value::as_u64) == Some(id)).then_some(resp)
+}
+
+/// 提取 SSE 流中各事件的 `data` 负载 (多行 data 以换行拼接)
+pub fn sse_data(body: &str) -> Vec<String> {
+    let mut events = Vec::new();
+    let mut current: Option<String> = None;
+    for line in body.lines() {
+        if line.is_empty() {
+            events.extend(current.take());
+        } else if let Some(data) = line.strip_prefix("data:") {
+            let data = data.strip_prefix(' ').unwrap_or(data);
+            match current.as_mut() {
+                Some(buf) => {
+                    buf.push('\n');
+                    buf.push_str(data);
+                }
+                None => current = Some(data.to_string()),
+            }
+        }
+    }
+    events.extend(current);
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sort_message_and_sse_data() {
+        let body = "event: message\ndata: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/tools/list_changed\"}\n\n\
+                    data: {\"jsonrpc\":\"2.0\",\"id\":7,\n\
+                    data: \"result\":{}}\n\n";
+        let events = sse_data(body);
+        assert_eq!(events.len(), 2);
+
+        let mut notes = Vec::new();
+        let mut found = None;
+        for data in events {
+            let msg: Value = serde_json::from_str(&data).unwrap();
+            found = found.or(sort_message(msg, 7, &mut notes));
+        }
+        assert_eq!(notes[0].method, "notifications/tools/list_changed");
+        assert_eq!(found.unwrap().into_result().unwrap(), json!({}));
+
+        let batch = json!([
+            { "jsonrpc": "2.0", "id": 1, "result": "other" },
+            { "jsonrpc": "2.0", "id": 2, "method": "ping" }
+        ]);
+        assert!(sort_message(batch, 7, &mut notes).is_none());
+        assert_eq!(notes.len(), 1);
+    }
+}
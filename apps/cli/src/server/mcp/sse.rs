@@ -1,14 +1,17 @@
 // apps/cli/src/server/mcp/sse.rs
 //! # MCP SSE Executor
+//!
+//! 响应前到达的通知事件缓存到下次 `take_notifications`。
 
-use super::parse_tools;
-use super::protocol::{JsonRpcRequest, JsonRpcResponse};
+use super::protocol::{JsonRpcRequest, sort_message};
 use anyhow::{Result, anyhow};
-use deve_core::mcp::{McpCallResult, McpExecutor, McpToolSpec};
+use deve_core::mcp::{McpExecutor, McpNotification};
 use futures::StreamExt;
 use reqwest_eventsource::{Event, EventSource};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 pub struct SseExecutor {
     url: String,
@@ -17,6 +20,8 @@ pub struct SseExecutor {
     timeout_ms: u64,
     retries: u32,
     backoff_ms: u64,
+    next_id: AtomicU64,
+    notifications: Mutex<Vec<McpNotification>>,
 }
 
 impl SseExecutor {
@@ -34,16 +39,14 @@ impl SseExecutor {
             timeout_ms,
             retries,
             backoff_ms,
+            next_id: AtomicU64::new(1),
+            notifications: Mutex::new(Vec::new()),
         }
     }
 
     fn call_rpc(&self, method: &str, params: Option<Value>) -> Result<Value> {
-        let req = JsonRpcRequest {
-            jsonrpc: "2.0".to_string(),
-            id: 1,
-            method: method.to_string(),
-            params,
-        };
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let req = JsonRpcRequest::new(id, method, params);
 
         let mut attempt = 0;
         loop {
@@ -66,12 +69,17 @@ impl SseExecutor {
                         msg = es.next() => {
                             match msg {
                                 Some(Ok(Event::Message(m))) => {
-                                    let resp: JsonRpcResponse = serde_json::from_str(&m.data)
+                                    let msg: Value = serde_json::from_str(&m.data)
                                         .map_err(|e| anyhow!("MCP SSE parse error: {}", e))?;
-                                    if let Some(err) = resp.error {
-                                        return Err(anyhow!("MCP error: {}", err));
+                                    let mut notes = Vec::new();
+                                    let resp = sort_message(msg, id, &mut notes);
+                                    if let Ok(mut pending) = self.notifications.lock() {
+                                        pending.extend(notes);
+                                    }
+                                    if let Some(resp) = resp {
+                                        es.close();
+                                        return resp.into_result();
                                     }
-                                    return resp.result.ok_or_else(|| anyhow!("Missing MCP result"));
                                 }
                                 Some(Ok(_)) => continue,
                                 Some(Err(e)) => return Err(anyhow!("MCP SSE error: {}", e)),
@@ -97,14 +105,14 @@ impl SseExecutor {
 }
 
 impl McpExecutor for SseExecutor {
-    fn list_tools(&self) -> Result<Vec<McpToolSpec>> {
-        let result = self.call_rpc("tools/list", None)?;
-        parse_tools(result)
+    fn request(&self, method: &str, params: Option<Value>) -> Result<Value> {
+        self.call_rpc(method, params)
     }
 
-    fn call_tool(&self, name: &str, args: Value) -> Result<McpCallResult> {
-        let params = serde_json::json!({ "name": name, "arguments": args });
-        let result = self.call_rpc("tools/call", Some(params))?;
-        Ok(McpCallResult { content: result })
+    fn take_notifications(&self) -> Vec<McpNotification> {
+        self.notifications
+            .lock()
+            .map(|mut pending| std::mem::take(&mut *pending))
+            .unwrap_or_default()
     }
 }
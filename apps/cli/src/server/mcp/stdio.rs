@@ -1,113 +1,465 @@
 // apps/cli/src/server/mcp/stdio.rs
 //! # MCP Stdio Executor
+//!
+//! 本地服务器作为受监管的常驻子进程运行:
+//! - 启动时完成一次 `initialize` 握手，之后所有请求复用同一会话
+//! - 读线程按 id 分发响应，通知缓存到下次 `take_notifications`，并应答服务器的 `ping`
+//! - 进程退出后在下次请求或健康检查时重启，连续失败按指数退避
+//! - 在途请求数受 `max_in_flight` 限制，超出时排队等待
+//!
+//! ## Invariants
+//! - 会话对象被替换或执行器销毁时结束对应子进程
+//! - 进程运行超过 `STABLE_AFTER` 后退出不计入连续失败
 
-use super::parse_tools;
-use super::protocol::{JsonRpcRequest, JsonRpcResponse};
-use anyhow::{Result, anyhow};
-use deve_core::mcp::{McpCallResult, McpExecutor, McpToolSpec};
-use serde_json::Value;
+use super::protocol::{JsonRpcNotification, JsonRpcRequest, JsonRpcResponse};
+use anyhow::{Result, anyhow, bail};
+use deve_core::mcp::{McpExecutor, McpNotification, McpServerCapabilities, initialize_params};
+use deve_core::protocol::McpSessionInfo;
+use serde_json::{Value, json};
 use std::collections::HashMap;
-use std::io::{Read, Write};
-use std::process::{Command, Stdio};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// 重启退避上限
+const MAX_BACKOFF_MS: u64 = 30_000;
+/// 进程稳定运行该时长后，退出不再累计连续失败
+const STABLE_AFTER: Duration = Duration::from_secs(30);
+
+type Pending = Arc<Mutex<HashMap<u64, mpsc::Sender<JsonRpcResponse>>>>;
 
 pub struct StdioExecutor {
+    name: String,
     command: String,
     args: Vec<String>,
     env: HashMap<String, String>,
     timeout_ms: u64,
     retries: u32,
     backoff_ms: u64,
+    max_in_flight: usize,
+    supervisor: Mutex<Supervisor>,
+    in_flight: Mutex<usize>,
+    slot_freed: Condvar,
+    next_id: AtomicU64,
+    restarts: AtomicU32,
+    notifications: Arc<Mutex<Vec<McpNotification>>>,
+}
+
+/// 进程监管状态
+#[derive(Default)]
+struct Supervisor {
+    session: Option<Arc<Session>>,
+    started_once: bool,
+    /// 连续失败次数 (启动失败或短时间内退出)
+    failures: u32,
+    retry_at: Option<Instant>,
+    last_error: Option<String>,
+}
+
+/// 一个已完成握手的子进程
+struct Session {
+    child: Mutex<Child>,
+    stdin: Arc<Mutex<ChildStdin>>,
+    pending: Pending,
+    alive: Arc<AtomicBool>,
+    caps: McpServerCapabilities,
+    started: Instant,
+    pid: u32,
+}
+
+/// 在途请求名额 (释放时唤醒排队者)
+struct Slot<'a> {
+    exec: &'a StdioExecutor,
+}
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        *lock(&self.exec.in_flight) -= 1;
+        self.exec.slot_freed.notify_one();
+    }
 }
 
 impl StdioExecutor {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
+        name: String,
         command: String,
         args: Vec<String>,
         env: HashMap<String, String>,
         timeout_ms: u64,
         retries: u32,
         backoff_ms: u64,
+        max_in_flight: usize,
     ) -> Self {
         Self {
+            name,
             command,
             args,
             env,
             timeout_ms,
             retries,
             backoff_ms,
+            max_in_flight: max_in_flight.max(1),
+            supervisor: Mutex::new(Supervisor::default()),
+            in_flight: Mutex::new(0),
+            slot_freed: Condvar::new(),
+            next_id: AtomicU64::new(1),
+            restarts: AtomicU32::new(0),
+            notifications: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
-    fn call_rpc(&self, method: &str, params: Option<Value>) -> Result<Value> {
-        let req = JsonRpcRequest {
-            jsonrpc: "2.0".to_string(),
-            id: 1,
-            method: method.to_string(),
-            params,
-        };
-        let payload = serde_json::to_string(&req)? + "\n";
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
 
-        let mut attempt = 0;
-        'retry: loop {
-            attempt += 1;
-            let mut cmd = Command::new(&self.command);
-            cmd.args(&self.args)
-                .stdin(Stdio::piped())
-                .stdout(Stdio::piped());
-            for (k, v) in &self.env {
-                cmd.env(k, v);
+    /// 第 `failures` 次连续失败后的重启等待时间
+    fn backoff(&self, failures: u32) -> Duration {
+        let exp = failures.saturating_sub(1).min(16);
+        let ms = self.backoff_ms.saturating_mul(1 << exp).min(MAX_BACKOFF_MS);
+        Duration::from_millis(ms)
+    }
+
+    /// 返回存活的会话；进程已退出且退避期已过时重启
+    fn session(&self) -> Result<Arc<Session>> {
+        let mut sup = lock(&self.supervisor);
+        if let Some(session) = sup.session.take() {
+            if session.alive() {
+                if session.started.elapsed() >= STABLE_AFTER {
+                    sup.failures = 0;
+                }
+                sup.session = Some(session.clone());
+                return Ok(session);
             }
+            let reason = session.exit_reason();
+            tracing::warn!("MCP server {} exited: {}", self.name, reason);
+            sup.failures = if session.started.elapsed() >= STABLE_AFTER {
+                1
+            } else {
+                sup.failures + 1
+            };
+            sup.retry_at = Some(Instant::now() + self.backoff(sup.failures));
+            sup.last_error = Some(reason);
+        }
 
-            let mut child = cmd.spawn()?;
-            if let Some(stdin) = child.stdin.as_mut() {
-                stdin.write_all(payload.as_bytes())?;
+        if let Some(at) = sup.retry_at {
+            let wait = at.saturating_duration_since(Instant::now());
+            if !wait.is_zero() {
+                bail!(
+                    "MCP server {} restarting in {} ms (attempt {}): {}",
+                    self.name,
+                    wait.as_millis(),
+                    sup.failures,
+                    sup.last_error.as_deref().unwrap_or("unknown error")
+                );
             }
+        }
+
+        match self.spawn() {
+            Ok(session) => {
+                let session = Arc::new(session);
+                if sup.started_once {
+                    self.restarts.fetch_add(1, Ordering::Relaxed);
+                    tracing::info!("MCP server {} restarted (pid {})", self.name, session.pid);
+                }
+                sup.started_once = true;
+                sup.retry_at = None;
+                sup.session = Some(session.clone());
+                Ok(session)
+            }
+            Err(e) => {
+                sup.failures += 1;
+                sup.retry_at = Some(Instant::now() + self.backoff(sup.failures));
+                sup.last_error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
 
-            let start = std::time::Instant::now();
-            loop {
-                if let Some(_status) = child.try_wait()? {
-                    break;
+    /// 启动子进程并完成握手
+    fn spawn(&self) -> Result<Session> {
+        let mut cmd = Command::new(&self.command);
+        cmd.args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        for (k, v) in &self.env {
+            cmd.env(k, v);
+        }
+        let mut child = cmd.spawn()?;
+        let pid = child.id();
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("MCP stdio: no stdin"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("MCP stdio: no stdout"))?;
+        if let Some(stderr) = child.stderr.take() {
+            let name = self.name.clone();
+            std::thread::spawn(move || {
+                for line in BufReader::new(stderr).lines() {
+                    let Ok(line) = line else { break };
+                    tracing::debug!("MCP[{}] stderr: {}", name, line);
                 }
-                if start.elapsed().as_millis() as u64 > self.timeout_ms {
-                    let _ = child.kill();
-                    if attempt <= self.retries {
-                        std::thread::sleep(std::time::Duration::from_millis(self.backoff_ms));
-                        continue 'retry;
-                    }
-                    return Err(anyhow!("MCP stdio timeout"));
+            });
+        }
+
+        let stdin = Arc::new(Mutex::new(stdin));
+        let pending: Pending = Arc::default();
+        let alive = Arc::new(AtomicBool::new(true));
+        spawn_reader(
+            stdout,
+            stdin.clone(),
+            pending.clone(),
+            alive.clone(),
+            self.notifications.clone(),
+        );
+
+        let mut session = Session {
+            child: Mutex::new(child),
+            stdin,
+            pending,
+            alive,
+            caps: McpServerCapabilities::default(),
+            started: Instant::now(),
+            pid,
+        };
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let result = session.call(id, "initialize", Some(initialize_params()), self.timeout())?;
+        session.notify("notifications/initialized", None)?;
+        session.caps = McpServerCapabilities::from_initialize(&result);
+        Ok(session)
+    }
+
+    /// 占用一个在途名额，满额时最多等待一个超时周期
+    fn acquire_slot(&self) -> Result<Slot<'_>> {
+        let deadline = Instant::now() + self.timeout();
+        let mut count = lock(&self.in_flight);
+        while *count >= self.max_in_flight {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                bail!(
+                    "MCP server {} busy: {} requests in flight",
+                    self.name,
+                    self.max_in_flight
+                );
+            }
+            count = self
+                .slot_freed
+                .wait_timeout(count, remaining)
+                .map(|(guard, _)| guard)
+                .unwrap_or_else(|e| e.into_inner().0);
+        }
+        *count += 1;
+        Ok(Slot { exec: self })
+    }
+
+    fn call_rpc(&self, method: &str, params: Option<Value>) -> Result<Value> {
+        let _slot = self.acquire_slot()?;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let session = self.session()?;
+            let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+            match session.call(id, method, params.clone(), self.timeout()) {
+                // 进程在请求途中退出: 退避后在新进程上重试
+                Err(_) if !session.alive() && attempt <= self.retries => {
+                    std::thread::sleep(self.backoff(1));
                 }
-                std::thread::sleep(std::time::Duration::from_millis(10));
+                result => return result,
             }
+        }
+    }
+}
 
-            let mut output = String::new();
-            if let Some(mut stdout) = child.stdout.take() {
-                stdout.read_to_string(&mut output)?;
+impl Session {
+    fn alive(&self) -> bool {
+        self.alive.load(Ordering::Acquire)
+    }
+
+    fn call(
+        &self,
+        id: u64,
+        method: &str,
+        params: Option<Value>,
+        timeout: Duration,
+    ) -> Result<Value> {
+        let (tx, rx) = mpsc::channel();
+        lock(&self.pending).insert(id, tx);
+        if !self.alive() {
+            lock(&self.pending).remove(&id);
+            bail!("MCP server exited before {}", method);
+        }
+        let request = JsonRpcRequest::new(id, method, params);
+        if let Err(e) = self.write(&serde_json::to_string(&request)?) {
+            lock(&self.pending).remove(&id);
+            self.kill();
+            return Err(e);
+        }
+        match rx.recv_timeout(timeout) {
+            Ok(resp) => resp.into_result(),
+            Err(RecvTimeoutError::Timeout) => {
+                lock(&self.pending).remove(&id);
+                Err(anyhow!("MCP stdio timeout: {}", method))
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                Err(anyhow!("MCP server exited before responding to {}", method))
             }
+        }
+    }
+
+    fn notify(&self, method: &str, params: Option<Value>) -> Result<()> {
+        let note = JsonRpcNotification::new(method, params);
+        self.write(&serde_json::to_string(&note)?)
+    }
+
+    fn write(&self, line: &str) -> Result<()> {
+        let mut stdin = lock(&self.stdin);
+        writeln!(stdin, "{}", line)?;
+        stdin.flush()?;
+        Ok(())
+    }
 
-            let line = output
-                .lines()
-                .rev()
-                .find(|l| !l.trim().is_empty())
-                .ok_or_else(|| anyhow!("Empty MCP response"))?;
+    /// 结束无响应的进程 (读线程随后标记会话失效)
+    fn kill(&self) {
+        self.alive.store(false, Ordering::Release);
+        let _ = lock(&self.child).kill();
+    }
 
-            let resp: JsonRpcResponse = serde_json::from_str(line)?;
-            if let Some(err) = resp.error {
-                return Err(anyhow!("MCP error: {}", err));
+    fn exit_reason(&self) -> String {
+        match lock(&self.child).try_wait() {
+            Ok(Some(status)) => format!("process exited ({})", status),
+            _ => "process stopped responding".to_string(),
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        let mut child = lock(&self.child);
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+/// 读线程: 分发响应、收集通知、应答服务器请求；EOF 时标记会话失效
+fn spawn_reader(
+    stdout: std::process::ChildStdout,
+    stdin: Arc<Mutex<ChildStdin>>,
+    pending: Pending,
+    alive: Arc<AtomicBool>,
+    notifications: Arc<Mutex<Vec<McpNotification>>>,
+) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            let Ok(msg) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            let items = match msg {
+                Value::Array(batch) => batch,
+                single => vec![single],
+            };
+            for item in items {
+                dispatch(item, &stdin, &pending, &notifications);
             }
-            return resp.result.ok_or_else(|| anyhow!("Missing MCP result"));
         }
+        alive.store(false, Ordering::Release);
+        // 丢弃发送端，等待中的请求立即得到断开错误
+        lock(&pending).clear();
+    });
+}
+
+fn dispatch(
+    msg: Value,
+    stdin: &Mutex<ChildStdin>,
+    pending: &Pending,
+    notifications: &Mutex<Vec<McpNotification>>,
+) {
+    if let Some(note) = McpNotification::from_message(&msg) {
+        lock(notifications).push(note);
+        return;
+    }
+    if let Some(method) = msg.get("method").and_then(Value::as_str) {
+        let id = msg.get("id").cloned().unwrap_or(Value::Null);
+        let reply = if method == "ping" {
+            json!({ "jsonrpc": "2.0", "id": id, "result": {} })
+        } else {
+            json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": -32601, "message": format!("Method not found: {}", method) }
+            })
+        };
+        let mut stdin = lock(stdin);
+        let _ = writeln!(stdin, "{}", reply).and_then(|_| stdin.flush());
+        return;
+    }
+    let Ok(resp) = serde_json::from_value::<JsonRpcResponse>(msg) else {
+        return;
+    };
+    let Some(id) = resp.id.as_ref().and_then(Value::as_u64) else {
+        return;
+    };
+    if let Some(tx) = lock(pending).remove(&id) {
+        let _ = tx.send(resp);
     }
 }
 
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
 impl McpExecutor for StdioExecutor {
-    fn list_tools(&self) -> Result<Vec<McpToolSpec>> {
-        let result = self.call_rpc("tools/list", None)?;
-        parse_tools(result)
+    fn request(&self, method: &str, params: Option<Value>) -> Result<Value> {
+        self.call_rpc(method, params)
+    }
+
+    fn notify(&self, method: &str, params: Option<Value>) -> Result<()> {
+        self.session()?.notify(method, params)
+    }
+
+    fn take_notifications(&self) -> Vec<McpNotification> {
+        std::mem::take(&mut *lock(&self.notifications))
+    }
+
+    /// 握手在进程启动时完成，这里返回当前会话协商的能力
+    fn initialize(&self) -> Result<McpServerCapabilities> {
+        Ok(self.session()?.caps.clone())
+    }
+
+    /// `ping` 超时视为进程卡死: 结束进程，交由下次检查重启
+    fn health_check(&self) -> Result<()> {
+        let session = self.session()?;
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        session
+            .call(id, "ping", None, self.timeout())
+            .inspect_err(|_| session.kill())?;
+        Ok(())
     }
 
-    fn call_tool(&self, name: &str, args: Value) -> Result<McpCallResult> {
-        let params = serde_json::json!({ "name": name, "arguments": args });
-        let result = self.call_rpc("tools/call", Some(params))?;
-        Ok(McpCallResult { content: result })
+    fn session_info(&self) -> Option<McpSessionInfo> {
+        let sup = lock(&self.supervisor);
+        let session = sup.session.as_ref().filter(|s| s.alive());
+        Some(McpSessionInfo {
+            pid: session.map(|s| s.pid),
+            restarts: self.restarts.load(Ordering::Relaxed),
+            failures: sup.failures,
+            retry_in_ms: match session {
+                Some(_) => None,
+                None => sup
+                    .retry_at
+                    .map(|at| at.saturating_duration_since(Instant::now()).as_millis() as u64),
+            },
+            in_flight: *lock(&self.in_flight),
+            max_in_flight: self.max_in_flight,
+            uptime_secs: session.map_or(0, |s| s.started.elapsed().as_secs()),
+            last_error: sup.last_error.clone(),
+        })
     }
 }
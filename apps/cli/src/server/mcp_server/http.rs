@@ -0,0 +1,77 @@
+// apps/cli/src/server/mcp_server/http.rs
+//! # MCP Streamable HTTP 传输
+//!
+//! `POST /mcp`: 请求体为 JSON-RPC 消息 (或批量)，响应为 `application/json`；
+//! 全部为通知时返回 202。不提供服务端主动推送流 (`GET /mcp` 返回 405)。
+//!
+//! ## Invariants
+//! - 请求须为 `Content-Type: application/json` (否则 415)，浏览器无法以简单请求跨域提交
+//! - 带 `Origin` 的请求须来自 CORS 允许列表 (否则 403)；无 `Origin` 的非浏览器客户端
+//!   (如 `deve mcp` 中继) 不受影响
+
+use crate::server::AppState;
+use crate::server::setup::AllowedOrigins;
+use axum::extract::State;
+use axum::extract::rejection::JsonRejection;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use serde_json::Value;
+use std::sync::Arc;
+
+pub async fn handle_post(
+    State(state): State<Arc<AppState>>,
+    Extension(origins): Extension<AllowedOrigins>,
+    headers: HeaderMap,
+    body: Result<Json<Value>, JsonRejection>,
+) -> Response {
+    if !origin_allowed(&headers, &origins) {
+        return (StatusCode::FORBIDDEN, "Origin not allowed").into_response();
+    }
+    let msg = match body {
+        Ok(Json(msg)) => msg,
+        Err(JsonRejection::MissingJsonContentType(e)) => return e.into_response(),
+        Err(e) => return Json(super::parse_error(e.body_text())).into_response(),
+    };
+
+    // 工具调用涉及 Ledger 与索引的阻塞读写
+    let result = tokio::task::spawn_blocking(move || super::handle_message(&state, msg)).await;
+    match result {
+        Ok(Some(response)) => Json(response).into_response(),
+        Ok(None) => StatusCode::ACCEPTED.into_response(),
+        Err(e) => {
+            tracing::error!("MCP request panicked: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+pub async fn handle_get() -> impl IntoResponse {
+    (StatusCode::METHOD_NOT_ALLOWED, [(header::ALLOW, "POST")])
+}
+
+/// 无 `Origin` (非浏览器客户端) 或来源在允许列表中
+fn origin_allowed(headers: &HeaderMap, origins: &AllowedOrigins) -> bool {
+    match headers.get(header::ORIGIN) {
+        None => true,
+        Some(origin) => origin.to_str().is_ok_and(|o| origins.contains(o)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_origin_allowed() {
+        let origins = AllowedOrigins::new(3001, false);
+        let mut headers = HeaderMap::new();
+        assert!(origin_allowed(&headers, &origins));
+
+        headers.insert(header::ORIGIN, "http://localhost:3001".parse().unwrap());
+        assert!(origin_allowed(&headers, &origins));
+
+        headers.insert(header::ORIGIN, "https://evil.example".parse().unwrap());
+        assert!(!origin_allowed(&headers, &origins));
+    }
+}
@@ -0,0 +1,198 @@
+// apps/cli/src/server/mcp_server/mod.rs
+//! # MCP Server (Deve-Note 作为 MCP 服务端)
+//!
+//! **功能**:
+//! 向外部 AI Agent 发布笔记的工具与资源 (JSON-RPC 2.0)。
+//! `server::mcp` 为客户端侧 (调用外部 MCP 服务)，本模块为服务端侧。
+//!
+//! **传输**:
+//! - `http`: Streamable HTTP (`POST /mcp`)，位于认证路由内 (JWT Cookie 或 Bearer)。
+//! - `deve mcp`: stdio 中继到运行中服务的 `/mcp` (见 `commands::mcp`)，认证与权限规则完全一致。
+//!
+//! **模块结构**:
+//! - `tools`: 搜索、读取、创建、编辑笔记与版本控制状态。
+//! - `resources`: `deve://notes/<path>` 与 `deve://source-control/changes`。
+//!
+//! ## Invariants
+//! - 只操作本地分支 (影子库只读，不对外暴露)
+//! - 路径校验与 WS 文档操作一致 (`check_path`)
+//! - 编辑以 Peer `mcp` 写入 Ledger，与客户端编辑同样定序并广播
+
+pub mod http;
+mod resources;
+mod tools;
+
+use crate::server::AppState;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+/// 支持的协议版本 (按新旧排序，首项为首选)
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// JSON-RPC 错误
+#[derive(Debug)]
+pub(crate) struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self {
+            code: INVALID_PARAMS,
+            message: message.into(),
+        }
+    }
+
+    fn internal(e: anyhow::Error) -> Self {
+        Self {
+            code: INTERNAL_ERROR,
+            message: e.to_string(),
+        }
+    }
+}
+
+/// 请求体不是合法 JSON 时的响应
+pub fn parse_error(detail: impl std::fmt::Display) -> Value {
+    error_response(
+        Value::Null,
+        RpcError {
+            code: PARSE_ERROR,
+            message: format!("Parse error: {}", detail),
+        },
+    )
+}
+
+/// 处理 JSON-RPC 消息 (单条消息或批量)
+///
+/// 返回 `None` 表示全部为通知，无需响应。
+pub fn handle_message(state: &Arc<AppState>, msg: Value) -> Option<Value> {
+    match msg {
+        Value::Array(batch) => {
+            let responses: Vec<Value> = batch
+                .into_iter()
+                .filter_map(|m| handle_single(state, m))
+                .collect();
+            (!responses.is_empty()).then_some(Value::Array(responses))
+        }
+        msg => handle_single(state, msg),
+    }
+}
+
+fn handle_single(state: &Arc<AppState>, msg: Value) -> Option<Value> {
+    let id = msg.get("id").cloned();
+    let Some(method) = msg.get("method").and_then(|m| m.as_str()) else {
+        // 客户端发来的响应 (本服务端不发起请求) 直接忽略
+        if msg.get("result").is_some() || msg.get("error").is_some() {
+            return None;
+        }
+        return Some(error_response(
+            id.unwrap_or(Value::Null),
+            RpcError {
+                code: INVALID_REQUEST,
+                message: "Invalid request".to_string(),
+            },
+        ));
+    };
+    let params = msg.get("params").cloned().unwrap_or(Value::Null);
+
+    // 通知 (无 id): 执行但不响应
+    let Some(id) = id else {
+        tracing::debug!("MCP notification: {}", method);
+        return None;
+    };
+
+    let result = dispatch(state, method, params);
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => {
+            tracing::warn!("MCP {} failed: {}", method, e.message);
+            error_response(id, e)
+        }
+    })
+}
+
+fn dispatch(state: &Arc<AppState>, method: &str, params: Value) -> Result<Value, RpcError> {
+    match method {
+        "initialize" => Ok(initialize_result(&params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tools::list_tools() })),
+        "tools/call" => {
+            let name = params
+                .get("name")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| RpcError::invalid_params("Missing tool name"))?;
+            let args = params.get("arguments").cloned().unwrap_or(json!({}));
+            tools::call_tool(state, name, args)
+        }
+        "resources/list" => resources::list_resources(state),
+        "resources/templates/list" => Ok(json!({
+            "resourceTemplates": resources::list_templates()
+        })),
+        "resources/read" => {
+            let uri = params
+                .get("uri")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| RpcError::invalid_params("Missing resource uri"))?;
+            resources::read_resource(state, uri)
+        }
+        _ => Err(RpcError {
+            code: METHOD_NOT_FOUND,
+            message: format!("Method not found: {}", method),
+        }),
+    }
+}
+
+/// 协商协议版本: 支持客户端版本时原样返回，否则返回首选版本
+fn negotiate_version(requested: Option<&str>) -> &'static str {
+    requested
+        .and_then(|v| PROTOCOL_VERSIONS.iter().find(|s| **s == v))
+        .copied()
+        .unwrap_or(PROTOCOL_VERSIONS[0])
+}
+
+fn initialize_result(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(|v| v.as_str());
+    if let Some(client) = params.get("clientInfo").and_then(|c| c.get("name")) {
+        tracing::info!("MCP client connected: {}", client);
+    }
+    json!({
+        "protocolVersion": negotiate_version(requested),
+        "capabilities": {
+            "tools": { "listChanged": false },
+            "resources": { "subscribe": false, "listChanged": false },
+        },
+        "serverInfo": {
+            "name": "deve-note",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "instructions": "Notes are Markdown files addressed by vault-relative path. \
+            Call read_note before edit_note; edits are recorded in the note history.",
+    })
+}
+
+fn error_response(id: Value, e: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": e.code, "message": e.message },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate_version() {
+        assert_eq!(negotiate_version(Some("2024-11-05")), "2024-11-05");
+        assert_eq!(negotiate_version(Some("1999-01-01")), PROTOCOL_VERSIONS[0]);
+        assert_eq!(negotiate_version(None), PROTOCOL_VERSIONS[0]);
+    }
+}
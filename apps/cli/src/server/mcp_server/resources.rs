@@ -0,0 +1,125 @@
+// apps/cli/src/server/mcp_server/resources.rs
+//! # MCP 资源
+//!
+//! - `deve://notes/<path>`: 笔记内容 (Markdown)
+//! - `deve://source-control/changes`: 未提交变更列表 (JSON)
+
+use super::RpcError;
+use crate::server::AppState;
+use crate::server::handlers::get_repo_id;
+use crate::server::plugin_docs::read_doc;
+use deve_core::ledger::listing::RepoListing;
+use deve_core::models::RepoType;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+const NOTE_PREFIX: &str = "deve://notes/";
+const CHANGES_URI: &str = "deve://source-control/changes";
+
+/// 资源不存在 (MCP 约定的错误码)
+const RESOURCE_NOT_FOUND: i64 = -32002;
+
+pub(super) fn list_resources(state: &Arc<AppState>) -> Result<Value, RpcError> {
+    let docs = state
+        .repo
+        .list_docs(&RepoType::Local(get_repo_id(state)))
+        .map_err(RpcError::internal)?;
+    let mut resources: Vec<Value> = docs
+        .into_iter()
+        .map(|(_, path)| {
+            json!({
+                "uri": note_uri(&path),
+                "name": path,
+                "mimeType": "text/markdown",
+            })
+        })
+        .collect();
+    resources.push(json!({
+        "uri": CHANGES_URI,
+        "name": "Uncommitted changes",
+        "mimeType": "application/json",
+    }));
+    Ok(json!({ "resources": resources }))
+}
+
+pub(super) fn list_templates() -> Value {
+    json!([{
+        "uriTemplate": format!("{}{{path}}", NOTE_PREFIX),
+        "name": "Note",
+        "description": "Note content by vault-relative path",
+        "mimeType": "text/markdown",
+    }])
+}
+
+pub(super) fn read_resource(state: &Arc<AppState>, uri: &str) -> Result<Value, RpcError> {
+    let (mime, text) = if uri == CHANGES_URI {
+        let changes = state.repo.list_changes().map_err(RpcError::internal)?;
+        let text =
+            serde_json::to_string_pretty(&changes).map_err(|e| RpcError::internal(e.into()))?;
+        ("application/json", text)
+    } else if let Some(path) = note_path(uri) {
+        let doc = read_doc(state, &path).map_err(|e| RpcError {
+            code: RESOURCE_NOT_FOUND,
+            message: e.to_string(),
+        })?;
+        ("text/markdown", doc.content)
+    } else {
+        return Err(RpcError {
+            code: RESOURCE_NOT_FOUND,
+            message: format!("Unknown resource: {}", uri),
+        });
+    };
+    Ok(json!({
+        "contents": [{ "uri": uri, "mimeType": mime, "text": text }]
+    }))
+}
+
+/// 笔记路径 -> URI (按段百分号编码，保留 `/`)
+fn note_uri(path: &str) -> String {
+    let mut uri = String::from(NOTE_PREFIX);
+    for (i, segment) in path.split('/').enumerate() {
+        if i > 0 {
+            uri.push('/');
+        }
+        for b in segment.bytes() {
+            if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+                uri.push(b as char);
+            } else {
+                uri.push_str(&format!("%{:02X}", b));
+            }
+        }
+    }
+    uri
+}
+
+/// URI -> 笔记路径 (非 `deve://notes/` 或编码无效时返回 None)
+fn note_path(uri: &str) -> Option<String> {
+    let encoded = uri.strip_prefix(NOTE_PREFIX)?;
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut iter = encoded.bytes();
+    while let Some(b) = iter.next() {
+        if b == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(b);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_uri_roundtrip() {
+        let path = "日记/2024 review.md";
+        let uri = note_uri(path);
+        assert_eq!(uri, "deve://notes/%E6%97%A5%E8%AE%B0/2024%20review.md");
+        assert_eq!(note_path(&uri).as_deref(), Some(path));
+        assert_eq!(note_path("deve://notes/a%2"), None);
+        assert_eq!(note_path("file:///a.md"), None);
+    }
+}
@@ -0,0 +1,323 @@
+// apps/cli/src/server/mcp_server/tools.rs
+//! # MCP 工具
+//!
+//! 工具失败 (文档不存在、版本冲突等) 以 `isError: true` 的结果返回给模型，
+//! 仅参数结构错误返回 JSON-RPC 错误。
+
+use super::RpcError;
+use crate::server::AppState;
+use crate::server::handlers::{get_repo_id, search::run_search};
+use crate::server::plugin_docs::{apply_doc_edits, create_doc, read_doc};
+use anyhow::{Result, anyhow, bail};
+use deve_core::ledger::listing::RepoListing;
+use deve_core::models::{PeerId, RepoType};
+use deve_core::plugin::runtime::doc_edit::TextEdit;
+use deve_core::protocol::DocQuery;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+/// MCP 客户端写入 Ledger 时使用的 Peer 身份
+const MCP_PEER: &str = "mcp";
+
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const MAX_SEARCH_LIMIT: usize = 100;
+
+pub(super) fn list_tools() -> Value {
+    json!([
+        {
+            "name": "search_notes",
+            "description": "Full-text search over notes. Supports filters such as \
+                `path:`, `tag:`, `in:history` and `in:peers`. Returns path, line and snippet per hit.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "limit": { "type": "integer", "minimum": 1, "maximum": MAX_SEARCH_LIMIT }
+                },
+                "required": ["query"]
+            }
+        },
+        {
+            "name": "list_notes",
+            "description": "List all notes as {doc_id, path}.",
+            "inputSchema": { "type": "object", "properties": {} }
+        },
+        {
+            "name": "query_notes",
+            "description": "Query notes by frontmatter properties and tags, \
+                e.g. `tag = project AND status != done SORT BY due`.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "query": { "type": "string" } },
+                "required": ["query"]
+            }
+        },
+        {
+            "name": "read_note",
+            "description": "Read a note. Returns {doc_id, path, content, version}.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "path": { "type": "string" } },
+                "required": ["path"]
+            }
+        },
+        {
+            "name": "create_note",
+            "description": "Create a note (`.md` is appended when missing). Fails if it exists.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string" },
+                    "content": { "type": "string" }
+                },
+                "required": ["path"]
+            }
+        },
+        {
+            "name": "edit_note",
+            "description": "Edit a note. Each edit is either {old_text, new_text} (old_text must \
+                occur exactly once) or {from, to, text} with character offsets; all edits refer to \
+                the current content and must not overlap. Pass `version` from read_note to reject \
+                the edit if the note changed meanwhile. Returns the new version.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string" },
+                    "edits": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "old_text": { "type": "string" },
+                                "new_text": { "type": "string" },
+                                "from": { "type": "integer", "minimum": 0 },
+                                "to": { "type": "integer", "minimum": 0 },
+                                "text": { "type": "string" }
+                            }
+                        }
+                    },
+                    "version": { "type": "integer", "minimum": 0 }
+                },
+                "required": ["path", "edits"]
+            }
+        },
+        {
+            "name": "source_control_status",
+            "description": "List uncommitted changes as {path, status}.",
+            "inputSchema": { "type": "object", "properties": {} }
+        }
+    ])
+}
+
+#[derive(Deserialize)]
+struct SearchArgs {
+    query: String,
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct QueryArgs {
+    query: String,
+}
+
+#[derive(Deserialize)]
+struct PathArgs {
+    path: String,
+}
+
+#[derive(Deserialize)]
+struct CreateArgs {
+    path: String,
+    #[serde(default)]
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct EditArgs {
+    path: String,
+    edits: Vec<NoteEdit>,
+    version: Option<u64>,
+}
+
+/// 按文本或按字符区间描述的编辑
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NoteEdit {
+    Replace { old_text: String, new_text: String },
+    Range(TextEdit),
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(args: Value) -> Result<T, RpcError> {
+    serde_json::from_value(args).map_err(|e| RpcError::invalid_params(e.to_string()))
+}
+
+pub(super) fn call_tool(state: &Arc<AppState>, name: &str, args: Value) -> Result<Value, RpcError> {
+    let result = match name {
+        "search_notes" => {
+            let args: SearchArgs = parse(args)?;
+            let limit = args
+                .limit
+                .unwrap_or(DEFAULT_SEARCH_LIMIT)
+                .clamp(1, MAX_SEARCH_LIMIT);
+            run_search(state, &args.query, limit)
+                .map_err(|e| anyhow!(e))
+                .and_then(to_json)
+        }
+        "list_notes" => state
+            .repo
+            .list_docs(&RepoType::Local(get_repo_id(state)))
+            .map(|docs| {
+                docs.into_iter()
+                    .map(|(doc_id, path)| json!({ "doc_id": doc_id, "path": path }))
+                    .collect::<Vec<_>>()
+            })
+            .and_then(to_json),
+        "query_notes" => {
+            let args: QueryArgs = parse(args)?;
+            DocQuery::parse(&args.query)
+                .map_err(|e| anyhow!("Invalid query: {}", e))
+                .and_then(|q| state.repo.query_docs(&q))
+                .and_then(to_json)
+        }
+        "read_note" => {
+            let args: PathArgs = parse(args)?;
+            read_doc(state, &args.path).and_then(to_json)
+        }
+        "create_note" => {
+            let args: CreateArgs = parse(args)?;
+            create_doc(state, &mcp_peer(), &args.path, &args.content).map(
+                |doc| json!({ "doc_id": doc.doc_id, "path": doc.path, "version": doc.version }),
+            )
+        }
+        "edit_note" => {
+            let args: EditArgs = parse(args)?;
+            edit_note(state, args)
+        }
+        "source_control_status" => state.repo.list_changes().and_then(to_json),
+        _ => {
+            return Err(RpcError::invalid_params(format!("Unknown tool: {}", name)));
+        }
+    };
+
+    Ok(match result {
+        Ok(value) => json!({
+            "content": [{ "type": "text", "text": text_of(&value) }],
+            "isError": false,
+        }),
+        Err(e) => {
+            tracing::warn!("MCP tool {} failed: {}", name, e);
+            json!({
+                "content": [{ "type": "text", "text": e.to_string() }],
+                "isError": true,
+            })
+        }
+    })
+}
+
+fn edit_note(state: &Arc<AppState>, args: EditArgs) -> Result<Value> {
+    let needs_content = args
+        .edits
+        .iter()
+        .any(|e| matches!(e, NoteEdit::Replace { .. }));
+    // 按文本定位时以读取到的版本为准，避免期间的并发修改使偏移失效
+    let (edits, version) = if needs_content {
+        let doc = read_doc(state, &args.path)?;
+        if let Some(expected) = args.version
+            && expected != doc.version
+        {
+            bail!(
+                "Document changed since version {} (now {})",
+                expected,
+                doc.version
+            );
+        }
+        (resolve_edits(&doc.content, args.edits)?, Some(doc.version))
+    } else {
+        (resolve_edits("", args.edits)?, args.version)
+    };
+    let version = apply_doc_edits(state, &mcp_peer(), &args.path, &edits, version)?;
+    Ok(json!({ "version": version }))
+}
+
+/// 将按文本描述的编辑转换为字符区间 (`old_text` 须恰好出现一次)
+fn resolve_edits(content: &str, edits: Vec<NoteEdit>) -> Result<Vec<TextEdit>> {
+    edits
+        .into_iter()
+        .map(|edit| match edit {
+            NoteEdit::Range(edit) => Ok(edit),
+            NoteEdit::Replace { old_text, new_text } => {
+                if old_text.is_empty() {
+                    bail!("old_text must not be empty");
+                }
+                let mut matches = content.match_indices(&old_text);
+                let (byte_pos, _) = matches
+                    .next()
+                    .ok_or_else(|| anyhow!("old_text not found: {:?}", old_text))?;
+                if matches.next().is_some() {
+                    bail!("old_text occurs more than once: {:?}", old_text);
+                }
+                let from = content[..byte_pos].chars().count();
+                Ok(TextEdit {
+                    from,
+                    to: from + old_text.chars().count(),
+                    text: new_text,
+                })
+            }
+        })
+        .collect()
+}
+
+fn mcp_peer() -> PeerId {
+    PeerId::new(MCP_PEER)
+}
+
+fn to_json<T: serde::Serialize>(value: T) -> Result<Value> {
+    Ok(serde_json::to_value(value)?)
+}
+
+/// 文本内容: 字符串原样输出，其余格式化为 JSON
+fn text_of(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_edits() {
+        let edits: Vec<NoteEdit> = serde_json::from_value(json!([
+            { "old_text": "wörld", "new_text": "there" },
+            { "from": 0, "to": 0, "text": "> " }
+        ]))
+        .unwrap();
+        let resolved = resolve_edits("héllo wörld", edits).unwrap();
+        assert_eq!(
+            resolved,
+            vec![
+                TextEdit {
+                    from: 6,
+                    to: 11,
+                    text: "there".into()
+                },
+                TextEdit {
+                    from: 0,
+                    to: 0,
+                    text: "> ".into()
+                },
+            ]
+        );
+
+        let replace = |old: &str| NoteEdit::Replace {
+            old_text: old.into(),
+            new_text: String::new(),
+        };
+        assert!(resolve_edits("a a", vec![replace("a")]).is_err());
+        assert!(resolve_edits("abc", vec![replace("x")]).is_err());
+        assert!(resolve_edits("abc", vec![replace("")]).is_err());
+    }
+}
@@ -0,0 +1,15 @@
+// apps/cli/src/server/mcp_status_http.rs
+//! # MCP 状态接口
+//!
+//! `GET /api/mcp/status`: 各 MCP 服务器的连接状态、目录规模与本地进程会话信息。
+
+use axum::Json;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+use crate::server::AppState;
+
+pub async fn status(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    Json(state.mcp.status_report())
+}
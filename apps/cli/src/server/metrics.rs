@@ -5,6 +5,7 @@
 //!
 //! **约束**: 768 MB 内存 VPS，禁止常驻采集线程。
 //! 采用定时快照策略：每 5 秒采集一次瞬时值并广播。
+//! 同一快照也由 `metrics_http` 以 OpenMetrics 格式导出。
 //!
 //! **平台支持**:
 //! - Linux: 解析 `/proc/meminfo` + `/proc/stat`
@@ -123,7 +124,7 @@ mod linux {
 
     fn parse_meminfo_kb(line: &str, prefix: &str) -> Option<u64> {
         let rest = line.strip_prefix(prefix)?;
-        rest.trim().split_whitespace().next()?.parse().ok()
+        rest.split_whitespace().next()?.parse().ok()
     }
 
     /// 瞬时 CPU 使用率 (/proc/stat 两次采样, 间隔 100ms)
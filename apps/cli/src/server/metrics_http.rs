@@ -0,0 +1,192 @@
+// apps/cli/src/server/metrics_http.rs
+//! # OpenMetrics 导出端点
+//!
+//! | Method | Path     | Auth                | Description                   |
+//! |--------|----------|---------------------|-------------------------------|
+//! | GET    | /metrics | Loopback 或 Bearer  | OpenMetrics 文本格式的全部指标 |
+//!
+//! ## 访问控制
+//! - 来自回环地址的请求直接放行 (本机 Prometheus / node_exporter 场景)
+//! - 远程请求需携带 `Authorization: Bearer <METRICS_TOKEN>`
+//! - 未设置 `METRICS_TOKEN` 时仅允许本机访问
+//! - 判断依据是 TCP 对端地址：经同机反向代理转发的外部请求同样来自回环地址，
+//!   此时应设置 `METRICS_ALLOW_LOOPBACK=false`，所有请求都校验 Token
+
+use axum::extract::{ConnectInfo, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use deve_core::metrics::{encode_histograms, encode_scalar};
+use deve_core::protocol::ServerMessage;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use crate::server::{AppState, metrics};
+
+const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// GET /metrics
+pub async fn scrape(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Response {
+    if !is_authorized(
+        &addr,
+        &headers,
+        std::env::var("METRICS_TOKEN").ok().as_deref(),
+        allow_loopback(),
+    ) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    // collect() 内含 100ms CPU 采样休眠，不能阻塞 async 运行时
+    let snapshot = tokio::task::spawn_blocking(move || metrics::collect(&state)).await;
+    let Ok(ServerMessage::SystemMetrics {
+        cpu_usage_percent,
+        memory_used_mb,
+        active_connections,
+        ops_processed,
+        uptime_secs,
+        db_size_bytes,
+        doc_count,
+    }) = snapshot
+    else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+
+    let mut out = String::with_capacity(8 * 1024);
+    encode_scalar(
+        &mut out,
+        "deve_cpu_usage_percent",
+        "gauge",
+        "Host CPU usage",
+        cpu_usage_percent as f64,
+    );
+    encode_scalar(
+        &mut out,
+        "deve_memory_used_bytes",
+        "gauge",
+        "Host memory in use",
+        (memory_used_mb * 1024 * 1024) as f64,
+    );
+    encode_scalar(
+        &mut out,
+        "deve_active_connections",
+        "gauge",
+        "Active WebSocket connections",
+        active_connections as f64,
+    );
+    encode_scalar(
+        &mut out,
+        "deve_ops_processed",
+        "counter",
+        "Client messages processed",
+        ops_processed as f64,
+    );
+    encode_scalar(
+        &mut out,
+        "deve_uptime_seconds",
+        "gauge",
+        "Server uptime",
+        uptime_secs as f64,
+    );
+    encode_scalar(
+        &mut out,
+        "deve_db_size_bytes",
+        "gauge",
+        "Local ledger database size",
+        db_size_bytes as f64,
+    );
+    encode_scalar(
+        &mut out,
+        "deve_documents",
+        "gauge",
+        "Documents in the local ledger",
+        doc_count as f64,
+    );
+    encode_histograms(&mut out);
+    out.push_str("# EOF\n");
+
+    ([(header::CONTENT_TYPE, CONTENT_TYPE)], out).into_response()
+}
+
+/// `METRICS_ALLOW_LOOPBACK` (默认 true)：是否免 Token 放行回环地址
+fn allow_loopback() -> bool {
+    std::env::var("METRICS_ALLOW_LOOPBACK")
+        .map(|v| !matches!(v.trim().to_ascii_lowercase().as_str(), "0" | "false" | "no"))
+        .unwrap_or(true)
+}
+
+/// 回环地址放行 (可关闭)；否则比较 Bearer Token
+fn is_authorized(
+    addr: &SocketAddr,
+    headers: &HeaderMap,
+    token: Option<&str>,
+    allow_loopback: bool,
+) -> bool {
+    if allow_loopback && addr.ip().is_loopback() {
+        return true;
+    }
+    let Some(expected) = token.filter(|t| !t.is_empty()) else {
+        return false;
+    };
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|provided| constant_time_eq(provided.as_bytes(), expected.as_bytes()))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_access_rules() {
+        let local: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        let remote: SocketAddr = "10.0.0.5:9000".parse().unwrap();
+
+        assert!(is_authorized(&local, &HeaderMap::new(), None, true));
+        assert!(!is_authorized(&remote, &HeaderMap::new(), None, true));
+        assert!(!is_authorized(&remote, &bearer("x"), None, true));
+        assert!(!is_authorized(
+            &remote,
+            &bearer("wrong"),
+            Some("secret"),
+            true
+        ));
+        assert!(is_authorized(
+            &remote,
+            &bearer("secret"),
+            Some("secret"),
+            true
+        ));
+
+        // 反向代理部署：回环地址同样需要 Token
+        assert!(!is_authorized(
+            &local,
+            &HeaderMap::new(),
+            Some("secret"),
+            false
+        ));
+        assert!(is_authorized(
+            &local,
+            &bearer("secret"),
+            Some("secret"),
+            false
+        ));
+    }
+}
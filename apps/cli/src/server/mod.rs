@@ -9,6 +9,8 @@
 //! - `start_server`: 启动 HTTP/WebSocket 服务器的主入口
 //! - `ws`: WebSocket 连接处理和消息路由
 //! - `handlers`: 客户端消息的业务逻辑
+//! - `mcp_server`: 对外 AI Agent 的 MCP 服务端 (`/mcp`)
+//! - `agent`: 服务端聊天智能体 (工具循环与持久化会话)
 //!
 //! 服务器使用 Axum 处理 HTTP/WebSocket，并向所有客户端广播变更。
 
@@ -17,7 +19,9 @@ use axum::{
     routing::{get, post},
 };
 use deve_core::ledger::RepoManager;
-use deve_core::plugin::runtime::PluginRuntime;
+use deve_core::ledger::doc_meta::indexer::MetadataIndexer;
+use deve_core::plugin::events::{EventBus, PluginEvent};
+use deve_core::plugin::registry::PluginRegistry;
 use deve_core::plugin::runtime::host;
 use deve_core::protocol::ServerMessage;
 use deve_core::sync::engine::SyncEngine;
@@ -29,17 +33,22 @@ use std::sync::RwLock;
 use tokio::sync::broadcast;
 
 #[cfg(feature = "search")]
-use deve_core::search::SearchService;
+use deve_core::search::{SearchIndexer, SearchService};
 
+pub mod agent;
 pub mod agent_bridge;
 pub mod ai_chat;
 pub mod auth;
 pub mod channel;
 pub mod handlers;
 pub mod mcp;
+pub mod mcp_server;
+pub mod mcp_status_http;
 pub mod metrics;
+pub mod metrics_http;
 pub mod node_role;
 pub mod node_role_http;
+pub mod plugin_docs;
 pub mod plugin_host;
 pub mod prewarm;
 mod rate_limit;
@@ -47,6 +56,7 @@ pub mod security;
 pub mod session;
 mod setup;
 pub mod source_control_proxy;
+pub mod tls;
 pub mod ws;
 
 #[allow(dead_code)] // repo_key: 为未来加密功能预留
@@ -55,21 +65,38 @@ pub struct AppState {
     pub sync_manager: Arc<deve_core::sync::SyncManager>,
     pub tx: broadcast::Sender<ServerMessage>,
     pub vault_path: std::path::PathBuf,
-    pub plugins: Vec<Box<dyn PluginRuntime>>,
+    /// 当前插件集合 (插件目录变化时热重载)
+    pub plugins: Arc<PluginRegistry>,
+    /// 插件事件总线 (订阅见各插件清单的 `events`)
+    pub events: EventBus,
     pub sync_engine: Arc<RwLock<SyncEngine>>,
     /// 文件树管理器 (增量更新)
     pub tree_manager: Arc<RwLock<TreeManager>>,
+    /// 属性/标签索引器 (QueryDocs 数据源)
+    pub meta_indexer: MetadataIndexer,
     #[cfg(feature = "search")]
-    pub search_service: Option<SearchService>,
+    pub search_service: Option<Arc<SearchService>>,
+    /// 后台索引器 (防抖批量提交)，与 `search_service` 同时存在
+    #[cfg(feature = "search")]
+    pub search_indexer: Option<SearchIndexer>,
     pub identity_key: Arc<deve_core::security::IdentityKeyPair>,
     pub repo_key: Option<deve_core::security::RepoKey>,
+    /// TOTP 双因素存储 (敏感操作校验第二因素)
+    pub mfa: Arc<deve_core::security::MfaStore>,
+    /// 插件设置值 (与宿主函数 `setting` 共享)
+    pub plugin_settings: Arc<deve_core::plugin::ui::PluginSettingsStore>,
+    /// MCP 客户端 (与宿主函数 `mcp_*` 共享)
+    pub mcp: Arc<deve_core::mcp::McpManager>,
+    /// 聊天智能体 (会话存储与运行注册表)
+    pub agent: Arc<agent::AgentService>,
 }
 
 pub async fn start_server(
     repo: Arc<RepoManager>,
     vault_path: std::path::PathBuf,
     port: u16,
-    plugins: Vec<Box<dyn PluginRuntime>>,
+    plugins: Arc<PluginRegistry>,
+    tls_opts: tls::TlsOptions,
 ) -> anyhow::Result<()> {
     let repo_api: Arc<dyn deve_core::ledger::traits::Repository> = repo.clone();
     host::set_repository(repo_api)?;
@@ -80,7 +107,10 @@ pub async fn start_server(
     });
     ai_chat::init_chat_stream_handler()?;
     metrics::init_start_time();
-    let mcp_manager = Arc::new(setup::load_mcp_manager(&vault_path));
+    // 握手与目录拉取为阻塞调用 (HTTP 传输内部 block_on)
+    let mcp_vault = vault_path.clone();
+    let mcp_manager =
+        Arc::new(tokio::task::spawn_blocking(move || setup::load_mcp_manager(&mcp_vault)).await?);
     let _ = host::set_mcp_manager(mcp_manager.clone());
     // Create broadcast channel for WS server
     let (tx, _rx) = broadcast::channel(100);
@@ -95,10 +125,11 @@ pub async fn start_server(
     #[cfg(feature = "search")]
     let search_service = {
         let index_path = vault_path.join(".deve_search_index");
-        match SearchService::new_on_disk(&index_path) {
+        let config = deve_core::search::SearchConfig::load(&vault_path.join(".deve"));
+        match SearchService::new_on_disk(&index_path, config.tokenizer) {
             Ok(s) => {
                 tracing::info!("Search service initialized at {:?}", index_path);
-                Some(s)
+                Some(Arc::new(s))
             }
             Err(e) => {
                 tracing::warn!("Failed to initialize search service: {:?}", e);
@@ -106,6 +137,12 @@ pub async fn start_server(
             }
         }
     };
+    #[cfg(feature = "search")]
+    let search_indexer = search_service
+        .as_ref()
+        .map(|service| SearchIndexer::spawn(repo.clone(), service.clone()));
+
+    let meta_indexer = MetadataIndexer::spawn(repo.clone());
 
     // Load or generate Identity Key
     let deve_dir = vault_path.join(".deve");
@@ -118,6 +155,25 @@ pub async fn start_server(
     // Load or generate Repo Key (Shared Secret)
     let repo_key = security::load_or_generate_repo_key(&deve_dir)?;
 
+    // 内置 TLS (可选)，须在签发任何 Cookie 之前确定
+    let tls_setup = tls::prepare(&tls_opts, &deve_dir).await?;
+
+    let mfa = Arc::new(deve_core::security::MfaStore::load(
+        &deve_dir.join("mfa.json"),
+    )?);
+
+    let plugin_settings = Arc::new(deve_core::plugin::ui::PluginSettingsStore::load(&deve_dir));
+    let _ = host::set_plugin_settings(plugin_settings.clone());
+    match deve_core::plugin::kv::PluginKvStore::open(&repo.ledger_dir().join("plugin_kv.redb")) {
+        Ok(store) => {
+            let _ = host::set_plugin_kv(Arc::new(store));
+        }
+        Err(e) => tracing::warn!("Plugin storage unavailable: {}", e),
+    }
+    let agent_service = Arc::new(agent::AgentService::open(
+        &repo.ledger_dir().join("conversations.redb"),
+    ));
+
     // Initialize SyncEngine (Relay Mode -> Auto)
     let sync_engine = Arc::new(RwLock::new(SyncEngine::new(
         peer_id.clone(),
@@ -142,25 +198,67 @@ pub async fn start_server(
         vault_path.clone(),
         tree_manager.clone(),
         tx.clone(),
+        meta_indexer.clone(),
+        #[cfg(feature = "search")]
+        search_indexer.clone(),
     );
 
+    let events = EventBus::new(plugins.clone());
     let app_state = Arc::new(AppState {
         repo: repo.clone(),
         sync_manager,
         tx,
         vault_path,
         plugins,
+        events,
         sync_engine,
         tree_manager,
+        meta_indexer,
         #[cfg(feature = "search")]
         search_service,
+        #[cfg(feature = "search")]
+        search_indexer,
         identity_key: key_pair,
         repo_key,
+        mfa: mfa.clone(),
+        plugin_settings,
+        mcp: mcp_manager.clone(),
+        agent: agent_service,
     });
 
     // 启动系统指标广播任务 (每 5 秒)
     metrics::spawn_broadcaster(app_state.clone());
 
+    // 插件经 Ledger 编辑笔记 (doc_read / doc_apply_edit / doc_create)；
+    // 须在 `server_start` 之前注册，订阅该事件的插件才能访问笔记
+    let _ = deve_core::plugin::runtime::doc_edit::set_doc_edit_handler(Arc::new(
+        plugin_docs::PluginDocEditor::new(Arc::downgrade(&app_state)),
+    ));
+    app_state.events.emit(PluginEvent::ServerStart);
+
+    // 插件热重载后向所有客户端推送新的插件列表
+    let weak_state = Arc::downgrade(&app_state);
+    app_state.plugins.on_reload(move || {
+        if let Some(state) = weak_state.upgrade() {
+            let _ = state.tx.send(ServerMessage::PluginList {
+                plugins: handlers::plugin::plugin_list(&state),
+            });
+        }
+    });
+
+    // MCP 服务器目录变化 (list_changed 通知) 后向所有客户端推送新目录
+    let catalog_tx = app_state.tx.clone();
+    let weak_mcp = Arc::downgrade(&mcp_manager);
+    mcp_manager.on_event(move |event| {
+        if let (deve_core::mcp::McpEvent::CatalogChanged { .. }, Some(mcp)) =
+            (event, weak_mcp.upgrade())
+        {
+            let _ = catalog_tx.send(handlers::plugin::mcp_catalog(&mcp));
+        }
+    });
+    // 本地服务器崩溃后自动重启，远程服务器恢复后重新握手
+    mcp::spawn_supervisor(Arc::downgrade(&mcp_manager));
+
     // --- 认证配置加载 ---
     let auth_config = load_auth_config();
     let auth_config = Arc::new(auth_config);
@@ -184,14 +282,32 @@ pub async fn start_server(
         )
         .route("/api/repo/docs", get(handlers::repo::http::list_docs))
         .route("/api/repo/doc", get(handlers::repo::http::doc_content))
+        .route("/api/repo/query", post(handlers::repo::http::query_docs))
+        .route(
+            "/api/repo/attachment",
+            get(handlers::repo::http::attachment),
+        )
+        .route("/api/mcp/status", get(mcp_status_http::status))
+        // MCP 服务端 (Streamable HTTP)
+        .route(
+            "/mcp",
+            post(mcp_server::http::handle_post).get(mcp_server::http::handle_get),
+        )
         .route("/api/auth/logout", post(auth::handlers::logout))
         .route("/api/auth/me", get(auth::handlers::me))
+        .route("/api/auth/totp/setup", post(auth::totp::setup))
+        .route("/api/auth/totp/enable", post(auth::totp::enable))
+        .route("/api/auth/totp/disable", post(auth::totp::disable))
+        .route("/api/auth/totp/verify", post(auth::totp::verify))
         .layer(axum::middleware::from_fn(auth::middleware::auth_middleware));
 
     // 公开路由 (无需认证)
     let public = Router::new()
         .route("/api/auth/login", post(auth::handlers::login))
-        .route("/api/node/role", get(node_role_http::role));
+        .route("/api/auth/login/totp", post(auth::totp::login_totp))
+        .route("/api/node/role", get(node_role_http::role))
+        // 自带访问控制 (回环地址或 METRICS_TOKEN)
+        .route("/metrics", get(metrics_http::scrape));
 
     let app = Router::new()
         .merge(protected)
@@ -201,12 +317,26 @@ pub async fn start_server(
         .layer(axum::middleware::from_fn(rate_limit::rate_limit_middleware))
         .layer(axum::Extension(auth_config))
         .layer(axum::Extension(brute_force))
+        .layer(axum::Extension(mfa))
         .layer(axum::Extension(limiter))
-        .layer(setup::build_cors_layer(port));
+        .layer(axum::Extension(setup::AllowedOrigins::new(
+            port,
+            tls_setup.is_some(),
+        )))
+        .layer(setup::build_cors_layer(port, tls_setup.is_some()));
 
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
-    println!("Server running on ws://{}", addr);
 
+    if let Some(tls_setup) = tls_setup {
+        println!("Server running on wss://{}", addr);
+        tls::spawn_reloader(&tls_setup);
+        axum_server::bind_rustls(addr, tls_setup.config)
+            .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+            .await?;
+        return Ok(());
+    }
+
+    println!("Server running on ws://{}", addr);
     let listener = tokio::net::TcpListener::bind(addr).await?;
     axum::serve(
         listener,
@@ -231,9 +361,6 @@ fn load_auth_config() -> deve_core::security::AuthConfig {
     }
 }
 
-pub async fn start_plugin_host_only(
-    plugins: Vec<Box<dyn PluginRuntime>>,
-    port: u16,
-) -> anyhow::Result<()> {
+pub async fn start_plugin_host_only(plugins: Arc<PluginRegistry>, port: u16) -> anyhow::Result<()> {
     plugin_host::start_plugin_host_only(plugins, port).await
 }
@@ -0,0 +1,213 @@
+// apps/cli/src/server/plugin_docs.rs
+//! # 插件文档编辑 (Plugin Document Editing)
+//!
+//! 实现宿主函数 `doc_read` / `doc_apply_edit` / `doc_create` 的服务端部分。
+//!
+//! 编辑与客户端编辑走同一路径: 在编辑定序锁内写入 Ledger (Peer 为 `plugin:<id>`)，
+//! 以 NewOp (client_id 0) 广播给打开该文档的编辑器，随后落盘、触发 `doc_saved` 并更新索引。
+//! `read_doc` / `apply_doc_edits` / `create_doc` 与 Peer 身份无关，MCP 服务端同样使用。
+
+use crate::server::AppState;
+use crate::server::channel::DualChannel;
+use crate::server::handlers::docs::check_path;
+use crate::server::handlers::docs::node_helpers::broadcast_parent_dirs;
+use crate::server::handlers::document::{append_op_locked, finish_edit};
+use crate::server::handlers::indexing::reconcile_index;
+use anyhow::{Result, anyhow, bail};
+use deve_core::ledger::node_meta;
+use deve_core::models::{DocId, NodeId, Op, PeerId};
+use deve_core::plugin::events::PluginEvent;
+use deve_core::plugin::runtime::doc_edit::{
+    DocEditHandler, DocSnapshot, TextEdit, edits_to_ops, plugin_peer_id,
+};
+use deve_core::protocol::ServerMessage;
+use deve_core::utils::path::{join_normalized, to_forward_slash};
+use std::sync::{Arc, Weak};
+
+/// 插件文档编辑器 (持有弱引用，服务停止后调用报错)
+pub struct PluginDocEditor {
+    state: Weak<AppState>,
+}
+
+impl PluginDocEditor {
+    pub fn new(state: Weak<AppState>) -> Self {
+        Self { state }
+    }
+
+    fn state(&self) -> Result<Arc<AppState>> {
+        self.state
+            .upgrade()
+            .ok_or_else(|| anyhow!("Server is shutting down"))
+    }
+}
+
+/// 规范化插件传入的文档路径
+fn normalize(path: &str) -> Result<String> {
+    let path = to_forward_slash(path.trim());
+    check_path(&path).map_err(|e| anyhow!(e))?;
+    Ok(path)
+}
+
+fn find_doc(state: &AppState, path: &str) -> Result<DocId> {
+    state
+        .repo
+        .get_docid(path)?
+        .ok_or_else(|| anyhow!("Document not found: {}", path))
+}
+
+impl DocEditHandler for PluginDocEditor {
+    fn read(&self, path: &str) -> Result<DocSnapshot> {
+        read_doc(&self.state()?, path)
+    }
+
+    fn apply_edit(
+        &self,
+        plugin_id: &str,
+        path: &str,
+        edits: &[TextEdit],
+        expected_version: Option<u64>,
+    ) -> Result<u64> {
+        let peer_id = plugin_peer_id(plugin_id);
+        apply_doc_edits(&self.state()?, &peer_id, path, edits, expected_version)
+    }
+
+    fn create(&self, plugin_id: &str, path: &str, content: &str) -> Result<DocSnapshot> {
+        create_doc(&self.state()?, &plugin_peer_id(plugin_id), path, content)
+    }
+}
+
+/// 读取文档当前内容与版本 (先对齐磁盘上的外部修改)
+pub(crate) fn read_doc(state: &Arc<AppState>, path: &str) -> Result<DocSnapshot> {
+    let path = normalize(path)?;
+    let doc_id = find_doc(state, &path)?;
+    // 与打开文档一致: 先对齐磁盘上的外部修改；持编辑定序锁，读到的版本即对齐结果
+    let guard = state.repo.lock_edits();
+    if let Err(e) = state.sync_manager.reconcile_doc_locked(doc_id) {
+        tracing::warn!("Reconcile before external read failed: {:?}", e);
+    }
+    let (content, version) = state.repo.get_local_state(doc_id)?;
+    drop(guard);
+    Ok(DocSnapshot {
+        doc_id,
+        path,
+        content,
+        version,
+    })
+}
+
+/// 以 `peer_id` 身份应用编辑，返回新版本
+///
+/// `expected_version` 为 `Some` 且与当前版本不同时拒绝。
+pub(crate) fn apply_doc_edits(
+    state: &Arc<AppState>,
+    peer_id: &PeerId,
+    path: &str,
+    edits: &[TextEdit],
+    expected_version: Option<u64>,
+) -> Result<u64> {
+    let path = normalize(path)?;
+    let doc_id = find_doc(state, &path)?;
+    let version = append_ops(state, peer_id, doc_id, |content, version| {
+        if let Some(expected) = expected_version
+            && expected != version
+        {
+            bail!(
+                "Document changed since version {} (now {})",
+                expected,
+                version
+            );
+        }
+        edits_to_ops(content, edits)
+    })?;
+    tracing::info!("{} edited {}", peer_id, path);
+    Ok(version)
+}
+
+/// 以 `peer_id` 身份创建文档并写入初始内容 (缺省补 `.md`，已存在时报错)
+pub(crate) fn create_doc(
+    state: &Arc<AppState>,
+    peer_id: &PeerId,
+    path: &str,
+    content: &str,
+) -> Result<DocSnapshot> {
+    let mut path = normalize(path)?;
+    if path.is_empty() || path.ends_with('/') {
+        bail!("Invalid document path: {}", path);
+    }
+    if !path.ends_with(".md") {
+        path.push_str(".md");
+    }
+    let full_path = join_normalized(&state.vault_path, &path);
+    if full_path.exists() || state.repo.get_docid(&path)?.is_some() {
+        bail!("Document already exists: {}", path);
+    }
+    if let Some(parent) = full_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&full_path, "")?;
+    let doc_id = state.repo.create_docid(&path)?;
+    tracing::info!("{} created {} ({})", peer_id, path, doc_id);
+    state
+        .events
+        .emit(PluginEvent::FileCreated { path: path.clone() });
+    broadcast_new_file(state, doc_id)?;
+    reconcile_index(state);
+
+    let version = append_ops(state, peer_id, doc_id, |_, _| {
+        Ok(if content.is_empty() {
+            Vec::new()
+        } else {
+            vec![Op::Insert {
+                pos: 0,
+                content: content.into(),
+            }]
+        })
+    })?;
+    Ok(DocSnapshot {
+        doc_id,
+        path,
+        content: content.to_string(),
+        version,
+    })
+}
+
+/// 在编辑定序锁内基于当前内容生成操作并以 `peer_id` 身份写入，返回新版本
+fn append_ops(
+    state: &AppState,
+    peer_id: &PeerId,
+    doc_id: DocId,
+    build: impl FnOnce(&str, u64) -> Result<Vec<Op>>,
+) -> Result<u64> {
+    let guard = state.repo.lock_edits();
+    let (content, mut version) = state.repo.get_local_state(doc_id)?;
+    let ops = build(&content, version)?;
+    for op in &ops {
+        (version, _) = append_op_locked(state, &state.tx, doc_id, peer_id, version, op, 0)?;
+    }
+    drop(guard);
+
+    if !ops.is_empty() {
+        finish_edit(state, doc_id)?;
+    }
+    Ok(version)
+}
+
+/// 向客户端广播新文件的树节点 (含尚未出现的父目录)
+fn broadcast_new_file(state: &Arc<AppState>, doc_id: DocId) -> Result<()> {
+    let node_id = NodeId::from_doc_id(doc_id);
+    let meta = state
+        .repo
+        .run_on_local_repo(state.repo.local_repo_name(), |db| {
+            node_meta::get_node_meta(db, node_id)
+                .and_then(|m| m.ok_or_else(|| anyhow!("File node meta missing")))
+        })?;
+    let ch = DualChannel::broadcast_only(state.tx.clone());
+    broadcast_parent_dirs(state, &ch, meta.parent_id)?;
+    let delta = state
+        .tree_manager
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .add_file(node_id, meta.path, meta.parent_id, meta.name, doc_id);
+    ch.broadcast(ServerMessage::TreeUpdate(delta));
+    Ok(())
+}
@@ -14,30 +14,26 @@ use crate::server::handlers::plugin::handle_plugin_call_with_plugins;
 use crate::server::handlers::{repo, source_control};
 use crate::server::node_role_http;
 use crate::server::ws::send;
-use deve_core::plugin::runtime::PluginRuntime;
+use deve_core::plugin::registry::PluginRegistry;
+use deve_core::plugin::runtime::cancel::CancelToken;
 use deve_core::protocol::{ClientMessage, ServerMessage};
 
 #[derive(Clone)]
 pub struct PluginHostState {
-    pub plugins: Arc<Vec<Box<dyn PluginRuntime>>>,
+    pub plugins: Arc<PluginRegistry>,
     pub tx: broadcast::Sender<ServerMessage>,
 }
 
-pub async fn start_plugin_host_only(
-    plugins: Vec<Box<dyn PluginRuntime>>,
-    port: u16,
-) -> anyhow::Result<()> {
+pub async fn start_plugin_host_only(plugins: Arc<PluginRegistry>, port: u16) -> anyhow::Result<()> {
     crate::server::ai_chat::init_chat_stream_handler()?;
     let (tx, _rx) = broadcast::channel(100);
-    let state = Arc::new(PluginHostState {
-        plugins: Arc::new(plugins),
-        tx,
-    });
+    let state = Arc::new(PluginHostState { plugins, tx });
 
     let app = Router::new()
         .route("/ws", get(ws_handler))
         .route("/api/repo/docs", get(repo::http::list_docs_plugin_host))
         .route("/api/repo/doc", get(repo::http::doc_content_plugin_host))
+        .route("/api/repo/query", post(repo::http::query_docs_plugin_host))
         .route(
             "/api/sc/status",
             get(source_control::http::status_plugin_host),
@@ -81,6 +77,7 @@ async fn handle_socket(
     let broadcast_rx = state.tx.subscribe();
     send::spawn_broadcast_forwarder(broadcast_rx, unicast_tx.clone());
     let ch = DualChannel::new(state.tx.clone(), unicast_tx);
+    let cancel = CancelToken::new();
 
     tracing::info!("Plugin host client connected: {}", peer_id);
 
@@ -102,8 +99,9 @@ async fn handle_socket(
                     args,
                 }) => {
                     handle_plugin_call_with_plugins(
-                        state.plugins.as_ref(),
+                        &state.plugins.snapshot(),
                         &ch,
+                        cancel.clone(),
                         req_id,
                         plugin_id,
                         fn_name,
@@ -122,4 +120,6 @@ async fn handle_socket(
             }
         }
     }
+
+    cancel.cancel();
 }
@@ -1,11 +1,11 @@
 use anyhow::Result;
 use deve_core::ledger::RepoManager;
-use deve_core::state;
 use deve_core::sync::snapshot_policy::SnapshotPolicy;
 use std::sync::Arc;
 use tokio::time::{Duration, sleep};
 
-const PREWARM_LIMIT: usize = 5;
+/// 启动时预热的文档数 (按操作数降序，超出缓存预算的部分会被 LRU 淘汰)
+const PREWARM_LIMIT: usize = 32;
 
 pub fn spawn_prewarm(repo: Arc<RepoManager>) {
     tokio::spawn(async move {
@@ -19,13 +19,13 @@ pub fn spawn_prewarm(repo: Arc<RepoManager>) {
     });
 }
 
+/// 将操作最多的文档载入文档状态缓存，并按快照策略补存快照
 fn prewarm_snapshots(repo: &RepoManager) -> Result<()> {
     let docs = repo.list_local_docs(None)?;
     let mut scored = Vec::new();
 
     for (doc_id, _path) in docs {
-        let entries = repo.get_local_ops(doc_id)?;
-        let count = entries.len() as u64;
+        let count = repo.count_local_ops(doc_id)?;
         if count > 0 {
             scored.push((doc_id, count));
         }
@@ -36,8 +36,7 @@ fn prewarm_snapshots(repo: &RepoManager) -> Result<()> {
     for (doc_id, _count) in scored.into_iter().take(PREWARM_LIMIT) {
         let snapshot = repo.load_latest_snapshot(doc_id)?;
         let base_seq = snapshot.as_ref().map(|(seq, _)| *seq).unwrap_or(0);
-        let entries = repo.get_local_ops(doc_id)?;
-        let max_seq = entries.last().map(|(seq, _)| *seq).unwrap_or(0);
+        let (content, max_seq) = repo.get_local_state(doc_id)?;
         let delta = max_seq.saturating_sub(base_seq);
         let doc_len = snapshot
             .as_ref()
@@ -50,8 +49,6 @@ fn prewarm_snapshots(repo: &RepoManager) -> Result<()> {
         }
 
         if snapshot.is_none() || policy.should_snapshot(doc_len, delta, 0) {
-            let ops: Vec<_> = entries.iter().map(|(_, e)| e.clone()).collect();
-            let content = state::reconstruct_content(&ops);
             let _ = repo.save_snapshot(doc_id, max_seq, &content);
         }
     }
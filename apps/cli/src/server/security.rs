@@ -15,7 +15,7 @@ use std::sync::Arc;
 ///
 /// # 不变量
 /// - 密钥文件仅 Owner 可读写。
-fn write_key_file(path: &Path, data: &[u8]) -> anyhow::Result<()> {
+pub(crate) fn write_key_file(path: &Path, data: &[u8]) -> anyhow::Result<()> {
     std::fs::write(path, data)?;
     #[cfg(unix)]
     {
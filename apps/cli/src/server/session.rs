@@ -8,9 +8,17 @@
 //! - `authenticated_peer_id`: P2P 握手后的对端 ID
 //! - `active_branch`: 当前活动分支 (None = 本地, Some = 影子库)
 //! - `active_db`: 当前锁定的数据库句柄
+//! - `mfa_at`: 第二因素最近校验时间 (握手 JWT 或会话内重新校验)
+//! - `plugin_cancel`: 连接断开时取消进行中的插件调用
 
 use deve_core::ledger::database::DatabaseHandle;
 use deve_core::models::PeerId;
+use deve_core::plugin::runtime::cancel::CancelToken;
+use deve_core::security::Claims;
+use deve_core::security::auth::jwt::MFA_FRESHNESS_SECS;
+
+/// 单个会话允许的第二因素连续失败次数
+const MAX_MFA_FAILURES: u32 = 5;
 
 /// WebSocket 会话状态
 ///
@@ -39,13 +47,55 @@ pub struct WsSession {
     ///
     /// 在切换 branch/repo 时更新，所有后续操作使用此句柄
     pub active_db: Option<DatabaseHandle>,
+
+    /// 第二因素最近校验时间 (Unix timestamp)
+    pub mfa_at: Option<i64>,
+
+    /// 会话内第二因素连续失败次数
+    pub mfa_failures: u32,
+
+    /// 本会话发起的插件调用共享的取消令牌
+    pub plugin_cancel: CancelToken,
+
+    /// 会话唯一标识 (用于校验智能体运行的归属)
+    pub session_id: String,
 }
 
 #[allow(dead_code)] // 为 P2P 握手和分支切换预留
 impl WsSession {
     /// 创建新会话
     pub fn new() -> Self {
-        Self::default()
+        Self::with_claims(None)
+    }
+
+    /// 以握手时的 JWT Claims 创建会话 (匿名 localhost 为 None)
+    pub fn with_claims(claims: Option<&Claims>) -> Self {
+        Self {
+            mfa_at: claims.and_then(|c| c.mfa_at),
+            session_id: uuid::Uuid::new_v4().to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// 第二因素是否在新鲜度窗口内完成校验
+    pub fn has_recent_mfa(&self, now: i64) -> bool {
+        self.mfa_at
+            .is_some_and(|at| now - at >= 0 && now - at <= MFA_FRESHNESS_SECS)
+    }
+
+    /// 记录第二因素校验结果
+    pub fn record_mfa(&mut self, success: bool, now: i64) {
+        if success {
+            self.mfa_at = Some(now);
+            self.mfa_failures = 0;
+        } else {
+            self.mfa_failures += 1;
+        }
+    }
+
+    /// 会话内第二因素失败次数是否已达上限
+    pub fn mfa_locked(&self) -> bool {
+        self.mfa_failures >= MAX_MFA_FAILURES
     }
 
     /// 设置已认证的 Peer ID
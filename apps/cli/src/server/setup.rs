@@ -2,6 +2,7 @@
 //! 服务器启动辅助: CORS 配置、MCP 加载、文件监视器
 
 use deve_core::ledger::RepoManager;
+use deve_core::ledger::doc_meta::indexer::MetadataIndexer;
 use deve_core::mcp::{McpManager, McpServerConfig};
 use deve_core::protocol::ServerMessage;
 use deve_core::tree::TreeManager;
@@ -11,16 +12,38 @@ use std::sync::RwLock;
 use tokio::sync::broadcast;
 use tower_http::cors::CorsLayer;
 
-/// 构建 CORS 层 — 仅允许 localhost 来源
-pub(super) fn build_cors_layer(port: u16) -> CorsLayer {
-    use tower_http::cors::AllowOrigin;
-    let origins: Vec<axum::http::HeaderValue> = (port..=port + 4)
+/// 允许的浏览器来源 — 仅 localhost，协议与服务端是否启用 TLS 一致
+pub(crate) fn allowed_origins(port: u16, tls: bool) -> Vec<String> {
+    let scheme = if tls { "https" } else { "http" };
+    (port..=port + 4)
         .flat_map(|p| {
             [
-                format!("http://localhost:{}", p),
-                format!("http://127.0.0.1:{}", p),
+                format!("{}://localhost:{}", scheme, p),
+                format!("{}://127.0.0.1:{}", scheme, p),
             ]
         })
+        .collect()
+}
+
+/// 允许的浏览器来源集合 (作为 Extension 供需自行校验 `Origin` 的路由使用)
+#[derive(Clone)]
+pub(crate) struct AllowedOrigins(Arc<Vec<String>>);
+
+impl AllowedOrigins {
+    pub(crate) fn new(port: u16, tls: bool) -> Self {
+        Self(Arc::new(allowed_origins(port, tls)))
+    }
+
+    pub(crate) fn contains(&self, origin: &str) -> bool {
+        self.0.iter().any(|o| o == origin)
+    }
+}
+
+/// 构建 CORS 层 — 仅允许 localhost 来源
+pub(super) fn build_cors_layer(port: u16, tls: bool) -> CorsLayer {
+    use tower_http::cors::AllowOrigin;
+    let origins: Vec<axum::http::HeaderValue> = allowed_origins(port, tls)
+        .into_iter()
         .filter_map(|s| s.parse().ok())
         .collect();
     CorsLayer::new()
@@ -63,6 +86,8 @@ pub(super) fn spawn_file_watcher(
     vault_path: std::path::PathBuf,
     tree_manager: Arc<RwLock<TreeManager>>,
     tx: broadcast::Sender<ServerMessage>,
+    meta_indexer: MetadataIndexer,
+    #[cfg(feature = "search")] search_indexer: Option<deve_core::search::SearchIndexer>,
 ) {
     tokio::task::spawn_blocking(move || {
         use deve_core::watcher::FsEventType;
@@ -81,8 +106,29 @@ pub(super) fn spawn_file_watcher(
                         let _ = tx.send(msg);
                     }
                 }
+                FsEventType::AttachmentChange => {
+                    if let Ok(nodes) = repo.list_local_nodes(None)
+                        && let Ok(mut tm) = tree_manager.write()
+                    {
+                        tm.init_from_nodes(nodes);
+                        let delta = tm.build_init_delta();
+                        let _ = tx.send(ServerMessage::TreeUpdate(delta));
+                    }
+                }
+                FsEventType::DocsTouched(doc_ids) => {
+                    doc_ids.iter().for_each(|id| meta_indexer.touch(*id));
+                    #[cfg(feature = "search")]
+                    if let Some(indexer) = &search_indexer {
+                        doc_ids.into_iter().for_each(|id| indexer.touch(id));
+                    }
+                }
                 FsEventType::DirChange => {
                     tracing::warn!("DirChange detected: ignore without Node update");
+                    meta_indexer.reconcile();
+                    #[cfg(feature = "search")]
+                    if let Some(indexer) = &search_indexer {
+                        indexer.reconcile();
+                    }
                 }
             },
         );
@@ -92,3 +138,20 @@ pub(super) fn spawn_file_watcher(
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowed_origins_follow_tls() {
+        let plain = allowed_origins(3001, false);
+        assert!(plain.contains(&"http://localhost:3001".to_string()));
+        assert!(plain.contains(&"http://127.0.0.1:3005".to_string()));
+        assert!(plain.iter().all(|o| o.starts_with("http://")));
+
+        let tls = allowed_origins(3001, true);
+        assert!(tls.contains(&"https://localhost:3001".to_string()));
+        assert!(tls.iter().all(|o| o.starts_with("https://")));
+    }
+}
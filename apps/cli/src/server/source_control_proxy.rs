@@ -4,6 +4,7 @@
 use anyhow::Result;
 use deve_core::ledger::traits::Repository;
 use deve_core::models::DocId;
+use deve_core::protocol::{DocQuery, DocRow};
 use deve_core::source_control::{ChangeEntry, CommitInfo};
 
 pub struct RemoteSourceControlApi {
@@ -12,14 +13,25 @@ pub struct RemoteSourceControlApi {
 }
 
 impl RemoteSourceControlApi {
+    /// `base_url` 指向本机主进程 (`http://` 或内置 TLS 的 `https://`)
     pub fn new(base_url: String) -> Self {
         Self {
+            client: loopback_client(&base_url),
             base_url,
-            client: reqwest::Client::new(),
         }
     }
 }
 
+/// 本机主进程客户端
+///
+/// 主进程可能使用自签名证书，回环地址上跳过证书校验 (流量不离开本机)。
+pub fn loopback_client(base_url: &str) -> reqwest::Client {
+    reqwest::Client::builder()
+        .danger_accept_invalid_certs(base_url.starts_with("https://127.0.0.1"))
+        .build()
+        .unwrap_or_default()
+}
+
 /// 在异步上下文中安全执行阻塞 HTTP 请求
 ///
 /// # 不变量
@@ -59,6 +71,21 @@ impl Repository for RemoteSourceControlApi {
         Ok(res)
     }
 
+    fn query_docs(&self, query: &DocQuery) -> Result<Vec<DocRow>> {
+        let url = format!("{}/api/repo/query", self.base_url);
+        let res = block_on_safe(async {
+            self.client
+                .post(&url)
+                .json(query)
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<Vec<DocRow>>()
+                .await
+        })?;
+        Ok(res)
+    }
+
     fn list_changes(&self) -> Result<Vec<ChangeEntry>> {
         let url = format!("{}/api/sc/status", self.base_url);
         let res = block_on_safe(async {
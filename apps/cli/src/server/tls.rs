@@ -0,0 +1,211 @@
+// apps/cli/src/server/tls.rs
+//! # 内置 TLS (HTTPS / WSS)
+//!
+//! 为 `deve serve` 提供 rustls 直连方案，无需反向代理即可启用 HTTPS/WSS。
+//!
+//! ## 证书来源
+//! - `--tls-cert` / `--tls-key`: 用户提供的 PEM 证书链与私钥
+//! - `--tls-self-signed`: 自动生成自签名证书 (保存于 `.deve/tls/`，重启复用)，
+//!   启动时打印 SHA-256 指纹供客户端手动核对
+//!
+//! ## Invariants
+//! - 证书文件变更 (如 certbot 续期) 后自动热加载，无需重启，已建立连接不受影响
+//! - 重新加载失败时保留旧证书继续服务
+//! - TLS 启用后 `is_active()` 返回 true，认证 Cookie 带 `Secure` 属性
+//! - 自签名私钥文件权限为 0600 (Unix)
+
+use anyhow::{Context, Result, anyhow};
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::pem::PemObject;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// 证书文件变更检测间隔
+const RELOAD_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 当前进程是否以 TLS 方式提供服务
+static TLS_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// `deve serve` 的 TLS 参数
+#[derive(Debug, Clone, Default)]
+pub struct TlsOptions {
+    pub cert: Option<PathBuf>,
+    pub key: Option<PathBuf>,
+    pub self_signed: bool,
+}
+
+impl TlsOptions {
+    pub fn is_enabled(&self) -> bool {
+        self.self_signed || self.cert.is_some() || self.key.is_some()
+    }
+}
+
+/// 已加载的 TLS 配置 (可热加载)
+pub struct TlsSetup {
+    pub config: RustlsConfig,
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+/// TLS 是否已启用 (决定 Cookie 的 `Secure` 属性)
+pub fn is_active() -> bool {
+    TLS_ACTIVE.load(Ordering::Relaxed)
+}
+
+/// 解析证书来源并加载 rustls 配置
+///
+/// # 后置条件
+/// - 未启用 TLS 时返回 `Ok(None)`
+/// - 成功时已打印证书指纹，`is_active()` 为 true
+pub async fn prepare(opts: &TlsOptions, deve_dir: &Path) -> Result<Option<TlsSetup>> {
+    if !opts.is_enabled() {
+        return Ok(None);
+    }
+
+    let (cert_path, key_path) = match (&opts.cert, &opts.key) {
+        (Some(cert), Some(key)) => (cert.clone(), key.clone()),
+        (None, None) => ensure_self_signed(&deve_dir.join("tls"))?,
+        _ => return Err(anyhow!("--tls-cert and --tls-key must be given together")),
+    };
+
+    // ring 作为进程级 crypto provider (重复安装返回 Err，可忽略)
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    let config = RustlsConfig::from_pem_file(&cert_path, &key_path)
+        .await
+        .with_context(|| format!("Failed to load TLS certificate {:?}", cert_path))?;
+
+    println!("TLS enabled with certificate {}", cert_path.display());
+    println!("  SHA-256 fingerprint: {}", fingerprint_file(&cert_path)?);
+    TLS_ACTIVE.store(true, Ordering::Relaxed);
+
+    Ok(Some(TlsSetup {
+        config,
+        cert_path,
+        key_path,
+    }))
+}
+
+/// 启动证书热加载任务 (轮询文件修改时间)
+pub fn spawn_reloader(setup: &TlsSetup) {
+    let config = setup.config.clone();
+    let cert_path = setup.cert_path.clone();
+    let key_path = setup.key_path.clone();
+    tokio::spawn(async move {
+        let mut last = modified_pair(&cert_path, &key_path);
+        let mut interval = tokio::time::interval(RELOAD_POLL_INTERVAL);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let current = modified_pair(&cert_path, &key_path);
+            if current == last {
+                continue;
+            }
+            match config.reload_from_pem_file(&cert_path, &key_path).await {
+                Ok(()) => {
+                    last = current;
+                    let fp = fingerprint_file(&cert_path).unwrap_or_default();
+                    tracing::info!(fingerprint = %fp, "TLS certificate reloaded");
+                }
+                // 续期工具可能分两步写入证书与私钥，下次轮询重试
+                Err(e) => tracing::warn!("TLS certificate reload failed: {}", e),
+            }
+        }
+    });
+}
+
+/// 复用或生成自签名证书
+fn ensure_self_signed(dir: &Path) -> Result<(PathBuf, PathBuf)> {
+    let cert_path = dir.join("cert.pem");
+    let key_path = dir.join("key.pem");
+    if cert_path.exists() && key_path.exists() {
+        return Ok((cert_path, key_path));
+    }
+
+    std::fs::create_dir_all(dir)?;
+    let (cert_pem, key_pem) = generate_self_signed()?;
+    std::fs::write(&cert_path, cert_pem)?;
+    std::fs::write(&key_path, key_pem)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&key_path, std::fs::Permissions::from_mode(0o600))?;
+    }
+    tracing::info!("Generated self-signed TLS certificate at {:?}", cert_path);
+    Ok((cert_path, key_path))
+}
+
+/// 生成覆盖本机名称的自签名证书 (PEM 证书, PEM 私钥)
+fn generate_self_signed() -> Result<(String, String)> {
+    let mut names = vec![
+        "localhost".to_string(),
+        "127.0.0.1".to_string(),
+        "::1".to_string(),
+    ];
+    if let Ok(host) = std::env::var("HOSTNAME")
+        && !host.is_empty()
+        && !names.contains(&host)
+    {
+        names.push(host);
+    }
+    let certified = rcgen::generate_simple_self_signed(names)?;
+    Ok((certified.cert.pem(), certified.key_pair.serialize_pem()))
+}
+
+/// 证书链首个证书 (叶子证书) 的 SHA-256 指纹，冒号分隔大写十六进制
+fn fingerprint_file(cert_path: &Path) -> Result<String> {
+    let pem = std::fs::read(cert_path)?;
+    let leaf = CertificateDer::pem_slice_iter(&pem)
+        .next()
+        .ok_or_else(|| anyhow!("No certificate found in {:?}", cert_path))??;
+    Ok(format_fingerprint(&leaf))
+}
+
+fn format_fingerprint(der: &[u8]) -> String {
+    deve_core::security::hashing::sha256_hex(der)
+        .to_uppercase()
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| String::from_utf8_lossy(pair).into_owned())
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn modified_pair(cert: &Path, key: &Path) -> (Option<SystemTime>, Option<SystemTime>) {
+    let mtime = |p: &Path| std::fs::metadata(p).and_then(|m| m.modified()).ok();
+    (mtime(cert), mtime(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_signed_is_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let (cert, key) = ensure_self_signed(dir.path()).unwrap();
+        let first = fingerprint_file(&cert).unwrap();
+        assert_eq!(first.len(), 32 * 3 - 1);
+        assert!(
+            std::fs::read_to_string(&key)
+                .unwrap()
+                .contains("PRIVATE KEY")
+        );
+
+        let (cert_again, _) = ensure_self_signed(dir.path()).unwrap();
+        assert_eq!(fingerprint_file(&cert_again).unwrap(), first);
+    }
+
+    #[tokio::test]
+    async fn test_options_require_pair() {
+        let opts = TlsOptions {
+            cert: Some(PathBuf::from("cert.pem")),
+            ..Default::default()
+        };
+        assert!(opts.is_enabled());
+        let dir = tempfile::tempdir().unwrap();
+        assert!(prepare(&opts, dir.path()).await.is_err());
+    }
+}
@@ -8,6 +8,7 @@ use crate::server::AppState;
 use crate::server::channel::DualChannel;
 use crate::server::session::WsSession;
 use deve_core::protocol::ClientMessage;
+use deve_core::security::Claims;
 use deve_core::security::auth::{config::AuthConfig, jwt};
 
 mod route;
@@ -29,10 +30,10 @@ pub async fn ws_handler(
 ) -> impl IntoResponse {
     // 提取 Cookie 中的 JWT
     let token = extract_cookie_from_parts(&req);
-    let authed = match token {
-        Some(ref t) => jwt::validate_token(&config.secret, t, config.token_version).is_ok(),
-        None => false,
-    };
+    let claims = token
+        .as_ref()
+        .and_then(|t| jwt::validate_token(&config.secret, t, config.token_version).ok());
+    let authed = claims.is_some();
 
     // localhost 免密策略
     let is_local = req
@@ -46,7 +47,7 @@ pub async fn ws_handler(
     }
 
     let peer_id = uuid::Uuid::new_v4().to_string();
-    ws.on_upgrade(move |socket| handle_socket(state, socket, peer_id))
+    ws.on_upgrade(move |socket| handle_socket(state, socket, peer_id, claims))
         .into_response()
 }
 
@@ -59,6 +60,7 @@ pub async fn handle_socket(
     state: Arc<AppState>,
     socket: axum::extract::ws::WebSocket,
     peer_id: String,
+    claims: Option<Claims>,
 ) {
     let (sender, mut receiver) = socket.split();
 
@@ -76,7 +78,7 @@ pub async fn handle_socket(
 
     tracing::info!("Client connected: {}", peer_id);
 
-    let mut session = WsSession::new();
+    let mut session = WsSession::with_claims(claims.as_ref());
 
     // Bincode 配置: 带大小限制防止内存耗尽攻击
     let bincode_config = bincode::options().with_limit(MAX_BINCODE_SIZE);
@@ -116,6 +118,9 @@ pub async fn handle_socket(
             _ => {}
         }
     }
+
+    // 连接已断开: 终止本会话仍在执行的插件调用
+    session.plugin_cancel.cancel();
 }
 
 fn extract_cookie_from_parts(parts: &axum::http::request::Parts) -> Option<String> {
@@ -1,4 +1,6 @@
-use crate::server::handlers::{document, key_exchange, listing, plugin, search, switcher, sync};
+use crate::server::handlers::{
+    agent, document, key_exchange, links, listing, mfa, plugin, query, search, switcher, sync,
+};
 use crate::server::{AppState, channel::DualChannel, session::WsSession};
 use deve_core::protocol::ClientMessage;
 use std::sync::Arc;
@@ -17,9 +19,10 @@ pub(super) async fn route_core(
         ClientMessage::Edit {
             doc_id,
             op,
+            base_seq,
             client_id,
         } => {
-            document::handle_edit(state, ch, session, doc_id, op, client_id).await;
+            document::handle_edit(state, ch, session, doc_id, op, base_seq, client_id).await;
         }
         ClientMessage::ListDocs => {
             listing::handle_list_docs(state, ch, session).await;
@@ -33,13 +36,79 @@ pub(super) async fn route_core(
         ClientMessage::Search { query, limit } => {
             search::handle_search(state, ch, query, limit).await;
         }
+        ClientMessage::QueryDocs { req_id, query: q } => {
+            query::handle_query_docs(state, ch, req_id, q).await;
+        }
+        ClientMessage::GetBacklinks { doc_id } => {
+            links::handle_get_backlinks(state, ch, doc_id).await;
+        }
+        ClientMessage::GetUnresolvedLinks => {
+            links::handle_get_unresolved_links(state, ch).await;
+        }
+        ClientMessage::GetGraph { root, depth } => {
+            links::handle_get_graph(state, ch, root, depth).await;
+        }
+        ClientMessage::RewriteLinks { old_path, new_path } => {
+            links::handle_rewrite_links(state, ch, session, old_path, new_path).await;
+        }
         ClientMessage::PluginCall {
             req_id,
             plugin_id,
             fn_name,
             args,
         } => {
-            plugin::handle_plugin_call(state, ch, req_id, plugin_id, fn_name, args).await;
+            plugin::handle_plugin_call(
+                state,
+                ch,
+                session.plugin_cancel.clone(),
+                req_id,
+                plugin_id,
+                fn_name,
+                args,
+            )
+            .await;
+        }
+        ClientMessage::ListPlugins => {
+            plugin::handle_list_plugins(state, ch);
+        }
+        ClientMessage::ChatSend {
+            req_id,
+            conversation_id,
+            message,
+            context,
+        } => {
+            agent::handle_chat_send(
+                state,
+                ch,
+                session,
+                req_id,
+                conversation_id,
+                message,
+                context,
+            )
+            .await;
+        }
+        ClientMessage::ChatCancel { req_id } => {
+            agent::handle_chat_cancel(state, session, req_id);
+        }
+        ClientMessage::ListConversations => {
+            agent::handle_list_conversations(state, ch).await;
+        }
+        ClientMessage::GetConversation { id } => {
+            agent::handle_get_conversation(state, ch, id).await;
+        }
+        ClientMessage::DeleteConversation { id } => {
+            agent::handle_delete_conversation(state, ch, id).await;
+        }
+        ClientMessage::ListMcpCatalog => {
+            plugin::handle_list_mcp_catalog(state, ch);
+        }
+        ClientMessage::SetPluginSetting {
+            plugin_id,
+            key,
+            value,
+        } => {
+            plugin::handle_set_plugin_setting(state, ch, plugin_id, key, value);
         }
         ClientMessage::SwitchBranch { peer_id } => {
             switcher::handle_switch_branch(state, ch, session, peer_id).await;
@@ -48,7 +117,9 @@ pub(super) async fn route_core(
             switcher::handle_switch_repo(state, ch, session, name).await;
         }
         ClientMessage::DeletePeer { peer_id } => {
-            sync::handle_delete_peer(state, ch, peer_id).await;
+            if mfa::ensure_recent_mfa(state, ch, session, "DeletePeer") {
+                sync::handle_delete_peer(state, ch, peer_id).await;
+            }
         }
         ClientMessage::SyncSnapshotRequest { peer_id, repo_id } => {
             sync::handle_sync_snapshot_request(state, ch, peer_id, repo_id).await;
@@ -60,11 +131,33 @@ pub(super) async fn route_core(
         } => {
             sync::handle_sync_push_snapshot(state, ch, peer_id, repo_id, ops).await;
         }
+        ClientMessage::SyncAttachmentManifest {
+            peer_id,
+            repo_id,
+            entries,
+        } => {
+            sync::handle_sync_attachment_manifest(state, ch, peer_id, repo_id, entries).await;
+        }
+        ClientMessage::SyncBlobRequest { hashes } => {
+            sync::handle_sync_blob_request(state, ch, hashes).await;
+        }
+        ClientMessage::SyncBlobChunk { peer_id, chunk } => {
+            sync::handle_sync_blob_chunk(state, ch, peer_id, chunk).await;
+        }
         ClientMessage::Ping => {
             ch.unicast(deve_core::protocol::ServerMessage::Pong);
         }
         ClientMessage::RequestKey => {
-            key_exchange::handle_request_key(state, ch).await;
+            if mfa::ensure_recent_mfa(state, ch, session, "RequestKey") {
+                key_exchange::handle_request_key(state, ch).await;
+            } else {
+                ch.unicast(deve_core::protocol::ServerMessage::KeyDenied {
+                    reason: "Second factor required".into(),
+                });
+            }
+        }
+        ClientMessage::VerifyTotp { code } => {
+            mfa::handle_verify_totp(state, ch, session, code).await;
         }
         other => {
             tracing::debug!("Unhandled client message: {:?}", other);
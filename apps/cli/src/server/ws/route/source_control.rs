@@ -38,6 +38,9 @@ pub(super) async fn route_source_control(
         ClientMessage::GetDocDiff { path } => {
             source_control::handle_get_doc_diff(state, ch, session, path).await;
         }
+        ClientMessage::GetHistoricalDiff { commit_id, doc_id } => {
+            source_control::handle_get_historical_diff(state, ch, commit_id, doc_id).await;
+        }
         other => super::core::route_core(state, ch, session, other).await,
     }
 }
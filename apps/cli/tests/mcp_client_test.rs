@@ -0,0 +1,186 @@
+// apps/cli/tests/mcp_client_test.rs
+//! MCP 客户端测试
+//!
+//! 以 `mock_mcp_server` 作为本地 stdio 服务器，验证能力协商、资源、提示词
+//! 与 `list_changed` 通知驱动的目录刷新，以及常驻进程的重启与并发上限。
+
+#[allow(dead_code)]
+#[path = "../src/server/mcp/mod.rs"]
+mod mcp;
+
+use deve_core::mcp::{McpEvent, McpManager, McpServerConfig, McpServerStatus};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+fn mock_manager(state_dir: &tempfile::TempDir) -> McpManager {
+    supervised_manager(state_dir, 200, 4)
+}
+
+fn supervised_manager(
+    state_dir: &tempfile::TempDir,
+    backoff_ms: u64,
+    max_in_flight: usize,
+) -> McpManager {
+    let mut env = HashMap::new();
+    env.insert(
+        "MOCK_MCP_STATE".to_string(),
+        state_dir.path().join("tools.txt").display().to_string(),
+    );
+    let mut manager = McpManager::new();
+    mcp::register_mcp_servers(
+        &mut manager,
+        vec![McpServerConfig::Local {
+            name: "mock".into(),
+            command: env!("CARGO_BIN_EXE_mock_mcp_server").into(),
+            args: Vec::new(),
+            env,
+            timeout_ms: Some(10_000),
+            retries: Some(0),
+            backoff_ms: Some(backoff_ms),
+            max_in_flight: Some(max_in_flight),
+        }],
+    );
+    manager
+}
+
+#[test]
+fn test_capabilities_resources_and_prompts() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let manager = mock_manager(&dir);
+
+    let status = manager.list_status();
+    assert!(matches!(status[0].1, McpServerStatus::Connected));
+    let caps = manager.capabilities("mock").expect("capabilities");
+    assert_eq!(caps.server_name.as_deref(), Some("mock-mcp"));
+    assert!(caps.tools_list_changed && caps.resources_subscribe && caps.prompts);
+
+    let resources = manager.list_all_resources();
+    assert_eq!(resources.len(), 1);
+    assert_eq!(resources[0].resource.uri, "mock://readme");
+    let contents = manager.read_resource("mock", "mock://readme")?;
+    assert_eq!(contents[0].text.as_deref(), Some("Mock readme text"));
+    assert!(manager.read_resource("mock", "mock://missing").is_err());
+
+    let prompts = manager.list_all_prompts();
+    assert_eq!(prompts[0].prompt.name, "review");
+    assert!(prompts[0].prompt.arguments[0].required);
+    let prompt = manager.get_prompt("mock", "review", json!({ "topic": "the plan" }))?;
+    assert_eq!(prompt.messages[0].role, "user");
+    assert_eq!(prompt.messages[0].content["text"], "Please review the plan");
+    Ok(())
+}
+
+#[test]
+fn test_notifications_refresh_tools_and_forward_updates() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let manager = mock_manager(&dir);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    manager.on_event(move |e| sink.lock().unwrap().push(e.clone()));
+
+    assert_eq!(manager.list_tools("mock").len(), 1);
+    let echoed = manager.call_tool("mock", "echo", json!({ "text": "hi" }))?;
+    assert_eq!(echoed.content["content"][0]["text"], "hi");
+    assert!(events.lock().unwrap().is_empty());
+
+    manager.call_tool("mock", "add_tool", json!({ "name": "fresh" }))?;
+    let names: Vec<String> = manager
+        .list_tools("mock")
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(names, vec!["echo", "fresh"]);
+
+    manager.subscribe_resource("mock", "mock://readme")?;
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![
+            McpEvent::CatalogChanged {
+                server: "mock".into()
+            },
+            McpEvent::ResourceUpdated {
+                server: "mock".into(),
+                uri: "mock://readme".into()
+            },
+        ]
+    );
+    Ok(())
+}
+
+fn call_text(manager: &McpManager, tool: &str, args: serde_json::Value) -> anyhow::Result<String> {
+    let result = manager.call_tool("mock", tool, args)?;
+    Ok(result.content["content"][0]["text"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string())
+}
+
+#[test]
+fn test_session_survives_calls_and_restarts_after_crash() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let manager = supervised_manager(&dir, 300, 4);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    manager.on_event(move |e| sink.lock().unwrap().push(e.clone()));
+
+    let pid = call_text(&manager, "pid", json!({}))?;
+    assert_eq!(call_text(&manager, "pid", json!({}))?, pid);
+    manager.check_health("mock")?;
+    assert!(events.lock().unwrap().is_empty());
+
+    assert!(manager.call_tool("mock", "crash", json!({})).is_err());
+    assert!(manager.check_health("mock").is_err());
+    let status = manager.list_status();
+    assert!(matches!(
+        status[0].1,
+        McpServerStatus::Restarting { attempt: 1, .. }
+    ));
+    assert_eq!(manager.status_report()[0].state, "restarting");
+
+    std::thread::sleep(Duration::from_millis(400));
+    manager.check_health("mock")?;
+    assert!(matches!(
+        manager.list_status()[0].1,
+        McpServerStatus::Connected
+    ));
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![McpEvent::CatalogChanged {
+            server: "mock".into()
+        }]
+    );
+
+    let report = manager.status_report();
+    let session = report[0].session.clone().expect("session info");
+    assert_eq!(report[0].state, "connected");
+    assert_eq!(session.restarts, 1);
+    assert_ne!(session.pid.map(|p| p.to_string()), Some(pid.clone()));
+    assert_ne!(call_text(&manager, "pid", json!({}))?, pid);
+    Ok(())
+}
+
+#[test]
+fn test_in_flight_requests_are_capped() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let manager = supervised_manager(&dir, 200, 1);
+
+    let start = Instant::now();
+    std::thread::scope(|scope| {
+        let calls: Vec<_> = (0..2)
+            .map(|_| scope.spawn(|| call_text(&manager, "sleep", json!({ "ms": 300 }))))
+            .collect();
+        for call in calls {
+            assert_eq!(call.join().unwrap().unwrap(), "slept");
+        }
+    });
+    // 上限为 1 时两个请求串行执行
+    assert!(start.elapsed() >= Duration::from_millis(600));
+    let session = manager.status_report()[0]
+        .session
+        .clone()
+        .expect("session info");
+    assert_eq!((session.in_flight, session.max_in_flight), (0, 1));
+    Ok(())
+}
@@ -22,7 +22,7 @@ import { languages } from "@codemirror/language-data";
 import { mathStateField } from "./extensions/math.js";
 import { hybridPlugin } from "./extensions/hybrid.js";
 import { tableStateField } from "./extensions/table.js";
-import { imageStateField } from "./extensions/image.js";
+import { imageStateField, setAttachmentContext } from "./extensions/image.js";
 import { checkboxStateField } from "./extensions/checkbox_ext.js";
 import { blockStyling } from "./extensions/block_styling.js";
 import { mermaidStateField } from "./extensions/mermaid.js";
@@ -146,3 +146,4 @@ window.applyRemoteOpsBatch = applyRemoteOpsBatch;
 globalThis.applyRemoteOpsBatch = applyRemoteOpsBatch;
 window.scrollGlobal = scrollGlobal;
 window.setReadOnly = setReadOnly;
+window.setAttachmentContext = setAttachmentContext;
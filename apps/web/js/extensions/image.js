@@ -1,6 +1,48 @@
 import { WidgetType, Decoration, EditorView } from "@codemirror/view";
 import { StateField } from "@codemirror/state";
 
+// 附件上下文: 服务器 HTTP 基地址 + 当前文档路径 (由 WASM 通过 setAttachmentContext 设置)
+const attachmentCtx = { base: "", docPath: "" };
+
+/**
+ * 设置附件解析上下文
+ *
+ * @param {string} base - 服务器 HTTP 基地址 (如 `http://host:port`)
+ * @param {string} docPath - 当前文档在仓库中的路径 (如 `notes/a.md`)
+ */
+export function setAttachmentContext(base, docPath) {
+  attachmentCtx.base = base || "";
+  attachmentCtx.docPath = docPath || "";
+}
+
+/**
+ * 将图片链接解析为可加载的 URL
+ *
+ * 绝对 URL (含协议、`data:`、`//`) 原样返回；
+ * 相对路径按当前文档所在目录解析，`/` 开头视为仓库根路径，
+ * 最终指向 `/api/repo/attachment?path=...`。
+ */
+export function resolveAttachmentUrl(url) {
+  if (/^([a-z][a-z0-9+.-]*:|\/\/)/i.test(url)) return url;
+
+  let raw = url.split(/[?#]/)[0];
+  try {
+    raw = decodeURIComponent(raw);
+  } catch (_) {
+    // 保留原始写法
+  }
+
+  const parts = raw.startsWith("/")
+    ? []
+    : attachmentCtx.docPath.split("/").slice(0, -1);
+  for (const seg of raw.split("/")) {
+    if (seg === "" || seg === ".") continue;
+    if (seg === "..") parts.pop();
+    else parts.push(seg);
+  }
+  return `${attachmentCtx.base}/api/repo/attachment?path=${encodeURIComponent(parts.join("/"))}`;
+}
+
 /**
  * Image Widget (图片组件)
  * 
@@ -12,10 +54,15 @@ class ImageWidget extends WidgetType {
     super();
     this.url = url;
     this.title = title;
+    this.docPath = attachmentCtx.docPath;
   }
 
   eq(other) {
-    return this.url === other.url && this.title === other.title;
+    return (
+      this.url === other.url &&
+      this.title === other.title &&
+      this.docPath === other.docPath
+    );
   }
 
   toDOM() {
@@ -23,7 +70,7 @@ class ImageWidget extends WidgetType {
     container.className = "cm-image-widget inline-block my-2";
     
     let img = document.createElement("img");
-    img.src = this.url;
+    img.src = resolveAttachmentUrl(this.url);
     if (this.title) img.title = this.title;
     img.alt = this.title || "Image";
     
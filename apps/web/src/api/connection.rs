@@ -76,12 +76,16 @@ pub fn spawn_connection_manager(
     });
 }
 
-async fn fetch_node_role(ws_url: String, set_node_role: WriteSignal<String>) {
-    let http_url = ws_url
+/// WebSocket 地址转换为 HTTP 基地址
+pub(super) fn http_base(ws_url: &str) -> String {
+    ws_url
         .replace("wss://", "https://")
         .replace("ws://", "http://")
-        .replace("/ws", "");
-    let url = format!("{}/api/node/role", http_url);
+        .replace("/ws", "")
+}
+
+async fn fetch_node_role(ws_url: String, set_node_role: WriteSignal<String>) {
+    let url = format!("{}/api/node/role", http_base(&ws_url));
     let res = Request::get(&url).send().await;
     if let Ok(resp) = res
         && let Ok(json) = resp.json::<serde_json::Value>().await
@@ -145,6 +145,20 @@ impl WsService {
         }
     }
 
+    /// 当前端点对应的 HTTP 基地址 (`ws://h:p/ws` -> `http://h:p`)
+    pub fn http_base(&self) -> String {
+        connection::http_base(&self.endpoint.get_untracked())
+    }
+
+    /// 附件内容的 HTTP 地址
+    pub fn attachment_url(&self, path: &str) -> String {
+        format!(
+            "{}/api/repo/attachment?path={}",
+            self.http_base(),
+            js_sys::encode_uri_component(path)
+        )
+    }
+
     /// 将消息排队发送到服务器
     ///
     /// 如果当前离线，消息将被缓存并在连接恢复时自动发送。
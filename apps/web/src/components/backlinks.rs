@@ -0,0 +1,70 @@
+// apps\web\src\components
+//! # Backlinks 组件 (Backlinks Panel)
+//!
+//! 显示链接到当前文档的其他文档 (反向链接)，点击跳转到来源行。
+
+use crate::hooks::use_core::{DocContext, EditorContext, LinksContext};
+use crate::i18n::{Locale, t};
+use deve_core::models::DocId;
+use leptos::prelude::*;
+
+#[component]
+pub fn Backlinks(doc_id: DocId) -> impl IntoView {
+    let links = expect_context::<LinksContext>();
+    let editor = expect_context::<EditorContext>();
+    let doc_ctx = expect_context::<DocContext>();
+    let locale = use_context::<RwSignal<Locale>>().unwrap_or_else(|| RwSignal::new(Locale::En));
+
+    // 文档加载完成后请求反链 (链接图由服务端后台索引维护)
+    Effect::new(move |_| {
+        if editor.load_state.get() == "ready" {
+            links.on_get_backlinks.run(doc_id);
+        }
+    });
+
+    let entries = Memo::new(move |_| {
+        links.backlinks.with(|b| match b {
+            Some((id, list)) if *id == doc_id => list.clone(),
+            _ => Vec::new(),
+        })
+    });
+
+    view! {
+        <div class="py-3 px-2 select-none border-t border-gray-200">
+            <div class="font-bold text-muted mb-2 px-2 text-[10px] uppercase tracking-wider">
+                {move || t::sidebar::backlinks(locale.get())}
+            </div>
+            {move || if entries.with(|e| e.is_empty()) {
+                view! {
+                    <div class="px-2 text-xs text-muted italic">
+                        {move || t::sidebar::no_backlinks(locale.get())}
+                    </div>
+                }.into_any()
+            } else {
+                view! {}.into_any()
+            }}
+            <For
+                each=move || entries.get()
+                key=|b| (b.source, b.line)
+                children=move |link| {
+                    let source = link.source;
+                    let line = link.line as usize;
+                    let title = format!("{}:{}", link.path, link.line);
+                    view! {
+                        <div
+                            class="py-1.5 px-2 text-xs text-secondary hover:bg-hover hover:text-primary cursor-pointer rounded transition-colors"
+                            title={title}
+                            on:click=move |_| {
+                                editor.set_pending_jump.set(Some((source, line)));
+                                doc_ctx.on_doc_select.run(source);
+                            }
+                        >
+                            <div class="font-medium truncate">{link.path.clone()}</div>
+                            <div class="text-[11px] text-muted truncate">{link.context.clone()}</div>
+                        </div>
+                    }
+                }
+            />
+        </div>
+    }
+}
@@ -1,7 +1,7 @@
 // apps/web/src/components/chat/actions.rs
-use crate::editor::ffi::getEditorContent;
-use crate::hooks::use_core::CoreState;
-use deve_core::models::Op;
+use crate::components::chat::mcp_context::{ChatMcp, resolve_slash, resource_context};
+use crate::editor::ffi::append_editor_text;
+use crate::hooks::use_core::{ConversationContext, CoreState};
 use deve_core::protocol::ClientMessage;
 use leptos::prelude::*;
 
@@ -10,7 +10,9 @@ pub fn make_send_text(
     is_streaming: ReadSignal<bool>,
     on_req_id: Option<Callback<String>>,
     on_user_text: Option<Callback<String>>,
+    mcp: Option<ChatMcp>,
 ) -> Callback<String> {
+    let conversation = use_context::<ConversationContext>();
     Callback::new(move |msg: String| {
         let msg = msg.trim().to_string();
         if msg.is_empty() || is_streaming.get() {
@@ -38,9 +40,30 @@ pub fn make_send_text(
             })
             .unwrap_or_default();
 
-        let context = serde_json::json!({ "current_file": current_doc_path });
-        let args = vec![serde_json::json!(req_id), serde_json::json!(msg), context];
+        let mut context = serde_json::json!({ "current_file": current_doc_path });
+        // 斜杠命令展开为 MCP 提示词，附加资源随本条消息发送后清空
+        if let Some(mcp) = mcp {
+            if let Some(prompt) = resolve_slash(&msg, &mcp.prompts.get_untracked()) {
+                context["prompt"] = prompt;
+            }
+            let attached = mcp.attached.get_untracked();
+            if !attached.is_empty() {
+                context["resources"] = resource_context(&attached);
+                mcp.attached.set(Vec::new());
+            }
+        }
         let plugin_id = core.ai_mode.get_untracked();
+        // API 模式由服务端智能体循环处理，会话持久化在服务端
+        if plugin_id == "ai-chat" {
+            core.ws.send(ClientMessage::ChatSend {
+                req_id,
+                conversation_id: conversation.and_then(|c| c.active.get_untracked()),
+                message: msg,
+                context,
+            });
+            return;
+        }
+        let args = vec![serde_json::json!(req_id), serde_json::json!(msg), context];
         core.on_plugin_call
             .run((req_id, plugin_id, "chat".to_string(), args));
     })
@@ -72,29 +95,17 @@ pub fn make_send_message(
     })
 }
 
+/// 将代码追加到当前文档末尾
+///
+/// 作为编辑器的本地编辑写入，由编辑器的 OT 状态机按序发送，与待确认的输入不冲突。
 pub fn make_on_apply(core: CoreState) -> Callback<String> {
     Callback::new(move |code: String| {
-        let Some(doc_id) = core.current_doc.get_untracked() else {
+        if core.current_doc.get_untracked().is_none() {
             leptos::logging::warn!("No active doc to apply code.");
             return;
-        };
-        let utf16_len = getEditorContent().encode_utf16().count();
-        let pos = match u32::try_from(utf16_len) {
-            Ok(v) => v,
-            Err(_) => {
-                leptos::logging::warn!("Apply code aborted: UTF-16 length overflow.");
-                return;
-            }
-        };
-        let op = Op::Insert {
-            pos,
-            content: code.into(),
-        };
-        let client_id = (js_sys::Math::random() * 1_000_000.0) as u64;
-        core.ws.send(ClientMessage::Edit {
-            doc_id,
-            op,
-            client_id,
-        });
+        }
+        if !append_editor_text(&code) {
+            leptos::logging::warn!("Apply code aborted: editor not ready or read-only.");
+        }
     })
 }
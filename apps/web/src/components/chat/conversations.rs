@@ -0,0 +1,103 @@
+// apps/web/src/components/chat/conversations.rs
+//! # 会话历史菜单
+//!
+//! 服务端智能体 (API 模式) 的持久化会话：新建、恢复与删除。
+//! 运行进行中时禁止切换，避免流式事件写入错误的会话。
+
+use crate::components::icons::{History, Plus, X};
+use crate::hooks::use_core::{ConversationContext, CoreState};
+use crate::i18n::{Locale, t};
+use deve_core::protocol::ClientMessage;
+use leptos::prelude::*;
+
+#[component]
+pub fn ConversationMenu(#[prop(optional)] mobile: bool) -> impl IntoView {
+    let core = expect_context::<CoreState>();
+    let conversation = expect_context::<ConversationContext>();
+    let locale = use_context::<RwSignal<Locale>>().expect("locale context");
+    let (open, set_open) = signal(false);
+    let busy = move || conversation.running.get().is_some();
+
+    let set_chat_messages = core.set_chat_messages;
+    let new_chat = move |_| {
+        conversation.set_active.set(None);
+        set_chat_messages.set(Vec::new());
+        set_open.set(false);
+    };
+    let ws_open = core.ws.clone();
+    let open_conversation = Callback::new(move |id: String| {
+        ws_open.send(ClientMessage::GetConversation { id });
+        set_open.set(false);
+    });
+    let ws_delete = core.ws.clone();
+    let delete_conversation = Callback::new(move |id: String| {
+        ws_delete.send(ClientMessage::DeleteConversation { id });
+    });
+
+    let button_class = if mobile {
+        "h-11 min-w-11 p-2 rounded-md text-secondary active:bg-hover disabled:opacity-50 transition-colors"
+    } else {
+        "p-1.5 rounded hover:bg-hover text-secondary disabled:opacity-50 transition-colors"
+    };
+
+    view! {
+        <div class="relative flex items-center gap-1">
+            <button
+                class=button_class
+                disabled=busy
+                on:click=new_chat
+                title=move || t::chat::new_chat(locale.get())
+                aria-label=move || t::chat::new_chat(locale.get())
+            >
+                <Plus class="w-4 h-4" />
+            </button>
+            <button
+                class=button_class
+                disabled=busy
+                on:click=move |_| set_open.update(|v| *v = !*v)
+                title=move || t::chat::history(locale.get())
+                aria-label=move || t::chat::history(locale.get())
+            >
+                <History class="w-4 h-4" />
+            </button>
+            <Show when=move || open.get() && !busy()>
+                <div class="absolute top-full right-0 mt-1 w-64 max-h-72 overflow-y-auto rounded border border-default bg-panel shadow-lg z-20">
+                    <Show when=move || conversation.conversations.get().is_empty()>
+                        <div class="px-2 py-2 text-xs text-muted">{move || t::chat::no_conversations(locale.get())}</div>
+                    </Show>
+                    <For
+                        each=move || conversation.conversations.get()
+                        key=|c| (c.id.clone(), c.updated_ms, c.title.clone())
+                        children=move |c| {
+                            let (open_id, delete_id, active_id) = (c.id.clone(), c.id.clone(), c.id.clone());
+                            let is_active = move || conversation.active.get().as_deref() == Some(active_id.as_str());
+                            view! {
+                                <div class=move || format!(
+                                    "group flex items-center gap-1 px-2 py-1 text-xs hover:bg-hover {}",
+                                    if is_active() { "bg-hover" } else { "" }
+                                )>
+                                    <button
+                                        class="flex-1 min-w-0 text-left truncate text-primary"
+                                        title=c.title.clone()
+                                        on:click=move |_| open_conversation.run(open_id.clone())
+                                    >
+                                        {c.title.clone()}
+                                    </button>
+                                    <span class="text-[10px] text-muted">{c.message_count}</span>
+                                    <button
+                                        class="p-0.5 rounded text-muted hover:text-red-600"
+                                        title=move || t::chat::delete_conversation(locale.get())
+                                        aria-label=move || t::chat::delete_conversation(locale.get())
+                                        on:click=move |_| delete_conversation.run(delete_id.clone())
+                                    >
+                                        <X class="w-3 h-3" />
+                                    </button>
+                                </div>
+                            }
+                        }
+                    />
+                </div>
+            </Show>
+        </div>
+    }
+}
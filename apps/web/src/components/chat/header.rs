@@ -1,11 +1,12 @@
 // apps/web/src/components/chat/header.rs
+use crate::components::chat::conversations::ConversationMenu;
 use crate::components::icons::*;
 use crate::i18n::{Locale, t};
 use leptos::prelude::*;
 
 #[component]
 pub fn ChatHeader(
-    #[prop(optional)] _ai_mode: Option<ReadSignal<String>>,
+    #[prop(optional)] ai_mode: Option<ReadSignal<String>>,
     #[prop(optional)] mobile: bool,
     on_close: Callback<()>,
 ) -> impl IntoView {
@@ -25,6 +26,10 @@ pub fn ChatHeader(
                 {move || t::chat::agent_bridge(locale.get())}
             </span>
             <div class="flex-1"></div>
+            // 会话历史仅在 API 模式 (服务端智能体) 下可用
+            <Show when=move || ai_mode.is_some_and(|m| m.get() == "ai-chat")>
+                <ConversationMenu mobile=mobile />
+            </Show>
             {move || if mobile {
                 view! {
                     <button
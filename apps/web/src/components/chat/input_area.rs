@@ -1,4 +1,5 @@
 // apps/web/src/components/chat/input_area.rs
+use crate::components::chat::mcp_context::{ChatMcp, command_name, matching_prompts, slash_filter};
 use crate::components::icons::*;
 use crate::i18n::{Locale, t};
 use leptos::prelude::*;
@@ -9,9 +10,54 @@ pub fn InputArea(
     set_input: WriteSignal<String>,
     is_streaming: ReadSignal<bool>,
     send_message: Callback<()>,
+    #[prop(optional)] mcp: Option<ChatMcp>,
     #[prop(optional)] mobile: bool,
 ) -> impl IntoView {
     let locale = use_context::<RwSignal<Locale>>().expect("locale context");
+    let (show_resources, set_show_resources) = signal(false);
+
+    // 斜杠命令候选: (命令名, 描述)
+    let suggestions = Memo::new(move |_| {
+        let Some(mcp) = mcp else {
+            return Vec::new();
+        };
+        let text = input.get();
+        let Some(filter) = slash_filter(&text) else {
+            return Vec::new();
+        };
+        let prompts = mcp.prompts.get();
+        matching_prompts(&prompts, filter)
+            .iter()
+            .map(|p| {
+                (
+                    command_name(p, &prompts),
+                    p.prompt.description.clone().unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>()
+    });
+    let complete = move |name: String| set_input.set(format!("/{} ", name));
+
+    let has_resources = move || mcp.is_some_and(|m| !m.resources.get().is_empty());
+    let attached = move || mcp.map(|m| m.attached.get()).unwrap_or_default();
+    let toggle_resource = move |server: String, uri: String| {
+        let Some(mcp) = mcp else { return };
+        let resources = mcp.resources.get_untracked();
+        mcp.attached.update(|list| {
+            if let Some(pos) = list
+                .iter()
+                .position(|r| r.server == server && r.resource.uri == uri)
+            {
+                list.remove(pos);
+            } else if let Some(found) = resources
+                .iter()
+                .find(|r| r.server == server && r.resource.uri == uri)
+            {
+                list.push(found.clone());
+            }
+        });
+    };
+
     view! {
         <div
             class=move || if mobile {
@@ -22,6 +68,62 @@ pub fn InputArea(
             style=move || if mobile { "padding-bottom: calc(8px + env(safe-area-inset-bottom));" } else { "" }
         >
             <div class="relative rounded border border-default bg-input focus-within:border-b-accent transition-colors">
+                <Show when=move || !suggestions.get().is_empty()>
+                    <div class="absolute bottom-full left-0 right-0 mb-1 max-h-48 overflow-y-auto rounded border border-default bg-panel shadow-lg z-10">
+                        <div class="px-2 py-1 text-[10px] uppercase text-muted">
+                            {move || t::chat::prompt_commands(locale.get())}
+                        </div>
+                        <For
+                            each=move || suggestions.get()
+                            key=|(name, _)| name.clone()
+                            children=move |(name, description)| {
+                                let label = format!("/{}", name);
+                                view! {
+                                    <button
+                                        class="w-full text-left px-2 py-1 text-xs hover:bg-hover flex gap-2"
+                                        on:mousedown=move |ev| {
+                                            ev.prevent_default();
+                                            complete(name.clone());
+                                        }
+                                    >
+                                        <span class="font-mono text-accent">{label}</span>
+                                        <span class="text-muted truncate">{description}</span>
+                                    </button>
+                                }
+                            }
+                        />
+                    </div>
+                </Show>
+                <Show when=move || !attached().is_empty()>
+                    <div class="flex flex-wrap gap-1 px-2 pt-2">
+                        <For
+                            each=attached
+                            key=|r| (r.server.clone(), r.resource.uri.clone())
+                            children=move |r| {
+                                let label = if r.resource.name.is_empty() {
+                                    r.resource.uri.clone()
+                                } else {
+                                    r.resource.name.clone()
+                                };
+                                let (server, uri) = (r.server.clone(), r.resource.uri.clone());
+                                view! {
+                                    <span class="inline-flex items-center gap-1 rounded bg-hover px-1.5 py-0.5 text-[11px] text-primary" title=r.resource.uri.clone()>
+                                        <Paperclip class="w-3 h-3" />
+                                        {label}
+                                        <button
+                                            class="text-muted hover:text-primary"
+                                            title=move || t::chat::remove_attachment(locale.get())
+                                            aria-label=move || t::chat::remove_attachment(locale.get())
+                                            on:click=move |_| toggle_resource(server.clone(), uri.clone())
+                                        >
+                                            <X class="w-3 h-3" />
+                                        </button>
+                                    </span>
+                                }
+                            }
+                        />
+                    </div>
+                </Show>
                 <textarea
                     name="ai-chat-input"
                     class="w-full max-h-32 p-2 bg-transparent border-none outline-none text-sm resize-none text-primary font-sans"
@@ -34,6 +136,16 @@ pub fn InputArea(
                         move |ev| {
                             if ev.key() == "Enter" && !ev.shift_key() {
                                 ev.prevent_default();
+                                // 命令尚未输完整时 Enter 先补全首个候选
+                                let text = input.get_untracked();
+                                let candidates = suggestions.get_untracked();
+                                let typed = slash_filter(&text).unwrap_or_default();
+                                if let Some((first, _)) = candidates.first()
+                                    && !candidates.iter().any(|(name, _)| name == typed)
+                                {
+                                    complete(first.clone());
+                                    return;
+                                }
                                 send_message.run(());
                             }
                         }
@@ -41,19 +153,72 @@ pub fn InputArea(
                 ></textarea>
                 <div class="flex justify-between items-center px-2 pb-2">
                     <span class="text-[10px] text-muted">{move || t::chat::markdown_supported(locale.get())}</span>
-                    <button
-                        class=move || if mobile {
-                            "h-11 min-w-11 p-2 rounded active:bg-hover text-accent disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
-                        } else {
-                            "p-1.5 rounded hover:bg-hover text-accent disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
-                        }
-                        disabled=move || input.get().trim().is_empty() || is_streaming.get()
-                        on:click=move |_| send_message.run(())
-                        title=move || t::chat::send(locale.get())
-                        aria-label=move || t::chat::send(locale.get())
-                    >
-                        <Send />
-                    </button>
+                    <div class="relative flex items-center gap-1">
+                        <Show when=has_resources>
+                            <button
+                                class=move || if mobile {
+                                    "h-11 min-w-11 p-2 rounded active:bg-hover text-muted transition-colors"
+                                } else {
+                                    "p-1.5 rounded hover:bg-hover text-muted transition-colors"
+                                }
+                                on:click=move |_| set_show_resources.update(|v| *v = !*v)
+                                title=move || t::chat::attach_context(locale.get())
+                                aria-label=move || t::chat::attach_context(locale.get())
+                            >
+                                <Paperclip />
+                            </button>
+                        </Show>
+                        <Show when=move || show_resources.get() && has_resources()>
+                            <div class="absolute bottom-full right-0 mb-1 w-64 max-h-60 overflow-y-auto rounded border border-default bg-panel shadow-lg z-10">
+                                <For
+                                    each=move || mcp.map(|m| m.resources.get()).unwrap_or_default()
+                                    key=|r| (r.server.clone(), r.resource.uri.clone())
+                                    children=move |r| {
+                                        let (server, uri) = (r.server.clone(), r.resource.uri.clone());
+                                        let (check_server, check_uri) = (server.clone(), uri.clone());
+                                        let selected = move || {
+                                            attached()
+                                                .iter()
+                                                .any(|a| a.server == check_server && a.resource.uri == check_uri)
+                                        };
+                                        let label = if r.resource.name.is_empty() {
+                                            r.resource.uri.clone()
+                                        } else {
+                                            r.resource.name.clone()
+                                        };
+                                        view! {
+                                            <button
+                                                class="w-full text-left px-2 py-1 text-xs hover:bg-hover flex items-center gap-2"
+                                                title=r.resource.uri.clone()
+                                                on:click=move |_| toggle_resource(server.clone(), uri.clone())
+                                            >
+                                                <span class="w-3 h-3 shrink-0 text-accent">
+                                                    <Show when=selected.clone()>
+                                                        <Check class="w-3 h-3" />
+                                                    </Show>
+                                                </span>
+                                                <span class="truncate text-primary">{label}</span>
+                                                <span class="ml-auto text-[10px] text-muted">{r.server.clone()}</span>
+                                            </button>
+                                        }
+                                    }
+                                />
+                            </div>
+                        </Show>
+                        <button
+                            class=move || if mobile {
+                                "h-11 min-w-11 p-2 rounded active:bg-hover text-accent disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+                            } else {
+                                "p-1.5 rounded hover:bg-hover text-accent disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+                            }
+                            disabled=move || input.get().trim().is_empty() || is_streaming.get()
+                            on:click=move |_| send_message.run(())
+                            title=move || t::chat::send(locale.get())
+                            aria-label=move || t::chat::send(locale.get())
+                        >
+                            <Send />
+                        </button>
+                    </div>
                 </div>
             </div>
         </div>
@@ -0,0 +1,151 @@
+// apps/web/src/components/chat/mcp_context.rs
+//! # MCP 斜杠命令与附加上下文
+//!
+//! 提示词以 `/name` 或 `/server:name` 调用；参数写作 `key=value`，
+//! 其余文本填入第一个未赋值的参数。资源以 `{server, uri, name}` 附加到聊天上下文。
+
+use deve_core::protocol::{McpPromptInfo, McpResourceInfo};
+use leptos::prelude::*;
+use serde_json::{Map, Value, json};
+
+/// 聊天输入的 MCP 状态: 可用目录与当前附加的资源 (发送后清空)
+#[derive(Clone, Copy)]
+pub struct ChatMcp {
+    pub prompts: ReadSignal<Vec<McpPromptInfo>>,
+    pub resources: ReadSignal<Vec<McpResourceInfo>>,
+    pub attached: RwSignal<Vec<McpResourceInfo>>,
+}
+
+/// 输入以 `/` 开头且尚未输入空白时，返回用于过滤命令的关键字
+pub fn slash_filter(input: &str) -> Option<&str> {
+    let rest = input.strip_prefix('/')?;
+    (!rest.contains(char::is_whitespace)).then_some(rest)
+}
+
+/// 命令名: 提示词名在多个服务器间重名时带服务器前缀
+pub fn command_name(prompt: &McpPromptInfo, all: &[McpPromptInfo]) -> String {
+    let duplicated = all
+        .iter()
+        .filter(|p| p.prompt.name == prompt.prompt.name)
+        .count()
+        > 1;
+    if duplicated {
+        format!("{}:{}", prompt.server, prompt.prompt.name)
+    } else {
+        prompt.prompt.name.clone()
+    }
+}
+
+/// 按前缀过滤可用命令
+pub fn matching_prompts(prompts: &[McpPromptInfo], filter: &str) -> Vec<McpPromptInfo> {
+    let filter = filter.to_lowercase();
+    prompts
+        .iter()
+        .filter(|p| command_name(p, prompts).to_lowercase().starts_with(&filter))
+        .cloned()
+        .collect()
+}
+
+/// 将 `/name args` 解析为提示词调用 `{server, name, arguments}` (非命令或未知命令返回 None)
+pub fn resolve_slash(input: &str, prompts: &[McpPromptInfo]) -> Option<Value> {
+    let body = input.trim().strip_prefix('/')?;
+    let (command, rest) = body.split_once(char::is_whitespace).unwrap_or((body, ""));
+    let prompt = prompts.iter().find(|p| {
+        command_name(p, prompts) == command || format!("{}:{}", p.server, p.prompt.name) == command
+    })?;
+
+    let declared = &prompt.prompt.arguments;
+    let mut arguments = Map::new();
+    let mut free_text = Vec::new();
+    for token in rest.split_whitespace() {
+        match token.split_once('=') {
+            Some((key, value)) if declared.iter().any(|a| a.name == key) => {
+                arguments.insert(key.to_string(), json!(value));
+            }
+            _ => free_text.push(token),
+        }
+    }
+    if !free_text.is_empty()
+        && let Some(arg) = declared.iter().find(|a| !arguments.contains_key(&a.name))
+    {
+        arguments.insert(arg.name.clone(), json!(free_text.join(" ")));
+    }
+
+    Some(json!({
+        "server": prompt.server,
+        "name": prompt.prompt.name,
+        "arguments": arguments,
+    }))
+}
+
+/// 附加资源的聊天上下文条目
+pub fn resource_context(resources: &[McpResourceInfo]) -> Value {
+    Value::Array(
+        resources
+            .iter()
+            .map(|r| {
+                json!({
+                    "server": r.server,
+                    "uri": r.resource.uri,
+                    "name": r.resource.name,
+                })
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deve_core::protocol::{McpPrompt, McpPromptArgument};
+
+    fn prompt(server: &str, name: &str, args: &[&str]) -> McpPromptInfo {
+        McpPromptInfo {
+            server: server.into(),
+            prompt: McpPrompt {
+                name: name.into(),
+                description: None,
+                arguments: args
+                    .iter()
+                    .map(|a| McpPromptArgument {
+                        name: a.to_string(),
+                        description: None,
+                        required: true,
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_slash_filter() {
+        assert_eq!(slash_filter("/rev"), Some("rev"));
+        assert_eq!(slash_filter("/"), Some(""));
+        assert_eq!(slash_filter("/review topic"), None);
+        assert_eq!(slash_filter("hello"), None);
+    }
+
+    #[test]
+    fn test_resolve_slash() {
+        let prompts = vec![
+            prompt("a", "review", &["topic", "tone"]),
+            prompt("a", "summary", &[]),
+            prompt("b", "summary", &[]),
+        ];
+        assert_eq!(command_name(&prompts[1], &prompts), "a:summary");
+        assert_eq!(matching_prompts(&prompts, "re").len(), 1);
+
+        let call = resolve_slash("/review tone=terse the new plan", &prompts).unwrap();
+        assert_eq!(call["server"], "a");
+        assert_eq!(call["arguments"]["tone"], "terse");
+        assert_eq!(call["arguments"]["topic"], "the new plan");
+
+        let call = resolve_slash("/b:summary", &prompts).unwrap();
+        assert_eq!(call["server"], "b");
+        assert_eq!(call["arguments"], json!({}));
+
+        assert!(resolve_slash("/summary", &prompts).is_none());
+        assert!(resolve_slash("/unknown x", &prompts).is_none());
+        assert!(resolve_slash("no command", &prompts).is_none());
+    }
+}
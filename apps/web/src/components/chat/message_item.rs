@@ -1,5 +1,5 @@
 // apps/web/src/components/chat/message_item.rs
-use crate::hooks::use_core::types::ChatMessage;
+use crate::hooks::use_core::types::{ChatMessage, ToolActivity};
 use crate::i18n::{Locale, t};
 use crate::utils::markdown::render_markdown;
 use leptos::prelude::*;
@@ -23,8 +23,54 @@ fn handle_link_click(ev: web_sys::MouseEvent) {
     }
 }
 
+/// 工具调用：折叠块展示参数与结果
+#[component]
+fn ToolItem(tool: ToolActivity, #[prop(optional)] mobile: bool) -> impl IntoView {
+    let locale = use_context::<RwSignal<Locale>>().expect("locale context");
+    let status_class = match (&tool.result, tool.is_error) {
+        (None, _) => "text-muted",
+        (Some(_), true) => "text-red-600",
+        (Some(_), false) => "text-badge-success",
+    };
+    let status = match (&tool.result, tool.is_error) {
+        (None, _) => "…",
+        (Some(_), true) => "✕",
+        (Some(_), false) => "✓",
+    };
+    let arguments = tool.arguments.clone();
+    let result = tool.result.clone();
+
+    view! {
+        <details class={format!("rounded border border-default bg-panel px-2 py-1 text-xs self-start {}",
+            if mobile { "max-w-[96%] mr-3" } else { "max-w-[90%] mr-8" }
+        )}>
+            <summary class="cursor-pointer select-none flex items-center gap-2 text-secondary">
+                <span class={format!("font-bold {}", status_class)}>{status}</span>
+                <span class="text-muted">{move || t::chat::tool_call(locale.get())}</span>
+                <span class="font-mono truncate">{tool.name.clone()}</span>
+            </summary>
+            <div class="mt-1 text-[10px] uppercase text-muted">{move || t::chat::tool_arguments(locale.get())}</div>
+            <pre class="whitespace-pre-wrap break-all font-mono text-[11px] text-primary">{arguments}</pre>
+            <div class="mt-1 text-[10px] uppercase text-muted">{move || t::chat::tool_result(locale.get())}</div>
+            {match result {
+                Some(text) => view! {
+                    <pre class={format!("whitespace-pre-wrap break-all font-mono text-[11px] max-h-60 overflow-y-auto {}",
+                        if tool.is_error { "text-red-600" } else { "text-primary" }
+                    )}>{text}</pre>
+                }.into_any(),
+                None => view! {
+                    <div class="text-[11px] text-muted animate-pulse">{move || t::chat::tool_running(locale.get())}</div>
+                }.into_any(),
+            }}
+        </details>
+    }
+}
+
 #[component]
 pub fn MessageItem(msg: ChatMessage, #[prop(optional)] mobile: bool) -> impl IntoView {
+    if let Some(tool) = msg.tool {
+        return view! { <ToolItem tool=tool mobile=mobile /> }.into_any();
+    }
     let locale = use_context::<RwSignal<Locale>>().expect("locale context");
     let is_user = msg.role == "user";
     let content = msg.content.clone();
@@ -71,4 +117,5 @@ pub fn MessageItem(msg: ChatMessage, #[prop(optional)] mobile: bool) -> impl Int
             </div>
         </div>
     }
+    .into_any()
 }
@@ -65,7 +65,7 @@ pub fn MessageList(
                 view! {
                     <For
                         each=move || messages.get()
-                        key=|msg| msg.req_id.clone().unwrap_or_else(|| msg.content.chars().take(32).collect())
+                        key=|msg| msg.render_key()
                         children=move |msg| view! { <MessageItem msg=msg mobile=mobile /> }
                     />
                 }.into_any()
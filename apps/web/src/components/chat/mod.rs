@@ -1,9 +1,11 @@
 pub mod actions;
+pub mod conversations;
 pub mod drag_overlay;
 pub mod drop_handler;
 pub mod empty_state;
 pub mod header;
 pub mod input_area;
+pub mod mcp_context;
 pub mod message_item;
 pub mod message_list;
 pub mod panel;
@@ -6,9 +6,12 @@ use crate::components::chat::drag_overlay::DragOverlay;
 use crate::components::chat::drop_handler::{on_drag_leave, on_drag_over, on_drop};
 use crate::components::chat::header::ChatHeader;
 use crate::components::chat::input_area::InputArea;
+use crate::components::chat::mcp_context::ChatMcp;
 use crate::components::chat::message_list::MessageList;
-use crate::hooks::use_core::CoreState;
+use crate::components::icons::Square;
+use crate::hooks::use_core::{ConversationContext, CoreState, McpContext};
 use crate::i18n::{Locale, t};
+use deve_core::protocol::ClientMessage;
 use leptos::prelude::*;
 
 #[component]
@@ -21,7 +24,15 @@ pub fn ChatPanel(#[prop(optional)] mobile: bool, on_close: Callback<()>) -> impl
     let (error_msg, set_error_msg) = signal(None::<String>);
     let (pending_reqs, set_pending_reqs) = signal(Vec::<String>::new());
 
+    let mcp_ctx = expect_context::<McpContext>();
+    let mcp = ChatMcp {
+        prompts: mcp_ctx.prompts,
+        resources: mcp_ctx.resources,
+        attached: RwSignal::new(Vec::new()),
+    };
+
     let messages = core.chat_messages;
+    let ai_mode = core.ai_mode;
     let is_streaming = core.is_chat_streaming;
 
     let on_req_id = Callback::new(move |req_id: String| {
@@ -37,10 +48,12 @@ pub fn ChatPanel(#[prop(optional)] mobile: bool, on_close: Callback<()>) -> impl
         is_streaming,
         Some(on_req_id),
         Some(on_user_text),
+        Some(mcp),
     );
     let send_message = make_send_message(input, set_input, is_streaming, send_text.clone());
     let send_example = make_send_example(send_text.clone(), set_input);
     let on_apply = make_on_apply(core.clone());
+    let ws = core.ws.clone();
     let retry = Callback::new(move |_| {
         let prompt = last_prompt.get_untracked();
         if !prompt.is_empty() {
@@ -62,6 +75,35 @@ pub fn ChatPanel(#[prop(optional)] mobile: bool, on_close: Callback<()>) -> impl
         }
     });
 
+    // 服务端智能体运行结束：清理挂起请求，失败/中止原因显示在错误条
+    let conversation = expect_context::<ConversationContext>();
+    Effect::new(move |_| {
+        let Some(done) = conversation.last_done.get() else {
+            return;
+        };
+        let matched = pending_reqs
+            .get_untracked()
+            .iter()
+            .any(|id| id == &done.req_id);
+        if !matched {
+            return;
+        }
+        set_pending_reqs.update(|v| v.retain(|id| id != &done.req_id));
+        let message = match done.reason.as_str() {
+            "error" => done.error,
+            "max_rounds" => Some(t::chat::max_rounds_reached(locale.get_untracked()).to_string()),
+            _ => None,
+        };
+        if message.is_some() {
+            set_error_msg.set(message);
+        }
+    });
+    let stop = Callback::new(move |_: ()| {
+        if let Some(req_id) = conversation.running.get_untracked() {
+            ws.send(ClientMessage::ChatCancel { req_id });
+        }
+    });
+
     let loading = Signal::derive(move || is_streaming.get() || !pending_reqs.get().is_empty());
 
     view! {
@@ -76,7 +118,7 @@ pub fn ChatPanel(#[prop(optional)] mobile: bool, on_close: Callback<()>) -> impl
             on:drop=on_drop(set_input, set_is_drag_over)
         >
             <DragOverlay is_drag_over=is_drag_over />
-            <ChatHeader mobile=mobile on_close=on_close />
+            <ChatHeader ai_mode=ai_mode mobile=mobile on_close=on_close />
             <MessageList
                 messages=messages
                 is_streaming=is_streaming
@@ -101,13 +143,26 @@ pub fn ChatPanel(#[prop(optional)] mobile: bool, on_close: Callback<()>) -> impl
                 </div>
             </Show>
             <Show when=move || loading.get()>
-                <div class="px-3 pb-1 text-[11px] text-muted">{move || t::chat::loading(locale.get())}</div>
+                <div class="px-3 pb-1 text-[11px] text-muted flex items-center gap-2">
+                    <span class="flex-1 min-w-0 truncate">{move || t::chat::loading(locale.get())}</span>
+                    <Show when=move || conversation.running.get().is_some()>
+                        <button
+                            class="h-6 px-2 rounded border border-default bg-panel text-secondary hover:bg-hover flex items-center gap-1"
+                            on:click=move |_| stop.run(())
+                            title=move || t::chat::stop(locale.get())
+                        >
+                            <Square class="w-3 h-3" />
+                            <span>{move || t::chat::stop(locale.get())}</span>
+                        </button>
+                    </Show>
+                </div>
             </Show>
             <InputArea
                 input=input
                 set_input=set_input
                 is_streaming=is_streaming
                 send_message=send_message
+                mcp=mcp
                 mobile=mobile
             />
         </div>
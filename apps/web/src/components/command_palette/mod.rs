@@ -10,7 +10,8 @@ mod types;
 
 pub use types::Command;
 
-use self::registry::{create_static_commands, filter_commands};
+use self::registry::{create_plugin_commands, create_static_commands, filter_commands};
+use crate::hooks::use_core::PluginUiContext;
 use crate::i18n::{Locale, t};
 use leptos::prelude::*;
 
@@ -22,6 +23,7 @@ pub fn CommandPalette(
     on_open: Callback<()>, // Opens the Open Document modal
 ) -> impl IntoView {
     let locale = use_context::<RwSignal<Locale>>().expect("locale context");
+    let plugin_ui = use_context::<PluginUiContext>();
     let (query, set_query) = signal(String::new());
     let (selected_index, set_selected_index) = signal(0);
 
@@ -37,11 +39,18 @@ pub fn CommandPalette(
         let q = query.get();
         let current_locale = locale.get();
 
-        let static_cmds =
+        let mut cmds =
             create_static_commands(current_locale, on_settings, on_open, set_show, locale);
+        if let Some(ctx) = plugin_ui {
+            cmds.extend(create_plugin_commands(
+                &ctx.plugins.get(),
+                ctx.on_plugin_call,
+                set_show,
+            ));
+        }
 
-        // 仅过滤静态命令，不包含文件命令
-        filter_commands(&q, static_cmds, 50)
+        // 仅过滤静态命令与插件命令，不包含文件命令
+        filter_commands(&q, cmds, 50)
     });
 
     // 验证索引
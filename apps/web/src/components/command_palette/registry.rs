@@ -1,9 +1,10 @@
 // apps\web\src\components\command_palette
-//! 命令面板的静态命令定义。
+//! 命令面板的静态命令与插件命令定义。
 
 use super::types::Command;
-use crate::components::main_layout::ChatControl;
+use crate::components::main_layout::{ChatControl, GraphControl};
 use crate::i18n::{Locale, t};
+use deve_core::plugin::ui::PluginInfo;
 use leptos::prelude::*;
 
 /// 创建静态命令列表。
@@ -16,6 +17,7 @@ pub fn create_static_commands(
 ) -> Vec<Command> {
     // Try to get ChatControl from context at creation time
     let chat_control = use_context::<ChatControl>();
+    let graph_control = use_context::<GraphControl>();
 
     let mut commands = vec![
         // 打开文档命令 - 打开文档模态框
@@ -107,9 +109,48 @@ pub fn create_static_commands(
         });
     }
 
+    if let Some(graph_ctrl) = graph_control {
+        commands.push(Command {
+            id: "toggle_graph".to_string(),
+            title: (t::command_palette::toggle_graph)(locale).to_string(),
+            action: Callback::new(move |_| {
+                graph_ctrl.set_graph_visible.update(|v| *v = !*v);
+                set_show.set(false);
+            }),
+            is_file: false,
+        });
+    }
+
     commands
 }
 
+/// 创建插件在清单 `ui.commands` 中声明的命令 (执行时以无参数调用处理函数)。
+pub fn create_plugin_commands(
+    plugins: &[PluginInfo],
+    on_plugin_call: Callback<(String, String, String, Vec<serde_json::Value>)>,
+    set_show: WriteSignal<bool>,
+) -> Vec<Command> {
+    plugins
+        .iter()
+        .flat_map(|plugin| {
+            plugin.ui.commands.iter().map(move |cmd| {
+                let plugin_id = plugin.id.clone();
+                let handler = cmd.handler.clone();
+                Command {
+                    id: format!("plugin:{}:{}", plugin.id, cmd.id),
+                    title: format!("{}: {}", plugin.name, cmd.title),
+                    action: Callback::new(move |_| {
+                        let req_id = uuid::Uuid::new_v4().to_string();
+                        on_plugin_call.run((req_id, plugin_id.clone(), handler.clone(), vec![]));
+                        set_show.set(false);
+                    }),
+                    is_file: false,
+                }
+            })
+        })
+        .collect()
+}
+
 /// 基于查询字符串筛选命令。
 pub fn filter_commands(query: &str, commands: Vec<Command>, max_results: usize) -> Vec<Command> {
     let q = query.to_lowercase();
@@ -0,0 +1,115 @@
+// apps/web/src/components/dashboard/mcp_card.rs
+//! # MCP Servers Card (MCP 服务器卡片)
+//!
+//! 挂载时请求 `GET /api/mcp/status`，之后每 `POLL_MS` 毫秒刷新一次。
+//! 显示各服务器的连接状态、目录规模，以及本地进程的重启次数与在途请求数。
+
+use crate::api::WsService;
+use deve_core::protocol::McpServerReport;
+use gloo_net::http::Request;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::time::Duration;
+
+/// 状态刷新间隔
+const POLL_MS: u64 = 10_000;
+
+fn state_class(state: &str) -> &'static str {
+    match state {
+        "connected" => "text-green-500",
+        "restarting" => "text-yellow-500",
+        "failed" => "text-red-500",
+        _ => "text-muted",
+    }
+}
+
+async fn fetch_status(base: String) -> Option<Vec<McpServerReport>> {
+    let resp = Request::get(&format!("{}/api/mcp/status", base))
+        .send()
+        .await
+        .ok()?;
+    if !resp.ok() {
+        return None;
+    }
+    resp.json::<Vec<McpServerReport>>().await.ok()
+}
+
+#[component]
+pub fn McpCard() -> impl IntoView {
+    let ws = expect_context::<WsService>();
+    let (servers, set_servers) = signal(None::<Vec<McpServerReport>>);
+
+    let refresh = move || {
+        let base = ws.http_base();
+        spawn_local(async move {
+            if let Some(list) = fetch_status(base).await {
+                set_servers.set(Some(list));
+            }
+        });
+    };
+    refresh();
+    if let Ok(handle) = set_interval_with_handle(refresh, Duration::from_millis(POLL_MS)) {
+        on_cleanup(move || handle.clear());
+    }
+
+    view! {
+        <div class="bg-panel rounded-lg border border-default p-4">
+            <h3 class="text-sm font-semibold text-secondary mb-3">"MCP Servers"</h3>
+            {move || match servers.get() {
+                None => view! {
+                    <div class="text-xs text-muted">"Loading..."</div>
+                }
+                .into_any(),
+                Some(list) if list.is_empty() => view! {
+                    <div class="text-xs text-muted">"No MCP servers configured"</div>
+                }
+                .into_any(),
+                Some(list) => view! {
+                    <ul class="space-y-2">
+                        {list.into_iter().map(server_row).collect_view()}
+                    </ul>
+                }
+                .into_any(),
+            }}
+        </div>
+    }
+}
+
+fn server_row(server: McpServerReport) -> impl IntoView {
+    let catalog = format!(
+        "{} tools · {} resources · {} prompts",
+        server.tools, server.resources, server.prompts
+    );
+    let session = server.session.map(|s| {
+        let mut parts = vec![
+            format!("{}/{} in flight", s.in_flight, s.max_in_flight),
+            format!("{} restarts", s.restarts),
+        ];
+        if let Some(pid) = s.pid {
+            parts.insert(0, format!("pid {}", pid));
+        }
+        if let Some(ms) = s.retry_in_ms {
+            parts.push(format!("retry in {}s", ms.div_ceil(1000)));
+        }
+        parts.join(" · ")
+    });
+
+    view! {
+        <li class="space-y-0.5">
+            <div class="flex justify-between items-center gap-2">
+                <span class="text-xs text-primary truncate">
+                    {server.name}
+                    <span class="ml-1 text-[10px] text-muted">{server.kind}</span>
+                </span>
+                <span class={format!("text-xs font-mono font-semibold {}", state_class(&server.state))}>
+                    {server.state.clone()}
+                </span>
+            </div>
+            <div class="text-[11px] text-muted">{catalog}</div>
+            {session.map(|text| view! { <div class="text-[11px] font-mono text-muted">{text}</div> })}
+            {server.reason.map(|reason| view! {
+                <div class="text-[11px] text-red-500 truncate" title=reason.clone()>{reason.clone()}</div>
+            })}
+        </li>
+    }
+}
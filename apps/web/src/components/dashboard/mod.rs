@@ -1,23 +1,29 @@
 // apps/web/src/components/dashboard/mod.rs
 //! # Dashboard (仪表盘)
 //!
-//! 当没有文档被选中时，在主内容区显示服务器运行指标。
+//! 当没有文档被选中时，在主内容区显示服务器运行指标、MCP 服务器状态、属性查询与断链报告卡片。
 //!
 //! **Invariant**: 所有指标仅存于 RAM 信号中，不持久化到 IndexedDB。
 //! 当 WebSocket 断开时，指标冻结并显示 "Waiting for server..." 提示。
 
 mod actions_card;
 mod health_card;
+mod mcp_card;
+mod query_card;
 mod storage_card;
 mod sync_card;
+mod unresolved_card;
 
 use crate::hooks::use_core::DashboardContext;
 use leptos::prelude::*;
 
 use self::actions_card::ActionsCard;
 use self::health_card::HealthCard;
+use self::mcp_card::McpCard;
+use self::query_card::QueryCard;
 use self::storage_card::StorageCard;
 use self::sync_card::SyncCard;
+use self::unresolved_card::UnresolvedCard;
 
 #[component]
 pub fn Dashboard() -> impl IntoView {
@@ -45,6 +51,13 @@ pub fn Dashboard() -> impl IntoView {
                         <ActionsCard />
                     }.into_any(),
                 }}
+                <McpCard />
+                <QueryCard
+                    title="Open Tasks"
+                    query="has due AND status != done SORT BY due LIMIT 8"
+                    column="due"
+                />
+                <UnresolvedCard />
             </div>
         </div>
     }
@@ -0,0 +1,73 @@
+// apps/web/src/components/dashboard/query_card.rs
+//! # Query Card (属性查询卡片)
+//!
+//! 挂载时发送一次 `QueryDocs`，按 `req_id` 读取结果并列出匹配文档。
+//! 点击条目打开对应文档。
+
+use crate::hooks::use_core::{DashboardContext, DocContext};
+use deve_core::protocol::DocQuery;
+use leptos::prelude::*;
+
+#[component]
+pub fn QueryCard(
+    title: &'static str,
+    /// `DocQuery` 文本语法
+    query: &'static str,
+    /// 每行右侧显示的属性
+    #[prop(optional)]
+    column: Option<&'static str>,
+) -> impl IntoView {
+    let ctx = expect_context::<DashboardContext>();
+    let doc_ctx = expect_context::<DocContext>();
+    let req_id = format!("dashboard:{title}");
+
+    match DocQuery::parse(query) {
+        Ok(q) => ctx.on_query_docs.run((req_id.clone(), q)),
+        Err(e) => leptos::logging::warn!("Invalid dashboard query '{}': {}", query, e),
+    }
+
+    let rows = Memo::new(move |_| {
+        ctx.doc_queries
+            .with(|map| map.get(&req_id).cloned().unwrap_or_default())
+    });
+
+    view! {
+        <div class="bg-panel rounded-lg border border-default p-4">
+            <h3 class="text-sm font-semibold text-secondary mb-3">{title}</h3>
+            {move || {
+                let rows = rows.get();
+                if rows.is_empty() {
+                    return view! {
+                        <div class="text-xs text-muted">"No matching documents"</div>
+                    }
+                    .into_any();
+                }
+                view! {
+                    <ul class="space-y-1">
+                        {rows
+                            .into_iter()
+                            .map(|row| {
+                                let doc_id = row.doc_id;
+                                let value = column
+                                    .and_then(|key| row.value(key))
+                                    .unwrap_or_default()
+                                    .to_string();
+                                view! {
+                                    <li
+                                        class="flex justify-between items-center gap-2 px-1 py-0.5 rounded \
+                                               cursor-pointer hover:bg-active"
+                                        on:click=move |_| doc_ctx.on_doc_select.run(doc_id)
+                                    >
+                                        <span class="text-xs text-primary truncate">{row.path}</span>
+                                        <span class="text-xs font-mono text-muted shrink-0">{value}</span>
+                                    </li>
+                                }
+                            })
+                            .collect_view()}
+                    </ul>
+                }
+                .into_any()
+            }}
+        </div>
+    }
+}
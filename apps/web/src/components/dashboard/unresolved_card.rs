@@ -0,0 +1,67 @@
+// apps/web/src/components/dashboard/unresolved_card.rs
+//! # Unresolved Links Card (断链报告卡片)
+//!
+//! 挂载时发送一次 `GetUnresolvedLinks`，列出指向不存在文档的链接。
+//! 点击条目打开来源文档并跳转到链接所在行。
+
+use crate::hooks::use_core::{DocContext, EditorContext, LinksContext};
+use leptos::prelude::*;
+
+/// 最多展示的断链条数
+const MAX_ROWS: usize = 8;
+
+#[component]
+pub fn UnresolvedCard() -> impl IntoView {
+    let ctx = expect_context::<LinksContext>();
+    let doc_ctx = expect_context::<DocContext>();
+    let editor = expect_context::<EditorContext>();
+
+    ctx.on_get_unresolved_links.run(());
+
+    view! {
+        <div class="bg-panel rounded-lg border border-default p-4">
+            <h3 class="text-sm font-semibold text-secondary mb-3">
+                "Unresolved Links"
+                {move || {
+                    let n = ctx.unresolved_links.with(|l| l.len());
+                    (n > 0).then(|| format!(" ({n})"))
+                }}
+            </h3>
+            {move || {
+                let links = ctx.unresolved_links.get();
+                if links.is_empty() {
+                    return view! {
+                        <div class="text-xs text-muted">"All links resolve"</div>
+                    }
+                    .into_any();
+                }
+                view! {
+                    <ul class="space-y-1">
+                        {links
+                            .into_iter()
+                            .take(MAX_ROWS)
+                            .map(|link| {
+                                let source = link.source;
+                                let line = link.line as usize;
+                                view! {
+                                    <li
+                                        class="flex justify-between items-center gap-2 px-1 py-0.5 rounded \
+                                               cursor-pointer hover:bg-active"
+                                        on:click=move |_| {
+                                            editor.set_pending_jump.set(Some((source, line)));
+                                            doc_ctx.on_doc_select.run(source);
+                                        }
+                                    >
+                                        <span class="text-xs text-primary truncate">{link.path}</span>
+                                        <span class="text-xs font-mono text-muted shrink-0">{link.target}</span>
+                                    </li>
+                                }
+                            })
+                            .collect_view()}
+                    </ul>
+                }
+                .into_any()
+            }}
+        </div>
+    }
+}
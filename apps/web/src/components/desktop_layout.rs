@@ -5,7 +5,9 @@ use crate::components::activity_bar::SidebarView;
 use crate::components::dashboard::Dashboard;
 use crate::components::desktop_chat_panel::DesktopChatPanel;
 use crate::components::diff_view::DiffView;
+use crate::components::graph_view::GraphView;
 use crate::components::header::Header;
+use crate::components::layout_context::GraphControl;
 use crate::editor::Editor;
 use crate::hooks::use_core::CoreState;
 use crate::hooks::use_layout::LayoutHookReturn;
@@ -37,6 +39,7 @@ pub fn DesktopLayout(
         _do_resize,
         _is_resizing,
     ) = layout;
+    let graph_visible = use_context::<GraphControl>().map(|g| g.graph_visible);
 
     view! {
         <Header
@@ -130,6 +133,9 @@ pub fn DesktopLayout(
                         }
                         .into_any();
                     }
+                    if graph_visible.is_some_and(|v| v.get()) {
+                        return view! { <GraphView /> }.into_any();
+                    }
 
                     match core.current_doc.get() {
                         Some(id) => view! { <Editor doc_id=id on_stats=core.on_stats /> }.into_any(),
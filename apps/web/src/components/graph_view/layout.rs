@@ -0,0 +1,363 @@
+// apps/web/src/components/graph_view/layout.rs
+//! # 力导向布局 (Force-Directed Layout)
+//!
+//! d3-force 风格的模拟: Barnes-Hut 四叉树近似斥力 (O(n log n))、链接弹簧、
+//! 向心力，按 alpha 冷却至静止。不依赖浏览器 API，可在原生目标上测试。
+//!
+//! ## Invariants
+//! - `x` / `y` 长度始终等于节点数，且模拟过程中保持有限值
+
+use std::f32::consts::PI;
+
+/// Barnes-Hut 近似阈值 θ² (θ = 0.9)
+const THETA2: f32 = 0.81;
+/// 节点间斥力强度 (负值为排斥)
+const CHARGE: f32 = -30.0;
+/// 链接目标长度
+const LINK_DISTANCE: f32 = 30.0;
+/// 向原点的拉力，避免不连通分量漂远
+const CENTER_STRENGTH: f32 = 0.01;
+const VELOCITY_DECAY: f32 = 0.4;
+/// 约 300 次迭代从 1 冷却到 `ALPHA_MIN`
+const ALPHA_DECAY: f32 = 0.0228;
+const ALPHA_MIN: f32 = 0.001;
+/// 距离平方下限，避免近距离斥力爆炸
+const MIN_DIST2: f32 = 1.0;
+/// 四叉树最大深度，重合点在此深度合并为一个桶
+const MAX_DEPTH: u32 = 24;
+
+const NONE: u32 = u32::MAX;
+
+pub struct Simulation {
+    pub x: Vec<f32>,
+    pub y: Vec<f32>,
+    vx: Vec<f32>,
+    vy: Vec<f32>,
+    edges: Vec<(usize, usize)>,
+    degree: Vec<u32>,
+    alpha: f32,
+    tree: QuadTree,
+}
+
+impl Simulation {
+    /// `seed(i)` 返回已知位置 (沿用上一次布局)，其余节点按向日葵螺旋排布
+    pub fn new(
+        n: usize,
+        edges: Vec<(usize, usize)>,
+        seed: impl Fn(usize) -> Option<(f32, f32)>,
+    ) -> Self {
+        let golden = PI * (3.0 - 5f32.sqrt());
+        let (mut x, mut y) = (Vec::with_capacity(n), Vec::with_capacity(n));
+        let mut seeded = 0;
+        for i in 0..n {
+            let (px, py) = seed(i).inspect(|_| seeded += 1).unwrap_or_else(|| {
+                let r = 10.0 * (0.5 + i as f32).sqrt();
+                let a = i as f32 * golden;
+                (r * a.cos(), r * a.sin())
+            });
+            x.push(px);
+            y.push(py);
+        }
+        let edges: Vec<_> = edges
+            .into_iter()
+            .filter(|(s, t)| s != t && *s < n && *t < n)
+            .collect();
+        let mut degree = vec![0u32; n];
+        for (s, t) in &edges {
+            degree[*s] += 1;
+            degree[*t] += 1;
+        }
+        // 大部分节点沿用旧位置时只需轻微调整
+        let alpha = if n > 0 && seeded * 10 >= n * 9 {
+            0.3
+        } else {
+            1.0
+        };
+        Self {
+            x,
+            y,
+            vx: vec![0.0; n],
+            vy: vec![0.0; n],
+            edges,
+            degree,
+            alpha,
+            tree: QuadTree::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.x.len()
+    }
+
+    pub fn degree(&self, i: usize) -> u32 {
+        self.degree[i]
+    }
+
+    pub fn is_settled(&self) -> bool {
+        self.alpha < ALPHA_MIN
+    }
+
+    /// 推进一次迭代
+    pub fn tick(&mut self) {
+        let n = self.len();
+        if n == 0 {
+            self.alpha = 0.0;
+            return;
+        }
+        let alpha = self.alpha;
+
+        for &(s, t) in &self.edges {
+            let mut dx = self.x[t] + self.vx[t] - self.x[s] - self.vx[s];
+            let dy = self.y[t] + self.vy[t] - self.y[s] - self.vy[s];
+            if dx == 0.0 && dy == 0.0 {
+                dx = jiggle(s);
+            }
+            let l = (dx * dx + dy * dy).sqrt();
+            let (ds, dt) = (self.degree[s] as f32, self.degree[t] as f32);
+            let k = (l - LINK_DISTANCE) / l * alpha / ds.min(dt);
+            let (fx, fy) = (dx * k, dy * k);
+            let bias = ds / (ds + dt);
+            self.vx[t] -= fx * bias;
+            self.vy[t] -= fy * bias;
+            self.vx[s] += fx * (1.0 - bias);
+            self.vy[s] += fy * (1.0 - bias);
+        }
+
+        self.tree.build(&self.x, &self.y);
+        for i in 0..n {
+            let (fx, fy) = self.tree.force(i, self.x[i], self.y[i]);
+            self.vx[i] += (fx - self.x[i] * CENTER_STRENGTH) * alpha;
+            self.vy[i] += (fy - self.y[i] * CENTER_STRENGTH) * alpha;
+        }
+
+        for i in 0..n {
+            self.vx[i] *= 1.0 - VELOCITY_DECAY;
+            self.vy[i] *= 1.0 - VELOCITY_DECAY;
+            self.x[i] += self.vx[i];
+            self.y[i] += self.vy[i];
+        }
+        self.alpha -= alpha * ALPHA_DECAY;
+    }
+
+    /// 距 `(px, py)` 最近且在 `radius` 内的节点
+    pub fn nearest(&self, px: f32, py: f32, radius: f32) -> Option<usize> {
+        let mut best = None;
+        let mut best_d2 = radius * radius;
+        for i in 0..self.len() {
+            let d2 = (self.x[i] - px).powi(2) + (self.y[i] - py).powi(2);
+            if d2 <= best_d2 {
+                best_d2 = d2;
+                best = Some(i);
+            }
+        }
+        best
+    }
+
+    /// 全部节点的包围盒 `(min_x, min_y, max_x, max_y)`
+    pub fn bounds(&self) -> Option<(f32, f32, f32, f32)> {
+        if self.len() == 0 {
+            return None;
+        }
+        let fold = |v: &[f32]| {
+            v.iter()
+                .fold((f32::MAX, f32::MIN), |(lo, hi), &p| (lo.min(p), hi.max(p)))
+        };
+        let (min_x, max_x) = fold(&self.x);
+        let (min_y, max_y) = fold(&self.y);
+        Some((min_x, min_y, max_x, max_y))
+    }
+}
+
+/// 重合点的确定性微小偏移
+fn jiggle(i: usize) -> f32 {
+    ((i % 7) as f32 - 3.0 + 0.5) * 1e-3
+}
+
+#[derive(Clone, Copy)]
+struct Cell {
+    cx: f32,
+    cy: f32,
+    half: f32,
+    mass: f32,
+    /// 质量加权坐标和 (质心 = sum / mass)
+    sx: f32,
+    sy: f32,
+    children: [u32; 4],
+    /// 叶子持有的点 (桶叶子只记录第一个点)
+    point: u32,
+}
+
+impl Cell {
+    fn new(cx: f32, cy: f32, half: f32) -> Self {
+        Self {
+            cx,
+            cy,
+            half,
+            mass: 0.0,
+            sx: 0.0,
+            sy: 0.0,
+            children: [NONE; 4],
+            point: NONE,
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.children == [NONE; 4]
+    }
+}
+
+/// 复用内存的扁平四叉树
+#[derive(Default)]
+struct QuadTree {
+    cells: Vec<Cell>,
+    stack: Vec<u32>,
+}
+
+impl QuadTree {
+    fn build(&mut self, x: &[f32], y: &[f32]) {
+        self.cells.clear();
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+        for (&px, &py) in x.iter().zip(y) {
+            min_x = min_x.min(px);
+            min_y = min_y.min(py);
+            max_x = max_x.max(px);
+            max_y = max_y.max(py);
+        }
+        let half = ((max_x - min_x).max(max_y - min_y) / 2.0).max(1.0);
+        self.cells.push(Cell::new(
+            (min_x + max_x) / 2.0,
+            (min_y + max_y) / 2.0,
+            half,
+        ));
+        for i in 0..x.len() {
+            self.insert(i, x, y);
+        }
+    }
+
+    fn insert(&mut self, i: usize, x: &[f32], y: &[f32]) {
+        let (px, py) = (x[i], y[i]);
+        let mut c = 0;
+        let mut depth = 0;
+        loop {
+            let cell = &mut self.cells[c];
+            let empty = cell.mass == 0.0;
+            cell.mass += 1.0;
+            cell.sx += px;
+            cell.sy += py;
+            if cell.is_leaf() {
+                if empty {
+                    cell.point = i as u32;
+                    return;
+                }
+                if depth >= MAX_DEPTH {
+                    return;
+                }
+                let old = std::mem::replace(&mut cell.point, NONE);
+                if old != NONE {
+                    let o = old as usize;
+                    let child = self.child(c, x[o], y[o]);
+                    let cell = &mut self.cells[child];
+                    cell.mass = 1.0;
+                    cell.sx = x[o];
+                    cell.sy = y[o];
+                    cell.point = old;
+                }
+            }
+            c = self.child(c, px, py);
+            depth += 1;
+        }
+    }
+
+    /// `px, py` 所在象限的子节点 (不存在则创建)
+    fn child(&mut self, c: usize, px: f32, py: f32) -> usize {
+        let cell = self.cells[c];
+        let q = (px >= cell.cx) as usize | ((py >= cell.cy) as usize) << 1;
+        if cell.children[q] == NONE {
+            let h = cell.half / 2.0;
+            let cx = if q & 1 == 1 { cell.cx + h } else { cell.cx - h };
+            let cy = if q & 2 == 2 { cell.cy + h } else { cell.cy - h };
+            self.cells.push(Cell::new(cx, cy, h));
+            self.cells[c].children[q] = (self.cells.len() - 1) as u32;
+        }
+        self.cells[c].children[q] as usize
+    }
+
+    /// 节点 `i` 受到的斥力 (未乘 alpha)
+    fn force(&mut self, i: usize, px: f32, py: f32) -> (f32, f32) {
+        let (mut fx, mut fy) = (0.0, 0.0);
+        self.stack.clear();
+        self.stack.push(0);
+        while let Some(c) = self.stack.pop() {
+            let cell = self.cells[c as usize];
+            let (mut mass, mut sx, mut sy) = (cell.mass, cell.sx, cell.sy);
+            if cell.point == i as u32 {
+                mass -= 1.0;
+                sx -= px;
+                sy -= py;
+            }
+            if mass <= 0.0 {
+                continue;
+            }
+            let mut dx = sx / mass - px;
+            let dy = sy / mass - py;
+            if dx == 0.0 && dy == 0.0 {
+                dx = jiggle(i);
+            }
+            let d2 = (dx * dx + dy * dy).max(MIN_DIST2);
+            let size = cell.half * 2.0;
+            if cell.is_leaf() || size * size / d2 < THETA2 {
+                let k = CHARGE * mass / d2;
+                fx += dx * k;
+                fy += dy * k;
+            } else {
+                self.stack
+                    .extend(cell.children.iter().copied().filter(|&ch| ch != NONE));
+            }
+        }
+        (fx, fy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settle(sim: &mut Simulation) -> usize {
+        let mut ticks = 0;
+        while !sim.is_settled() {
+            sim.tick();
+            ticks += 1;
+        }
+        ticks
+    }
+
+    fn dist(sim: &Simulation, a: usize, b: usize) -> f32 {
+        ((sim.x[a] - sim.x[b]).powi(2) + (sim.y[a] - sim.y[b]).powi(2)).sqrt()
+    }
+
+    #[test]
+    fn test_linked_nodes_settle_closer() {
+        // 两个三角形之间一条桥
+        let edges = vec![(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)];
+        let mut sim = Simulation::new(6, edges, |_| None);
+        let ticks = settle(&mut sim);
+        assert!(ticks < 400);
+        assert!(sim.x.iter().chain(&sim.y).all(|v| v.is_finite()));
+        assert!(dist(&sim, 0, 1) < dist(&sim, 0, 5));
+        assert!(dist(&sim, 3, 4) < dist(&sim, 1, 4));
+    }
+
+    #[test]
+    fn test_large_graph_with_coincident_seeds() {
+        let n = 3000;
+        let edges: Vec<_> = (1..n).map(|i| (i, i / 4)).collect();
+        let mut sim = Simulation::new(n, edges, |i| (i % 2 == 0).then_some((0.0, 0.0)));
+        for _ in 0..50 {
+            sim.tick();
+        }
+        assert!(sim.x.iter().chain(&sim.y).all(|v| v.is_finite()));
+        let (min_x, _, max_x, _) = sim.bounds().unwrap();
+        assert!(max_x - min_x > 100.0);
+        assert_eq!(sim.nearest(sim.x[7], sim.y[7], 0.01), Some(7));
+    }
+}
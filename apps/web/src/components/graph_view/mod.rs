@@ -0,0 +1,422 @@
+// apps/web/src/components/graph_view/mod.rs
+//! # Graph View (知识图谱面板)
+//!
+//! 以力导向布局绘制文档/标签图，显示时占据主内容区。
+//!
+//! **核心功能清单**:
+//! - 全库图谱或当前文档周边 N 跳子图 (`GetGraph { root, depth }`)
+//! - 按文件夹与标签过滤 (客户端，过滤后沿用已有节点位置)
+//! - 滚轮缩放、拖拽平移；点击文档节点打开文档，点击标签节点按标签过滤
+//!
+//! **性能**: 模拟在 Canvas 帧循环中按时间预算推进，静止后停止调度，
+//! 仅在交互时重绘。
+
+mod layout;
+mod model;
+mod render;
+
+use self::layout::Simulation;
+use self::model::ViewGraph;
+use self::render::{Highlight, Surface, Viewport};
+use crate::components::layout_context::GraphControl;
+use crate::hooks::use_core::{DocContext, LinksContext};
+use crate::i18n::{Locale, t};
+use deve_core::models::DocId;
+use deve_core::protocol::GraphNodeKind;
+use leptos::html::Canvas;
+use leptos::prelude::*;
+use std::collections::HashMap;
+use wasm_bindgen::JsCast;
+use web_sys::CanvasRenderingContext2d;
+
+/// 每帧用于推进模拟的时间预算
+const TICK_BUDGET_MS: f64 = 10.0;
+/// 指针移动超过该距离 (CSS 像素) 视为拖拽而非点击
+const DRAG_THRESHOLD: f32 = 4.0;
+/// 点击命中半径 (CSS 像素)
+const HIT_RADIUS: f32 = 10.0;
+/// 周边子图的最大深度
+const MAX_DEPTH: u32 = 3;
+
+struct PointerDown {
+    start: (f32, f32),
+    last: (f32, f32),
+    moved: bool,
+    node: Option<usize>,
+}
+
+/// 帧循环使用的非响应式状态
+struct GraphState {
+    graph: ViewGraph,
+    sim: Simulation,
+    view: Viewport,
+    hover: Option<usize>,
+    current_doc: Option<DocId>,
+    /// 用户未平移/缩放前，每帧自动适配视口
+    auto_fit: bool,
+    pointer: Option<PointerDown>,
+    frame_pending: bool,
+}
+
+impl Default for GraphState {
+    fn default() -> Self {
+        Self {
+            graph: ViewGraph::default(),
+            sim: Simulation::new(0, Vec::new(), |_| None),
+            view: Viewport::default(),
+            hover: None,
+            current_doc: None,
+            auto_fit: true,
+            pointer: None,
+            frame_pending: false,
+        }
+    }
+}
+
+impl GraphState {
+    fn hit(&self, sx: f32, sy: f32) -> Option<usize> {
+        let (wx, wy) = self.view.to_world(sx, sy);
+        self.sim.nearest(wx, wy, HIT_RADIUS / self.view.scale)
+    }
+}
+
+type State = StoredValue<GraphState, LocalStorage>;
+
+fn now_ms() -> f64 {
+    web_sys::window()
+        .and_then(|w| w.performance())
+        .map(|p| p.now())
+        .unwrap_or_default()
+}
+
+/// 请求下一帧 (同一时刻至多一个待执行帧)
+fn request_frame(state: State, canvas: NodeRef<Canvas>) {
+    let pending = state.try_update_value(|s| std::mem::replace(&mut s.frame_pending, true));
+    if pending == Some(false) {
+        request_animation_frame(move || frame(state, canvas));
+    }
+}
+
+fn frame(state: State, canvas: NodeRef<Canvas>) {
+    let Some(el) = canvas.get_untracked() else {
+        let _ = state.try_update_value(|s| s.frame_pending = false);
+        return;
+    };
+    let Some(ctx) = el
+        .get_context("2d")
+        .ok()
+        .flatten()
+        .and_then(|c| c.dyn_into::<CanvasRenderingContext2d>().ok())
+    else {
+        return;
+    };
+    let dpr = web_sys::window()
+        .map(|w| w.device_pixel_ratio() as f32)
+        .unwrap_or(1.0);
+    let surface = Surface {
+        width: el.client_width() as f32,
+        height: el.client_height() as f32,
+        dpr,
+    };
+    let (pw, ph) = ((surface.width * dpr) as u32, (surface.height * dpr) as u32);
+    if el.width() != pw || el.height() != ph {
+        el.set_width(pw);
+        el.set_height(ph);
+    }
+
+    let running = state.try_update_value(|s| {
+        s.frame_pending = false;
+        let start = now_ms();
+        while !s.sim.is_settled() {
+            s.sim.tick();
+            if now_ms() - start > TICK_BUDGET_MS {
+                break;
+            }
+        }
+        if s.auto_fit
+            && let Some(bounds) = s.sim.bounds()
+        {
+            s.view.fit(bounds, surface.width, surface.height);
+        }
+        let current = s
+            .current_doc
+            .and_then(|id| s.graph.nodes.iter().position(|n| n.doc_id == Some(id)));
+        let highlight = Highlight {
+            hover: s.hover,
+            current,
+        };
+        render::draw(&ctx, surface, &s.graph, &s.sim, &s.view, &highlight);
+        !s.sim.is_settled()
+    });
+    if running == Some(true) {
+        request_frame(state, canvas);
+    }
+}
+
+fn pointer_pos(ev: &web_sys::MouseEvent) -> (f32, f32) {
+    (ev.offset_x() as f32, ev.offset_y() as f32)
+}
+
+#[component]
+pub fn GraphView() -> impl IntoView {
+    let links = expect_context::<LinksContext>();
+    let doc_ctx = expect_context::<DocContext>();
+    let control = expect_context::<GraphControl>();
+    let locale = use_context::<RwSignal<Locale>>().unwrap_or_else(|| RwSignal::new(Locale::En));
+
+    let (local, set_local) = signal(false);
+    let (depth, set_depth) = signal(2u32);
+    let (folder, set_folder) = signal(String::new());
+    let (tag, set_tag) = signal(String::new());
+    let (hovering, set_hovering) = signal(false);
+
+    let state: State = StoredValue::new_local(GraphState::default());
+    let canvas = NodeRef::<Canvas>::new();
+
+    // 范围变化时重新请求图谱
+    Effect::new(move |_| {
+        let root = if local.get() {
+            doc_ctx.current_doc.get()
+        } else {
+            None
+        };
+        links.on_get_graph.run((root, depth.get()));
+    });
+
+    let folders = Memo::new(move |_| {
+        links
+            .graph
+            .with(|g| g.as_ref().map(model::folders).unwrap_or_default())
+    });
+    let tags = Memo::new(move |_| {
+        links
+            .graph
+            .with(|g| g.as_ref().map(model::tags).unwrap_or_default())
+    });
+    let view_graph = Memo::new(move |_| {
+        links.graph.with(|g| {
+            g.as_ref()
+                .map(|g| model::filter_graph(g, &folder.get(), &tag.get()))
+        })
+    });
+
+    // 过滤结果变化时重建模拟，保留已有节点位置以避免整图跳动
+    Effect::new(move |_| {
+        let Some(graph) = view_graph.get() else {
+            return;
+        };
+        state.update_value(|s| {
+            let old: HashMap<(GraphNodeKind, &str), (f32, f32)> = s
+                .graph
+                .nodes
+                .iter()
+                .enumerate()
+                .map(|(i, n)| ((n.kind, n.key.as_str()), (s.sim.x[i], s.sim.y[i])))
+                .collect();
+            let edges = graph.edges.iter().map(|&(a, b, _)| (a, b)).collect();
+            let sim = Simulation::new(graph.nodes.len(), edges, |i| {
+                let node = &graph.nodes[i];
+                old.get(&(node.kind, node.key.as_str())).copied()
+            });
+            drop(old);
+            s.sim = sim;
+            s.graph = graph;
+            s.hover = None;
+            s.auto_fit = true;
+        });
+        request_frame(state, canvas);
+    });
+
+    Effect::new(move |_| {
+        let current = doc_ctx.current_doc.get();
+        state.update_value(|s| s.current_doc = current);
+        request_frame(state, canvas);
+    });
+
+    let resize = window_event_listener(leptos::ev::resize, move |_| request_frame(state, canvas));
+    on_cleanup(move || resize.remove());
+
+    let on_pointerdown = move |ev: web_sys::PointerEvent| {
+        if let Some(el) = canvas.get_untracked() {
+            let _ = el.set_pointer_capture(ev.pointer_id());
+        }
+        let pos = pointer_pos(&ev);
+        state.update_value(|s| {
+            s.pointer = Some(PointerDown {
+                start: pos,
+                last: pos,
+                moved: false,
+                node: s.hit(pos.0, pos.1),
+            });
+        });
+    };
+    let on_pointermove = move |ev: web_sys::PointerEvent| {
+        let (x, y) = pointer_pos(&ev);
+        let redraw = state.try_update_value(|s| {
+            if let Some(p) = s.pointer.as_mut() {
+                p.moved |= (x - p.start.0).hypot(y - p.start.1) > DRAG_THRESHOLD;
+                if !p.moved {
+                    return false;
+                }
+                s.view.tx += x - p.last.0;
+                s.view.ty += y - p.last.1;
+                p.last = (x, y);
+                s.auto_fit = false;
+                return true;
+            }
+            let hover = s.hit(x, y);
+            let changed = std::mem::replace(&mut s.hover, hover) != hover;
+            if changed {
+                set_hovering.set(hover.is_some());
+            }
+            changed
+        });
+        if redraw == Some(true) {
+            request_frame(state, canvas);
+        }
+    };
+    let on_pointerup = move |_: web_sys::PointerEvent| {
+        let clicked = state
+            .try_update_value(|s| {
+                let p = s.pointer.take()?;
+                let node = p.node.filter(|_| !p.moved)?;
+                s.graph.nodes.get(node).cloned()
+            })
+            .flatten();
+        match clicked {
+            Some(node) if node.kind == GraphNodeKind::Tag => set_tag.set(node.key),
+            Some(node) => {
+                if let Some(doc_id) = node.doc_id {
+                    control.set_graph_visible.set(false);
+                    doc_ctx.on_doc_select.run(doc_id);
+                }
+            }
+            None => {}
+        }
+    };
+    let on_pointerleave = move |_: web_sys::PointerEvent| {
+        state.update_value(|s| s.hover = None);
+        set_hovering.set(false);
+        request_frame(state, canvas);
+    };
+    let on_wheel = move |ev: web_sys::WheelEvent| {
+        ev.prevent_default();
+        let (x, y) = pointer_pos(&ev);
+        let factor = (-ev.delta_y() as f32 * 0.002).exp();
+        state.update_value(|s| {
+            s.view.zoom_at(x, y, factor);
+            s.auto_fit = false;
+        });
+        request_frame(state, canvas);
+    };
+    let on_fit = move |_| {
+        state.update_value(|s| s.auto_fit = true);
+        request_frame(state, canvas);
+    };
+
+    let select_class = "rounded border border-default bg-panel text-xs px-1 py-0.5 max-w-40";
+    let node_count = move || {
+        view_graph.with(|g| {
+            g.as_ref()
+                .map(|g| g.nodes.len().to_string())
+                .unwrap_or_default()
+        })
+    };
+
+    view! {
+        <div class="h-full w-full flex flex-col">
+            <div class="flex items-center gap-2 px-3 py-2 border-b border-default text-xs flex-wrap">
+                <span class="font-semibold text-secondary">{move || t::graph::title(locale.get())}</span>
+                <span class="text-muted font-mono">{node_count}</span>
+                <select
+                    class=select_class
+                    prop:value=move || folder.get()
+                    on:change=move |ev| set_folder.set(event_target_value(&ev))
+                >
+                    <option value="">{move || t::graph::all_folders(locale.get())}</option>
+                    <For
+                        each=move || folders.get()
+                        key=|f| f.clone()
+                        children=move |f| view! { <option value=f.clone()>{f.clone()}</option> }
+                    />
+                </select>
+                <select
+                    class=select_class
+                    prop:value=move || tag.get()
+                    on:change=move |ev| set_tag.set(event_target_value(&ev))
+                >
+                    <option value="">{move || t::graph::all_tags(locale.get())}</option>
+                    <For
+                        each=move || tags.get()
+                        key=|t| t.clone()
+                        children=move |t| view! { <option value=t.clone()>{format!("#{t}")}</option> }
+                    />
+                </select>
+                <label class="flex items-center gap-1 text-secondary cursor-pointer">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || local.get()
+                        on:change=move |ev| set_local.set(event_target_checked(&ev))
+                    />
+                    {move || t::graph::local_graph(locale.get())}
+                </label>
+                <Show when=move || local.get()>
+                    <label class="flex items-center gap-1 text-secondary">
+                        {move || t::graph::depth(locale.get())}
+                        <select
+                            class=select_class
+                            prop:value=move || depth.get().to_string()
+                            on:change=move |ev| {
+                                if let Ok(d) = event_target_value(&ev).parse() {
+                                    set_depth.set(d);
+                                }
+                            }
+                        >
+                            {(1..=MAX_DEPTH)
+                                .map(|d| view! { <option value=d.to_string()>{d.to_string()}</option> })
+                                .collect_view()}
+                        </select>
+                    </label>
+                </Show>
+                <div class="flex-1"></div>
+                <button
+                    class="px-2 py-0.5 rounded hover:bg-hover text-secondary"
+                    title="Fit"
+                    on:click=on_fit
+                >
+                    "⤢"
+                </button>
+                <button
+                    class="px-2 py-0.5 rounded hover:bg-hover text-secondary"
+                    on:click=move |_| control.set_graph_visible.set(false)
+                >
+                    {move || t::graph::close(locale.get())}
+                </button>
+            </div>
+            <div class="flex-1 relative min-h-0">
+                <canvas
+                    node_ref=canvas
+                    class="absolute inset-0 w-full h-full touch-none"
+                    style=move || if hovering.get() { "cursor: pointer" } else { "cursor: grab" }
+                    on:pointerdown=on_pointerdown
+                    on:pointermove=on_pointermove
+                    on:pointerup=on_pointerup
+                    on:pointerleave=on_pointerleave
+                    on:wheel=on_wheel
+                ></canvas>
+                {move || {
+                    let text = links.graph.with(|g| match g {
+                        None => Some(t::graph::loading(locale.get())),
+                        Some(g) if g.truncated => Some(t::graph::truncated(locale.get())),
+                        Some(_) => None,
+                    });
+                    text.map(|text| view! {
+                        <div class="absolute top-2 left-1/2 -translate-x-1/2 px-3 py-1 text-xs text-muted bg-panel/90 border border-default rounded pointer-events-none">
+                            {text}
+                        </div>
+                    })
+                }}
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,104 @@
+// apps/web/src/components/graph_view/model.rs
+//! # 图谱视图模型
+//!
+//! 在客户端按文件夹与标签过滤服务端返回的 `KnowledgeGraph`，并重排节点下标。
+
+use deve_core::protocol::{GraphEdgeKind, GraphNode, GraphNodeKind, KnowledgeGraph};
+use std::collections::BTreeSet;
+
+/// 过滤后的图 (边端点为 `nodes` 下标)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ViewGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<(usize, usize, GraphEdgeKind)>,
+}
+
+/// 按文件夹前缀与标签过滤
+///
+/// - `folder` 为空: 不限文件夹
+/// - `tag` 为空: 不限标签；否则仅保留带该标签的文档
+/// - 标签节点仅在至少连接一个保留文档时保留
+pub fn filter_graph(graph: &KnowledgeGraph, folder: &str, tag: &str) -> ViewGraph {
+    let prefix = format!("{}/", folder.trim_end_matches('/'));
+    let tagged: BTreeSet<usize> = if tag.is_empty() {
+        BTreeSet::new()
+    } else {
+        graph
+            .edges
+            .iter()
+            .filter(|e| {
+                e.kind == GraphEdgeKind::Tag
+                    && graph.nodes.get(e.target as usize).map(|n| n.key.as_str()) == Some(tag)
+            })
+            .map(|e| e.source as usize)
+            .collect()
+    };
+    let keep_doc = |i: usize, node: &GraphNode| {
+        (folder.is_empty() || node.key.starts_with(&prefix))
+            && (tag.is_empty() || tagged.contains(&i))
+    };
+
+    let mut index = vec![None; graph.nodes.len()];
+    let mut view = ViewGraph::default();
+    for (i, node) in graph.nodes.iter().enumerate() {
+        if node.kind == GraphNodeKind::Doc && keep_doc(i, node) {
+            index[i] = Some(view.nodes.len());
+            view.nodes.push(node.clone());
+        }
+    }
+    for e in &graph.edges {
+        let (s, t) = (e.source as usize, e.target as usize);
+        let Some(source) = index.get(s).copied().flatten() else {
+            continue;
+        };
+        let target = match (e.kind, index.get(t).copied().flatten()) {
+            (_, Some(target)) => target,
+            (GraphEdgeKind::Tag, None) => match graph.nodes.get(t) {
+                Some(node) => {
+                    index[t] = Some(view.nodes.len());
+                    view.nodes.push(node.clone());
+                    view.nodes.len() - 1
+                }
+                None => continue,
+            },
+            (GraphEdgeKind::Link, None) => continue,
+        };
+        view.edges.push((source, target, e.kind));
+    }
+    view
+}
+
+/// 图中出现的全部文件夹 (含各级父目录)
+pub fn folders(graph: &KnowledgeGraph) -> Vec<String> {
+    let mut out = BTreeSet::new();
+    for node in graph.nodes.iter().filter(|n| n.kind == GraphNodeKind::Doc) {
+        let mut path = node.key.as_str();
+        while let Some((parent, _)) = path.rsplit_once('/') {
+            out.insert(parent.to_string());
+            path = parent;
+        }
+    }
+    out.into_iter().collect()
+}
+
+/// 图中出现的全部标签
+pub fn tags(graph: &KnowledgeGraph) -> Vec<String> {
+    let set: BTreeSet<_> = graph
+        .nodes
+        .iter()
+        .filter(|n| n.kind == GraphNodeKind::Tag)
+        .map(|n| n.key.clone())
+        .collect();
+    set.into_iter().collect()
+}
+
+/// 节点显示名: 文档取文件名 (去 `.md`)，标签加 `#`
+pub fn label(node: &GraphNode) -> String {
+    match node.kind {
+        GraphNodeKind::Doc => {
+            let name = node.key.rsplit('/').next().unwrap_or(&node.key);
+            name.strip_suffix(".md").unwrap_or(name).to_string()
+        }
+        GraphNodeKind::Tag => format!("#{}", node.key),
+    }
+}
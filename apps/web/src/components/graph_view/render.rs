@@ -0,0 +1,170 @@
+// apps/web/src/components/graph_view/render.rs
+//! # 图谱 Canvas 绘制
+//!
+//! 每类元素合并为一条路径后一次描边/填充，数千节点时仍可逐帧重绘。
+//! 标签文字仅在放大后 (或悬停/当前文档) 绘制，并剔除视口外节点。
+
+use super::layout::Simulation;
+use super::model::{ViewGraph, label};
+use deve_core::protocol::{GraphEdgeKind, GraphNodeKind};
+use std::f64::consts::TAU;
+use web_sys::CanvasRenderingContext2d;
+
+/// 缩放达到该值后显示全部标签
+const LABEL_SCALE: f32 = 1.4;
+
+const LINK_COLOR: &str = "rgba(148, 163, 184, 0.55)";
+const TAG_EDGE_COLOR: &str = "rgba(167, 139, 250, 0.35)";
+const DOC_COLOR: &str = "#64748b";
+const TAG_COLOR: &str = "#8b5cf6";
+const CURRENT_COLOR: &str = "#2563eb";
+const HOVER_COLOR: &str = "#f59e0b";
+const LABEL_COLOR: &str = "#334155";
+
+/// 世界坐标到屏幕坐标: `screen = world * scale + (tx, ty)` (CSS 像素)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    pub scale: f32,
+    pub tx: f32,
+    pub ty: f32,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            tx: 0.0,
+            ty: 0.0,
+        }
+    }
+}
+
+impl Viewport {
+    pub fn to_world(self, sx: f32, sy: f32) -> (f32, f32) {
+        ((sx - self.tx) / self.scale, (sy - self.ty) / self.scale)
+    }
+
+    /// 以屏幕点 `(sx, sy)` 为中心缩放
+    pub fn zoom_at(&mut self, sx: f32, sy: f32, factor: f32) {
+        let (wx, wy) = self.to_world(sx, sy);
+        self.scale = (self.scale * factor).clamp(0.05, 8.0);
+        self.tx = sx - wx * self.scale;
+        self.ty = sy - wy * self.scale;
+    }
+
+    /// 使包围盒居中并充满 `width x height`
+    pub fn fit(&mut self, bounds: (f32, f32, f32, f32), width: f32, height: f32) {
+        let (min_x, min_y, max_x, max_y) = bounds;
+        let pad = 40.0;
+        let w = (max_x - min_x).max(1.0);
+        let h = (max_y - min_y).max(1.0);
+        self.scale = ((width - pad * 2.0) / w)
+            .min((height - pad * 2.0) / h)
+            .clamp(0.05, 2.0);
+        self.tx = width / 2.0 - (min_x + max_x) / 2.0 * self.scale;
+        self.ty = height / 2.0 - (min_y + max_y) / 2.0 * self.scale;
+    }
+}
+
+/// 节点半径 (世界坐标)
+pub fn node_radius(graph: &ViewGraph, sim: &Simulation, i: usize) -> f32 {
+    match graph.nodes[i].kind {
+        GraphNodeKind::Doc => 3.0 + (sim.degree(i) as f32).sqrt().min(6.0),
+        GraphNodeKind::Tag => 4.0,
+    }
+}
+
+/// Canvas 尺寸 (CSS 像素) 与设备像素比
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Surface {
+    pub width: f32,
+    pub height: f32,
+    pub dpr: f32,
+}
+
+pub struct Highlight {
+    pub hover: Option<usize>,
+    pub current: Option<usize>,
+}
+
+/// 绘制一帧
+pub fn draw(
+    ctx: &CanvasRenderingContext2d,
+    surface: Surface,
+    graph: &ViewGraph,
+    sim: &Simulation,
+    view: &Viewport,
+    highlight: &Highlight,
+) {
+    let Surface { width, height, dpr } = surface;
+    let _ = ctx.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+    ctx.clear_rect(0.0, 0.0, (width * dpr) as f64, (height * dpr) as f64);
+    let s = (view.scale * dpr) as f64;
+    let _ = ctx.set_transform(
+        s,
+        0.0,
+        0.0,
+        s,
+        (view.tx * dpr) as f64,
+        (view.ty * dpr) as f64,
+    );
+    let px = 1.0 / view.scale as f64;
+
+    for (kind, color) in [
+        (GraphEdgeKind::Tag, TAG_EDGE_COLOR),
+        (GraphEdgeKind::Link, LINK_COLOR),
+    ] {
+        ctx.begin_path();
+        for &(s, t, k) in &graph.edges {
+            if k == kind {
+                ctx.move_to(sim.x[s] as f64, sim.y[s] as f64);
+                ctx.line_to(sim.x[t] as f64, sim.y[t] as f64);
+            }
+        }
+        ctx.set_line_width(px);
+        ctx.set_stroke_style_str(color);
+        ctx.stroke();
+    }
+
+    let fill_nodes = |color: &str, pick: &dyn Fn(usize) -> bool| {
+        ctx.begin_path();
+        for i in (0..graph.nodes.len()).filter(|&i| pick(i)) {
+            let (x, y) = (sim.x[i] as f64, sim.y[i] as f64);
+            let r = node_radius(graph, sim, i) as f64;
+            ctx.move_to(x + r, y);
+            let _ = ctx.arc(x, y, r, 0.0, TAU);
+        }
+        ctx.set_fill_style_str(color);
+        ctx.fill();
+    };
+    let special = |i: usize| highlight.hover == Some(i) || highlight.current == Some(i);
+    fill_nodes(DOC_COLOR, &|i| {
+        graph.nodes[i].kind == GraphNodeKind::Doc && !special(i)
+    });
+    fill_nodes(TAG_COLOR, &|i| {
+        graph.nodes[i].kind == GraphNodeKind::Tag && !special(i)
+    });
+    fill_nodes(CURRENT_COLOR, &|i| highlight.current == Some(i));
+    fill_nodes(HOVER_COLOR, &|i| highlight.hover == Some(i));
+
+    let show_all = view.scale >= LABEL_SCALE;
+    let (min_x, min_y) = view.to_world(0.0, 0.0);
+    let (max_x, max_y) = view.to_world(width, height);
+    ctx.set_font(&format!("{}px sans-serif", 11.0 / view.scale));
+    ctx.set_fill_style_str(LABEL_COLOR);
+    for i in 0..graph.nodes.len() {
+        if !(show_all || special(i)) {
+            continue;
+        }
+        let (x, y) = (sim.x[i], sim.y[i]);
+        if x < min_x || x > max_x || y < min_y || y > max_y {
+            continue;
+        }
+        let r = node_radius(graph, sim, i);
+        let _ = ctx.fill_text(
+            &label(&graph.nodes[i]),
+            (x + r + 2.0 / view.scale) as f64,
+            (y + 4.0 / view.scale) as f64,
+        );
+    }
+}
@@ -124,9 +124,28 @@ icon!(
         <polygon points="22 2 15 22 11 13 2 9 22 2"/>
     }
 );
+icon!(
+    Paperclip,
+    view! {
+        <path d="m21.44 11.05-9.19 9.19a6 6 0 0 1-8.49-8.49l8.57-8.57A4 4 0 1 1 18 8.84l-8.59 8.57a2 2 0 0 1-2.83-2.83l8.49-8.48"/>
+    }
+);
 icon!(
     X,
     view! {
         <path d="M18 6 6 18"/><path d="m6 6 12 12"/>
     }
 );
+icon!(
+    History,
+    view! {
+        <path d="M3 12a9 9 0 1 0 9-9 9.75 9.75 0 0 0-6.74 2.74L3 8"/>
+        <path d="M3 3v5h5"/><path d="M12 7v5l4 2"/>
+    }
+);
+icon!(
+    Square,
+    view! {
+        <rect width="14" height="14" x="5" y="5" rx="2"/>
+    }
+);
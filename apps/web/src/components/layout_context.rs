@@ -21,6 +21,13 @@ pub struct ChatControl {
     pub set_chat_visible: WriteSignal<bool>,
 }
 
+/// 知识图谱面板控制上下文 (显示时占据主内容区)
+#[derive(Clone, Copy)]
+pub struct GraphControl {
+    pub graph_visible: ReadSignal<bool>,
+    pub set_graph_visible: WriteSignal<bool>,
+}
+
 /// Editor content context for outline rendering
 #[derive(Clone, Copy)]
 pub struct EditorContentContext {
@@ -4,7 +4,7 @@
 use crate::components::activity_bar::SidebarView;
 use crate::components::desktop_layout::DesktopLayout;
 use crate::components::disconnect_overlay::DisconnectedOverlay;
-pub use crate::components::layout_context::{ChatControl, SearchControl};
+pub use crate::components::layout_context::{ChatControl, GraphControl, SearchControl};
 use crate::components::merge_modal_slot::MergeModalSlot;
 use crate::components::mobile_layout::MobileLayout;
 use crate::hooks::use_core::use_core;
@@ -62,6 +62,11 @@ pub fn MainLayout() -> impl IntoView {
         chat_visible,
         set_chat_visible,
     });
+    let (graph_visible, set_graph_visible) = signal(false);
+    provide_context(GraphControl {
+        graph_visible,
+        set_graph_visible,
+    });
 
     let handle_keydown = create_global_shortcut_handler(
         show_search.into(),
@@ -3,6 +3,7 @@
 //!
 //! 包含 Web 应用程序的所有 Leptos UI 组件。
 //! 结构遵循 "Activity Bar + Resizable Slot" 布局。
+pub mod backlinks;
 pub mod bottom_bar;
 pub mod command_palette;
 pub mod dropdown;
@@ -12,6 +13,7 @@ pub mod layout_context;
 pub mod outline;
 pub mod outline_render;
 pub mod playback;
+pub mod plugin_widget;
 pub mod settings;
 pub mod sidebar;
 pub mod sidebar_menu;
@@ -32,5 +34,6 @@ pub mod dashboard;
 pub mod desktop_chat_panel;
 pub mod desktop_layout;
 pub mod diff_view;
+pub mod graph_view;
 pub mod mobile_layout;
 pub mod settings_sections;
@@ -0,0 +1,230 @@
+// apps/web/src/components/plugin_widget.rs
+//! # Plugin Widgets (插件组件渲染)
+//!
+//! 将插件返回的 JSON 组件描述 (`deve_core::plugin::ui::Widget`) 渲染为视图。
+//! 组件只描述结构，交互统一回到插件处理函数 (`WidgetAction`)，前端不执行插件代码。
+//!
+//! - `PluginWidget`: list / tree / form / markdown
+//! - `SettingInput`: 单个设置项输入 (表单组件与设置面板共用)
+
+use crate::i18n::{Locale, t};
+use crate::utils::markdown::render_markdown;
+use deve_core::plugin::ui::{ListItem, SettingField, SettingKind, TreeItem, Widget, WidgetAction};
+use leptos::prelude::*;
+
+/// 单个设置项输入，值变化时回调新的 JSON 值
+#[component]
+pub fn SettingInput(
+    field: SettingField,
+    value: serde_json::Value,
+    on_change: Callback<serde_json::Value>,
+) -> impl IntoView {
+    let input_class = "w-full px-2 py-1 text-sm bg-panel text-primary border border-default rounded outline-none focus:border-accent";
+    match field.kind {
+        SettingKind::Boolean => view! {
+            <input
+                type="checkbox"
+                class="w-4 h-4 accent-current"
+                prop:checked=value.as_bool().unwrap_or(false)
+                on:change=move |ev| on_change.run(serde_json::Value::Bool(event_target_checked(&ev)))
+            />
+        }
+        .into_any(),
+        SettingKind::Number => view! {
+            <input
+                type="number"
+                class=input_class
+                prop:value=value.as_f64().map(|n| n.to_string()).unwrap_or_default()
+                on:change=move |ev| {
+                    if let Some(n) = event_target_value(&ev)
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(serde_json::Number::from_f64)
+                    {
+                        on_change.run(serde_json::Value::Number(n));
+                    }
+                }
+            />
+        }
+        .into_any(),
+        SettingKind::Select => {
+            let current = value.as_str().unwrap_or_default().to_string();
+            view! {
+                <select
+                    class=input_class
+                    on:change=move |ev| on_change.run(serde_json::Value::String(event_target_value(&ev)))
+                >
+                    {field
+                        .options
+                        .into_iter()
+                        .map(|opt| {
+                            let selected = opt == current;
+                            view! { <option value=opt.clone() selected=selected>{opt.clone()}</option> }
+                        })
+                        .collect_view()}
+                </select>
+            }
+            .into_any()
+        }
+        SettingKind::String => view! {
+            <input
+                type="text"
+                class=input_class
+                prop:value=value.as_str().unwrap_or_default().to_string()
+                on:change=move |ev| on_change.run(serde_json::Value::String(event_target_value(&ev)))
+            />
+        }
+        .into_any(),
+    }
+}
+
+/// 渲染插件组件，交互通过 `on_action` 回到插件
+#[component]
+pub fn PluginWidget(widget: Widget, on_action: Callback<WidgetAction>) -> impl IntoView {
+    match widget {
+        Widget::List { items } => view! {
+            <ul class="flex flex-col">
+                {items.into_iter().map(|item| list_item(item, on_action)).collect_view()}
+            </ul>
+        }
+        .into_any(),
+        Widget::Tree { nodes } => view! {
+            <ul class="flex flex-col">
+                {nodes.into_iter().map(|node| tree_item(node, 0, on_action)).collect_view()}
+            </ul>
+        }
+        .into_any(),
+        Widget::Form {
+            fields,
+            submit,
+            submit_label,
+        } => form_view(fields, submit, submit_label, on_action),
+        Widget::Markdown { content } => {
+            let locale = use_context::<RwSignal<Locale>>()
+                .map(|l| l.get_untracked())
+                .unwrap_or_default();
+            view! {
+                <div
+                    class="prose prose-sm max-w-none px-2 text-primary"
+                    inner_html=render_markdown(&content, t::chat::apply(locale))
+                />
+            }
+            .into_any()
+        }
+    }
+}
+
+fn list_item(item: ListItem, on_action: Callback<WidgetAction>) -> impl IntoView {
+    let clickable = item.action.is_some();
+    let action = item.action;
+    view! {
+        <li
+            class=if clickable {
+                "px-2 py-1 rounded cursor-pointer hover:bg-hover"
+            } else {
+                "px-2 py-1"
+            }
+            on:click=move |_| {
+                if let Some(action) = action.clone() {
+                    on_action.run(action);
+                }
+            }
+        >
+            <div class="text-sm text-primary truncate">{item.label}</div>
+            {item.detail.map(|d| view! { <div class="text-xs text-muted truncate">{d}</div> })}
+        </li>
+    }
+}
+
+/// 树节点 (递归渲染须擦除类型)
+fn tree_item(node: TreeItem, depth: usize, on_action: Callback<WidgetAction>) -> AnyView {
+    let (expanded, set_expanded) = signal(true);
+    let has_children = !node.children.is_empty();
+    let action = node.action;
+    let children = node.children;
+    view! {
+        <li>
+            <div
+                class="flex items-center gap-1 px-2 py-1 rounded cursor-pointer hover:bg-hover text-sm text-primary"
+                style=format!("padding-left: {}rem", 0.5 + depth as f32 * 0.75)
+                on:click=move |_| {
+                    if let Some(action) = action.clone() {
+                        on_action.run(action);
+                    } else if has_children {
+                        set_expanded.update(|v| *v = !*v);
+                    }
+                }
+            >
+                <span class="w-3 text-muted">
+                    {move || match (has_children, expanded.get()) {
+                        (false, _) => "",
+                        (true, true) => "▾",
+                        (true, false) => "▸",
+                    }}
+                </span>
+                <span class="truncate">{node.label}</span>
+            </div>
+            <Show when=move || expanded.get() && has_children>
+                <ul>
+                    {children
+                        .clone()
+                        .into_iter()
+                        .map(|child| tree_item(child, depth + 1, on_action))
+                        .collect_view()}
+                </ul>
+            </Show>
+        </li>
+    }
+    .into_any()
+}
+
+/// 表单: 提交时以 `{键: 值}` 对象调用 `submit`
+fn form_view(
+    fields: Vec<SettingField>,
+    submit: String,
+    submit_label: Option<String>,
+    on_action: Callback<WidgetAction>,
+) -> AnyView {
+    let values = RwSignal::new(
+        fields
+            .iter()
+            .map(|f| (f.key.clone(), f.default.clone()))
+            .collect::<serde_json::Map<_, _>>(),
+    );
+    let on_submit = move |ev: web_sys::SubmitEvent| {
+        ev.prevent_default();
+        on_action.run(WidgetAction {
+            handler: submit.clone(),
+            args: vec![serde_json::Value::Object(values.get_untracked())],
+        });
+    };
+    view! {
+        <form class="flex flex-col gap-2 px-2" on:submit=on_submit>
+            {fields
+                .into_iter()
+                .map(|field| {
+                    let key = field.key.clone();
+                    let value = field.default.clone();
+                    let on_change = Callback::new(move |v| {
+                        values.update(|m| {
+                            m.insert(key.clone(), v);
+                        })
+                    });
+                    view! {
+                        <label class="flex flex-col gap-1 text-xs text-muted">
+                            {field.label.clone()}
+                            <SettingInput field=field value=value on_change=on_change />
+                        </label>
+                    }
+                })
+                .collect_view()}
+            <button
+                type="submit"
+                class="self-start px-3 py-1 text-xs font-bold bg-accent text-on-accent rounded"
+            >
+                {submit_label.unwrap_or_else(|| "Submit".to_string())}
+            </button>
+        </form>
+    }
+    .into_any()
+}
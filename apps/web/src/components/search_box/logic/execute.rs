@@ -1,6 +1,7 @@
 use crate::components::search_box::file_ops;
 use crate::components::search_box::types::{InsertQuery, SearchAction};
 use crate::hooks::use_core::CoreState;
+use deve_core::protocol::SearchSource;
 use leptos::prelude::*;
 
 pub(crate) fn execute_action(
@@ -17,6 +18,28 @@ pub(crate) fn execute_action(
             core.on_doc_select.run(*id);
             set_show.set(false);
         }
+        SearchAction::OpenHit(hit) => {
+            match &hit.source {
+                // 历史版本: 与当前版本对比
+                SearchSource::Commit { commit_id, .. } => {
+                    core.on_get_historical_diff
+                        .run((commit_id.clone(), hit.doc_id));
+                }
+                // 影子库: 切换到只读旁观视图后打开 (BranchSwitched 会重新请求当前文档)
+                SearchSource::Peer { peer_id, .. } => {
+                    core.on_switch_branch.run(Some(peer_id.to_string()));
+                    core.set_pending_jump
+                        .set(Some((hit.doc_id, hit.line as usize)));
+                    core.on_doc_select.run(hit.doc_id);
+                }
+                SearchSource::Local => {
+                    core.set_pending_jump
+                        .set(Some((hit.doc_id, hit.line as usize)));
+                    core.on_doc_select.run(hit.doc_id);
+                }
+            }
+            set_show.set(false);
+        }
         SearchAction::RunCommand(cmd) => cmd.action.run(()),
         SearchAction::SwitchBranch(branch) => {
             if branch == "Local (Master)" {
@@ -44,6 +44,13 @@ pub fn create_results_memo(
                 .or(Some("Local (Master)".to_string()));
             return providers::BranchProvider::new(core.shadow_repos.get(), current).search(&q);
         }
+        if let Some(stripped) = q.strip_prefix('?') {
+            return if stripped.trim().is_empty() {
+                Vec::new()
+            } else {
+                providers::fulltext_results(&core.search_results.get())
+            };
+        }
         if let Some(stripped) = q.strip_prefix('+') {
             let path = stripped.trim();
             return if path.is_empty() {
@@ -76,6 +83,8 @@ pub fn create_placeholder_memo(query: Signal<String>, locale: RwSignal<Locale>)
             t::search::placeholder_command(now_locale).to_string()
         } else if q.starts_with('@') {
             t::search::placeholder_branch(now_locale).to_string()
+        } else if q.starts_with('?') {
+            t::search::placeholder_fulltext(now_locale).to_string()
         } else if q.starts_with('+') {
             t::common::new_file(now_locale).to_string()
         } else {
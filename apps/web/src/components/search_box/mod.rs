@@ -70,6 +70,19 @@ pub fn UnifiedSearch(
         });
     }
 
+    // `?` 前缀为服务端全文检索，结果经 `search_results` 异步回填。
+    {
+        let on_search = core.on_search;
+        Effect::new(move |_| {
+            let q = debounced_query.get();
+            if let Some(text) = q.strip_prefix('?')
+                && !text.trim().is_empty()
+            {
+                on_search.run(text.trim().to_string());
+            }
+        });
+    }
+
     // 按查询类型动态选择 Provider 并生成结果列表。
     let providers_results = logic::create_results_memo(
         show,
@@ -2,6 +2,7 @@
 use crate::components::command_palette::Command;
 use crate::components::search_box::types::{SearchAction, SearchProvider, SearchResult};
 use deve_core::models::DocId;
+use deve_core::protocol::{SearchHit, SearchSource};
 
 // --- File Provider ---
 pub struct FileProvider {
@@ -216,3 +217,27 @@ impl SearchProvider for BranchProvider {
         // Validation only
     }
 }
+
+// --- Full-text Provider (`?` 前缀，结果由服务端异步返回) ---
+///
+/// 历史提交命中标注 `@短提交号`，影子库命中标注 `⇄ Peer`。
+pub fn fulltext_results(hits: &[SearchHit]) -> Vec<SearchResult> {
+    hits.iter()
+        .map(|hit| SearchResult {
+            id: format!("{}:{}:{:?}", hit.doc_id, hit.line, hit.source),
+            title: match &hit.source {
+                SearchSource::Local => format!("{}:{}", hit.path, hit.line),
+                SearchSource::Commit { commit_id, .. } => {
+                    let short: String = commit_id.chars().take(7).collect();
+                    format!("{}:{}  @{}", hit.path, hit.line, short)
+                }
+                SearchSource::Peer { peer_id, .. } => {
+                    format!("{}:{}  ⇄ {}", hit.path, hit.line, peer_id)
+                }
+            },
+            detail: Some(hit.snippet.clone()),
+            score: hit.score,
+            action: SearchAction::OpenHit(hit.clone()),
+        })
+        .collect()
+}
@@ -64,6 +64,10 @@ pub fn result_item(
 
     let action_clone = item.action.clone();
     let detail_clone = item.detail.clone();
+    let highlights = match &item.action {
+        SearchAction::OpenHit(hit) => hit.highlights.clone(),
+        _ => Vec::new(),
+    };
 
     view! {
         <button
@@ -112,7 +116,7 @@ pub fn result_item(
             } else {
                 item_icon(is_sel, action_clone.clone(), detail_clone.clone()).into_any()
             }}
-            {item_content(item.title.clone(), detail_text_cond, detail_text)}
+            {item_content(item.title.clone(), detail_text_cond, detail_text, highlights)}
             {move || if is_mobile {
                 view! {}.into_any()
             } else {
@@ -127,7 +131,7 @@ fn item_icon(is_sel: bool, action: SearchAction, detail: Option<String>) -> impl
     let icon = match action {
         SearchAction::RunCommand(_) => "command",
         SearchAction::SwitchBranch(_) => "branch",
-        SearchAction::OpenDoc(_) => "file",
+        SearchAction::OpenDoc(_) | SearchAction::OpenHit(_) => "file",
         SearchAction::CreateDoc(_) => "plus",
         SearchAction::FileOp(_) => "fileop",
         SearchAction::InsertQuery(_) => "folder",
@@ -158,6 +162,7 @@ fn item_content(
     title: String,
     detail_cond: Option<String>,
     detail_text: Option<String>,
+    highlights: Vec<(u32, u32)>,
 ) -> impl IntoView {
     let is_mobile = window_width().map(|w| w <= 768).unwrap_or(false);
     view! {
@@ -165,13 +170,35 @@ fn item_content(
             <span class="font-medium">{title}</span>
             <Show when=move || detail_cond.is_some()>
                 <span class=if is_mobile { "text-[11px] opacity-60 font-mono" } else { "text-xs opacity-60 font-mono" }>
-                    {detail_text.clone().unwrap()}
+                    {highlighted(detail_text.clone().unwrap(), highlights.clone())}
                 </span>
             </Show>
         </div>
     }
 }
 
+/// 将摘要按高亮区间 (字节偏移) 切分，命中部分用 `<mark>` 包裹
+fn highlighted(text: String, highlights: Vec<(u32, u32)>) -> impl IntoView {
+    let mut parts = Vec::new();
+    let mut cursor = 0;
+    for (start, end) in highlights {
+        let (start, end) = (start as usize, end as usize);
+        let (Some(before), Some(hit)) = (text.get(cursor..start), text.get(start..end)) else {
+            continue;
+        };
+        parts.push(view! { <span>{before.to_string()}</span> }.into_any());
+        parts.push(
+            view! { <mark class="bg-accent-subtle text-accent rounded-sm">{hit.to_string()}</mark> }
+                .into_any(),
+        );
+        cursor = end;
+    }
+    parts.push(
+        view! { <span>{text.get(cursor..).unwrap_or_default().to_string()}</span> }.into_any(),
+    );
+    parts
+}
+
 fn selection_arrow(is_sel: bool) -> impl IntoView {
     view! {
         <Show when=move || is_sel>
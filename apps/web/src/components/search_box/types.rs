@@ -4,10 +4,13 @@
 
 use crate::components::command_palette::Command;
 use deve_core::models::DocId;
+use deve_core::protocol::SearchHit;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum SearchAction {
     OpenDoc(DocId),
+    /// 全文检索命中: 打开文档并跳转到命中行
+    OpenHit(SearchHit),
     RunCommand(Command),
     SwitchBranch(String),
     CreateDoc(String),
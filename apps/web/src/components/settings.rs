@@ -4,7 +4,9 @@
 //! 设置模态框，允许用户更改语言、同步模式等全局配置。
 //! 显示版本信息和未来功能占位符（如混合模式）。
 
-use crate::components::settings_sections::{AiBackendSection, SyncModeSection};
+use crate::components::settings_sections::{
+    AiBackendSection, PluginSettingsSection, SyncModeSection,
+};
 use crate::i18n::{Locale, t};
 use leptos::prelude::*;
 
@@ -73,6 +75,9 @@ pub fn SettingsModal(show: ReadSignal<bool>, set_show: WriteSignal<bool>) -> imp
                         // AI 后端设置
                         <AiBackendSection locale=locale />
 
+                        // 插件声明的设置项
+                        <PluginSettingsSection locale=locale />
+
                         // 混合模式占位符
                         <div class="opacity-50 pointer-events-none grayscale">
                              <div class="flex items-center justify-between">
@@ -1,8 +1,9 @@
 // apps/web/src/components/settings_sections.rs
 //! # Settings Modal — Section Components
 //!
-//! Extracted sub-sections: Sync Mode, AI Backend.
+//! Extracted sub-sections: Sync Mode, AI Backend, Plugin Settings.
 
+use crate::components::plugin_widget::SettingInput;
 use crate::i18n::{Locale, t};
 use leptos::prelude::*;
 
@@ -83,3 +84,70 @@ pub fn AiBackendSection(locale: RwSignal<Locale>) -> impl IntoView {
         }
     }
 }
+
+/// Settings declared by plugins in their manifest `ui.settings` (one card per plugin).
+#[component]
+pub fn PluginSettingsSection(locale: RwSignal<Locale>) -> impl IntoView {
+    move || {
+        let ctx = expect_context::<crate::hooks::use_core::PluginUiContext>();
+        let plugins: Vec<_> = ctx
+            .plugins
+            .get()
+            .into_iter()
+            .filter(|p| !p.ui.settings.is_empty())
+            .collect();
+        if plugins.is_empty() {
+            return None;
+        }
+        Some(view! {
+            <div class="flex flex-col gap-3">
+                <h3 class="font-medium text-primary">{move || t::settings::plugin_settings(locale.get())}</h3>
+                {plugins
+                    .into_iter()
+                    .map(|plugin| {
+                        let plugin_id = plugin.id.clone();
+                        view! {
+                            <div class="bg-sidebar p-4 rounded-lg border border-default flex flex-col gap-3">
+                                <span class="font-medium text-primary">{plugin.name.clone()}</span>
+                                {plugin
+                                    .ui
+                                    .settings
+                                    .into_iter()
+                                    .map(|field| {
+                                        let value = plugin
+                                            .settings
+                                            .get(&field.key)
+                                            .cloned()
+                                            .unwrap_or_else(|| field.default.clone());
+                                        let on_change = {
+                                            let plugin_id = plugin_id.clone();
+                                            let key = field.key.clone();
+                                            Callback::new(move |v| {
+                                                ctx.on_set_plugin_setting
+                                                    .run((plugin_id.clone(), key.clone(), v))
+                                            })
+                                        };
+                                        view! {
+                                            <div class="flex justify-between items-center gap-4">
+                                                <div class="min-w-0">
+                                                    <span class="text-sm text-primary">{field.label.clone()}</span>
+                                                    {field
+                                                        .description
+                                                        .clone()
+                                                        .map(|d| view! { <p class="text-xs text-muted">{d}</p> })}
+                                                </div>
+                                                <div class="w-40 flex-none flex justify-end">
+                                                    <SettingInput field=field value=value on_change=on_change />
+                                                </div>
+                                            </div>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </div>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+        })
+    }
+}
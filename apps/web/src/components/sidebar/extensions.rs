@@ -1,22 +1,138 @@
 // apps\web\src\components\sidebar
 //! # ExtensionsView 组件 (ExtensionsView Component)
 //!
-//! 扩展视图的占位符。计划在第三阶段实现插件系统。
+//! 列出已加载插件，并渲染插件在清单 `ui.panels` 中声明的侧边栏面板。
+//!
+//! 面板打开或刷新时以无参数调用其处理函数，返回值按 `Widget` 解析渲染；
+//! 组件交互调用插件后自动刷新面板。
 
-use crate::components::icons::Puzzle;
+use crate::components::icons::{Puzzle, RefreshCw};
+use crate::components::plugin_widget::PluginWidget;
+use crate::hooks::use_core::PluginUiContext;
+use crate::i18n::{Locale, t};
+use deve_core::plugin::ui::{PluginPanel, Widget, WidgetAction};
 use leptos::prelude::*;
 
 #[component]
 pub fn ExtensionsView() -> impl IntoView {
+    let locale = use_context::<RwSignal<Locale>>().expect("locale context");
+    let ctx = expect_context::<PluginUiContext>();
+
     view! {
          <div class="h-full w-full bg-sidebar flex flex-col">
             <div class="flex-none h-12 flex items-center justify-between px-3 border-b border-default">
-                 <span class="font-medium text-sm text-primary">"Extensions"</span>
+                 <span class="font-medium text-sm text-primary">{move || t::sidebar::extensions(locale.get())}</span>
             </div>
-            <div class="flex-1 flex flex-col items-center justify-center text-muted p-4 text-center">
-                <Puzzle class="w-12 h-12 mb-2" />
-                <p class="text-sm">"Plugin System coming in Phase 3"</p>
+            {move || {
+                let plugins = ctx.plugins.get();
+                if plugins.is_empty() {
+                    return view! {
+                        <div class="flex-1 flex flex-col items-center justify-center text-muted p-4 text-center">
+                            <Puzzle class="w-12 h-12 mb-2" />
+                            <p class="text-sm">{move || t::sidebar::no_plugins(locale.get())}</p>
+                        </div>
+                    }
+                    .into_any();
+                }
+                view! {
+                    <div class="flex-1 overflow-y-auto">
+                        {plugins
+                            .into_iter()
+                            .map(|plugin| {
+                                let plugin_id = plugin.id.clone();
+                                view! {
+                                    <div class="border-b border-default py-2">
+                                        <div class="px-3 pb-1 flex items-baseline gap-2">
+                                            <span class="text-sm font-medium text-primary truncate">{plugin.name}</span>
+                                            <span class="text-xs text-muted">{plugin.version}</span>
+                                        </div>
+                                        {plugin
+                                            .ui
+                                            .panels
+                                            .into_iter()
+                                            .map(|panel| view! { <PanelView plugin_id=plugin_id.clone() panel=panel /> })
+                                            .collect_view()}
+                                    </div>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                }
+                .into_any()
+            }}
+        </div>
+    }
+}
+
+/// 单个插件面板: 调用处理函数并渲染返回的组件
+#[component]
+fn PanelView(plugin_id: String, panel: PluginPanel) -> impl IntoView {
+    let ctx = expect_context::<PluginUiContext>();
+    let (render_req, set_render_req) = signal(None::<String>);
+    let (action_req, set_action_req) = signal(None::<String>);
+    let (widget, set_widget) = signal(None::<Result<Widget, String>>);
+
+    let refresh = {
+        let plugin_id = plugin_id.clone();
+        let handler = panel.handler.clone();
+        Callback::new(move |_: ()| {
+            let req_id = uuid::Uuid::new_v4().to_string();
+            set_render_req.set(Some(req_id.clone()));
+            ctx.on_plugin_call
+                .run((req_id, plugin_id.clone(), handler.clone(), vec![]));
+        })
+    };
+    refresh.run(());
+
+    let on_action = {
+        let plugin_id = plugin_id.clone();
+        Callback::new(move |action: WidgetAction| {
+            let req_id = uuid::Uuid::new_v4().to_string();
+            set_action_req.set(Some(req_id.clone()));
+            ctx.on_plugin_call
+                .run((req_id, plugin_id.clone(), action.handler, action.args));
+        })
+    };
+
+    // 按 req_id 匹配响应: 面板渲染结果或交互完成后刷新
+    Effect::new(move |_| {
+        let Some((req_id, result, error)) = ctx.last_response.get() else {
+            return;
+        };
+        if render_req.get_untracked().as_deref() == Some(req_id.as_str()) {
+            set_render_req.set(None);
+            let parsed = match (result, error) {
+                (_, Some(e)) => Err(e),
+                (Some(value), None) => serde_json::from_value(value).map_err(|e| e.to_string()),
+                (None, None) => Err("Empty response".to_string()),
+            };
+            set_widget.set(Some(parsed));
+        } else if action_req.get_untracked().as_deref() == Some(req_id.as_str()) {
+            set_action_req.set(None);
+            if let Some(e) = error {
+                set_widget.set(Some(Err(e)));
+            } else {
+                refresh.run(());
+            }
+        }
+    });
+
+    view! {
+        <div class="mt-1">
+            <div class="group flex items-center justify-between px-3 py-1">
+                <span class="text-xs font-bold uppercase tracking-wide text-muted truncate">{panel.title}</span>
+                <button
+                    class="p-0.5 rounded text-muted hover:text-primary hover:bg-hover opacity-0 group-hover:opacity-100"
+                    on:click=move |_| refresh.run(())
+                >
+                    <RefreshCw class="w-3.5 h-3.5" />
+                </button>
             </div>
+            {move || match widget.get() {
+                None => view! { <div class="px-3 py-1 text-xs text-muted">"…"</div> }.into_any(),
+                Some(Err(e)) => view! { <div class="px-3 py-1 text-xs text-red-500 break-words">{e}</div> }.into_any(),
+                Some(Ok(w)) => view! { <PluginWidget widget=w on_action=on_action /> }.into_any(),
+            }}
         </div>
     }
 }
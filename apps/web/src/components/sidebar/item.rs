@@ -6,14 +6,16 @@
 //!
 //! **核心功能清单**:
 //! - 递归渲染：遇到文件夹时递归渲染子节点。
-//! - 交互：点击打开文档，点击展开/折叠文件夹。
+//! - 交互：点击打开文档，点击展开/折叠文件夹，点击附件在新标签页预览。
 //! - 上下文菜单：右键或点击更多按钮触发 `handle_action` (Rename, Copy, Move, Delete)。
 //!
 //! **类型**: Core MUST (核心必选)
 
+use crate::api::WsService;
 use crate::components::dropdown::AnchorRect;
 use crate::components::sidebar::types::FileActionsContext;
 use crate::components::sidebar_menu::{MenuAction, SidebarMenu};
+use deve_core::models::NodeKind;
 use deve_core::tree::FileNode;
 use leptos::prelude::*;
 use wasm_bindgen::JsCast;
@@ -23,7 +25,9 @@ pub fn FileTreeItem(node: FileNode, #[prop(default = 0)] depth: usize) -> impl I
     let actions = expect_context::<FileActionsContext>();
 
     let (is_expanded, set_expanded) = signal(true);
-    let is_folder = node.doc_id.is_none();
+    let is_folder = node.kind == NodeKind::Dir;
+    let attachment_path = (node.kind == NodeKind::Attachment).then(|| node.path.clone());
+    let ws = use_context::<WsService>();
 
     let padding = format!("padding-left: {}px", depth * 12 + 8);
 
@@ -118,6 +122,12 @@ pub fn FileTreeItem(node: FileNode, #[prop(default = 0)] depth: usize) -> impl I
                         set_expanded.update(|b| *b = !*b);
                     } else if let Some(id) = node.doc_id {
                         on_select.run(id);
+                    } else if let Some(path) = &attachment_path
+                        && let Some(ws) = &ws
+                        && let Some(window) = web_sys::window()
+                    {
+                        // 附件在新标签页中由浏览器直接预览
+                        let _ = window.open_with_url_and_target(&ws.attachment_url(path), "_blank");
                     }
                 }
             >
@@ -5,7 +5,7 @@
 
 #![allow(dead_code)]
 
-use deve_core::models::{DocId, NodeId};
+use deve_core::models::{DocId, NodeId, NodeKind};
 use deve_core::tree::FileNode;
 use std::collections::BTreeMap;
 
@@ -68,6 +68,7 @@ pub fn build_file_tree(docs: Vec<(DocId, String)>) -> Vec<FileNode> {
                 name,
                 path: full_path,
                 doc_id: Some(doc_id),
+                kind: NodeKind::File,
                 children,
             },
             None => FileNode {
@@ -75,6 +76,7 @@ pub fn build_file_tree(docs: Vec<(DocId, String)>) -> Vec<FileNode> {
                 name,
                 path: full_path,
                 doc_id: None,
+                kind: NodeKind::Dir,
                 children,
             },
         }
@@ -42,6 +42,14 @@ unsafe extern "C" {
     #[wasm_bindgen(js_name = setReadOnly)]
     pub fn set_read_only(read_only: bool);
 
+    /// 设置图片附件解析上下文 (服务器 HTTP 基地址, 当前文档路径)
+    #[wasm_bindgen(js_namespace = window, js_name = setAttachmentContext)]
+    pub fn set_attachment_context(base: &str, doc_path: &str);
+
+    /// 以本地编辑在文末追加文本 (经 OT 状态机发送)，编辑器未就绪或只读时返回 false
+    #[wasm_bindgen(js_namespace = window, js_name = appendEditorText)]
+    pub fn append_editor_text(text: &str) -> bool;
+
     /// Mobile: 在光标处插入文本
     #[wasm_bindgen(js_namespace = window, js_name = mobileInsertText)]
     pub fn mobile_insert_text(text: &str);
@@ -19,6 +19,7 @@ use crate::hooks::use_core::EditorContext;
 use deve_core::models::DocId;
 use deve_core::protocol::ClientMessage;
 use deve_core::security::RepoKey;
+use deve_core::state::ot::OtClient;
 use leptos::html::Div;
 use leptos::prelude::*;
 use wasm_bindgen::prelude::*;
@@ -60,6 +61,9 @@ pub fn use_editor(
     // 生成会话 client_id
     let client_id = (js_sys::Math::random() * 1_000_000.0) as u64;
 
+    // OT 状态机: 本地操作逐个发送，收到 Ack 后再发下一个
+    let ot = StoredValue::new(OtClient::default());
+
     // 初始请求: 打开文档
     let ws_clone = ws.clone();
     let set_doc_ver = core.set_doc_version;
@@ -69,6 +73,7 @@ pub fn use_editor(
         set_history.set(Vec::new());
         set_doc_ver.set(0);
         set_playback_version.set(0);
+        ot.set_value(OtClient::default());
         set_load_state.set("loading".to_string());
         set_load_progress.set((0, 0));
         set_load_eta_ms.set(0);
@@ -101,6 +106,7 @@ pub fn use_editor(
                 local_version,
                 set_local_version,
                 set_history,
+                ot,
                 is_playback,
                 set_playback_version,
                 set_load_state,
@@ -136,15 +142,19 @@ pub fn use_editor(
                     }
                 };
 
-                // 转换 Delta 为 Op 并发送
+                // 转换 Delta 为 Op，交由 OT 状态机决定是否立即发送
                 for delta in deltas {
-                    let ops = delta.to_ops();
-                    for op in ops {
-                        ws_for_update.send(ClientMessage::Edit {
-                            doc_id,
-                            op: op.clone(),
-                            client_id,
-                        });
+                    for op in delta.to_ops() {
+                        if let Some((base_seq, op)) =
+                            ot.try_update_value(|c| c.local_edit(op)).flatten()
+                        {
+                            ws_for_update.send(ClientMessage::Edit {
+                                doc_id,
+                                op,
+                                base_seq,
+                                client_id,
+                            });
+                        }
                     }
                 }
 
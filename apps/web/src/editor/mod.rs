@@ -3,7 +3,7 @@
 //!
 //! **架构作用**:
 //! 编辑器的主 UI 容器。
-//! 整合了 CodeMirror (通过 `hook.rs`)，大纲视图 (`Outline`)，以及旁观者模式/历史回放的状态展示。
+//! 整合了 CodeMirror (通过 `hook.rs`)，大纲视图 (`Outline`)，反链面板 (`Backlinks`)，以及旁观者模式/历史回放的状态展示。
 //!
 //! **核心功能清单**:
 //! - `Editor`: 主组件。
@@ -11,8 +11,9 @@
 //! - 显示 "Spectator Mode" (旁观者模式) 提示。
 //! - 管理大纲视图的显示/隐藏。
 
+use crate::api::WsService;
 use crate::components::layout_context::EditorContentContext;
-use crate::hooks::use_core::EditorContext;
+use crate::hooks::use_core::{DocContext, EditorContext};
 use crate::hooks::use_outline::use_outline;
 use deve_core::models::DocId;
 use leptos::html::Div;
@@ -49,6 +50,8 @@ pub fn Editor(
 
     // 获取 CoreState 用于 Spectator 模式
     let core = expect_context::<EditorContext>();
+    let (load_state, pending_jump, set_pending_jump) =
+        (core.load_state, core.pending_jump, core.set_pending_jump);
 
     provide_context(EditorContentContext { content });
 
@@ -61,6 +64,35 @@ pub fn Editor(
         ffi::set_read_only(should_readonly);
     });
 
+    // 搜索结果跳转: 目标文档加载完成后滚动到命中行 (嵌入模式不消费)
+    Effect::new(move |_| {
+        if embedded || load_state.get() != "ready" {
+            return;
+        }
+        if let Some((target, line)) = pending_jump.get()
+            && target == doc_id
+        {
+            set_pending_jump.set(None);
+            ffi::scroll_global(line);
+        }
+    });
+
+    // 图片附件按当前文档路径解析 (相对链接 -> /api/repo/attachment)
+    if let (Some(ws), Some(doc_ctx)) = (use_context::<WsService>(), use_context::<DocContext>()) {
+        Effect::new(move |_| {
+            ws.endpoint.track();
+            let doc_path = doc_ctx
+                .docs
+                .with(|docs| {
+                    docs.iter()
+                        .find(|(id, _)| *id == doc_id)
+                        .map(|(_, p)| p.clone())
+                })
+                .unwrap_or_default();
+            ffi::set_attachment_context(&ws.http_base(), &doc_path);
+        });
+    }
+
     // 大纲状态 (嵌入模式下默认禁用且不显示，否则使用持久化状态)
     let (outline_pref, set_outline_pref) = use_outline();
     let show_outline = Signal::derive(move || !embedded && outline_pref.get());
@@ -120,10 +152,17 @@ pub fn Editor(
                             class="bg-[#f9f9f9] border-l border-gray-200 transition-all duration-300 ease-in-out overflow-hidden"
                             style=move || if show_outline.get() { "width: 250px; opacity: 1;" } else { "width: 0px; opacity: 0;" }
                         >
-                            <crate::components::outline::Outline
-                                content=content
-                                on_scroll=on_scroll
-                            />
+                            <div class="h-full flex flex-col">
+                                <div class="flex-1 min-h-0">
+                                    <crate::components::outline::Outline
+                                        content=content
+                                        on_scroll=on_scroll
+                                    />
+                                </div>
+                                <div class="max-h-[40%] overflow-y-auto">
+                                    <crate::components::backlinks::Backlinks doc_id=doc_id />
+                                </div>
+                            </div>
                         </div>
                     }.into_any()
                 } else {
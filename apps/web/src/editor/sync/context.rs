@@ -5,6 +5,7 @@ use crate::api::WsService;
 use crate::editor::EditorStats;
 use deve_core::models::{DocId, Op};
 use deve_core::security::RepoKey;
+use deve_core::state::ot::OtClient;
 use leptos::prelude::*;
 
 /// 同步消息处理所需的全部上下文
@@ -12,6 +13,7 @@ use leptos::prelude::*;
 /// # Invariants
 /// - `doc_id` 在整个编辑器会话中保持不变
 /// - `client_id` 唯一标识当前客户端实例
+/// - `ot` 的版本只由快照、他人的 NewOp 与自己的 Ack 推进
 /// - `repo_key` 仅在内存中持有，页面卸载时清除 (NEVER persisted)
 pub struct SyncContext<'a> {
     pub doc_id: DocId,
@@ -24,6 +26,8 @@ pub struct SyncContext<'a> {
     pub set_local_version: WriteSignal<u64>,
     // 历史记录
     pub set_history: WriteSignal<Vec<(u64, Op)>>,
+    // OT 状态机 (在途/缓冲的本地操作)
+    pub ot: StoredValue<OtClient>,
     // 回放控制
     pub is_playback: ReadSignal<bool>,
     pub set_playback_version: WriteSignal<u64>,
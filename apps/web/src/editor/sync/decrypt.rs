@@ -6,6 +6,7 @@
 //! ## Invariants
 //! - 若无 RepoKey，加密操作将被跳过并记录警告
 //! - 解密后的 LedgerEntry.op 与 NewOp 走相同的应用路径
+//! - CRDT 操作需完整文档状态才能定位，收到时重新打开文档而非直接应用
 
 use super::context::SyncContext;
 use crate::editor::EditorStats;
@@ -54,6 +55,12 @@ fn apply_decrypted_entry(ctx: &SyncContext, entry: deve_core::models::LedgerEntr
         return;
     }
 
+    if matches!(entry.op, deve_core::models::Op::Crdt(_)) {
+        ctx.ws
+            .send(deve_core::protocol::ClientMessage::OpenDoc { doc_id: ctx.doc_id });
+        return;
+    }
+
     // 应用远程操作到编辑器
     if let Ok(json) = serde_json::to_string(&entry.op) {
         applyRemoteOp(&json);
@@ -11,7 +11,7 @@ mod snapshot;
 use super::EditorStats;
 use super::ffi::{applyRemoteOp, getEditorContent};
 use context::SyncContext;
-use deve_core::protocol::ServerMessage;
+use deve_core::protocol::{ClientMessage, ServerMessage};
 use deve_core::security::RepoKey;
 use leptos::prelude::*;
 
@@ -50,6 +50,25 @@ pub fn handle_server_message(msg: ServerMessage, ctx: &SyncContext) {
             }
             handle_new_op(ctx, op, seq, origin_id);
         }
+        ServerMessage::Ack {
+            doc_id: msg_doc_id,
+            seq,
+            client_id,
+            ..
+        } => {
+            if msg_doc_id != ctx.doc_id || client_id != ctx.client_id {
+                return;
+            }
+            // 在途操作已定序，发送下一个缓冲操作
+            if let Some((base_seq, op)) = ctx.ot.try_update_value(|c| c.ack(seq)).flatten() {
+                ctx.ws.send(ClientMessage::Edit {
+                    doc_id: ctx.doc_id,
+                    op,
+                    base_seq,
+                    client_id: ctx.client_id,
+                });
+            }
+        }
         ServerMessage::SyncHello {
             peer_id, vector: _, ..
         } => {
@@ -92,10 +111,23 @@ fn handle_new_op(ctx: &SyncContext, op: deve_core::models::Op, seq: u64, origin_
         return;
     }
 
-    // 过滤回显 (Echoes)
+    // 服务端合并的 CRDT 操作需完整文档状态才能定位，重新打开文档
+    if matches!(op, deve_core::models::Op::Crdt(_)) {
+        ctx.ws.send(ClientMessage::OpenDoc { doc_id: ctx.doc_id });
+        return;
+    }
+
+    // 过滤回显 (Echoes)；他人的操作需与本地未确认操作互相变换后再应用
     if origin_id != ctx.client_id {
-        if let Ok(json) = serde_json::to_string(&op) {
-            applyRemoteOp(&json);
+        let ops = ctx
+            .ot
+            .try_update_value(|c| c.remote_op(&op, seq))
+            .unwrap_or_default();
+        // 变换结果按顺序逐个应用 (批量接口按原文坐标解释)
+        for op in &ops {
+            if let Ok(json) = serde_json::to_string(op) {
+                applyRemoteOp(&json);
+            }
         }
         let txt = getEditorContent();
         if let Some(cb) = ctx.on_stats {
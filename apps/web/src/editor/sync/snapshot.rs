@@ -7,6 +7,7 @@ use crate::editor::ffi::{applyRemoteContent, applyRemoteOpsBatch, getEditorConte
 use crate::editor::prefetch::{PrefetchConfig, apply_ops_in_batches};
 use deve_core::models::Op;
 use deve_core::protocol::ClientMessage;
+use deve_core::state::ot::OtClient;
 use leptos::prelude::*;
 
 /// 处理 ServerMessage::Snapshot
@@ -39,6 +40,7 @@ pub(super) fn handle_snapshot(
     applyRemoteContent(&new_content);
     ctx.set_content.set(new_content);
     ctx.set_local_version.set(base_seq);
+    ctx.ot.set_value(OtClient::new(base_seq));
 
     // 初始化回放范围
     ctx.set_playback_version.set(base_seq);
@@ -60,6 +62,7 @@ pub(super) fn handle_snapshot(
     let set_playback_version = ctx.set_playback_version;
     let set_load_state = ctx.set_load_state;
     let on_stats = ctx.on_stats;
+    let ot = ctx.ot;
 
     let apply_batch = std::rc::Rc::new(move |batch: &[(u64, Op)]| {
         let ops_only: Vec<Op> = batch.iter().map(|(_, op)| op.clone()).collect();
@@ -95,6 +98,7 @@ pub(super) fn handle_snapshot(
         emit_stats(on_stats, &txt);
         set_content.set(txt);
         set_playback_version.set(version);
+        ot.update_value(|c| *c = OtClient::new(c.version().max(version)));
         set_load_state.set("ready".to_string());
         set_load_progress.set((0, 0));
         set_load_eta_ms.set(0);
@@ -123,6 +127,8 @@ pub(super) fn handle_snapshot(
 fn finalize_load(ctx: &SyncContext, version: u64, load_start: f64) {
     ctx.set_local_version.set(version);
     ctx.set_playback_version.set(version);
+    ctx.ot
+        .update_value(|c| *c = OtClient::new(c.version().max(version)));
     ctx.set_load_state.set("ready".to_string());
     ctx.set_load_progress.set((0, 0));
     ctx.set_load_eta_ms.set(0);
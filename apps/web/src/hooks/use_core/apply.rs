@@ -19,6 +19,7 @@ pub fn apply_tree_delta(current: &mut Vec<FileNode>, delta: TreeDelta) {
             name,
             path,
             doc_id,
+            kind,
         } => {
             remove_node(current, node_id);
             remove_node_by_path(current, &path);
@@ -27,6 +28,7 @@ pub fn apply_tree_delta(current: &mut Vec<FileNode>, delta: TreeDelta) {
                 name,
                 path,
                 doc_id,
+                kind,
                 children: vec![],
             };
             insert_node(current, parent_id, new_node);
@@ -126,7 +128,7 @@ fn update_children_paths(node: &mut FileNode, old_prefix: &str, new_prefix: &str
 
 /// 排序节点 (文件夹优先，然后按字母顺序)
 fn sort_nodes(nodes: &mut [FileNode]) {
-    nodes.sort_by(|a, b| match (a.doc_id.is_none(), b.doc_id.is_none()) {
+    nodes.sort_by(|a, b| match (a.is_folder(), b.is_folder()) {
         (true, false) => std::cmp::Ordering::Less,
         (false, true) => std::cmp::Ordering::Greater,
         _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
@@ -142,11 +142,17 @@ pub fn create_sync_callbacks(
     }
 }
 
-/// 其他回调 (插件, 搜索, 统计)
+/// 其他回调 (插件, 搜索, 属性查询, 链接图, 统计)
 pub struct MiscCallbacks {
     pub on_stats: Callback<crate::editor::EditorStats>,
     pub on_plugin_call: Callback<(String, String, String, Vec<serde_json::Value>)>,
+    /// 保存插件设置 `(plugin_id, key, value)`
+    pub on_set_plugin_setting: Callback<(String, String, serde_json::Value)>,
     pub on_search: Callback<String>,
+    pub on_query_docs: Callback<(String, deve_core::protocol::DocQuery)>,
+    pub on_get_backlinks: Callback<DocId>,
+    pub on_get_unresolved_links: Callback<()>,
+    pub on_get_graph: Callback<(Option<DocId>, u32)>,
 }
 
 /// 创建其他回调
@@ -174,6 +180,17 @@ pub fn create_misc_callbacks(
         },
     );
 
+    let ws_setting = ws.clone();
+    let on_set_plugin_setting = Callback::new(
+        move |(plugin_id, key, value): (String, String, serde_json::Value)| {
+            ws_setting.send(ClientMessage::SetPluginSetting {
+                plugin_id,
+                key,
+                value,
+            });
+        },
+    );
+
     let ws_search = ws.clone();
     let on_search = Callback::new(move |query: String| {
         if load_state.get_untracked() != "ready" {
@@ -183,10 +200,35 @@ pub fn create_misc_callbacks(
         ws_search.send(ClientMessage::Search { query, limit: 50 });
     });
 
+    let ws_query = ws.clone();
+    let on_query_docs = Callback::new(move |(req_id, query)| {
+        ws_query.send(ClientMessage::QueryDocs { req_id, query });
+    });
+
+    let ws_backlinks = ws.clone();
+    let on_get_backlinks = Callback::new(move |doc_id| {
+        ws_backlinks.send(ClientMessage::GetBacklinks { doc_id });
+    });
+
+    let ws_unresolved = ws.clone();
+    let on_get_unresolved_links = Callback::new(move |_| {
+        ws_unresolved.send(ClientMessage::GetUnresolvedLinks);
+    });
+
+    let ws_graph = ws.clone();
+    let on_get_graph = Callback::new(move |(root, depth)| {
+        ws_graph.send(ClientMessage::GetGraph { root, depth });
+    });
+
     MiscCallbacks {
         on_stats,
         on_plugin_call,
+        on_set_plugin_setting,
         on_search,
+        on_query_docs,
+        on_get_backlinks,
+        on_get_unresolved_links,
+        on_get_graph,
     }
 }
 
@@ -4,6 +4,7 @@
 //! 处理 Git 风格的版本控制操作回调。
 
 use crate::api::WsService;
+use deve_core::models::DocId;
 use deve_core::protocol::ClientMessage;
 use leptos::prelude::*;
 
@@ -18,6 +19,7 @@ pub struct SourceControlCallbacks {
     pub on_commit: Callback<String>,
     pub on_get_history: Callback<u32>,
     pub on_get_doc_diff: Callback<String>,
+    pub on_get_historical_diff: Callback<(String, DocId)>,
 }
 
 /// 创建 Source Control 回调
@@ -68,6 +70,11 @@ pub fn create_source_control_callbacks(ws: &WsService) -> SourceControlCallbacks
         ws6.send(ClientMessage::GetDocDiff { path });
     });
 
+    let ws_hist = ws.clone();
+    let on_get_historical_diff = Callback::new(move |(commit_id, doc_id): (String, DocId)| {
+        ws_hist.send(ClientMessage::GetHistoricalDiff { commit_id, doc_id });
+    });
+
     let ws7 = ws.clone();
     let on_discard_file = Callback::new(move |path: String| {
         leptos::logging::log!("on_discard_file callback triggered for: {}", path);
@@ -84,5 +91,6 @@ pub fn create_source_control_callbacks(ws: &WsService) -> SourceControlCallbacks
         on_commit,
         on_get_history,
         on_get_doc_diff,
+        on_get_historical_diff,
     }
 }
@@ -0,0 +1,244 @@
+// apps/web/src/hooks/use_core/chat_agent.rs
+//! # Agent Chat Events (智能体聊天事件)
+//!
+//! 将服务端智能体的流式事件与持久化会话折叠为聊天消息列表。
+//! 纯函数，时间戳由调用方传入，便于在宿主环境测试。
+//!
+//! ## Invariants
+//! - 文本增量只追加到末尾的同请求 `assistant` 消息；工具调用之后的文本另起一条
+//! - 工具消息以 `(req_id, round, index)` 聚合参数增量，以 `call_id` 匹配结果
+
+use super::types::{ChatMessage, ToolActivity};
+use deve_core::protocol::AgentMessage;
+
+/// 追加文本增量
+pub fn push_text_delta(msgs: &mut Vec<ChatMessage>, req_id: &str, delta: &str, ts_ms: u64) {
+    if let Some(last) = msgs.last_mut()
+        && last.role == "assistant"
+        && last.req_id.as_deref() == Some(req_id)
+    {
+        last.content.push_str(delta);
+        return;
+    }
+    msgs.push(ChatMessage {
+        role: "assistant".to_string(),
+        content: delta.to_string(),
+        req_id: Some(req_id.to_string()),
+        ts_ms,
+        tool: None,
+    });
+}
+
+fn find_tool<'a>(
+    msgs: &'a mut [ChatMessage],
+    req_id: &str,
+    matches: impl Fn(&ToolActivity) -> bool,
+) -> Option<&'a mut ToolActivity> {
+    msgs.iter_mut()
+        .rev()
+        .filter(|m| m.req_id.as_deref() == Some(req_id))
+        .filter_map(|m| m.tool.as_mut())
+        .find(|t| matches(t))
+}
+
+fn push_tool(msgs: &mut Vec<ChatMessage>, req_id: Option<String>, tool: ToolActivity, ts_ms: u64) {
+    msgs.push(ChatMessage {
+        role: "tool".to_string(),
+        content: String::new(),
+        req_id,
+        ts_ms,
+        tool: Some(tool),
+    });
+}
+
+/// 合并工具调用增量
+#[allow(clippy::too_many_arguments)]
+pub fn apply_tool_delta(
+    msgs: &mut Vec<ChatMessage>,
+    req_id: &str,
+    round: u32,
+    index: u32,
+    id: Option<String>,
+    name: Option<String>,
+    arguments: Option<String>,
+    ts_ms: u64,
+) {
+    let tool = match find_tool(msgs, req_id, |t| t.round == round && t.index == index) {
+        Some(tool) => tool,
+        None => {
+            let tool = ToolActivity {
+                round,
+                index,
+                ..Default::default()
+            };
+            push_tool(msgs, Some(req_id.to_string()), tool, ts_ms);
+            msgs.last_mut()
+                .and_then(|m| m.tool.as_mut())
+                .expect("tool message just pushed")
+        }
+    };
+    if let Some(id) = id {
+        tool.call_id = id;
+    }
+    if let Some(name) = name {
+        tool.name = name;
+    }
+    if let Some(arguments) = arguments {
+        tool.arguments.push_str(&arguments);
+    }
+}
+
+/// 填入工具执行结果
+pub fn apply_tool_result(
+    msgs: &mut Vec<ChatMessage>,
+    req_id: &str,
+    call_id: &str,
+    name: &str,
+    content: String,
+    is_error: bool,
+    ts_ms: u64,
+) {
+    if let Some(tool) = find_tool(msgs, req_id, |t| t.call_id == call_id) {
+        tool.result = Some(content);
+        tool.is_error = is_error;
+        return;
+    }
+    let tool = ToolActivity {
+        call_id: call_id.to_string(),
+        name: name.to_string(),
+        result: Some(content),
+        is_error,
+        ..Default::default()
+    };
+    push_tool(msgs, Some(req_id.to_string()), tool, ts_ms);
+}
+
+/// 持久化会话转为聊天消息 (工具结果并入对应的调用)
+pub fn conversation_messages(messages: &[AgentMessage]) -> Vec<ChatMessage> {
+    let mut out: Vec<ChatMessage> = Vec::new();
+    let mut round = 0;
+    for message in messages {
+        match message.role.as_str() {
+            "tool" => {
+                let call_id = message.tool_call_id.as_deref().unwrap_or_default();
+                if let Some(tool) = out
+                    .iter_mut()
+                    .rev()
+                    .filter_map(|m| m.tool.as_mut())
+                    .find(|t| t.call_id == call_id)
+                {
+                    tool.result = Some(message.content.clone());
+                    tool.is_error = message.is_error;
+                }
+            }
+            role => {
+                if !message.content.is_empty() || message.tool_calls.is_empty() {
+                    out.push(ChatMessage {
+                        role: role.to_string(),
+                        content: message.display_text().to_string(),
+                        req_id: None,
+                        ts_ms: message.ts_ms,
+                        tool: None,
+                    });
+                }
+                if !message.tool_calls.is_empty() {
+                    round += 1;
+                }
+                for (index, call) in message.tool_calls.iter().enumerate() {
+                    let tool = ToolActivity {
+                        round,
+                        index: index as u32,
+                        call_id: call.id.clone(),
+                        name: call.name.clone(),
+                        arguments: call.arguments.clone(),
+                        result: None,
+                        is_error: false,
+                    };
+                    push_tool(&mut out, None, tool, message.ts_ms);
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deve_core::protocol::AgentToolCall;
+
+    #[test]
+    fn test_stream_events_fold_into_messages() {
+        let mut msgs = vec![ChatMessage {
+            role: "user".into(),
+            content: "hi".into(),
+            ..Default::default()
+        }];
+        apply_tool_delta(
+            &mut msgs,
+            "r",
+            1,
+            0,
+            Some("c1".into()),
+            Some("read_file".into()),
+            None,
+            1,
+        );
+        apply_tool_delta(
+            &mut msgs,
+            "r",
+            1,
+            1,
+            Some("c2".into()),
+            Some("git_status".into()),
+            None,
+            1,
+        );
+        apply_tool_delta(&mut msgs, "r", 1, 0, None, None, Some("{\"pa".into()), 2);
+        apply_tool_delta(&mut msgs, "r", 1, 0, None, None, Some("th\":1}".into()), 2);
+        apply_tool_result(&mut msgs, "r", "c2", "git_status", "clean".into(), false, 3);
+        apply_tool_result(
+            &mut msgs,
+            "r",
+            "c1",
+            "read_file",
+            "Error: x".into(),
+            true,
+            3,
+        );
+        push_text_delta(&mut msgs, "r", "do", 4);
+        push_text_delta(&mut msgs, "r", "ne", 4);
+
+        assert_eq!(msgs.len(), 4);
+        let first = msgs[1].tool.as_ref().unwrap();
+        assert_eq!(first.arguments, "{\"path\":1}");
+        assert!(first.is_error);
+        assert_eq!(
+            msgs[2].tool.as_ref().unwrap().result.as_deref(),
+            Some("clean")
+        );
+        assert_eq!(msgs[3].content, "done");
+        assert_ne!(msgs[1].render_key(), msgs[2].render_key());
+    }
+
+    #[test]
+    fn test_conversation_messages_merge_results() {
+        let call = AgentToolCall {
+            id: "c1".into(),
+            name: "mcp__notes__search".into(),
+            arguments: "{}".into(),
+        };
+        let messages = vec![
+            AgentMessage::user("expanded".into(), Some("typed".into()), 1),
+            AgentMessage::assistant(String::new(), vec![call], 2),
+            AgentMessage::tool("c1".into(), "found".into(), false, 3),
+            AgentMessage::assistant("answer".into(), Vec::new(), 4),
+        ];
+        let msgs = conversation_messages(&messages);
+        let roles: Vec<&str> = msgs.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, vec!["user", "tool", "assistant"]);
+        assert_eq!(msgs[0].content, "typed");
+        let tool = msgs[1].tool.as_ref().unwrap();
+        assert_eq!((tool.round, tool.result.as_deref()), (1, Some("found")));
+    }
+}
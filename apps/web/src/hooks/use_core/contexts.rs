@@ -8,12 +8,18 @@
 
 use super::diff_session::DiffSessionWire;
 use super::state::PluginResponse;
-use super::types::ChatMessage;
+use super::types::{ChatDoneInfo, ChatMessage};
 use crate::editor::EditorStats;
 use deve_core::models::{DocId, PeerId};
+use deve_core::plugin::ui::PluginInfo;
+use deve_core::protocol::{
+    Backlink, ConversationSummary, DocQuery, DocRow, KnowledgeGraph, McpPromptInfo,
+    McpResourceInfo, SearchHit, UnresolvedLink,
+};
 use deve_core::source_control::{ChangeEntry, CommitInfo};
 use deve_core::tree::FileNode;
 use leptos::prelude::*;
+use std::collections::HashMap;
 
 /// 文档与文件树上下文
 #[derive(Clone)]
@@ -28,7 +34,7 @@ pub struct DocContext {
     pub on_doc_delete: Callback<String>,
     pub on_doc_copy: Callback<(String, String)>,
     pub on_doc_move: Callback<(String, String)>,
-    pub search_results: ReadSignal<Vec<(String, String, f32)>>,
+    pub search_results: ReadSignal<Vec<SearchHit>>,
     pub on_search: Callback<String>,
 }
 
@@ -48,6 +54,8 @@ pub struct EditorContext {
     pub playback_version: ReadSignal<u64>,
     pub set_playback_version: WriteSignal<u64>,
     pub is_spectator: Signal<bool>,
+    pub pending_jump: ReadSignal<Option<(DocId, usize)>>,
+    pub set_pending_jump: WriteSignal<Option<(DocId, usize)>>,
 }
 
 /// AI 聊天与插件上下文
@@ -128,4 +136,51 @@ pub struct SystemMetricsData {
 #[derive(Clone)]
 pub struct DashboardContext {
     pub metrics: ReadSignal<Option<SystemMetricsData>>,
+    /// 属性查询结果 (req_id -> rows)
+    pub doc_queries: ReadSignal<HashMap<String, Vec<DocRow>>>,
+    /// 发送 `QueryDocs` (req_id, query)
+    pub on_query_docs: Callback<(String, DocQuery)>,
+}
+
+/// 链接图上下文 (反链面板、断链报告卡片、知识图谱面板消费)
+#[derive(Clone, Copy)]
+pub struct LinksContext {
+    pub backlinks: ReadSignal<Option<(DocId, Vec<Backlink>)>>,
+    pub unresolved_links: ReadSignal<Vec<UnresolvedLink>>,
+    pub on_get_backlinks: Callback<DocId>,
+    pub on_get_unresolved_links: Callback<()>,
+    pub graph: ReadSignal<Option<KnowledgeGraph>>,
+    /// `(root, depth)`
+    pub on_get_graph: Callback<(Option<DocId>, u32)>,
+}
+
+/// 插件 UI 上下文 (命令面板、扩展侧边栏、设置面板消费)
+#[derive(Clone, Copy)]
+pub struct PluginUiContext {
+    pub plugins: ReadSignal<Vec<PluginInfo>>,
+    /// 最近一次插件响应 (按 req_id 匹配)
+    pub last_response: ReadSignal<PluginResponse>,
+    /// `(req_id, plugin_id, fn_name, args)`
+    pub on_plugin_call: Callback<(String, String, String, Vec<serde_json::Value>)>,
+    /// `(plugin_id, key, value)`
+    pub on_set_plugin_setting: Callback<(String, String, serde_json::Value)>,
+}
+
+/// 服务端智能体会话上下文 (聊天面板的历史菜单与停止按钮消费)
+#[derive(Clone, Copy)]
+pub struct ConversationContext {
+    pub conversations: ReadSignal<Vec<ConversationSummary>>,
+    /// 当前会话 ID (None 表示下一条消息新建会话)
+    pub active: ReadSignal<Option<String>>,
+    pub set_active: WriteSignal<Option<String>>,
+    /// 进行中的运行 (req_id)
+    pub running: ReadSignal<Option<String>>,
+    pub last_done: ReadSignal<Option<ChatDoneInfo>>,
+}
+
+/// MCP 目录上下文 (聊天面板的斜杠命令与附加上下文消费)
+#[derive(Clone, Copy)]
+pub struct McpContext {
+    pub prompts: ReadSignal<Vec<McpPromptInfo>>,
+    pub resources: ReadSignal<Vec<McpResourceInfo>>,
 }
@@ -58,6 +58,12 @@ pub fn setup_handshake_effect(
             ws_clone.send(ClientMessage::ListDocs);
             // 请求仓库列表
             ws_clone.send(ClientMessage::ListRepos);
+            // 请求插件列表 (命令、面板与设置项)
+            ws_clone.send(ClientMessage::ListPlugins);
+            // 请求 MCP 提示词与资源目录 (聊天斜杠命令与附加上下文)
+            ws_clone.send(ClientMessage::ListMcpCatalog);
+            // 请求服务端智能体的会话列表 (聊天历史菜单)
+            ws_clone.send(ClientMessage::ListConversations);
         }
     });
 }
@@ -72,6 +78,9 @@ pub fn setup_message_effect(ws: &WsService, signals: &CoreSignals) {
     let set_current_doc = signals.set_current_doc;
     let set_peers = signals.set_peers;
     let set_plugin_response = signals.set_plugin_response;
+    let set_plugins = signals.set_plugins;
+    let set_mcp_prompts = signals.set_mcp_prompts;
+    let set_mcp_resources = signals.set_mcp_resources;
     let set_search_results = signals.set_search_results;
     let set_sync_mode = signals.set_sync_mode;
     let set_pending_ops_count = signals.set_pending_ops_count;
@@ -87,7 +96,21 @@ pub fn setup_message_effect(ws: &WsService, signals: &CoreSignals) {
     let set_current_repo = signals.set_current_repo;
     let set_chat_messages = signals.set_chat_messages;
     let set_is_chat_streaming = signals.set_is_chat_streaming;
+    let agent = effects_msg::AgentSignals {
+        set_chat_messages: signals.set_chat_messages,
+        set_is_chat_streaming: signals.set_is_chat_streaming,
+        set_conversations: signals.set_conversations,
+        active_conversation: signals.active_conversation,
+        set_active_conversation: signals.set_active_conversation,
+        chat_run: signals.chat_run,
+        set_chat_run: signals.set_chat_run,
+        set_chat_done: signals.set_chat_done,
+    };
     let set_system_metrics = signals.set_system_metrics;
+    let set_doc_queries = signals.set_doc_queries;
+    let set_backlinks = signals.set_backlinks;
+    let set_unresolved_links = signals.set_unresolved_links;
+    let set_graph = signals.set_graph;
     let changes_refresh = Rc::new(RefCell::new(None::<Timeout>));
 
     Effect::new(move |_| {
@@ -123,6 +146,13 @@ pub fn setup_message_effect(ws: &WsService, signals: &CoreSignals) {
                 } => {
                     set_plugin_response.set(Some((req_id, result, error)));
                 }
+                ServerMessage::PluginList { plugins } => {
+                    set_plugins.set(plugins);
+                }
+                ServerMessage::McpCatalog { prompts, resources } => {
+                    set_mcp_prompts.set(prompts);
+                    set_mcp_resources.set(resources);
+                }
                 ServerMessage::ChatChunk {
                     req_id,
                     delta,
@@ -136,6 +166,32 @@ pub fn setup_message_effect(ws: &WsService, signals: &CoreSignals) {
                         set_is_chat_streaming,
                     );
                 }
+                msg @ (ServerMessage::ChatStarted { .. }
+                | ServerMessage::ChatToolCallDelta { .. }
+                | ServerMessage::ChatToolResult { .. }
+                | ServerMessage::ChatDone { .. }
+                | ServerMessage::ConversationList { .. }
+                | ServerMessage::ConversationData { .. }
+                | ServerMessage::ConversationDeleted { .. }) => {
+                    effects_msg::handle_agent_message(&ws_rx, msg, agent);
+                }
+                ServerMessage::Backlinks { doc_id, links } => {
+                    set_backlinks.set(Some((doc_id, links)));
+                }
+                ServerMessage::UnresolvedLinks { links } => {
+                    set_unresolved_links.set(links);
+                }
+                ServerMessage::Graph { graph, .. } => {
+                    set_graph.set(Some(graph));
+                }
+                ServerMessage::LinkRewriteOffer {
+                    old_path,
+                    new_path,
+                    docs,
+                    links,
+                } => {
+                    effects_msg::handle_link_rewrite_offer(&ws_rx, old_path, new_path, docs, links);
+                }
                 ServerMessage::SearchResults { results } => {
                     set_search_results.set(results);
                 }
@@ -221,7 +277,7 @@ pub fn setup_message_effect(ws: &WsService, signals: &CoreSignals) {
                         set_nodes.update(|nodes| apply_tree_delta(nodes, delta));
                     });
                 }
-                other => effects_msg::handle_remaining(other, set_system_metrics),
+                other => effects_msg::handle_remaining(other, set_system_metrics, set_doc_queries),
             }
         }
     });
@@ -5,12 +5,12 @@
 
 use crate::api::WsService;
 use deve_core::models::{DocId, PeerId};
-use deve_core::protocol::ClientMessage;
+use deve_core::protocol::{ClientMessage, ConversationSummary};
 use leptos::prelude::*;
 
 use deve_core::protocol::ServerMessage;
 
-use super::types::{ChatMessage, PeerSession};
+use super::types::{ChatDoneInfo, ChatMessage, PeerSession};
 
 /// 处理 DocList 消息
 pub fn handle_doc_list(
@@ -55,21 +55,9 @@ pub fn handle_chat_chunk(
     set_is_chat_streaming: WriteSignal<bool>,
 ) {
     if let Some(text) = delta {
+        let now = js_sys::Date::now() as u64;
         set_chat_messages.update(|msgs| {
-            if let Some(msg) = msgs
-                .iter_mut()
-                .rev()
-                .find(|m| m.req_id.as_deref() == Some(&req_id))
-            {
-                msg.content.push_str(&text);
-            } else {
-                msgs.push(ChatMessage {
-                    role: "assistant".to_string(),
-                    content: text,
-                    req_id: Some(req_id.clone()),
-                    ts_ms: js_sys::Date::now() as u64,
-                });
-            }
+            super::chat_agent::push_text_delta(msgs, &req_id, &text, now);
         });
         set_is_chat_streaming.set(true);
     }
@@ -78,6 +66,101 @@ pub fn handle_chat_chunk(
     }
 }
 
+/// 服务端智能体事件涉及的信号
+#[derive(Clone, Copy)]
+pub struct AgentSignals {
+    pub set_chat_messages: WriteSignal<Vec<ChatMessage>>,
+    pub set_is_chat_streaming: WriteSignal<bool>,
+    pub set_conversations: WriteSignal<Vec<ConversationSummary>>,
+    pub active_conversation: ReadSignal<Option<String>>,
+    pub set_active_conversation: WriteSignal<Option<String>>,
+    pub chat_run: ReadSignal<Option<String>>,
+    pub set_chat_run: WriteSignal<Option<String>>,
+    pub set_chat_done: WriteSignal<Option<ChatDoneInfo>>,
+}
+
+/// 处理服务端智能体事件与会话消息
+pub fn handle_agent_message(ws: &WsService, msg: ServerMessage, s: AgentSignals) {
+    let now = js_sys::Date::now() as u64;
+    match msg {
+        ServerMessage::ChatStarted {
+            req_id,
+            conversation_id,
+        } => {
+            s.set_active_conversation.set(Some(conversation_id));
+            s.set_chat_run.set(Some(req_id));
+            s.set_is_chat_streaming.set(true);
+        }
+        ServerMessage::ChatToolCallDelta {
+            req_id,
+            round,
+            index,
+            id,
+            name,
+            arguments,
+        } => {
+            s.set_chat_messages.update(|msgs| {
+                super::chat_agent::apply_tool_delta(
+                    msgs, &req_id, round, index, id, name, arguments, now,
+                );
+            });
+        }
+        ServerMessage::ChatToolResult {
+            req_id,
+            call_id,
+            name,
+            content,
+            is_error,
+        } => {
+            s.set_chat_messages.update(|msgs| {
+                super::chat_agent::apply_tool_result(
+                    msgs, &req_id, &call_id, &name, content, is_error, now,
+                );
+            });
+        }
+        ServerMessage::ChatDone {
+            req_id,
+            conversation_id,
+            reason,
+            error,
+        } => {
+            if s.chat_run.get_untracked().as_deref() == Some(req_id.as_str()) {
+                s.set_chat_run.set(None);
+                s.set_is_chat_streaming.set(false);
+            }
+            if !conversation_id.is_empty() && s.active_conversation.get_untracked().is_none() {
+                s.set_active_conversation.set(Some(conversation_id));
+            }
+            s.set_chat_done.set(Some(ChatDoneInfo {
+                req_id,
+                reason,
+                error,
+            }));
+            ws.send(ClientMessage::ListConversations);
+        }
+        ServerMessage::ConversationList { conversations } => {
+            s.set_conversations.set(conversations);
+        }
+        ServerMessage::ConversationData { conversation } => {
+            s.set_active_conversation
+                .set(Some(conversation.summary.id.clone()));
+            s.set_chat_messages
+                .set(super::chat_agent::conversation_messages(
+                    &conversation.messages,
+                ));
+        }
+        ServerMessage::ConversationDeleted { id } => {
+            s.set_conversations
+                .update(|list| list.retain(|c| c.id != id));
+            if s.active_conversation.get_untracked().as_deref() == Some(id.as_str()) {
+                s.set_active_conversation.set(None);
+                s.set_chat_messages.set(Vec::new());
+            }
+        }
+        _ => {}
+    }
+}
+
 /// 处理 BranchSwitched 消息
 pub fn handle_branch_switched(
     ws: &WsService,
@@ -115,15 +198,42 @@ pub fn handle_repo_switched(
     ws.send(ClientMessage::GetCommitHistory { limit: 50 });
 }
 
+/// 处理 LinkRewriteOffer: 询问用户是否改写入链
+pub fn handle_link_rewrite_offer(
+    ws: &WsService,
+    old_path: String,
+    new_path: String,
+    docs: u32,
+    links: u32,
+) {
+    let locale = use_context::<RwSignal<crate::i18n::Locale>>()
+        .map(|l| l.get_untracked())
+        .unwrap_or_default();
+    let prompt = crate::i18n::t::sidebar::rewrite_links_prompt(locale, links, docs);
+    let confirmed = web_sys::window()
+        .and_then(|w| w.confirm_with_message(&prompt).ok())
+        .unwrap_or(false);
+    if confirmed {
+        ws.send(ClientMessage::RewriteLinks { old_path, new_path });
+    }
+}
+
 /// 处理 effects.rs 主 match 未覆盖的剩余消息
 ///
-/// 当前仅处理 `SystemMetrics`，其余忽略。
+/// 当前处理 `SystemMetrics` 与 `QueryDocsResult`，其余忽略。
 /// 随着消息类型增加，可继续在此扩展。
 pub fn handle_remaining(
     msg: ServerMessage,
     set_system_metrics: WriteSignal<Option<super::contexts::SystemMetricsData>>,
+    set_doc_queries: WriteSignal<
+        std::collections::HashMap<String, Vec<deve_core::protocol::DocRow>>,
+    >,
 ) {
-    if let ServerMessage::SystemMetrics {
+    if let ServerMessage::QueryDocsResult { req_id, rows } = msg {
+        set_doc_queries.update(|map| {
+            map.insert(req_id, rows);
+        });
+    } else if let ServerMessage::SystemMetrics {
         cpu_usage_percent,
         memory_used_mb,
         active_connections,
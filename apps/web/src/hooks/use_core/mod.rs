@@ -10,10 +10,12 @@
 //! - `effects`: 响应式效果
 //! - `callbacks`: 用户交互回调
 //! - `callbacks_sc`: Source Control 回调 (已拆分)
+//! - `chat_agent`: 服务端智能体事件折叠为聊天消息
 
 pub mod apply;
 pub mod callbacks;
 pub mod callbacks_sc;
+pub mod chat_agent;
 pub mod contexts;
 pub mod diff_session;
 pub mod effects;
@@ -118,6 +120,8 @@ pub fn use_core() -> CoreState {
         on_plugin_call: misc_callbacks.on_plugin_call,
         search_results: signals.search_results,
         on_search: misc_callbacks.on_search,
+        pending_jump: signals.pending_jump,
+        set_pending_jump: signals.set_pending_jump,
         load_state: signals.load_state,
         set_load_state: signals.set_load_state,
         load_progress: signals.load_progress,
@@ -160,6 +164,7 @@ pub fn use_core() -> CoreState {
         diff_content: signals.diff_content,
         set_diff_content: signals.set_diff_content,
         on_get_doc_diff: sc_callbacks.on_get_doc_diff,
+        on_get_historical_diff: sc_callbacks.on_get_historical_diff,
         on_merge_peer: sync_callbacks.on_merge_peer,
         tree_nodes: signals.tree_nodes,
         chat_messages: signals.chat_messages,
@@ -170,11 +175,38 @@ pub fn use_core() -> CoreState {
         set_ai_mode: signals.set_ai_mode,
     };
 
-    // 7. 提供上下文 (CoreState 兼容 + 6 个子上下文 + Dashboard)
+    // 7. 提供上下文 (CoreState 兼容 + 6 个子上下文 + Dashboard + Links + PluginUi + Mcp + Conversation)
     provide_context(state.clone());
     provide::provide_sub_contexts(&state);
     provide_context(contexts::DashboardContext {
         metrics: signals.system_metrics,
+        doc_queries: signals.doc_queries,
+        on_query_docs: misc_callbacks.on_query_docs,
+    });
+    provide_context(contexts::LinksContext {
+        backlinks: signals.backlinks,
+        unresolved_links: signals.unresolved_links,
+        on_get_backlinks: misc_callbacks.on_get_backlinks,
+        on_get_unresolved_links: misc_callbacks.on_get_unresolved_links,
+        graph: signals.graph,
+        on_get_graph: misc_callbacks.on_get_graph,
+    });
+    provide_context(contexts::PluginUiContext {
+        plugins: signals.plugins,
+        last_response: signals.plugin_response,
+        on_plugin_call: misc_callbacks.on_plugin_call,
+        on_set_plugin_setting: misc_callbacks.on_set_plugin_setting,
+    });
+    provide_context(contexts::McpContext {
+        prompts: signals.mcp_prompts,
+        resources: signals.mcp_resources,
+    });
+    provide_context(contexts::ConversationContext {
+        conversations: signals.conversations,
+        active: signals.active_conversation,
+        set_active: signals.set_active_conversation,
+        running: signals.chat_run,
+        last_done: signals.chat_done,
     });
 
     state
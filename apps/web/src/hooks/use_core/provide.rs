@@ -38,6 +38,8 @@ pub fn provide_sub_contexts(state: &CoreState) {
         playback_version: state.playback_version,
         set_playback_version: state.set_playback_version,
         is_spectator: state.is_spectator,
+        pending_jump: state.pending_jump,
+        set_pending_jump: state.set_pending_jump,
     });
     provide_context(ChatContext {
         messages: state.chat_messages,
@@ -5,6 +5,11 @@
 
 use crate::editor::EditorStats;
 use deve_core::models::{DocId, PeerId};
+use deve_core::plugin::ui::PluginInfo;
+use deve_core::protocol::{
+    Backlink, ConversationSummary, DocRow, KnowledgeGraph, McpPromptInfo, McpResourceInfo,
+    SearchHit, UnresolvedLink,
+};
 use deve_core::source_control::{ChangeEntry, CommitInfo};
 use deve_core::tree::FileNode;
 use leptos::prelude::*;
@@ -12,7 +17,7 @@ use std::collections::HashMap;
 
 use super::contexts::SystemMetricsData;
 use super::diff_session::DiffSessionWire;
-use super::types::{ChatMessage, PeerSession};
+use super::types::{ChatDoneInfo, ChatMessage, PeerSession};
 
 /// 插件响应类型别名
 pub type PluginResponse = Option<(String, Option<serde_json::Value>, Option<String>)>;
@@ -38,6 +43,14 @@ pub struct CoreSignals {
     // 插件
     pub plugin_response: ReadSignal<PluginResponse>,
     pub set_plugin_response: WriteSignal<PluginResponse>,
+    /// 已加载插件及其 UI 贡献 (来自 `PluginList`)
+    pub plugins: ReadSignal<Vec<PluginInfo>>,
+    pub set_plugins: WriteSignal<Vec<PluginInfo>>,
+    /// MCP 提示词与资源目录 (来自 `McpCatalog`)
+    pub mcp_prompts: ReadSignal<Vec<McpPromptInfo>>,
+    pub set_mcp_prompts: WriteSignal<Vec<McpPromptInfo>>,
+    pub mcp_resources: ReadSignal<Vec<McpResourceInfo>>,
+    pub set_mcp_resources: WriteSignal<Vec<McpResourceInfo>>,
 
     // AI Chat
     pub chat_messages: ReadSignal<Vec<ChatMessage>>,
@@ -46,10 +59,25 @@ pub struct CoreSignals {
     pub set_is_chat_streaming: WriteSignal<bool>,
     pub ai_mode: ReadSignal<String>,
     pub set_ai_mode: WriteSignal<String>,
+    /// 服务端智能体会话列表 (来自 `ConversationList`)
+    pub conversations: ReadSignal<Vec<ConversationSummary>>,
+    pub set_conversations: WriteSignal<Vec<ConversationSummary>>,
+    /// 当前会话 ID (None 表示下一条消息新建会话)
+    pub active_conversation: ReadSignal<Option<String>>,
+    pub set_active_conversation: WriteSignal<Option<String>>,
+    /// 进行中的智能体运行 (req_id)
+    pub chat_run: ReadSignal<Option<String>>,
+    pub set_chat_run: WriteSignal<Option<String>>,
+    /// 最近一次运行结束事件 (来自 `ChatDone`)
+    pub chat_done: ReadSignal<Option<ChatDoneInfo>>,
+    pub set_chat_done: WriteSignal<Option<ChatDoneInfo>>,
 
     // 搜索
-    pub search_results: ReadSignal<Vec<(String, String, f32)>>,
-    pub set_search_results: WriteSignal<Vec<(String, String, f32)>>,
+    pub search_results: ReadSignal<Vec<SearchHit>>,
+    pub set_search_results: WriteSignal<Vec<SearchHit>>,
+    /// 待跳转位置 (文档, 行号)，由编辑器在该文档加载完成后消费
+    pub pending_jump: ReadSignal<Option<(DocId, usize)>>,
+    pub set_pending_jump: WriteSignal<Option<(DocId, usize)>>,
 
     // 文档加载状态
     pub load_state: ReadSignal<String>,
@@ -101,6 +129,18 @@ pub struct CoreSignals {
     // Dashboard 系统指标
     pub system_metrics: ReadSignal<Option<SystemMetricsData>>,
     pub set_system_metrics: WriteSignal<Option<SystemMetricsData>>,
+
+    // 属性查询结果 (按 req_id 缓存)
+    pub doc_queries: ReadSignal<HashMap<String, Vec<DocRow>>>,
+    pub set_doc_queries: WriteSignal<HashMap<String, Vec<DocRow>>>,
+
+    // 链接图 (当前文档反链、断链报告、知识图谱)
+    pub backlinks: ReadSignal<Option<(DocId, Vec<Backlink>)>>,
+    pub set_backlinks: WriteSignal<Option<(DocId, Vec<Backlink>)>>,
+    pub unresolved_links: ReadSignal<Vec<UnresolvedLink>>,
+    pub set_unresolved_links: WriteSignal<Vec<UnresolvedLink>>,
+    pub graph: ReadSignal<Option<KnowledgeGraph>>,
+    pub set_graph: WriteSignal<Option<KnowledgeGraph>>,
 }
 
 /// 初始化所有核心信号
@@ -110,10 +150,18 @@ pub fn init_signals() -> CoreSignals {
     let (stats, set_stats) = signal(EditorStats::default());
     let (peers, set_peers) = signal(HashMap::<PeerId, PeerSession>::new());
     let (plugin_response, set_plugin_response) = signal(None);
+    let (plugins, set_plugins) = signal(Vec::<PluginInfo>::new());
+    let (mcp_prompts, set_mcp_prompts) = signal(Vec::<McpPromptInfo>::new());
+    let (mcp_resources, set_mcp_resources) = signal(Vec::<McpResourceInfo>::new());
     let (chat_messages, set_chat_messages) = signal(Vec::new());
     let (is_chat_streaming, set_is_chat_streaming) = signal(false);
     let (ai_mode, set_ai_mode) = signal("agent-bridge".to_string());
+    let (conversations, set_conversations) = signal(Vec::<ConversationSummary>::new());
+    let (active_conversation, set_active_conversation) = signal(None::<String>);
+    let (chat_run, set_chat_run) = signal(None::<String>);
+    let (chat_done, set_chat_done) = signal(None::<ChatDoneInfo>);
     let (search_results, set_search_results) = signal(Vec::new());
+    let (pending_jump, set_pending_jump) = signal(None::<(DocId, usize)>);
     let (load_state, set_load_state) = signal("ready".to_string());
     let (load_progress, set_load_progress) = signal((0usize, 0usize));
     let (load_eta_ms, set_load_eta_ms) = signal(0u64);
@@ -133,6 +181,10 @@ pub fn init_signals() -> CoreSignals {
     let (diff_content, set_diff_content) = signal(None::<DiffSessionWire>);
     let (tree_nodes, set_tree_nodes) = signal(Vec::<FileNode>::new());
     let (system_metrics, set_system_metrics) = signal(None::<SystemMetricsData>);
+    let (doc_queries, set_doc_queries) = signal(HashMap::<String, Vec<DocRow>>::new());
+    let (backlinks, set_backlinks) = signal(None::<(DocId, Vec<Backlink>)>);
+    let (unresolved_links, set_unresolved_links) = signal(Vec::<UnresolvedLink>::new());
+    let (graph, set_graph) = signal(None::<KnowledgeGraph>);
 
     CoreSignals {
         docs,
@@ -145,14 +197,30 @@ pub fn init_signals() -> CoreSignals {
         set_peers,
         plugin_response,
         set_plugin_response,
+        plugins,
+        set_plugins,
+        mcp_prompts,
+        set_mcp_prompts,
+        mcp_resources,
+        set_mcp_resources,
         chat_messages,
         set_chat_messages,
         is_chat_streaming,
         set_is_chat_streaming,
         ai_mode,
         set_ai_mode,
+        conversations,
+        set_conversations,
+        active_conversation,
+        set_active_conversation,
+        chat_run,
+        set_chat_run,
+        chat_done,
+        set_chat_done,
         search_results,
         set_search_results,
+        pending_jump,
+        set_pending_jump,
         load_state,
         set_load_state,
         load_progress,
@@ -190,5 +258,13 @@ pub fn init_signals() -> CoreSignals {
         set_tree_nodes,
         system_metrics,
         set_system_metrics,
+        doc_queries,
+        set_doc_queries,
+        backlinks,
+        set_backlinks,
+        unresolved_links,
+        set_unresolved_links,
+        graph,
+        set_graph,
     }
 }
@@ -5,6 +5,7 @@
 use crate::api::WsService;
 use crate::editor::EditorStats;
 use deve_core::models::{DocId, PeerId, VersionVector};
+use deve_core::protocol::SearchHit;
 use deve_core::source_control::{ChangeEntry, CommitInfo};
 use deve_core::tree::FileNode;
 use leptos::prelude::*;
@@ -20,12 +21,51 @@ pub struct PeerSession {
     pub last_seen: u64, // timestamp
 }
 
+/// 服务端智能体的一次工具调用 (参数流式累积，结果到达后填入)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ToolActivity {
+    pub round: u32,
+    pub index: u32,
+    pub call_id: String,
+    pub name: String,
+    pub arguments: String,
+    pub result: Option<String>,
+    pub is_error: bool,
+}
+
+/// 智能体运行结束事件 (`ChatDone`)
 #[derive(Clone, Debug, PartialEq)]
+pub struct ChatDoneInfo {
+    pub req_id: String,
+    pub reason: String,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct ChatMessage {
-    pub role: String, // "user" or "assistant"
+    pub role: String, // "user", "assistant" or "tool"
     pub content: String,
     pub req_id: Option<String>, // To link with streaming chunks
     pub ts_ms: u64,
+    /// `tool` 消息的调用详情
+    pub tool: Option<ToolActivity>,
+}
+
+impl ChatMessage {
+    /// 列表渲染键: 内容变化 (流式增量、工具结果) 时随之变化以触发重绘
+    pub fn render_key(&self) -> String {
+        let req_id = self.req_id.as_deref().unwrap_or_default();
+        let id = match &self.tool {
+            Some(tool) => format!("{}:{}:{}:{}", req_id, tool.round, tool.index, tool.call_id),
+            None => format!("{}:{}:{}", req_id, self.role, self.ts_ms),
+        };
+        let tool_len = self
+            .tool
+            .as_ref()
+            .map(|t| t.arguments.len() + t.result.as_ref().map_or(0, |r| r.len() + 1))
+            .unwrap_or(0);
+        format!("{}:{}:{}", id, self.content.len(), tool_len)
+    }
 }
 
 #[derive(Clone)]
@@ -61,8 +101,10 @@ pub struct CoreState {
     pub set_ai_mode: WriteSignal<String>,
 
     // 搜索
-    pub search_results: ReadSignal<Vec<(String, String, f32)>>, // (doc_id, path, score)
+    pub search_results: ReadSignal<Vec<SearchHit>>,
     pub on_search: Callback<String>,
+    pub pending_jump: ReadSignal<Option<(DocId, usize)>>,
+    pub set_pending_jump: WriteSignal<Option<(DocId, usize)>>,
 
     // 文档加载状态
     pub load_state: ReadSignal<String>,
@@ -123,6 +165,7 @@ pub struct CoreState {
     pub diff_content: ReadSignal<Option<DiffSessionWire>>,
     pub set_diff_content: WriteSignal<Option<DiffSessionWire>>,
     pub on_get_doc_diff: Callback<String>,
+    pub on_get_historical_diff: Callback<(String, DocId)>,
     pub on_merge_peer: Callback<String>,
 
     // 文件树 (增量更新)
@@ -137,6 +180,7 @@ impl CoreState {
                 content: content.to_string(),
                 req_id,
                 ts_ms: js_sys::Date::now() as u64,
+                tool: None,
             });
         });
     }
@@ -156,6 +200,7 @@ impl CoreState {
                     content: delta.to_string(),
                     req_id: Some(req_id.to_string()),
                     ts_ms: js_sys::Date::now() as u64,
+                    tool: None,
                 });
             }
         });
@@ -158,3 +158,94 @@ pub fn example_find_bugs(locale: Locale) -> &'static str {
         Locale::Zh => "这个文件里有什么 bug？",
     }
 }
+
+pub fn attach_context(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Attach MCP resource",
+        Locale::Zh => "附加 MCP 资源",
+    }
+}
+
+pub fn remove_attachment(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Remove",
+        Locale::Zh => "移除",
+    }
+}
+
+pub fn prompt_commands(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "MCP prompts",
+        Locale::Zh => "MCP 提示词",
+    }
+}
+
+pub fn stop(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Stop",
+        Locale::Zh => "停止",
+    }
+}
+
+pub fn history(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Conversations",
+        Locale::Zh => "会话历史",
+    }
+}
+
+pub fn new_chat(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "New chat",
+        Locale::Zh => "新会话",
+    }
+}
+
+pub fn delete_conversation(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Delete conversation",
+        Locale::Zh => "删除会话",
+    }
+}
+
+pub fn no_conversations(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "No saved conversations",
+        Locale::Zh => "暂无会话",
+    }
+}
+
+pub fn tool_call(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Tool",
+        Locale::Zh => "工具",
+    }
+}
+
+pub fn tool_arguments(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Arguments",
+        Locale::Zh => "参数",
+    }
+}
+
+pub fn tool_result(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Result",
+        Locale::Zh => "结果",
+    }
+}
+
+pub fn tool_running(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Running...",
+        Locale::Zh => "执行中...",
+    }
+}
+
+pub fn max_rounds_reached(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Stopped after reaching the tool round limit",
+        Locale::Zh => "已达到工具轮次上限，已停止",
+    }
+}
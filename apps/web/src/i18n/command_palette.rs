@@ -67,3 +67,10 @@ pub fn toggle_ai_chat(locale: Locale) -> &'static str {
         Locale::Zh => "AI: 切换聊天面板",
     }
 }
+
+pub fn toggle_graph(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "View: Toggle Graph",
+        Locale::Zh => "视图: 切换知识图谱",
+    }
+}
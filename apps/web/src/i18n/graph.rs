@@ -0,0 +1,60 @@
+// apps\web\src\i18n
+//! # I18n Graph Module (知识图谱翻译)
+
+use super::Locale;
+
+pub fn title(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Graph",
+        Locale::Zh => "知识图谱",
+    }
+}
+
+pub fn local_graph(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Around current doc",
+        Locale::Zh => "仅当前文档周边",
+    }
+}
+
+pub fn depth(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Depth",
+        Locale::Zh => "深度",
+    }
+}
+
+pub fn all_folders(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "All folders",
+        Locale::Zh => "全部文件夹",
+    }
+}
+
+pub fn all_tags(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "All tags",
+        Locale::Zh => "全部标签",
+    }
+}
+
+pub fn loading(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Loading graph...",
+        Locale::Zh => "正在加载图谱...",
+    }
+}
+
+pub fn truncated(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Showing the most connected documents only",
+        Locale::Zh => "仅显示连接最多的文档",
+    }
+}
+
+pub fn close(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Close",
+        Locale::Zh => "关闭",
+    }
+}
@@ -15,6 +15,7 @@
 //! - `command_palette`: 命令面板翻译
 //! - `search`: 搜索框翻译
 //! - `source_control`: 版本控制面板翻译
+//! - `graph`: 知识图谱面板翻译
 
 pub mod bottom_bar;
 pub mod chat;
@@ -22,6 +23,7 @@ pub mod command_palette;
 pub mod common;
 pub mod context_menu;
 pub mod diff;
+pub mod graph;
 pub mod header;
 pub mod merge;
 pub mod playback;
@@ -65,6 +67,7 @@ pub mod t {
     pub use super::common;
     pub use super::context_menu;
     pub use super::diff;
+    pub use super::graph;
     pub use super::header;
     pub use super::merge;
     pub use super::playback;
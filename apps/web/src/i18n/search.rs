@@ -17,6 +17,15 @@ pub fn placeholder_branch(locale: Locale) -> &'static str {
     }
 }
 
+pub fn placeholder_fulltext(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => {
+            "Full-text: words \"phrase\" path: tag: after: before: in:history in:peers word~"
+        }
+        Locale::Zh => "全文: 关键词 \"短语\" path: tag: after: before: in:history in:peers 词~",
+    }
+}
+
 pub fn placeholder_file(locale: Locale) -> &'static str {
     match locale {
         Locale::En => "folder/.../file(.md)",
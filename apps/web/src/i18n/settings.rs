@@ -100,3 +100,10 @@ pub fn ai_backend_desc(locale: Locale) -> &'static str {
         Locale::Zh => "CLI: 外部 Agent。API: 内置 OpenAI 兼容接口。",
     }
 }
+
+pub fn plugin_settings(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Plugin Settings",
+        Locale::Zh => "插件设置",
+    }
+}
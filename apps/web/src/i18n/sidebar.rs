@@ -73,6 +73,13 @@ pub fn extensions(locale: Locale) -> &'static str {
     }
 }
 
+pub fn no_plugins(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "No plugins loaded",
+        Locale::Zh => "未加载插件",
+    }
+}
+
 pub fn more(locale: Locale) -> &'static str {
     match locale {
         Locale::En => "More",
@@ -107,3 +114,25 @@ pub fn local_master_branch(locale: Locale) -> &'static str {
         Locale::Zh => "本地 (主分支)",
     }
 }
+
+pub fn backlinks(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Backlinks",
+        Locale::Zh => "反向链接",
+    }
+}
+
+pub fn no_backlinks(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "No backlinks",
+        Locale::Zh => "暂无反向链接",
+    }
+}
+
+/// 重命名后询问是否改写入链
+pub fn rewrite_links_prompt(locale: Locale, links: u32, docs: u32) -> String {
+    match locale {
+        Locale::En => format!("Update {links} link(s) in {docs} document(s) to the new path?"),
+        Locale::Zh => format!("是否将 {docs} 个文档中的 {links} 处链接更新为新路径？"),
+    }
+}
@@ -0,0 +1,9 @@
+// crates/core/src/agent/mod.rs
+//! # Agent (服务端智能体)
+//!
+//! 智能体会话的持久化存储。循环本身 (模型流式调用、工具执行) 由上层实现，
+//! 消息类型见 `protocol::agent`。
+
+mod store;
+
+pub use store::{ConversationStore, MAX_TITLE_CHARS, conversation_title};
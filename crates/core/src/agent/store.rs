@@ -0,0 +1,212 @@
+// crates/core/src/agent/store.rs
+//! # Conversation Store (会话存储)
+//!
+//! **架构作用**:
+//! 持久化服务端智能体的会话，存放在独立的 redb 文件中 (不进入 Ledger，也不随仓库同步)。
+//!
+//! **核心功能清单**:
+//! - `ConversationStore`: `list` / `get` / `create` / `append` / `delete`。
+//!
+//! ## Invariants
+//! - 摘要与消息分表存放，列表只读取摘要
+//! - `append` 在同一事务内更新消息与摘要 (`message_count` / `updated_ms`)
+//!
+//! **类型**: Core MUST (核心必选)
+
+use crate::protocol::{AgentMessage, Conversation, ConversationSummary};
+use anyhow::{Result, anyhow};
+use redb::{Database, ReadableTable, TableDefinition};
+use std::path::Path;
+
+// Conversation ID -> JSON ConversationSummary (Bytes)
+const SUMMARIES: TableDefinition<&str, &[u8]> = TableDefinition::new("conversation_summaries");
+// Conversation ID -> JSON Vec<AgentMessage> (Bytes)
+const MESSAGES: TableDefinition<&str, &[u8]> = TableDefinition::new("conversation_messages");
+
+/// 会话标题的最大字符数
+pub const MAX_TITLE_CHARS: usize = 60;
+
+/// 由首条用户消息生成会话标题 (取首行并截断)
+pub fn conversation_title(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("");
+    if line.chars().count() <= MAX_TITLE_CHARS {
+        return line.to_string();
+    }
+    let mut title: String = line.chars().take(MAX_TITLE_CHARS - 1).collect();
+    title.push('…');
+    title
+}
+
+/// 智能体会话存储
+pub struct ConversationStore {
+    db: Database,
+}
+
+impl ConversationStore {
+    /// 打开或创建存储文件
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let db = Database::create(path)?;
+        let txn = db.begin_write()?;
+        txn.open_table(SUMMARIES)?;
+        txn.open_table(MESSAGES)?;
+        txn.commit()?;
+        Ok(Self { db })
+    }
+
+    /// 列出全部会话 (按最近更新倒序)
+    pub fn list(&self) -> Result<Vec<ConversationSummary>> {
+        let txn = self.db.begin_read()?;
+        let table = txn.open_table(SUMMARIES)?;
+        let mut list = Vec::new();
+        for item in table.iter()? {
+            let (_, bytes) = item?;
+            list.push(serde_json::from_slice::<ConversationSummary>(
+                bytes.value(),
+            )?);
+        }
+        list.sort_by(|a, b| b.updated_ms.cmp(&a.updated_ms).then(a.id.cmp(&b.id)));
+        Ok(list)
+    }
+
+    pub fn get(&self, id: &str) -> Result<Option<Conversation>> {
+        let txn = self.db.begin_read()?;
+        let summaries = txn.open_table(SUMMARIES)?;
+        let Some(summary) = summaries.get(id)? else {
+            return Ok(None);
+        };
+        let summary: ConversationSummary = serde_json::from_slice(summary.value())?;
+        let messages = txn.open_table(MESSAGES)?;
+        let messages = match messages.get(id)? {
+            Some(bytes) => serde_json::from_slice(bytes.value())?,
+            None => Vec::new(),
+        };
+        Ok(Some(Conversation { summary, messages }))
+    }
+
+    /// 新建空会话
+    pub fn create(&self, title: &str, now_ms: u64) -> Result<ConversationSummary> {
+        let summary = ConversationSummary {
+            id: uuid::Uuid::new_v4().to_string(),
+            title: conversation_title(title),
+            created_ms: now_ms,
+            updated_ms: now_ms,
+            message_count: 0,
+        };
+        let txn = self.db.begin_write()?;
+        {
+            let mut summaries = txn.open_table(SUMMARIES)?;
+            summaries.insert(
+                summary.id.as_str(),
+                serde_json::to_vec(&summary)?.as_slice(),
+            )?;
+            let mut messages = txn.open_table(MESSAGES)?;
+            messages.insert(summary.id.as_str(), b"[]".as_slice())?;
+        }
+        txn.commit()?;
+        Ok(summary)
+    }
+
+    /// 追加消息，返回更新后的摘要
+    pub fn append(
+        &self,
+        id: &str,
+        new_messages: &[AgentMessage],
+        now_ms: u64,
+    ) -> Result<ConversationSummary> {
+        let txn = self.db.begin_write()?;
+        let summary = {
+            let mut summaries = txn.open_table(SUMMARIES)?;
+            let mut summary: ConversationSummary = match summaries.get(id)? {
+                Some(bytes) => serde_json::from_slice(bytes.value())?,
+                None => return Err(anyhow!("Conversation not found: {}", id)),
+            };
+            let mut messages_table = txn.open_table(MESSAGES)?;
+            let mut messages: Vec<AgentMessage> = match messages_table.get(id)? {
+                Some(bytes) => serde_json::from_slice(bytes.value())?,
+                None => Vec::new(),
+            };
+            messages.extend_from_slice(new_messages);
+            summary.message_count = messages.len() as u32;
+            summary.updated_ms = now_ms;
+            messages_table.insert(id, serde_json::to_vec(&messages)?.as_slice())?;
+            summaries.insert(id, serde_json::to_vec(&summary)?.as_slice())?;
+            summary
+        };
+        txn.commit()?;
+        Ok(summary)
+    }
+
+    /// 删除会话，返回会话是否存在
+    pub fn delete(&self, id: &str) -> Result<bool> {
+        let txn = self.db.begin_write()?;
+        let existed = {
+            let mut summaries = txn.open_table(SUMMARIES)?;
+            let mut messages = txn.open_table(MESSAGES)?;
+            messages.remove(id)?;
+            summaries.remove(id)?.is_some()
+        };
+        txn.commit()?;
+        Ok(existed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversation_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("conversations.redb");
+        let (first, second) = {
+            let store = ConversationStore::open(&path).unwrap();
+            let first = store.create("  \nSummarize notes\nplease", 10).unwrap();
+            let second = store.create("Other", 20).unwrap();
+            assert_eq!(first.title, "Summarize notes");
+
+            let summary = store
+                .append(
+                    &first.id,
+                    &[
+                        AgentMessage::user("hi".into(), None, 30),
+                        AgentMessage::assistant("hello".into(), Vec::new(), 31),
+                    ],
+                    31,
+                )
+                .unwrap();
+            assert_eq!(summary.message_count, 2);
+            assert!(store.append("missing", &[], 40).is_err());
+            (first, second)
+        };
+
+        let store = ConversationStore::open(&path).unwrap();
+        let ids: Vec<_> = store.list().unwrap().into_iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![first.id.clone(), second.id.clone()]);
+
+        let conversation = store.get(&first.id).unwrap().unwrap();
+        assert_eq!(conversation.messages.len(), 2);
+        assert_eq!(conversation.messages[1].content, "hello");
+        assert_eq!(conversation.summary.created_ms, 10);
+
+        assert!(store.delete(&first.id).unwrap());
+        assert!(!store.delete(&first.id).unwrap());
+        assert!(store.get(&first.id).unwrap().is_none());
+        assert_eq!(store.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_conversation_title_truncates() {
+        let long = "x".repeat(100);
+        let title = conversation_title(&long);
+        assert_eq!(title.chars().count(), MAX_TITLE_CHARS);
+        assert!(title.ends_with('…'));
+        assert_eq!(conversation_title(""), "");
+    }
+}
@@ -9,6 +9,7 @@
 //! - `AppProfile`: 应用运行模式枚举 (Standard/LowSpec)
 //! - `SyncMode`: P2P 同步模式枚举 (Auto/Manual)
 //! - `MergeStrategy`: 合并冲突策略枚举 (Manual/Auto)
+//! - `DocModel`: 文档模型枚举 (Text/Crdt，仓库级设置)
 //! - `Config`: 聚合所有配置项的结构体
 //! - `Config::load()`: 从环境加载配置的工厂方法
 //!
@@ -50,6 +51,16 @@ pub enum AppProfile {
     LowSpec,
 }
 
+impl AppProfile {
+    /// 服务端文档状态缓存的字节预算
+    pub fn doc_cache_bytes(self) -> usize {
+        match self {
+            AppProfile::Standard => 64 * 1024 * 1024,
+            AppProfile::LowSpec => 8 * 1024 * 1024,
+        }
+    }
+}
+
 impl FromStr for AppProfile {
     type Err = ();
 
@@ -84,6 +95,32 @@ impl FromStr for MergeStrategy {
     }
 }
 
+/// 文档模型 (07_diff_logic.md)
+///
+/// 仓库级设置，保存在本地库 `REPO_METADATA` 中 (见 `RepoManager::set_doc_model`)。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocModel {
+    /// 位置操作 (默认): 并发编辑经三方合并处理
+    #[default]
+    Text,
+    /// 序列 CRDT: 操作携带稳定字符标识，并发编辑取操作并集自动合并
+    Crdt,
+}
+
+impl FromStr for DocModel {
+    type Err = ();
+
+    /// 显式设置，未知取值报错而非回退
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(DocModel::Text),
+            "crdt" => Ok(DocModel::Crdt),
+            _ => Err(()),
+        }
+    }
+}
+
 /// 核心配置结构体
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
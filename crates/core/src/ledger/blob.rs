@@ -0,0 +1,285 @@
+// crates/core/src/ledger/blob.rs
+//! # 附件 Blob Store (内容寻址)
+//!
+//! 非 Markdown 文件 (图片、PDF 等) 不进入操作日志，而是按内容 SHA-256 存储为分块 Blob，
+//! 并通过 `ATTACHMENTS` 表将附件节点 (NodeId) 绑定到 Blob。
+//!
+//! ## Invariants
+//! - `BLOB_META[hash]` 存在 ⇔ 全部分块已写入且拼接后 `sha256 == hash`
+//! - 相同内容只存储一次 (去重)，附件删除不回收 Blob (历史提交可能仍引用)
+//! - 分块大小固定为 `CHUNK_SIZE`，最后一块可以更短
+//! - `BLOB_PENDING[hash]` 记录传输中已收到的不同分块数，到齐时才拼接校验
+
+use crate::ledger::schema::{ATTACHMENTS, BLOB_CHUNKS, BLOB_META, BLOB_PENDING};
+use crate::models::NodeId;
+use crate::security::hashing::sha256_hex;
+use anyhow::{Result, anyhow};
+use redb::{Database, ReadableTable};
+use serde::{Deserialize, Serialize};
+
+/// 单个分块大小 (256 KiB)，同时也是 P2P 传输的单条消息载荷上限
+pub const CHUNK_SIZE: usize = 256 * 1024;
+
+/// 扫描/监听时接收的最大附件大小 (超出的文件忽略)
+pub const MAX_ATTACHMENT_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Blob 元数据
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobMeta {
+    pub size: u64,
+    pub chunk_count: u32,
+}
+
+/// 附件节点指向的 Blob
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttachmentRef {
+    /// 内容 SHA-256 (小写十六进制)
+    pub hash: String,
+    pub size: u64,
+}
+
+/// 分块数量 (空文件也占一块，便于传输协议统一处理)
+pub fn chunk_count(size: u64) -> u32 {
+    (size.div_ceil(CHUNK_SIZE as u64)).max(1) as u32
+}
+
+/// 写入 Blob，返回内容哈希
+///
+/// # 后置条件
+/// - `has_blob(hash)` 为 true；已存在时不重复写入
+pub fn put_blob(db: &Database, bytes: &[u8]) -> Result<String> {
+    let hash = sha256_hex(bytes);
+    if has_blob(db, &hash)? {
+        return Ok(hash);
+    }
+
+    let meta = BlobMeta {
+        size: bytes.len() as u64,
+        chunk_count: chunk_count(bytes.len() as u64),
+    };
+    let write_txn = db.begin_write()?;
+    {
+        let mut chunks = write_txn.open_table(BLOB_CHUNKS)?;
+        if bytes.is_empty() {
+            chunks.insert((hash.as_str(), 0), &[][..])?;
+        }
+        for (idx, chunk) in bytes.chunks(CHUNK_SIZE).enumerate() {
+            chunks.insert((hash.as_str(), idx as u32), chunk)?;
+        }
+        let mut metas = write_txn.open_table(BLOB_META)?;
+        metas.insert(hash.as_str(), bincode::serialize(&meta)?.as_slice())?;
+    }
+    write_txn.commit()?;
+    Ok(hash)
+}
+
+pub fn blob_meta(db: &Database, hash: &str) -> Result<Option<BlobMeta>> {
+    let read_txn = db.begin_read()?;
+    let table = match read_txn.open_table(BLOB_META) {
+        Ok(t) => t,
+        Err(redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    match table.get(hash)? {
+        Some(v) => Ok(Some(bincode::deserialize(v.value())?)),
+        None => Ok(None),
+    }
+}
+
+pub fn has_blob(db: &Database, hash: &str) -> Result<bool> {
+    Ok(blob_meta(db, hash)?.is_some())
+}
+
+/// 读取单个分块 (仅完整 Blob)
+pub fn get_chunk(db: &Database, hash: &str, index: u32) -> Result<Option<Vec<u8>>> {
+    if !has_blob(db, hash)? {
+        return Ok(None);
+    }
+    let read_txn = db.begin_read()?;
+    let table = read_txn.open_table(BLOB_CHUNKS)?;
+    Ok(table.get((hash, index))?.map(|v| v.value().to_vec()))
+}
+
+/// 读取完整 Blob
+pub fn get_blob(db: &Database, hash: &str) -> Result<Option<Vec<u8>>> {
+    let Some(meta) = blob_meta(db, hash)? else {
+        return Ok(None);
+    };
+    let read_txn = db.begin_read()?;
+    let table = read_txn.open_table(BLOB_CHUNKS)?;
+    let mut out = Vec::with_capacity(meta.size as usize);
+    for idx in 0..meta.chunk_count {
+        let chunk = table
+            .get((hash, idx))?
+            .ok_or_else(|| anyhow!("Blob {} is missing chunk {}", hash, idx))?;
+        out.extend_from_slice(chunk.value());
+    }
+    Ok(Some(out))
+}
+
+/// 写入远端传来的分块，全部到齐后校验并登记 Blob
+///
+/// # 前置条件
+/// - `index < total`
+///
+/// # 后置条件
+/// - 返回 true 表示 Blob 已完整 (含此前已存在的情况)
+/// - 校验失败时删除该哈希的全部分块并返回错误
+pub fn put_chunk(db: &Database, hash: &str, index: u32, total: u32, bytes: &[u8]) -> Result<bool> {
+    if index >= total {
+        return Err(anyhow!("Chunk index {} out of range ({})", index, total));
+    }
+    if bytes.len() > CHUNK_SIZE {
+        return Err(anyhow!("Chunk exceeds {} bytes", CHUNK_SIZE));
+    }
+    if has_blob(db, hash)? {
+        return Ok(true);
+    }
+
+    let write_txn = db.begin_write()?;
+    let complete = {
+        let mut chunks = write_txn.open_table(BLOB_CHUNKS)?;
+        let mut pending = write_txn.open_table(BLOB_PENDING)?;
+        let is_new = chunks.insert((hash, index), bytes)?.is_none();
+        let received = pending.get(hash)?.map(|v| v.value()).unwrap_or(0) + u32::from(is_new);
+
+        let complete = received >= total;
+        if complete {
+            pending.remove(hash)?;
+            let mut assembled = Vec::new();
+            for idx in 0..total {
+                let chunk = chunks
+                    .get((hash, idx))?
+                    .ok_or_else(|| anyhow!("Blob {} is missing chunk {}", hash, idx))?;
+                assembled.extend_from_slice(chunk.value());
+            }
+            if sha256_hex(&assembled) != hash {
+                for idx in 0..total {
+                    chunks.remove((hash, idx))?;
+                }
+                drop(chunks);
+                drop(pending);
+                write_txn.commit()?;
+                return Err(anyhow!("Blob {} failed hash verification", hash));
+            }
+            let meta = BlobMeta {
+                size: assembled.len() as u64,
+                chunk_count: total,
+            };
+            let mut metas = write_txn.open_table(BLOB_META)?;
+            metas.insert(hash, bincode::serialize(&meta)?.as_slice())?;
+        } else {
+            pending.insert(hash, received)?;
+        }
+        complete
+    };
+    write_txn.commit()?;
+    Ok(complete)
+}
+
+pub fn get_attachment(db: &Database, node_id: NodeId) -> Result<Option<AttachmentRef>> {
+    let read_txn = db.begin_read()?;
+    let table = match read_txn.open_table(ATTACHMENTS) {
+        Ok(t) => t,
+        Err(redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    match table.get(node_id.as_u128())? {
+        Some(v) => Ok(Some(bincode::deserialize(v.value())?)),
+        None => Ok(None),
+    }
+}
+
+pub fn set_attachment(db: &Database, node_id: NodeId, attachment: &AttachmentRef) -> Result<()> {
+    let write_txn = db.begin_write()?;
+    {
+        let mut table = write_txn.open_table(ATTACHMENTS)?;
+        table.insert(
+            node_id.as_u128(),
+            bincode::serialize(attachment)?.as_slice(),
+        )?;
+    }
+    write_txn.commit()?;
+    Ok(())
+}
+
+pub fn remove_attachment(db: &Database, node_id: NodeId) -> Result<()> {
+    let write_txn = db.begin_write()?;
+    {
+        let mut table = write_txn.open_table(ATTACHMENTS)?;
+        table.remove(node_id.as_u128())?;
+    }
+    write_txn.commit()?;
+    Ok(())
+}
+
+pub fn list_attachments(db: &Database) -> Result<Vec<(NodeId, AttachmentRef)>> {
+    let read_txn = db.begin_read()?;
+    let table = match read_txn.open_table(ATTACHMENTS) {
+        Ok(t) => t,
+        Err(redb::TableError::TableDoesNotExist(_)) => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut out = Vec::new();
+    for item in table.iter()? {
+        let (k, v) = item?;
+        out.push((
+            NodeId::from_u128(k.value()),
+            bincode::deserialize(v.value())?,
+        ));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_db() -> (tempfile::TempDir, Database) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::create(dir.path().join("blob.redb")).unwrap();
+        (dir, db)
+    }
+
+    #[test]
+    fn test_put_blob_chunks_and_dedupes() {
+        let (_dir, db) = temp_db();
+        let bytes: Vec<u8> = (0..CHUNK_SIZE * 2 + 10).map(|i| (i % 251) as u8).collect();
+
+        let hash = put_blob(&db, &bytes).unwrap();
+        assert_eq!(hash, sha256_hex(&bytes));
+        assert_eq!(
+            blob_meta(&db, &hash).unwrap(),
+            Some(BlobMeta {
+                size: bytes.len() as u64,
+                chunk_count: 3
+            })
+        );
+        assert_eq!(get_chunk(&db, &hash, 2).unwrap().unwrap().len(), 10);
+        assert_eq!(put_blob(&db, &bytes).unwrap(), hash);
+        assert_eq!(get_blob(&db, &hash).unwrap().unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_put_chunk_verifies_on_completion() {
+        let (_dir, src) = temp_db();
+        let (_dir2, dst) = temp_db();
+        let bytes = vec![7u8; CHUNK_SIZE + 1];
+        let hash = put_blob(&src, &bytes).unwrap();
+
+        // 乱序到达
+        let c1 = get_chunk(&src, &hash, 1).unwrap().unwrap();
+        let c0 = get_chunk(&src, &hash, 0).unwrap().unwrap();
+        assert!(!put_chunk(&dst, &hash, 1, 2, &c1).unwrap());
+        // 重复到达的分块不计数
+        assert!(!put_chunk(&dst, &hash, 1, 2, &c1).unwrap());
+        assert!(get_blob(&dst, &hash).unwrap().is_none());
+        assert!(put_chunk(&dst, &hash, 0, 2, &c0).unwrap());
+        assert_eq!(get_blob(&dst, &hash).unwrap().unwrap(), bytes);
+
+        // 篡改内容无法登记
+        let bogus = sha256_hex(b"other");
+        assert!(put_chunk(&dst, &bogus, 0, 1, b"tampered").is_err());
+        assert!(!has_blob(&dst, &bogus).unwrap());
+    }
+}
@@ -0,0 +1,318 @@
+// crates/core/src/ledger/doc_cache.rs
+//! # 文档状态缓存 (Document State Cache)
+//!
+//! 以 `DocId` 为键常驻本地库文档的 `DocState`，避免 OpenDoc、Diff、
+//! 变更列表与快照校验反复从头重放操作日志。
+//!
+//! ## 一致性
+//! - 追加本地操作后由 `RepoManager` 调用 `on_append` 增量推进
+//! - 读取时以 DOC_OPS 的 (最大序号, 操作数) 校验：落后则补齐增量，
+//!   不一致 (删除、乱序追加等) 则重建。缓存只影响性能，不影响结果
+//! - 重建以最新快照为起点，只重放其后的操作；CRDT 文档的字符标识无法从
+//!   快照文本恢复，仍从头重放
+//!
+//! ## CRDT 位置效果
+//! - CRDT 文档的条目保留最近 `RECENT_EFFECTS` 条操作对文本的位置效果，
+//!   供服务端变换 (`RepoManager::rebase_local_op`) 直接取用，无需从头重放日志
+//!
+//! ## 容量
+//! - 按文本与保留效果的字节数 (加固定开销) 计量，超出预算时淘汰最久未使用的文档
+//! - 单篇超过预算的文档不缓存
+//!
+//! ## Invariants
+//! - 条目的 `state` 等于依序应用 `count` 个序号不超过 `seq` 的操作后的结果
+//! - `recent` 依序记录序号在 (`floor`, `seq`] 内的全部操作的位置效果
+
+use crate::ledger::{ops, snapshot};
+use crate::models::{DocId, Op};
+use crate::state::DocState;
+use anyhow::Result;
+use redb::Database;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// 每个条目的估算固定开销 (Rope 节点、索引缓存、哈希表槽位)
+const ENTRY_OVERHEAD: usize = 1024;
+
+/// CRDT 文档保留位置效果的最近操作数
+const RECENT_EFFECTS: usize = 256;
+
+struct Entry {
+    state: DocState,
+    /// 已应用的最大操作序号
+    seq: u64,
+    /// 已应用的操作数
+    count: u64,
+    /// 最近 CRDT 文档操作的 (序号, 位置效果)，覆盖序号大于 `floor` 的全部操作
+    recent: VecDeque<(u64, Vec<Op>)>,
+    floor: u64,
+    last_used: u64,
+}
+
+impl Entry {
+    fn new(state: DocState, seq: u64, count: u64) -> Self {
+        Self {
+            state,
+            seq,
+            count,
+            recent: VecDeque::new(),
+            floor: seq,
+            last_used: 0,
+        }
+    }
+
+    fn weight(&self) -> usize {
+        let effects: usize = self
+            .recent
+            .iter()
+            .flat_map(|(_, ops)| ops)
+            .map(|op| match op {
+                Op::Insert { content, .. } => content.len() + 16,
+                _ => 16,
+            })
+            .sum();
+        self.state.len_bytes() + effects + ENTRY_OVERHEAD
+    }
+
+    /// 应用序号为 `seq` 的操作，CRDT 文档同时记录其位置效果
+    fn apply(&mut self, seq: u64, op: &Op) {
+        if self.state.is_crdt() || matches!(op, Op::Crdt(_)) {
+            let effects = self.state.apply_effects(op);
+            self.recent.push_back((seq, effects));
+            if self.recent.len() > RECENT_EFFECTS
+                && let Some((oldest, _)) = self.recent.pop_front()
+            {
+                self.floor = oldest;
+            }
+        } else {
+            self.state.apply(op);
+            self.floor = seq;
+        }
+        self.seq = seq;
+        self.count += 1;
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    budget: usize,
+    used: usize,
+    tick: u64,
+    entries: HashMap<DocId, Entry>,
+}
+
+impl Inner {
+    fn remove(&mut self, doc_id: DocId) -> Option<Entry> {
+        let entry = self.entries.remove(&doc_id)?;
+        self.used -= entry.weight();
+        Some(entry)
+    }
+
+    fn insert(&mut self, doc_id: DocId, mut entry: Entry) {
+        if entry.weight() > self.budget {
+            return;
+        }
+        self.tick += 1;
+        entry.last_used = self.tick;
+        self.used += entry.weight();
+        self.entries.insert(doc_id, entry);
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.used > self.budget {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(id, _)| *id)
+            else {
+                break;
+            };
+            self.remove(oldest);
+        }
+    }
+}
+
+/// 有界 LRU 文档状态缓存
+pub struct DocCache {
+    inner: Mutex<Inner>,
+}
+
+impl DocCache {
+    /// 创建缓存，`budget` 为字节预算 (0 表示禁用)
+    pub fn new(budget: usize) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                budget,
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 调整字节预算，超出部分立即淘汰
+    pub fn set_budget(&self, budget: usize) {
+        let mut inner = self.lock();
+        inner.budget = budget;
+        inner.evict();
+    }
+
+    /// 当前缓存的 (文档数, 字节数)
+    pub fn usage(&self) -> (usize, usize) {
+        let inner = self.lock();
+        (inner.entries.len(), inner.used)
+    }
+
+    /// 读取文档当前内容与最大序号 (无操作时为空串与 0)
+    ///
+    /// # 前置条件
+    /// - `db` 为该缓存所属的本地库
+    ///
+    /// # 后置条件
+    /// - 返回内容等价于对 `db` 中该文档的全部操作调用 `reconstruct_content`
+    pub fn content(&self, db: &Database, doc_id: DocId) -> Result<(String, u64)> {
+        self.with_state(db, doc_id, |state, seq| (state.content(), seq))
+    }
+
+    /// 以文档当前状态与最大序号调用 `f` (前置条件同 `content`)
+    pub fn with_state<R>(
+        &self,
+        db: &Database,
+        doc_id: DocId,
+        f: impl FnOnce(&DocState, u64) -> R,
+    ) -> Result<R> {
+        self.with_entry(db, doc_id, |entry| f(&entry.state, entry.seq))
+    }
+
+    /// 序号大于 `after` 的操作对文本的等价位置操作 (前置条件同 `content`)
+    ///
+    /// 结果与对全部操作调用 `state::text_ops_after` 相同；只保留 CRDT 文档最近
+    /// `RECENT_EFFECTS` 条操作的效果，`after` 更早或文档不是 CRDT 文档时返回 `None`。
+    pub fn text_ops_after(
+        &self,
+        db: &Database,
+        doc_id: DocId,
+        after: u64,
+    ) -> Result<Option<Vec<(u64, Op)>>> {
+        self.with_entry(db, doc_id, |entry| {
+            (after >= entry.floor).then(|| {
+                entry
+                    .recent
+                    .iter()
+                    .filter(|(seq, _)| *seq > after)
+                    .flat_map(|(seq, ops)| ops.iter().map(|op| (*seq, op.clone())))
+                    .collect()
+            })
+        })
+    }
+
+    fn with_entry<R>(
+        &self,
+        db: &Database,
+        doc_id: DocId,
+        f: impl FnOnce(&Entry) -> R,
+    ) -> Result<R> {
+        let (max_seq, count) = ops::doc_ops_stats(db, doc_id)?;
+        // 取出条目后释放锁，重放期间不阻塞其他文档
+        let cached = self.lock().remove(doc_id);
+        let entry = match cached {
+            Some(entry) if entry.seq == max_seq && entry.count == count => entry,
+            Some(mut entry) if entry.seq < max_seq => {
+                let delta = ops::get_ops_from_db_after(db, doc_id, entry.seq)?;
+                if entry.count + delta.len() as u64 == count {
+                    for (seq, e) in delta {
+                        entry.apply(seq, &e.op);
+                    }
+                    entry
+                } else {
+                    rebuild(db, doc_id, count)?
+                }
+            }
+            _ => rebuild(db, doc_id, count)?,
+        };
+
+        let result = f(&entry);
+        let seq = entry.seq;
+        let mut inner = self.lock();
+        // 并发读取可能已放回更新的状态
+        if inner.entries.get(&doc_id).is_none_or(|e| e.seq < seq) {
+            inner.remove(doc_id);
+            inner.insert(doc_id, entry);
+        }
+        Ok(result)
+    }
+
+    /// 新操作已写入账本后推进缓存状态
+    ///
+    /// 序号不大于已应用序号时 (乱序提交) 丢弃条目，由下次读取重建。
+    pub fn on_append(&self, doc_id: DocId, seq: u64, op: &Op) {
+        let mut inner = self.lock();
+        let Some(mut entry) = inner.remove(doc_id) else {
+            return;
+        };
+        if seq > entry.seq {
+            entry.apply(seq, op);
+            inner.insert(doc_id, entry);
+        }
+    }
+
+    /// 丢弃文档的缓存状态
+    pub fn invalidate(&self, doc_id: DocId) {
+        self.lock().remove(doc_id);
+    }
+
+    /// 丢弃全部缓存状态
+    pub fn clear(&self) {
+        let mut inner = self.lock();
+        inner.entries.clear();
+        inner.used = 0;
+    }
+}
+
+/// 重建文档状态，`count` 为该文档的操作总数
+fn rebuild(db: &Database, doc_id: DocId, count: u64) -> Result<Entry> {
+    let _timer = crate::metrics::RECONSTRUCT_SECONDS.start_timer();
+    if let Some(entry) = from_snapshot(db, doc_id, count)? {
+        return Ok(entry);
+    }
+    let mut entry = Entry::new(DocState::new(), 0, 0);
+    for (seq, e) in ops::get_ops_from_db(db, doc_id)? {
+        entry.apply(seq, &e.op);
+    }
+    Ok(entry)
+}
+
+/// 以最新快照为起点重放其后的操作
+///
+/// 快照序号处的操作已被删除 (快照过期) 或涉及 CRDT 操作时返回 `None`。
+/// 迁移后的本地写入均为 CRDT 操作 (见 `RepoManager::convert_for_append`)，
+/// 因此快照序号处为位置操作即说明快照时文档尚未迁移。
+fn from_snapshot(db: &Database, doc_id: DocId, count: u64) -> Result<Option<Entry>> {
+    let Some((snap_seq, content)) = snapshot::load_latest_snapshot(db, doc_id)? else {
+        return Ok(None);
+    };
+    // 含快照序号处的操作，用于校验快照仍对应账本
+    let delta = ops::get_ops_from_db_after(db, doc_id, snap_seq.saturating_sub(1))?;
+    let stale = delta.first().is_none_or(|(seq, _)| *seq != snap_seq);
+    if stale || delta.len() as u64 > count || delta.iter().any(|(_, e)| matches!(e.op, Op::Crdt(_)))
+    {
+        return Ok(None);
+    }
+
+    let mut state = DocState::new();
+    if !content.is_empty() {
+        state.apply(&Op::Insert {
+            pos: 0,
+            content: content.into(),
+        });
+    }
+    let mut entry = Entry::new(state, snap_seq, count - delta.len() as u64 + 1);
+    for (seq, e) in delta.into_iter().skip(1) {
+        entry.apply(seq, &e.op);
+    }
+    Ok(Some(entry))
+}
@@ -0,0 +1,118 @@
+// crates/core/src/ledger/doc_meta/indexer.rs
+//! # Metadata Indexer (后台属性/链接提取线程)
+//!
+//! 与全文索引共用事件来源: 本地编辑/Watcher/重命名/删除发送 `Touch`，
+//! 目录级变更发送 `Reconcile`；启动时自动排队一次 `Rebuild`。
+//!
+//! ## Invariants
+//! - `Touch` 以 Ledger 当前状态为准: 文档存在则重新提取，不存在则删除
+//! - 每个批次结束时反链均与最新路径集合一致: 路径集合变化 (新建/重命名/删除) 时整体重建，
+//!   否则只更新出链变化的文档的反链行
+//! - 同一批次中 `Reconcile`/`Rebuild` 已覆盖全部文档，`Touch` 不再重复处理
+
+use crate::ledger::RepoManager;
+use crate::ledger::manager::PathSet;
+use crate::models::DocId;
+use crate::utils::debounce::{DEFAULT_DEBOUNCE, MAX_BATCH_DELAY, spawn_batcher};
+use anyhow::Result;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+/// 属性索引事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetaEvent {
+    /// 文档内容或路径可能变化 (含删除)
+    Touch(DocId),
+    /// 路径集合变化: 补齐缺失项、删除多余项并重建反链
+    Reconcile,
+    /// 重新提取全部文档
+    Rebuild,
+}
+
+/// 后台属性索引器句柄 (可克隆，丢弃全部句柄后线程退出)
+#[derive(Clone)]
+pub struct MetadataIndexer {
+    tx: Sender<MetaEvent>,
+}
+
+impl MetadataIndexer {
+    /// 启动后台线程，并立即排队一次全量提取
+    pub fn spawn(repo: Arc<RepoManager>) -> Self {
+        Self::spawn_with_debounce(repo, DEFAULT_DEBOUNCE)
+    }
+
+    pub fn spawn_with_debounce(repo: Arc<RepoManager>, debounce: Duration) -> Self {
+        // 路径集合缓存 (仅索引线程使用)，首次批次或路径变化后重新加载
+        let mut paths: Option<PathSet> = None;
+        let tx = spawn_batcher(
+            "metadata-indexer",
+            debounce,
+            MAX_BATCH_DELAY,
+            move |events: Vec<MetaEvent>| match flush(&repo, &mut paths, events) {
+                Ok(n) if n > 0 => tracing::debug!("Metadata index updated {} docs", n),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Metadata indexing failed: {:?}", e),
+            },
+        );
+        let _ = tx.send(MetaEvent::Rebuild);
+        Self { tx }
+    }
+
+    pub fn touch(&self, doc_id: DocId) {
+        let _ = self.tx.send(MetaEvent::Touch(doc_id));
+    }
+
+    pub fn reconcile(&self) {
+        let _ = self.tx.send(MetaEvent::Reconcile);
+    }
+}
+
+/// 处理一个防抖窗口内的事件，返回变化的文档数
+fn flush(repo: &RepoManager, paths: &mut Option<PathSet>, events: Vec<MetaEvent>) -> Result<usize> {
+    if events.contains(&MetaEvent::Rebuild) {
+        let changed = repo.reconcile_doc_metadata(true)?;
+        *paths = Some(repo.path_set()?);
+        return Ok(changed);
+    }
+    let touched: HashSet<DocId> = events
+        .iter()
+        .filter_map(|e| match e {
+            MetaEvent::Touch(id) => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    // 被触及文档的路径与缓存不一致即为新建/重命名/删除
+    let mut paths_changed = events.contains(&MetaEvent::Reconcile);
+    if !paths_changed {
+        paths_changed = match paths.as_ref() {
+            Some(cached) => touched.iter().try_fold(false, |acc, id| {
+                Ok::<_, anyhow::Error>(
+                    acc || repo.get_path_by_docid(*id)?.as_deref() != cached.path(*id),
+                )
+            })?,
+            None => true,
+        };
+    }
+
+    let mut changed = 0;
+    if let (false, Some(cached)) = (paths_changed, paths.as_ref()) {
+        for doc_id in touched {
+            changed += usize::from(repo.refresh_doc_links(doc_id, cached)?);
+        }
+        return Ok(changed);
+    }
+
+    for doc_id in touched {
+        changed += usize::from(repo.refresh_doc_metadata(doc_id)?);
+    }
+    if events.contains(&MetaEvent::Reconcile) {
+        changed += repo.reconcile_doc_metadata(false)?;
+    } else {
+        repo.rebuild_backlinks()?;
+    }
+    *paths = Some(repo.path_set()?);
+    Ok(changed)
+}
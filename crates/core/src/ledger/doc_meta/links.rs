@@ -0,0 +1,138 @@
+// crates/core/src/ledger/doc_meta/links.rs
+//! # 链接图存储 (Link Graph)
+//!
+//! - `DOC_LINKS`: 每个文档的原始出链 (`markdown::links::Link`)
+//! - `BACKLINKS`: 解析后的 目标 -> 来源 边
+//!
+//! 出链目标能否解析取决于当前的路径集合，因此新建/重命名/删除后
+//! 由 `rebuild_backlinks` 基于 `DOC_LINKS` 整体重建反链，无需重新读取正文；
+//! 路径集合不变时 (普通编辑) 只以 `update_source_backlinks` 更新出链变化的来源文档。
+
+use crate::ledger::schema::{BACKLINKS, DOC_LINKS};
+use crate::markdown::links::Link;
+use crate::models::DocId;
+use anyhow::Result;
+use redb::{Database, ReadableMultimapTable, ReadableTable};
+use std::collections::HashSet;
+
+pub fn get_doc_links(db: &Database, doc_id: DocId) -> Result<Option<Vec<Link>>> {
+    let read_txn = db.begin_read()?;
+    let table = read_txn.open_table(DOC_LINKS)?;
+    match table.get(doc_id.as_u128())? {
+        Some(v) => Ok(Some(bincode::deserialize(v.value())?)),
+        None => Ok(None),
+    }
+}
+
+/// 写入文档出链，返回是否发生变化
+pub fn set_doc_links(db: &Database, doc_id: DocId, links: &[Link]) -> Result<bool> {
+    if get_doc_links(db, doc_id)?.as_deref() == Some(links) {
+        return Ok(false);
+    }
+    let write_txn = db.begin_write()?;
+    write_txn
+        .open_table(DOC_LINKS)?
+        .insert(doc_id.as_u128(), bincode::serialize(links)?.as_slice())?;
+    write_txn.commit()?;
+    Ok(true)
+}
+
+/// 删除文档出链，返回是否存在
+pub fn remove_doc_links(db: &Database, doc_id: DocId) -> Result<bool> {
+    let write_txn = db.begin_write()?;
+    let existed = write_txn
+        .open_table(DOC_LINKS)?
+        .remove(doc_id.as_u128())?
+        .is_some();
+    write_txn.commit()?;
+    Ok(existed)
+}
+
+/// 全部文档的出链
+pub fn all_doc_links(db: &Database) -> Result<Vec<(DocId, Vec<Link>)>> {
+    let read_txn = db.begin_read()?;
+    let table = read_txn.open_table(DOC_LINKS)?;
+    let mut out = Vec::new();
+    for item in table.iter()? {
+        let (k, v) = item?;
+        out.push((
+            DocId::from_u128(k.value()),
+            bincode::deserialize(v.value())?,
+        ));
+    }
+    Ok(out)
+}
+
+/// 以 `(target, source)` 边集合整体替换反链表
+pub fn replace_backlinks(db: &Database, edges: &HashSet<(DocId, DocId)>) -> Result<()> {
+    let write_txn = db.begin_write()?;
+    write_txn.delete_multimap_table(BACKLINKS)?;
+    {
+        let mut table = write_txn.open_multimap_table(BACKLINKS)?;
+        for (target, source) in edges {
+            table.insert(target.as_u128(), source.as_u128())?;
+        }
+    }
+    write_txn.commit()?;
+    Ok(())
+}
+
+/// 更新单个来源文档的反链行: 删除 `old - new` 的目标，加入 `new - old` 的目标
+pub fn update_source_backlinks(
+    db: &Database,
+    source: DocId,
+    old_targets: &HashSet<DocId>,
+    new_targets: &HashSet<DocId>,
+) -> Result<()> {
+    let write_txn = db.begin_write()?;
+    {
+        let mut table = write_txn.open_multimap_table(BACKLINKS)?;
+        for target in old_targets.difference(new_targets) {
+            table.remove(target.as_u128(), source.as_u128())?;
+        }
+        for target in new_targets.difference(old_targets) {
+            table.insert(target.as_u128(), source.as_u128())?;
+        }
+    }
+    write_txn.commit()?;
+    Ok(())
+}
+
+/// 链接到 `target` 的来源文档
+pub fn backlink_sources(db: &Database, target: DocId) -> Result<Vec<DocId>> {
+    let read_txn = db.begin_read()?;
+    let table = read_txn.open_multimap_table(BACKLINKS)?;
+    let mut out = Vec::new();
+    for v in table.get(target.as_u128())? {
+        out.push(DocId::from_u128(v?.value()));
+    }
+    Ok(out)
+}
+
+/// 全部已解析链接边 `(source, target)`
+pub fn all_link_edges(db: &Database) -> Result<Vec<(DocId, DocId)>> {
+    let read_txn = db.begin_read()?;
+    let table = read_txn.open_multimap_table(BACKLINKS)?;
+    let mut out = Vec::new();
+    for item in table.iter()? {
+        let (target, sources) = item?;
+        let target = DocId::from_u128(target.value());
+        for source in sources {
+            out.push((DocId::from_u128(source?.value()), target));
+        }
+    }
+    Ok(out)
+}
+
+/// 一个文档的入链改写计划
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkRewrite {
+    pub doc_id: DocId,
+    pub path: String,
+    /// 改写前正文
+    pub content: String,
+    /// 改写后正文
+    pub new_content: String,
+    /// 改写的链接数
+    pub links: usize,
+}
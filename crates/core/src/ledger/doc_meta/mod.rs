@@ -0,0 +1,150 @@
+// crates/core/src/ledger/doc_meta/mod.rs
+//! # 文档属性索引 (Doc Metadata Index)
+//!
+//! 存储每个文档的 `DocMetadata` (frontmatter 属性 + 标签)，并维护两张倒排表，
+//! 供 `QueryDocs` 快速筛选候选文档。
+//!
+//! - `DOC_META`: DocId -> DocMetadata
+//! - `TAG_DOCS`: tag -> DocIds
+//! - `PROP_DOCS`: (key, 小写 value) -> DocIds
+//! - `links`: 出链与反链 (`DOC_LINKS` / `BACKLINKS`)
+//!
+//! ## Invariants
+//! - 倒排表与 `DOC_META` 在同一写事务中更新，二者始终一致
+//! - 派生数据: 可随时由操作日志重新提取 (见 `RepoManager::reconcile_doc_metadata`)
+
+pub mod indexer;
+pub mod links;
+
+use crate::ledger::schema::{DOC_META, PROP_DOCS, TAG_DOCS};
+use crate::markdown::DocMetadata;
+use crate::models::DocId;
+use crate::protocol::query::fold_value;
+use anyhow::Result;
+use redb::{Database, ReadableMultimapTable, ReadableTable, WriteTransaction};
+use std::collections::HashSet;
+
+pub fn get_doc_meta(db: &Database, doc_id: DocId) -> Result<Option<DocMetadata>> {
+    let read_txn = db.begin_read()?;
+    let table = match read_txn.open_table(DOC_META) {
+        Ok(t) => t,
+        Err(redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    match table.get(doc_id.as_u128())? {
+        Some(v) => Ok(Some(bincode::deserialize(v.value())?)),
+        None => Ok(None),
+    }
+}
+
+/// 写入文档元数据并更新倒排表
+///
+/// # 后置条件
+/// - 返回 true 表示元数据发生变化；未变化时不写入
+pub fn set_doc_meta(db: &Database, doc_id: DocId, meta: &DocMetadata) -> Result<bool> {
+    let old = get_doc_meta(db, doc_id)?;
+    if old.as_ref() == Some(meta) {
+        return Ok(false);
+    }
+    let write_txn = db.begin_write()?;
+    if let Some(old) = &old {
+        unindex(&write_txn, doc_id, old)?;
+    }
+    {
+        let id = doc_id.as_u128();
+        let mut tags = write_txn.open_multimap_table(TAG_DOCS)?;
+        for tag in &meta.tags {
+            tags.insert(tag.as_str(), id)?;
+        }
+        let mut props = write_txn.open_multimap_table(PROP_DOCS)?;
+        for (key, values) in &meta.properties {
+            for value in values {
+                props.insert((key.as_str(), fold_value(value).as_str()), id)?;
+            }
+        }
+        let mut table = write_txn.open_table(DOC_META)?;
+        table.insert(id, bincode::serialize(meta)?.as_slice())?;
+    }
+    write_txn.commit()?;
+    Ok(true)
+}
+
+/// 删除文档元数据及其倒排项，返回是否存在
+pub fn remove_doc_meta(db: &Database, doc_id: DocId) -> Result<bool> {
+    let Some(old) = get_doc_meta(db, doc_id)? else {
+        return Ok(false);
+    };
+    let write_txn = db.begin_write()?;
+    unindex(&write_txn, doc_id, &old)?;
+    write_txn.open_table(DOC_META)?.remove(doc_id.as_u128())?;
+    write_txn.commit()?;
+    Ok(true)
+}
+
+fn unindex(write_txn: &WriteTransaction, doc_id: DocId, meta: &DocMetadata) -> Result<()> {
+    let id = doc_id.as_u128();
+    let mut tags = write_txn.open_multimap_table(TAG_DOCS)?;
+    for tag in &meta.tags {
+        tags.remove(tag.as_str(), id)?;
+    }
+    let mut props = write_txn.open_multimap_table(PROP_DOCS)?;
+    for (key, values) in &meta.properties {
+        for value in values {
+            props.remove((key.as_str(), fold_value(value).as_str()), id)?;
+        }
+    }
+    Ok(())
+}
+
+/// 已提取元数据的全部文档
+pub fn indexed_docs(db: &Database) -> Result<HashSet<DocId>> {
+    let read_txn = db.begin_read()?;
+    let table = match read_txn.open_table(DOC_META) {
+        Ok(t) => t,
+        Err(redb::TableError::TableDoesNotExist(_)) => return Ok(HashSet::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut docs = HashSet::new();
+    for item in table.iter()? {
+        let (k, _) = item?;
+        docs.insert(DocId::from_u128(k.value()));
+    }
+    Ok(docs)
+}
+
+/// 全部 `(tag, DocId)` 标签归属
+pub fn all_tag_docs(db: &Database) -> Result<Vec<(String, DocId)>> {
+    let read_txn = db.begin_read()?;
+    let table = read_txn.open_multimap_table(TAG_DOCS)?;
+    let mut out = Vec::new();
+    for item in table.iter()? {
+        let (tag, docs) = item?;
+        let tag = tag.value().to_string();
+        for doc in docs {
+            out.push((tag.clone(), DocId::from_u128(doc?.value())));
+        }
+    }
+    Ok(out)
+}
+
+/// 带有指定标签的文档
+pub fn docs_with_tag(db: &Database, tag: &str) -> Result<HashSet<DocId>> {
+    let read_txn = db.begin_read()?;
+    let table = read_txn.open_multimap_table(TAG_DOCS)?;
+    let mut docs = HashSet::new();
+    for v in table.get(tag)? {
+        docs.insert(DocId::from_u128(v?.value()));
+    }
+    Ok(docs)
+}
+
+/// 属性值等于 `value` (不区分大小写) 的文档
+pub fn docs_with_property(db: &Database, key: &str, value: &str) -> Result<HashSet<DocId>> {
+    let read_txn = db.begin_read()?;
+    let table = read_txn.open_multimap_table(PROP_DOCS)?;
+    let mut docs = HashSet::new();
+    for v in table.get((key, fold_value(value).as_str()))? {
+        docs.insert(DocId::from_u128(v?.value()));
+    }
+    Ok(docs)
+}
@@ -22,6 +22,7 @@
 //!         └── repo_name_3.redb
 //! ```
 
+use crate::config::AppProfile;
 use anyhow::{Context, Result};
 use redb::Database;
 use std::collections::HashMap;
@@ -29,6 +30,7 @@ use std::path::Path;
 use std::sync::RwLock;
 
 use super::RepoManager;
+use super::doc_cache::DocCache;
 use super::node_check;
 use super::node_meta;
 use super::schema::*;
@@ -169,6 +171,7 @@ pub fn init(
         }
         write_txn.commit()?;
     }
+    let (doc_model, site_id) = super::manager::load_crdt_settings(&local_db)?;
 
     Ok(RepoManager {
         ledger_dir,
@@ -177,6 +180,11 @@ pub fn init(
         extra_local_dbs: RwLock::new(HashMap::new()),
         shadow_dbs: RwLock::new(HashMap::new()),
         snapshot_depth,
+        doc_cache: DocCache::new(AppProfile::Standard.doc_cache_bytes()),
+        doc_model: RwLock::new(doc_model),
+        site_id,
+        append_lock: Default::default(),
+        edit_lock: Default::default(),
     })
 }
 
@@ -193,6 +201,9 @@ pub fn init(
 /// - `DOC_OPS`: 文档操作索引
 /// - `SNAPSHOT_INDEX`: 快照索引
 /// - `SNAPSHOT_DATA`: 快照数据
+/// - `BLOB_META` / `BLOB_CHUNKS` / `BLOB_PENDING` / `ATTACHMENTS`: 附件 Blob Store
+/// - `DOC_META` / `TAG_DOCS` / `PROP_DOCS`: 文档属性与标签索引
+/// - `DOC_LINKS` / `BACKLINKS`: 文档链接图
 fn init_core_tables(db: &Database) -> Result<()> {
     let write_txn = db.begin_write()?;
     {
@@ -206,6 +217,15 @@ fn init_core_tables(db: &Database) -> Result<()> {
         let _ = write_txn.open_multimap_table(DOC_OPS)?;
         let _ = write_txn.open_multimap_table(SNAPSHOT_INDEX)?;
         let _ = write_txn.open_table(SNAPSHOT_DATA)?;
+        let _ = write_txn.open_table(BLOB_META)?;
+        let _ = write_txn.open_table(BLOB_CHUNKS)?;
+        let _ = write_txn.open_table(BLOB_PENDING)?;
+        let _ = write_txn.open_table(ATTACHMENTS)?;
+        let _ = write_txn.open_table(DOC_META)?;
+        let _ = write_txn.open_multimap_table(TAG_DOCS)?;
+        let _ = write_txn.open_multimap_table(PROP_DOCS)?;
+        let _ = write_txn.open_table(DOC_LINKS)?;
+        let _ = write_txn.open_multimap_table(BACKLINKS)?;
     }
     write_txn.commit()?;
     Ok(())
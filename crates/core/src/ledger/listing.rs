@@ -3,7 +3,7 @@
 //!
 //! 提供 `RepoListing` trait，扩展 `RepoManager` 的列表查询能力。
 
-use crate::ledger::{RepoManager, metadata, node_meta};
+use crate::ledger::{RepoManager, metadata, node_check, node_meta};
 use crate::models::{DocId, NodeId, NodeMeta, PeerId, RepoType};
 use anyhow::Result;
 
@@ -53,6 +53,8 @@ impl RepoListing for RepoManager {
                     anyhow::anyhow!("未找到指定 Repo 的影子库: {}/{}", peer_id, repo_id)
                 })?;
                 node_meta::migrate_nodes_from_docs(db)?;
+                // 附件清单可能先于文档操作到达，Node 表非空时仍需补齐文档节点
+                node_check::repair_missing_nodes(db)?;
                 node_meta::list_nodes(db)
             }
         }
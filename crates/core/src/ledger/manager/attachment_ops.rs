@@ -0,0 +1,154 @@
+// crates/core/src/ledger/manager/attachment_ops.rs
+//! # 附件操作
+//!
+//! 实现 `RepoManager` 的附件 (非 Markdown 文件) 存取方法。
+//! 附件以 `NodeKind::Attachment` 节点出现在 Node 表中，内容存于 Blob Store。
+
+use crate::ledger::RepoManager;
+use crate::ledger::blob::{self, AttachmentRef, BlobMeta};
+use crate::ledger::node_meta;
+use crate::models::{NodeId, NodeKind, PeerId, RepoId};
+use crate::utils::path::to_forward_slash;
+use anyhow::Result;
+use redb::Database;
+
+impl RepoManager {
+    /// 存储附件内容并绑定到路径
+    ///
+    /// # 后置条件
+    /// - 路径对应一个 Attachment 节点，指向内容哈希
+    /// - 返回 `(节点, 是否发生变化)`；内容未变时不写入
+    pub fn store_attachment(&self, path: &str, bytes: &[u8]) -> Result<(NodeId, bool)> {
+        let node_id = node_meta::ensure_attachment_node(&self.local_db, path)?;
+        let hash = blob::put_blob(&self.local_db, bytes)?;
+        let attachment = AttachmentRef {
+            hash,
+            size: bytes.len() as u64,
+        };
+        if blob::get_attachment(&self.local_db, node_id)?.as_ref() == Some(&attachment) {
+            return Ok((node_id, false));
+        }
+        blob::set_attachment(&self.local_db, node_id, &attachment)?;
+        Ok((node_id, true))
+    }
+
+    /// 获取路径对应的附件引用
+    pub fn get_attachment(&self, path: &str) -> Result<Option<AttachmentRef>> {
+        attachment_at(&self.local_db, path)
+    }
+
+    /// 读取路径对应的附件内容
+    pub fn read_attachment(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        match self.get_attachment(path)? {
+            Some(a) => blob::get_blob(&self.local_db, &a.hash),
+            None => Ok(None),
+        }
+    }
+
+    /// 删除附件节点 (Blob 保留)，返回被删除的节点
+    pub fn remove_attachment(&self, path: &str) -> Result<Option<NodeId>> {
+        let normalized = to_forward_slash(path);
+        let Some(node_id) = node_meta::get_node_id(&self.local_db, &normalized)? else {
+            return Ok(None);
+        };
+        if blob::get_attachment(&self.local_db, node_id)?.is_none() {
+            return Ok(None);
+        }
+        blob::remove_attachment(&self.local_db, node_id)?;
+        node_meta::remove_node_by_path(&self.local_db, &normalized)?;
+        Ok(Some(node_id))
+    }
+
+    /// 重命名附件节点 (NodeId 不变，引用随节点迁移)
+    pub fn rename_attachment(&self, old_path: &str, new_path: &str) -> Result<()> {
+        let old = to_forward_slash(old_path);
+        if self.get_attachment(&old)?.is_none() {
+            return Err(anyhow::anyhow!("Attachment not found: {}", old));
+        }
+        node_meta::rename_path_prefix(&self.local_db, &old, &to_forward_slash(new_path))
+    }
+
+    /// 列出本地全部附件 `(路径, 引用)`，按路径排序
+    pub fn list_attachments(&self) -> Result<Vec<(String, AttachmentRef)>> {
+        list_attachments_in(&self.local_db)
+    }
+
+    /// 本地是否已有完整 Blob
+    pub fn has_blob(&self, hash: &str) -> Result<bool> {
+        blob::has_blob(&self.local_db, hash)
+    }
+
+    pub fn blob_meta(&self, hash: &str) -> Result<Option<BlobMeta>> {
+        blob::blob_meta(&self.local_db, hash)
+    }
+
+    /// 按哈希读取完整 Blob
+    pub fn get_blob(&self, hash: &str) -> Result<Option<Vec<u8>>> {
+        blob::get_blob(&self.local_db, hash)
+    }
+
+    pub fn get_blob_chunk(&self, hash: &str, index: u32) -> Result<Option<Vec<u8>>> {
+        blob::get_chunk(&self.local_db, hash, index)
+    }
+
+    /// 写入远端分块，返回 Blob 是否已完整
+    pub fn put_blob_chunk(&self, hash: &str, index: u32, total: u32, bytes: &[u8]) -> Result<bool> {
+        blob::put_chunk(&self.local_db, hash, index, total, bytes)
+    }
+
+    /// 用远端附件清单覆盖影子库中的附件节点
+    ///
+    /// Blob 统一存于本地库 (内容寻址，与来源无关)，影子库只记录路径到哈希的映射。
+    pub fn apply_remote_attachments(
+        &self,
+        peer_id: &PeerId,
+        repo_id: &RepoId,
+        entries: &[(String, AttachmentRef)],
+    ) -> Result<()> {
+        self.ensure_shadow_db(peer_id, repo_id)?;
+        let dbs = self.shadow_dbs.read().unwrap();
+        let db = dbs
+            .get(peer_id)
+            .and_then(|repos| repos.get(repo_id))
+            .ok_or_else(|| anyhow::anyhow!("未找到指定 Repo 的影子库: {}/{}", peer_id, repo_id))?;
+
+        node_meta::migrate_nodes_from_docs(db)?;
+
+        let incoming: std::collections::HashSet<&str> =
+            entries.iter().map(|(p, _)| p.as_str()).collect();
+        for (path, _) in list_attachments_in(db)? {
+            if !incoming.contains(path.as_str())
+                && let Some(node_id) = node_meta::get_node_id(db, &path)?
+            {
+                blob::remove_attachment(db, node_id)?;
+                node_meta::remove_node_by_path(db, &path)?;
+            }
+        }
+        for (path, attachment) in entries {
+            let node_id = node_meta::ensure_attachment_node(db, path)?;
+            blob::set_attachment(db, node_id, attachment)?;
+        }
+        Ok(())
+    }
+}
+
+fn attachment_at(db: &Database, path: &str) -> Result<Option<AttachmentRef>> {
+    match node_meta::get_node_id(db, &to_forward_slash(path))? {
+        Some(node_id) => blob::get_attachment(db, node_id),
+        None => Ok(None),
+    }
+}
+
+/// 以 Node 表为准列出附件 (跳过已删除节点残留的引用)
+fn list_attachments_in(db: &Database) -> Result<Vec<(String, AttachmentRef)>> {
+    let mut out = Vec::new();
+    for (node_id, attachment) in blob::list_attachments(db)? {
+        if let Some(meta) = node_meta::get_node_meta(db, node_id)?
+            && meta.kind == NodeKind::Attachment
+        {
+            out.push((meta.path, attachment));
+        }
+    }
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(out)
+}
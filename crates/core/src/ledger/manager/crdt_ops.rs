@@ -0,0 +1,305 @@
+// crates/core/src/ledger/manager/crdt_ops.rs
+//! # CRDT 文档模型
+//!
+//! 实现 `RepoManager` 的文档模型设置、迁移与 CRDT 合并方法。
+//!
+//! ## 基线
+//! 文档首个 CRDT 操作之前由位置操作得到的文本称为基线，迁移插入 (`DocState::migration_op`)
+//! 以新的字符标识承载它。两端基线一致 (都为空，或一端已包含另一端的首个 CRDT 插入) 时，
+//! 合并即取操作并集；否则回退到三方合并，成功后由 `adopt_crdt_ops` 统一到一方的基线。
+//!
+//! ## 站点标识
+//! 每个本地库首次打开时随机生成 (`REPO_METADATA` 键 2)。复制账本目录到另一台设备
+//! 会共享同一站点，应改为通过同步获取数据。
+
+use crate::config::DocModel;
+use crate::ledger::ops;
+use crate::ledger::schema::REPO_METADATA;
+use crate::ledger::{MergedOp, RepoManager};
+use crate::models::{DocId, LedgerEntry, Op, PeerId, RepoId, RepoType};
+use crate::state::crdt::{CrdtOp, OpId};
+use crate::state::{compute_diff, reconstruct_content};
+use anyhow::Result;
+use redb::{Database, ReadableTable};
+
+const KEY_DOC_MODEL: u8 = 1;
+const KEY_SITE_ID: u8 = 2;
+
+/// 读取文档模型与站点标识 (站点标识缺失时生成并写入)
+pub(crate) fn load_crdt_settings(db: &Database) -> Result<(DocModel, u64)> {
+    let write_txn = db.begin_write()?;
+    let settings = {
+        let mut table = write_txn.open_table(REPO_METADATA)?;
+        let model = match table.get(&KEY_DOC_MODEL)? {
+            Some(guard) => bincode::deserialize(guard.value())?,
+            None => DocModel::default(),
+        };
+        let stored = table
+            .get(&KEY_SITE_ID)?
+            .and_then(|guard| guard.value().try_into().ok())
+            .map(u64::from_le_bytes);
+        let site = match stored {
+            Some(site) => site,
+            None => {
+                // 站点 0 保留给匿名转换 (见 `DocState`)
+                let site = uuid::Uuid::new_v4().as_u64_pair().0.max(1);
+                table.insert(&KEY_SITE_ID, site.to_le_bytes().as_slice())?;
+                site
+            }
+        };
+        (model, site)
+    };
+    write_txn.commit()?;
+    Ok(settings)
+}
+
+/// 首个 CRDT 操作的下标与其插入标识
+fn first_crdt(entries: &[(u64, LedgerEntry)]) -> Option<(usize, Option<OpId>)> {
+    entries
+        .iter()
+        .enumerate()
+        .find_map(|(i, (_, e))| match &e.op {
+            Op::Crdt(CrdtOp::Insert { id, .. }) => Some((i, Some(*id))),
+            Op::Crdt(_) => Some((i, None)),
+            _ => None,
+        })
+}
+
+/// 日志中是否有以 `id` 开始的插入
+fn knows(entries: &[(u64, LedgerEntry)], id: OpId) -> bool {
+    entries
+        .iter()
+        .any(|(_, e)| matches!(&e.op, Op::Crdt(CrdtOp::Insert { id: i, .. }) if *i == id))
+}
+
+fn prefix_is_empty(entries: &[(u64, LedgerEntry)], len: usize) -> bool {
+    let prefix: Vec<LedgerEntry> = entries[..len].iter().map(|(_, e)| e.clone()).collect();
+    reconstruct_content(&prefix).is_empty()
+}
+
+/// 两端都是 CRDT 文档且基线一致
+fn same_baseline(local: &[(u64, LedgerEntry)], remote: &[(u64, LedgerEntry)]) -> bool {
+    let (Some((l, local_first)), Some((r, remote_first))) = (first_crdt(local), first_crdt(remote))
+    else {
+        return false;
+    };
+    if prefix_is_empty(local, l) && prefix_is_empty(remote, r) {
+        return true;
+    }
+    match (local_first, remote_first) {
+        (Some(a), Some(b)) => a == b || knows(remote, a) || knows(local, b),
+        _ => false,
+    }
+}
+
+impl RepoManager {
+    /// 当前文档模型
+    pub fn doc_model(&self) -> DocModel {
+        *self.doc_model.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 本地库的 CRDT 站点标识
+    pub fn site_id(&self) -> u64 {
+        self.site_id
+    }
+
+    /// 设置并持久化文档模型
+    ///
+    /// 切换为 `Crdt` 后，已有文档在下一次本地写入时迁移；需要立即迁移时调用 `migrate_to_crdt`。
+    /// 切换回 `Text` 不会改写已迁移的文档，其后续编辑仍写入 CRDT 操作。
+    pub fn set_doc_model(&self, model: DocModel) -> Result<()> {
+        let write_txn = self.local_db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(REPO_METADATA)?;
+            table.insert(&KEY_DOC_MODEL, bincode::serialize(&model)?.as_slice())?;
+        }
+        write_txn.commit()?;
+        *self.doc_model.write().unwrap_or_else(|e| e.into_inner()) = model;
+        Ok(())
+    }
+
+    /// 为所有尚未迁移的非空文档写入迁移插入，返回迁移的文档数
+    ///
+    /// 应在一台设备上迁移后再同步到其他设备；各自迁移的副本基线不同，
+    /// 首次合并会回退到三方合并。
+    pub fn migrate_to_crdt(&self, peer_id: &PeerId) -> Result<usize> {
+        let mut migrated = 0;
+        for (doc_id, _) in self.list_local_docs(None)? {
+            let _guard = self.append_lock.lock().unwrap_or_else(|e| e.into_inner());
+            let genesis = self
+                .doc_cache
+                .with_state(&self.local_db, doc_id, |state, _| {
+                    state.migration_op(self.site_id)
+                })?;
+            let Some(genesis) = genesis else {
+                continue;
+            };
+            self.append_converted(doc_id, peer_id, vec![Op::Crdt(genesis)], |seq, op| {
+                LedgerEntry {
+                    doc_id,
+                    op,
+                    timestamp: chrono::Utc::now().timestamp_millis(),
+                    peer_id: peer_id.clone(),
+                    seq,
+                }
+            })?;
+            migrated += 1;
+        }
+        Ok(migrated)
+    }
+
+    /// 按文档模型转换待追加的操作
+    ///
+    /// 返回 `None` 表示原样追加；否则为转换后的操作 (可能为空，表示无实际效果)。
+    ///
+    /// # 前置条件
+    /// - 调用方持有 `append_lock`，直到转换结果写入账本
+    pub(crate) fn convert_for_append(&self, doc_id: DocId, op: &Op) -> Result<Option<Vec<Op>>> {
+        if matches!(op, Op::Crdt(_)) {
+            return Ok(None);
+        }
+        let model = self.doc_model();
+        self.doc_cache
+            .with_state(&self.local_db, doc_id, |state, _| {
+                (model == DocModel::Crdt || state.is_crdt()).then(|| {
+                    state
+                        .to_crdt_ops(op, self.site_id)
+                        .into_iter()
+                        .map(Op::Crdt)
+                        .collect()
+                })
+            })
+    }
+
+    /// 以生成的本地序号依次追加操作，返回最后一条的 (全局序号, 本地序号)
+    ///
+    /// # 前置条件
+    /// - 调用方持有 `append_lock`
+    pub(crate) fn append_converted(
+        &self,
+        doc_id: DocId,
+        peer_id: &PeerId,
+        converted: Vec<Op>,
+        mut build: impl FnMut(u64, Op) -> LedgerEntry,
+    ) -> Result<Option<(u64, u64)>> {
+        let mut last = None;
+        for op in converted {
+            let seqs = ops::append_generated_op(&self.local_db, doc_id, peer_id.clone(), |seq| {
+                build(seq, op.clone())
+            })?;
+            self.doc_cache.on_append(doc_id, seqs.0, &op);
+            last = Some(seqs);
+        }
+        Ok(last)
+    }
+
+    /// 取影子库中该文档的 CRDT 操作并集合并到本地库
+    ///
+    /// 返回新写入的操作及其位置效果，供编辑器按序应用。
+    /// 任一端不是 CRDT 文档或两端基线不同时返回 `None`，由调用方回退到三方合并。
+    ///
+    /// # 后置条件
+    /// - 合并结果与操作到达顺序无关；重复合并不写入新操作
+    pub fn merge_crdt_ops(
+        &self,
+        peer_id: &PeerId,
+        repo_id: &RepoId,
+        doc_id: DocId,
+    ) -> Result<Option<Vec<MergedOp>>> {
+        let Ok(remote) = self.get_ops(&RepoType::Remote(peer_id.clone(), *repo_id), doc_id) else {
+            return Ok(None);
+        };
+        let _guard = self.append_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let local = self.get_local_ops(doc_id)?;
+        if !same_baseline(&local, &remote) {
+            return Ok(None);
+        }
+        self.integrate_remote(doc_id, &remote).map(Some)
+    }
+
+    /// 三方合并成功后统一基线: 本地非 CRDT 文档，或远端基线标识更小时，接入远端 CRDT 操作
+    ///
+    /// 本地已是 CRDT 文档时先删除全部可见字符；接入后以本地操作写入到 `merged` 的差异。
+    /// 之后两端基线一致，可直接取并集合并。返回是否接入。
+    pub(crate) fn adopt_crdt_ops(
+        &self,
+        doc_id: DocId,
+        local: &[(u64, LedgerEntry)],
+        remote: &[(u64, LedgerEntry)],
+        merged: &str,
+    ) -> Result<bool> {
+        let Some((_, Some(remote_first))) = first_crdt(remote) else {
+            return Ok(false);
+        };
+        if let Some((_, local_first)) = first_crdt(local)
+            && local_first.is_none_or(|id| id <= remote_first)
+        {
+            return Ok(false);
+        }
+        let _guard = self.append_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let peer_id = PeerId::new("local_watcher");
+        let build = |seq, op| LedgerEntry {
+            doc_id,
+            op,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            peer_id: peer_id.clone(),
+            seq,
+        };
+        let clear = self
+            .doc_cache
+            .with_state(&self.local_db, doc_id, |state, _| {
+                state.crdt().and_then(|crdt| {
+                    crdt.local_op(
+                        &Op::Delete {
+                            pos: 0,
+                            len: u32::MAX,
+                        },
+                        self.site_id,
+                    )
+                })
+            })?;
+        self.append_converted(
+            doc_id,
+            &peer_id,
+            clear.into_iter().map(Op::Crdt).collect(),
+            build,
+        )?;
+        self.integrate_remote(doc_id, remote)?;
+
+        let (current, _) = self.get_local_state(doc_id)?;
+        for op in compute_diff(&current, merged) {
+            let converted = self.convert_for_append(doc_id, &op)?.unwrap_or_default();
+            self.append_converted(doc_id, &peer_id, converted, build)?;
+        }
+        Ok(true)
+    }
+
+    /// 按远端顺序写入本地尚未生效的 CRDT 操作 (保留原 PeerId 与序号)
+    fn integrate_remote(
+        &self,
+        doc_id: DocId,
+        remote: &[(u64, LedgerEntry)],
+    ) -> Result<Vec<MergedOp>> {
+        let mut state = self
+            .doc_cache
+            .with_state(&self.local_db, doc_id, |state, _| state.clone())?;
+        let mut merged = Vec::new();
+        for (_, entry) in remote {
+            let Op::Crdt(op) = &entry.op else {
+                continue;
+            };
+            if state.crdt().is_some_and(|crdt| crdt.is_redundant(op)) {
+                continue;
+            }
+            let effects = state.apply_effects(&entry.op);
+            let seq = ops::append_op_to_db(&self.local_db, entry)?;
+            self.doc_cache.on_append(doc_id, seq, &entry.op);
+            merged.push(MergedOp {
+                seq,
+                op: entry.op.clone(),
+                effects,
+            });
+        }
+        Ok(merged)
+    }
+}
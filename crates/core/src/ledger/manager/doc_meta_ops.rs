@@ -0,0 +1,114 @@
+// crates/core/src/ledger/manager/doc_meta_ops.rs
+//! # 文档属性操作
+//!
+//! 实现 `RepoManager` 的属性/出链提取、对账与 `DocQuery` 查询。
+
+use crate::ledger::RepoManager;
+use crate::ledger::doc_meta::{self, links};
+use crate::markdown::links::extract_links;
+use crate::markdown::{DocMetadata, extract_metadata};
+use crate::models::DocId;
+use crate::protocol::query::{CompareOp, DocFilter, DocQuery, DocRow};
+use anyhow::Result;
+use std::collections::HashSet;
+
+impl RepoManager {
+    /// 从本地操作日志重新提取单个文档的元数据与出链
+    ///
+    /// # 后置条件
+    /// - 文档存在则写入最新元数据与出链，不存在则删除
+    /// - 返回 true 表示索引发生变化 (反链需另行 `rebuild_backlinks`)
+    pub fn refresh_doc_metadata(&self, doc_id: DocId) -> Result<bool> {
+        if self.get_path_by_docid(doc_id)?.is_none() {
+            let meta = doc_meta::remove_doc_meta(&self.local_db, doc_id)?;
+            let links = links::remove_doc_links(&self.local_db, doc_id)?;
+            return Ok(meta || links);
+        }
+        let content = self.get_local_content(doc_id)?;
+        let meta = doc_meta::set_doc_meta(&self.local_db, doc_id, &extract_metadata(&content))?;
+        let links = links::set_doc_links(&self.local_db, doc_id, &extract_links(&content))?;
+        Ok(meta || links)
+    }
+
+    /// 对账: 删除已不存在文档的索引并重建反链，返回变化条数
+    ///
+    /// - `full`: 重新提取全部文档 (启动时，离线期间内容可能变化)
+    /// - 否则仅提取尚未索引的文档 (目录级变更只影响路径集合)
+    pub fn reconcile_doc_metadata(&self, full: bool) -> Result<usize> {
+        let indexed = doc_meta::indexed_docs(&self.local_db)?;
+        let mut stale: HashSet<DocId> = indexed.clone();
+        stale.extend(
+            links::all_doc_links(&self.local_db)?
+                .into_iter()
+                .map(|(id, _)| id),
+        );
+        let mut changed = 0;
+        for (doc_id, _) in self.list_local_docs(None)? {
+            stale.remove(&doc_id);
+            if full || !indexed.contains(&doc_id) {
+                changed += usize::from(self.refresh_doc_metadata(doc_id)?);
+            }
+        }
+        for doc_id in stale {
+            let meta = doc_meta::remove_doc_meta(&self.local_db, doc_id)?;
+            let links = links::remove_doc_links(&self.local_db, doc_id)?;
+            changed += usize::from(meta || links);
+        }
+        self.rebuild_backlinks()?;
+        Ok(changed)
+    }
+
+    pub fn get_doc_metadata(&self, doc_id: DocId) -> Result<Option<DocMetadata>> {
+        doc_meta::get_doc_meta(&self.local_db, doc_id)
+    }
+
+    /// 执行属性查询
+    ///
+    /// 先用倒排表 (`tag = x`、`key = v`) 求候选集交集，再逐个校验全部条件。
+    /// 查询先规范化，来自 WebSocket / HTTP 的查询与文本语法等价。
+    pub fn query_docs(&self, query: &DocQuery) -> Result<Vec<DocRow>> {
+        let query = &query.normalized();
+        let mut candidates: Option<HashSet<DocId>> = None;
+        for filter in &query.filters {
+            let hits = match filter {
+                DocFilter::Tag { tag, negate: false } => {
+                    doc_meta::docs_with_tag(&self.local_db, tag)?
+                }
+                DocFilter::Property {
+                    key,
+                    op: CompareOp::Eq,
+                    value,
+                } if key != "path" => doc_meta::docs_with_property(&self.local_db, key, value)?,
+                _ => continue,
+            };
+            candidates = Some(match candidates {
+                Some(c) => c.intersection(&hits).copied().collect(),
+                None => hits,
+            });
+        }
+        let candidates = match candidates {
+            Some(c) => c,
+            None => doc_meta::indexed_docs(&self.local_db)?,
+        };
+
+        let mut rows = Vec::new();
+        for doc_id in candidates {
+            let (Some(path), Some(meta)) = (
+                self.get_path_by_docid(doc_id)?,
+                self.get_doc_metadata(doc_id)?,
+            ) else {
+                continue;
+            };
+            if query.matches(&path, &meta) {
+                rows.push(DocRow {
+                    doc_id,
+                    path,
+                    tags: meta.tags,
+                    properties: meta.properties,
+                });
+            }
+        }
+        query.sort_and_limit(&mut rows);
+        Ok(rows)
+    }
+}
@@ -0,0 +1,118 @@
+// crates/core/src/ledger/manager/graph_ops.rs
+//! # 知识图谱操作
+//!
+//! 由反链表与标签索引组装文档/标签图，供 Web 图谱面板使用。
+
+use crate::ledger::RepoManager;
+use crate::ledger::doc_meta::{self, links};
+use crate::models::DocId;
+use crate::protocol::{GraphEdge, GraphEdgeKind, GraphNode, GraphNodeKind, KnowledgeGraph};
+use anyhow::Result;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// 单次返回的文档节点上限 (超出时按连接度截断)
+pub const MAX_GRAPH_DOCS: usize = 5000;
+
+impl RepoManager {
+    /// 组装知识图谱
+    ///
+    /// - `root` 为空: 全库文档及其标签
+    /// - `root` 非空: 沿链接 (忽略方向) 广度优先扩展 `depth` 跳内的文档
+    ///
+    /// 标签只作为已选文档的叶子节点附加，不参与扩展，避免热门标签把子图撑成全库。
+    ///
+    /// # 后置条件
+    /// - 文档节点在前、标签节点在后；边端点均为有效节点下标
+    /// - 文档数不超过 `MAX_GRAPH_DOCS`，截断时 `truncated = true`
+    pub fn get_graph(&self, root: Option<DocId>, depth: u32) -> Result<KnowledgeGraph> {
+        let docs = self.list_local_docs(None)?;
+        let paths: HashMap<DocId, String> = docs.into_iter().collect();
+
+        let link_edges: Vec<(DocId, DocId)> = links::all_link_edges(&self.local_db)?
+            .into_iter()
+            .filter(|(s, t)| s != t && paths.contains_key(s) && paths.contains_key(t))
+            .collect();
+        let mut adjacency: HashMap<DocId, Vec<DocId>> = HashMap::new();
+        for (s, t) in &link_edges {
+            adjacency.entry(*s).or_default().push(*t);
+            adjacency.entry(*t).or_default().push(*s);
+        }
+
+        let mut selected = match root {
+            Some(root) if paths.contains_key(&root) => {
+                let mut seen = HashSet::from([root]);
+                let mut order = vec![root];
+                let mut queue = VecDeque::from([(root, 0u32)]);
+                while let Some((doc, d)) = queue.pop_front() {
+                    if d >= depth {
+                        continue;
+                    }
+                    for next in adjacency.get(&doc).into_iter().flatten() {
+                        if seen.insert(*next) {
+                            order.push(*next);
+                            queue.push_back((*next, d + 1));
+                        }
+                    }
+                }
+                order
+            }
+            Some(_) => Vec::new(),
+            None => {
+                let mut all: Vec<DocId> = paths.keys().copied().collect();
+                all.sort_by(|a, b| {
+                    let degree = |id: &DocId| adjacency.get(id).map_or(0, Vec::len);
+                    degree(b)
+                        .cmp(&degree(a))
+                        .then_with(|| paths[a].cmp(&paths[b]))
+                });
+                all
+            }
+        };
+        let truncated = selected.len() > MAX_GRAPH_DOCS;
+        selected.truncate(MAX_GRAPH_DOCS);
+
+        let mut graph = KnowledgeGraph {
+            truncated,
+            ..Default::default()
+        };
+        let mut doc_index: HashMap<DocId, u32> = HashMap::new();
+        for doc in selected {
+            doc_index.insert(doc, graph.nodes.len() as u32);
+            graph.nodes.push(GraphNode {
+                kind: GraphNodeKind::Doc,
+                doc_id: Some(doc),
+                key: paths[&doc].clone(),
+            });
+        }
+        for (s, t) in link_edges {
+            if let (Some(&source), Some(&target)) = (doc_index.get(&s), doc_index.get(&t)) {
+                graph.edges.push(GraphEdge {
+                    source,
+                    target,
+                    kind: GraphEdgeKind::Link,
+                });
+            }
+        }
+
+        let mut tag_index: HashMap<String, u32> = HashMap::new();
+        for (tag, doc) in doc_meta::all_tag_docs(&self.local_db)? {
+            let Some(&source) = doc_index.get(&doc) else {
+                continue;
+            };
+            let target = *tag_index.entry(tag.clone()).or_insert_with(|| {
+                graph.nodes.push(GraphNode {
+                    kind: GraphNodeKind::Tag,
+                    doc_id: None,
+                    key: tag,
+                });
+                graph.nodes.len() as u32 - 1
+            });
+            graph.edges.push(GraphEdge {
+                source,
+                target,
+                kind: GraphEdgeKind::Tag,
+            });
+        }
+        Ok(graph)
+    }
+}
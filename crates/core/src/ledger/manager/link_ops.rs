@@ -0,0 +1,220 @@
+// crates/core/src/ledger/manager/link_ops.rs
+//! # 链接图操作
+//!
+//! 实现 `RepoManager` 的反链查询、断链报告与重命名后的入链改写计划。
+//! `PathSet` 缓存路径集合，供属性索引线程在普通编辑后增量更新反链。
+
+use crate::ledger::RepoManager;
+use crate::ledger::doc_meta::links::{self, LinkRewrite};
+use crate::markdown::links::{Link, LinkResolver, apply_edits, extract_links};
+use crate::models::DocId;
+use crate::protocol::{Backlink, UnresolvedLink};
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+/// 本地文档路径集合及其链接解析器
+///
+/// **Invariant**: 仅在路径集合未变化时有效；新建、重命名、删除后须重新加载。
+pub struct PathSet {
+    paths: HashMap<DocId, String>,
+    ids: HashMap<String, DocId>,
+    resolver: LinkResolver,
+}
+
+impl PathSet {
+    pub fn new(docs: Vec<(DocId, String)>) -> Self {
+        let resolver = LinkResolver::new(docs.iter().map(|(_, p)| p.as_str()));
+        let ids = docs.iter().map(|(id, p)| (p.clone(), *id)).collect();
+        Self {
+            paths: docs.into_iter().collect(),
+            ids,
+            resolver,
+        }
+    }
+
+    pub fn path(&self, doc_id: DocId) -> Option<&str> {
+        self.paths.get(&doc_id).map(String::as_str)
+    }
+
+    /// 解析来源文档出链的目标文档集合
+    fn targets(&self, source_path: &str, out: &[Link]) -> HashSet<DocId> {
+        out.iter()
+            .filter_map(|link| self.resolver.resolve(source_path, link))
+            .filter_map(|target| self.ids.get(&target).copied())
+            .collect()
+    }
+}
+
+impl RepoManager {
+    /// 当前本地文档的路径集合
+    pub fn path_set(&self) -> Result<PathSet> {
+        Ok(PathSet::new(self.list_local_docs(None)?))
+    }
+
+    /// 路径集合不变时刷新文档的属性与出链，只更新该文档作为来源的反链行
+    ///
+    /// 返回属性或出链是否变化。文档须在 `paths` 中且路径未变 (否则应整体重建)。
+    pub fn refresh_doc_links(&self, doc_id: DocId, paths: &PathSet) -> Result<bool> {
+        let Some(source_path) = paths.path(doc_id) else {
+            return self.refresh_doc_metadata(doc_id);
+        };
+        let old = links::get_doc_links(&self.local_db, doc_id)?.unwrap_or_default();
+        let changed = self.refresh_doc_metadata(doc_id)?;
+        let new = links::get_doc_links(&self.local_db, doc_id)?.unwrap_or_default();
+        if old != new {
+            links::update_source_backlinks(
+                &self.local_db,
+                doc_id,
+                &paths.targets(source_path, &old),
+                &paths.targets(source_path, &new),
+            )?;
+        }
+        Ok(changed)
+    }
+
+    /// 基于当前路径集合重新解析全部出链，整体替换反链表
+    pub fn rebuild_backlinks(&self) -> Result<()> {
+        let paths = self.path_set()?;
+        let mut edges = HashSet::new();
+        for (source, out) in links::all_doc_links(&self.local_db)? {
+            let Some(source_path) = paths.path(source) else {
+                continue;
+            };
+            for target in paths.targets(source_path, &out) {
+                edges.insert((target, source));
+            }
+        }
+        links::replace_backlinks(&self.local_db, &edges)
+    }
+
+    /// 指向文档的全部链接，按来源路径与行号排序
+    pub fn get_backlinks(&self, doc_id: DocId) -> Result<Vec<Backlink>> {
+        let Some(target) = self.get_path_by_docid(doc_id)? else {
+            return Ok(Vec::new());
+        };
+        let docs = self.list_local_docs(None)?;
+        let resolver = LinkResolver::new(docs.iter().map(|(_, p)| p.as_str()));
+
+        let mut out = Vec::new();
+        for source in links::backlink_sources(&self.local_db, doc_id)? {
+            let (Some(path), Some(doc_links)) = (
+                self.get_path_by_docid(source)?,
+                links::get_doc_links(&self.local_db, source)?,
+            ) else {
+                continue;
+            };
+            for link in doc_links {
+                if resolver.resolve(&path, &link).as_deref() == Some(target.as_str()) {
+                    out.push(Backlink {
+                        source,
+                        path: path.clone(),
+                        line: link.line,
+                        context: link.context,
+                    });
+                }
+            }
+        }
+        out.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+        Ok(out)
+    }
+
+    /// 无法解析到任何文档的链接
+    pub fn unresolved_links(&self) -> Result<Vec<UnresolvedLink>> {
+        let docs = self.list_local_docs(None)?;
+        let resolver = LinkResolver::new(docs.iter().map(|(_, p)| p.as_str()));
+        let paths: HashMap<DocId, &str> = docs.iter().map(|(id, p)| (*id, p.as_str())).collect();
+
+        let mut out = Vec::new();
+        for (source, doc_links) in links::all_doc_links(&self.local_db)? {
+            let Some(path) = paths.get(&source) else {
+                continue;
+            };
+            for link in doc_links {
+                if resolver.resolve(path, &link).is_none() {
+                    out.push(UnresolvedLink {
+                        source,
+                        path: path.to_string(),
+                        target: link.target,
+                        line: link.line,
+                    });
+                }
+            }
+        }
+        out.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+        Ok(out)
+    }
+
+    /// 计算 `old_path -> new_path` (文档或文件夹) 重命名后需要改写的链接
+    ///
+    /// 在 Ledger 已完成重命名后调用: 将当前路径集合映射回旧路径，
+    /// 按旧路径解析每个链接，若其在新路径集合下不再指向同一文档则改写。
+    /// 同时覆盖两类情况: 指向被移动文档的入链，以及被移动文档内的相对链接。
+    ///
+    /// # 后置条件
+    /// - 仅包含至少改写一处链接的文档；无需改写的链接保持原样
+    pub fn plan_link_rewrites(&self, old_path: &str, new_path: &str) -> Result<Vec<LinkRewrite>> {
+        let old_of = |p: &str| -> Option<String> {
+            if p == new_path {
+                return Some(old_path.to_string());
+            }
+            p.strip_prefix(new_path)
+                .and_then(|rest| rest.strip_prefix('/'))
+                .map(|rest| format!("{}/{}", old_path, rest))
+        };
+        let docs = self.list_local_docs(None)?;
+        let moved: HashMap<String, String> = docs
+            .iter()
+            .filter_map(|(_, p)| old_of(p).map(|old| (old, p.clone())))
+            .collect();
+        if moved.is_empty() {
+            return Ok(Vec::new());
+        }
+        let old_paths: Vec<String> = docs
+            .iter()
+            .map(|(_, p)| old_of(p).unwrap_or_else(|| p.clone()))
+            .collect();
+        let old_resolver = LinkResolver::new(old_paths.iter().map(String::as_str));
+        let new_resolver = LinkResolver::new(docs.iter().map(|(_, p)| p.as_str()));
+        let stored: HashMap<DocId, _> = links::all_doc_links(&self.local_db)?.into_iter().collect();
+
+        let mut plans = Vec::new();
+        for ((doc_id, path), source_old) in docs.iter().zip(&old_paths) {
+            // 先用已索引的出链快速排除无关文档
+            let relevant = source_old != path
+                || stored.get(doc_id).is_some_and(|out| {
+                    out.iter().any(|l| {
+                        old_resolver
+                            .resolve(source_old, l)
+                            .is_some_and(|t| moved.contains_key(&t))
+                    })
+                });
+            if !relevant {
+                continue;
+            }
+
+            let content = self.get_local_content(*doc_id)?;
+            let mut edits = Vec::new();
+            for link in extract_links(&content) {
+                let Some(target_old) = old_resolver.resolve(source_old, &link) else {
+                    continue;
+                };
+                let target = moved.get(&target_old).cloned().unwrap_or(target_old);
+                if new_resolver.resolve(path, &link).as_deref() == Some(target.as_str()) {
+                    continue;
+                }
+                let replacement = new_resolver.rewrite(path, &link, &target);
+                edits.push((link.span, replacement));
+            }
+            if !edits.is_empty() {
+                plans.push(LinkRewrite {
+                    doc_id: *doc_id,
+                    path: path.clone(),
+                    new_content: apply_edits(&content, &edits),
+                    content,
+                    links: edits.len(),
+                });
+            }
+        }
+        Ok(plans)
+    }
+}
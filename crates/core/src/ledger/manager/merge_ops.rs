@@ -17,12 +17,19 @@ impl RepoManager {
     /// 3. 找到 LCA (Lowest Common Ancestor)
     /// 4. 重建 base/local/remote 内容
     /// 5. 执行三方合并
+    ///
+    /// 两端均为基线一致的 CRDT 文档时直接取操作并集 (见 `merge_crdt_ops`)，不会冲突；
+    /// 远端为 CRDT 文档而基线不同时，三方合并成功后由本地接入远端基线。
     pub fn merge_peer(
         &self,
         peer_id: &PeerId,
         repo_id: &RepoId,
         doc_id: DocId,
     ) -> Result<MergeResult> {
+        if self.merge_crdt_ops(peer_id, repo_id, doc_id)?.is_some() {
+            return Ok(MergeResult::Success(self.get_local_state(doc_id)?.0));
+        }
+
         // 1. 获取操作
         let local_ops = self.get_local_ops(doc_id)?;
         let remote_ops = match self.get_ops(&RepoType::Remote(peer_id.clone(), *repo_id), doc_id) {
@@ -70,10 +77,10 @@ impl RepoManager {
             MergeEngine::reconstruct_state_at(doc_id, &all_remote_entries, &remote_vv);
 
         // 5. 执行三方合并
-        Ok(MergeEngine::merge_commits(
-            &base_content,
-            &local_content,
-            &remote_content,
-        ))
+        let result = MergeEngine::merge_commits(&base_content, &local_content, &remote_content);
+        if let MergeResult::Success(merged) = &result {
+            self.adopt_crdt_ops(doc_id, &local_ops, &remote_ops, merged)?;
+        }
+        Ok(result)
     }
 }
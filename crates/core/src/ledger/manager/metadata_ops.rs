@@ -19,6 +19,11 @@ impl RepoManager {
         metadata::create_docid(&self.local_db, path)
     }
 
+    /// 以指定 DocId 注册路径
+    pub fn register_docid(&self, path: &str, doc_id: DocId) -> Result<()> {
+        metadata::register_docid(&self.local_db, path, doc_id)
+    }
+
     /// 根据 DocId 获取路径
     pub fn get_path_by_docid(&self, doc_id: DocId) -> Result<Option<String>> {
         metadata::get_path_by_docid(&self.local_db, doc_id)
@@ -8,6 +8,11 @@ pub mod locator;
 pub mod maintenance;
 pub mod types;
 
+mod attachment_ops;
+mod crdt_ops;
+mod doc_meta_ops;
+mod graph_ops;
+mod link_ops;
 mod merge_ops;
 mod metadata_ops;
 mod ops_ops;
@@ -16,3 +21,6 @@ mod snapshot_ops;
 mod source_control_api;
 mod source_control_ops;
 mod source_control_query_ops;
+
+pub(crate) use crdt_ops::load_crdt_settings;
+pub(crate) use link_ops::PathSet;
@@ -3,30 +3,78 @@
 //!
 //! 实现 `RepoManager` 的操作追加和读取方法。
 
+use crate::config::AppProfile;
 use crate::ledger::RepoManager;
 use crate::ledger::ops;
-use crate::models::{DocId, LedgerEntry, PeerId, RepoType};
+use crate::metrics;
+use crate::models::{DocId, LedgerEntry, Op, PeerId, RepoType};
+use crate::state::{self, ot};
 use anyhow::Result;
+use std::sync::MutexGuard;
 
 impl RepoManager {
     /// 追加操作到本地库 (Store B)
     ///
     /// **权限**: Local Write Only - 仅接受本地用户的操作。
     pub fn append_local_op(&self, entry: &LedgerEntry) -> Result<u64> {
-        ops::append_op_to_db(&self.local_db, entry)
+        let _timer = metrics::OP_APPEND_SECONDS.start_timer();
+        let _guard = self.append_lock.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(converted) = self.convert_for_append(entry.doc_id, &entry.op)? {
+            // 转换结果可能含多条操作，改用生成的本地序号避免 (PeerId, Seq) 重复
+            let last =
+                self.append_converted(entry.doc_id, &entry.peer_id, converted, |seq, op| {
+                    LedgerEntry {
+                        op,
+                        seq,
+                        ..entry.clone()
+                    }
+                })?;
+            return match last {
+                Some((seq, _)) => Ok(seq),
+                None => Ok(ops::doc_ops_stats(&self.local_db, entry.doc_id)?.0),
+            };
+        }
+        let seq = ops::append_op_to_db(&self.local_db, entry)?;
+        self.doc_cache.on_append(entry.doc_id, seq, &entry.op);
+        Ok(seq)
     }
 
     /// 原子生成序号并追加操作 (推荐用于本地编辑)
     ///
     /// 自动计算下一个 Local Sequence，避免竞态条件。
-    /// 返回: (GlobalSeq, LocalSeq)
+    /// 文档为 CRDT 模型时操作先转换 (可能写入多条或不写入)，构建器会被多次调用，须无副作用。
+    /// 返回: (GlobalSeq, LocalSeq)；未写入时为 (当前最大序号, 0)
     pub fn append_generated_op(
         &self,
         doc_id: DocId,
         peer_id: PeerId,
-        op_entry_builder: impl FnMut(u64) -> LedgerEntry,
+        mut op_entry_builder: impl FnMut(u64) -> LedgerEntry,
     ) -> Result<(u64, u64)> {
-        ops::append_generated_op(&self.local_db, doc_id, peer_id, op_entry_builder)
+        let _timer = metrics::OP_APPEND_SECONDS.start_timer();
+        let _guard = self.append_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let probe = op_entry_builder(0).op;
+        if let Some(converted) = self.convert_for_append(doc_id, &probe)? {
+            let last =
+                self.append_converted(doc_id, &peer_id, converted, |seq, op| LedgerEntry {
+                    op,
+                    ..op_entry_builder(seq)
+                })?;
+            return match last {
+                Some(seqs) => Ok(seqs),
+                None => Ok((ops::doc_ops_stats(&self.local_db, doc_id)?.0, 0)),
+            };
+        }
+        let mut op = None;
+        let seqs = ops::append_generated_op(&self.local_db, doc_id, peer_id, |local_seq| {
+            let entry = op_entry_builder(local_seq);
+            op = Some(entry.op.clone());
+            entry
+        })?;
+        match op {
+            Some(op) => self.doc_cache.on_append(doc_id, seqs.0, &op),
+            None => self.doc_cache.invalidate(doc_id),
+        }
+        Ok(seqs)
     }
 
     /// 从指定仓库读取操作
@@ -47,6 +95,57 @@ impl RepoManager {
         }
     }
 
+    /// 读取本地库文档的当前内容与最大序号 (经文档状态缓存)
+    pub fn get_local_state(&self, doc_id: DocId) -> Result<(String, u64)> {
+        self.doc_cache.content(&self.local_db, doc_id)
+    }
+
+    /// 读取本地库文档的当前内容 (经文档状态缓存)
+    pub fn get_local_content(&self, doc_id: DocId) -> Result<String> {
+        Ok(self.get_local_state(doc_id)?.0)
+    }
+
+    /// 按运行模式调整文档状态缓存预算
+    pub fn set_profile(&self, profile: AppProfile) {
+        self.doc_cache.set_budget(profile.doc_cache_bytes());
+    }
+
+    /// 本地库文档的操作数 (只扫描索引)
+    pub fn count_local_ops(&self, doc_id: DocId) -> Result<u64> {
+        ops::count_ops_from_db(&self.local_db, doc_id)
+    }
+
+    /// 获取编辑定序锁
+    ///
+    /// 本地库的读取-变换-追加 (编辑、对账、丢弃更改等) 持锁执行，
+    /// 期间其他本地写入不会插入，广播的操作也按序号排列。锁不可重入。
+    pub fn lock_edits(&self) -> MutexGuard<'_, ()> {
+        self.edit_lock.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 将基于 `base_seq` 生成的客户端操作变换到本地库其后的并发操作之上
+    ///
+    /// # 前置条件
+    /// - 调用方持有 `lock_edits`，直到变换结果写入账本 (否则并发操作可能遗漏)
+    pub fn rebase_local_op(&self, doc_id: DocId, base_seq: u64, op: &Op) -> Result<Vec<Op>> {
+        let delta = ops::get_ops_from_db_after(&self.local_db, doc_id, base_seq)?;
+        let concurrent: Vec<Op> = if delta.iter().any(|(_, e)| matches!(e.op, Op::Crdt(_))) {
+            // CRDT 操作以其对文本的位置效果参与变换: 优先取缓存的最近效果，
+            // 基线早于缓存覆盖范围时才从头重放
+            let effects = match self
+                .doc_cache
+                .text_ops_after(&self.local_db, doc_id, base_seq)?
+            {
+                Some(effects) => effects,
+                None => state::text_ops_after(&self.get_local_ops(doc_id)?, base_seq),
+            };
+            effects.into_iter().map(|(_, op)| op).collect()
+        } else {
+            delta.into_iter().map(|(_, entry)| entry.op).collect()
+        };
+        Ok(ot::rebase(op, &concurrent))
+    }
+
     /// 从本地库读取操作（便捷方法）
     pub fn get_local_ops(&self, doc_id: DocId) -> Result<Vec<(u64, LedgerEntry)>> {
         self.get_ops(&RepoType::Local(uuid::Uuid::nil()), doc_id)
@@ -6,8 +6,8 @@ use crate::ledger::listing::RepoListing;
 use crate::ledger::traits::Repository;
 use crate::models::DocId;
 use crate::models::RepoType;
+use crate::protocol::{DocQuery, DocRow};
 use crate::source_control::{ChangeEntry, CommitInfo};
-use crate::state::reconstruct_content;
 use anyhow::Result;
 
 impl Repository for RepoManager {
@@ -22,9 +22,11 @@ impl Repository for RepoManager {
     }
 
     fn get_doc_content(&self, doc_id: DocId) -> Result<String> {
-        let ops = self.get_local_ops(doc_id)?;
-        let entries: Vec<_> = ops.into_iter().map(|(_, e)| e).collect();
-        Ok(reconstruct_content(&entries))
+        self.get_local_content(doc_id)
+    }
+
+    fn query_docs(&self, query: &DocQuery) -> Result<Vec<DocRow>> {
+        self.query_docs(query)
     }
 
     fn list_changes(&self) -> Result<Vec<ChangeEntry>> {
@@ -6,12 +6,18 @@
 use crate::ledger::RepoManager;
 use crate::ledger::snapshot;
 use crate::models::DocId;
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 
 impl RepoManager {
     /// 保存文档快照 (仅限本地库)
+    ///
+    /// 以文档状态缓存校验 `content` 与 `seq`，无需整篇重放操作日志。
     pub fn save_snapshot(&self, doc_id: DocId, seq: u64, content: &str) -> Result<()> {
-        snapshot::save_snapshot(&self.local_db, doc_id, seq, content, self.snapshot_depth)
+        let (current, max_seq) = self.get_local_state(doc_id)?;
+        if max_seq != seq || current != content {
+            return Err(anyhow!("Snapshot verification failed"));
+        }
+        snapshot::store_snapshot(&self.local_db, doc_id, seq, content, self.snapshot_depth)
     }
 
     /// 读取文档的最新快照 (仅限本地库)
@@ -6,8 +6,8 @@
 use crate::ledger::RepoManager;
 use crate::ledger::source_control;
 use crate::models::DocId;
-use crate::source_control::snapshot_paths;
 use crate::source_control::{ChangeEntry, ChangeStatus, CommitInfo, SnapshotUpdate};
+use crate::source_control::{attachment_snapshots, snapshot_paths};
 use crate::utils::path::to_forward_slash;
 use anyhow::Result;
 
@@ -44,6 +44,25 @@ impl RepoManager {
         source_control::list_commits(&self.local_db, limit)
     }
 
+    /// 按 ID 获取提交
+    pub fn get_commit(&self, commit_id: &str) -> Result<Option<CommitInfo>> {
+        source_control::get_commit(&self.local_db, commit_id)
+    }
+
+    /// 重放 `ledger_seq` (含) 之前的本地操作，得到文档在该提交时的内容
+    ///
+    /// `commit_snapshots` 只保存最近一次提交的内容，历史版本须从 Ledger 重建。
+    /// 提交时文档尚不存在则返回空字符串。
+    pub fn get_content_at_seq(&self, doc_id: DocId, ledger_seq: u64) -> Result<String> {
+        let entries: Vec<_> = self
+            .get_local_ops(doc_id)?
+            .into_iter()
+            .filter(|(seq, _)| *seq <= ledger_seq)
+            .map(|(_, e)| e)
+            .collect();
+        Ok(crate::state::reconstruct_content(&entries))
+    }
+
     /// 获取文档的已提交内容 (用于 Diff)
     pub fn get_committed_content(&self, doc_id: DocId) -> Result<Option<String>> {
         source_control::get_committed_content(&self.local_db, doc_id)
@@ -65,18 +84,23 @@ impl RepoManager {
             if let Ok(Some(doc_id)) =
                 crate::ledger::metadata::get_docid(&self.local_db, &normalized)
             {
-                let ops = self.get_local_ops(doc_id).ok()?;
-                let entries: Vec<_> = ops.into_iter().map(|(_, entry)| entry).collect();
-                let content = crate::state::reconstruct_content(&entries);
+                let content = self.get_local_content(doc_id).ok()?;
                 Some(SnapshotUpdate::Save {
                     doc_id,
                     path: normalized,
                     content,
                 })
+            } else if let Ok(Some(attachment)) = self.get_attachment(&normalized) {
+                Some(SnapshotUpdate::SaveAttachment {
+                    path: normalized,
+                    hash: attachment.hash,
+                })
             } else if let Ok(Some(doc_id)) =
                 snapshot_paths::find_snapshot_doc_id(&self.local_db, &normalized)
             {
                 Some(SnapshotUpdate::Delete { doc_id })
+            } else if let Ok(Some(_)) = attachment_snapshots::get(&self.local_db, &normalized) {
+                Some(SnapshotUpdate::DeleteAttachment { path: normalized })
             } else {
                 None
             }
@@ -5,10 +5,9 @@
 
 use crate::ledger::RepoManager;
 use crate::ledger::metadata;
-use crate::source_control::ChangeEntry;
 use crate::source_control::diff;
 use crate::source_control::snapshot_paths;
-use crate::state::reconstruct_content;
+use crate::source_control::{ChangeEntry, ChangeStatus, attachment_snapshots};
 use crate::utils::path::to_forward_slash;
 use anyhow::Result;
 
@@ -25,9 +24,7 @@ impl RepoManager {
 
         for (doc_id, path) in docs {
             let committed = self.get_committed_content(doc_id)?;
-            let ops = self.get_local_ops(doc_id)?;
-            let entries: Vec<_> = ops.into_iter().map(|(_, entry)| entry).collect();
-            let current = reconstruct_content(&entries);
+            let current = self.get_local_content(doc_id)?;
 
             if let Some(status) = self.detect_change(committed.as_deref(), Some(&current)) {
                 changes.push(ChangeEntry { path, status });
@@ -44,12 +41,54 @@ impl RepoManager {
             }
         }
 
+        changes.extend(self.list_attachment_changes()?);
+        Ok(changes)
+    }
+
+    /// 附件变更: 对比当前内容哈希与已提交哈希
+    fn list_attachment_changes(&self) -> Result<Vec<ChangeEntry>> {
+        let mut committed: std::collections::HashMap<String, String> =
+            attachment_snapshots::list(&self.local_db)?
+                .into_iter()
+                .collect();
+        let mut changes = Vec::new();
+
+        for (path, attachment) in self.list_attachments()? {
+            let status = match committed.remove(&path) {
+                None => Some(ChangeStatus::Added),
+                Some(hash) if hash != attachment.hash => Some(ChangeStatus::Modified),
+                Some(_) => None,
+            };
+            if let Some(status) = status {
+                changes.push(ChangeEntry { path, status });
+            }
+        }
+        for path in committed.into_keys() {
+            changes.push(ChangeEntry {
+                path,
+                status: ChangeStatus::Deleted,
+            });
+        }
         Ok(changes)
     }
 
     /// 生成指定路径的统一 Diff (基于快照对比)
     pub fn diff_doc_path(&self, path: &str) -> Result<String> {
         let normalized = to_forward_slash(path);
+        if metadata::get_docid(&self.local_db, &normalized)?.is_none() {
+            let current = self.get_attachment(&normalized)?.map(|a| a.hash);
+            let committed = attachment_snapshots::get(&self.local_db, &normalized)?;
+            if current.is_some() || committed.is_some() {
+                let short =
+                    |h: Option<String>| h.map_or("none".to_string(), |h| h[..12].to_string());
+                return Ok(format!(
+                    "Binary file {} changed: {} -> {}\n",
+                    normalized,
+                    short(committed),
+                    short(current)
+                ));
+            }
+        }
         let doc_id = metadata::get_docid(&self.local_db, &normalized)?
             .or_else(|| {
                 snapshot_paths::find_snapshot_doc_id(&self.local_db, &normalized)
@@ -60,9 +99,7 @@ impl RepoManager {
 
         let committed = self.get_committed_content(doc_id)?;
         let current = if metadata::get_docid(&self.local_db, &normalized)?.is_some() {
-            let ops = self.get_local_ops(doc_id)?;
-            let entries: Vec<_> = ops.into_iter().map(|(_, entry)| entry).collect();
-            reconstruct_content(&entries)
+            self.get_local_content(doc_id)?
         } else {
             String::new()
         };
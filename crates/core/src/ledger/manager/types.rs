@@ -1,9 +1,11 @@
-use crate::models::{PeerId, RepoId};
+use crate::config::DocModel;
+use crate::ledger::doc_cache::DocCache;
+use crate::models::{Op, PeerId, RepoId};
 use redb::Database;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::RwLock;
+use std::sync::{Mutex, RwLock};
 
 /// 仓库元数据信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +18,17 @@ pub struct RepoInfo {
     pub url: Option<String>,
 }
 
+/// CRDT 合并写入本地库的一条远端操作 (见 `RepoManager::merge_crdt_ops`)
+#[derive(Debug, Clone)]
+pub struct MergedOp {
+    /// 本地库全局序号
+    pub seq: u64,
+    /// 写入的操作 (`Op::Crdt`)
+    pub op: Op,
+    /// 对写入前文本的位置效果 (按顺序应用；依赖未到达时为空)
+    pub effects: Vec<Op>,
+}
+
 /// 仓库管理器 (Repository Manager)
 ///
 /// 管理本地唯一的 Local Repo (Store B) 和多个 Shadow Repos (Store C)。
@@ -33,4 +46,14 @@ pub struct RepoManager {
     pub(crate) shadow_dbs: RwLock<HashMap<PeerId, HashMap<RepoId, Database>>>,
     /// 快照保留深度
     pub snapshot_depth: usize,
+    /// 主库文档状态缓存 (预算见 `AppProfile::doc_cache_bytes`)
+    pub(crate) doc_cache: DocCache,
+    /// 文档模型 (仓库级设置，持久化于 `REPO_METADATA`)
+    pub(crate) doc_model: RwLock<DocModel>,
+    /// 本地库的 CRDT 站点标识 (生成 `OpId`)
+    pub(crate) site_id: u64,
+    /// 串行化需转换为 CRDT 操作的追加 (转换依赖追加前的文档状态)
+    pub(crate) append_lock: Mutex<()>,
+    /// 编辑定序锁: 基于版本的变换与本地追加须整体串行 (见 `lock_edits`)
+    pub(crate) edit_lock: Mutex<()>,
 }
@@ -23,8 +23,17 @@ pub fn get_docid(db: &Database, path: &str) -> Result<Option<DocId>> {
 }
 
 pub fn create_docid(db: &Database, path: &str) -> Result<DocId> {
-    let normalized = to_forward_slash(path);
     let id = DocId::new();
+    register_docid(db, path, id)?;
+    Ok(id)
+}
+
+/// 以指定 DocId 注册路径 (导入时保留原 DocId)
+///
+/// # 前置条件
+/// - `path` 与 `id` 均未被占用，由调用方检查
+pub fn register_docid(db: &Database, path: &str, id: DocId) -> Result<()> {
+    let normalized = to_forward_slash(path);
     let write_txn = db.begin_write()?;
     {
         let mut p2d = write_txn.open_table(PATH_TO_DOCID)?;
@@ -35,7 +44,7 @@ pub fn create_docid(db: &Database, path: &str) -> Result<DocId> {
     }
     write_txn.commit()?;
     node_meta::ensure_file_node(db, &normalized, id)?;
-    Ok(id)
+    Ok(())
 }
 
 pub fn get_path_by_docid(db: &Database, doc_id: DocId) -> Result<Option<String>> {
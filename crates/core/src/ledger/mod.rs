@@ -11,6 +11,9 @@
 //! ## 模块结构
 //!
 //! - `schema`: 数据库表定义
+//! - `blob`: 附件内容寻址存储 (Blob Store)
+//! - `doc_cache`: 主库文档状态 LRU 缓存
+//! - `doc_meta`: 文档属性/标签索引、链接图与后台提取线程
 //! - `init`: 初始化逻辑
 //! - `metadata`: Path/DocId 映射
 //! - `node_meta`: NodeId/Path/Meta 映射
@@ -27,7 +30,10 @@
 
 // ========== 子模块声明 ==========
 
+pub mod blob;
 pub mod database;
+pub mod doc_cache;
+pub mod doc_meta;
 pub mod init;
 pub mod listing;
 mod manager;
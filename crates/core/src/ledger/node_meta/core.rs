@@ -98,8 +98,8 @@ pub fn ensure_file_node(db: &Database, path: &str, doc_id: DocId) -> Result<Node
     if let Some(existing) = get_node_id(db, &normalized)? {
         let meta = get_node_meta(db, existing)?
             .ok_or_else(|| anyhow!("Node meta missing: {}", normalized))?;
-        if meta.kind == NodeKind::Dir {
-            return Err(anyhow!("Path is a directory: {}", normalized));
+        if meta.kind != NodeKind::File {
+            return Err(anyhow!("Path is not a document: {}", normalized));
         }
         let expected = NodeId::from_doc_id(doc_id);
         if existing != expected {
@@ -122,6 +122,35 @@ pub fn ensure_file_node(db: &Database, path: &str, doc_id: DocId) -> Result<Node
     Ok(node_id)
 }
 
+/// 确保附件节点存在 (NodeKind::Attachment，无 DocId)
+pub fn ensure_attachment_node(db: &Database, path: &str) -> Result<NodeId> {
+    let normalized = to_forward_slash(path);
+    if normalized.ends_with('/') {
+        return Err(anyhow!("File path must not end with '/': {}", normalized));
+    }
+    if let Some(existing) = get_node_id(db, &normalized)? {
+        let meta = get_node_meta(db, existing)?
+            .ok_or_else(|| anyhow!("Node meta missing: {}", normalized))?;
+        if meta.kind != NodeKind::Attachment {
+            return Err(anyhow!("Path is not an attachment: {}", normalized));
+        }
+        return Ok(existing);
+    }
+    let (parent_path, name) = split_path(&normalized);
+    let parent_id = ensure_dir_chain(db, parent_path)?;
+    let node_id = NodeId::new();
+
+    let meta = NodeMeta {
+        kind: NodeKind::Attachment,
+        name: name.to_string(),
+        parent_id,
+        path: normalized,
+        doc_id: None,
+    };
+    upsert_node(db, node_id, &meta)?;
+    Ok(node_id)
+}
+
 pub fn create_dir_node(db: &Database, path: &str) -> Result<NodeId> {
     let normalized = to_forward_slash(path).trim_end_matches('/').to_string();
     if normalized.is_empty() {
@@ -135,6 +135,18 @@ pub fn get_ops_from_db(db: &Database, doc_id: DocId) -> Result<Vec<(u64, LedgerE
     Ok(entries)
 }
 
+/// 列出数据库中存在操作记录的全部文档 (升序)
+pub fn list_doc_ids_from_db(db: &Database) -> Result<Vec<DocId>> {
+    let read_txn = db.begin_read()?;
+    let doc_ops_table = read_txn.open_multimap_table(DOC_OPS)?;
+    let mut ids = Vec::new();
+    for item in doc_ops_table.iter()? {
+        let (key, _) = item?;
+        ids.push(DocId::from_u128(key.value()));
+    }
+    Ok(ids)
+}
+
 pub fn count_ops_from_db(db: &Database, doc_id: DocId) -> Result<u64> {
     let read_txn = db.begin_read()?;
     let doc_ops_table = read_txn.open_multimap_table(DOC_OPS)?;
@@ -159,6 +171,18 @@ pub fn max_seq_from_db(db: &Database, doc_id: DocId) -> Result<u64> {
     Ok(max_seq)
 }
 
+/// 文档的 (最大序号, 操作数)，只扫描 DOC_OPS 索引、不反序列化操作
+pub fn doc_ops_stats(db: &Database, doc_id: DocId) -> Result<(u64, u64)> {
+    let read_txn = db.begin_read()?;
+    let doc_ops_table = read_txn.open_multimap_table(DOC_OPS)?;
+    let (mut max_seq, mut count) = (0u64, 0u64);
+    for item in doc_ops_table.get(doc_id.as_u128())? {
+        max_seq = max_seq.max(item?.value());
+        count += 1;
+    }
+    Ok((max_seq, count))
+}
+
 /// 从指定数据库读取指定序列号之后的操作。
 pub fn get_ops_from_db_after(
     db: &Database,
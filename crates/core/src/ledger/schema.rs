@@ -1,4 +1,4 @@
-﻿// crates\core\src\ledger
+// crates\core\src\ledger
 use redb::{MultimapTableDefinition, TableDefinition};
 
 // DocId (u128) -> Path String
@@ -41,8 +41,39 @@ pub const SNAPSHOT_DATA: TableDefinition<u64, &[u8]> = TableDefinition::new("sna
 
 // Metadata Key (u8) -> Metadata Value (Bytes - JSON/Bincode)
 // Key 0: RepoInfo (UUID, Name, URL)
+// Key 1: DocModel (bincode，缺失为 Text)
+// Key 2: CRDT 站点标识 (u64 小端，每个本地库随机生成)
 pub const REPO_METADATA: TableDefinition<u8, &[u8]> = TableDefinition::new("repo_metadata");
 
 // (DocId (u128), PeerId (&str)) -> MaxSeq (u64)
 // Used for atomic sequence generation and O(1) retrieval.
 pub const PEER_DOC_SEQ: TableDefinition<(u128, &str), u64> = TableDefinition::new("peer_doc_seq");
+
+// Blob Hash (sha256 hex) -> BlobMeta (Bytes) - 仅在全部分块写入并校验后出现
+pub const BLOB_META: TableDefinition<&str, &[u8]> = TableDefinition::new("blob_meta");
+
+// (Blob Hash, Chunk Index) -> Chunk Bytes
+pub const BLOB_CHUNKS: TableDefinition<(&str, u32), &[u8]> = TableDefinition::new("blob_chunks");
+
+// Blob Hash -> 已收到的分块数 (仅在分块传输未完成时存在)
+pub const BLOB_PENDING: TableDefinition<&str, u32> = TableDefinition::new("blob_pending");
+
+// NodeId (u128) -> AttachmentRef (Bytes) - 以 NodeId 为键，重命名/移动无需改写
+pub const ATTACHMENTS: TableDefinition<u128, &[u8]> = TableDefinition::new("attachments");
+
+// DocId (u128) -> DocMetadata (Bytes) - frontmatter 属性与标签 (派生数据，可由操作日志重建)
+pub const DOC_META: TableDefinition<u128, &[u8]> = TableDefinition::new("doc_meta");
+
+// Tag -> DocIds - 标签倒排索引
+pub const TAG_DOCS: MultimapTableDefinition<&str, u128> = MultimapTableDefinition::new("tag_docs");
+
+// (Property Key, Lowercased Value) -> DocIds - 属性倒排索引
+pub const PROP_DOCS: MultimapTableDefinition<(&str, &str), u128> =
+    MultimapTableDefinition::new("prop_docs");
+
+// DocId (u128) -> Vec<Link> (Bytes) - 文档出链 (原始目标，解析依赖当前路径集合)
+pub const DOC_LINKS: TableDefinition<u128, &[u8]> = TableDefinition::new("doc_links");
+
+// Target DocId (u128) -> Source DocIds - 反链索引 (随路径集合变化整体重建)
+pub const BACKLINKS: MultimapTableDefinition<u128, u128> =
+    MultimapTableDefinition::new("backlinks");
@@ -86,6 +86,43 @@ impl RepoManager {
         range::get_max_seq(db)
     }
 
+    /// 列出影子库中的全部文档及路径
+    ///
+    /// 同步只复制操作，影子库的路径表可能为空: 依次取影子库路径、
+    /// 本地同一文档的路径，都没有时以 DocId 作为占位路径。
+    pub fn list_shadow_docs(
+        &self,
+        peer_id: &PeerId,
+        repo_id: &RepoId,
+    ) -> Result<Vec<(DocId, String)>> {
+        self.ensure_shadow_db(peer_id, repo_id)?;
+
+        let (doc_ids, paths) = {
+            let dbs = self.shadow_dbs.read().unwrap();
+            let db = dbs
+                .get(peer_id)
+                .and_then(|repos| repos.get(repo_id))
+                .ok_or_else(|| {
+                    anyhow::anyhow!("未找到指定 Repo 的影子库: {}/{}", peer_id, repo_id)
+                })?;
+            let paths: std::collections::HashMap<_, _> =
+                super::metadata::list_docs(db)?.into_iter().collect();
+            (ops::list_doc_ids_from_db(db)?, paths)
+        };
+
+        let mut docs = Vec::with_capacity(doc_ids.len());
+        for doc_id in doc_ids {
+            let path = match paths.get(&doc_id) {
+                Some(path) => path.clone(),
+                None => self
+                    .get_path_by_docid(doc_id)?
+                    .unwrap_or_else(|| doc_id.to_string()),
+            };
+            docs.push((doc_id, path));
+        }
+        Ok(docs)
+    }
+
     /// 获取指定影子库指定序列号范围的操作
     ///
     /// 用于 P2P 同步中的增量拉取。
@@ -22,6 +22,20 @@ pub fn save_snapshot(
     if !verified {
         return Err(anyhow!("Snapshot verification failed"));
     }
+    store_snapshot(db, doc_id, seq, content, depth)
+}
+
+/// 写入快照并按深度清理，不做一致性校验
+///
+/// # 前置条件
+/// - 调用方已确认 `content` 为该文档在 `seq` 处的状态 (如经 `DocCache` 比对)
+pub fn store_snapshot(
+    db: &Database,
+    doc_id: DocId,
+    seq: u64,
+    content: &str,
+    depth: usize,
+) -> Result<()> {
     let write_txn = db.begin_write()?;
     {
         let mut index = write_txn.open_multimap_table(SNAPSHOT_INDEX)?;
@@ -11,7 +11,8 @@
 use crate::ledger::range;
 use crate::models::DocId;
 use crate::source_control::{
-    ChangeEntry, ChangeStatus, CommitInfo, SnapshotUpdate, changes, commits, staging,
+    ChangeEntry, ChangeStatus, CommitInfo, SnapshotUpdate, attachment_snapshots, changes, commits,
+    staging,
 };
 use anyhow::Result;
 use redb::Database;
@@ -21,6 +22,7 @@ pub fn init_tables(db: &Database) -> Result<()> {
     staging::init_table(db)?;
     commits::init_table(db)?;
     changes::init_table(db)?;
+    attachment_snapshots::init_table(db)?;
     Ok(())
 }
 
@@ -95,6 +97,12 @@ pub fn create_commit_with_updates(
                     content,
                 } => changes::save_snapshot(db, doc_id, &path, &content)?,
                 SnapshotUpdate::Delete { doc_id } => changes::remove_snapshot(db, doc_id)?,
+                SnapshotUpdate::SaveAttachment { path, hash } => {
+                    attachment_snapshots::save(db, &path, &hash)?
+                }
+                SnapshotUpdate::DeleteAttachment { path } => {
+                    attachment_snapshots::remove(db, &path)?
+                }
             }
         }
     }
@@ -111,6 +119,11 @@ pub fn list_commits(db: &Database, limit: u32) -> Result<Vec<CommitInfo>> {
     commits::list(db, limit)
 }
 
+/// 按 ID 获取提交
+pub fn get_commit(db: &Database, commit_id: &str) -> Result<Option<CommitInfo>> {
+    commits::get(db, commit_id)
+}
+
 /// 获取文档的已提交内容 (快照)
 pub fn get_committed_content(db: &Database, doc_id: DocId) -> Result<Option<String>> {
     changes::get_committed_content(db, doc_id)
@@ -216,3 +216,497 @@ fn test_node_repair_missing() -> Result<()> {
 
     Ok(())
 }
+
+/// 测试附件的暂存/提交流程
+///
+/// 验证:
+/// - 新附件为 Added，提交后无变更
+/// - 内容变化为 Modified，删除为 Deleted
+/// - 重命名沿用同一节点与 Blob
+#[test]
+fn test_attachment_commit_cycle() -> Result<()> {
+    use crate::source_control::ChangeStatus;
+
+    let tmp_dir = TempDir::new()?;
+    let repo = RepoManager::init(tmp_dir.path().join("ledger"), 2, None, None)?;
+    let status_of = |repo: &RepoManager, path: &str| -> Result<Option<ChangeStatus>> {
+        Ok(repo
+            .list_changes()?
+            .into_iter()
+            .find(|c| c.path == path)
+            .map(|c| c.status))
+    };
+
+    let path = "assets/diagram.png";
+    let (node_id, changed) = repo.store_attachment(path, b"v1")?;
+    assert!(changed);
+    assert!(repo.get_docid(path)?.is_none());
+    assert_eq!(status_of(&repo, path)?, Some(ChangeStatus::Added));
+
+    repo.stage_file(path)?;
+    repo.commit_staged("add diagram")?;
+    assert_eq!(status_of(&repo, path)?, None);
+
+    assert!(!repo.store_attachment(path, b"v1")?.1);
+    repo.store_attachment(path, b"v2")?;
+    assert_eq!(status_of(&repo, path)?, Some(ChangeStatus::Modified));
+
+    repo.rename_folder("assets", "media")?;
+    assert_eq!(
+        repo.read_attachment("media/diagram.png")?,
+        Some(b"v2".to_vec())
+    );
+    assert_eq!(
+        repo.run_on_local_repo(repo.local_repo_name(), |db| node_meta::get_node_id(
+            db,
+            "media/diagram.png"
+        ))?,
+        Some(node_id)
+    );
+    repo.rename_attachment("media/diagram.png", "media/chart.png")?;
+    assert!(repo.get_attachment("media/diagram.png")?.is_none());
+
+    assert_eq!(repo.remove_attachment("media/chart.png")?, Some(node_id));
+    assert_eq!(status_of(&repo, path)?, Some(ChangeStatus::Deleted));
+    repo.stage_file(path)?;
+    repo.commit_staged("remove diagram")?;
+    assert!(repo.list_changes()?.is_empty());
+
+    Ok(())
+}
+
+/// 以 "全删 + 插入" 两条操作把文档内容替换为 `text` (不存在时创建)
+fn write_doc(repo: &RepoManager, path: &str, text: &str) -> Result<DocId> {
+    use crate::models::Op;
+
+    let doc_id = match repo.get_docid(path)? {
+        Some(id) => id,
+        None => repo.create_docid(path)?,
+    };
+    let entries: Vec<_> = repo
+        .get_local_ops(doc_id)?
+        .into_iter()
+        .map(|(_, e)| e)
+        .collect();
+    let len = crate::state::reconstruct_content(&entries).chars().count() as u32;
+    for op in [
+        Op::Delete { pos: 0, len },
+        Op::Insert {
+            pos: 0,
+            content: text.into(),
+        },
+    ] {
+        repo.append_local_op(&LedgerEntry {
+            doc_id,
+            op,
+            timestamp: 0,
+            peer_id: PeerId::new("test"),
+            seq: 0,
+        })?;
+    }
+    Ok(doc_id)
+}
+
+/// 测试文档属性索引与 `DocQuery`
+///
+/// 验证:
+/// - 编辑后重新提取会替换旧的倒排项
+/// - 过滤、排序与删除后的对账
+#[test]
+fn test_doc_metadata_query() -> Result<()> {
+    use crate::protocol::DocQuery;
+
+    let tmp_dir = TempDir::new()?;
+    let repo = RepoManager::init(tmp_dir.path(), 10, None, None)?;
+    let write = |path: &str, text: &str| write_doc(&repo, path, text);
+    let a = write("a.md", "---\nstatus: doing\ndue: 2024-06-20\n---\n#project")?;
+    let b = write("b.md", "---\nstatus: todo\ndue: 2024-06-10\n---\n#project")?;
+    write("c.md", "---\nstatus: done\ndue: 2024-06-01\n---\n#project")?;
+    write("d.md", "---\nstatus: Ärger\n---\n#other")?;
+    assert_eq!(repo.reconcile_doc_metadata(true)?, 4);
+
+    let paths = |q: &str| -> Result<Vec<String>> {
+        let rows = repo.query_docs(&DocQuery::parse(q)?)?;
+        Ok(rows.into_iter().map(|r| r.path).collect())
+    };
+    assert_eq!(
+        paths("tag = project AND status != done SORT BY due")?,
+        vec!["b.md", "a.md"]
+    );
+    assert_eq!(paths("status = TODO")?, vec!["b.md"]);
+    // 非 ASCII 值: 索引与逐条过滤使用同一大小写折叠
+    assert_eq!(paths("status = ärger")?, vec!["d.md"]);
+    assert_eq!(paths("has due SORT BY due DESC LIMIT 1")?, vec!["a.md"]);
+
+    // 编辑后旧属性值不再命中
+    write("b.md", "---\nstatus: done\n---\n#project")?;
+    assert!(repo.refresh_doc_metadata(b)?);
+    assert!(!repo.refresh_doc_metadata(b)?);
+    assert!(paths("status = todo")?.is_empty());
+    assert_eq!(
+        paths("tag = project AND status = done")?,
+        vec!["b.md", "c.md"]
+    );
+
+    // 删除后由对账清理
+    repo.delete_doc("a.md")?;
+    assert_eq!(repo.reconcile_doc_metadata(false)?, 1);
+    assert!(repo.get_doc_metadata(a)?.is_none());
+    assert_eq!(paths("")?, vec!["b.md", "c.md", "d.md"]);
+    Ok(())
+}
+
+/// 测试链接图与重命名后的入链改写
+///
+/// 验证:
+/// - 反链、断链报告
+/// - 文件夹重命名后: 入链按原写法改写，被移动文档内的相对链接随之调整
+#[test]
+fn test_link_graph_and_rename_rewrite() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let repo = RepoManager::init(tmp_dir.path(), 10, None, None)?;
+    let target = write_doc(&repo, "notes/beta.md", "back to [index](../index.md)")?;
+    let index = write_doc(
+        &repo,
+        "index.md",
+        "[[beta]] · [[notes/beta|Beta]] · [b](notes/beta.md) · [[missing]]",
+    )?;
+    repo.reconcile_doc_metadata(true)?;
+
+    let backlinks = repo.get_backlinks(target)?;
+    assert_eq!(backlinks.len(), 3);
+    assert!(backlinks.iter().all(|b| b.source == index && b.line == 1));
+    assert_eq!(repo.get_backlinks(index)?.len(), 1);
+    let unresolved = repo.unresolved_links()?;
+    assert_eq!(unresolved.len(), 1);
+    assert_eq!(unresolved[0].target, "missing");
+
+    // 路径集合不变的编辑: 只增量更新该来源文档的反链
+    let paths = repo.path_set()?;
+    write_doc(&repo, "index.md", "[[beta]] · [[missing]]")?;
+    assert!(repo.refresh_doc_links(index, &paths)?);
+    assert_eq!(repo.get_backlinks(target)?.len(), 1);
+    write_doc(&repo, "index.md", "[[missing]]")?;
+    repo.refresh_doc_links(index, &paths)?;
+    assert!(repo.get_backlinks(target)?.is_empty());
+    assert_eq!(repo.get_backlinks(index)?.len(), 1);
+    write_doc(
+        &repo,
+        "index.md",
+        "[[beta]] · [[notes/beta|Beta]] · [b](notes/beta.md) · [[missing]]",
+    )?;
+    repo.refresh_doc_links(index, &paths)?;
+    assert_eq!(repo.get_backlinks(target)?.len(), 3);
+
+    repo.rename_folder("notes", "archive/notes")?;
+    repo.reconcile_doc_metadata(false)?;
+    assert_eq!(repo.get_backlinks(target)?.len(), 1);
+
+    let plans = repo.plan_link_rewrites("notes", "archive/notes")?;
+    let content = |id: DocId| -> Option<String> {
+        plans
+            .iter()
+            .find(|p| p.doc_id == id)
+            .map(|p| p.new_content.clone())
+    };
+    assert_eq!(
+        content(index).as_deref(),
+        Some("[[beta]] · [[archive/notes/beta|Beta]] · [b](archive/notes/beta.md) · [[missing]]")
+    );
+    assert_eq!(
+        content(target).as_deref(),
+        Some("back to [index](../../index.md)")
+    );
+    assert!(repo.plan_link_rewrites("other", "elsewhere")?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_knowledge_graph() -> Result<()> {
+    use crate::protocol::{GraphEdgeKind, GraphNodeKind};
+
+    let tmp_dir = TempDir::new()?;
+    let repo = RepoManager::init(tmp_dir.path(), 10, None, None)?;
+    let a = write_doc(&repo, "a.md", "#idea [[b]]")?;
+    let b = write_doc(&repo, "b.md", "[[c]] [[b]]")?;
+    let c = write_doc(&repo, "c.md", "#idea")?;
+    write_doc(&repo, "lonely.md", "no links")?;
+    repo.reconcile_doc_metadata(true)?;
+
+    let full = repo.get_graph(None, 0)?;
+    let docs = full
+        .nodes
+        .iter()
+        .filter(|n| n.kind == GraphNodeKind::Doc)
+        .count();
+    assert_eq!(docs, 4);
+    assert!(!full.truncated);
+    assert_eq!(
+        full.edges
+            .iter()
+            .filter(|e| e.kind == GraphEdgeKind::Link)
+            .count(),
+        2,
+        "self-links are dropped"
+    );
+    let idea = full
+        .nodes
+        .iter()
+        .position(|n| n.kind == GraphNodeKind::Tag && n.key == "idea")
+        .expect("tag node") as u32;
+    assert_eq!(full.edges.iter().filter(|e| e.target == idea).count(), 2);
+
+    let sub = repo.get_graph(Some(a), 1)?;
+    let ids: Vec<_> = sub.nodes.iter().filter_map(|n| n.doc_id).collect();
+    assert_eq!(ids, vec![a, b]);
+    assert!(sub.nodes.iter().any(|n| n.key == "idea"));
+    let sub = repo.get_graph(Some(c), 2)?;
+    assert_eq!(sub.nodes.iter().filter(|n| n.doc_id.is_some()).count(), 3);
+    Ok(())
+}
+
+/// 测试文档状态缓存
+///
+/// 验证:
+/// - 追加操作后增量推进，绕过钩子直接写库时由读取校验补齐
+/// - 超出预算时淘汰最久未使用的文档
+/// - 快照以缓存状态校验
+/// - 重建以最新快照为起点，CRDT 文档仍从头重放
+#[test]
+fn test_doc_state_cache() -> Result<()> {
+    use crate::models::Op;
+
+    let tmp_dir = TempDir::new()?;
+    let repo = RepoManager::init(tmp_dir.path().join("ledger"), 10, None, None)?;
+    let a = write_doc(&repo, "a.md", "alpha")?;
+    assert_eq!(repo.get_local_state(a)?, ("alpha".to_string(), 2));
+    write_doc(&repo, "a.md", "alpha 🍎")?;
+    assert_eq!(repo.get_local_content(a)?, "alpha 🍎");
+
+    // 直接写库不经过 on_append
+    ops::append_op_to_db(
+        &repo.local_db,
+        &LedgerEntry {
+            doc_id: a,
+            op: Op::Insert {
+                pos: 8,
+                content: "!".into(),
+            },
+            timestamp: 0,
+            peer_id: PeerId::new("test"),
+            seq: 0,
+        },
+    )?;
+    let (content, seq) = repo.get_local_state(a)?;
+    assert_eq!(content, "alpha 🍎!");
+    assert_eq!(seq, 5);
+
+    // 快照内容或序号不符时拒绝
+    assert!(repo.save_snapshot(a, seq, "alpha").is_err());
+    assert!(repo.save_snapshot(a, seq - 1, &content).is_err());
+    repo.save_snapshot(a, seq, &content)?;
+    assert_eq!(repo.load_latest_snapshot(a)?, Some((seq, content)));
+
+    // 预算只够一篇文档: 读取 b 后 a 被淘汰，结果不受影响
+    let b = write_doc(&repo, "b.md", "beta")?;
+    repo.doc_cache.set_budget(1500);
+    repo.get_local_content(b)?;
+    assert_eq!(repo.doc_cache.usage().0, 1);
+    assert_eq!(repo.get_local_content(a)?, "alpha 🍎!");
+    assert_eq!(repo.doc_cache.usage().0, 1);
+
+    // 重建以最新快照为起点: 以不同于账本的快照内容验证未从头重放
+    snapshot::store_snapshot(&repo.local_db, a, seq, "seeded", 10)?;
+    repo.doc_cache.clear();
+    assert_eq!(repo.get_local_content(a)?, "seeded");
+    write_doc(&repo, "a.md", "seeded!")?;
+    repo.doc_cache.clear();
+    assert_eq!(repo.get_local_content(a)?, "seeded!");
+
+    // 迁移为 CRDT 后快照不能承载字符标识，从头重放
+    repo.set_doc_model(crate::config::DocModel::Crdt)?;
+    repo.migrate_to_crdt(&PeerId::new("test"))?;
+    let (_, seq) = repo.get_local_state(a)?;
+    snapshot::store_snapshot(&repo.local_db, a, seq, "stale", 10)?;
+    repo.doc_cache.clear();
+    assert_eq!(repo.get_local_content(a)?, "seeded!");
+    Ok(())
+}
+
+/// 测试基于旧版本的客户端操作变换到并发操作之后
+#[test]
+fn test_rebase_local_op() -> Result<()> {
+    use crate::models::Op;
+
+    let tmp_dir = TempDir::new()?;
+    let repo = RepoManager::init(tmp_dir.path().join("ledger"), 10, None, None)?;
+    let a = write_doc(&repo, "a.md", "hello world")?;
+    let (_, base) = repo.get_local_state(a)?;
+
+    // 并发: 另一客户端在开头插入
+    repo.append_local_op(&LedgerEntry {
+        doc_id: a,
+        op: Op::Insert {
+            pos: 0,
+            content: ">> ".into(),
+        },
+        timestamp: 0,
+        peer_id: PeerId::new("test"),
+        seq: 0,
+    })?;
+
+    // 基于 base 删除 "world"，位置后移到并发插入之后；最新版本之上无需变换
+    let del = Op::Delete { pos: 6, len: 5 };
+    let ops = repo.rebase_local_op(a, base, &del)?;
+    assert!(matches!(ops[..], [Op::Delete { pos: 9, len: 5 }]));
+    let (_, head) = repo.get_local_state(a)?;
+    let ops = repo.rebase_local_op(a, head, &del)?;
+    assert!(matches!(ops[..], [Op::Delete { pos: 6, len: 5 }]));
+
+    // CRDT 文档: 并发操作的位置效果取自缓存，与从头重放一致；基线过早时回退重放
+    repo.set_doc_model(crate::config::DocModel::Crdt)?;
+    let peer = PeerId::new("test");
+    repo.migrate_to_crdt(&peer)?;
+    let (_, base) = repo.get_local_state(a)?;
+    for i in 0..300 {
+        repo.append_local_op(&LedgerEntry {
+            doc_id: a,
+            op: Op::Insert {
+                pos: 3,
+                content: if i % 2 == 0 { "x" } else { "y" }.into(),
+            },
+            timestamp: 0,
+            peer_id: peer.clone(),
+            seq: 0,
+        })?;
+    }
+    let (_, head) = repo.get_local_state(a)?;
+    let cached = repo
+        .doc_cache
+        .text_ops_after(&repo.local_db, a, head - 10)?
+        .expect("covered by recent effects");
+    let replayed = crate::state::text_ops_after(&repo.get_local_ops(a)?, head - 10);
+    assert_eq!(format!("{cached:?}"), format!("{replayed:?}"));
+    assert!(
+        repo.doc_cache
+            .text_ops_after(&repo.local_db, a, base)?
+            .is_none()
+    );
+    let ops = repo.rebase_local_op(a, head - 1, &Op::Delete { pos: 0, len: 3 })?;
+    assert!(matches!(ops[..], [Op::Delete { pos: 0, len: 3 }]));
+    let ops = repo.rebase_local_op(
+        a,
+        base,
+        &Op::Insert {
+            pos: 5,
+            content: "!".into(),
+        },
+    )?;
+    assert!(matches!(ops[..], [Op::Insert { pos: 305, .. }]));
+    Ok(())
+}
+
+/// 以 `peer` 身份把 `from` 中文档的全部操作同步到 `to` 的影子库
+fn push_doc(from: &RepoManager, peer: &PeerId, to: &RepoManager, doc: DocId) -> Result<Uuid> {
+    let repo_id = from.get_repo_info()?.expect("repo info").uuid;
+    to.reset_shadow_doc(peer, &repo_id, &doc)?;
+    for (_, entry) in from.get_local_ops(doc)? {
+        to.append_remote_op(peer, &repo_id, &entry)?;
+    }
+    Ok(repo_id)
+}
+
+/// 测试 CRDT 文档模型
+///
+/// 验证:
+/// - 迁移保留内容，模型与站点标识持久化
+/// - 对端首次接收时接入迁移基线
+/// - 并发编辑取操作并集合并，两端收敛且重复合并无新写入
+#[test]
+fn test_crdt_doc_model_merge() -> Result<()> {
+    use crate::config::DocModel;
+    use crate::ledger::merge::MergeResult;
+    use crate::models::Op;
+    use crate::state::DocState;
+
+    let tmp_dir = TempDir::new()?;
+    let (pa, pb) = (PeerId::new("peer-a"), PeerId::new("peer-b"));
+    let a = RepoManager::init(tmp_dir.path().join("a"), 10, None, None)?;
+    let doc = write_doc(&a, "n.md", "hello world")?;
+    a.set_doc_model(DocModel::Crdt)?;
+    assert_eq!(a.migrate_to_crdt(&pa)?, 1);
+    assert_eq!(a.migrate_to_crdt(&pa)?, 0);
+    assert_eq!(a.get_local_content(doc)?, "hello world");
+    let site = a.site_id();
+    drop(a);
+    let a = RepoManager::init(tmp_dir.path().join("a"), 10, None, None)?;
+    assert_eq!((a.doc_model(), a.site_id()), (DocModel::Crdt, site));
+
+    // b 仍为 Text 模型: 首次合并经三方合并后接入 a 的基线
+    let b = RepoManager::init(tmp_dir.path().join("b"), 10, None, None)?;
+    let repo_a = push_doc(&a, &pa, &b, doc)?;
+    assert!(b.merge_crdt_ops(&pa, &repo_a, doc)?.is_none());
+    let merged = b.merge_peer(&pa, &repo_a, doc)?;
+    assert!(matches!(merged, MergeResult::Success(ref c) if c == "hello world"));
+    assert_eq!(b.get_local_content(doc)?, "hello world");
+
+    // 并发编辑 (b 的文档已是 CRDT，本地位置操作同样被转换)
+    let edit = |repo: &RepoManager, peer: &PeerId, op: Op| {
+        repo.append_local_op(&LedgerEntry {
+            doc_id: doc,
+            op,
+            timestamp: 0,
+            peer_id: peer.clone(),
+            seq: 0,
+        })
+    };
+    edit(
+        &a,
+        &pa,
+        Op::Insert {
+            pos: 6,
+            content: "big ".into(),
+        },
+    )?;
+    edit(&b, &pb, Op::Delete { pos: 0, len: 6 })?;
+    edit(
+        &b,
+        &pb,
+        Op::Insert {
+            pos: 5,
+            content: "!".into(),
+        },
+    )?;
+    assert!(
+        b.get_local_ops(doc)?
+            .iter()
+            .rev()
+            .take(2)
+            .all(|(_, e)| matches!(e.op, Op::Crdt(_)))
+    );
+
+    let repo_b = push_doc(&b, &pb, &a, doc)?;
+    push_doc(&a, &pa, &b, doc)?;
+    let before = a.get_local_content(doc)?;
+    let merged = a.merge_crdt_ops(&pb, &repo_b, doc)?.expect("same baseline");
+    assert!(!merged.is_empty());
+    assert!(b.merge_crdt_ops(&pa, &repo_a, doc)?.is_some());
+    assert_eq!(a.get_local_content(doc)?, "big world!");
+    assert_eq!(b.get_local_content(doc)?, "big world!");
+
+    // 位置效果依序作用于合并前的文本即得到合并结果
+    let mut view = DocState::new();
+    view.apply(&Op::Insert {
+        pos: 0,
+        content: before.as_str().into(),
+    });
+    for op in merged.iter().flat_map(|m| &m.effects) {
+        view.apply(op);
+    }
+    assert_eq!(view.content(), "big world!");
+
+    assert!(a.merge_crdt_ops(&pb, &repo_b, doc)?.unwrap().is_empty());
+    let merged = a.merge_peer(&pb, &repo_b, doc)?;
+    assert!(matches!(merged, MergeResult::Success(ref c) if c == "big world!"));
+    Ok(())
+}
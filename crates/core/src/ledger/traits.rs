@@ -2,12 +2,14 @@
 //! # Repository Trait
 
 use crate::models::DocId;
+use crate::protocol::{DocQuery, DocRow};
 use crate::source_control::{ChangeEntry, CommitInfo};
 use anyhow::Result;
 
 pub trait Repository: Send + Sync {
     fn list_docs(&self) -> Result<Vec<(DocId, String)>>;
     fn get_doc_content(&self, doc_id: DocId) -> Result<String>;
+    fn query_docs(&self, query: &DocQuery) -> Result<Vec<DocRow>>;
 
     fn list_changes(&self) -> Result<Vec<ChangeEntry>>;
     fn diff_doc_path(&self, path: &str) -> Result<String>;
@@ -9,6 +9,7 @@
 //! - `models`: 核心数据类型（DocId, Op, LedgerEntry）
 //! - `protocol`: WebSocket 消息定义（ClientMessage, ServerMessage）
 //! - `state`: 文档状态管理（内容重建、差异计算）
+//! - `markdown`: Markdown 元数据提取（行内标签）
 //! - `error`: 统一错误处理
 //! - `utils`: 跨平台工具函数
 //!
@@ -17,14 +18,21 @@
 //! - `vfs`: 虚拟文件系统操作
 //! - `watcher`: 文件系统变更检测
 //! - `sync`: 文档同步与调和
+//! - `metrics`: 热路径延迟/字节直方图（OpenMetrics 导出）
+//! - `agent`: 服务端智能体会话存储
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod agent;
 pub mod config;
 pub mod context;
 pub mod error;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod ledger;
+pub mod markdown;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod mcp;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod metrics;
 pub mod models;
 pub mod plugin;
 pub mod protocol;
@@ -0,0 +1,111 @@
+// crates\core\src\markdown
+//! # YAML Frontmatter
+//!
+//! 解析文档开头 `---` 包围的属性块。只支持笔记中常见的 YAML 子集:
+//! - `key: value` (值可加单/双引号)
+//! - `key: [a, b]` 行内列表
+//! - `key:` 后接缩进的 `- item` 块列表
+//!
+//! 嵌套映射、多行字符串等其余语法被忽略 (不报错)。
+//!
+//! ## Invariants
+//! - 键统一小写并去除首尾空白；空值不产生条目
+//! - 未闭合的 `---` 视为没有 frontmatter
+
+use std::collections::BTreeMap;
+
+/// 拆分 frontmatter 与正文，返回 `(属性块, 正文)`
+pub fn split_frontmatter(text: &str) -> (Option<&str>, &str) {
+    let Some(rest) = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+    else {
+        return (None, text);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, text)
+}
+
+/// 解析属性块为 `键 -> 值列表`
+pub fn parse_frontmatter(block: &str) -> BTreeMap<String, Vec<String>> {
+    let mut props: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut list_key: Option<String> = None;
+    for line in block.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix("- ")
+            && let Some(key) = &list_key
+        {
+            push_value(&mut props, key, item);
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) {
+            continue; // 嵌套映射
+        }
+        list_key = None;
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let value = value.trim();
+        if value.is_empty() {
+            list_key = Some(key);
+        } else if let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            inner
+                .split(',')
+                .for_each(|v| push_value(&mut props, &key, v));
+        } else {
+            push_value(&mut props, &key, value);
+        }
+    }
+    props
+}
+
+fn push_value(props: &mut BTreeMap<String, Vec<String>>, key: &str, raw: &str) {
+    let raw = raw.trim();
+    let value = raw
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| raw.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(raw)
+        .trim();
+    if !value.is_empty() {
+        props
+            .entry(key.to_string())
+            .or_default()
+            .push(value.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_frontmatter_subset() {
+        let text = "---\nStatus: in progress\nowner: \"Alice\"\ndue: 2024-06-01\ntags: [Work, 'q2']\naliases:\n  - Plan\n  - Roadmap\nnested:\n  key: ignored\n# comment\n---\n# Body\n";
+        let (block, body) = split_frontmatter(text);
+        assert_eq!(body, "# Body\n");
+        let props = parse_frontmatter(block.unwrap());
+        assert_eq!(props["status"], ["in progress"]);
+        assert_eq!(props["owner"], ["Alice"]);
+        assert_eq!(props["due"], ["2024-06-01"]);
+        assert_eq!(props["tags"], ["Work", "q2"]);
+        assert_eq!(props["aliases"], ["Plan", "Roadmap"]);
+        assert!(!props.contains_key("nested"));
+
+        assert_eq!(
+            split_frontmatter("---\nunclosed: yes\n"),
+            (None, "---\nunclosed: yes\n")
+        );
+        assert_eq!(split_frontmatter("no frontmatter").0, None);
+    }
+}
@@ -0,0 +1,376 @@
+// crates\core\src\markdown
+//! # 文档链接 (Wikilinks / Markdown Links)
+//!
+//! 提取、解析与改写文档间链接:
+//! - `[[note]]`、`[[folder/note|别名]]`、`[[note#标题]]`、`![[note]]`
+//! - `[文本](../folder/note.md#anchor)` (仅相对路径且指向 `.md`)
+//!
+//! ## Invariants
+//! - 围栏代码块与行内代码内的链接不计入
+//! - `Link::span` 为目标路径部分 (不含别名、锚点) 在原文中的字节区间
+//! - 解析以正斜杠 Vault 相对路径为准，大小写不敏感
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinkKind {
+    Wiki,
+    Markdown,
+}
+
+/// 文档中的一个出链
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Link {
+    pub kind: LinkKind,
+    /// 目标路径 (已解码，不含别名与锚点)
+    pub target: String,
+    /// 目标在原文中的字节区间 `[start, end)`
+    pub span: (usize, usize),
+    /// 行号 (从 1 开始)
+    pub line: u32,
+    /// 所在行 (截断)，用于反链上下文
+    pub context: String,
+    /// Markdown 目标是否写在 `<...>` 中 (允许空格，不做百分号编码)
+    pub angle: bool,
+}
+
+const CONTEXT_MAX_CHARS: usize = 120;
+
+/// 提取文档中的全部出链
+pub fn extract_links(text: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut in_fence = false;
+    let mut offset = 0;
+    for (idx, raw_line) in text.split_inclusive('\n').enumerate() {
+        let line_start = offset;
+        offset += raw_line.len();
+        let line = raw_line.trim_end_matches(['\n', '\r']);
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let context: String = line.trim().chars().take(CONTEXT_MAX_CHARS).collect();
+        let mut push = |kind, target: String, start: usize, end: usize, angle| {
+            if !target.is_empty() {
+                links.push(Link {
+                    kind,
+                    target,
+                    span: (line_start + start, line_start + end),
+                    line: idx as u32 + 1,
+                    context: context.clone(),
+                    angle,
+                });
+            }
+        };
+
+        let bytes = line.as_bytes();
+        let mut in_code = false;
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'`' => in_code = !in_code,
+                b'[' if !in_code && bytes.get(i + 1) == Some(&b'[') => {
+                    if let Some(len) = line[i + 2..].find("]]") {
+                        let inner = &line[i + 2..i + 2 + len];
+                        let end = inner.find(['|', '#']).unwrap_or(inner.len());
+                        let target = inner[..end].trim_end();
+                        push(
+                            LinkKind::Wiki,
+                            target.to_string(),
+                            i + 2,
+                            i + 2 + target.len(),
+                            false,
+                        );
+                        i += len + 4;
+                        continue;
+                    }
+                }
+                b'[' if !in_code && (i == 0 || bytes[i - 1] != b'!') => {
+                    if let Some((start, end, angle)) = markdown_dest(line, i) {
+                        let dest = &line[start..end];
+                        let path_end = dest.find(['#', '?']).unwrap_or(dest.len());
+                        let path = &dest[..path_end];
+                        if is_relative_doc(path) {
+                            let target = if angle {
+                                path.to_string()
+                            } else {
+                                percent_decode(path)
+                            };
+                            push(LinkKind::Markdown, target, start, start + path_end, angle);
+                        }
+                        i = end;
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+    links
+}
+
+/// 定位 `[text](dest)` 中 dest 的字节区间 (行内)，返回 `(start, end, angle)`
+fn markdown_dest(line: &str, open: usize) -> Option<(usize, usize, bool)> {
+    let close = open + 1 + line[open + 1..].find(']')?;
+    let rest = line[close + 1..].strip_prefix('(')?;
+    let paren = close + 2;
+    if let Some(inner) = rest.strip_prefix('<') {
+        let len = inner.find('>')?;
+        return Some((paren + 1, paren + 1 + len, true));
+    }
+    let len = rest.find([')', ' ', '\t'])?;
+    Some((paren, paren + len, false))
+}
+
+fn is_relative_doc(path: &str) -> bool {
+    !path.is_empty()
+        && !path.contains("://")
+        && !path.starts_with("mailto:")
+        && path.to_ascii_lowercase().ends_with(".md")
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(b) = s
+                .get(i + 1..i + 3)
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+        {
+            out.push(b);
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8(out).unwrap_or_else(|_| s.to_string())
+}
+
+fn percent_encode(s: &str) -> String {
+    s.replace('%', "%25").replace(' ', "%20")
+}
+
+/// 所在目录 (`a/b.md` -> `a`，根目录为空串)
+fn parent_dir(path: &str) -> &str {
+    path.rsplit_once('/').map(|(d, _)| d).unwrap_or("")
+}
+
+/// 文件名去掉 `.md` 后缀
+fn stem(path: &str) -> &str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    strip_md(name)
+}
+
+fn strip_md(path: &str) -> &str {
+    match path.len().checked_sub(3) {
+        Some(n) if path[n..].eq_ignore_ascii_case(".md") => &path[..n],
+        _ => path,
+    }
+}
+
+/// 拼接相对路径并消解 `.` / `..`；越过根目录时返回 None
+fn join_relative(dir: &str, rel: &str) -> Option<String> {
+    let mut parts: Vec<&str> = dir.split('/').filter(|p| !p.is_empty()).collect();
+    for part in rel.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            p => parts.push(p),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// 从 `from_dir` 指向 `to` 的相对路径
+fn relative_path(from_dir: &str, to: &str) -> String {
+    let from: Vec<&str> = from_dir.split('/').filter(|p| !p.is_empty()).collect();
+    let to_parts: Vec<&str> = to.split('/').collect();
+    let common = from
+        .iter()
+        .zip(&to_parts)
+        .take_while(|(a, b)| a == b)
+        .count()
+        .min(to_parts.len() - 1);
+    let mut out: Vec<&str> = vec![".."; from.len() - common];
+    out.extend(&to_parts[common..]);
+    out.join("/")
+}
+
+/// 基于当前文档路径集合的链接解析器
+pub struct LinkResolver {
+    by_path: HashMap<String, String>,
+    by_stem: HashMap<String, Vec<String>>,
+}
+
+impl LinkResolver {
+    pub fn new<'a>(paths: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut by_path = HashMap::new();
+        let mut by_stem: HashMap<String, Vec<String>> = HashMap::new();
+        for path in paths {
+            by_path.insert(path.to_lowercase(), path.to_string());
+            by_stem
+                .entry(stem(path).to_lowercase())
+                .or_default()
+                .push(path.to_string());
+        }
+        for candidates in by_stem.values_mut() {
+            candidates.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        }
+        Self { by_path, by_stem }
+    }
+
+    fn lookup(&self, path: &str) -> Option<&String> {
+        self.by_path.get(&path.to_lowercase())
+    }
+
+    /// 解析链接目标，返回目标文档路径
+    ///
+    /// - Wiki: 含 `/` 时先按 Vault 根再按当前目录查找；否则按文件名匹配，
+    ///   同目录优先，其次路径最短者
+    /// - Markdown: 相对当前文档所在目录
+    pub fn resolve(&self, source: &str, link: &Link) -> Option<String> {
+        let dir = parent_dir(source);
+        match link.kind {
+            LinkKind::Markdown => self.lookup(&join_relative(dir, &link.target)?).cloned(),
+            LinkKind::Wiki => {
+                let target = link.target.replace('\\', "/");
+                let with_ext = format!("{}.md", strip_md(&target));
+                if target.contains('/') {
+                    let from_root = join_relative("", &with_ext);
+                    let from_dir = join_relative(dir, &with_ext);
+                    return [from_root, from_dir]
+                        .into_iter()
+                        .flatten()
+                        .find_map(|p| self.lookup(&p).cloned());
+                }
+                let candidates = self.by_stem.get(&strip_md(&target).to_lowercase())?;
+                candidates
+                    .iter()
+                    .find(|p| parent_dir(p) == dir)
+                    .or_else(|| candidates.first())
+                    .cloned()
+            }
+        }
+    }
+
+    /// 生成指向 `target` 的新链接文本 (替换 `link.span`)，保持原有写法风格
+    pub fn rewrite(&self, source: &str, link: &Link, target: &str) -> String {
+        match link.kind {
+            LinkKind::Markdown => {
+                let rel = relative_path(parent_dir(source), target);
+                if link.angle {
+                    rel
+                } else {
+                    percent_encode(&rel)
+                }
+            }
+            LinkKind::Wiki => {
+                let keep_ext = link.target.to_ascii_lowercase().ends_with(".md");
+                let short = if keep_ext {
+                    target.rsplit('/').next().unwrap_or(target)
+                } else {
+                    stem(target)
+                };
+                let probe = Link {
+                    target: short.to_string(),
+                    ..link.clone()
+                };
+                if !link.target.contains('/')
+                    && self.resolve(source, &probe).as_deref() == Some(target)
+                {
+                    short.to_string()
+                } else if keep_ext {
+                    target.to_string()
+                } else {
+                    strip_md(target).to_string()
+                }
+            }
+        }
+    }
+}
+
+/// 按字节区间替换原文 (区间互不重叠)
+pub fn apply_edits(text: &str, edits: &[((usize, usize), String)]) -> String {
+    let mut edits: Vec<_> = edits.iter().collect();
+    edits.sort_by_key(|(span, _)| span.0);
+    let mut out = String::with_capacity(text.len());
+    let mut cursor = 0;
+    for ((start, end), replacement) in edits {
+        out.push_str(&text[cursor..*start]);
+        out.push_str(replacement);
+        cursor = *end;
+    }
+    out.push_str(&text[cursor..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_and_resolve_links() {
+        let text = "See [[Alpha]] and [[notes/beta|Beta]] or [[gamma#Intro]].\n\
+            ```\n[[fenced]]\n```\n\
+            Read [the plan](../plan%20v2.md#goals), `[[inline]]`, ![img](pic.md) [web](https://x.io/a.md)\n";
+        let links = extract_links(text);
+        let targets: Vec<_> = links.iter().map(|l| l.target.as_str()).collect();
+        assert_eq!(targets, ["Alpha", "notes/beta", "gamma", "../plan v2.md"]);
+        assert_eq!(&text[links[3].span.0..links[3].span.1], "../plan%20v2.md");
+        assert_eq!(links[3].line, 5);
+
+        let resolver = LinkResolver::new([
+            "alpha.md",
+            "notes/alpha.md",
+            "notes/beta.md",
+            "archive/gamma.md",
+            "plan v2.md",
+        ]);
+        let source = "notes/today.md";
+        let resolved: Vec<_> = links
+            .iter()
+            .map(|l| resolver.resolve(source, l).unwrap_or_default())
+            .collect();
+        assert_eq!(
+            resolved,
+            [
+                "notes/alpha.md",
+                "notes/beta.md",
+                "archive/gamma.md",
+                "plan v2.md"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rewrite_links_preserves_style() {
+        let text = "[[beta]] [[notes/beta]] [b](beta.md) [c](<../x y.md>)";
+        let links = extract_links(text);
+        let resolver = LinkResolver::new(["archive/beta.md", "notes/src.md", "z/x y.md"]);
+        let source = "notes/src.md";
+        let edits: Vec<_> = links
+            .iter()
+            .zip([
+                "archive/beta.md",
+                "archive/beta.md",
+                "archive/beta.md",
+                "z/x y.md",
+            ])
+            .map(|(l, t)| (l.span, resolver.rewrite(source, l, t)))
+            .collect();
+        assert_eq!(
+            apply_edits(text, &edits),
+            "[[beta]] [[archive/beta]] [b](../archive/beta.md) [c](<../z/x y.md>)"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+// crates\core\src\markdown
+//! # Markdown 元数据提取
+//!
+//! 从文档正文中提取结构化信息，供搜索索引、属性查询等模块使用。纯文本处理，前后端通用。
+//!
+//! - `tags`: 行内 `#标签` 提取
+//! - `frontmatter`: YAML 属性块解析
+//! - `links`: Wikilink / 相对 Markdown 链接的提取、解析与改写
+//! - `extract_metadata`: 合并两者得到 `DocMetadata`
+
+pub mod frontmatter;
+pub mod links;
+pub mod tags;
+
+pub use tags::extract_tags;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 文档元数据
+///
+/// ## Invariants
+/// - `tags` 小写、去重，包含 frontmatter `tags`/`tag` 与正文行内标签
+/// - `properties` 不含 `tags`/`tag` 键
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocMetadata {
+    pub tags: Vec<String>,
+    pub properties: BTreeMap<String, Vec<String>>,
+}
+
+impl DocMetadata {
+    /// 属性的首个值 (用于比较与排序)
+    pub fn property(&self, key: &str) -> Option<&str> {
+        self.properties
+            .get(key)
+            .and_then(|v| v.first())
+            .map(String::as_str)
+    }
+}
+
+/// 提取文档的标签与属性
+pub fn extract_metadata(text: &str) -> DocMetadata {
+    let (block, body) = frontmatter::split_frontmatter(text);
+    let mut properties = block
+        .map(frontmatter::parse_frontmatter)
+        .unwrap_or_default();
+
+    let mut tags: Vec<String> = Vec::new();
+    for key in ["tags", "tag"] {
+        for value in properties.remove(key).unwrap_or_default() {
+            let tag = value.trim_start_matches('#').to_lowercase();
+            if !tag.is_empty() && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+    for tag in extract_tags(body) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    DocMetadata { tags, properties }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_metadata_merges_tags() {
+        let meta = extract_metadata(
+            "---\ntags: [Work, \"#q2\"]\nstatus: todo\n---\nNotes #work #review\n",
+        );
+        assert_eq!(meta.tags, ["work", "q2", "review"]);
+        assert_eq!(meta.property("status"), Some("todo"));
+        assert!(!meta.properties.contains_key("tags"));
+    }
+}
@@ -0,0 +1,68 @@
+// crates\core\src\markdown
+//! # 行内标签 (Inline Tags)
+//!
+//! 识别正文中的 `#tag` / `#area/sub-tag`。
+//!
+//! ## Invariants
+//! - `#` 须位于行首或空白之后 (排除 URL 片段、`a#b`)
+//! - 标签至少包含一个非数字字符 (排除 `#123` 之类的编号)
+//! - 围栏代码块 (```) 与行内代码内的内容不计入
+//! - 结果统一小写、去重，按首次出现顺序排列
+
+/// 标签允许的字符: 字母数字 (含 CJK)、`_`、`-`、`/`
+fn is_tag_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '/')
+}
+
+/// 提取文档中的全部行内标签 (不含 `#`)
+pub fn extract_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    let mut in_fence = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let mut in_code = false;
+        let mut prev = ' ';
+        let mut chars = line.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if c == '`' {
+                in_code = !in_code;
+            } else if c == '#' && !in_code && prev.is_whitespace() {
+                let rest = &line[i + 1..];
+                let len = rest.find(|c: char| !is_tag_char(c)).unwrap_or(rest.len());
+                let tag = rest[..len].trim_end_matches('/');
+                if tag.chars().any(|c| !c.is_ascii_digit()) && !tag.starts_with('/') {
+                    let tag = tag.to_lowercase();
+                    if !tags.contains(&tag) {
+                        tags.push(tag);
+                    }
+                }
+                while chars.next_if(|(j, _)| *j < i + 1 + len).is_some() {}
+                prev = '#';
+                continue;
+            }
+            prev = c;
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_tags() {
+        let text = "# Heading\n\
+            Plan #Project/Alpha and #写作, see http://x.io/#anchor\n\
+            Issue #42 fixed; a#b is not a tag; `#inline` neither\n\
+            ```\n#fenced\n```\n\
+            #project/alpha again #todo-list";
+        assert_eq!(extract_tags(text), ["project/alpha", "写作", "todo-list"]);
+    }
+}
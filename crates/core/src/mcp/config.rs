@@ -26,6 +26,9 @@ pub enum McpServerConfig {
         retries: Option<u32>,
         #[serde(default)]
         backoff_ms: Option<u64>,
+        /// 同时在途请求上限 (常驻进程串行能力有限)
+        #[serde(default)]
+        max_in_flight: Option<usize>,
     },
     Remote {
         name: String,
@@ -92,4 +95,12 @@ impl McpServerConfig {
             | Self::RemoteSse { backoff_ms, .. } => backoff_ms.unwrap_or(default_ms),
         }
     }
+
+    /// 仅本地常驻进程限制并发；远程服务器返回默认值
+    pub fn max_in_flight(&self, default_max: usize) -> usize {
+        match self {
+            Self::Local { max_in_flight, .. } => max_in_flight.unwrap_or(default_max).max(1),
+            Self::Remote { .. } | Self::RemoteSse { .. } => default_max,
+        }
+    }
 }
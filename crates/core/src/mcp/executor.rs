@@ -1,13 +1,121 @@
 // crates/core/src/mcp/executor.rs
 //! # MCP Executor Trait
 //!
-//! 提供可插拔的 MCP 传输实现（stdio/http）。
+//! 提供可插拔的 MCP 传输实现（stdio/http/sse）。
+//! 传输只需实现 `request` 与通知收集，各 MCP 方法以默认实现构建在其上。
 
-use super::{McpCallResult, McpToolSpec};
+use super::protocol::{
+    MCP_PROTOCOL_VERSION, McpCallResult, McpNotification, McpPromptResult, McpResourceContent,
+    McpServerCapabilities, McpToolSpec, parse_prompts, parse_resource_contents, parse_resources,
+    parse_tools,
+};
+use crate::protocol::{McpPrompt, McpResource, McpSessionInfo};
 use anyhow::Result;
-use serde_json::Value;
+use serde_json::{Value, json};
+
+/// 分页列表的最大页数 (防止服务器返回循环游标)
+const MAX_LIST_PAGES: usize = 32;
 
 pub trait McpExecutor: Send + Sync {
-    fn list_tools(&self) -> Result<Vec<McpToolSpec>>;
-    fn call_tool(&self, name: &str, args: Value) -> Result<McpCallResult>;
+    /// 发送 JSON-RPC 请求并返回 `result`
+    fn request(&self, method: &str, params: Option<Value>) -> Result<Value>;
+
+    /// 发送 JSON-RPC 通知 (无响应)
+    fn notify(&self, _method: &str, _params: Option<Value>) -> Result<()> {
+        Ok(())
+    }
+
+    /// 取出自上次调用以来收到的服务器通知
+    fn take_notifications(&self) -> Vec<McpNotification> {
+        Vec::new()
+    }
+
+    /// 健康检查 (默认发送 `ping`)
+    fn health_check(&self) -> Result<()> {
+        self.request("ping", None)?;
+        Ok(())
+    }
+
+    /// 常驻进程的会话信息 (无会话的传输返回 None)
+    fn session_info(&self) -> Option<McpSessionInfo> {
+        None
+    }
+
+    /// 握手并返回服务器能力
+    fn initialize(&self) -> Result<McpServerCapabilities> {
+        let result = self.request("initialize", Some(initialize_params()))?;
+        self.notify("notifications/initialized", None)?;
+        Ok(McpServerCapabilities::from_initialize(&result))
+    }
+
+    fn list_tools(&self) -> Result<Vec<McpToolSpec>> {
+        list_paged(self, "tools/list", parse_tools)
+    }
+
+    fn call_tool(&self, name: &str, args: Value) -> Result<McpCallResult> {
+        let params = json!({ "name": name, "arguments": args });
+        let result = self.request("tools/call", Some(params))?;
+        Ok(McpCallResult { content: result })
+    }
+
+    fn list_resources(&self) -> Result<Vec<McpResource>> {
+        list_paged(self, "resources/list", parse_resources)
+    }
+
+    fn read_resource(&self, uri: &str) -> Result<Vec<McpResourceContent>> {
+        let result = self.request("resources/read", Some(json!({ "uri": uri })))?;
+        parse_resource_contents(result)
+    }
+
+    fn subscribe_resource(&self, uri: &str) -> Result<()> {
+        self.request("resources/subscribe", Some(json!({ "uri": uri })))?;
+        Ok(())
+    }
+
+    fn unsubscribe_resource(&self, uri: &str) -> Result<()> {
+        self.request("resources/unsubscribe", Some(json!({ "uri": uri })))?;
+        Ok(())
+    }
+
+    fn list_prompts(&self) -> Result<Vec<McpPrompt>> {
+        list_paged(self, "prompts/list", parse_prompts)
+    }
+
+    fn get_prompt(&self, name: &str, args: Value) -> Result<McpPromptResult> {
+        let params = json!({ "name": name, "arguments": args });
+        let result = self.request("prompts/get", Some(params))?;
+        Ok(serde_json::from_value(result)?)
+    }
+}
+
+/// 客户端 `initialize` 参数 (不声明 roots/sampling 等客户端能力)
+pub fn initialize_params() -> Value {
+    json!({
+        "protocolVersion": MCP_PROTOCOL_VERSION,
+        "capabilities": {},
+        "clientInfo": { "name": "deve-note", "version": env!("CARGO_PKG_VERSION") }
+    })
+}
+
+/// 按 `nextCursor` 拉取全部分页
+fn list_paged<E, T>(exec: &E, method: &str, parse: fn(Value) -> Result<Vec<T>>) -> Result<Vec<T>>
+where
+    E: McpExecutor + ?Sized,
+{
+    let mut items = Vec::new();
+    let mut cursor: Option<String> = None;
+    for _ in 0..MAX_LIST_PAGES {
+        let params = cursor.as_ref().map(|c| json!({ "cursor": c }));
+        let result = exec.request(method, params)?;
+        let next = result
+            .get("nextCursor")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        items.extend(parse(result)?);
+        match next {
+            Some(c) if !c.is_empty() => cursor = Some(c),
+            _ => break,
+        }
+    }
+    Ok(items)
 }
@@ -1,34 +1,89 @@
 // crates/core/src/mcp/manager.rs
-//! # MCP Manager (Stub)
+//! # MCP Manager
 //!
-//! 仅提供工具注册与查询入口，避免引入重型 SDK。
-//! 未来可扩展为 Stdio/SSE 客户端。
+//! 持有各服务器的执行器与目录缓存 (工具、资源、提示词) 及协商得到的能力。
+//!
+//! ## Invariants
+//! - 目录缓存只在 `connect` 与收到 `*/list_changed` 通知时刷新
+//! - 每次经执行器调用后都会处理其间收到的通知，再返回结果
+//! - 调用执行器时不持有任何缓存锁
+//! - 健康检查发现进程已重启或从故障中恢复时重新握手，并通知目录变化
 
-use super::{McpCallResult, McpExecutor, McpServerConfig, McpToolSpec};
-use anyhow::{Result, anyhow};
+use super::protocol::{
+    McpNotification, McpPromptResult, McpResourceContent, McpServerCapabilities,
+};
+use super::{McpCallResult, McpExecutor, McpServerConfig, McpServerKind, McpToolSpec};
+use crate::protocol::{McpPrompt, McpPromptInfo, McpResource, McpResourceInfo, McpServerReport};
+use anyhow::{Result, anyhow, bail};
 use serde_json::Value;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+
+/// 单次调用后处理通知的最大轮数 (刷新本身也可能触发通知)
+const MAX_NOTIFICATION_ROUNDS: usize = 4;
 
 #[derive(Debug, Clone)]
 pub enum McpServerStatus {
     Connected,
-    Failed { reason: String },
+    /// 进程已退出，等待退避后重启 (`attempt` 为连续失败次数)
+    Restarting {
+        attempt: u32,
+        reason: String,
+    },
+    Failed {
+        reason: String,
+    },
     NotConfigured,
 }
 
+impl McpServerStatus {
+    /// 状态名 (脚本与状态接口共用)
+    pub fn state_name(&self) -> &'static str {
+        match self {
+            Self::Connected => "connected",
+            Self::Restarting { .. } => "restarting",
+            Self::Failed { .. } => "failed",
+            Self::NotConfigured => "not_configured",
+        }
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Self::Restarting { reason, .. } | Self::Failed { reason } => Some(reason),
+            Self::Connected | Self::NotConfigured => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct McpToolEntry {
     pub server: String,
     pub tool: McpToolSpec,
 }
 
+/// 目录变化事件 (供上层广播给客户端)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum McpEvent {
+    /// 某服务器的工具、资源或提示词列表已刷新
+    CatalogChanged { server: String },
+    /// 已订阅的资源内容发生变化
+    ResourceUpdated { server: String, uri: String },
+}
+
+type EventHook = Box<dyn Fn(&McpEvent) + Send + Sync>;
+
 #[derive(Default)]
 pub struct McpManager {
     servers: HashMap<String, McpServerConfig>,
-    tools: HashMap<String, Vec<McpToolSpec>>,
     executors: HashMap<String, Arc<dyn McpExecutor>>,
-    statuses: HashMap<String, McpServerStatus>,
+    tools: RwLock<HashMap<String, Vec<McpToolSpec>>>,
+    resources: RwLock<HashMap<String, Vec<McpResource>>>,
+    prompts: RwLock<HashMap<String, Vec<McpPrompt>>>,
+    capabilities: RwLock<HashMap<String, McpServerCapabilities>>,
+    statuses: RwLock<HashMap<String, McpServerStatus>>,
+    /// 上次握手时会话的重启次数 (用于发现进程已被替换)
+    generations: RwLock<HashMap<String, u32>>,
+    hooks: RwLock<Vec<EventHook>>,
 }
 
 impl McpManager {
@@ -39,22 +94,153 @@ impl McpManager {
     pub fn register_server(&mut self, cfg: McpServerConfig) {
         let name = cfg.name().to_string();
         self.servers.insert(name.clone(), cfg);
-        self.statuses
-            .entry(name)
-            .or_insert(McpServerStatus::NotConfigured);
+        if let Ok(mut statuses) = self.statuses.write() {
+            statuses
+                .entry(name)
+                .or_insert(McpServerStatus::NotConfigured);
+        }
     }
 
     pub fn register_executor(&mut self, name: &str, exec: Arc<dyn McpExecutor>) {
         self.executors.insert(name.to_string(), exec);
     }
 
-    pub fn set_status(&mut self, name: &str, status: McpServerStatus) {
-        self.statuses.insert(name.to_string(), status);
+    /// 注册目录变化通知
+    pub fn on_event(&self, hook: impl Fn(&McpEvent) + Send + Sync + 'static) {
+        if let Ok(mut hooks) = self.hooks.write() {
+            hooks.push(Box::new(hook));
+        }
+    }
+
+    /// 握手、记录能力并拉取服务器声明支持的全部目录
+    ///
+    /// 握手失败的服务器按旧式处理 (仅工具)；工具列表失败时标记为 `Failed`。
+    pub fn connect(&self, server: &str) -> Result<()> {
+        let exec = self.executor(server)?;
+        let caps = exec.initialize().unwrap_or_else(|e| {
+            tracing::debug!(
+                "MCP initialize failed for {}, assuming tools only: {}",
+                server,
+                e
+            );
+            McpServerCapabilities::legacy()
+        });
+        if let Some(session) = exec.session_info() {
+            write(&self.generations).insert(server.to_string(), session.restarts);
+        }
+        write(&self.capabilities).insert(server.to_string(), caps.clone());
+
+        let result = if caps.tools {
+            self.refresh_tools(server, exec.as_ref())
+        } else {
+            Ok(())
+        };
+        if caps.resources {
+            let _ = self.refresh_resources(server, exec.as_ref());
+        }
+        if caps.prompts {
+            let _ = self.refresh_prompts(server, exec.as_ref());
+        }
+
+        match &result {
+            Ok(()) => self.set_status(server, McpServerStatus::Connected),
+            Err(e) => self.set_status(
+                server,
+                McpServerStatus::Failed {
+                    reason: e.to_string(),
+                },
+            ),
+        }
+        self.drain_notifications(server, exec.as_ref());
+        result
+    }
+
+    /// 健康检查: 失败时记为 `Restarting` (受监管进程) 或 `Failed`；
+    /// 恢复或进程已重启时重新握手并刷新目录
+    pub fn check_health(&self, server: &str) -> Result<()> {
+        let exec = self.executor(server)?;
+        let checked = exec.health_check();
+        let session = exec.session_info();
+        let result = match checked {
+            Ok(()) => {
+                let restarts = session.as_ref().map(|s| s.restarts);
+                let seen = read(&self.generations).get(server).copied();
+                let connected = matches!(self.status(server), Some(McpServerStatus::Connected));
+                if connected && seen == restarts {
+                    Ok(())
+                } else {
+                    tracing::info!("MCP server {} recovered, reconnecting", server);
+                    let result = self.connect(server);
+                    if result.is_ok() {
+                        self.emit(&McpEvent::CatalogChanged {
+                            server: server.to_string(),
+                        });
+                    }
+                    result
+                }
+            }
+            Err(e) => {
+                let reason = e.to_string();
+                let status = match &session {
+                    Some(s) => McpServerStatus::Restarting {
+                        attempt: s.failures.max(1),
+                        reason,
+                    },
+                    None => McpServerStatus::Failed { reason },
+                };
+                self.set_status(server, status);
+                Err(e)
+            }
+        };
+        self.drain_notifications(server, exec.as_ref());
+        result
+    }
+
+    /// 处理两次调用之间服务器主动发来的通知
+    pub fn poll_notifications(&self, server: &str) {
+        if let Ok(exec) = self.executor(server) {
+            self.drain_notifications(server, exec.as_ref());
+        }
+    }
+
+    /// 各服务器的状态、目录规模与会话信息
+    pub fn status_report(&self) -> Vec<McpServerReport> {
+        self.list_servers()
+            .into_iter()
+            .map(|name| {
+                let status = self.status(&name).unwrap_or(McpServerStatus::NotConfigured);
+                let kind = match self.servers.get(&name).map(McpServerConfig::kind) {
+                    Some(McpServerKind::Remote) => "remote",
+                    _ => "local",
+                };
+                McpServerReport {
+                    kind: kind.to_string(),
+                    state: status.state_name().to_string(),
+                    reason: status.reason().map(str::to_string),
+                    protocol_version: self.capabilities(&name).and_then(|c| c.protocol_version),
+                    tools: self.list_tools(&name).len(),
+                    resources: self.list_resources(&name).len(),
+                    prompts: self.list_prompts(&name).len(),
+                    session: self
+                        .executors
+                        .get(&name)
+                        .and_then(|exec| exec.session_info()),
+                    name,
+                }
+            })
+            .collect()
+    }
+
+    pub fn status(&self, name: &str) -> Option<McpServerStatus> {
+        read(&self.statuses).get(name).cloned()
+    }
+
+    pub fn set_status(&self, name: &str, status: McpServerStatus) {
+        write(&self.statuses).insert(name.to_string(), status);
     }
 
     pub fn list_status(&self) -> Vec<(String, McpServerStatus)> {
-        let mut out: Vec<(String, McpServerStatus)> = self
-            .statuses
+        let mut out: Vec<(String, McpServerStatus)> = read(&self.statuses)
             .iter()
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect();
@@ -68,32 +254,287 @@ impl McpManager {
         names
     }
 
-    pub fn set_tools(&mut self, server: &str, tools: Vec<McpToolSpec>) {
-        self.tools.insert(server.to_string(), tools);
+    /// 协商得到的服务器能力 (未连接时为 None)
+    pub fn capabilities(&self, server: &str) -> Option<McpServerCapabilities> {
+        read(&self.capabilities).get(server).cloned()
+    }
+
+    pub fn set_tools(&self, server: &str, tools: Vec<McpToolSpec>) {
+        write(&self.tools).insert(server.to_string(), tools);
     }
 
     pub fn list_tools(&self, server: &str) -> Vec<McpToolSpec> {
-        self.tools.get(server).cloned().unwrap_or_default()
+        read(&self.tools).get(server).cloned().unwrap_or_default()
     }
 
     pub fn list_all_tools(&self) -> Vec<McpToolEntry> {
-        let mut out = Vec::new();
-        for (server, tools) in &self.tools {
-            for tool in tools {
-                out.push(McpToolEntry {
+        let mut out: Vec<McpToolEntry> = read(&self.tools)
+            .iter()
+            .flat_map(|(server, tools)| {
+                tools.iter().map(|tool| McpToolEntry {
                     server: server.clone(),
                     tool: tool.clone(),
-                });
-            }
-        }
+                })
+            })
+            .collect();
+        out.sort_by(|a, b| (&a.server, &a.tool.name).cmp(&(&b.server, &b.tool.name)));
+        out
+    }
+
+    pub fn list_resources(&self, server: &str) -> Vec<McpResource> {
+        read(&self.resources)
+            .get(server)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn list_all_resources(&self) -> Vec<McpResourceInfo> {
+        let mut out: Vec<McpResourceInfo> = read(&self.resources)
+            .iter()
+            .flat_map(|(server, items)| {
+                items.iter().map(|resource| McpResourceInfo {
+                    server: server.clone(),
+                    resource: resource.clone(),
+                })
+            })
+            .collect();
+        out.sort_by(|a, b| (&a.server, &a.resource.uri).cmp(&(&b.server, &b.resource.uri)));
+        out
+    }
+
+    pub fn list_prompts(&self, server: &str) -> Vec<McpPrompt> {
+        read(&self.prompts).get(server).cloned().unwrap_or_default()
+    }
+
+    pub fn list_all_prompts(&self) -> Vec<McpPromptInfo> {
+        let mut out: Vec<McpPromptInfo> = read(&self.prompts)
+            .iter()
+            .flat_map(|(server, items)| {
+                items.iter().map(|prompt| McpPromptInfo {
+                    server: server.clone(),
+                    prompt: prompt.clone(),
+                })
+            })
+            .collect();
+        out.sort_by(|a, b| (&a.server, &a.prompt.name).cmp(&(&b.server, &b.prompt.name)));
         out
     }
 
     pub fn call_tool(&self, server: &str, tool: &str, args: Value) -> Result<McpCallResult> {
-        let exec = self
-            .executors
+        let exec = self.executor(server)?;
+        let start = std::time::Instant::now();
+        let result = exec.call_tool(tool, args);
+        crate::metrics::MCP_CALL_SECONDS.observe(&[server], start.elapsed().as_secs_f64());
+        self.drain_notifications(server, exec.as_ref());
+        result
+    }
+
+    pub fn read_resource(&self, server: &str, uri: &str) -> Result<Vec<McpResourceContent>> {
+        let exec = self.executor(server)?;
+        let result = exec.read_resource(uri);
+        self.drain_notifications(server, exec.as_ref());
+        result
+    }
+
+    /// 订阅资源变化 (服务器须声明 `resources.subscribe`)
+    pub fn subscribe_resource(&self, server: &str, uri: &str) -> Result<()> {
+        if !self
+            .capabilities(server)
+            .is_some_and(|c| c.resources_subscribe)
+        {
+            bail!(
+                "MCP server {} does not support resource subscriptions",
+                server
+            );
+        }
+        let exec = self.executor(server)?;
+        let result = exec.subscribe_resource(uri);
+        self.drain_notifications(server, exec.as_ref());
+        result
+    }
+
+    pub fn unsubscribe_resource(&self, server: &str, uri: &str) -> Result<()> {
+        let exec = self.executor(server)?;
+        let result = exec.unsubscribe_resource(uri);
+        self.drain_notifications(server, exec.as_ref());
+        result
+    }
+
+    pub fn get_prompt(&self, server: &str, name: &str, args: Value) -> Result<McpPromptResult> {
+        let exec = self.executor(server)?;
+        let result = exec.get_prompt(name, args);
+        self.drain_notifications(server, exec.as_ref());
+        result
+    }
+
+    fn executor(&self, server: &str) -> Result<Arc<dyn McpExecutor>> {
+        self.executors
             .get(server)
-            .ok_or_else(|| anyhow!("MCP executor not found: {}", server))?;
-        exec.call_tool(tool, args)
+            .cloned()
+            .ok_or_else(|| anyhow!("MCP executor not found: {}", server))
+    }
+
+    /// 处理执行器缓存的通知: 列表变化时刷新对应缓存，资源更新时转发事件
+    fn drain_notifications(&self, server: &str, exec: &dyn McpExecutor) {
+        for _ in 0..MAX_NOTIFICATION_ROUNDS {
+            let notes = exec.take_notifications();
+            if notes.is_empty() {
+                return;
+            }
+            for note in notes {
+                self.handle_notification(server, exec, &note);
+            }
+        }
+    }
+
+    fn handle_notification(&self, server: &str, exec: &dyn McpExecutor, note: &McpNotification) {
+        let refreshed = match note.method.as_str() {
+            "notifications/tools/list_changed" => self.refresh_tools(server, exec),
+            "notifications/resources/list_changed" => self.refresh_resources(server, exec),
+            "notifications/prompts/list_changed" => self.refresh_prompts(server, exec),
+            "notifications/resources/updated" => {
+                if let Some(uri) = note
+                    .params
+                    .as_ref()
+                    .and_then(|p| p.get("uri"))
+                    .and_then(Value::as_str)
+                {
+                    self.emit(&McpEvent::ResourceUpdated {
+                        server: server.to_string(),
+                        uri: uri.to_string(),
+                    });
+                }
+                return;
+            }
+            other => {
+                tracing::debug!("Ignoring MCP notification {} from {}", other, server);
+                return;
+            }
+        };
+        if refreshed.is_ok() {
+            self.emit(&McpEvent::CatalogChanged {
+                server: server.to_string(),
+            });
+        }
+    }
+
+    fn refresh_tools(&self, server: &str, exec: &dyn McpExecutor) -> Result<()> {
+        let tools = exec.list_tools().inspect_err(|e| {
+            tracing::warn!("MCP list_tools failed for {}: {:?}", server, e);
+        })?;
+        self.set_tools(server, tools);
+        Ok(())
+    }
+
+    fn refresh_resources(&self, server: &str, exec: &dyn McpExecutor) -> Result<()> {
+        let items = exec.list_resources().inspect_err(|e| {
+            tracing::warn!("MCP list_resources failed for {}: {:?}", server, e);
+        })?;
+        write(&self.resources).insert(server.to_string(), items);
+        Ok(())
+    }
+
+    fn refresh_prompts(&self, server: &str, exec: &dyn McpExecutor) -> Result<()> {
+        let items = exec.list_prompts().inspect_err(|e| {
+            tracing::warn!("MCP list_prompts failed for {}: {:?}", server, e);
+        })?;
+        write(&self.prompts).insert(server.to_string(), items);
+        Ok(())
+    }
+
+    fn emit(&self, event: &McpEvent) {
+        for hook in read(&self.hooks).iter() {
+            hook(event);
+        }
+    }
+}
+
+fn read<T>(lock: &RwLock<T>) -> std::sync::RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn write<T>(lock: &RwLock<T>) -> std::sync::RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    /// 内存中的服务器: 调用 `bump` 工具后新增一个工具并发出 list_changed
+    struct StubExecutor {
+        tools: Mutex<Vec<&'static str>>,
+        pending: Mutex<Vec<McpNotification>>,
+    }
+
+    impl McpExecutor for StubExecutor {
+        fn request(&self, method: &str, _params: Option<Value>) -> Result<Value> {
+            match method {
+                "initialize" => Ok(json!({
+                    "protocolVersion": "2025-06-18",
+                    "capabilities": { "tools": { "listChanged": true }, "prompts": {} }
+                })),
+                "tools/list" => {
+                    let tools = self.tools.lock().unwrap();
+                    Ok(
+                        json!({ "tools": tools.iter().map(|n| json!({ "name": n })).collect::<Vec<_>>() }),
+                    )
+                }
+                "prompts/list" => Ok(json!({ "prompts": [{ "name": "summarize" }] })),
+                "tools/call" => {
+                    self.tools.lock().unwrap().push("extra");
+                    self.pending.lock().unwrap().push(McpNotification {
+                        method: "notifications/tools/list_changed".into(),
+                        params: None,
+                    });
+                    Ok(json!({ "content": [] }))
+                }
+                other => bail!("unexpected {}", other),
+            }
+        }
+
+        fn take_notifications(&self) -> Vec<McpNotification> {
+            std::mem::take(&mut *self.pending.lock().unwrap())
+        }
+    }
+
+    #[test]
+    fn test_connect_and_refresh_on_list_changed() {
+        let mut manager = McpManager::new();
+        manager.register_executor(
+            "stub",
+            Arc::new(StubExecutor {
+                tools: Mutex::new(vec!["bump"]),
+                pending: Mutex::new(Vec::new()),
+            }),
+        );
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        manager.on_event(move |e| sink.lock().unwrap().push(e.clone()));
+
+        manager.connect("stub").unwrap();
+        let caps = manager.capabilities("stub").unwrap();
+        assert!(caps.tools_list_changed && caps.prompts && !caps.resources);
+        assert_eq!(manager.list_tools("stub").len(), 1);
+        assert_eq!(manager.list_all_prompts()[0].prompt.name, "summarize");
+        assert!(manager.list_all_resources().is_empty());
+
+        manager.call_tool("stub", "bump", json!({})).unwrap();
+        let names: Vec<String> = manager
+            .list_tools("stub")
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(names, vec!["bump", "extra"]);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![McpEvent::CatalogChanged {
+                server: "stub".into()
+            }]
+        );
+
+        assert!(manager.subscribe_resource("stub", "x://y").is_err());
     }
 }
@@ -1,8 +1,8 @@
 // crates/core/src/mcp/mod.rs
-//! # MCP Stub (Model Context Protocol)
+//! # MCP Client (Model Context Protocol)
 //!
-//! 轻量级 MCP 预留接口：只定义配置与工具元数据，
-//! 运行时调用由上层实现或未来扩展。
+//! 轻量级 MCP 客户端核心：配置、协议元数据、执行器接口与目录管理。
+//! 具体传输 (stdio/http/sse) 由上层实现 `McpExecutor`。
 
 mod config;
 mod executor;
@@ -10,6 +10,10 @@ mod manager;
 mod protocol;
 
 pub use config::{McpServerConfig, McpServerKind};
-pub use executor::McpExecutor;
-pub use manager::{McpManager, McpServerStatus, McpToolEntry};
-pub use protocol::{McpCallResult, McpToolSpec};
+pub use executor::{McpExecutor, initialize_params};
+pub use manager::{McpEvent, McpManager, McpServerStatus, McpToolEntry};
+pub use protocol::{
+    MCP_PROTOCOL_VERSION, McpCallResult, McpNotification, McpPromptMessage, McpPromptResult,
+    McpResourceContent, McpServerCapabilities, McpToolSpec, parse_prompts, parse_resource_contents,
+    parse_resources, parse_tools,
+};
@@ -1,9 +1,17 @@
 // crates/core/src/mcp/protocol.rs
-//! # MCP 协议元数据 (Stub)
+//! # MCP 协议元数据
+//!
+//! 工具/资源/提示词的结构与 `initialize` 协商得到的服务器能力。
+//! 提示词与资源条目定义在 `protocol::mcp`，以便前端共用。
 
+use crate::protocol::{McpPrompt, McpResource};
+use anyhow::{Result, anyhow};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+/// 客户端声明的协议版本
+pub const MCP_PROTOCOL_VERSION: &str = "2025-06-18";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpToolSpec {
     pub name: String,
@@ -16,3 +24,241 @@ pub struct McpToolSpec {
 pub struct McpCallResult {
     pub content: Value,
 }
+
+/// `resources/read` 返回的单段内容 (`text` 与 `blob` 二选一)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpResourceContent {
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Base64 编码的二进制内容
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob: Option<String>,
+}
+
+/// 提示词展开后的单条消息 (`content` 保留原始结构: text/image/resource)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpPromptMessage {
+    pub role: String,
+    pub content: Value,
+}
+
+/// `prompts/get` 结果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct McpPromptResult {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub messages: Vec<McpPromptMessage>,
+}
+
+/// 服务器主动发送的通知 (无 id 的 JSON-RPC 消息)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpNotification {
+    pub method: String,
+    #[serde(default)]
+    pub params: Option<Value>,
+}
+
+impl McpNotification {
+    /// 从 JSON-RPC 消息中识别通知 (有 `method` 且无 `id`)
+    pub fn from_message(msg: &Value) -> Option<Self> {
+        if msg.get("id").is_some() {
+            return None;
+        }
+        let method = msg.get("method")?.as_str()?.to_string();
+        Some(Self {
+            method,
+            params: msg.get("params").cloned(),
+        })
+    }
+}
+
+/// `initialize` 协商得到的服务器能力
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpServerCapabilities {
+    pub protocol_version: Option<String>,
+    pub server_name: Option<String>,
+    pub tools: bool,
+    pub tools_list_changed: bool,
+    pub resources: bool,
+    pub resources_subscribe: bool,
+    pub resources_list_changed: bool,
+    pub prompts: bool,
+    pub prompts_list_changed: bool,
+}
+
+impl McpServerCapabilities {
+    /// 解析 `initialize` 结果
+    pub fn from_initialize(result: &Value) -> Self {
+        let caps = result.get("capabilities");
+        let section = |name: &str| caps.and_then(|c| c.get(name)).filter(|v| !v.is_null());
+        let flag = |name: &str, key: &str| {
+            section(name)
+                .and_then(|s| s.get(key))
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+        };
+        Self {
+            protocol_version: result
+                .get("protocolVersion")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            server_name: result
+                .pointer("/serverInfo/name")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            tools: section("tools").is_some(),
+            tools_list_changed: flag("tools", "listChanged"),
+            resources: section("resources").is_some(),
+            resources_subscribe: flag("resources", "subscribe"),
+            resources_list_changed: flag("resources", "listChanged"),
+            prompts: section("prompts").is_some(),
+            prompts_list_changed: flag("prompts", "listChanged"),
+        }
+    }
+
+    /// 未完成握手的旧式服务器: 仅假定支持工具
+    pub fn legacy() -> Self {
+        Self {
+            tools: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// 解析 `tools/list` 结果 (兼容直接返回数组与 `input_schema` 写法)
+pub fn parse_tools(value: Value) -> Result<Vec<McpToolSpec>> {
+    let tools_val = value.get("tools").cloned().unwrap_or(value);
+    let arr = tools_val
+        .as_array()
+        .ok_or_else(|| anyhow!("Invalid MCP tools"))?;
+
+    let mut tools = Vec::new();
+    for t in arr {
+        let name = t.get("name").and_then(|v| v.as_str()).unwrap_or("");
+        if name.is_empty() {
+            continue;
+        }
+        let desc = t
+            .get("description")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let schema = t
+            .get("inputSchema")
+            .or_else(|| t.get("input_schema"))
+            .cloned()
+            .unwrap_or_else(|| serde_json::json!({"type":"object"}));
+        tools.push(McpToolSpec {
+            name: name.to_string(),
+            description: desc,
+            input_schema: schema,
+        });
+    }
+    Ok(tools)
+}
+
+/// 解析 `resources/list` 结果 (跳过缺少 uri 的条目)
+pub fn parse_resources(value: Value) -> Result<Vec<McpResource>> {
+    parse_list(value, "resources", |r: &McpResource| !r.uri.is_empty())
+}
+
+/// 解析 `prompts/list` 结果 (跳过缺少 name 的条目)
+pub fn parse_prompts(value: Value) -> Result<Vec<McpPrompt>> {
+    parse_list(value, "prompts", |p: &McpPrompt| !p.name.is_empty())
+}
+
+/// 解析 `resources/read` 结果
+pub fn parse_resource_contents(value: Value) -> Result<Vec<McpResourceContent>> {
+    parse_list(value, "contents", |c: &McpResourceContent| {
+        c.text.is_some() || c.blob.is_some()
+    })
+}
+
+fn parse_list<T: for<'de> Deserialize<'de>>(
+    value: Value,
+    key: &str,
+    keep: impl Fn(&T) -> bool,
+) -> Result<Vec<T>> {
+    let items = value.get(key).cloned().unwrap_or(value);
+    let arr = items
+        .as_array()
+        .ok_or_else(|| anyhow!("Invalid MCP {}", key))?;
+    Ok(arr
+        .iter()
+        .filter_map(|item| serde_json::from_value::<T>(item.clone()).ok())
+        .filter(|item| keep(item))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_capabilities_from_initialize() {
+        let caps = McpServerCapabilities::from_initialize(&json!({
+            "protocolVersion": "2025-03-26",
+            "serverInfo": { "name": "stub" },
+            "capabilities": {
+                "tools": { "listChanged": true },
+                "resources": { "subscribe": true },
+                "prompts": {}
+            }
+        }));
+        assert_eq!(caps.protocol_version.as_deref(), Some("2025-03-26"));
+        assert_eq!(caps.server_name.as_deref(), Some("stub"));
+        assert!(caps.tools && caps.tools_list_changed);
+        assert!(caps.resources && caps.resources_subscribe && !caps.resources_list_changed);
+        assert!(caps.prompts && !caps.prompts_list_changed);
+
+        let none = McpServerCapabilities::from_initialize(&json!({ "capabilities": {} }));
+        assert!(!none.tools && !none.resources && !none.prompts);
+    }
+
+    #[test]
+    fn test_parse_lists() {
+        let resources = parse_resources(json!({
+            "resources": [
+                { "uri": "file:///a.txt", "name": "a", "mimeType": "text/plain" },
+                { "name": "missing uri" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].mime_type.as_deref(), Some("text/plain"));
+
+        let prompts = parse_prompts(json!({
+            "prompts": [{
+                "name": "review",
+                "arguments": [{ "name": "code", "required": true }]
+            }]
+        }))
+        .unwrap();
+        assert_eq!(prompts[0].arguments[0].name, "code");
+        assert!(prompts[0].arguments[0].required);
+
+        let contents = parse_resource_contents(json!({
+            "contents": [{ "uri": "file:///a.txt", "text": "hello" }]
+        }))
+        .unwrap();
+        assert_eq!(contents[0].text.as_deref(), Some("hello"));
+
+        assert!(parse_prompts(json!({ "prompts": "nope" })).is_err());
+    }
+
+    #[test]
+    fn test_notification_from_message() {
+        let note = McpNotification::from_message(&json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/tools/list_changed"
+        }))
+        .unwrap();
+        assert_eq!(note.method, "notifications/tools/list_changed");
+        assert!(McpNotification::from_message(&json!({ "id": 1, "method": "ping" })).is_none());
+    }
+}
@@ -0,0 +1,314 @@
+// crates/core/src/metrics.rs
+//! # 性能直方图 (Latency/Size Histograms)
+//!
+//! **架构作用**:
+//! 为核心热路径提供轻量级直方图，供后端 `/metrics` 端点以 OpenMetrics 文本格式导出。
+//! 无外部依赖，全局静态注册，无后台线程。
+//!
+//! **核心功能清单**:
+//! - `Histogram`: 固定桶直方图 (`observe` / `start_timer`)
+//! - `LabeledHistogram`: 按标签值分组的直方图 (基数受限)
+//! - `encode_histograms`: 将全部核心直方图编码为 OpenMetrics 文本
+//!
+//! **Invariants**:
+//! - 桶上界严格递增，编码时输出累计计数并追加 `+Inf`
+//! - 标签组合数超过 `MAX_LABEL_SETS` 后新组合归入 `__other__`
+//!
+//! **类型**: Plugin MAY (可观测性扩展)
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// 单个带标签直方图允许的最大标签组合数
+const MAX_LABEL_SETS: usize = 256;
+
+/// 延迟类指标的默认桶 (秒)
+pub const LATENCY_BUCKETS: &[f64] = &[
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+/// 网络等待类指标的桶 (秒)
+pub const SLOW_BUCKETS: &[f64] = &[0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0, 60.0];
+/// 字节量类指标的桶
+pub const BYTE_BUCKETS: &[f64] = &[
+    256.0, 1024.0, 4096.0, 16384.0, 65536.0, 262144.0, 1048576.0, 4194304.0, 16777216.0,
+];
+
+/// 本地操作追加到账本的耗时
+pub static OP_APPEND_SECONDS: Histogram = Histogram::new(
+    "deve_op_append_seconds",
+    "Time to append a local op to the ledger",
+    LATENCY_BUCKETS,
+);
+/// 快照 + 增量重建文档的耗时
+pub static SNAPSHOT_REBUILD_SECONDS: Histogram = Histogram::new(
+    "deve_snapshot_rebuild_seconds",
+    "Time to rebuild a document from its latest snapshot plus delta ops",
+    LATENCY_BUCKETS,
+);
+/// `state::reconstruct_content` 的耗时
+pub static RECONSTRUCT_SECONDS: Histogram = Histogram::new(
+    "deve_reconstruct_content_seconds",
+    "Time spent replaying ops in reconstruct_content",
+    LATENCY_BUCKETS,
+);
+/// P2P 同步每批次字节数 (按对端与方向)
+pub static SYNC_BYTES: LabeledHistogram = LabeledHistogram::new(
+    "deve_sync_bytes",
+    "Encrypted op bytes per sync batch",
+    &["peer", "direction"],
+    BYTE_BUCKETS,
+);
+/// MCP 工具调用耗时 (按服务器)
+pub static MCP_CALL_SECONDS: LabeledHistogram = LabeledHistogram::new(
+    "deve_mcp_call_seconds",
+    "MCP tool call latency",
+    &["server"],
+    SLOW_BUCKETS,
+);
+/// AI 流式响应首 token 延迟
+pub static AI_TTFT_SECONDS: Histogram = Histogram::new(
+    "deve_ai_time_to_first_token_seconds",
+    "Time from AI stream request to first content or tool-call delta",
+    SLOW_BUCKETS,
+);
+
+#[derive(Debug, Default, Clone)]
+struct HistogramData {
+    /// 各桶 (非累计) 计数，最后一个为 +Inf
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl HistogramData {
+    fn observe(&mut self, buckets: &[f64], value: f64) {
+        if self.counts.is_empty() {
+            self.counts = vec![0; buckets.len() + 1];
+        }
+        let idx = buckets
+            .iter()
+            .position(|b| value <= *b)
+            .unwrap_or(buckets.len());
+        self.counts[idx] += 1;
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// 固定桶直方图
+pub struct Histogram {
+    name: &'static str,
+    help: &'static str,
+    buckets: &'static [f64],
+    data: Mutex<HistogramData>,
+}
+
+impl Histogram {
+    pub const fn new(name: &'static str, help: &'static str, buckets: &'static [f64]) -> Self {
+        Self {
+            name,
+            help,
+            buckets,
+            data: Mutex::new(HistogramData {
+                counts: Vec::new(),
+                sum: 0.0,
+                count: 0,
+            }),
+        }
+    }
+
+    /// 记录一次观测值
+    pub fn observe(&self, value: f64) {
+        self.data
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .observe(self.buckets, value);
+    }
+
+    /// 开始计时，Guard 析构时记录耗时 (秒)
+    pub fn start_timer(&self) -> HistogramTimer<'_> {
+        HistogramTimer {
+            histogram: self,
+            start: Instant::now(),
+        }
+    }
+
+    /// 累计观测次数
+    pub fn count(&self) -> u64 {
+        self.data.lock().unwrap_or_else(|e| e.into_inner()).count
+    }
+
+    /// 以 OpenMetrics 文本格式编码
+    pub fn encode(&self, out: &mut String) {
+        let data = self.data.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        write_header(out, self.name, self.help);
+        write_series(out, self.name, "", self.buckets, &data);
+    }
+}
+
+/// 计时 Guard
+pub struct HistogramTimer<'a> {
+    histogram: &'a Histogram,
+    start: Instant,
+}
+
+impl Drop for HistogramTimer<'_> {
+    fn drop(&mut self) {
+        self.histogram.observe(self.start.elapsed().as_secs_f64());
+    }
+}
+
+/// 按标签值分组的直方图
+pub struct LabeledHistogram {
+    name: &'static str,
+    help: &'static str,
+    labels: &'static [&'static str],
+    buckets: &'static [f64],
+    series: Mutex<BTreeMap<Vec<String>, HistogramData>>,
+}
+
+impl LabeledHistogram {
+    pub const fn new(
+        name: &'static str,
+        help: &'static str,
+        labels: &'static [&'static str],
+        buckets: &'static [f64],
+    ) -> Self {
+        Self {
+            name,
+            help,
+            labels,
+            buckets,
+            series: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// 记录一次观测值
+    ///
+    /// # 前置条件
+    /// - `values.len()` 等于标签名数量
+    pub fn observe(&self, values: &[&str], value: f64) {
+        debug_assert_eq!(values.len(), self.labels.len());
+        let mut series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        let mut key: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+        if !series.contains_key(&key) && series.len() >= MAX_LABEL_SETS {
+            key = vec!["__other__".to_string(); self.labels.len()];
+        }
+        series.entry(key).or_default().observe(self.buckets, value);
+    }
+
+    /// 以 OpenMetrics 文本格式编码
+    pub fn encode(&self, out: &mut String) {
+        let series = self
+            .series
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        write_header(out, self.name, self.help);
+        for (values, data) in &series {
+            let labels = self
+                .labels
+                .iter()
+                .zip(values)
+                .map(|(k, v)| format!("{}=\"{}\"", k, escape_label(v)))
+                .collect::<Vec<_>>()
+                .join(",");
+            write_series(out, self.name, &labels, self.buckets, data);
+        }
+    }
+}
+
+/// 编码全部核心直方图
+pub fn encode_histograms(out: &mut String) {
+    OP_APPEND_SECONDS.encode(out);
+    SNAPSHOT_REBUILD_SECONDS.encode(out);
+    RECONSTRUCT_SECONDS.encode(out);
+    SYNC_BYTES.encode(out);
+    MCP_CALL_SECONDS.encode(out);
+    AI_TTFT_SECONDS.encode(out);
+}
+
+/// 编码单值指标 (gauge / counter)
+///
+/// counter 类型按 OpenMetrics 规范自动追加 `_total` 后缀。
+pub fn encode_scalar(out: &mut String, name: &str, kind: &str, help: &str, value: f64) {
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let suffix = if kind == "counter" { "_total" } else { "" };
+    let _ = writeln!(out, "{}{} {}", name, suffix, value);
+}
+
+fn write_header(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# TYPE {} histogram", name);
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+}
+
+fn write_series(out: &mut String, name: &str, labels: &str, buckets: &[f64], data: &HistogramData) {
+    let sep = if labels.is_empty() { "" } else { "," };
+    let mut cumulative = 0u64;
+    for (i, bound) in buckets.iter().enumerate() {
+        cumulative += data.counts.get(i).copied().unwrap_or(0);
+        let _ = writeln!(
+            out,
+            "{}_bucket{{{}{}le=\"{}\"}} {}",
+            name, labels, sep, bound, cumulative
+        );
+    }
+    let _ = writeln!(
+        out,
+        "{}_bucket{{{}{}le=\"+Inf\"}} {}",
+        name, labels, sep, data.count
+    );
+    let braces = if labels.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", labels)
+    };
+    let _ = writeln!(out, "{}_sum{} {}", name, braces, data.sum);
+    let _ = writeln!(out, "{}_count{} {}", name, braces, data.count);
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_cumulative_buckets() {
+        static H: Histogram = Histogram::new("t_seconds", "test", &[0.1, 1.0]);
+        H.observe(0.05);
+        H.observe(0.5);
+        H.observe(5.0);
+
+        let mut out = String::new();
+        H.encode(&mut out);
+        assert!(out.contains("# TYPE t_seconds histogram"));
+        assert!(out.contains("t_seconds_bucket{le=\"0.1\"} 1"));
+        assert!(out.contains("t_seconds_bucket{le=\"1\"} 2"));
+        assert!(out.contains("t_seconds_bucket{le=\"+Inf\"} 3"));
+        assert!(out.contains("t_seconds_count 3"));
+    }
+
+    #[test]
+    fn test_labeled_histogram_escapes_and_caps() {
+        static L: LabeledHistogram = LabeledHistogram::new("t_bytes", "test", &["peer"], &[10.0]);
+        L.observe(&["a\"b"], 3.0);
+        for i in 0..(MAX_LABEL_SETS + 5) {
+            L.observe(&[&format!("p{}", i)], 20.0);
+        }
+
+        let mut out = String::new();
+        L.encode(&mut out);
+        assert!(out.contains("t_bytes_bucket{peer=\"a\\\"b\",le=\"10\"} 1"));
+        assert!(out.contains("t_bytes_count{peer=\"__other__\"}"));
+        assert!(L.series.lock().unwrap().len() <= MAX_LABEL_SETS + 1);
+    }
+}
@@ -146,6 +146,8 @@ impl fmt::Display for NodeId {
 pub enum NodeKind {
     File,
     Dir,
+    /// 非 Markdown 附件 (内容存于 Blob Store，`doc_id` 为 None)
+    Attachment,
 }
 
 /// 节点元数据
@@ -169,10 +171,14 @@ pub struct NodeMeta {
 /// 2. 索引使用 `u32` 代替 `usize` (UTF-16 code unit 索引)：
 ///    - 节省 8 字节/操作 (64-bit 平台)
 ///    - 4GB 文档大小限制对文本编辑器来说是无限的
+///
+/// 3. `Crdt` 为序列 CRDT 文档模型的操作 (见 `state::crdt`)，按稳定字符标识定位，
+///    供编辑器使用前经 `DocState::apply_effects` 转换为位置操作。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Op {
     Insert { pos: u32, content: SmolStr },
     Delete { pos: u32, len: u32 },
+    Crdt(crate::state::crdt::CrdtOp),
 }
 
 /// 账本条目 (Ledger Entry)
@@ -0,0 +1,411 @@
+// crates\core\src\plugin\events.rs
+//! # Plugin Event Bus (插件事件总线)
+//!
+//! **架构作用**:
+//! 将账本、版本控制与同步层的事件分发给在清单中订阅的插件。
+//!
+//! **核心功能清单**:
+//! - `PluginEvent`: 事件及其载荷 (以 JSON 对象传给处理函数，`type` 字段为事件名)。
+//! - `EventSubscription`: 清单 `events` 字段中的订阅项。
+//! - `EventBus::emit`: 后台线程异步分发，不阻塞请求路径。
+//! - `EventBus::check`: 同步分发可否决事件 (`before_commit`)，返回首个拒绝。
+//!
+//! **超时**:
+//! 每个处理函数在独立线程中执行，超时后不再等待其结果并取消调用
+//! (Rhai 在下一次进度检查时终止；阻塞中的宿主函数须先返回)。
+//! 出错或超时的处理函数默认视为放行，避免一个插件的故障阻塞提交；
+//! 订阅项设置 `fail_closed` 时改为视为拒绝。
+//!
+//! **类型**: Core MUST (核心必选)
+
+use crate::models::DocId;
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::plugin::registry::{PluginRegistry, PluginSet};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::plugin::runtime::cancel::{CancelScope, CancelToken};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::{Arc, mpsc};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+
+/// 处理函数默认超时 (毫秒)
+pub const DEFAULT_TIMEOUT_MS: u64 = 5_000;
+
+/// 可订阅的事件名
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    DocOpened,
+    DocSaved,
+    FileCreated,
+    FileRenamed,
+    BeforeCommit,
+    CommitCreated,
+    PeerMerged,
+    ServerStart,
+}
+
+/// 插件事件
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PluginEvent {
+    /// 客户端打开了文档
+    DocOpened {
+        doc_id: DocId,
+        path: String,
+    },
+    /// 编辑已写入 Vault
+    DocSaved {
+        doc_id: DocId,
+        path: String,
+    },
+    FileCreated {
+        path: String,
+    },
+    FileRenamed {
+        from: String,
+        to: String,
+    },
+    /// 创建提交之前；处理函数返回 `#{ reject: "原因" }` 可否决提交
+    BeforeCommit {
+        message: String,
+    },
+    CommitCreated {
+        commit_id: String,
+        message: String,
+    },
+    /// 已合并来自对端的变更
+    PeerMerged {
+        peer_id: String,
+        doc_id: DocId,
+    },
+    ServerStart,
+}
+
+impl PluginEvent {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Self::DocOpened { .. } => EventKind::DocOpened,
+            Self::DocSaved { .. } => EventKind::DocSaved,
+            Self::FileCreated { .. } => EventKind::FileCreated,
+            Self::FileRenamed { .. } => EventKind::FileRenamed,
+            Self::BeforeCommit { .. } => EventKind::BeforeCommit,
+            Self::CommitCreated { .. } => EventKind::CommitCreated,
+            Self::PeerMerged { .. } => EventKind::PeerMerged,
+            Self::ServerStart => EventKind::ServerStart,
+        }
+    }
+}
+
+/// 清单中的事件订阅
+///
+/// 例: `{"event": "before_commit", "handler": "lint_message", "timeout_ms": 2000}`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventSubscription {
+    pub event: EventKind,
+    /// 插件内的处理函数名，以事件对象为唯一参数
+    pub handler: String,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// 处理函数出错或超时时视为拒绝 (仅对可否决事件有效，默认放行)
+    #[serde(default)]
+    pub fail_closed: bool,
+}
+
+/// 处理函数对可否决事件的拒绝
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rejection {
+    pub plugin_id: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Rejected by plugin '{}': {}",
+            self.plugin_id, self.reason
+        )
+    }
+}
+
+/// 插件事件总线
+///
+/// **Invariant**: `emit` 的事件按发送顺序分发；队列中连续的相同事件合并为一次
+/// (例如连续编辑产生的 `doc_saved`)。每次分发使用注册表的当前快照，热重载后立即生效。
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone)]
+pub struct EventBus {
+    registry: Arc<PluginRegistry>,
+    tx: mpsc::Sender<PluginEvent>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl EventBus {
+    /// 创建总线并启动分发线程
+    pub fn new(registry: Arc<PluginRegistry>) -> Self {
+        let (tx, rx) = mpsc::channel::<PluginEvent>();
+        let worker_registry = registry.clone();
+        std::thread::Builder::new()
+            .name("plugin-events".into())
+            .spawn(move || {
+                while let Ok(first) = rx.recv() {
+                    let mut batch = vec![first];
+                    for event in rx.try_iter() {
+                        if batch.last() != Some(&event) {
+                            batch.push(event);
+                        }
+                    }
+                    for event in &batch {
+                        let worker_plugins = worker_registry.snapshot();
+                        for (index, sub) in subscribers(&worker_plugins, event.kind()) {
+                            if let Err(e) = run_handler(&worker_plugins, index, &sub, event) {
+                                tracing::warn!(
+                                    "Plugin '{}' handler '{}' failed: {}",
+                                    worker_plugins[index].manifest().id,
+                                    sub.handler,
+                                    e
+                                );
+                            }
+                        }
+                    }
+                }
+            })
+            .expect("Failed to spawn plugin event thread");
+        Self { registry, tx }
+    }
+
+    /// 是否有插件订阅该事件
+    pub fn has_subscribers(&self, kind: EventKind) -> bool {
+        !subscribers(&self.registry.snapshot(), kind).is_empty()
+    }
+
+    /// 异步分发事件 (无订阅者时直接返回)
+    pub fn emit(&self, event: PluginEvent) {
+        if !self.has_subscribers(event.kind()) {
+            return;
+        }
+        let _ = self.tx.send(event);
+    }
+
+    /// 同步分发事件，返回首个拒绝 (阻塞至多各处理函数的超时之和)
+    pub fn check(&self, event: &PluginEvent) -> Option<Rejection> {
+        let plugins = self.registry.snapshot();
+        for (index, sub) in subscribers(&plugins, event.kind()) {
+            let plugin_id = &plugins[index].manifest().id;
+            match run_handler(&plugins, index, &sub, event) {
+                Ok(result) => {
+                    if let Some(reason) = result.get("reject").and_then(|r| r.as_str()) {
+                        return Some(Rejection {
+                            plugin_id: plugin_id.clone(),
+                            reason: reason.to_string(),
+                        });
+                    }
+                }
+                Err(e) if sub.fail_closed => {
+                    tracing::warn!(
+                        "Plugin '{}' handler '{}' failed, rejecting: {}",
+                        plugin_id,
+                        sub.handler,
+                        e
+                    );
+                    return Some(Rejection {
+                        plugin_id: plugin_id.clone(),
+                        reason: format!("handler failed: {}", e),
+                    });
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Plugin '{}' handler '{}' failed, allowing: {}",
+                        plugin_id,
+                        sub.handler,
+                        e
+                    );
+                }
+            }
+        }
+        None
+    }
+}
+
+/// 订阅该事件的 (插件下标, 订阅项)
+#[cfg(not(target_arch = "wasm32"))]
+fn subscribers(plugins: &PluginSet, kind: EventKind) -> Vec<(usize, EventSubscription)> {
+    plugins
+        .iter()
+        .enumerate()
+        .flat_map(|(index, plugin)| {
+            plugin
+                .manifest()
+                .events
+                .iter()
+                .filter(|sub| sub.event == kind)
+                .map(move |sub| (index, sub.clone()))
+        })
+        .collect()
+}
+
+/// 在独立线程中执行处理函数并等待至超时
+#[cfg(not(target_arch = "wasm32"))]
+fn run_handler(
+    plugins: &PluginSet,
+    index: usize,
+    sub: &EventSubscription,
+    event: &PluginEvent,
+) -> anyhow::Result<serde_json::Value> {
+    let arg = rhai::serde::to_dynamic(event).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let timeout = Duration::from_millis(sub.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS));
+    let (tx, rx) = mpsc::channel();
+    let plugins = plugins.clone();
+    let handler = sub.handler.clone();
+    let token = CancelToken::new();
+    let call_token = token.clone();
+    std::thread::spawn(move || {
+        let _cancel = CancelScope::new(call_token);
+        let _ = tx.send(plugins[index].call(&handler, vec![arg]));
+    });
+    let result = rx.recv_timeout(timeout).map_err(|_| {
+        token.cancel();
+        anyhow::anyhow!("timed out after {:?}", timeout)
+    })??;
+    Ok(rhai::serde::from_dynamic(&result).unwrap_or(serde_json::Value::Null))
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::plugin::manifest::{Capability, PluginManifest};
+    use crate::plugin::runtime::{PluginRuntime, RhaiRuntime};
+    use std::path::PathBuf;
+    use std::time::Instant;
+
+    fn plugin(
+        id: &str,
+        events: Vec<EventSubscription>,
+        caps: Capability,
+        script: &str,
+    ) -> Box<dyn PluginRuntime> {
+        let manifest = PluginManifest {
+            id: id.into(),
+            name: id.into(),
+            version: "0.1".into(),
+            entry: "main.rhai".into(),
+            engine: Default::default(),
+            events,
+            limits: Default::default(),
+            ui: Default::default(),
+            capabilities: caps,
+        };
+        let mut rt = RhaiRuntime::new(manifest.clone(), PathBuf::from("."));
+        rt.load(manifest, script).unwrap();
+        Box::new(rt)
+    }
+
+    fn sub(event: EventKind, handler: &str, timeout_ms: Option<u64>) -> EventSubscription {
+        EventSubscription {
+            event,
+            handler: handler.into(),
+            timeout_ms,
+            fail_closed: false,
+        }
+    }
+
+    #[test]
+    fn test_before_commit_veto() {
+        let lint = plugin(
+            "lint",
+            vec![sub(EventKind::BeforeCommit, "lint", None)],
+            Default::default(),
+            r#"fn lint(e) { if e.message.len() < 5 { #{ reject: "message too short" } } }"#,
+        );
+        let bus = EventBus::new(Arc::new(PluginRegistry::from_plugins(vec![lint])));
+
+        let rejection = bus
+            .check(&PluginEvent::BeforeCommit {
+                message: "wip".into(),
+            })
+            .unwrap();
+        assert_eq!(rejection.plugin_id, "lint");
+        assert_eq!(rejection.reason, "message too short");
+        assert!(
+            bus.check(&PluginEvent::BeforeCommit {
+                message: "Fix sync".into()
+            })
+            .is_none()
+        );
+        assert!(!bus.has_subscribers(EventKind::DocSaved));
+    }
+
+    #[test]
+    fn test_emit_runs_off_caller() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("saved.txt");
+        let caps = Capability {
+            allow_fs_write: vec![dir.path().to_path_buf()],
+            ..Default::default()
+        };
+        let script = format!(
+            r#"fn on_saved(e) {{ fs_write("{}", e.path); }}"#,
+            out.to_str().unwrap().replace('\\', "\\\\")
+        );
+        let saver = plugin(
+            "saver",
+            vec![sub(EventKind::DocSaved, "on_saved", None)],
+            caps,
+            &script,
+        );
+        let bus = EventBus::new(Arc::new(PluginRegistry::from_plugins(vec![saver])));
+        bus.emit(PluginEvent::DocSaved {
+            doc_id: DocId::new(),
+            path: "notes/a.md".into(),
+        });
+
+        let start = Instant::now();
+        while !out.exists() && start.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), "notes/a.md");
+    }
+
+    #[test]
+    fn test_handler_timeout_allows() {
+        let slow = plugin(
+            "slow",
+            vec![sub(EventKind::BeforeCommit, "spin", Some(50))],
+            Default::default(),
+            r#"fn spin(e) { let n = 0; for i in 0..20000000 { n += i; } #{ reject: "late" } }"#,
+        );
+        let bus = EventBus::new(Arc::new(PluginRegistry::from_plugins(vec![slow])));
+        let start = Instant::now();
+        assert!(
+            bus.check(&PluginEvent::BeforeCommit {
+                message: "x".into()
+            })
+            .is_none()
+        );
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_fail_closed_timeout_rejects() {
+        let slow = plugin(
+            "slow",
+            vec![EventSubscription {
+                fail_closed: true,
+                ..sub(EventKind::BeforeCommit, "spin", Some(50))
+            }],
+            Default::default(),
+            r#"fn spin(e) { let n = 0; for i in 0..20000000 { n += i; } }"#,
+        );
+        let bus = EventBus::new(Arc::new(PluginRegistry::from_plugins(vec![slow])));
+        let rejection = bus
+            .check(&PluginEvent::BeforeCommit {
+                message: "x".into(),
+            })
+            .unwrap();
+        assert_eq!(rejection.plugin_id, "slow");
+        assert!(rejection.reason.starts_with("handler failed"));
+    }
+}
@@ -0,0 +1,301 @@
+// crates\core\src\plugin\install.rs
+//! # Plugin Installation (插件安装)
+//!
+//! **架构作用**:
+//! 管理插件目录中已安装的插件包与启用状态，供 `deve plugin` 子命令与加载器使用。
+//!
+//! **核心功能清单**:
+//! - `PluginState`: 插件目录下的 `.state.json` (禁用列表与安装记录)。
+//! - `PluginInstaller`: 安装、卸载、启用、禁用与列出插件。
+//!
+//! ## Invariants
+//! - 通过包安装的插件，只有清单能力与安装时批准的能力一致、且目录内容摘要与
+//!   安装时的包摘要一致才会加载；目录被改动后需重新安装
+//! - 直接放入插件目录的插件 (无安装记录) 视为本地开发插件，照常加载
+//!
+//! **类型**: Core MUST (核心必选)
+
+use crate::plugin::manifest::{Capability, PluginManifest};
+use crate::plugin::package::{PluginPackage, TrustedPublisher};
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// 插件目录下的状态文件名
+pub const STATE_FILE: &str = ".state.json";
+
+/// 安装记录
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InstallRecord {
+    pub version: String,
+    /// 发布者名称 (来自受信任列表)
+    pub publisher: String,
+    /// 发布者公钥 (hex)
+    pub publisher_key: String,
+    /// 安装时批准的能力
+    pub capabilities: Capability,
+    /// 包摘要 (hex，见 `PluginPackage::digest`)；旧版记录为空时不校验
+    #[serde(default)]
+    pub digest: String,
+}
+
+/// 插件目录状态
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct PluginState {
+    pub disabled: BTreeSet<String>,
+    pub installed: BTreeMap<String, InstallRecord>,
+}
+
+impl PluginState {
+    /// 读取状态，文件不存在或无法解析时为空
+    pub fn load(plugin_dir: &Path) -> Self {
+        let path = plugin_dir.join(STATE_FILE);
+        match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                tracing::warn!("Invalid {:?}, ignoring: {}", path, e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, plugin_dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(plugin_dir)?;
+        std::fs::write(
+            plugin_dir.join(STATE_FILE),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        Ok(())
+    }
+
+    /// 插件是否可以加载 (未禁用，且能力与包摘要均与安装时一致)
+    ///
+    /// `dir` 为插件目录，仅对有安装记录的插件读取并计算摘要。
+    pub fn check_loadable(&self, manifest: &PluginManifest, dir: &Path) -> Result<()> {
+        if self.disabled.contains(&manifest.id) {
+            bail!("Plugin '{}' is disabled", manifest.id);
+        }
+        let Some(record) = self.installed.get(&manifest.id) else {
+            return Ok(());
+        };
+        if record.capabilities != manifest.capabilities {
+            bail!(
+                "Plugin '{}' requests capabilities that were not approved at install time",
+                manifest.id
+            );
+        }
+        if !record.digest.is_empty()
+            && hex::encode(PluginPackage::from_dir(dir)?.digest()) != record.digest
+        {
+            bail!(
+                "Plugin '{}' files were modified after install; reinstall the package",
+                manifest.id
+            );
+        }
+        Ok(())
+    }
+}
+
+/// `list` 的条目
+#[derive(Debug, Clone)]
+pub struct InstalledPlugin {
+    pub manifest: PluginManifest,
+    pub enabled: bool,
+    /// 通过包安装时的记录 (本地开发插件为 `None`)
+    pub record: Option<InstallRecord>,
+}
+
+/// 插件目录管理
+pub struct PluginInstaller {
+    plugin_dir: PathBuf,
+}
+
+impl PluginInstaller {
+    pub fn new(plugin_dir: PathBuf) -> Self {
+        Self { plugin_dir }
+    }
+
+    /// 安装已校验的包 (覆盖同 ID 的旧版本)，并记录批准的能力
+    ///
+    /// # 前置条件
+    /// - `package.verify` 已通过，且用户已批准 `package.manifest.capabilities`
+    pub fn install(&self, package: &PluginPackage, publisher: &TrustedPublisher) -> Result<()> {
+        let id = &package.manifest.id;
+        if !is_valid_id(id) {
+            bail!("Invalid plugin id '{}'", id);
+        }
+        std::fs::create_dir_all(&self.plugin_dir)?;
+
+        // 先解压到临时目录再替换，避免热重载读到半写入的插件
+        let staging = self.plugin_dir.join(format!(".{}.installing", id));
+        if staging.exists() {
+            std::fs::remove_dir_all(&staging)?;
+        }
+        package.extract_to(&staging)?;
+        let target = self.plugin_dir.join(id);
+        if target.exists() {
+            std::fs::remove_dir_all(&target)?;
+        }
+        std::fs::rename(&staging, &target)
+            .with_context(|| format!("Failed to move plugin into {:?}", target))?;
+
+        let mut state = PluginState::load(&self.plugin_dir);
+        state.installed.insert(
+            id.clone(),
+            InstallRecord {
+                version: package.manifest.version.clone(),
+                publisher: publisher.name.clone(),
+                publisher_key: publisher.public_key.clone(),
+                capabilities: package.manifest.capabilities.clone(),
+                digest: hex::encode(package.digest()),
+            },
+        );
+        state.save(&self.plugin_dir)
+    }
+
+    /// 卸载插件 (删除目录与记录)
+    pub fn uninstall(&self, id: &str) -> Result<()> {
+        let dir = self.find_dir(id)?;
+        std::fs::remove_dir_all(&dir)?;
+        let mut state = PluginState::load(&self.plugin_dir);
+        state.installed.remove(id);
+        state.disabled.remove(id);
+        state.save(&self.plugin_dir)
+    }
+
+    /// 启用或禁用插件
+    pub fn set_enabled(&self, id: &str, enabled: bool) -> Result<()> {
+        self.find_dir(id)?;
+        let mut state = PluginState::load(&self.plugin_dir);
+        if enabled {
+            state.disabled.remove(id);
+        } else {
+            state.disabled.insert(id.to_string());
+        }
+        state.save(&self.plugin_dir)
+    }
+
+    /// 列出插件目录中的全部插件
+    pub fn list(&self) -> Result<Vec<InstalledPlugin>> {
+        let state = PluginState::load(&self.plugin_dir);
+        let mut plugins: Vec<InstalledPlugin> = self
+            .manifests()?
+            .into_iter()
+            .map(|(_, manifest)| InstalledPlugin {
+                enabled: !state.disabled.contains(&manifest.id),
+                record: state.installed.get(&manifest.id).cloned(),
+                manifest,
+            })
+            .collect();
+        plugins.sort_by(|a, b| a.manifest.id.cmp(&b.manifest.id));
+        Ok(plugins)
+    }
+
+    /// 按清单 ID 查找插件目录 (目录名不必等于 ID)
+    fn find_dir(&self, id: &str) -> Result<PathBuf> {
+        self.manifests()?
+            .into_iter()
+            .find(|(_, m)| m.id == id)
+            .map(|(dir, _)| dir)
+            .ok_or_else(|| anyhow::anyhow!("Plugin '{}' is not installed", id))
+    }
+
+    fn manifests(&self) -> Result<Vec<(PathBuf, PluginManifest)>> {
+        let mut found = Vec::new();
+        if !self.plugin_dir.exists() {
+            return Ok(found);
+        }
+        for entry in std::fs::read_dir(&self.plugin_dir)? {
+            let path = entry?.path();
+            if !path.is_dir() || is_hidden(&path) {
+                continue;
+            }
+            if let Ok(text) = std::fs::read_to_string(path.join("manifest.json"))
+                && let Ok(manifest) = serde_json::from_str::<PluginManifest>(&text)
+            {
+                found.push((path, manifest));
+            }
+        }
+        Ok(found)
+    }
+}
+
+/// 以 `.` 开头的条目 (状态文件、安装中的临时目录) 不是插件
+pub(crate) fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+}
+
+/// 插件 ID 用作目录名: 仅允许字母、数字、`-`、`_`、`.` (不以 `.` 开头)
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && !id.starts_with('.')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::IdentityKeyPair;
+
+    fn package(caps: &str) -> PluginPackage {
+        let src = tempfile::tempdir().unwrap();
+        std::fs::write(
+            src.path().join("manifest.json"),
+            format!(
+                r#"{{"id": "hello", "name": "Hello", "version": "1.0.0", "entry": "main.rhai", "capabilities": {caps}}}"#
+            ),
+        )
+        .unwrap();
+        std::fs::write(src.path().join("main.rhai"), "fn hi() { 1 }").unwrap();
+        let mut pkg = PluginPackage::from_dir(src.path()).unwrap();
+        pkg.sign(&IdentityKeyPair::generate());
+        pkg
+    }
+
+    #[test]
+    fn test_install_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let installer = PluginInstaller::new(dir.path().to_path_buf());
+        let publisher = TrustedPublisher {
+            name: "acme".into(),
+            public_key: "00".into(),
+        };
+        let pkg = package(r#"{"allow_env": ["HOME"]}"#);
+        installer.install(&pkg, &publisher).unwrap();
+
+        let list = installer.list().unwrap();
+        assert_eq!(list.len(), 1);
+        assert!(list[0].enabled);
+        assert_eq!(list[0].record.as_ref().unwrap().publisher, "acme");
+        let plugin_dir = dir.path().join("hello");
+        let state = PluginState::load(dir.path());
+        assert!(state.check_loadable(&pkg.manifest, &plugin_dir).is_ok());
+
+        installer.set_enabled("hello", false).unwrap();
+        let state = PluginState::load(dir.path());
+        assert!(state.check_loadable(&pkg.manifest, &plugin_dir).is_err());
+        installer.set_enabled("hello", true).unwrap();
+
+        // 目录被改动后请求更多权限: 拒绝加载
+        let escalated = package(r#"{"allow_env": ["HOME", "AWS_SECRET"]}"#);
+        let state = PluginState::load(dir.path());
+        assert!(
+            state
+                .check_loadable(&escalated.manifest, &plugin_dir)
+                .is_err()
+        );
+
+        // 能力不变但脚本被改动: 拒绝加载
+        std::fs::write(plugin_dir.join("main.rhai"), "fn hi() { 2 }").unwrap();
+        assert!(state.check_loadable(&pkg.manifest, &plugin_dir).is_err());
+
+        installer.uninstall("hello").unwrap();
+        assert!(installer.list().unwrap().is_empty());
+        assert!(installer.uninstall("hello").is_err());
+    }
+}
@@ -0,0 +1,173 @@
+// crates\core\src\plugin\kv.rs
+//! # Plugin Storage (插件键值存储)
+//!
+//! **架构作用**:
+//! 为插件提供持久化的私有状态，按插件 ID 分区存放在独立的 redb 文件中
+//! (不进入 Ledger，也不随仓库同步)。
+//!
+//! **核心功能清单**:
+//! - `PluginKvStore`: `get` / `set` / `delete` / `keys`，值为 JSON。
+//!
+//! ## Invariants
+//! - 插件只能访问自身 ID 下的键 (宿主函数绑定清单 ID)
+//! - 键不超过 `MAX_KEY_BYTES`，值序列化后不超过 `MAX_VALUE_BYTES`
+//!
+//! **类型**: Core MUST (核心必选)
+
+use anyhow::{Result, bail};
+use redb::{Database, TableDefinition};
+use std::path::Path;
+
+// (Plugin ID, Key) -> JSON Value (Bytes)
+const PLUGIN_KV: TableDefinition<(&str, &str), &[u8]> = TableDefinition::new("plugin_kv");
+
+/// 单个键的最大字节数
+pub const MAX_KEY_BYTES: usize = 256;
+/// 单个值 (JSON) 的最大字节数
+pub const MAX_VALUE_BYTES: usize = 1024 * 1024;
+
+/// 插件键值存储
+pub struct PluginKvStore {
+    db: Database,
+}
+
+impl PluginKvStore {
+    /// 打开或创建存储文件
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let db = Database::create(path)?;
+        let txn = db.begin_write()?;
+        txn.open_table(PLUGIN_KV)?;
+        txn.commit()?;
+        Ok(Self { db })
+    }
+
+    pub fn get(&self, plugin_id: &str, key: &str) -> Result<Option<serde_json::Value>> {
+        let txn = self.db.begin_read()?;
+        let table = txn.open_table(PLUGIN_KV)?;
+        match table.get((plugin_id, key))? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(bytes.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn set(&self, plugin_id: &str, key: &str, value: &serde_json::Value) -> Result<()> {
+        if key.is_empty() || key.len() > MAX_KEY_BYTES {
+            bail!("Key must be 1..={} bytes", MAX_KEY_BYTES);
+        }
+        let bytes = serde_json::to_vec(value)?;
+        if bytes.len() > MAX_VALUE_BYTES {
+            bail!("Value exceeds {} bytes", MAX_VALUE_BYTES);
+        }
+        let txn = self.db.begin_write()?;
+        {
+            let mut table = txn.open_table(PLUGIN_KV)?;
+            table.insert((plugin_id, key), bytes.as_slice())?;
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// 删除键，返回键是否存在
+    pub fn delete(&self, plugin_id: &str, key: &str) -> Result<bool> {
+        let txn = self.db.begin_write()?;
+        let existed = {
+            let mut table = txn.open_table(PLUGIN_KV)?;
+            table.remove((plugin_id, key))?.is_some()
+        };
+        txn.commit()?;
+        Ok(existed)
+    }
+
+    /// 列出以 `prefix` 开头的键 (按字典序)
+    pub fn keys(&self, plugin_id: &str, prefix: &str) -> Result<Vec<String>> {
+        let txn = self.db.begin_read()?;
+        let table = txn.open_table(PLUGIN_KV)?;
+        let mut keys = Vec::new();
+        for item in table.range((plugin_id, prefix)..)? {
+            let (k, _) = item?;
+            let (id, key) = k.value();
+            if id != plugin_id || !key.starts_with(prefix) {
+                break;
+            }
+            keys.push(key.to_string());
+        }
+        Ok(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_kv_namespaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plugin_kv.redb");
+        {
+            let store = PluginKvStore::open(&path).unwrap();
+            store.set("a", "count", &json!(1)).unwrap();
+            store.set("a", "todo:1", &json!({"done": false})).unwrap();
+            store.set("a", "todo:2", &json!("x")).unwrap();
+            store.set("b", "count", &json!(99)).unwrap();
+            assert!(store.set("a", "", &json!(1)).is_err());
+        }
+
+        let store = PluginKvStore::open(&path).unwrap();
+        assert_eq!(store.get("a", "count").unwrap(), Some(json!(1)));
+        assert_eq!(store.get("b", "count").unwrap(), Some(json!(99)));
+        assert_eq!(store.get("c", "count").unwrap(), None);
+        assert_eq!(store.keys("a", "todo:").unwrap(), vec!["todo:1", "todo:2"]);
+        assert_eq!(store.keys("a", "").unwrap().len(), 3);
+
+        assert!(store.delete("a", "count").unwrap());
+        assert!(!store.delete("a", "count").unwrap());
+        assert_eq!(store.get("b", "count").unwrap(), Some(json!(99)));
+    }
+
+    #[test]
+    fn test_kv_host_fns() {
+        use crate::plugin::manifest::PluginManifest;
+        use crate::plugin::runtime::{PluginRuntime, RhaiRuntime};
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = PluginKvStore::open(&dir.path().join("plugin_kv.redb")).unwrap();
+        crate::plugin::runtime::host::set_plugin_kv(std::sync::Arc::new(store)).unwrap();
+
+        let manifest = PluginManifest {
+            id: "counter".into(),
+            name: "Counter".into(),
+            version: "0.1".into(),
+            entry: "main.rhai".into(),
+            engine: Default::default(),
+            events: Vec::new(),
+            limits: Default::default(),
+            ui: Default::default(),
+            capabilities: Default::default(),
+        };
+        let mut rt = RhaiRuntime::new(manifest.clone(), std::path::PathBuf::from("."));
+        rt.load(
+            manifest,
+            r#"
+            fn bump() {
+                let n = kv_get("n");
+                if n == () { n = 0; }
+                kv_set("n", n + 1);
+                kv_set("seen:" + n, #{ at: n });
+                n + 1
+            }
+            fn seen() { kv_keys("seen:").len() }
+            fn reset() { kv_delete("n") }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(rt.call("bump", vec![]).unwrap().as_int().unwrap(), 1);
+        assert_eq!(rt.call("bump", vec![]).unwrap().as_int().unwrap(), 2);
+        assert_eq!(rt.call("seen", vec![]).unwrap().as_int().unwrap(), 2);
+        assert!(rt.call("reset", vec![]).unwrap().as_bool().unwrap());
+        assert_eq!(rt.call("bump", vec![]).unwrap().as_int().unwrap(), 1);
+    }
+}
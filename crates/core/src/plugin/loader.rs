@@ -7,14 +7,19 @@
 //! **核心功能清单**:
 //! - `PluginLoader`: 管理插件加载流程。
 //! - `scan_plugins`: 遍历指定目录，寻找 `manifest.json`。
-//! - `load_plugin`: 读取 Manifest 与 Entry Script，创建 Runtime 实例。
+//! - `load_plugin`: 读取 Manifest 与入口文件，按 `engine` 创建 Runtime 实例。
+//!
+//! 禁用的插件与能力未获批准的已安装插件不加载 (见 `plugin::install::PluginState`)；
+//! 以 `.` 开头的条目 (状态文件、安装中的临时目录) 被跳过。
 //!
 //! **类型**: Core MUST (核心必选)
 
 #[cfg(not(target_arch = "wasm32"))]
-use crate::plugin::manifest::PluginManifest;
+use crate::plugin::install::{PluginState, is_hidden};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::plugin::manifest::{PluginEngine, PluginManifest};
 #[cfg(not(target_arch = "wasm32"))]
-use crate::plugin::runtime::{PluginRuntime, RhaiRuntime};
+use crate::plugin::runtime::{PluginRuntime, RhaiRuntime, WasmRuntime};
 #[cfg(not(target_arch = "wasm32"))]
 use anyhow::{Context, Result};
 #[cfg(not(target_arch = "wasm32"))]
@@ -33,8 +38,21 @@ impl PluginLoader {
         Self { plugin_dir }
     }
 
+    pub fn plugin_dir(&self) -> &Path {
+        &self.plugin_dir
+    }
+
     /// Scan and load all plugins in the plugin directory.
     pub fn load_all(&self) -> Result<Vec<Box<dyn PluginRuntime>>> {
+        Ok(self
+            .load_all_with_dirs()?
+            .into_iter()
+            .map(|(_, p)| p)
+            .collect())
+    }
+
+    /// Scan and load all plugins, paired with their directories.
+    pub fn load_all_with_dirs(&self) -> Result<Vec<(PathBuf, Box<dyn PluginRuntime>)>> {
         let mut plugins = Vec::new();
 
         if !self.plugin_dir.exists() {
@@ -45,11 +63,11 @@ impl PluginLoader {
             let entry = entry?;
             let path = entry.path();
 
-            if path.is_dir() {
+            if path.is_dir() && !is_hidden(&path) {
                 match self.load_plugin(&path) {
                     Ok(runtime) => {
                         println!("Loaded plugin: {}", runtime.manifest().name);
-                        plugins.push(runtime);
+                        plugins.push((path, runtime));
                     }
                     Err(e) => {
                         eprintln!("Failed to load plugin at {:?}: {}", path, e);
@@ -69,17 +87,23 @@ impl PluginLoader {
 
         let manifest: PluginManifest = serde_json::from_str(&manifest_content)
             .with_context(|| "Failed to parse manifest.json")?;
+        PluginState::load(&self.plugin_dir).check_loadable(&manifest, path)?;
 
-        // 2. Read entry script
+        // 2. Read entry (script or wasm module)
         let entry_path = path.join(&manifest.entry);
-        let script_content = fs::read_to_string(&entry_path)
+        let entry = fs::read(&entry_path)
             .with_context(|| format!("Missing entry script '{}' in {:?}", manifest.entry, path))?;
 
-        // 3. Initialize Runtime (传递插件目录路径以支持模块解析)
-        let mut runtime = RhaiRuntime::new(manifest.clone(), path.to_path_buf());
-        runtime.load(manifest, &script_content)?;
+        // 3. Initialize Runtime by engine (Rhai 传递插件目录路径以支持模块解析)
+        let mut runtime: Box<dyn PluginRuntime> = match manifest.engine {
+            PluginEngine::RhaiV1 => {
+                Box::new(RhaiRuntime::new(manifest.clone(), path.to_path_buf()))
+            }
+            PluginEngine::WasmV1 => Box::new(WasmRuntime::new(manifest.clone())),
+        };
+        runtime.load_bytes(manifest, &entry)?;
 
-        Ok(Box::new(runtime))
+        Ok(runtime)
     }
 }
 
@@ -1,4 +1,4 @@
-﻿// crates\core\src\plugin
+// crates\core\src\plugin
 //! # Plugin Manifest & Capabilities (插件清单与能力)
 //!
 //! **架构作用**:
@@ -6,11 +6,16 @@
 //!
 //! **核心功能清单**:
 //! - `PluginManifest`: 插件配置结构体。
-//! - `Capability`: 插件请求的权限集合（网络、文件读写、环境变量）。
+//! - `PluginEngine`: 插件运行时引擎 (`rhai-v1` / `wasm-v1`)。
+//! - `PluginLimits`: Rhai 插件的执行与内存限制。
+//! - `PluginUi`: 插件贡献的 UI (见 `plugin::ui`)。
+//! - `Capability`: 插件请求的权限集合（网络、文件读写、环境变量、笔记编辑）。
 //! - `check_*`: 权限校验逻辑（Default Deny）。
 //!
 //! **类型**: Core MUST (核心必选)
 
+use crate::plugin::events::EventSubscription;
+use crate::plugin::ui::PluginUi;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
@@ -19,11 +24,98 @@ pub struct PluginManifest {
     pub id: String,
     pub name: String,
     pub version: String,
-    pub entry: String, // Entry point script (e.g., "main.rhai")
+    pub entry: String, // Entry point (e.g., "main.rhai" or "plugin.wasm")
+    #[serde(default)]
+    pub engine: PluginEngine,
+    /// 事件订阅 (见 `plugin::events`)
+    #[serde(default)]
+    pub events: Vec<EventSubscription>,
+    #[serde(default)]
+    pub limits: PluginLimits,
+    /// 命令、侧边栏面板与设置项 (见 `plugin::ui`)
+    #[serde(default)]
+    pub ui: PluginUi,
     #[serde(default)]
     pub capabilities: Capability,
 }
 
+/// 插件运行时引擎，缺省为 `rhai-v1`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum PluginEngine {
+    #[default]
+    #[serde(rename = "rhai-v1")]
+    RhaiV1,
+    /// 单个 `.wasm` 模块，ABI 见 `runtime::wasm_v1`
+    #[serde(rename = "wasm-v1")]
+    WasmV1,
+}
+
+/// Rhai 插件单次调用的资源限制 (缺省或为 0 的字段取默认值)
+///
+/// 清单只能在宿主上限 (`PluginLimits::CEILING`) 内调整，实际生效值见 `effective`。
+/// WASM 插件由燃料与线性内存上限约束，见 `runtime::wasm_v1::WasmLimits`。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PluginLimits {
+    /// 最大操作数
+    pub max_operations: u64,
+    /// 墙钟超时 (毫秒)；阻塞中的宿主函数返回后才会生效
+    pub timeout_ms: u64,
+    /// 字符串最大字节数
+    pub max_string_size: usize,
+    /// 数组最大元素数
+    pub max_array_size: usize,
+    /// 对象映射最大条目数
+    pub max_map_size: usize,
+    /// 最大函数调用深度
+    pub max_call_levels: usize,
+}
+
+impl Default for PluginLimits {
+    fn default() -> Self {
+        Self {
+            max_operations: 50_000_000,
+            timeout_ms: 60_000,
+            max_string_size: 16 * 1024 * 1024,
+            max_array_size: 1_000_000,
+            max_map_size: 1_000_000,
+            max_call_levels: 64,
+        }
+    }
+}
+
+impl PluginLimits {
+    /// 宿主上限: 清单中更大的值按此截断
+    pub const CEILING: Self = Self {
+        max_operations: 500_000_000,
+        timeout_ms: 600_000,
+        max_string_size: 64 * 1024 * 1024,
+        max_array_size: 10_000_000,
+        max_map_size: 10_000_000,
+        max_call_levels: 128,
+    };
+
+    /// 运行时实际采用的限制: 为 0 的字段取默认值，其余截断到 `CEILING`
+    pub fn effective(&self) -> Self {
+        fn pick<T: Copy + Ord + Default>(value: T, default: T, ceiling: T) -> T {
+            if value == T::default() {
+                default
+            } else {
+                value.min(ceiling)
+            }
+        }
+        let (d, c) = (Self::default(), Self::CEILING);
+        Self {
+            max_operations: pick(self.max_operations, d.max_operations, c.max_operations),
+            timeout_ms: pick(self.timeout_ms, d.timeout_ms, c.timeout_ms),
+            max_string_size: pick(self.max_string_size, d.max_string_size, c.max_string_size),
+            max_array_size: pick(self.max_array_size, d.max_array_size, c.max_array_size),
+            max_map_size: pick(self.max_map_size, d.max_map_size, c.max_map_size),
+            max_call_levels: pick(self.max_call_levels, d.max_call_levels, c.max_call_levels),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct Capability {
     #[serde(default)]
@@ -34,8 +126,12 @@ pub struct Capability {
     pub allow_fs_write: Vec<PathBuf>,
     #[serde(default)]
     pub allow_env: Vec<String>,
+    /// 版本控制；亦可只读访问笔记 (`doc_read` / `query_docs`)
     #[serde(default)]
     pub allow_source_control: bool,
+    /// 读取、查询与编辑笔记 (`doc_read` / `query_docs` / `doc_apply_edit` / `doc_create`)
+    #[serde(default)]
+    pub allow_doc_edit: bool,
 }
 
 impl Capability {
@@ -91,12 +187,36 @@ impl Capability {
     pub fn check_source_control(&self) -> bool {
         self.allow_source_control
     }
+
+    /// Check if editing notes through the ledger is allowed.
+    pub fn check_doc_edit(&self) -> bool {
+        self.allow_doc_edit
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_plugin_limits_effective() {
+        let limits: PluginLimits = serde_json::from_str(
+            r#"{"max_operations": 0, "timeout_ms": 86400000, "max_call_levels": 8}"#,
+        )
+        .unwrap();
+        let effective = limits.effective();
+        assert_eq!(
+            effective.max_operations,
+            PluginLimits::default().max_operations
+        );
+        assert_eq!(effective.timeout_ms, PluginLimits::CEILING.timeout_ms);
+        assert_eq!(effective.max_call_levels, 8);
+        assert_eq!(
+            effective.max_string_size,
+            PluginLimits::default().max_string_size
+        );
+    }
+
     #[test]
     fn test_capability_check_net() {
         let cap = Capability {
@@ -118,6 +238,8 @@ mod tests {
 
         // Read checks
         assert!(cap.check_read(Path::new("/data/vault/notes.md")));
+        // 反斜杠仅在 Windows 上是路径分隔符
+        #[cfg(windows)]
         assert!(cap.check_read(Path::new("C:\\Notes\\file.txt")));
         assert!(!cap.check_read(Path::new("/etc/passwd")));
 
@@ -130,6 +252,28 @@ mod tests {
         assert!(!cap.check_write(Path::new("/data/vault/public/../../private.md")));
     }
 
+    #[test]
+    fn test_manifest_engine() {
+        let manifest: PluginManifest = serde_json::from_str(
+            r#"{"id": "a", "name": "A", "version": "1", "entry": "main.rhai"}"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.engine, PluginEngine::RhaiV1);
+
+        let manifest: PluginManifest = serde_json::from_str(
+            r#"{"id": "a", "name": "A", "version": "1", "entry": "a.wasm", "engine": "wasm-v1"}"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.engine, PluginEngine::WasmV1);
+
+        assert!(
+            serde_json::from_str::<PluginManifest>(
+                r#"{"id": "a", "name": "A", "version": "1", "entry": "a", "engine": "lua"}"#,
+            )
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_capability_check_env() {
         let cap = Capability {
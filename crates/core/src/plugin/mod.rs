@@ -6,9 +6,24 @@
 //!
 //! **核心功能清单**:
 //! - `manifest`: 定义插件清单与能力列表。
+//! - `events`: 插件事件订阅与事件总线。
+//! - `ui`: 插件声明的命令、面板与设置项 (JSON UI 协议)。
+//! - `package` / `install`: 签名插件包与插件目录管理。
+//! - `registry`: 当前插件集合与热重载。
+//! - `kv`: 按插件分区的持久化键值存储。
 //!
 //! **类型**: Core MUST (核心必选)
 
+pub mod events;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod install;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod kv;
 pub mod loader;
 pub mod manifest;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod package;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod registry;
 pub mod runtime;
+pub mod ui;
@@ -0,0 +1,312 @@
+// crates\core\src\plugin\package.rs
+//! # Plugin Package (插件包)
+//!
+//! **架构作用**:
+//! 插件以签名的 `.tar.gz` 包分发，由 `deve plugin install` 校验后解压到插件目录。
+//!
+//! **包格式**:
+//! - 根目录为插件文件 (`manifest.json`、入口脚本或模块及其依赖文件)。
+//! - `SIGNATURE.json`: `{"publisher": 公钥 hex, "signature": 签名 hex}`。
+//!
+//! **签名**:
+//! 发布者以 Ed25519 密钥 (`security::keypair`) 对包摘要签名。摘要为按路径排序的
+//! `路径\0内容 SHA-256 hex\n` 序列的 SHA-256，与打包顺序、压缩方式无关。
+//! 安装时公钥须在受信任发布者列表 (`.deve/trusted-publishers.json`) 中。
+//!
+//! ## Invariants
+//! - 包内路径必须为相对路径且不含 `..`，只接受普通文件与目录
+//! - 除 `SIGNATURE.json` 外的每个文件都参与摘要
+//!
+//! **类型**: Core MUST (核心必选)
+
+use crate::plugin::manifest::PluginManifest;
+use crate::security::IdentityKeyPair;
+use crate::security::hashing::{sha256_bytes, sha256_hex};
+use crate::security::keypair::verify_signature;
+use anyhow::{Context, Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+
+/// 包内签名文件名
+pub const SIGNATURE_FILE: &str = "SIGNATURE.json";
+
+/// 解压后单个包的大小上限
+const MAX_PACKAGE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// 包签名 (`SIGNATURE.json`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PackageSignature {
+    /// 发布者公钥 (hex)
+    pub publisher: String,
+    /// 包摘要的 Ed25519 签名 (hex)
+    pub signature: String,
+}
+
+/// 受信任的发布者
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrustedPublisher {
+    pub name: String,
+    /// Ed25519 公钥 (hex)
+    pub public_key: String,
+}
+
+/// 受信任发布者列表 (`.deve/trusted-publishers.json`，JSON 数组)
+#[derive(Debug, Clone, Default)]
+pub struct TrustedPublishers(pub Vec<TrustedPublisher>);
+
+impl TrustedPublishers {
+    /// 读取列表，文件不存在时为空 (此时任何包都无法安装)
+    pub fn load(deve_dir: &Path) -> Result<Self> {
+        let path = deve_dir.join("trusted-publishers.json");
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Ok(Self(
+                serde_json::from_str(&text).with_context(|| format!("Invalid {:?}", path))?,
+            )),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn find(&self, public_key: &str) -> Option<&TrustedPublisher> {
+        self.0
+            .iter()
+            .find(|p| p.public_key.eq_ignore_ascii_case(public_key))
+    }
+}
+
+/// 内存中的插件包
+pub struct PluginPackage {
+    pub manifest: PluginManifest,
+    /// 相对路径 (`/` 分隔) -> 文件内容，不含签名文件
+    pub files: BTreeMap<String, Vec<u8>>,
+    pub signature: Option<PackageSignature>,
+}
+
+impl PluginPackage {
+    /// 从插件目录收集文件 (用于打包)
+    pub fn from_dir(dir: &Path) -> Result<Self> {
+        let mut files = BTreeMap::new();
+        for entry in walkdir::WalkDir::new(dir).sort_by_file_name() {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let rel = entry.path().strip_prefix(dir)?;
+            let rel = crate::utils::path::to_forward_slash(&rel.to_string_lossy());
+            if rel == SIGNATURE_FILE {
+                continue;
+            }
+            files.insert(rel, std::fs::read(entry.path())?);
+        }
+        Self::from_files(files, None)
+    }
+
+    /// 读取 `.tar.gz` 包
+    pub fn read(archive: &Path) -> Result<Self> {
+        let file =
+            std::fs::File::open(archive).with_context(|| format!("Cannot open {:?}", archive))?;
+        let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        let mut files = BTreeMap::new();
+        let mut signature = None;
+        let mut total = 0u64;
+        for entry in tar.entries()? {
+            let mut entry = entry?;
+            let kind = entry.header().entry_type();
+            if kind.is_dir() {
+                continue;
+            }
+            if !kind.is_file() {
+                bail!("Unsupported entry type in package: {:?}", entry.path()?);
+            }
+            let rel = safe_relative(&entry.path()?)?;
+            total += entry.size();
+            if total > MAX_PACKAGE_BYTES {
+                bail!("Package exceeds {} bytes", MAX_PACKAGE_BYTES);
+            }
+            let mut bytes = Vec::new();
+            std::io::Read::read_to_end(&mut entry, &mut bytes)?;
+            if rel == SIGNATURE_FILE {
+                signature = Some(serde_json::from_slice(&bytes).context("Invalid SIGNATURE.json")?);
+            } else {
+                files.insert(rel, bytes);
+            }
+        }
+        Self::from_files(files, signature)
+    }
+
+    fn from_files(
+        files: BTreeMap<String, Vec<u8>>,
+        signature: Option<PackageSignature>,
+    ) -> Result<Self> {
+        let manifest = files
+            .get("manifest.json")
+            .ok_or_else(|| anyhow!("Package has no manifest.json"))?;
+        let manifest: PluginManifest =
+            serde_json::from_slice(manifest).context("Failed to parse manifest.json")?;
+        if !files.contains_key(&manifest.entry) {
+            bail!("Package is missing entry '{}'", manifest.entry);
+        }
+        Ok(Self {
+            manifest,
+            files,
+            signature,
+        })
+    }
+
+    /// 包摘要 (见模块文档)
+    pub fn digest(&self) -> [u8; 32] {
+        let mut listing = String::new();
+        for (path, bytes) in &self.files {
+            listing.push_str(path);
+            listing.push('\0');
+            listing.push_str(&sha256_hex(bytes));
+            listing.push('\n');
+        }
+        sha256_bytes(listing.as_bytes())
+    }
+
+    /// 以发布者密钥签名
+    pub fn sign(&mut self, key: &IdentityKeyPair) {
+        self.signature = Some(PackageSignature {
+            publisher: hex::encode(key.public_key_bytes()),
+            signature: hex::encode(key.sign(&self.digest())),
+        });
+    }
+
+    /// 校验签名并返回对应的受信任发布者
+    pub fn verify(&self, trusted: &TrustedPublishers) -> Result<TrustedPublisher> {
+        let sig = self
+            .signature
+            .as_ref()
+            .ok_or_else(|| anyhow!("Package is not signed"))?;
+        let publisher = trusted
+            .find(&sig.publisher)
+            .ok_or_else(|| anyhow!("Publisher {} is not trusted", sig.publisher))?;
+        let key = hex::decode(&sig.publisher).context("Invalid publisher key")?;
+        let signature = hex::decode(&sig.signature).context("Invalid signature encoding")?;
+        if !verify_signature(&key, &self.digest(), &signature) {
+            bail!("Package signature does not match its contents");
+        }
+        Ok(publisher.clone())
+    }
+
+    /// 写出 `.tar.gz` 包 (含签名文件)
+    pub fn write(&self, archive: &Path) -> Result<()> {
+        let file = std::fs::File::create(archive)?;
+        let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        let mut tar = tar::Builder::new(encoder);
+        let signature = self
+            .signature
+            .as_ref()
+            .map(serde_json::to_vec_pretty)
+            .transpose()?;
+        let entries = self
+            .files
+            .iter()
+            .map(|(path, bytes)| (path.as_str(), bytes.as_slice()))
+            .chain(signature.as_deref().map(|s| (SIGNATURE_FILE, s)));
+        for (path, bytes) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(bytes.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append_data(&mut header, path, bytes)?;
+        }
+        tar.into_inner()?.finish()?;
+        Ok(())
+    }
+
+    /// 解压文件到目录 (不含签名文件)
+    pub fn extract_to(&self, dir: &Path) -> Result<()> {
+        for (path, bytes) in &self.files {
+            let target = dir.join(path);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&target, bytes)?;
+        }
+        Ok(())
+    }
+}
+
+/// 校验包内路径并转为 `/` 分隔的相对路径
+fn safe_relative(path: &Path) -> Result<String> {
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().into_owned()),
+            Component::CurDir => {}
+            _ => bail!("Unsafe path in package: {:?}", path),
+        }
+    }
+    if parts.is_empty() {
+        bail!("Empty path in package");
+    }
+    Ok(parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("manifest.json"),
+            r#"{"id": "hello", "name": "Hello", "version": "1.0.0", "entry": "main.rhai"}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("main.rhai"), "fn hi() { 1 }").unwrap();
+        dir
+    }
+
+    fn trusted(key: &IdentityKeyPair) -> TrustedPublishers {
+        TrustedPublishers(vec![TrustedPublisher {
+            name: "acme".into(),
+            public_key: hex::encode(key.public_key_bytes()),
+        }])
+    }
+
+    #[test]
+    fn test_sign_roundtrip() {
+        let src = plugin_dir();
+        let key = IdentityKeyPair::generate();
+        let mut pkg = PluginPackage::from_dir(src.path()).unwrap();
+        pkg.sign(&key);
+
+        let out = tempfile::tempdir().unwrap();
+        let archive = out.path().join("hello.tar.gz");
+        pkg.write(&archive).unwrap();
+
+        let read = PluginPackage::read(&archive).unwrap();
+        assert_eq!(read.manifest.id, "hello");
+        assert_eq!(read.verify(&trusted(&key)).unwrap().name, "acme");
+
+        let stranger = IdentityKeyPair::generate();
+        assert!(read.verify(&trusted(&stranger)).is_err());
+    }
+
+    #[test]
+    fn test_tampered_package_rejected() {
+        let src = plugin_dir();
+        let key = IdentityKeyPair::generate();
+        let mut pkg = PluginPackage::from_dir(src.path()).unwrap();
+        pkg.sign(&key);
+        pkg.files
+            .insert("main.rhai".into(), b"fn hi() { 2 }".to_vec());
+        assert!(pkg.verify(&trusted(&key)).is_err());
+
+        pkg.signature = None;
+        assert!(pkg.verify(&trusted(&key)).is_err());
+    }
+
+    #[test]
+    fn test_unsafe_paths() {
+        assert!(safe_relative(Path::new("../evil")).is_err());
+        assert!(safe_relative(Path::new("/etc/passwd")).is_err());
+        assert_eq!(
+            safe_relative(Path::new("./lib/a.rhai")).unwrap(),
+            "lib/a.rhai"
+        );
+    }
+}
@@ -0,0 +1,325 @@
+// crates\core\src\plugin\registry.rs
+//! # Plugin Registry (插件注册表)
+//!
+//! **架构作用**:
+//! 持有当前加载的插件集合，并在插件目录变化时热重载，无需重启 `deve serve`。
+//!
+//! **核心功能清单**:
+//! - `snapshot`: 当前插件集合的不可变快照 (调用方在一次调用内按下标使用)。
+//! - `reload_dir`: 重新加载单个插件目录 (目录被删除或插件被禁用时卸下)。
+//! - `watch`: 后台监听插件目录，按变化的子目录重载；状态文件变化时全部重载。
+//! - `on_reload`: 重载后的通知 (如向客户端广播新的插件列表)。
+//!
+//! ## Invariants
+//! - 重载只替换集合，不影响进行中的调用 (其持有旧快照)
+//! - 只重载内容变化的插件，其他插件的脚本状态保持不变
+//!
+//! **类型**: Core MUST (核心必选)
+
+use crate::plugin::install::{STATE_FILE, is_hidden};
+use crate::plugin::loader::PluginLoader;
+use crate::plugin::runtime::PluginRuntime;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+/// 插件集合快照
+pub type PluginSet = Arc<Vec<Arc<dyn PluginRuntime>>>;
+
+type ReloadHook = Box<dyn Fn() + Send + Sync>;
+
+struct Loaded {
+    /// 与 `plugins` 一一对应的来源目录 (`from_plugins` 创建的为 `None`)
+    dirs: Vec<Option<PathBuf>>,
+    plugins: PluginSet,
+}
+
+/// 插件注册表
+pub struct PluginRegistry {
+    loader: Option<PluginLoader>,
+    loaded: RwLock<Loaded>,
+    hooks: RwLock<Vec<ReloadHook>>,
+}
+
+impl PluginRegistry {
+    /// 加载插件目录中的全部插件
+    pub fn load(plugin_dir: PathBuf) -> Self {
+        let loader = PluginLoader::new(plugin_dir);
+        let (dirs, plugins) = match loader.load_all_with_dirs() {
+            Ok(found) => found
+                .into_iter()
+                .map(|(dir, p)| (Some(dir), Arc::from(p)))
+                .unzip(),
+            Err(e) => {
+                tracing::warn!("Failed to load plugins: {}", e);
+                (Vec::new(), Vec::new())
+            }
+        };
+        Self {
+            loader: Some(loader),
+            loaded: RwLock::new(Loaded {
+                dirs,
+                plugins: Arc::new(plugins),
+            }),
+            hooks: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// 固定的插件集合 (不关联目录，不会重载)
+    pub fn from_plugins(plugins: Vec<Box<dyn PluginRuntime>>) -> Self {
+        let plugins: Vec<Arc<dyn PluginRuntime>> = plugins.into_iter().map(Arc::from).collect();
+        Self {
+            loader: None,
+            loaded: RwLock::new(Loaded {
+                dirs: vec![None; plugins.len()],
+                plugins: Arc::new(plugins),
+            }),
+            hooks: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub fn snapshot(&self) -> PluginSet {
+        self.loaded
+            .read()
+            .map(|l| l.plugins.clone())
+            .unwrap_or_default()
+    }
+
+    /// 注册重载通知
+    pub fn on_reload(&self, hook: impl Fn() + Send + Sync + 'static) {
+        if let Ok(mut hooks) = self.hooks.write() {
+            hooks.push(Box::new(hook));
+        }
+    }
+
+    /// 重新加载单个插件目录
+    pub fn reload_dir(&self, dir: &Path) {
+        let Some(loader) = &self.loader else {
+            return;
+        };
+        let fresh = if dir.join("manifest.json").exists() {
+            match loader.load_plugin(dir) {
+                Ok(p) => Some(p),
+                Err(e) => {
+                    tracing::warn!("Plugin at {:?} not loaded: {}", dir, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        {
+            let Ok(mut loaded) = self.loaded.write() else {
+                return;
+            };
+            let mut dirs = Vec::new();
+            let mut plugins = Vec::new();
+            for (d, p) in loaded.dirs.iter().zip(loaded.plugins.iter()) {
+                if d.as_deref() != Some(dir) {
+                    dirs.push(d.clone());
+                    plugins.push(p.clone());
+                }
+            }
+            if let Some(p) = fresh {
+                tracing::info!("Reloaded plugin '{}'", p.manifest().id);
+                dirs.push(Some(dir.to_path_buf()));
+                plugins.push(Arc::from(p));
+            }
+            *loaded = Loaded {
+                dirs,
+                plugins: Arc::new(plugins),
+            };
+        }
+        self.notify();
+    }
+
+    /// 重新加载全部插件 (启用状态变化时)
+    pub fn reload_all(&self) {
+        let Some(loader) = &self.loader else {
+            return;
+        };
+        match loader.load_all_with_dirs() {
+            Ok(found) => {
+                let (dirs, plugins): (Vec<_>, Vec<_>) = found
+                    .into_iter()
+                    .map(|(dir, p)| (Some(dir), Arc::from(p)))
+                    .unzip();
+                if let Ok(mut loaded) = self.loaded.write() {
+                    *loaded = Loaded {
+                        dirs,
+                        plugins: Arc::new(plugins),
+                    };
+                }
+                self.notify();
+            }
+            Err(e) => tracing::warn!("Failed to reload plugins: {}", e),
+        }
+    }
+
+    fn notify(&self) {
+        if let Ok(hooks) = self.hooks.read() {
+            for hook in hooks.iter() {
+                hook();
+            }
+        }
+    }
+
+    /// 在后台线程监听插件目录并热重载
+    pub fn watch(self: &Arc<Self>) -> Result<()> {
+        let Some(loader) = &self.loader else {
+            return Ok(());
+        };
+        std::fs::create_dir_all(loader.plugin_dir())?;
+        let root = std::fs::canonicalize(loader.plugin_dir())?;
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut debouncer = notify_debouncer_mini::new_debouncer(Duration::from_millis(300), tx)?;
+        debouncer.watcher().watch(
+            &root,
+            notify_debouncer_mini::notify::RecursiveMode::Recursive,
+        )?;
+
+        let registry = self.clone();
+        let plugin_dir = loader.plugin_dir().to_path_buf();
+        // 加载插件时的读取也会产生事件，只在内容指纹变化时重载
+        let mut fingerprints: HashMap<String, Fingerprint> = HashMap::new();
+        for entry in std::fs::read_dir(&plugin_dir)?.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            fingerprints.insert(name, fingerprint(&entry.path()));
+        }
+        std::thread::Builder::new()
+            .name("plugin-watch".into())
+            .spawn(move || {
+                let _debouncer = debouncer;
+                for result in rx {
+                    let Ok(events) = result else {
+                        continue;
+                    };
+                    let mut state_changed = false;
+                    let mut dirs: Vec<String> = Vec::new();
+                    for event in &events {
+                        let Some(top) = event
+                            .path
+                            .strip_prefix(&root)
+                            .ok()
+                            .and_then(|rel| rel.components().next())
+                        else {
+                            continue;
+                        };
+                        let name = top.as_os_str().to_string_lossy().into_owned();
+                        if (name != STATE_FILE && is_hidden(Path::new(&name)))
+                            || dirs.contains(&name)
+                        {
+                            continue;
+                        }
+                        let current = fingerprint(&plugin_dir.join(&name));
+                        if fingerprints.get(&name) == Some(&current) {
+                            continue;
+                        }
+                        fingerprints.insert(name.clone(), current);
+                        if name == STATE_FILE {
+                            state_changed = true;
+                        } else {
+                            dirs.push(name);
+                        }
+                    }
+                    if state_changed {
+                        registry.reload_all();
+                    } else {
+                        for name in dirs {
+                            registry.reload_dir(&plugin_dir.join(name));
+                        }
+                    }
+                }
+            })?;
+        tracing::info!("Watching {:?} for plugin changes", loader.plugin_dir());
+        Ok(())
+    }
+}
+
+/// 目录或文件的内容指纹 (路径、大小、修改时间)
+type Fingerprint = Vec<(PathBuf, u64, Option<SystemTime>)>;
+
+fn fingerprint(path: &Path) -> Fingerprint {
+    walkdir::WalkDir::new(path)
+        .sort_by_file_name()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            Some((entry.into_path(), meta.len(), meta.modified().ok()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_plugin(dir: &Path, version: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(
+            dir.join("manifest.json"),
+            format!(
+                r#"{{"id": "hot", "name": "Hot", "version": "{version}", "entry": "main.rhai"}}"#
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("main.rhai"),
+            format!(r#"fn v() {{ "{version}" }}"#),
+        )
+        .unwrap();
+    }
+
+    fn version(registry: &PluginRegistry) -> Option<String> {
+        let plugins = registry.snapshot();
+        let plugin = plugins.iter().find(|p| p.manifest().id == "hot")?;
+        Some(plugin.call("v", vec![]).unwrap().into_string().unwrap())
+    }
+
+    #[test]
+    fn test_reload_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("hot");
+        write_plugin(&dir, "1");
+        let registry = PluginRegistry::load(root.path().to_path_buf());
+        assert_eq!(version(&registry).as_deref(), Some("1"));
+
+        let reloads = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = reloads.clone();
+        registry.on_reload(move || {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        });
+
+        let old = registry.snapshot();
+        write_plugin(&dir, "2");
+        registry.reload_dir(&dir);
+        assert_eq!(version(&registry).as_deref(), Some("2"));
+        // 旧快照不受影响
+        assert_eq!(
+            old[0].call("v", vec![]).unwrap().into_string().unwrap(),
+            "1"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        registry.reload_dir(&dir);
+        assert_eq!(version(&registry), None);
+        assert_eq!(reloads.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_watch_hot_reload() {
+        let root = tempfile::tempdir().unwrap();
+        let registry = Arc::new(PluginRegistry::load(root.path().to_path_buf()));
+        registry.watch().unwrap();
+        write_plugin(&root.path().join("hot"), "1");
+
+        let start = std::time::Instant::now();
+        while version(&registry).is_none() && start.elapsed() < Duration::from_secs(10) {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        assert_eq!(version(&registry).as_deref(), Some("1"));
+    }
+}
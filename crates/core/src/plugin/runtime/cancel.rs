@@ -0,0 +1,87 @@
+// crates/core/src/plugin/runtime/cancel.rs
+//! # Plugin Call Cancellation (插件调用取消)
+//!
+//! **功能**:
+//! 调用方 (WebSocket 会话、事件总线) 持有 `CancelToken`，在调用线程上以
+//! `CancelScope` 注入；运行时在执行过程中轮询 `is_cancelled` 并尽快终止。
+//! 异步任务可 `cancelled().await` 等待取消，无需轮询。
+//!
+//! ## Invariants
+//! - `CancelScope` 的生命周期必须覆盖插件调用期 (与 `ChatStreamScope` 相同)
+//! - 取消是单向的，已取消的令牌不能恢复
+
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::Notify;
+
+#[derive(Debug, Default)]
+struct TokenState {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+/// 可跨线程共享的取消令牌
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<TokenState>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::Release);
+        self.0.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::Acquire)
+    }
+
+    /// 等待至令牌被取消 (已取消时立即返回)
+    pub async fn cancelled(&self) {
+        loop {
+            // 先登记等待再检查标志，避免错过检查与等待之间的 `cancel`
+            let notified = self.0.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT_TOKEN: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+/// RAII guard: 将令牌注入当前线程，析构时恢复之前的令牌
+pub struct CancelScope {
+    previous: Option<CancelToken>,
+}
+
+impl CancelScope {
+    pub fn new(token: CancelToken) -> Self {
+        let previous = CURRENT_TOKEN.with(|cell| cell.replace(Some(token)));
+        Self { previous }
+    }
+}
+
+impl Drop for CancelScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT_TOKEN.with(|cell| {
+            cell.replace(previous);
+        });
+    }
+}
+
+/// 当前线程的调用是否已被取消
+pub fn is_cancelled() -> bool {
+    CURRENT_TOKEN.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .is_some_and(CancelToken::is_cancelled)
+    })
+}
@@ -0,0 +1,246 @@
+// crates/core/src/plugin/runtime/doc_edit.rs
+//! # Document Edit Bridge (文档编辑桥接)
+//!
+//! **功能**:
+//! 宿主函数 `doc_read` / `doc_apply_edit` / `doc_create` 经此桥接交给服务端执行，
+//! 编辑以插件身份 (`plugin_peer_id`) 写入 Ledger 并广播给客户端，
+//! 而不是绕过操作日志直接改写文件。
+//!
+//! ## Invariants
+//! - `DOC_EDIT_HANDLER` 在进程生命周期内只能设置一次 (未设置时编辑函数报错)
+//! - `TextEdit` 的位置为字符偏移 (与 Rhai 字符串一致)，写入前转换为 UTF-16 位置
+
+use crate::models::{DocId, Op, PeerId};
+use anyhow::{Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, OnceLock};
+
+/// 文档当前状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocSnapshot {
+    pub doc_id: DocId,
+    pub path: String,
+    pub content: String,
+    /// 最新操作序号 (传给 `doc_apply_edit` 做并发检查)
+    pub version: u64,
+}
+
+/// 文本替换: 将字符区间 `[from, to)` 替换为 `text`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TextEdit {
+    pub from: usize,
+    pub to: usize,
+    #[serde(default)]
+    pub text: String,
+}
+
+/// 文档编辑接口 (由服务端实现)
+pub trait DocEditHandler: Send + Sync {
+    fn read(&self, path: &str) -> Result<DocSnapshot>;
+
+    /// 以插件身份应用编辑，返回新版本
+    ///
+    /// `expected_version` 为 `Some` 且与当前版本不同时拒绝 (文档已被他人修改)。
+    fn apply_edit(
+        &self,
+        plugin_id: &str,
+        path: &str,
+        edits: &[TextEdit],
+        expected_version: Option<u64>,
+    ) -> Result<u64>;
+
+    /// 以插件身份创建文档并写入初始内容 (路径已存在时报错)
+    fn create(&self, plugin_id: &str, path: &str, content: &str) -> Result<DocSnapshot>;
+}
+
+static DOC_EDIT_HANDLER: OnceLock<Arc<dyn DocEditHandler>> = OnceLock::new();
+
+pub fn set_doc_edit_handler(handler: Arc<dyn DocEditHandler>) -> Result<()> {
+    DOC_EDIT_HANDLER
+        .set(handler)
+        .map_err(|_| anyhow!("Doc edit handler already set"))
+}
+
+pub fn doc_edit_handler() -> Option<Arc<dyn DocEditHandler>> {
+    DOC_EDIT_HANDLER.get().cloned()
+}
+
+/// 插件写入 Ledger 时使用的 Peer 身份
+pub fn plugin_peer_id(plugin_id: &str) -> PeerId {
+    PeerId::new(format!("plugin:{}", plugin_id))
+}
+
+/// 将基于 `content` 的一组编辑转换为按序应用的位置操作
+///
+/// 编辑区间互不重叠且都以原内容为准；按位置倒序生成操作，
+/// 使前面的操作不影响后面操作的位置。
+pub fn edits_to_ops(content: &str, edits: &[TextEdit]) -> Result<Vec<Op>> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut sorted: Vec<&TextEdit> = edits.iter().collect();
+    sorted.sort_by_key(|e| std::cmp::Reverse(e.from));
+
+    let mut ops = Vec::new();
+    let mut limit = chars.len();
+    for edit in sorted {
+        if edit.from > edit.to || edit.to > limit {
+            bail!(
+                "Invalid edit range {}..{} (overlapping or beyond {} chars)",
+                edit.from,
+                edit.to,
+                chars.len()
+            );
+        }
+        limit = edit.from;
+        let pos = utf16_offset(&chars[..edit.from]);
+        let len = utf16_offset(&chars[edit.from..edit.to]);
+        if len > 0 {
+            ops.push(Op::Delete { pos, len });
+        }
+        if !edit.text.is_empty() {
+            ops.push(Op::Insert {
+                pos,
+                content: edit.text.as_str().into(),
+            });
+        }
+    }
+    Ok(ops)
+}
+
+fn utf16_offset(chars: &[char]) -> u32 {
+    chars.iter().map(|c| c.len_utf16() as u32).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LedgerEntry;
+
+    fn apply(content: &str, edits: &[TextEdit]) -> String {
+        let ops = edits_to_ops(content, edits).unwrap();
+        let mut entries = vec![LedgerEntry {
+            doc_id: DocId::new(),
+            op: Op::Insert {
+                pos: 0,
+                content: content.into(),
+            },
+            timestamp: 0,
+            peer_id: plugin_peer_id("t"),
+            seq: 0,
+        }];
+        for op in ops {
+            entries.push(LedgerEntry {
+                op,
+                ..entries[0].clone()
+            });
+        }
+        crate::state::reconstruct_content(&entries)
+    }
+
+    #[test]
+    fn test_edits_to_ops() {
+        let edit = |from, to, text: &str| TextEdit {
+            from,
+            to,
+            text: text.into(),
+        };
+        assert_eq!(
+            apply("hello world", &[edit(0, 5, "hi"), edit(11, 11, "!")]),
+            "hi world!"
+        );
+        // 字符偏移: emoji 在 UTF-16 中占两个单元
+        assert_eq!(apply("😀ab", &[edit(1, 2, "X")]), "😀Xb");
+        assert!(edits_to_ops("abc", &[edit(0, 2, ""), edit(1, 3, "")]).is_err());
+        assert!(edits_to_ops("abc", &[edit(2, 9, "")]).is_err());
+    }
+
+    /// 内存中的单文档实现，验证宿主函数的能力检查与参数转换
+    struct MemoryDocs(std::sync::Mutex<(String, u64)>);
+
+    impl DocEditHandler for MemoryDocs {
+        fn read(&self, path: &str) -> Result<DocSnapshot> {
+            let (content, version) = self.0.lock().unwrap().clone();
+            Ok(DocSnapshot {
+                doc_id: DocId::from_u128(1),
+                path: path.to_string(),
+                content,
+                version,
+            })
+        }
+
+        fn apply_edit(
+            &self,
+            plugin_id: &str,
+            _path: &str,
+            edits: &[TextEdit],
+            expected_version: Option<u64>,
+        ) -> Result<u64> {
+            assert_eq!(plugin_id, "editor");
+            let mut doc = self.0.lock().unwrap();
+            if expected_version.is_some_and(|v| v != doc.1) {
+                bail!("Document changed");
+            }
+            doc.0 = apply(&doc.0, edits);
+            doc.1 += 1;
+            Ok(doc.1)
+        }
+
+        fn create(&self, _plugin_id: &str, path: &str, content: &str) -> Result<DocSnapshot> {
+            *self.0.lock().unwrap() = (content.to_string(), 1);
+            self.read(path)
+        }
+    }
+
+    #[test]
+    fn test_doc_host_fns() {
+        use crate::plugin::manifest::{Capability, PluginManifest};
+        use crate::plugin::runtime::{PluginRuntime, RhaiRuntime};
+
+        set_doc_edit_handler(Arc::new(MemoryDocs(Default::default()))).unwrap();
+        let script = r#"
+            fn make() { doc_create("n.md", "hello").version }
+            fn shout(v) { doc_apply_edit("n.md", [#{from: 0, to: 1, text: "H"}], v) }
+            fn append() { doc_apply_edit("n.md", #{from: 5, to: 5, text: "!"}) }
+            fn read() { doc_read("n.md").content }
+        "#;
+        let runtime = |caps: Capability| {
+            let manifest = PluginManifest {
+                id: "editor".into(),
+                name: "Editor".into(),
+                version: "0.1".into(),
+                entry: "main.rhai".into(),
+                engine: Default::default(),
+                events: Vec::new(),
+                limits: Default::default(),
+                ui: Default::default(),
+                capabilities: caps,
+            };
+            let mut rt = RhaiRuntime::new(manifest.clone(), std::path::PathBuf::from("."));
+            rt.load(manifest, script).unwrap();
+            rt
+        };
+
+        let denied = runtime(Capability::default());
+        assert!(denied.call("make", vec![]).is_err());
+        assert!(denied.call("read", vec![]).is_err());
+
+        let rt = runtime(Capability {
+            allow_doc_edit: true,
+            ..Default::default()
+        });
+        let version = rt.call("make", vec![]).unwrap().as_int().unwrap();
+        assert_eq!(
+            rt.call("shout", vec![version.into()])
+                .unwrap()
+                .as_int()
+                .unwrap(),
+            2
+        );
+        // 过期版本被拒绝
+        assert!(rt.call("shout", vec![version.into()]).is_err());
+        assert_eq!(rt.call("append", vec![]).unwrap().as_int().unwrap(), 3);
+        assert_eq!(
+            rt.call("read", vec![]).unwrap().into_string().unwrap(),
+            "Hello!"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+// crates/core/src/plugin/runtime/host/docs.rs
+//! # 文档宿主函数
+//!
+//! **功能**:
+//! - `query_docs`: 按 frontmatter 属性与标签查询文档 (与 `QueryDocs` 消息同一语法)。
+//! - `doc_read` / `doc_apply_edit` / `doc_create`: 经 `doc_edit` 桥接读取与编辑笔记，
+//!   编辑以插件身份写入 Ledger 并广播。
+//!
+//! **安全**:
+//! - `query_docs` 需 doc_edit 能力 (读取笔记元数据)。
+//! - `doc_read` 需 source_control 或 doc_edit 能力；编辑与创建需 doc_edit 能力。
+
+use crate::plugin::manifest::Capability;
+use crate::plugin::runtime::doc_edit::{self, DocEditHandler, TextEdit};
+use crate::protocol::DocQuery;
+use rhai::{Dynamic, Engine, EvalAltResult};
+use std::sync::Arc;
+
+fn handler() -> Result<Arc<dyn DocEditHandler>, Box<EvalAltResult>> {
+    doc_edit::doc_edit_handler().ok_or_else(|| "Document editing not available".into())
+}
+
+/// 单个编辑对象或编辑数组
+fn parse_edits(edits: Dynamic) -> Result<Vec<TextEdit>, Box<EvalAltResult>> {
+    if edits.is_map() {
+        Ok(vec![rhai::serde::from_dynamic(&edits)?])
+    } else {
+        rhai::serde::from_dynamic(&edits)
+    }
+}
+
+/// 注册文档 API
+pub fn register_docs_api(engine: &mut Engine, caps: Arc<Capability>, plugin_id: &str) {
+    // API: query_docs(q) -> Array<#{doc_id, path, tags, properties}>
+    // 例: query_docs("tag = project AND status != done SORT BY due")
+    let caps_query = caps.clone();
+    engine.register_fn(
+        "query_docs",
+        move |q: &str| -> Result<Dynamic, Box<EvalAltResult>> {
+            if !caps_query.check_source_control() && !caps_query.check_doc_edit() {
+                return Err("Permission denied: document access not allowed.".into());
+            }
+            let query = DocQuery::parse(q).map_err(|e| format!("Invalid query: {e}"))?;
+            let repo = super::repository().map_err(|e| e.to_string())?;
+            let rows = repo.query_docs(&query).map_err(|e| e.to_string())?;
+            let json = serde_json::to_value(&rows).map_err(|e| e.to_string())?;
+            rhai::serde::to_dynamic(&json).map_err(|e| e.to_string().into())
+        },
+    );
+
+    // API: doc_read(path) -> #{doc_id, path, content, version}
+    let caps_read = caps.clone();
+    engine.register_fn(
+        "doc_read",
+        move |path: &str| -> Result<Dynamic, Box<EvalAltResult>> {
+            if !caps_read.check_source_control() && !caps_read.check_doc_edit() {
+                return Err("Permission denied: document access not allowed.".into());
+            }
+            let doc = handler()?.read(path).map_err(|e| e.to_string())?;
+            rhai::serde::to_dynamic(&doc)
+        },
+    );
+
+    // API: doc_apply_edit(path, edits[, version]) -> 新版本
+    // edits 为 #{from, to, text} 或其数组，字符偏移均以当前内容为准
+    let apply = {
+        let caps = caps.clone();
+        let plugin_id = plugin_id.to_string();
+        move |path: &str, edits: Dynamic, version: Option<u64>| -> Result<i64, Box<EvalAltResult>> {
+            if !caps.check_doc_edit() {
+                return Err("Permission denied: document editing not allowed.".into());
+            }
+            let edits = parse_edits(edits)?;
+            let version = handler()?
+                .apply_edit(&plugin_id, path, &edits, version)
+                .map_err(|e| e.to_string())?;
+            Ok(version as i64)
+        }
+    };
+    let apply_latest = apply.clone();
+    engine.register_fn("doc_apply_edit", move |path: &str, edits: Dynamic| {
+        apply_latest(path, edits, None)
+    });
+    engine.register_fn(
+        "doc_apply_edit",
+        move |path: &str, edits: Dynamic, version: i64| apply(path, edits, Some(version as u64)),
+    );
+
+    // API: doc_create(path, content) -> #{doc_id, path, content, version}
+    let plugin_id = plugin_id.to_string();
+    engine.register_fn(
+        "doc_create",
+        move |path: &str, content: &str| -> Result<Dynamic, Box<EvalAltResult>> {
+            if !caps.check_doc_edit() {
+                return Err("Permission denied: document editing not allowed.".into());
+            }
+            let doc = handler()?
+                .create(&plugin_id, path, content)
+                .map_err(|e| e.to_string())?;
+            rhai::serde::to_dynamic(&doc)
+        },
+    );
+}
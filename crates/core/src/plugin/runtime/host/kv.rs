@@ -0,0 +1,64 @@
+// crates/core/src/plugin/runtime/host/kv.rs
+//! # 插件键值存储宿主函数
+//!
+//! **功能**: 读写本插件的持久化状态 (`kv_get` / `kv_set` / `kv_delete` / `kv_keys`)。
+//! **安全**: 键按清单 ID 分区，只能访问自身数据，无需 Capability。
+
+use crate::plugin::manifest::PluginManifest;
+use rhai::{Array, Dynamic, Engine, EvalAltResult};
+
+fn store() -> Result<std::sync::Arc<crate::plugin::kv::PluginKvStore>, Box<EvalAltResult>> {
+    super::plugin_kv().ok_or_else(|| "Plugin storage not configured".into())
+}
+
+/// 注册键值存储 API
+pub fn register_kv_api(engine: &mut Engine, manifest: &PluginManifest) {
+    let id = manifest.id.clone();
+
+    // API: kv_get(key) -> Dynamic (不存在时返回 ())
+    let plugin_id = id.clone();
+    engine.register_fn(
+        "kv_get",
+        move |key: &str| -> Result<Dynamic, Box<EvalAltResult>> {
+            match store()?.get(&plugin_id, key).map_err(|e| e.to_string())? {
+                Some(value) => rhai::serde::to_dynamic(&value),
+                None => Ok(Dynamic::UNIT),
+            }
+        },
+    );
+
+    // API: kv_set(key, value)
+    let plugin_id = id.clone();
+    engine.register_fn(
+        "kv_set",
+        move |key: &str, value: Dynamic| -> Result<(), Box<EvalAltResult>> {
+            let value: serde_json::Value = rhai::serde::from_dynamic(&value)?;
+            store()?
+                .set(&plugin_id, key, &value)
+                .map_err(|e| e.to_string().into())
+        },
+    );
+
+    // API: kv_delete(key) -> bool (键是否存在)
+    let plugin_id = id.clone();
+    engine.register_fn(
+        "kv_delete",
+        move |key: &str| -> Result<bool, Box<EvalAltResult>> {
+            store()?
+                .delete(&plugin_id, key)
+                .map_err(|e| e.to_string().into())
+        },
+    );
+
+    // API: kv_keys() / kv_keys(prefix) -> Array<String>
+    let plugin_id = id.clone();
+    let keys = move |prefix: &str| -> Result<Array, Box<EvalAltResult>> {
+        let keys = store()?
+            .keys(&plugin_id, prefix)
+            .map_err(|e| e.to_string())?;
+        Ok(keys.into_iter().map(Dynamic::from).collect())
+    };
+    let keys_all = keys.clone();
+    engine.register_fn("kv_keys", move || keys_all(""));
+    engine.register_fn("kv_keys", keys);
+}
@@ -1,9 +1,9 @@
 // crates/core/src/plugin/runtime/host/mcp.rs
-//! # MCP 宿主函数 (Stub)
+//! # MCP 宿主函数
 //!
-//! **功能**: 向 Rhai 暴露 MCP 工具列表与调用接口。
+//! **功能**: 向 Rhai 暴露 MCP 工具、资源与提示词接口。
 
-use crate::mcp::{McpManager, McpServerStatus};
+use crate::mcp::McpManager;
 use rhai::{Engine, EvalAltResult};
 use std::sync::Arc;
 
@@ -38,14 +38,11 @@ pub fn register_mcp_api(engine: &mut Engine, manager: Arc<McpManager>) {
                 .list_status()
                 .into_iter()
                 .map(|(name, status)| {
-                    let status_text = match status {
-                        McpServerStatus::Connected => "connected",
-                        McpServerStatus::Failed { .. } => "failed",
-                        McpServerStatus::NotConfigured => "not_configured",
-                    };
                     serde_json::json!({
                         "name": name,
-                        "status": status_text,
+                        "status": status.state_name(),
+                        "reason": status.reason(),
+                        "capabilities": manager_status.capabilities(&name),
                     })
                 })
                 .collect();
@@ -69,4 +66,59 @@ pub fn register_mcp_api(engine: &mut Engine, manager: Arc<McpManager>) {
             rhai::serde::to_dynamic(&res.content).map_err(|e| e.to_string().into())
         },
     );
+
+    let manager_resources = manager.clone();
+    engine.register_fn(
+        "mcp_list_resources",
+        move || -> Result<rhai::Dynamic, Box<EvalAltResult>> {
+            rhai::serde::to_dynamic(manager_resources.list_all_resources())
+                .map_err(|e| e.to_string().into())
+        },
+    );
+
+    let manager_read = manager.clone();
+    engine.register_fn(
+        "mcp_read_resource",
+        move |server: &str, uri: &str| -> Result<rhai::Dynamic, Box<EvalAltResult>> {
+            let contents = manager_read
+                .read_resource(server, uri)
+                .map_err(|e| e.to_string())?;
+            rhai::serde::to_dynamic(contents).map_err(|e| e.to_string().into())
+        },
+    );
+
+    let manager_sub = manager.clone();
+    engine.register_fn(
+        "mcp_subscribe_resource",
+        move |server: &str, uri: &str| -> Result<(), Box<EvalAltResult>> {
+            manager_sub
+                .subscribe_resource(server, uri)
+                .map_err(|e| e.to_string().into())
+        },
+    );
+
+    let manager_prompts = manager.clone();
+    engine.register_fn(
+        "mcp_list_prompts",
+        move || -> Result<rhai::Dynamic, Box<EvalAltResult>> {
+            rhai::serde::to_dynamic(manager_prompts.list_all_prompts())
+                .map_err(|e| e.to_string().into())
+        },
+    );
+
+    let manager_prompt = manager;
+    engine.register_fn(
+        "mcp_get_prompt",
+        move |server: &str,
+              name: &str,
+              args: rhai::Dynamic|
+              -> Result<rhai::Dynamic, Box<EvalAltResult>> {
+            let args_json: serde_json::Value =
+                rhai::serde::from_dynamic(&args).map_err(|e| e.to_string())?;
+            let res = manager_prompt
+                .get_prompt(server, name, args_json)
+                .map_err(|e| e.to_string())?;
+            rhai::serde::to_dynamic(res).map_err(|e| e.to_string().into())
+        },
+    );
 }
@@ -7,7 +7,10 @@
 //! **模块结构**:
 //! - `fs`: 文件系统操作 (fs_read, fs_write, get_project_tree) [仅非 WASM]
 //! - `git`: 版本控制操作 (sc_status, sc_diff, sc_stage, sc_commit) [仅非 WASM]
+//! - `docs`: 文档查询与编辑 (query_docs, doc_read, doc_apply_edit, doc_create) [仅非 WASM]
+//! - `kv`: 插件键值存储 (kv_get, kv_set, kv_delete, kv_keys) [仅非 WASM]
 //! - `chat`: AI 聊天流式处理 (ai_chat_stream, ai_chat_stream_with_tools) [仅非 WASM]
+//! - `settings`: 插件设置读取 (setting) [仅非 WASM]
 //! - `util`: 辅助函数 (to_json, parse_json, env, log_info)
 //!
 //! **安全**:
@@ -16,14 +19,20 @@
 #[cfg(not(target_arch = "wasm32"))]
 mod chat;
 #[cfg(not(target_arch = "wasm32"))]
+mod docs;
+#[cfg(not(target_arch = "wasm32"))]
 mod fs;
 #[cfg(not(target_arch = "wasm32"))]
 mod git;
 #[cfg(not(target_arch = "wasm32"))]
+mod kv;
+#[cfg(not(target_arch = "wasm32"))]
 mod mcp;
 #[cfg(not(target_arch = "wasm32"))]
 mod search;
 #[cfg(not(target_arch = "wasm32"))]
+mod settings;
+#[cfg(not(target_arch = "wasm32"))]
 mod skill;
 mod util;
 
@@ -35,12 +44,20 @@ use crate::ledger::traits::Repository;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::mcp::McpManager;
 #[cfg(not(target_arch = "wasm32"))]
+use crate::plugin::kv::PluginKvStore;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::plugin::ui::PluginSettingsStore;
+#[cfg(not(target_arch = "wasm32"))]
 use std::sync::{Arc, OnceLock};
 
 #[cfg(not(target_arch = "wasm32"))]
 static REPOSITORY: OnceLock<Arc<dyn Repository>> = OnceLock::new();
 #[cfg(not(target_arch = "wasm32"))]
 static MCP_MANAGER: OnceLock<Arc<McpManager>> = OnceLock::new();
+#[cfg(not(target_arch = "wasm32"))]
+static PLUGIN_SETTINGS: OnceLock<Arc<PluginSettingsStore>> = OnceLock::new();
+#[cfg(not(target_arch = "wasm32"))]
+static PLUGIN_KV: OnceLock<Arc<PluginKvStore>> = OnceLock::new();
 
 #[cfg(not(target_arch = "wasm32"))]
 pub fn set_repository(repo: Arc<dyn Repository>) -> Result<(), anyhow::Error> {
@@ -56,6 +73,20 @@ pub fn set_mcp_manager(manager: Arc<McpManager>) -> Result<(), anyhow::Error> {
         .map_err(|_| anyhow::anyhow!("McpManager already set"))
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub fn set_plugin_settings(store: Arc<PluginSettingsStore>) -> Result<(), anyhow::Error> {
+    PLUGIN_SETTINGS
+        .set(store)
+        .map_err(|_| anyhow::anyhow!("PluginSettingsStore already set"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn set_plugin_kv(store: Arc<PluginKvStore>) -> Result<(), anyhow::Error> {
+    PLUGIN_KV
+        .set(store)
+        .map_err(|_| anyhow::anyhow!("PluginKvStore already set"))
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub fn repository() -> Result<Arc<dyn Repository>, anyhow::Error> {
     REPOSITORY
@@ -64,11 +95,24 @@ pub fn repository() -> Result<Arc<dyn Repository>, anyhow::Error> {
         .ok_or_else(|| anyhow::anyhow!("Repository not configured"))
 }
 
+/// MCP 客户端 (未配置时 `mcp_*` 返回空目录)
 #[cfg(not(target_arch = "wasm32"))]
-pub(crate) fn mcp_manager() -> Option<Arc<McpManager>> {
+pub fn mcp_manager() -> Option<Arc<McpManager>> {
     MCP_MANAGER.get().cloned()
 }
 
+/// 插件设置存储 (未配置时 `setting` 返回清单默认值)
+#[cfg(not(target_arch = "wasm32"))]
+pub fn plugin_settings() -> Option<Arc<PluginSettingsStore>> {
+    PLUGIN_SETTINGS.get().cloned()
+}
+
+/// 插件键值存储 (未配置时 `kv_*` 报错)
+#[cfg(not(target_arch = "wasm32"))]
+pub fn plugin_kv() -> Option<Arc<PluginKvStore>> {
+    PLUGIN_KV.get().cloned()
+}
+
 /// 注册核心 API 到 Rhai 引擎
 #[allow(unused_variables)]
 pub fn register_core_api(engine: &mut Engine, manifest: &PluginManifest) {
@@ -80,10 +124,13 @@ pub fn register_core_api(engine: &mut Engine, manifest: &PluginManifest) {
         // 注册各领域 API (仅非 WASM 环境)
         fs::register_fs_api(engine, caps.clone());
         git::register_git_api(engine, caps.clone());
+        docs::register_docs_api(engine, caps.clone(), &manifest.id);
         chat::register_chat_api(engine, caps.clone());
         util::register_util_api(engine, caps.clone());
         skill::register_skill_api(engine);
         search::register_search_api(engine);
+        settings::register_settings_api(engine, manifest);
+        kv::register_kv_api(engine, manifest);
         let manager = mcp_manager().unwrap_or_else(|| Arc::new(McpManager::new()));
         mcp::register_mcp_api(engine, manager);
     }
@@ -0,0 +1,31 @@
+// crates/core/src/plugin/runtime/host/settings.rs
+//! # 插件设置宿主函数
+//!
+//! **功能**: 读取本插件在清单 `ui.settings` 中声明的设置项 (用户保存值优先，否则为默认值)。
+//! **安全**: 只能读取自身插件的设置，无需 Capability。
+
+use crate::plugin::manifest::PluginManifest;
+use rhai::{Dynamic, Engine, EvalAltResult};
+
+/// 注册设置读取 API
+pub fn register_settings_api(engine: &mut Engine, manifest: &PluginManifest) {
+    let plugin_id = manifest.id.clone();
+    let ui = manifest.ui.clone();
+
+    // API: setting(key) -> Dynamic (未声明的键返回 ())
+    engine.register_fn(
+        "setting",
+        move |key: &str| -> Result<Dynamic, Box<EvalAltResult>> {
+            let value = match super::plugin_settings() {
+                Some(store) => store.get(&plugin_id, &ui, key),
+                None => ui
+                    .settings
+                    .iter()
+                    .find(|f| f.key == key)
+                    .map(|f| f.default.clone())
+                    .unwrap_or(serde_json::Value::Null),
+            };
+            rhai::serde::to_dynamic(&value).map_err(|e| e.to_string().into())
+        },
+    );
+}
@@ -7,23 +7,32 @@
 //! **模块结构**:
 //! - `mod`: 接口定义。
 //! - `rhai_v1`: Rhai 引擎实现。
+//! - `wasm_v1`: WASM 引擎实现 (wasmi) [仅非 WASM]。
 //! - `host`: 宿主函数注入。
+//! - `cancel`: 调用取消令牌。
+//! - `doc_edit`: 文档编辑桥接 (由服务端实现)。
 
 use crate::plugin::manifest::PluginManifest;
 use anyhow::Result;
 use rhai::Dynamic;
 
+pub mod cancel;
 pub mod chat_stream;
+pub mod doc_edit;
 pub mod host;
 pub mod provider;
 pub mod rhai_v1;
 pub mod tools;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod wasm_v1;
 
 pub use rhai_v1::RhaiRuntime;
+#[cfg(not(target_arch = "wasm32"))]
+pub use wasm_v1::{WasmLimits, WasmRuntime};
 
 /// 插件运行时抽象接口
 ///
-/// 允许未来扩展其他脚本引擎 (e.g., Lua, Wasm)。
+/// 由清单的 `engine` 字段选择实现 (`rhai-v1` / `wasm-v1`)。
 pub trait PluginRuntime: Send + Sync {
     /// 加载插件
     ///
@@ -32,6 +41,15 @@ pub trait PluginRuntime: Send + Sync {
     /// - `script`: 源代码
     fn load(&mut self, manifest: PluginManifest, script: &str) -> Result<()>;
 
+    /// 从入口文件的原始字节加载插件
+    ///
+    /// 默认要求 UTF-8 文本并转交 `load`；二进制格式的运行时需覆盖此方法。
+    fn load_bytes(&mut self, manifest: PluginManifest, bytes: &[u8]) -> Result<()> {
+        let script = std::str::from_utf8(bytes)
+            .map_err(|_| anyhow::anyhow!("Entry script is not valid UTF-8"))?;
+        self.load(manifest, script)
+    }
+
     /// 调用函数
     ///
     /// **参数**:
@@ -7,17 +7,65 @@
 //! **模块化支持**:
 //! 通过 FileModuleResolver 支持 `import "module_name"` 语法，
 //! 允许插件拆分为多个 .rhai 文件。(仅非 WASM 环境)
+//!
+//! **资源限制**:
+//! 按清单 `limits` 的生效值 (`PluginLimits::effective`，截断到宿主上限) 设置
+//! 操作数、字符串/数组/映射大小与调用深度上限；
+//! 墙钟超时与调用方取消 (`cancel::CancelScope`) 在进度回调中检查。
+//! 每次调用使用全局 Scope 的副本，执行期间不持有锁，调用之间互不阻塞。
 
-use super::{PluginRuntime, host};
-use crate::plugin::manifest::PluginManifest;
+use super::{PluginRuntime, cancel, host};
+use crate::plugin::manifest::{PluginLimits, PluginManifest};
 use anyhow::{Result, anyhow};
-use rhai::{AST, Dynamic, Engine, Scope};
+use rhai::{AST, Dynamic, Engine, EvalAltResult, Scope};
+use std::cell::Cell;
 use std::path::PathBuf;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 #[cfg(not(target_arch = "wasm32"))]
 use rhai::module_resolvers::FileModuleResolver;
 
+/// 每隔多少次操作检查一次超时与取消
+const PROGRESS_CHECK_INTERVAL: u64 = 1024;
+
+thread_local! {
+    // 当前线程上正在执行的调用的截止时间
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// RAII guard: 设置当前线程的截止时间，析构时恢复
+struct DeadlineScope {
+    previous: Option<Instant>,
+}
+
+impl DeadlineScope {
+    fn new(timeout_ms: u64) -> Self {
+        let deadline = (timeout_ms > 0).then(|| Instant::now() + Duration::from_millis(timeout_ms));
+        Self {
+            previous: DEADLINE.with(|cell| cell.replace(deadline)),
+        }
+    }
+}
+
+impl Drop for DeadlineScope {
+    fn drop(&mut self) {
+        DEADLINE.with(|cell| cell.set(self.previous));
+    }
+}
+
+/// 进度回调: 返回终止原因时中止脚本
+fn check_progress(ops: u64) -> Option<Dynamic> {
+    if !ops.is_multiple_of(PROGRESS_CHECK_INTERVAL) {
+        return None;
+    }
+    if cancel::is_cancelled() {
+        return Some("cancelled".into());
+    }
+    let expired = DEADLINE.with(|cell| cell.get().is_some_and(|d| Instant::now() >= d));
+    expired.then(|| "timeout".into())
+}
+
 /// Rhai 引擎运行时
 ///
 /// **Invariant**: `base_dir` 必须是插件根目录的有效路径。
@@ -27,6 +75,8 @@ pub struct RhaiRuntime {
     ast: Option<AST>,
     scope: Mutex<Scope<'static>>,
     manifest: PluginManifest,
+    /// 生效的资源限制
+    limits: PluginLimits,
     #[allow(dead_code)]
     base_dir: PathBuf,
 }
@@ -41,6 +91,14 @@ impl RhaiRuntime {
         let mut engine = Engine::new();
         engine.set_max_expr_depths(128, 128);
 
+        let limits = manifest.limits.effective();
+        engine.set_max_operations(limits.max_operations);
+        engine.set_max_string_size(limits.max_string_size);
+        engine.set_max_array_size(limits.max_array_size);
+        engine.set_max_map_size(limits.max_map_size);
+        engine.set_max_call_levels(limits.max_call_levels);
+        engine.on_progress(check_progress);
+
         // 配置模块解析器 (仅非 WASM 环境支持文件系统)
         #[cfg(not(target_arch = "wasm32"))]
         {
@@ -56,6 +114,7 @@ impl RhaiRuntime {
             ast: None,
             scope: Mutex::new(Scope::new()),
             manifest,
+            limits,
             base_dir,
         }
     }
@@ -75,6 +134,7 @@ impl PluginRuntime for RhaiRuntime {
             .lock()
             .map_err(|_| anyhow!("Failed to lock plugin scope"))?;
 
+        let _deadline = DeadlineScope::new(self.limits.timeout_ms);
         self.engine
             .run_ast_with_scope(&mut scope, &ast)
             .map_err(|e| anyhow!("Failed to initialize plugin: {}", e))?;
@@ -89,15 +149,24 @@ impl PluginRuntime for RhaiRuntime {
             .as_ref()
             .ok_or_else(|| anyhow!("Plugin not loaded"))?;
 
-        // 警告: 持有 Scope 锁期间阻止重入 (Reentrancy)
+        // 仅在复制全局 Scope 时持锁；call_fn 会回退 Scope，副本与原实现等价
         let mut scope = self
             .scope
             .lock()
-            .map_err(|_| anyhow!("Failed to lock plugin scope"))?;
+            .map_err(|_| anyhow!("Failed to lock plugin scope"))?
+            .clone();
 
+        let _deadline = DeadlineScope::new(self.limits.timeout_ms);
         self.engine
             .call_fn(&mut scope, ast, fn_name, args)
-            .map_err(|e| anyhow!("Runtime error in function '{}': {}", fn_name, e))
+            .map_err(|e| match *e {
+                EvalAltResult::ErrorTerminated(reason, _) => anyhow!(
+                    "Runtime error in function '{}': terminated ({})",
+                    fn_name,
+                    reason
+                ),
+                e => anyhow!("Runtime error in function '{}': {}", fn_name, e),
+            })
     }
 
     fn manifest(&self) -> &PluginManifest {
@@ -119,6 +188,10 @@ mod tests {
             name: "Test".into(),
             version: "0.1".into(),
             entry: "main.rhai".into(),
+            engine: Default::default(),
+            events: Vec::new(),
+            limits: Default::default(),
+            ui: Default::default(),
             capabilities: Default::default(),
         };
         let base_dir = PathBuf::from(".");
@@ -130,6 +203,83 @@ mod tests {
         assert_eq!(res.as_int().unwrap(), 3);
     }
 
+    fn limited(limits: crate::plugin::manifest::PluginLimits, script: &str) -> RhaiRuntime {
+        let manifest = PluginManifest {
+            id: "limited".into(),
+            name: "Limited".into(),
+            version: "0.1".into(),
+            entry: "main.rhai".into(),
+            engine: Default::default(),
+            events: Vec::new(),
+            limits,
+            ui: Default::default(),
+            capabilities: Default::default(),
+        };
+        let mut rt = RhaiRuntime::new(manifest.clone(), PathBuf::from("."));
+        rt.load(manifest, script).unwrap();
+        rt
+    }
+
+    #[test]
+    fn test_rhai_limits() {
+        use crate::plugin::manifest::PluginLimits;
+
+        let rt = limited(
+            PluginLimits {
+                max_operations: 10_000,
+                ..Default::default()
+            },
+            "fn spin() { loop {} }",
+        );
+        assert!(rt.call("spin", vec![]).is_err());
+
+        let rt = limited(
+            PluginLimits {
+                timeout_ms: 50,
+                ..Default::default()
+            },
+            "fn spin() { loop {} }",
+        );
+        let err = rt.call("spin", vec![]).unwrap_err().to_string();
+        assert!(err.contains("timeout"), "{err}");
+
+        let rt = limited(
+            PluginLimits {
+                max_string_size: 1024,
+                ..Default::default()
+            },
+            r#"fn grow() { let s = "x"; loop { s += s; } }"#,
+        );
+        assert!(rt.call("grow", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_rhai_cancel() {
+        use crate::plugin::manifest::PluginLimits;
+        use crate::plugin::runtime::cancel::{CancelScope, CancelToken};
+        use std::sync::Arc;
+
+        let rt = Arc::new(limited(
+            PluginLimits::default(),
+            "fn spin() { loop {} } fn ping() { 1 }",
+        ));
+        let token = CancelToken::new();
+        let handle = {
+            let (rt, token) = (rt.clone(), token.clone());
+            std::thread::spawn(move || {
+                let _cancel = CancelScope::new(token);
+                rt.call("spin", vec![])
+            })
+        };
+        // 长时间运行的调用不阻塞其他调用
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(rt.call("ping", vec![]).unwrap().as_int().unwrap(), 1);
+
+        token.cancel();
+        let err = handle.join().unwrap().unwrap_err().to_string();
+        assert!(err.contains("cancelled"), "{err}");
+    }
+
     #[test]
     #[cfg(not(target_arch = "wasm32"))]
     fn test_rhai_security() {
@@ -147,6 +297,10 @@ mod tests {
             name: "OK".into(),
             version: "0.1".into(),
             entry: "m.rhai".into(),
+            engine: Default::default(),
+            events: Vec::new(),
+            limits: Default::default(),
+            ui: Default::default(),
             capabilities: cap,
         };
         let mut rt = RhaiRuntime::new(manifest.clone(), base_dir.clone());
@@ -165,6 +319,10 @@ mod tests {
             name: "Deny".into(),
             version: "0.1".into(),
             entry: "m.rhai".into(),
+            engine: Default::default(),
+            events: Vec::new(),
+            limits: Default::default(),
+            ui: Default::default(),
             capabilities: Default::default(),
         };
         let mut rt_deny = RhaiRuntime::new(manifest_deny.clone(), base_dir);
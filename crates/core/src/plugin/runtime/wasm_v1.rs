@@ -0,0 +1,346 @@
+// crates\core\src\plugin\runtime\wasm_v1.rs
+//! # WASM Runtime Implementation
+//!
+//! **功能**:
+//! 基于 wasmi 解释器实现的插件运行时，插件以单个 `.wasm` 模块分发
+//! (例如 Rust 以 `wasm32-unknown-unknown` 目标编译的 `cdylib`)。
+//!
+//! **ABI (`wasm-v1`)**:
+//! - 模块导出 `memory` 与 `alloc(len: i32) -> i32` (宿主写入数据前向插件申请内存)。
+//! - 插件函数签名为 `(ptr: i32, len: i32) -> i64`: 入参为参数数组的 JSON，
+//!   返回值为结果 JSON 的 `(ptr << 32) | len`，长度 0 表示无返回值。
+//! - 导入 `deve.host_call(name_ptr, name_len, args_ptr, args_len) -> i64`: 按名称调用
+//!   宿主函数 (与 Rhai 插件同一套 `host` API)，返回 `{"ok": 值}` 或 `{"err": 消息}` 的 JSON。
+//!
+//! **安全**:
+//! 宿主函数经由 `host::register_core_api` 注册，Capability 检查与 Rhai 插件一致。
+//! 每次调用重置燃料 (fuel)，线性内存大小受 `WasmLimits` 限制。
+//! 解释执行无法从外部中断，调用方取消 (`cancel::CancelScope`) 在下一次 `host_call` 时生效。
+
+use super::{PluginRuntime, cancel, host};
+use crate::plugin::manifest::PluginManifest;
+use anyhow::{Result, anyhow, bail};
+use rhai::{Dynamic, Scope};
+use serde_json::{Value, json};
+use std::sync::Mutex;
+use wasmi::{
+    AsContext, AsContextMut, Caller, Config, Engine, Extern, Instance, Linker, Memory, Module,
+    Store, StoreLimits, StoreLimitsBuilder, TrapCode, TypedFunc,
+};
+
+/// WASM 插件资源限制
+#[derive(Debug, Clone, Copy)]
+pub struct WasmLimits {
+    /// 单次调用可消耗的燃料 (约等于执行的指令数)
+    pub fuel: u64,
+    /// 线性内存上限 (字节)
+    pub max_memory_bytes: usize,
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        Self {
+            fuel: 500_000_000,
+            max_memory_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+/// Store 关联的宿主状态
+struct HostState {
+    /// 注册了宿主 API 的 Rhai 引擎，仅用于分发 `host_call`
+    host: rhai::Engine,
+    limits: StoreLimits,
+}
+
+struct Loaded {
+    store: Store<HostState>,
+    instance: Instance,
+}
+
+/// WASM 插件运行时
+///
+/// **Invariant**: `loaded` 为 `None` 时所有调用返回 "Plugin not loaded"。
+pub struct WasmRuntime {
+    engine: Engine,
+    loaded: Mutex<Option<Loaded>>,
+    manifest: PluginManifest,
+    limits: WasmLimits,
+}
+
+impl WasmRuntime {
+    /// 以默认资源限制创建运行时
+    pub fn new(manifest: PluginManifest) -> Self {
+        Self::with_limits(manifest, WasmLimits::default())
+    }
+
+    /// 以指定资源限制创建运行时
+    pub fn with_limits(manifest: PluginManifest, limits: WasmLimits) -> Self {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        Self {
+            engine: Engine::new(&config),
+            loaded: Mutex::new(None),
+            manifest,
+            limits,
+        }
+    }
+}
+
+impl PluginRuntime for WasmRuntime {
+    fn load(&mut self, manifest: PluginManifest, script: &str) -> Result<()> {
+        self.load_bytes(manifest, script.as_bytes())
+    }
+
+    fn load_bytes(&mut self, _manifest: PluginManifest, bytes: &[u8]) -> Result<()> {
+        let module = Module::new(&self.engine, bytes)
+            .map_err(|e| anyhow!("Failed to compile wasm module: {}", e))?;
+
+        let mut host_engine = rhai::Engine::new();
+        host_engine.disable_symbol("eval");
+        host::register_core_api(&mut host_engine, &self.manifest);
+        let state = HostState {
+            host: host_engine,
+            limits: StoreLimitsBuilder::new()
+                .memory_size(self.limits.max_memory_bytes)
+                .instances(1)
+                .build(),
+        };
+
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(self.limits.fuel)?;
+
+        let mut linker = Linker::new(&self.engine);
+        linker.func_wrap("deve", "host_call", host_call)?;
+        let instance = linker
+            .instantiate_and_start(&mut store, &module)
+            .map_err(|e| anyhow!("Failed to initialize plugin: {}", e))?;
+
+        *self
+            .loaded
+            .lock()
+            .map_err(|_| anyhow!("Failed to lock plugin store"))? =
+            Some(Loaded { store, instance });
+        Ok(())
+    }
+
+    fn call(&self, fn_name: &str, args: Vec<Dynamic>) -> Result<Dynamic> {
+        let mut guard = self
+            .loaded
+            .lock()
+            .map_err(|_| anyhow!("Failed to lock plugin store"))?;
+        let Loaded { store, instance } =
+            guard.as_mut().ok_or_else(|| anyhow!("Plugin not loaded"))?;
+
+        let func = instance
+            .get_typed_func::<(i32, i32), i64>(&*store, fn_name)
+            .map_err(|e| anyhow!("Function '{}' not found: {}", fn_name, e))?;
+        let memory = instance
+            .get_memory(&*store, "memory")
+            .ok_or_else(|| anyhow!("Plugin does not export 'memory'"))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&*store, "alloc")?;
+
+        store.set_fuel(self.limits.fuel)?;
+        let input = serde_json::to_vec(&args)?;
+        let (ptr, len) = unpack(write_guest(&mut *store, memory, alloc, &input)?);
+        let packed = func
+            .call(&mut *store, (ptr, len))
+            .map_err(|e| match e.as_trap_code() {
+                Some(TrapCode::OutOfFuel) => {
+                    anyhow!("Runtime error in function '{}': fuel exhausted", fn_name)
+                }
+                _ => anyhow!("Runtime error in function '{}': {}", fn_name, e),
+            })?;
+
+        let (ptr, len) = unpack(packed);
+        if len == 0 {
+            return Ok(Dynamic::UNIT);
+        }
+        let output = read_guest(&*store, memory, ptr, len)?;
+        let value: Value = serde_json::from_slice(&output)
+            .map_err(|e| anyhow!("Invalid result from function '{}': {}", fn_name, e))?;
+        rhai::serde::to_dynamic(&value).map_err(|e| anyhow!("{}", e))
+    }
+
+    fn manifest(&self) -> &PluginManifest {
+        &self.manifest
+    }
+}
+
+fn unpack(packed: i64) -> (i32, i32) {
+    ((packed >> 32) as i32, packed as i32)
+}
+
+fn read_guest(ctx: impl AsContext, memory: Memory, ptr: i32, len: i32) -> Result<Vec<u8>> {
+    let mut buf = vec![0; len as u32 as usize];
+    memory
+        .read(ctx, ptr as u32 as usize, &mut buf)
+        .map_err(|e| anyhow!("Guest memory read out of bounds: {}", e))?;
+    Ok(buf)
+}
+
+/// 通过插件的 `alloc` 申请内存并写入，返回 `(ptr << 32) | len`
+fn write_guest(
+    mut ctx: impl AsContextMut,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    bytes: &[u8],
+) -> Result<i64> {
+    let len = i32::try_from(bytes.len()).map_err(|_| anyhow!("Payload too large"))?;
+    let ptr = alloc.call(&mut ctx, len)?;
+    memory
+        .write(&mut ctx, ptr as u32 as usize, bytes)
+        .map_err(|e| anyhow!("Guest memory write out of bounds: {}", e))?;
+    Ok(((ptr as u32 as i64) << 32) | len as u32 as i64)
+}
+
+/// 按名称调用宿主函数，参数为 JSON 数组
+fn dispatch(host: &rhai::Engine, name: &str, args: &[u8]) -> Result<Value> {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        bail!("Invalid host function name '{}'", name);
+    }
+    let args: Vec<Value> = serde_json::from_slice(args)?;
+
+    // 参数以变量传入，表达式中只出现校验过的函数名
+    let mut scope = Scope::new();
+    let mut params = Vec::with_capacity(args.len());
+    for (i, arg) in args.iter().enumerate() {
+        let var = format!("a{i}");
+        scope.push_dynamic(
+            var.clone(),
+            rhai::serde::to_dynamic(arg).map_err(|e| anyhow!("{}", e))?,
+        );
+        params.push(var);
+    }
+    let result: Dynamic = host
+        .eval_expression_with_scope(&mut scope, &format!("{}({})", name, params.join(", ")))
+        .map_err(|e| anyhow!("{}", e))?;
+    Ok(serde_json::to_value(&result)?)
+}
+
+fn host_call(
+    mut caller: Caller<'_, HostState>,
+    name_ptr: i32,
+    name_len: i32,
+    args_ptr: i32,
+    args_len: i32,
+) -> Result<i64, wasmi::Error> {
+    if cancel::is_cancelled() {
+        return Err(wasmi::Error::new("cancelled"));
+    }
+    let exports = (
+        caller.get_export("memory").and_then(Extern::into_memory),
+        caller.get_export("alloc").and_then(Extern::into_func),
+    );
+    let (Some(memory), Some(alloc)) = exports else {
+        return Err(wasmi::Error::new("Plugin must export 'memory' and 'alloc'"));
+    };
+    let alloc = alloc.typed::<i32, i32>(&caller)?;
+
+    let reply = (|| {
+        let name = String::from_utf8(read_guest(&caller, memory, name_ptr, name_len)?)?;
+        let args = read_guest(&caller, memory, args_ptr, args_len)?;
+        dispatch(&caller.data().host, &name, &args)
+    })();
+    let reply = match reply {
+        Ok(value) => json!({ "ok": value }),
+        Err(e) => json!({ "err": e.to_string() }),
+    };
+    write_guest(&mut caller, memory, alloc, reply.to_string().as_bytes())
+        .map_err(|e| wasmi::Error::new(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::manifest::{Capability, PluginEngine};
+    use std::io::Write;
+
+    const MODULE: &str = r#"
+        (module
+          (import "deve" "host_call" (func $host_call (param i32 i32 i32 i32) (result i64)))
+          (memory (export "memory") 1)
+          (global $heap (mut i32) (i32.const 1024))
+          (data (i32.const 0) "fs_read")
+          (func (export "alloc") (param $len i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr (global.get $heap))
+            (global.set $heap (i32.add (global.get $heap) (local.get $len)))
+            (local.get $ptr))
+          (func (export "echo") (param $ptr i32) (param $len i32) (result i64)
+            (i64.or
+              (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+              (i64.extend_i32_u (local.get $len))))
+          (func (export "read") (param $ptr i32) (param $len i32) (result i64)
+            (call $host_call (i32.const 0) (i32.const 7) (local.get $ptr) (local.get $len)))
+          (func (export "spin") (param i32 i32) (result i64)
+            (loop $l (br $l))
+            (i64.const 0)))
+    "#;
+
+    fn runtime(capabilities: Capability, limits: WasmLimits, wat: &str) -> Result<WasmRuntime> {
+        let manifest = PluginManifest {
+            id: "wasm".into(),
+            name: "Wasm".into(),
+            version: "0.1".into(),
+            entry: "plugin.wasm".into(),
+            engine: PluginEngine::WasmV1,
+            events: Vec::new(),
+            limits: Default::default(),
+            ui: Default::default(),
+            capabilities,
+        };
+        let mut rt = WasmRuntime::with_limits(manifest.clone(), limits);
+        rt.load_bytes(manifest, &wat::parse_str(wat).unwrap())?;
+        Ok(rt)
+    }
+
+    #[test]
+    fn test_wasm_call_roundtrip() {
+        let rt = runtime(Default::default(), Default::default(), MODULE).unwrap();
+        let res = rt.call("echo", vec![1.into(), "a".into()]).unwrap();
+        let res: Value = rhai::serde::from_dynamic(&res).unwrap();
+        assert_eq!(res, json!([1, "a"]));
+        assert!(rt.call("missing", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_wasm_host_call_capability() {
+        let mut temp = tempfile::NamedTempFile::new().unwrap();
+        write!(temp, "secret").unwrap();
+        let path = temp.path().to_path_buf();
+        let arg: Dynamic = path.to_str().unwrap().into();
+
+        let mut cap = Capability::default();
+        cap.allow_fs_read.push(path.clone());
+        let rt = runtime(cap, Default::default(), MODULE).unwrap();
+        let res: Value =
+            rhai::serde::from_dynamic(&rt.call("read", vec![arg.clone()]).unwrap()).unwrap();
+        assert_eq!(res, json!({ "ok": "secret" }));
+
+        let rt_deny = runtime(Default::default(), Default::default(), MODULE).unwrap();
+        let res: Value =
+            rhai::serde::from_dynamic(&rt_deny.call("read", vec![arg]).unwrap()).unwrap();
+        assert!(res["err"].as_str().unwrap().contains("Permission denied"));
+    }
+
+    #[test]
+    fn test_wasm_limits() {
+        let limits = WasmLimits {
+            fuel: 100_000,
+            max_memory_bytes: 1024 * 1024,
+        };
+        let rt = runtime(Default::default(), limits, MODULE).unwrap();
+        let err = rt.call("spin", vec![]).unwrap_err();
+        assert!(err.to_string().contains("fuel exhausted"));
+        // 燃料按调用重置
+        assert!(rt.call("echo", vec![]).is_ok());
+
+        let big = r#"(module (memory (export "memory") 32))"#;
+        assert!(runtime(Default::default(), Default::default(), big).is_ok());
+        assert!(runtime(Default::default(), limits, big).is_err());
+    }
+}
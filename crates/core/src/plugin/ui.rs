@@ -0,0 +1,426 @@
+// crates\core\src\plugin\ui.rs
+//! # Plugin UI Contributions (插件 UI 贡献)
+//!
+//! **架构作用**:
+//! 插件在清单的 `ui` 字段中声明命令、侧边栏面板与设置项；前端据此渲染，
+//! 不执行插件代码 (JSON UI 协议)。
+//!
+//! **核心功能清单**:
+//! - `PluginUi`: 清单 `ui` 字段 (`commands` / `panels` / `settings`)。
+//! - `Widget`: 面板处理函数返回的小型组件描述 (list / tree / form / markdown)。
+//! - `PluginSettingsStore`: 按插件持久化的设置值 (`.deve/plugin-settings.json`)。
+//! - `PluginInfo`: 下发给前端的插件摘要 (清单 UI + 当前设置值)。
+//!
+//! **传输**:
+//! `PluginInfo` 经 WebSocket 以 bincode 下发，bincode 无法解码 `serde_json::Value`；
+//! 设置值与默认值在非人类可读格式中编码为 JSON 字符串，清单与设置文件中仍为原生 JSON 值。
+//!
+//! **类型**: Core MUST (核心必选)
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 单个插件的设置值 (键 -> JSON 值)
+pub type SettingValues = BTreeMap<String, serde_json::Value>;
+
+/// 清单 `ui` 字段
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct PluginUi {
+    pub commands: Vec<PluginCommand>,
+    pub panels: Vec<PluginPanel>,
+    pub settings: Vec<SettingField>,
+}
+
+impl PluginUi {
+    /// 设置项的清单默认值
+    pub fn default_settings(&self) -> SettingValues {
+        self.settings
+            .iter()
+            .map(|f| (f.key.clone(), f.default.clone()))
+            .collect()
+    }
+}
+
+/// 命令面板中的命令，执行时以无参数调用 `handler`
+///
+/// 例: `{"id": "word-count", "title": "Count words", "handler": "count_words"}`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PluginCommand {
+    pub id: String,
+    pub title: String,
+    pub handler: String,
+}
+
+/// 侧边栏面板，打开或刷新时以无参数调用 `handler`，返回值须为 `Widget`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PluginPanel {
+    pub id: String,
+    pub title: String,
+    pub handler: String,
+}
+
+/// 设置项类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingKind {
+    #[default]
+    String,
+    Number,
+    Boolean,
+    /// 从 `options` 中选择一项
+    Select,
+}
+
+/// 设置项 (清单 `ui.settings` 的元素)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SettingField {
+    pub key: String,
+    pub label: String,
+    #[serde(default)]
+    pub kind: SettingKind,
+    #[serde(default, with = "json_text")]
+    pub default: serde_json::Value,
+    #[serde(default)]
+    pub options: Vec<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl SettingField {
+    /// 值是否符合该设置项的类型
+    pub fn accepts(&self, value: &serde_json::Value) -> bool {
+        match self.kind {
+            SettingKind::String => value.is_string(),
+            SettingKind::Number => value.is_number(),
+            SettingKind::Boolean => value.is_boolean(),
+            SettingKind::Select => value
+                .as_str()
+                .is_some_and(|v| self.options.iter().any(|o| o == v)),
+        }
+    }
+}
+
+/// 组件触发的插件调用 (`handler` 以 `args` 为参数)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WidgetAction {
+    pub handler: String,
+    #[serde(default)]
+    pub args: Vec<serde_json::Value>,
+}
+
+/// 面板组件 (面板处理函数的返回值)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Widget {
+    List {
+        items: Vec<ListItem>,
+    },
+    Tree {
+        nodes: Vec<TreeItem>,
+    },
+    /// 提交时以 `#{ 字段键: 值 }` 为唯一参数调用 `submit`
+    Form {
+        fields: Vec<SettingField>,
+        submit: String,
+        #[serde(default)]
+        submit_label: Option<String>,
+    },
+    Markdown {
+        content: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ListItem {
+    pub label: String,
+    #[serde(default)]
+    pub detail: Option<String>,
+    #[serde(default)]
+    pub action: Option<WidgetAction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TreeItem {
+    pub label: String,
+    #[serde(default)]
+    pub children: Vec<TreeItem>,
+    #[serde(default)]
+    pub action: Option<WidgetAction>,
+}
+
+/// 下发给前端的插件摘要
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PluginInfo {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub ui: PluginUi,
+    /// 生效的设置值 (已保存值覆盖清单默认值)
+    #[serde(with = "json_text::map")]
+    pub settings: SettingValues,
+}
+
+/// JSON 值的双格式编码: 人类可读格式 (JSON) 中为原生值，bincode 中为 JSON 字符串
+mod json_text {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(value: &serde_json::Value, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            value.serialize(s)
+        } else {
+            s.serialize_str(&value.to_string())
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<serde_json::Value, D::Error> {
+        if d.is_human_readable() {
+            serde_json::Value::deserialize(d)
+        } else {
+            let text = String::deserialize(d)?;
+            serde_json::from_str(&text).map_err(D::Error::custom)
+        }
+    }
+
+    pub mod map {
+        use super::super::SettingValues;
+        use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+        use std::collections::BTreeMap;
+
+        pub fn serialize<S: Serializer>(values: &SettingValues, s: S) -> Result<S::Ok, S::Error> {
+            if s.is_human_readable() {
+                values.serialize(s)
+            } else {
+                values
+                    .iter()
+                    .map(|(k, v)| (k, v.to_string()))
+                    .collect::<BTreeMap<_, _>>()
+                    .serialize(s)
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<SettingValues, D::Error> {
+            if d.is_human_readable() {
+                SettingValues::deserialize(d)
+            } else {
+                BTreeMap::<String, String>::deserialize(d)?
+                    .into_iter()
+                    .map(|(k, v)| {
+                        serde_json::from_str(&v)
+                            .map(|v| (k, v))
+                            .map_err(D::Error::custom)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use store::PluginSettingsStore;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod store {
+    use super::{PluginUi, SettingValues};
+    use anyhow::Result;
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+    use std::sync::RwLock;
+
+    /// 插件设置存储
+    ///
+    /// **Invariant**: 每次写入后整体落盘，文件格式为 `{插件 ID: {键: 值}}`。
+    pub struct PluginSettingsStore {
+        path: PathBuf,
+        values: RwLock<BTreeMap<String, SettingValues>>,
+    }
+
+    impl PluginSettingsStore {
+        /// 从 `.deve/plugin-settings.json` 加载 (不存在或无法解析时为空)
+        pub fn load(deve_dir: &std::path::Path) -> Self {
+            let path = deve_dir.join("plugin-settings.json");
+            let values = match std::fs::read_to_string(&path) {
+                Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                    tracing::warn!("Invalid {:?}, ignoring: {}", path, e);
+                    BTreeMap::new()
+                }),
+                Err(_) => BTreeMap::new(),
+            };
+            Self {
+                path,
+                values: RwLock::new(values),
+            }
+        }
+
+        /// 读取生效的设置值 (已保存值优先，否则为清单默认值)
+        pub fn get(&self, plugin_id: &str, ui: &PluginUi, key: &str) -> serde_json::Value {
+            let saved = self
+                .values
+                .read()
+                .ok()
+                .and_then(|v| v.get(plugin_id).and_then(|m| m.get(key)).cloned());
+            saved.unwrap_or_else(|| {
+                ui.settings
+                    .iter()
+                    .find(|f| f.key == key)
+                    .map(|f| f.default.clone())
+                    .unwrap_or(serde_json::Value::Null)
+            })
+        }
+
+        /// 插件全部设置的生效值
+        pub fn effective(&self, plugin_id: &str, ui: &PluginUi) -> SettingValues {
+            let mut values = ui.default_settings();
+            if let Ok(saved) = self.values.read()
+                && let Some(saved) = saved.get(plugin_id)
+            {
+                for (key, value) in saved {
+                    if values.contains_key(key) {
+                        values.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+            values
+        }
+
+        /// 保存设置值，键须在清单中声明且类型匹配
+        pub fn set(
+            &self,
+            plugin_id: &str,
+            ui: &PluginUi,
+            key: &str,
+            value: serde_json::Value,
+        ) -> Result<()> {
+            let field = ui
+                .settings
+                .iter()
+                .find(|f| f.key == key)
+                .ok_or_else(|| anyhow::anyhow!("Unknown setting '{}'", key))?;
+            if !field.accepts(&value) {
+                anyhow::bail!("Invalid value for setting '{}'", key);
+            }
+            let mut values = self
+                .values
+                .write()
+                .map_err(|_| anyhow::anyhow!("Settings lock poisoned"))?;
+            values
+                .entry(plugin_id.to_string())
+                .or_default()
+                .insert(key.to_string(), value);
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&self.path, serde_json::to_string_pretty(&*values)?)?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn ui() -> PluginUi {
+        serde_json::from_value(json!({
+            "commands": [{"id": "wc", "title": "Count words", "handler": "count_words"}],
+            "settings": [
+                {"key": "lang", "label": "Language", "kind": "select", "options": ["en", "zh"], "default": "en"},
+                {"key": "limit", "label": "Limit", "kind": "number", "default": 10}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_widget_serde() {
+        let widget: Widget = serde_json::from_value(json!({
+            "type": "tree",
+            "nodes": [{"label": "a", "children": [{"label": "b", "action": {"handler": "open", "args": ["b"]}}]}]
+        }))
+        .unwrap();
+        let Widget::Tree { nodes } = widget else {
+            panic!("expected tree");
+        };
+        assert_eq!(
+            nodes[0].children[0].action.as_ref().unwrap().handler,
+            "open"
+        );
+
+        assert!(serde_json::from_value::<Widget>(json!({"type": "iframe"})).is_err());
+        assert_eq!(ui().commands[0].handler, "count_words");
+    }
+
+    #[test]
+    fn test_plugin_list_bincode_roundtrip() {
+        use crate::protocol::ServerMessage;
+
+        let manifest_ui = ui();
+        let msg = ServerMessage::PluginList {
+            plugins: vec![PluginInfo {
+                id: "p".into(),
+                name: "P".into(),
+                version: "0.1".into(),
+                settings: manifest_ui.default_settings(),
+                ui: manifest_ui,
+            }],
+        };
+        let bytes = bincode::serialize(&msg).unwrap();
+        match bincode::deserialize::<ServerMessage>(&bytes).unwrap() {
+            ServerMessage::PluginList { plugins } => {
+                assert_eq!(plugins[0].settings["limit"], json!(10));
+                assert_eq!(plugins[0].ui.settings[0].default, json!("en"));
+            }
+            _ => panic!("expected PluginList"),
+        }
+
+        // JSON 中仍为原生值
+        let text = serde_json::to_value(&ui().settings[1]).unwrap();
+        assert_eq!(text["default"], json!(10));
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn test_settings_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let ui = ui();
+        let store = PluginSettingsStore::load(dir.path());
+        assert_eq!(store.get("p", &ui, "lang"), json!("en"));
+
+        store.set("p", &ui, "lang", json!("zh")).unwrap();
+        assert!(store.set("p", &ui, "lang", json!("fr")).is_err());
+        assert!(store.set("p", &ui, "limit", json!("ten")).is_err());
+        assert!(store.set("p", &ui, "missing", json!(1)).is_err());
+
+        let reloaded = PluginSettingsStore::load(dir.path());
+        assert_eq!(reloaded.get("p", &ui, "lang"), json!("zh"));
+        assert_eq!(reloaded.get("other", &ui, "lang"), json!("en"));
+        let effective = reloaded.effective("p", &ui);
+        assert_eq!(effective["limit"], json!(10));
+        assert_eq!(effective["lang"], json!("zh"));
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn test_setting_host_fn() {
+        use crate::plugin::manifest::PluginManifest;
+        use crate::plugin::runtime::{PluginRuntime, RhaiRuntime};
+
+        let manifest = PluginManifest {
+            id: "p".into(),
+            name: "P".into(),
+            version: "0.1".into(),
+            entry: "main.rhai".into(),
+            engine: Default::default(),
+            events: Vec::new(),
+            limits: Default::default(),
+            ui: ui(),
+            capabilities: Default::default(),
+        };
+        let mut rt = RhaiRuntime::new(manifest.clone(), std::path::PathBuf::from("."));
+        rt.load(manifest, r#"fn get(k) { setting(k) }"#).unwrap();
+        let limit = rt.call("get", vec!["limit".into()]).unwrap();
+        assert_eq!(limit.as_int().unwrap(), 10);
+        assert!(rt.call("get", vec!["missing".into()]).unwrap().is_unit());
+    }
+}
@@ -0,0 +1,134 @@
+// crates\core\src\protocol
+//! # Agent Conversation Messages (智能体会话)
+//!
+//! 服务端智能体循环的会话记录，前后端共用。
+//! 消息按 OpenAI Chat Completions 的角色划分 (`user` / `assistant` / `tool`)，
+//! 可原样回放给模型以恢复会话。
+//! 经 WebSocket 以 bincode 传输：字段不可按条件省略，工具参数保留为 JSON 字符串。
+
+use serde::{Deserialize, Serialize};
+
+/// 模型发起的一次工具调用
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentToolCall {
+    pub id: String,
+    /// 暴露给模型的工具名 (MCP 工具为 `mcp__{server}__{tool}`)
+    pub name: String,
+    /// 参数 JSON 字符串 (模型原样输出，可能不合法)
+    pub arguments: String,
+}
+
+/// 会话中的一条消息
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentMessage {
+    /// `user` / `assistant` / `tool`
+    pub role: String,
+    /// 发送给模型的正文 (用户消息已展开 MCP 提示词与资源)
+    pub content: String,
+    /// 用户实际输入的文本 (与 `content` 不同时才记录)，仅用于展示
+    #[serde(default)]
+    pub display: Option<String>,
+    /// `assistant` 消息请求的工具调用
+    #[serde(default)]
+    pub tool_calls: Vec<AgentToolCall>,
+    /// `tool` 消息对应的调用 ID
+    #[serde(default)]
+    pub tool_call_id: Option<String>,
+    /// 工具执行失败 (仅 `tool` 消息)
+    #[serde(default)]
+    pub is_error: bool,
+    #[serde(default)]
+    pub ts_ms: u64,
+}
+
+impl AgentMessage {
+    pub fn user(content: String, display: Option<String>, ts_ms: u64) -> Self {
+        Self {
+            role: "user".into(),
+            content,
+            display,
+            ts_ms,
+            ..Default::default()
+        }
+    }
+
+    pub fn assistant(content: String, tool_calls: Vec<AgentToolCall>, ts_ms: u64) -> Self {
+        Self {
+            role: "assistant".into(),
+            content,
+            tool_calls,
+            ts_ms,
+            ..Default::default()
+        }
+    }
+
+    pub fn tool(call_id: String, content: String, is_error: bool, ts_ms: u64) -> Self {
+        Self {
+            role: "tool".into(),
+            content,
+            tool_call_id: Some(call_id),
+            is_error,
+            ts_ms,
+            ..Default::default()
+        }
+    }
+
+    /// 界面展示文本
+    pub fn display_text(&self) -> &str {
+        self.display.as_deref().unwrap_or(&self.content)
+    }
+}
+
+/// 会话列表条目
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationSummary {
+    pub id: String,
+    /// 取自首条用户消息
+    pub title: String,
+    pub created_ms: u64,
+    pub updated_ms: u64,
+    pub message_count: u32,
+}
+
+/// 完整会话
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Conversation {
+    pub summary: ConversationSummary,
+    pub messages: Vec<AgentMessage>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversation_bincode_roundtrip() {
+        let conversation = Conversation {
+            summary: ConversationSummary {
+                id: "c1".into(),
+                title: "hello".into(),
+                created_ms: 1,
+                updated_ms: 2,
+                message_count: 3,
+            },
+            messages: vec![
+                AgentMessage::user("expanded".into(), Some("hi".into()), 1),
+                AgentMessage::assistant(
+                    String::new(),
+                    vec![AgentToolCall {
+                        id: "call_1".into(),
+                        name: "read_file".into(),
+                        arguments: r#"{"path":"a.md"}"#.into(),
+                    }],
+                    2,
+                ),
+                AgentMessage::tool("call_1".into(), "text".into(), false, 3),
+            ],
+        };
+        let bytes = bincode::serialize(&conversation).unwrap();
+        let back: Conversation = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(back, conversation);
+        assert_eq!(back.messages[0].display_text(), "hi");
+        assert_eq!(back.messages[2].display_text(), "text");
+    }
+}
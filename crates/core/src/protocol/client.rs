@@ -1,8 +1,9 @@
 // crates\core\src\protocol
 //! # Client Messages (客户端消息)
 
+use super::DocQuery;
 use crate::models::{DocId, Op, PeerId, VersionVector};
-use crate::security::EncryptedOp;
+use crate::security::{EncryptedChunk, EncryptedOp};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,9 +43,12 @@ pub enum ClientMessage {
         ops: Vec<EncryptedOp>, // Snapshot Ops
     },
     /// 客户端发送编辑操作 (针对特定文档)
+    ///
+    /// `base_seq`: 生成该操作时已应用的最后一个服务端序号，服务端据此变换并发操作
     Edit {
         doc_id: DocId,
         op: Op,
+        base_seq: u64,
         client_id: u64,
     },
     /// 请求文档的完整操作历史
@@ -70,7 +74,7 @@ pub enum ClientMessage {
         fn_name: String,
         args: Vec<serde_json::Value>,
     },
-    /// 全文搜索查询
+    /// 全文搜索查询 (支持 `path:` / `tag:` / `after:` / `before:` / `in:` 过滤与短语、模糊语法)
     Search { query: String, limit: u32 },
 
     // === Manual Merge Messages (手动合并模式) ===
@@ -134,4 +138,84 @@ pub enum ClientMessage {
     /// **Pre-condition**: 客户端已通过 JWT 认证。
     /// **Post-condition**: 服务端回复 `ServerMessage::KeyProvide`。
     RequestKey,
+
+    // === Two-Factor Step-Up (第二因素重新校验) ===
+    /// 在当前 WS 会话内提交 TOTP 口令或恢复码
+    ///
+    /// **Post-condition**: 服务端回复 `ServerMessage::MfaVerified`，
+    /// 成功后会话在新鲜度窗口内可执行敏感操作 (RequestKey, DeletePeer)。
+    VerifyTotp { code: String },
+
+    // === Agent Conversations (服务端智能体会话) ===
+    /// 在会话中发送一条消息并启动智能体循环
+    ///
+    /// `conversation_id` 为空时新建会话；`context` 与插件聊天相同
+    /// (`current_file` / `prompt` / `resources`)。
+    /// **Post-condition**: 服务端依次推送 `ChatStarted`、`ChatChunk` / `ChatToolCallDelta` /
+    /// `ChatToolResult`，最后以 `ChatDone` 结束。
+    ChatSend {
+        req_id: String,
+        conversation_id: Option<String>,
+        message: String,
+        context: serde_json::Value,
+    },
+    /// 取消正在运行的智能体循环 (响应 `ChatDone { reason: "cancelled" }`)
+    ChatCancel { req_id: String },
+    /// 请求会话列表 (响应 `ConversationList`)
+    ListConversations,
+    /// 请求完整会话 (响应 `ConversationData`)
+    GetConversation { id: String },
+    /// 删除会话 (响应 `ConversationDeleted`)
+    DeleteConversation { id: String },
+
+    // === Attachment Sync (附件同步) ===
+    /// P2P: 附件清单 [(path, hash, size)]，接收方据此请求缺失的 Blob
+    SyncAttachmentManifest {
+        peer_id: PeerId,
+        repo_id: crate::models::RepoId,
+        entries: Vec<(String, String, u64)>,
+    },
+    /// P2P: 按内容哈希请求附件 Blob
+    SyncBlobRequest { hashes: Vec<String> },
+    /// P2P: 推送单个加密附件分块
+    SyncBlobChunk {
+        peer_id: PeerId,
+        chunk: EncryptedChunk,
+    },
+
+    // === Document Query (属性查询) ===
+    /// 按 frontmatter 属性与标签查询文档 (仪表盘卡片等，`req_id` 由调用方生成)
+    QueryDocs { req_id: String, query: DocQuery },
+
+    // === Wikilinks (双链) ===
+    /// 获取链接到指定文档的反链
+    GetBacklinks { doc_id: DocId },
+    /// 获取无法解析的链接报告
+    GetUnresolvedLinks,
+    /// 确认 `LinkRewriteOffer`: 将指向旧路径的入链改写为新路径 (作为普通编辑操作写入)
+    RewriteLinks { old_path: String, new_path: String },
+
+    // === Knowledge Graph (知识图谱) ===
+    /// 获取知识图谱: `root` 为空时返回全库，否则返回距 `root` 不超过 `depth` 跳链接的子图
+    GetGraph { root: Option<DocId>, depth: u32 },
+
+    // === Historical Diff (历史版本 Diff) ===
+    /// 获取文档在指定提交时的版本与当前版本的 Diff (历史搜索命中)
+    ///
+    /// **Post-condition**: 服务端回复 `ServerMessage::DocDiff` (old 为历史版本)。
+    GetHistoricalDiff { commit_id: String, doc_id: DocId },
+
+    // === Plugin UI (插件 UI) ===
+    /// 请求已加载插件及其 UI 贡献 (响应 `PluginList`)
+    ListPlugins,
+    /// 保存插件设置项 (成功后广播新的 `PluginList`)
+    SetPluginSetting {
+        plugin_id: String,
+        key: String,
+        value: serde_json::Value,
+    },
+
+    // === MCP Catalog (MCP 目录) ===
+    /// 请求 MCP 提示词与资源目录 (响应 `McpCatalog`)
+    ListMcpCatalog,
 }
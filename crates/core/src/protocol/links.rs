@@ -0,0 +1,70 @@
+// crates\core\src\protocol
+//! # Link Graph Messages (链接图)
+//!
+//! 反链、未解析链接报告与知识图谱的传输结构。
+
+use crate::models::DocId;
+use serde::{Deserialize, Serialize};
+
+/// 指向某文档的一处链接
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Backlink {
+    pub source: DocId,
+    pub path: String,
+    /// 行号 (从 1 开始)
+    pub line: u32,
+    /// 链接所在行 (截断)
+    pub context: String,
+}
+
+/// 无法解析到任何文档的链接
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnresolvedLink {
+    pub source: DocId,
+    pub path: String,
+    pub target: String,
+    pub line: u32,
+}
+
+/// 知识图谱节点类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GraphNodeKind {
+    Doc,
+    Tag,
+}
+
+/// 知识图谱节点
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphNode {
+    pub kind: GraphNodeKind,
+    /// `Doc` 节点的文档 ID
+    pub doc_id: Option<DocId>,
+    /// `Doc`: 文档路径；`Tag`: 标签名
+    pub key: String,
+}
+
+/// 知识图谱边类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GraphEdgeKind {
+    /// 文档链接 (source 链接到 target)
+    Link,
+    /// 标签归属 (source 文档带有 target 标签)
+    Tag,
+}
+
+/// 知识图谱边，端点为 `KnowledgeGraph::nodes` 下标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub source: u32,
+    pub target: u32,
+    pub kind: GraphEdgeKind,
+}
+
+/// 文档与标签构成的知识图谱
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnowledgeGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// 文档数超过上限被截断
+    pub truncated: bool,
+}
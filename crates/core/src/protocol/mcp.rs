@@ -0,0 +1,139 @@
+// crates\core\src\protocol
+//! # MCP Catalog Messages (MCP 目录)
+//!
+//! 已连接 MCP 服务器提供的提示词 (Prompts) 与资源 (Resources)，前后端共用。
+//! 聊天面板将提示词呈现为斜杠命令，资源呈现为可附加的上下文。
+//! 目录条目经 WebSocket 以 bincode 传输，字段不可按条件省略。
+//! 服务器状态报告供仪表盘展示连接与进程监管情况。
+
+use serde::{Deserialize, Serialize};
+
+/// 提示词参数声明
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpPromptArgument {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// 提示词模板 (`prompts/list` 条目)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpPrompt {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<McpPromptArgument>,
+}
+
+/// 资源 (`resources/list` 条目)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpResource {
+    pub uri: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub mime_type: Option<String>,
+}
+
+/// 带所属服务器的提示词
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpPromptInfo {
+    pub server: String,
+    pub prompt: McpPrompt,
+}
+
+/// 带所属服务器的资源
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpResourceInfo {
+    pub server: String,
+    pub resource: McpResource,
+}
+
+/// 本地常驻进程的会话信息 (远程服务器无会话)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpSessionInfo {
+    /// 当前进程号 (进程未运行时为 None)
+    #[serde(default)]
+    pub pid: Option<u32>,
+    /// 首次启动之后的重启次数
+    pub restarts: u32,
+    /// 连续失败次数 (进程运行稳定后清零)
+    pub failures: u32,
+    /// 距下次重启的等待时间 (进程运行中为 None)
+    #[serde(default)]
+    pub retry_in_ms: Option<u64>,
+    pub in_flight: usize,
+    pub max_in_flight: usize,
+    pub uptime_secs: u64,
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+/// 单个 MCP 服务器的状态报告 (`GET /api/mcp/status`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpServerReport {
+    pub name: String,
+    /// `local` | `remote`
+    pub kind: String,
+    /// `connected` | `restarting` | `failed` | `not_configured`
+    pub state: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub protocol_version: Option<String>,
+    pub tools: usize,
+    pub resources: usize,
+    pub prompts: usize,
+    #[serde(default)]
+    pub session: Option<McpSessionInfo>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ServerMessage;
+
+    #[test]
+    fn test_catalog_bincode_roundtrip() {
+        let msg = ServerMessage::McpCatalog {
+            prompts: vec![McpPromptInfo {
+                server: "a".into(),
+                prompt: McpPrompt {
+                    name: "review".into(),
+                    description: None,
+                    arguments: vec![McpPromptArgument {
+                        name: "topic".into(),
+                        description: None,
+                        required: true,
+                    }],
+                },
+            }],
+            resources: vec![McpResourceInfo {
+                server: "a".into(),
+                resource: McpResource {
+                    uri: "mock://readme".into(),
+                    name: "README".into(),
+                    description: None,
+                    mime_type: Some("text/plain".into()),
+                },
+            }],
+        };
+        let bytes = bincode::serialize(&msg).unwrap();
+        match bincode::deserialize::<ServerMessage>(&bytes).unwrap() {
+            ServerMessage::McpCatalog { prompts, resources } => {
+                assert_eq!(prompts[0].prompt.arguments[0].name, "topic");
+                assert_eq!(
+                    resources[0].resource.mime_type.as_deref(),
+                    Some("text/plain")
+                );
+            }
+            _ => panic!("expected McpCatalog"),
+        }
+    }
+}
@@ -7,6 +7,11 @@
 //! **核心功能清单**:
 //! - `ClientMessage`: 定义客户端发起的请求（Edit, List, Open, Create, Copy, Move, Delete 等）。
 //! - `ServerMessage`: 定义服务端推送的响应与事件（DocList, Snapshot, NewOps, Error 等）。
+//! - `SearchHit`: 全文检索的结构化命中项（摘要、高亮、行号、来源版本）。
+//! - `DocQuery`: 基于 frontmatter 属性与标签的文档查询（过滤、排序）。
+//! - `Backlink` / `UnresolvedLink` / `KnowledgeGraph`: 链接图查询结果（反链、断链报告、知识图谱）。
+//! - `McpPromptInfo` / `McpResourceInfo`: 已连接 MCP 服务器的提示词与资源目录。
+//! - `Conversation` / `AgentMessage`: 服务端智能体循环的持久化会话。
 //! - `Op`: 定义 CRDT 操作单元。
 //!
 //! **类型**: Core MUST (核心必选)
@@ -15,8 +20,55 @@
 //!   - Ack（确认）, NewOp（新操作）, Snapshot（快照）
 //!   - History（历史）, DocList（文档列表）, Error（错误）
 
+pub mod agent;
 pub mod client;
+pub mod links;
+pub mod mcp;
+pub mod query;
+pub mod search;
 pub mod server;
 
+pub use agent::{AgentMessage, AgentToolCall, Conversation, ConversationSummary};
 pub use client::ClientMessage;
+pub use links::{
+    Backlink, GraphEdge, GraphEdgeKind, GraphNode, GraphNodeKind, KnowledgeGraph, UnresolvedLink,
+};
+pub use mcp::{
+    McpPrompt, McpPromptArgument, McpPromptInfo, McpResource, McpResourceInfo, McpServerReport,
+    McpSessionInfo,
+};
+pub use query::{DocQuery, DocRow};
+pub use search::{SearchHit, SearchSource};
 pub use server::ServerMessage;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// bincode 变体下标 (编码的前 4 字节)
+    fn variant_index<T: serde::Serialize>(msg: &T) -> u32 {
+        let bytes = bincode::serialize(msg).unwrap();
+        u32::from_le_bytes(bytes[..4].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_existing_variants_keep_wire_index() {
+        // 新变体只能追加在末尾，已有变体的下标不变
+        assert_eq!(variant_index(&ClientMessage::ListDocs), 8);
+        assert_eq!(
+            variant_index(&ClientMessage::DiscardFile {
+                path: String::new()
+            }),
+            34
+        );
+        assert_eq!(variant_index(&ClientMessage::RequestKey), 37);
+        assert_eq!(variant_index(&ServerMessage::PendingDiscarded), 17);
+        assert_eq!(variant_index(&ServerMessage::Error(String::new())), 33);
+        assert_eq!(
+            variant_index(&ServerMessage::KeyDenied {
+                reason: String::new()
+            }),
+            37
+        );
+    }
+}
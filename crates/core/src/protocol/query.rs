@@ -0,0 +1,370 @@
+// crates\core\src\protocol
+//! # Document Query (文档属性查询)
+//!
+//! 基于 frontmatter 属性与标签的结构化查询，供仪表盘卡片与插件使用。
+//!
+//! 文本语法 (关键字不区分大小写):
+//! ```text
+//! tag = project AND status != done AND due < 2024-07-01 AND has owner SORT BY due DESC LIMIT 20
+//! ```
+//! - `tag = x` / `tag != x`: 标签 (不区分大小写)
+//! - `key = v` / `key != v`: 任一属性值相等 (不区分大小写)；`!=` 也匹配缺少该属性的文档
+//! - `key < v` 等比较: 取首个值，两侧均为数字时按数值比较，否则按字符串比较 (ISO 日期可直接比较)
+//! - `has key`: 存在该属性
+//! - `path` 可作为属性名参与比较与排序
+//!
+//! ## Invariants
+//! - 排序时缺少该属性的文档始终排在最后，相同值按路径升序
+
+use crate::markdown::DocMetadata;
+use crate::models::DocId;
+use anyhow::{Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// 比较运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// 单个过滤条件 (多个条件之间为 AND)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DocFilter {
+    Tag {
+        tag: String,
+        negate: bool,
+    },
+    Property {
+        key: String,
+        op: CompareOp,
+        value: String,
+    },
+    Exists(String),
+}
+
+/// 排序键
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocSort {
+    pub key: String,
+    pub descending: bool,
+}
+
+/// 文档查询
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocQuery {
+    pub filters: Vec<DocFilter>,
+    pub sort: Option<DocSort>,
+    pub limit: Option<u32>,
+}
+
+/// 查询结果行
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocRow {
+    pub doc_id: DocId,
+    pub path: String,
+    pub tags: Vec<String>,
+    pub properties: BTreeMap<String, Vec<String>>,
+}
+
+impl DocRow {
+    /// 属性首个值；`path` 返回文档路径
+    pub fn value(&self, key: &str) -> Option<&str> {
+        if key == "path" {
+            return Some(&self.path);
+        }
+        self.properties
+            .get(key)
+            .and_then(|v| v.first())
+            .map(String::as_str)
+    }
+}
+
+impl DocQuery {
+    /// 解析文本语法
+    pub fn parse(input: &str) -> Result<Self> {
+        let tokens = lex(input)?;
+        let mut query = DocQuery::default();
+        let mut iter = tokens.into_iter().peekable();
+        let keyword = |t: &Token, k: &str| matches!(t, Token::Word(w) if w.eq_ignore_ascii_case(k));
+
+        while let Some(token) = iter.next() {
+            if keyword(&token, "and") {
+                continue;
+            }
+            if keyword(&token, "sort") {
+                if !iter.next().is_some_and(|t| keyword(&t, "by")) {
+                    bail!("Expected BY after SORT");
+                }
+                let key = word(iter.next(), "sort key")?.to_lowercase();
+                let descending = match iter.peek() {
+                    Some(t) if keyword(t, "desc") => true,
+                    Some(t) if keyword(t, "asc") => false,
+                    _ => {
+                        query.sort = Some(DocSort {
+                            key,
+                            descending: false,
+                        });
+                        continue;
+                    }
+                };
+                iter.next();
+                query.sort = Some(DocSort { key, descending });
+                continue;
+            }
+            if keyword(&token, "limit") {
+                let n = word(iter.next(), "limit")?;
+                query.limit = Some(n.parse().map_err(|_| anyhow!("Invalid LIMIT '{}'", n))?);
+                continue;
+            }
+            if keyword(&token, "has") {
+                query.filters.push(DocFilter::Exists(
+                    word(iter.next(), "property")?.to_lowercase(),
+                ));
+                continue;
+            }
+
+            let key = word(Some(token), "property")?.to_lowercase();
+            let op = match iter.next() {
+                Some(Token::Op(op)) => op,
+                _ => bail!("Expected operator after '{}'", key),
+            };
+            let value = match iter.next() {
+                Some(Token::Word(v) | Token::Quoted(v)) => v,
+                _ => bail!("Expected value after '{}'", key),
+            };
+            if key == "tag" || key == "tags" {
+                let negate = match op {
+                    CompareOp::Eq => false,
+                    CompareOp::Ne => true,
+                    _ => bail!("Tags only support = and !="),
+                };
+                let tag = value.trim_start_matches('#').to_lowercase();
+                query.filters.push(DocFilter::Tag { tag, negate });
+            } else {
+                query.filters.push(DocFilter::Property { key, op, value });
+            }
+        }
+        Ok(query)
+    }
+
+    /// 按 `parse` 的规则规范化 (属性键与标签小写、去掉标签的 `#`)
+    ///
+    /// 经 WebSocket / HTTP 反序列化的查询未经 `parse`，执行前须规范化。
+    pub fn normalized(&self) -> Self {
+        let mut query = self.clone();
+        for filter in &mut query.filters {
+            match filter {
+                DocFilter::Tag { tag, .. } => *tag = tag.trim_start_matches('#').to_lowercase(),
+                DocFilter::Property { key, .. } | DocFilter::Exists(key) => {
+                    *key = key.to_lowercase()
+                }
+            }
+        }
+        if let Some(sort) = &mut query.sort {
+            sort.key = sort.key.to_lowercase();
+        }
+        query
+    }
+
+    /// 文档是否满足全部条件
+    pub fn matches(&self, path: &str, meta: &DocMetadata) -> bool {
+        self.filters.iter().all(|f| f.matches(path, meta))
+    }
+
+    /// 按 `sort` 排序并截断到 `limit`
+    pub fn sort_and_limit(&self, rows: &mut Vec<DocRow>) {
+        match &self.sort {
+            Some(sort) => rows.sort_by(|a, b| {
+                let ord = match (a.value(&sort.key), b.value(&sort.key)) {
+                    (Some(x), Some(y)) if sort.descending => compare_values(y, x),
+                    (Some(x), Some(y)) => compare_values(x, y),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                };
+                ord.then_with(|| a.path.cmp(&b.path))
+            }),
+            None => rows.sort_by(|a, b| a.path.cmp(&b.path)),
+        }
+        if let Some(limit) = self.limit {
+            rows.truncate(limit as usize);
+        }
+    }
+}
+
+impl DocFilter {
+    pub fn matches(&self, path: &str, meta: &DocMetadata) -> bool {
+        match self {
+            DocFilter::Tag { tag, negate } => meta.tags.contains(tag) != *negate,
+            DocFilter::Exists(key) => key == "path" || meta.properties.contains_key(key),
+            DocFilter::Property { key, op, value } => {
+                let values: Vec<&str> = if key == "path" {
+                    vec![path]
+                } else {
+                    meta.properties
+                        .get(key)
+                        .map(|v| v.iter().map(String::as_str).collect())
+                        .unwrap_or_default()
+                };
+                let folded = fold_value(value);
+                let eq = values.iter().any(|v| fold_value(v) == folded);
+                match op {
+                    CompareOp::Eq => eq,
+                    CompareOp::Ne => !eq,
+                    _ => values.first().is_some_and(|v| {
+                        let ord = compare_values(v, value);
+                        match op {
+                            CompareOp::Lt => ord.is_lt(),
+                            CompareOp::Le => ord.is_le(),
+                            CompareOp::Gt => ord.is_gt(),
+                            _ => ord.is_ge(),
+                        }
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// 属性值的大小写折叠 (Unicode 小写)
+///
+/// 过滤与 `PROP_DOCS` 倒排索引必须使用同一折叠，否则索引命中与逐条过滤结果不一致。
+pub fn fold_value(value: &str) -> String {
+    value.to_lowercase()
+}
+
+/// 两侧均为数字时按数值比较，否则按字符串比较
+fn compare_values(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(x), Ok(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+        _ => a.cmp(b),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(CompareOp),
+}
+
+fn word(token: Option<Token>, what: &str) -> Result<String> {
+    match token {
+        Some(Token::Word(w) | Token::Quoted(w)) => Ok(w),
+        _ => bail!("Expected {}", what),
+    }
+}
+
+fn lex(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' || c == '\'' {
+            chars.next();
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    Some(q) if q == c => break,
+                    Some(ch) => value.push(ch),
+                    None => bail!("Unterminated quote"),
+                }
+            }
+            tokens.push(Token::Quoted(value));
+        } else if matches!(c, '=' | '!' | '<' | '>') {
+            chars.next();
+            let eq = chars.next_if_eq(&'=').is_some();
+            let op = match (c, eq) {
+                ('=', _) => CompareOp::Eq,
+                ('!', true) => CompareOp::Ne,
+                ('<', false) => CompareOp::Lt,
+                ('<', true) => CompareOp::Le,
+                ('>', false) => CompareOp::Gt,
+                ('>', true) => CompareOp::Ge,
+                _ => bail!("Unexpected '!'"),
+            };
+            tokens.push(Token::Op(op));
+        } else {
+            let mut value = String::new();
+            while let Some(ch) =
+                chars.next_if(|ch| !ch.is_whitespace() && !matches!(ch, '=' | '!' | '<' | '>'))
+            {
+                value.push(ch);
+            }
+            tokens.push(Token::Word(value));
+        }
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::extract_metadata;
+
+    #[test]
+    fn test_parse_and_match_doc_query() -> Result<()> {
+        let q = DocQuery::parse(
+            "tag = #Project and status!=done AND due<2024-07-01 AND has owner sort by due desc LIMIT 5",
+        )?;
+        assert_eq!(
+            q.filters[0],
+            DocFilter::Tag {
+                tag: "project".into(),
+                negate: false
+            }
+        );
+        assert_eq!(
+            q.sort,
+            Some(DocSort {
+                key: "due".into(),
+                descending: true
+            })
+        );
+        assert_eq!(q.limit, Some(5));
+
+        let meta = |text: &str| extract_metadata(text);
+        let open = meta("---\nstatus: Doing\ndue: 2024-06-15\nowner: bo\n---\n#project");
+        let done = meta("---\nstatus: DONE\ndue: 2024-06-01\nowner: bo\n---\n#project");
+        let late = meta("---\ndue: 2024-08-01\nowner: bo\n---\n#project");
+        assert!(q.matches("a.md", &open));
+        assert!(!q.matches("b.md", &done));
+        assert!(!q.matches("c.md", &late));
+        // `!=` 匹配缺少属性的文档
+        let unset = DocQuery::parse("status != done")?;
+        assert!(unset.matches("d.md", &meta("no frontmatter")));
+
+        // 反序列化的查询规范化后与 `parse` 结果一致
+        let wire = DocQuery {
+            filters: vec![
+                DocFilter::Tag {
+                    tag: "#Project".into(),
+                    negate: false,
+                },
+                DocFilter::Exists("Owner".into()),
+            ],
+            sort: Some(DocSort {
+                key: "Due".into(),
+                descending: true,
+            }),
+            limit: None,
+        };
+        assert_eq!(
+            wire.normalized(),
+            DocQuery::parse("tag = #project AND has owner SORT BY due DESC")?
+        );
+
+        assert!(DocQuery::parse("status").is_err());
+        assert!(DocQuery::parse("tag < x").is_err());
+        assert!(DocQuery::parse("title = \"open").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+// crates\core\src\protocol
+//! # Search Messages (搜索结果)
+//!
+//! 全文检索返回的结构化命中项，前后端共用。
+
+use crate::models::{DocId, PeerId, RepoId};
+use serde::{Deserialize, Serialize};
+
+/// 命中所在的版本来源
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SearchSource {
+    /// 本地当前版本
+    #[default]
+    Local,
+    /// 某次提交时的历史版本 (`timestamp` 为提交时间，毫秒)
+    Commit { commit_id: String, timestamp: i64 },
+    /// Peer 影子库 (`remotes/<peer>/<repo>.redb`)
+    Peer { peer_id: PeerId, repo_id: RepoId },
+}
+
+/// 单条搜索命中
+///
+/// ## Invariants
+/// - `line` 从 1 开始，指向首个高亮所在行 (无高亮时为摘要起始行)
+/// - `highlights` 为 `snippet` 内的字节区间，升序且互不重叠
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub doc_id: DocId,
+    pub path: String,
+    pub score: f32,
+    pub line: u32,
+    /// 单行摘要 (换行替换为空格)
+    pub snippet: String,
+    pub highlights: Vec<(u32, u32)>,
+    #[serde(default)]
+    pub source: SearchSource,
+}
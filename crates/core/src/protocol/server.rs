@@ -1,8 +1,13 @@
 // crates\core\src\protocol
 //! # Server Messages (服务端消息)
 
+use super::{
+    Backlink, Conversation, ConversationSummary, DocRow, KnowledgeGraph, McpPromptInfo,
+    McpResourceInfo, SearchHit, UnresolvedLink,
+};
 use crate::models::{DocId, Op, PeerId, VersionVector};
-use crate::security::EncryptedOp;
+use crate::plugin::ui::PluginInfo;
+use crate::security::{EncryptedChunk, EncryptedOp};
 use crate::source_control::{ChangeEntry, CommitInfo};
 use serde::{Deserialize, Serialize};
 
@@ -10,8 +15,15 @@ use serde::{Deserialize, Serialize};
 pub enum ServerMessage {
     /// 心跳 Pong
     Pong,
-    /// 服务端确认操作已持久化
-    Ack { doc_id: DocId, seq: u64 },
+    /// 服务端确认编辑已定序并持久化 (广播，与 NewOp 保持顺序)
+    ///
+    /// `ops` 为变换后实际写入的操作 (可能拆分或为空)，`seq` 为其中最后一个的序号。
+    Ack {
+        doc_id: DocId,
+        seq: u64,
+        client_id: u64,
+        ops: Vec<Op>,
+    },
     /// P2P: 服务端 Hello (响应客户端 Hello)
     SyncHello {
         peer_id: PeerId,
@@ -43,7 +55,7 @@ pub enum ServerMessage {
         finish_reason: Option<String>,
     },
 
-    /// 服务端广播来自其他客户端的新操作
+    /// 服务端广播来自其他客户端的新操作 (`seq` 为账本全局序号，与 Snapshot 版本一致)
     NewOp {
         doc_id: DocId,
         op: Op,
@@ -69,10 +81,7 @@ pub enum ServerMessage {
         error: Option<String>,
     },
     /// 全文搜索结果
-    SearchResults {
-        /// (DocId String, Path, Score)
-        results: Vec<(String, String, f32)>,
-    },
+    SearchResults { results: Vec<SearchHit> },
 
     // === Manual Merge Messages (手动合并模式) ===
     /// 当前同步模式状态
@@ -199,4 +208,108 @@ pub enum ServerMessage {
         db_size_bytes: u64,
         doc_count: u32,
     },
+
+    // === Two-Factor Step-Up (第二因素重新校验) ===
+    /// 敏感操作需要近期完成的第二因素校验
+    ///
+    /// **Post-condition**: 客户端提示输入口令并发送 `ClientMessage::VerifyTotp`。
+    MfaRequired {
+        /// 被拦截的操作 (e.g. "RequestKey", "DeletePeer")
+        action: String,
+    },
+    /// 第二因素校验结果
+    MfaVerified { success: bool },
+
+    // === Agent Conversations (服务端智能体会话) ===
+    /// 智能体循环已启动 (新会话时携带新分配的 ID)
+    ChatStarted {
+        req_id: String,
+        conversation_id: String,
+    },
+    /// 工具调用增量 (按 `round` 内的 `index` 聚合，`arguments` 为追加片段)
+    ChatToolCallDelta {
+        req_id: String,
+        round: u32,
+        index: u32,
+        id: Option<String>,
+        name: Option<String>,
+        arguments: Option<String>,
+    },
+    /// 工具执行结果
+    ChatToolResult {
+        req_id: String,
+        call_id: String,
+        name: String,
+        content: String,
+        is_error: bool,
+    },
+    /// 智能体循环结束
+    ///
+    /// `reason`: `stop` / `max_rounds` / `cancelled` / `error` (附 `error`)。
+    ChatDone {
+        req_id: String,
+        conversation_id: String,
+        reason: String,
+        error: Option<String>,
+    },
+    /// 会话列表 (按最近更新倒序)
+    ConversationList {
+        conversations: Vec<ConversationSummary>,
+    },
+    /// 完整会话
+    ConversationData { conversation: Conversation },
+    /// 会话已删除
+    ConversationDeleted { id: String },
+
+    // === Attachment Sync (附件同步) ===
+    /// P2P: 附件清单 [(path, hash, size)]，接收方据此请求缺失的 Blob
+    SyncAttachmentManifest {
+        peer_id: PeerId,
+        repo_id: crate::models::RepoId,
+        entries: Vec<(String, String, u64)>,
+    },
+    /// P2P: 按内容哈希请求附件 Blob
+    SyncBlobRequest { hashes: Vec<String> },
+    /// P2P: 推送单个加密附件分块
+    SyncBlobChunk {
+        peer_id: PeerId,
+        chunk: EncryptedChunk,
+    },
+
+    // === Document Query (属性查询) ===
+    /// 属性查询结果 (对应 `ClientMessage::QueryDocs` 的 `req_id`)
+    QueryDocsResult { req_id: String, rows: Vec<DocRow> },
+
+    // === Wikilinks (双链) ===
+    /// 反链列表
+    Backlinks { doc_id: DocId, links: Vec<Backlink> },
+    /// 未解析链接报告
+    UnresolvedLinks { links: Vec<UnresolvedLink> },
+    /// 重命名/移动后存在需改写的入链，询问是否执行 `RewriteLinks`
+    LinkRewriteOffer {
+        old_path: String,
+        new_path: String,
+        docs: u32,
+        links: u32,
+    },
+    /// 入链改写完成
+    LinksRewritten { docs: u32, links: u32 },
+
+    // === Knowledge Graph (知识图谱) ===
+    /// 知识图谱 (对应 `ClientMessage::GetGraph`)
+    Graph {
+        root: Option<DocId>,
+        graph: KnowledgeGraph,
+    },
+
+    // === Plugin UI (插件 UI) ===
+    /// 已加载插件及其 UI 贡献与设置值
+    PluginList { plugins: Vec<PluginInfo> },
+
+    // === MCP Catalog (MCP 目录) ===
+    /// MCP 提示词与资源目录 (服务器列表变化时广播)
+    McpCatalog {
+        prompts: Vec<McpPromptInfo>,
+        resources: Vec<McpResourceInfo>,
+    },
 }
@@ -0,0 +1,346 @@
+// crates/core/src/search/archive.rs
+//! # 归档版本索引 (History & Peer Shadows)
+//!
+//! 将历史提交版本与 Peer 影子库中的文档写入同一个 tantivy 索引，
+//! 以 `scope` 字段区分，供 `in:history` / `in:peers` 查询。
+//!
+//! ## 历史提交
+//! - `commit_snapshots` 只保存最近一次提交，历史版本由本地 Ledger 重放到 `ledger_seq` 得到
+//! - 文档仅在内容与上一次提交不同时索引，未改动的文档不会在每个提交下重复命中
+//! - 提交不可变: 已处理的提交不再重建；有新提交时只重放最后一个已处理提交之后的部分，
+//!   且跳过该区间内没有操作的文档
+//!
+//! ## 影子库
+//! - 以影子库最大序号判断是否变化，变化时整体替换 (路径解析见 `list_shadow_docs`)
+//! - 磁盘上已删除的影子库 (如 `DeletePeer`) 从索引中移除
+//!
+//! ## Invariants
+//! - 每个 (来源, 文档) 至多一个条目；空内容不索引
+
+use super::{IndexUpdate, SearchService};
+use crate::ledger::RepoManager;
+use crate::ledger::listing::RepoListing;
+use crate::models::{LedgerEntry, PeerId, RepoId};
+use crate::protocol::SearchSource;
+use crate::state::reconstruct_content;
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+/// 归档同步状态 (由 `SearchService` 持有，仅内存；重启后由索引内容恢复已处理的提交)
+#[derive(Debug, Default)]
+pub struct ArchiveState {
+    /// 已处理的提交 (含无文档变化、因而没有条目的提交)
+    commits: HashSet<String>,
+    /// 影子库上次索引时的最大序号
+    peers: HashMap<(PeerId, RepoId), u64>,
+}
+
+/// 计算使索引与提交历史、影子库一致所需的更新
+///
+/// 状态在计算时即视为已同步；调用方提交失败时应 `reset_archive_state`。
+pub fn archive_updates(repo: &RepoManager, service: &SearchService) -> Result<Vec<IndexUpdate>> {
+    let mut state = service.archive.lock().unwrap_or_else(|e| e.into_inner());
+    let state = &mut *state;
+    let archived = service.archived_sources()?;
+    for source in &archived {
+        if let SearchSource::Commit { commit_id, .. } = source {
+            state.commits.insert(commit_id.clone());
+        }
+    }
+
+    let mut wanted = HashSet::new();
+    let mut updates = commit_updates(repo, state, &mut wanted)?;
+    updates.extend(peer_updates(repo, &archived, state, &mut wanted)?);
+    for source in archived.difference(&wanted) {
+        if let SearchSource::Peer { peer_id, repo_id } = source {
+            state.peers.remove(&(peer_id.clone(), *repo_id));
+        }
+        updates.push(IndexUpdate::DropSource(source.clone()));
+    }
+    Ok(updates)
+}
+
+/// 计算并提交归档更新，返回更新条数
+///
+/// 无变化时只读取提交列表与各影子库的最大序号，可在每次历史/影子库搜索前调用。
+pub fn sync_archive(repo: &RepoManager, service: &SearchService) -> Result<usize> {
+    let updates = archive_updates(repo, service)?;
+    if let Err(e) = service.apply_updates(&updates) {
+        reset_archive_state(service);
+        return Err(e);
+    }
+    Ok(updates.len())
+}
+
+/// 丢弃内存状态，下次同步时按索引内容重新判断
+pub fn reset_archive_state(service: &SearchService) {
+    *service.archive.lock().unwrap_or_else(|e| e.into_inner()) = ArchiveState::default();
+}
+
+fn commit_updates(
+    repo: &RepoManager,
+    state: &mut ArchiveState,
+    wanted: &mut HashSet<SearchSource>,
+) -> Result<Vec<IndexUpdate>> {
+    let mut commits = repo.list_commits(u32::MAX)?;
+    commits.reverse();
+    let sources: Vec<_> = commits
+        .iter()
+        .map(|c| SearchSource::Commit {
+            commit_id: c.id.clone(),
+            timestamp: c.timestamp,
+        })
+        .collect();
+    wanted.extend(sources.iter().cloned());
+    // 提交按时间追加: 首个未处理提交之前的都已索引过
+    let Some(first_new) = commits.iter().position(|c| !state.commits.contains(&c.id)) else {
+        return Ok(Vec::new());
+    };
+    // 最后一个已处理提交的内容作为比较基线 (内容不变的文档不重复索引)
+    let base_seq = first_new.checked_sub(1).map(|i| commits[i].ledger_seq);
+    let last_seq = commits.iter().map(|c| c.ledger_seq).max().unwrap_or(0);
+
+    let mut updates = Vec::new();
+    for (doc_id, path) in repo.list_local_docs(None)? {
+        let mut ops = repo.get_local_ops(doc_id)?;
+        ops.sort_by_key(|(seq, _)| *seq);
+        let base_count = base_seq.map_or(0, |seq| ops.partition_point(|(s, _)| *s <= seq));
+        if base_count == ops.partition_point(|(seq, _)| *seq <= last_seq) {
+            continue;
+        }
+        let entries: Vec<LedgerEntry> = ops.iter().map(|(_, e)| e.clone()).collect();
+
+        // 按提交顺序重放，只在操作前缀变化时重建内容
+        let mut applied = base_count;
+        let mut previous = if base_count == 0 {
+            String::new()
+        } else {
+            reconstruct_content(&entries[..base_count])
+        };
+        for (commit, source) in commits.iter().zip(&sources).skip(first_new) {
+            let count = ops.partition_point(|(seq, _)| *seq <= commit.ledger_seq);
+            if count == applied {
+                continue;
+            }
+            applied = count;
+            let content = reconstruct_content(&entries[..count]);
+            if content != previous && !content.is_empty() && !state.commits.contains(&commit.id) {
+                updates.push(IndexUpdate::Archive {
+                    source: source.clone(),
+                    doc_id,
+                    path: path.clone(),
+                    content: content.clone(),
+                    modified: commit.timestamp,
+                });
+            }
+            previous = content;
+        }
+    }
+    state.commits.extend(commits.into_iter().map(|c| c.id));
+    Ok(updates)
+}
+
+fn peer_updates(
+    repo: &RepoManager,
+    archived: &HashSet<SearchSource>,
+    state: &mut ArchiveState,
+    wanted: &mut HashSet<SearchSource>,
+) -> Result<Vec<IndexUpdate>> {
+    let mut updates = Vec::new();
+    for peer_id in repo.list_shadows_on_disk()? {
+        for name in repo.list_repos(Some(&peer_id))? {
+            // 影子库文件以 RepoId 命名
+            let Ok(repo_id) = uuid::Uuid::parse_str(&name) else {
+                continue;
+            };
+            let source = SearchSource::Peer {
+                peer_id: peer_id.clone(),
+                repo_id,
+            };
+            wanted.insert(source.clone());
+            match shadow_updates(repo, &source, archived, state) {
+                Ok(shadow) => updates.extend(shadow),
+                Err(e) => tracing::warn!("Skip shadow {}/{} in search: {:?}", peer_id, name, e),
+            }
+        }
+    }
+    Ok(updates)
+}
+
+/// 影子库最大序号变化 (追加或重置文档都会递增) 时整体替换其条目
+fn shadow_updates(
+    repo: &RepoManager,
+    source: &SearchSource,
+    archived: &HashSet<SearchSource>,
+    state: &mut ArchiveState,
+) -> Result<Vec<IndexUpdate>> {
+    let SearchSource::Peer { peer_id, repo_id } = source else {
+        return Ok(Vec::new());
+    };
+    let max_seq = repo.get_shadow_max_seq(peer_id, repo_id)?;
+    let key = (peer_id.clone(), *repo_id);
+    if state.peers.get(&key) == Some(&max_seq) && archived.contains(source) {
+        return Ok(Vec::new());
+    }
+
+    let mut updates = vec![IndexUpdate::DropSource(source.clone())];
+    for (doc_id, path) in repo.list_shadow_docs(peer_id, repo_id)? {
+        let entries: Vec<_> = repo
+            .get_shadow_ops(peer_id, repo_id, doc_id)?
+            .into_iter()
+            .map(|(_, e)| e)
+            .collect();
+        let content = reconstruct_content(&entries);
+        if content.is_empty() {
+            continue;
+        }
+        updates.push(IndexUpdate::Archive {
+            source: source.clone(),
+            doc_id,
+            path,
+            content,
+            modified: entries.iter().map(|e| e.timestamp).max().unwrap_or(0),
+        });
+    }
+    state.peers.insert(key, max_seq);
+    Ok(updates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DocId, Op};
+
+    fn edit(repo: &RepoManager, doc_id: DocId, op: Op) {
+        repo.append_local_op(&LedgerEntry {
+            doc_id,
+            op,
+            timestamp: 0,
+            peer_id: PeerId::new("test"),
+            seq: 0,
+        })
+        .unwrap();
+    }
+
+    fn commit(repo: &RepoManager, doc_id: DocId, message: &str) -> String {
+        repo.stage_file("notes/plan.md").unwrap();
+        let content = repo.get_content_at_seq(doc_id, u64::MAX).unwrap();
+        repo.create_commit_with_snapshots(message, |_| Some((doc_id, content.clone())))
+            .unwrap()
+            .id
+    }
+
+    #[test]
+    fn test_history_and_peer_versions() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let repo = RepoManager::init(dir.path(), 10, None, None)?;
+        let service = SearchService::new_in_memory()?;
+
+        let doc = repo.create_docid("notes/plan.md")?;
+        let text = "orchard harvest schedule";
+        edit(
+            &repo,
+            doc,
+            Op::Insert {
+                pos: 0,
+                content: text.into(),
+            },
+        );
+        let first = commit(&repo, doc, "draft");
+        // 第二个提交内容不变: 不重复索引
+        commit(&repo, doc, "noop");
+        edit(
+            &repo,
+            doc,
+            Op::Delete {
+                pos: 0,
+                len: "orchard ".chars().count() as u32,
+            },
+        );
+        let third = commit(&repo, doc, "trim");
+        service.index_document(doc, "notes/plan.md", "harvest schedule")?;
+
+        // 远端影子库
+        let peer = PeerId::new("peer-b");
+        let repo_id = uuid::Uuid::new_v4();
+        repo.append_remote_op(
+            &peer,
+            &repo_id,
+            &LedgerEntry {
+                doc_id: doc,
+                op: Op::Insert {
+                    pos: 0,
+                    content: "violet orchard notes".into(),
+                },
+                timestamp: 5,
+                peer_id: peer.clone(),
+                seq: 0,
+            },
+        )?;
+
+        assert!(sync_archive(&repo, &service)? > 0);
+        let sources = |q: &str| -> Result<Vec<SearchSource>> {
+            Ok(service
+                .search(q, 10)?
+                .into_iter()
+                .map(|h| h.source)
+                .collect())
+        };
+
+        assert!(sources("orchard")?.is_empty());
+        let history = sources("orchard in:history")?;
+        assert!(
+            matches!(&history[..], [SearchSource::Commit { commit_id, .. }] if *commit_id == first)
+        );
+        let all = sources("harvest in:history")?;
+        assert_eq!(all.len(), 2);
+        assert!(
+            all.iter().any(
+                |s| matches!(s, SearchSource::Commit { commit_id, .. } if *commit_id == third)
+            )
+        );
+        assert_eq!(
+            sources("violet in:peers")?,
+            [SearchSource::Peer {
+                peer_id: peer.clone(),
+                repo_id
+            }]
+        );
+        // 影子库无路径表时沿用本地路径
+        assert_eq!(
+            service.search("violet in:peers", 10)?[0].path,
+            "notes/plan.md"
+        );
+        assert_eq!(sources("orchard in:all")?.len(), 2);
+
+        // 无变化时不产生更新；本地重建不影响归档条目
+        assert_eq!(sync_archive(&repo, &service)?, 0);
+        service.apply_updates(&[IndexUpdate::Clear])?;
+        assert_eq!(sources("violet in:peers")?.len(), 1);
+        assert!(service.indexed_docs()?.is_empty());
+
+        // 影子库被删除后移除
+        std::fs::remove_dir_all(dir.path().join("remotes").join(peer.to_filename()))?;
+        assert_eq!(sync_archive(&repo, &service)?, 1);
+        assert!(sources("violet in:peers")?.is_empty());
+
+        // 新提交只索引变化的文档，已处理的提交不重放
+        commit(&repo, doc, "noop again");
+        assert_eq!(sync_archive(&repo, &service)?, 0);
+        edit(
+            &repo,
+            doc,
+            Op::Insert {
+                pos: 0,
+                content: "quince ".into(),
+            },
+        );
+        let fifth = commit(&repo, doc, "quince");
+        assert_eq!(sync_archive(&repo, &service)?, 1);
+        assert!(
+            matches!(&sources("quince in:history")?[..], [SearchSource::Commit { commit_id, .. }] if *commit_id == fifth)
+        );
+        assert_eq!(sources("harvest in:history")?.len(), 3);
+        Ok(())
+    }
+}
@@ -0,0 +1,274 @@
+// crates/core/src/search/indexer.rs
+//! # Search Indexer (后台索引线程)
+//!
+//! 将文档变更事件合并为批次，统一写入 `SearchService`。
+//!
+//! ## 事件来源
+//! - 启动时自动 `Rebuild` (从 `list_local_docs` 全量构建)
+//! - 本地编辑、Watcher、重命名/删除: `Touch(doc_id)`
+//! - 目录级变更 (文件夹重命名/删除): `Reconcile`
+//! - 启动、提交与历史/影子库搜索前: `Archive` (同步历史提交与影子库版本，见 `archive`)
+//!
+//! 远端同步的操作只写入影子库；合并到本地时会写回 Vault 文件，
+//! 因此经 Watcher 的 `Touch` 进入索引。
+//!
+//! ## Invariants
+//! - 同一批次内的全部更新只产生一次 tantivy commit
+//! - `Touch` 以 Ledger 当前状态为准: 文档存在则覆盖，不存在则删除
+
+use super::archive::{archive_updates, reset_archive_state};
+use super::{IndexUpdate, SearchService};
+use crate::ledger::RepoManager;
+use crate::models::DocId;
+use crate::state::reconstruct_content;
+use crate::utils::debounce::spawn_batcher;
+use anyhow::Result;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+pub use crate::utils::debounce::{DEFAULT_DEBOUNCE, MAX_BATCH_DELAY};
+
+/// 索引事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexEvent {
+    /// 文档内容或路径可能变化 (含删除)
+    Touch(DocId),
+    /// 对比 Ledger 与索引的文档集合，修正路径与缺失/多余项
+    Reconcile,
+    /// 清空并全量重建
+    Rebuild,
+    /// 同步历史提交与 Peer 影子库版本
+    Archive,
+}
+
+/// 后台索引器句柄 (可克隆，丢弃全部句柄后线程退出)
+#[derive(Clone)]
+pub struct SearchIndexer {
+    tx: Sender<IndexEvent>,
+}
+
+impl SearchIndexer {
+    /// 启动后台线程，并立即排队一次全量重建
+    pub fn spawn(repo: Arc<RepoManager>, service: Arc<SearchService>) -> Self {
+        Self::spawn_with_debounce(repo, service, DEFAULT_DEBOUNCE)
+    }
+
+    pub fn spawn_with_debounce(
+        repo: Arc<RepoManager>,
+        service: Arc<SearchService>,
+        debounce: Duration,
+    ) -> Self {
+        let tx = spawn_batcher(
+            "search-indexer",
+            debounce,
+            MAX_BATCH_DELAY,
+            move |events: Vec<IndexEvent>| {
+                let mut batch = Batch::default();
+                events.into_iter().for_each(|e| batch.push(e));
+                match batch.flush(&repo, &service) {
+                    Ok(n) if n > 0 => tracing::debug!("Search index committed {} updates", n),
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("Search indexing failed: {:?}", e),
+                }
+            },
+        );
+        let _ = tx.send(IndexEvent::Rebuild);
+        let _ = tx.send(IndexEvent::Archive);
+        Self { tx }
+    }
+
+    pub fn send(&self, event: IndexEvent) {
+        let _ = self.tx.send(event);
+    }
+
+    pub fn touch(&self, doc_id: DocId) {
+        self.send(IndexEvent::Touch(doc_id));
+    }
+
+    pub fn reconcile(&self) {
+        self.send(IndexEvent::Reconcile);
+    }
+
+    pub fn archive(&self) {
+        self.send(IndexEvent::Archive);
+    }
+}
+
+/// 从 Ledger 全量重建索引 (单次提交)，返回文档数
+pub fn rebuild_index(repo: &RepoManager, service: &SearchService) -> Result<usize> {
+    let mut batch = Batch::default();
+    batch.push(IndexEvent::Rebuild);
+    batch.flush(repo, service)?;
+    Ok(repo.list_local_docs(None)?.len())
+}
+
+/// 一个防抖窗口内累积的事件
+#[derive(Default)]
+struct Batch {
+    rebuild: bool,
+    reconcile: bool,
+    archive: bool,
+    touched: HashSet<DocId>,
+}
+
+impl Batch {
+    fn push(&mut self, event: IndexEvent) {
+        match event {
+            IndexEvent::Touch(doc_id) => {
+                self.touched.insert(doc_id);
+            }
+            IndexEvent::Reconcile => self.reconcile = true,
+            IndexEvent::Rebuild => self.rebuild = true,
+            IndexEvent::Archive => self.archive = true,
+        }
+    }
+
+    /// 计算并提交本批次的更新，返回更新条数
+    fn flush(self, repo: &RepoManager, service: &SearchService) -> Result<usize> {
+        let archive = self.archive;
+        let result = self.flush_inner(repo, service);
+        if archive && result.is_err() {
+            reset_archive_state(service);
+        }
+        result
+    }
+
+    fn flush_inner(self, repo: &RepoManager, service: &SearchService) -> Result<usize> {
+        let mut updates = Vec::new();
+        if self.archive {
+            updates.extend(archive_updates(repo, service)?);
+        }
+
+        if self.rebuild {
+            updates.push(IndexUpdate::Clear);
+            for (doc_id, path) in repo.list_local_docs(None)? {
+                updates.push(upsert(repo, doc_id, path)?);
+            }
+            service.apply_updates(&updates)?;
+            return Ok(updates.len());
+        }
+
+        let mut done = HashSet::new();
+        if self.reconcile {
+            let mut indexed = service.indexed_docs()?;
+            for (doc_id, path) in repo.list_local_docs(None)? {
+                let stale = indexed.remove(&doc_id).is_none_or(|p| p != path);
+                if stale || self.touched.contains(&doc_id) {
+                    updates.push(upsert(repo, doc_id, path)?);
+                    done.insert(doc_id);
+                }
+            }
+            for doc_id in indexed.into_keys() {
+                updates.push(IndexUpdate::Delete(doc_id));
+                done.insert(doc_id);
+            }
+        }
+
+        for doc_id in self.touched {
+            if done.contains(&doc_id) {
+                continue;
+            }
+            match repo.get_path_by_docid(doc_id)? {
+                Some(path) => updates.push(upsert(repo, doc_id, path)?),
+                None => updates.push(IndexUpdate::Delete(doc_id)),
+            }
+        }
+
+        service.apply_updates(&updates)?;
+        Ok(updates.len())
+    }
+}
+
+fn upsert(repo: &RepoManager, doc_id: DocId, path: String) -> Result<IndexUpdate> {
+    let entries: Vec<_> = repo
+        .get_local_ops(doc_id)?
+        .into_iter()
+        .map(|(_, e)| e)
+        .collect();
+    Ok(IndexUpdate::Upsert {
+        doc_id,
+        path,
+        content: reconstruct_content(&entries),
+        modified: entries.iter().map(|e| e.timestamp).max().unwrap_or(0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{LedgerEntry, Op, PeerId};
+    use std::time::Instant;
+
+    fn write_doc(repo: &RepoManager, path: &str, text: &str) -> DocId {
+        let doc_id = repo.create_docid(path).unwrap();
+        repo.append_local_op(&LedgerEntry {
+            doc_id,
+            op: Op::Insert {
+                pos: 0,
+                content: text.into(),
+            },
+            timestamp: 0,
+            peer_id: PeerId::new("test"),
+            seq: 0,
+        })
+        .unwrap();
+        doc_id
+    }
+
+    fn wait_until(mut cond: impl FnMut() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !cond() {
+            assert!(Instant::now() < deadline, "indexer did not catch up");
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn test_indexer_bootstrap_and_incremental_updates() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Arc::new(RepoManager::init(dir.path(), 10, None, None).unwrap());
+        let service = Arc::new(SearchService::new_in_memory().unwrap());
+        let existing = write_doc(&repo, "notes/alpha.md", "orchard apples");
+
+        let indexer = SearchIndexer::spawn_with_debounce(
+            repo.clone(),
+            service.clone(),
+            Duration::from_millis(20),
+        );
+        let hits = |q: &str| service.search(q, 10).unwrap();
+        wait_until(|| hits("orchard").len() == 1);
+
+        // 编辑与新建
+        let created = write_doc(&repo, "notes/beta.md", "violet harbor");
+        indexer.touch(created);
+        wait_until(|| hits("harbor").len() == 1);
+
+        // 文件夹重命名由 Reconcile 修正路径
+        repo.rename_folder("notes", "archive").unwrap();
+        indexer.reconcile();
+        wait_until(|| hits("archive").len() == 2);
+
+        // 删除
+        repo.delete_doc("archive/alpha.md").unwrap();
+        indexer.touch(existing);
+        wait_until(|| hits("orchard").is_empty());
+        assert_eq!(service.indexed_docs().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_rebuild_index_replaces_stale_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = RepoManager::init(dir.path(), 10, None, None).unwrap();
+        let service = SearchService::new_in_memory().unwrap();
+        service
+            .index_document(DocId::new(), "ghost.md", "stale")
+            .unwrap();
+        write_doc(&repo, "live.md", "fresh words");
+
+        assert_eq!(rebuild_index(&repo, &service).unwrap(), 1);
+        assert!(service.search("stale", 10).unwrap().is_empty());
+        assert_eq!(service.search("fresh", 10).unwrap().len(), 1);
+    }
+}
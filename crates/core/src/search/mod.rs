@@ -8,24 +8,71 @@
 //! - `SearchService`: 管理 Tantivy 索引。
 //!   - `index_document(doc_id, path, content)`: 索引文档。
 //!   - `delete_document(doc_id)`: 从索引中删除文档。
-//!   - `search(query, limit)`: 执行搜索查询。
+//!   - `apply_updates(updates)`: 批量更新，单次提交。
+//!   - `search(query, limit)`: 执行搜索查询，返回带摘要、高亮与行号的 `SearchHit`。
+//! - `SearchIndexer`: 后台索引线程 (防抖 + 批量提交)，见 `indexer`。
+//! - 历史提交与 Peer 影子库版本 (`in:history` / `in:peers`)，见 `archive`。
+//! - `TokenizerKind`: 仓库级分词策略 (CJK 二元组 / 西文词干)，见 `tokenizer`。
+//! - 查询语法 (路径/标签/日期过滤、短语、模糊)，见 `query`。
 //!
 //! **类型**: Plugin MAY (插件可选) - 仅 Standard Profile 启用
 
+use crate::markdown::extract_metadata;
 use crate::models::DocId;
+use crate::protocol::{SearchHit, SearchSource};
+use std::collections::{HashMap, HashSet};
+use std::ops::Bound;
 use std::path::Path;
 use std::sync::Mutex;
-use tantivy::collector::TopDocs;
-use tantivy::query::QueryParser;
-use tantivy::schema::{Field, STORED, STRING, Schema, TEXT, Value};
-use tantivy::{Index, IndexWriter, ReloadPolicy, TantivyDocument, doc};
-
-/// Search result entry
-#[derive(Debug, Clone)]
-pub struct SearchResult {
-    pub doc_id: String, // UUID as string
-    pub path: String,
-    pub score: f32,
+use tantivy::collector::{DocSetCollector, TopDocs};
+use tantivy::query::{
+    AllQuery, BooleanQuery, FuzzyTermQuery, Occur, Query, QueryParser, RangeQuery, RegexQuery,
+    TermQuery,
+};
+use tantivy::schema::{
+    FAST, Field, INDEXED, IndexRecordOption, STORED, STRING, Schema, TextFieldIndexing,
+    TextOptions, Value,
+};
+use tantivy::snippet::SnippetGenerator;
+use tantivy::{Index, IndexWriter, ReloadPolicy, TantivyDocument, Term, doc};
+
+pub mod archive;
+pub mod indexer;
+pub mod query;
+pub mod tokenizer;
+
+pub use indexer::{IndexEvent, SearchIndexer};
+pub use query::Scope;
+pub use tokenizer::{SearchConfig, TokenizerKind};
+
+/// 摘要最大字符数
+const SNIPPET_MAX_CHARS: usize = 160;
+
+/// 索引增量更新
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexUpdate {
+    /// 清空本地版本 (全量重建前使用；历史与影子库条目保留)
+    Clear,
+    /// 新增或覆盖本地文档 (标签从 frontmatter 与正文提取)
+    Upsert {
+        doc_id: DocId,
+        path: String,
+        content: String,
+        /// 最后修改时间 (毫秒)
+        modified: i64,
+    },
+    /// 删除本地文档
+    Delete(DocId),
+    /// 新增或覆盖某一历史提交 / 影子库中的文档版本
+    Archive {
+        source: SearchSource,
+        doc_id: DocId,
+        path: String,
+        content: String,
+        modified: i64,
+    },
+    /// 删除某一来源的全部条目
+    DropSource(SearchSource),
 }
 
 /// Tantivy-based full-text search service
@@ -36,90 +83,229 @@ pub struct SearchService {
     schema: Schema,
     field_doc_id: Field,
     field_path: Field,
+    field_path_raw: Field,
     field_content: Field,
+    field_tags: Field,
+    field_modified: Field,
+    field_source: Field,
+    field_scope: Field,
+    field_entry: Field,
+    /// 历史/影子库归档的同步状态，见 `archive`
+    archive: Mutex<archive::ArchiveState>,
 }
 
 impl SearchService {
     /// Create a new in-memory search service
     pub fn new_in_memory() -> anyhow::Result<Self> {
-        let mut schema_builder = Schema::builder();
-        // Store doc_id as STRING (exact, stored)
-        let field_doc_id = schema_builder.add_text_field("doc_id", STRING | STORED);
-        let field_path = schema_builder.add_text_field("path", TEXT | STORED);
-        let field_content = schema_builder.add_text_field("content", TEXT);
-        let schema = schema_builder.build();
+        Self::new_in_memory_with(TokenizerKind::default())
+    }
 
+    pub fn new_in_memory_with(tokenizer: TokenizerKind) -> anyhow::Result<Self> {
+        let schema = build_schema(tokenizer);
         let index = Index::create_in_ram(schema.clone());
-        let writer = index.writer(50_000_000)?; // 50MB heap
-
-        Ok(Self {
-            index,
-            writer: Mutex::new(writer),
-            schema,
-            field_doc_id,
-            field_path,
-            field_content,
-        })
+        Self::from_index(index, schema)
     }
 
     /// Create a search service backed by a directory
-    pub fn new_on_disk(index_path: &Path) -> anyhow::Result<Self> {
-        let mut schema_builder = Schema::builder();
-        let field_doc_id = schema_builder.add_text_field("doc_id", STRING | STORED);
-        let field_path = schema_builder.add_text_field("path", TEXT | STORED);
-        let field_content = schema_builder.add_text_field("content", TEXT);
-        let schema = schema_builder.build();
-
+    ///
+    /// 已有索引的 Schema (字段或分词器) 与当前版本不一致时清空重建
+    /// (内容由索引器重新填充)。
+    pub fn new_on_disk(index_path: &Path, tokenizer: TokenizerKind) -> anyhow::Result<Self> {
+        let schema = build_schema(tokenizer);
+        if let Ok(existing) = Index::open_in_dir(index_path)
+            && existing.schema() != schema
+        {
+            tracing::info!(
+                "Search schema changed (tokenizer {:?}), recreating index at {:?}",
+                tokenizer,
+                index_path
+            );
+            drop(existing);
+            std::fs::remove_dir_all(index_path)?;
+        }
         std::fs::create_dir_all(index_path)?;
         let index = Index::create_in_dir(index_path, schema.clone())
             .or_else(|_| Index::open_in_dir(index_path))?;
-        let writer = index.writer(50_000_000)?;
+        Self::from_index(index, schema)
+    }
 
+    fn from_index(index: Index, schema: Schema) -> anyhow::Result<Self> {
+        tokenizer::register(&index);
+        let writer = index.writer(50_000_000)?; // 50MB heap
+        let field = |name: &str| schema.get_field(name);
         Ok(Self {
+            field_doc_id: field("doc_id")?,
+            field_path: field("path")?,
+            field_path_raw: field("path_raw")?,
+            field_content: field("content")?,
+            field_tags: field("tags")?,
+            field_modified: field("modified")?,
+            field_source: field("source")?,
+            field_scope: field("scope")?,
+            field_entry: field("entry")?,
+            archive: Mutex::default(),
             index,
             writer: Mutex::new(writer),
             schema,
-            field_doc_id,
-            field_path,
-            field_content,
         })
     }
 
-    /// Index a document
+    /// Index a document (修改时间取当前时间)
     pub fn index_document(&self, doc_id: DocId, path: &str, content: &str) -> anyhow::Result<()> {
-        let mut writer = self.writer.lock().unwrap();
-        let doc_id_str = doc_id.to_string();
+        self.apply_updates(&[IndexUpdate::Upsert {
+            doc_id,
+            path: path.to_string(),
+            content: content.to_string(),
+            modified: chrono::Utc::now().timestamp_millis(),
+        }])
+    }
 
-        // Delete existing document first (upsert)
-        writer.delete_term(tantivy::Term::from_field_text(
-            self.field_doc_id,
-            &doc_id_str,
-        ));
+    /// Delete a document from the index
+    pub fn delete_document(&self, doc_id: DocId) -> anyhow::Result<()> {
+        self.apply_updates(&[IndexUpdate::Delete(doc_id)])
+    }
 
-        writer.add_document(doc!(
-            self.field_doc_id => doc_id_str,
+    /// 批量应用更新，只提交一次
+    ///
+    /// # 后置条件
+    /// - 全部更新在同一次 tantivy commit 中可见；空列表不提交
+    pub fn apply_updates(&self, updates: &[IndexUpdate]) -> anyhow::Result<()> {
+        if updates.is_empty() {
+            return Ok(());
+        }
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        for update in updates {
+            match update {
+                IndexUpdate::Clear => {
+                    writer.delete_term(self.scope_term(Scope::Local));
+                }
+                IndexUpdate::Upsert {
+                    doc_id,
+                    path,
+                    content,
+                    modified,
+                } => {
+                    let source = SearchSource::Local;
+                    writer.delete_term(self.entry_term(&source, *doc_id)?);
+                    writer.add_document(
+                        self.build_doc(&source, *doc_id, path, content, *modified)?,
+                    )?;
+                }
+                IndexUpdate::Delete(doc_id) => {
+                    writer.delete_term(self.entry_term(&SearchSource::Local, *doc_id)?);
+                }
+                IndexUpdate::Archive {
+                    source,
+                    doc_id,
+                    path,
+                    content,
+                    modified,
+                } => {
+                    writer.delete_term(self.entry_term(source, *doc_id)?);
+                    writer
+                        .add_document(self.build_doc(source, *doc_id, path, content, *modified)?)?;
+                }
+                IndexUpdate::DropSource(source) => {
+                    let key = serde_json::to_string(source)?;
+                    writer.delete_term(Term::from_field_text(self.field_source, &key));
+                }
+            }
+        }
+        writer.commit()?;
+        Ok(())
+    }
+
+    fn build_doc(
+        &self,
+        source: &SearchSource,
+        doc_id: DocId,
+        path: &str,
+        content: &str,
+        modified: i64,
+    ) -> anyhow::Result<TantivyDocument> {
+        let mut document = doc!(
+            self.field_doc_id => doc_id.to_string(),
             self.field_path => path,
+            self.field_path_raw => path,
             self.field_content => content,
-        ))?;
+            self.field_modified => modified,
+            self.field_source => serde_json::to_string(source)?,
+            self.field_scope => scope_of(source).as_str(),
+            self.field_entry => entry_key(source, doc_id)?,
+        );
+        for tag in extract_metadata(content).tags {
+            document.add_text(self.field_tags, tag);
+        }
+        Ok(document)
+    }
 
-        writer.commit()?;
-        Ok(())
+    /// 列出索引中的全部本地文档 (DocId -> path)
+    pub fn indexed_docs(&self) -> anyhow::Result<HashMap<DocId, String>> {
+        let searcher = self.index.reader()?.searcher();
+        let local = TermQuery::new(self.scope_term(Scope::Local), IndexRecordOption::Basic);
+        let mut out = HashMap::new();
+        for address in searcher.search(&local, &DocSetCollector)? {
+            let doc: TantivyDocument = searcher.doc(address)?;
+            let path = doc.get_first(self.field_path).and_then(|v| v.as_str());
+            if let (Some(id), Some(path)) = (self.doc_id_of(&doc), path) {
+                out.insert(id, path.to_string());
+            }
+        }
+        Ok(out)
     }
 
-    /// Delete a document from the index
-    pub fn delete_document(&self, doc_id: DocId) -> anyhow::Result<()> {
-        let mut writer = self.writer.lock().unwrap();
-        let doc_id_str = doc_id.to_string();
-        writer.delete_term(tantivy::Term::from_field_text(
-            self.field_doc_id,
-            &doc_id_str,
-        ));
-        writer.commit()?;
-        Ok(())
+    /// 列出已索引的历史提交与影子库来源
+    pub fn archived_sources(&self) -> anyhow::Result<HashSet<SearchSource>> {
+        let searcher = self.index.reader()?.searcher();
+        let local = TermQuery::new(self.scope_term(Scope::Local), IndexRecordOption::Basic);
+        let archived = BooleanQuery::new(vec![
+            (Occur::Must, Box::new(AllQuery) as Box<dyn Query>),
+            (Occur::MustNot, Box::new(local)),
+        ]);
+        let mut out = HashSet::new();
+        for address in searcher.search(&archived, &DocSetCollector)? {
+            let doc: TantivyDocument = searcher.doc(address)?;
+            out.insert(self.source_of(&doc));
+        }
+        Ok(out)
+    }
+
+    fn scope_term(&self, scope: Scope) -> Term {
+        Term::from_field_text(self.field_scope, scope.as_str())
+    }
+
+    fn entry_term(&self, source: &SearchSource, doc_id: DocId) -> anyhow::Result<Term> {
+        Ok(Term::from_field_text(
+            self.field_entry,
+            &entry_key(source, doc_id)?,
+        ))
+    }
+
+    fn source_of(&self, doc: &TantivyDocument) -> SearchSource {
+        doc.get_first(self.field_source)
+            .and_then(|v| v.as_str())
+            .and_then(|s| serde_json::from_str(s).ok())
+            .unwrap_or_default()
+    }
+
+    fn doc_id_of(&self, doc: &TantivyDocument) -> Option<DocId> {
+        doc.get_first(self.field_doc_id)
+            .and_then(|v| v.as_str())
+            .and_then(|s| uuid::Uuid::parse_str(s).ok())
+            .map(DocId)
     }
 
     /// Search for documents
-    pub fn search(&self, query: &str, limit: usize) -> anyhow::Result<Vec<SearchResult>> {
+    ///
+    /// 查询语法见 `query` 模块；空查询返回空列表。
+    ///
+    /// # 后置条件
+    /// - 结果按得分降序；每项附带正文摘要、高亮区间与首个命中的行号
+    pub fn search(&self, query: &str, limit: usize) -> anyhow::Result<Vec<SearchHit>> {
+        let parsed = query::parse(query)?;
+        if parsed.is_empty() {
+            return Ok(Vec::new());
+        }
         let reader = self
             .index
             .reader_builder()
@@ -127,37 +313,189 @@ impl SearchService {
             .try_into()?;
         let searcher = reader.searcher();
 
-        let query_parser =
-            QueryParser::for_index(&self.index, vec![self.field_path, self.field_content]);
-        let query = query_parser.parse_query(query)?;
-
+        let query = self.build_query(&parsed)?;
         let top_docs = searcher.search(&query, &TopDocs::with_limit(limit))?;
 
+        let mut snippets = SnippetGenerator::create(&searcher, &query, self.field_content)?;
+        snippets.set_max_num_chars(SNIPPET_MAX_CHARS);
+
         let mut results = Vec::new();
         for (score, doc_address) in top_docs {
             let retrieved_doc: TantivyDocument = searcher.doc(doc_address)?;
+            let Some(doc_id) = self.doc_id_of(&retrieved_doc) else {
+                continue;
+            };
+            let text = |field| {
+                retrieved_doc
+                    .get_first(field)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            let content = text(self.field_content);
+            let snippet = snippets.snippet(&content);
+            let (line, snippet, highlights) =
+                locate_snippet(&content, snippet.fragment(), snippet.highlighted());
 
-            let doc_id = retrieved_doc
-                .get_first(self.field_doc_id)
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string())
-                .unwrap_or_default();
-
-            let path = retrieved_doc
-                .get_first(self.field_path)
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string())
-                .unwrap_or_default();
-
-            results.push(SearchResult {
+            results.push(SearchHit {
                 doc_id,
-                path,
+                path: text(self.field_path),
                 score,
+                line,
+                snippet,
+                highlights,
+                source: self.source_of(&retrieved_doc),
             });
         }
 
         Ok(results)
     }
+
+    /// 将解析结果组合为 tantivy 查询 (各条件之间为 AND)
+    fn build_query(&self, parsed: &query::ParsedQuery) -> anyhow::Result<Box<dyn Query>> {
+        let scopes: Vec<(Occur, Box<dyn Query>)> = parsed
+            .scopes()
+            .into_iter()
+            .map(|scope| {
+                let term = TermQuery::new(self.scope_term(scope), IndexRecordOption::Basic);
+                (Occur::Should, Box::new(term) as Box<dyn Query>)
+            })
+            .collect();
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> =
+            vec![(Occur::Must, Box::new(BooleanQuery::new(scopes)))];
+
+        if !parsed.text.is_empty() {
+            let query_parser =
+                QueryParser::for_index(&self.index, vec![self.field_path, self.field_content]);
+            clauses.push((
+                Occur::Must,
+                query_parser.parse_query(&parsed.text.join(" "))?,
+            ));
+        }
+
+        // 模糊词按正文分析器切分，每个 token 都须近似命中
+        let mut analyzer = self.index.tokenizer_for_field(self.field_content)?;
+        for (word, distance) in &parsed.fuzzy {
+            let mut stream = analyzer.token_stream(word);
+            while let Some(token) = stream.next() {
+                let term = Term::from_field_text(self.field_content, &token.text);
+                clauses.push((
+                    Occur::Must,
+                    Box::new(FuzzyTermQuery::new(term, *distance, true)),
+                ));
+            }
+        }
+
+        if let Some(prefix) = &parsed.path_prefix {
+            let pattern = format!("{}.*", regex::escape(prefix));
+            clauses.push((
+                Occur::Must,
+                Box::new(RegexQuery::from_pattern(&pattern, self.field_path_raw)?),
+            ));
+        }
+
+        for tag in &parsed.tags {
+            let term = Term::from_field_text(self.field_tags, tag);
+            clauses.push((
+                Occur::Must,
+                Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
+            ));
+        }
+
+        if parsed.after.is_some() || parsed.before.is_some() {
+            clauses.push((
+                Occur::Must,
+                Box::new(RangeQuery::new_i64_bounds(
+                    "modified".to_string(),
+                    parsed.after.map_or(Bound::Unbounded, Bound::Included),
+                    parsed.before.map_or(Bound::Unbounded, Bound::Excluded),
+                )),
+            ));
+        }
+
+        Ok(Box::new(BooleanQuery::new(clauses)))
+    }
+}
+
+/// 由 tantivy 摘要计算行号，并整理为单行摘要与有序、不重叠的高亮区间
+///
+/// 无高亮 (如仅路径命中) 时退回到首个非空行。
+fn locate_snippet(
+    content: &str,
+    fragment: &str,
+    highlighted: &[std::ops::Range<usize>],
+) -> (u32, String, Vec<(u32, u32)>) {
+    let offset = if fragment.is_empty() || highlighted.is_empty() {
+        None
+    } else {
+        content.find(fragment)
+    };
+    let Some(offset) = offset else {
+        let (index, first) = content
+            .lines()
+            .enumerate()
+            .find(|(_, l)| !l.trim().is_empty())
+            .unwrap_or((0, ""));
+        let snippet: String = first.trim().chars().take(SNIPPET_MAX_CHARS).collect();
+        return (index as u32 + 1, snippet, Vec::new());
+    };
+
+    let mut ranges: Vec<(usize, usize)> = highlighted.iter().map(|r| (r.start, r.end)).collect();
+    ranges.sort_unstable();
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    let line = content[..offset + merged[0].0].matches('\n').count() as u32 + 1;
+    // 去掉开头空白并把换行替换为空格 (字节长度不变)
+    let trimmed = fragment.trim_start();
+    let shift = fragment.len() - trimmed.len();
+    let snippet = trimmed.replace(['\n', '\r', '\t'], " ");
+    let highlights = merged
+        .into_iter()
+        .map(|(s, e)| ((s - shift) as u32, (e - shift) as u32))
+        .collect();
+    (line, snippet, highlights)
+}
+
+fn scope_of(source: &SearchSource) -> Scope {
+    match source {
+        SearchSource::Local => Scope::Local,
+        SearchSource::Commit { .. } => Scope::History,
+        SearchSource::Peer { .. } => Scope::Peers,
+    }
+}
+
+/// 条目唯一键: 同一来源中每个文档只保留一个版本
+fn entry_key(source: &SearchSource, doc_id: DocId) -> anyhow::Result<String> {
+    Ok(format!("{}|{}", serde_json::to_string(source)?, doc_id))
+}
+
+fn build_schema(tokenizer: TokenizerKind) -> Schema {
+    let text = TextOptions::default().set_indexing_options(
+        TextFieldIndexing::default()
+            .set_tokenizer(tokenizer.name())
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+    );
+    let mut schema_builder = Schema::builder();
+    // Store doc_id as STRING (exact, stored)
+    schema_builder.add_text_field("doc_id", STRING | STORED);
+    schema_builder.add_text_field("path", text.clone() | STORED);
+    // 原始路径 (不分词)，用于前缀过滤
+    schema_builder.add_text_field("path_raw", STRING);
+    // 正文需存储以生成摘要
+    schema_builder.add_text_field("content", text | STORED);
+    schema_builder.add_text_field("tags", STRING);
+    schema_builder.add_i64_field("modified", INDEXED | FAST | STORED);
+    // 来源 (SearchSource JSON)、范围与 (来源, 文档) 唯一键
+    schema_builder.add_text_field("source", STRING | STORED);
+    schema_builder.add_text_field("scope", STRING);
+    schema_builder.add_text_field("entry", STRING);
+    schema_builder.build()
 }
 
 #[cfg(test)]
@@ -180,11 +518,11 @@ mod tests {
         // Search
         let results = service.search("hello", 10)?;
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0].doc_id, doc1.to_string());
+        assert_eq!(results[0].doc_id, doc1);
 
         let results = service.search("rust", 10)?;
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0].doc_id, doc2.to_string());
+        assert_eq!(results[0].doc_id, doc2);
 
         // Search by path
         let results = service.search("docs", 10)?;
@@ -192,4 +530,197 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_apply_updates_single_batch() -> anyhow::Result<()> {
+        let service = SearchService::new_in_memory()?;
+        let doc1 = DocId::new();
+        let doc2 = DocId::new();
+
+        service.apply_updates(&[
+            IndexUpdate::Upsert {
+                doc_id: doc1,
+                path: "a.md".into(),
+                content: "alpha".into(),
+                modified: 0,
+            },
+            IndexUpdate::Upsert {
+                doc_id: doc2,
+                path: "b.md".into(),
+                content: "beta".into(),
+                modified: 0,
+            },
+            IndexUpdate::Upsert {
+                doc_id: doc1,
+                path: "a2.md".into(),
+                content: "gamma".into(),
+                modified: 0,
+            },
+        ])?;
+        assert!(service.search("alpha", 10)?.is_empty());
+        assert_eq!(service.search("gamma", 10)?[0].path, "a2.md");
+        assert_eq!(service.indexed_docs()?.len(), 2);
+
+        service.apply_updates(&[IndexUpdate::Clear, IndexUpdate::Delete(doc2)])?;
+        assert!(service.indexed_docs()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_mixed_chinese_english_queries() -> anyhow::Result<()> {
+        let service = SearchService::new_in_memory_with(TokenizerKind::Cjk)?;
+        let sync = DocId::new();
+        let search = DocId::new();
+        let english = DocId::new();
+        service.apply_updates(&[
+            IndexUpdate::Upsert {
+                doc_id: sync,
+                path: "笔记/同步设计.md".into(),
+                content: "P2P 同步使用 Vector Clock 判断落后的操作".into(),
+                modified: 0,
+            },
+            IndexUpdate::Upsert {
+                doc_id: search,
+                path: "notes/search.md".into(),
+                content: "全文搜索基于Tantivy，支持中文分词与 stemming".into(),
+                modified: 0,
+            },
+            IndexUpdate::Upsert {
+                doc_id: english,
+                path: "notes/english.md".into(),
+                content: "Indexing documents with searchable vectors".into(),
+                modified: 0,
+            },
+        ])?;
+        let ids = |q: &str| -> anyhow::Result<Vec<String>> {
+            Ok(service
+                .search(q, 10)?
+                .into_iter()
+                .map(|r| r.doc_id.to_string())
+                .collect())
+        };
+
+        assert_eq!(ids("同步")?, [sync.to_string()]);
+        assert_eq!(ids("中文分词")?, [search.to_string()]);
+        assert_eq!(ids("搜索 tantivy")?.first(), Some(&search.to_string()));
+        assert_eq!(ids("基于Tantivy")?, [search.to_string()]);
+        // 路径中的中文同样可检索
+        assert_eq!(ids("设计")?, [sync.to_string()]);
+        // 西文词干: vector / vectors, index / indexing
+        let mut hits = ids("vector")?;
+        hits.sort();
+        let mut expected = vec![sync.to_string(), english.to_string()];
+        expected.sort();
+        assert_eq!(hits, expected);
+        assert_eq!(ids("indexed")?, [english.to_string()]);
+        // 非子串不命中
+        assert!(ids("搜同")?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_snippet_highlights_and_line_numbers() -> anyhow::Result<()> {
+        let service = SearchService::new_in_memory_with(TokenizerKind::Cjk)?;
+        let doc = DocId::new();
+        let content =
+            "# Title\n\nIntro paragraph.\nSecond line mentions the orchard.\n支持中文分词检索\n";
+        service.index_document(doc, "notes/a.md", content)?;
+
+        let hit = &service.search("orchard", 10)?[0];
+        assert_eq!(hit.line, 4);
+        assert!(!hit.snippet.contains('\n'));
+        let (s, e) = hit.highlights[0];
+        assert_eq!(&hit.snippet[s as usize..e as usize], "orchard");
+
+        // 重叠的二元组高亮合并为一个区间
+        let hit = &service.search("中文分词", 10)?[0];
+        assert_eq!(hit.line, 5);
+        let (s, e) = hit.highlights[0];
+        assert_eq!(hit.highlights.len(), 1);
+        assert_eq!(&hit.snippet[s as usize..e as usize], "中文分词");
+
+        // 仅路径命中: 退回首个非空行
+        let hit = &service.search("notes", 10)?[0];
+        assert_eq!((hit.line, hit.snippet.as_str()), (1, "# Title"));
+        assert!(hit.highlights.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_filters() -> anyhow::Result<()> {
+        let service = SearchService::new_in_memory()?;
+        let day = |d: &str| {
+            query::parse(&format!("after:{}", d))
+                .unwrap()
+                .after
+                .unwrap()
+        };
+        let alpha = DocId::new();
+        let beta = DocId::new();
+        let gamma = DocId::new();
+        service.apply_updates(&[
+            IndexUpdate::Upsert {
+                doc_id: alpha,
+                path: "projects/alpha.md".into(),
+                content: "Vector clock merge notes #Rust".into(),
+                modified: day("2024-05-10"),
+            },
+            IndexUpdate::Upsert {
+                doc_id: beta,
+                path: "projects-old/beta.md".into(),
+                content: "clock vector ordering #rust #draft".into(),
+                modified: day("2024-06-10"),
+            },
+            IndexUpdate::Upsert {
+                doc_id: gamma,
+                path: "journal/gamma.md".into(),
+                content: "Synchronisation merge log".into(),
+                modified: day("2024-05-20"),
+            },
+        ])?;
+        let ids = |q: &str| -> anyhow::Result<Vec<DocId>> {
+            let mut ids: Vec<_> = service
+                .search(q, 10)?
+                .into_iter()
+                .map(|r| r.doc_id)
+                .collect();
+            ids.sort_by_key(|d| d.to_string());
+            Ok(ids)
+        };
+        let sorted = |mut v: Vec<DocId>| {
+            v.sort_by_key(|d| d.to_string());
+            v
+        };
+
+        assert_eq!(ids("path:projects/ clock")?, [alpha]);
+        assert_eq!(ids("tag:rust")?, sorted(vec![alpha, beta]));
+        assert_eq!(ids("#draft")?, [beta]);
+        assert_eq!(ids("merge after:2024-05-15")?, [gamma]);
+        assert_eq!(ids("before:2024-06-10")?, sorted(vec![alpha, gamma]));
+        assert_eq!(ids("\"vector clock\"")?, [alpha]);
+        assert_eq!(ids("synchronization~2")?, [gamma]);
+        assert!(ids("synchronization")?.is_empty());
+        assert!(service.search("after:soon", 10).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_on_disk_tokenizer_switch_recreates_index() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("index");
+        {
+            let service = SearchService::new_on_disk(&path, TokenizerKind::Latin)?;
+            service.index_document(DocId::new(), "a.md", "中文内容")?;
+            assert_eq!(service.indexed_docs()?.len(), 1);
+        }
+        let service = SearchService::new_on_disk(&path, TokenizerKind::Latin)?;
+        assert_eq!(service.indexed_docs()?.len(), 1);
+        drop(service);
+
+        let service = SearchService::new_on_disk(&path, TokenizerKind::Cjk)?;
+        assert!(service.indexed_docs()?.is_empty());
+        service.index_document(DocId::new(), "a.md", "中文内容")?;
+        assert_eq!(service.search("内容", 10)?.len(), 1);
+        Ok(())
+    }
 }
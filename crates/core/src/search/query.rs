@@ -0,0 +1,235 @@
+// crates\core\src\search
+//! # 查询语法 (Query Syntax)
+//!
+//! 将用户输入拆分为全文部分与过滤条件:
+//! - `path:projects/`: 路径前缀
+//! - `tag:rust` / `#rust`: 标签 (不区分大小写)
+//! - `after:2024-05-01` / `before:2024-06-01`: 修改时间 (UTC，`after` 含当天，`before` 不含)
+//! - `"exact phrase"`: 短语
+//! - `word~` / `word~2`: 模糊匹配 (编辑距离 1 或 2)
+//! - `in:history` / `in:peers` / `in:local` / `in:all`: 搜索范围 (可叠加，缺省仅本地)
+//!
+//! 其余词交给 tantivy `QueryParser`，保持原有的多字段 OR 语义。
+
+use anyhow::{Context, Result, bail};
+use chrono::NaiveDate;
+
+/// 模糊匹配允许的最大编辑距离 (tantivy 上限)
+const MAX_FUZZY_DISTANCE: u8 = 2;
+
+/// 搜索范围: 本地当前版本、历史提交版本、Peer 影子库
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    Local,
+    History,
+    Peers,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 3] = [Scope::Local, Scope::History, Scope::Peers];
+
+    /// 索引中 `scope` 字段的取值
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Scope::Local => "local",
+            Scope::History => "history",
+            Scope::Peers => "peers",
+        }
+    }
+}
+
+/// 解析后的查询
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ParsedQuery {
+    /// 普通词与短语 (短语保留双引号)，交给 QueryParser
+    pub text: Vec<String>,
+    /// 模糊词及编辑距离
+    pub fuzzy: Vec<(String, u8)>,
+    pub path_prefix: Option<String>,
+    pub tags: Vec<String>,
+    /// 修改时间下界 (含，毫秒)
+    pub after: Option<i64>,
+    /// 修改时间上界 (不含，毫秒)
+    pub before: Option<i64>,
+    /// 显式指定的范围 (为空表示仅本地)
+    pub scopes: Vec<Scope>,
+}
+
+impl ParsedQuery {
+    /// 是否包含全文条件 (否则仅按过滤条件枚举)
+    pub fn has_text(&self) -> bool {
+        !self.text.is_empty() || !self.fuzzy.is_empty()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.has_text()
+            && self.path_prefix.is_none()
+            && self.tags.is_empty()
+            && self.after.is_none()
+            && self.before.is_none()
+    }
+
+    /// 实际生效的搜索范围
+    pub fn scopes(&self) -> Vec<Scope> {
+        if self.scopes.is_empty() {
+            vec![Scope::Local]
+        } else {
+            self.scopes.clone()
+        }
+    }
+}
+
+/// 解析查询字符串
+///
+/// # 前置条件
+/// - 日期须为 `YYYY-MM-DD`，否则返回错误
+pub fn parse(input: &str) -> Result<ParsedQuery> {
+    let mut parsed = ParsedQuery::default();
+    for word in split_words(input) {
+        if word.starts_with('"') {
+            parsed.text.push(word);
+        } else if let Some(prefix) = word.strip_prefix("path:") {
+            parsed.path_prefix = Some(prefix.trim_start_matches('/').to_string());
+        } else if let Some(tag) = word.strip_prefix("tag:").or_else(|| tag_word(&word)) {
+            let tag = tag.trim_start_matches('#').to_lowercase();
+            if !tag.is_empty() {
+                parsed.tags.push(tag);
+            }
+        } else if let Some(date) = word.strip_prefix("after:") {
+            parsed.after = Some(day_start_ms(date)?);
+        } else if let Some(date) = word.strip_prefix("before:") {
+            parsed.before = Some(day_start_ms(date)?);
+        } else if let Some(scope) = word.strip_prefix("in:") {
+            for scope in parse_scope(scope)? {
+                if !parsed.scopes.contains(&scope) {
+                    parsed.scopes.push(scope);
+                }
+            }
+        } else if let Some((term, distance)) = fuzzy_word(&word)? {
+            parsed.fuzzy.push((term, distance));
+        } else {
+            parsed.text.push(word);
+        }
+    }
+    Ok(parsed)
+}
+
+/// 按空白切分，双引号内的空白保留；未闭合的引号视为延伸到结尾
+fn split_words(input: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in input.chars() {
+        match c {
+            '"' if quoted => {
+                current.push(c);
+                quoted = false;
+                words.push(std::mem::take(&mut current));
+            }
+            '"' if current.is_empty() => {
+                current.push(c);
+                quoted = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    words.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if quoted {
+        current.push('"');
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words.retain(|w| w != "\"\"");
+    words
+}
+
+fn tag_word(word: &str) -> Option<&str> {
+    word.strip_prefix('#')
+        .filter(|t| t.chars().any(|c| !c.is_ascii_digit()))
+}
+
+fn fuzzy_word(word: &str) -> Result<Option<(String, u8)>> {
+    let Some((term, distance)) = word.rsplit_once('~') else {
+        return Ok(None);
+    };
+    if term.is_empty() {
+        return Ok(None);
+    }
+    let distance = match distance {
+        "" => 1,
+        d => d
+            .parse::<u8>()
+            .with_context(|| format!("Invalid fuzzy distance in '{}'", word))?,
+    };
+    if distance == 0 || distance > MAX_FUZZY_DISTANCE {
+        bail!(
+            "Fuzzy distance must be 1..={} in '{}'",
+            MAX_FUZZY_DISTANCE,
+            word
+        );
+    }
+    Ok(Some((term.to_string(), distance)))
+}
+
+fn parse_scope(scope: &str) -> Result<Vec<Scope>> {
+    Ok(match scope.to_lowercase().as_str() {
+        "local" => vec![Scope::Local],
+        "history" => vec![Scope::History],
+        "peers" | "peer" => vec![Scope::Peers],
+        "all" => Scope::ALL.to_vec(),
+        _ => bail!(
+            "Unknown search scope '{}', expected local/history/peers/all",
+            scope
+        ),
+    })
+}
+
+fn day_start_ms(date: &str) -> Result<i64> {
+    let day = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .with_context(|| format!("Invalid date '{}', expected YYYY-MM-DD", date))?;
+    Ok(day
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is valid")
+        .and_utc()
+        .timestamp_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filters_and_syntax() -> Result<()> {
+        let q = parse(
+            r#"path:/projects/ tag:Rust #async "vector clock" merge~ sync~2 after:2024-05-01 before:2024-06-01 plain"#,
+        )?;
+        assert_eq!(q.path_prefix.as_deref(), Some("projects/"));
+        assert_eq!(q.tags, ["rust", "async"]);
+        assert_eq!(q.text, ["\"vector clock\"", "plain"]);
+        assert_eq!(q.fuzzy, [("merge".to_string(), 1), ("sync".to_string(), 2)]);
+        assert_eq!(q.after, Some(1_714_521_600_000));
+        assert_eq!(q.before, Some(1_717_200_000_000));
+
+        assert!(parse("after:yesterday").is_err());
+        assert!(parse("word~5").is_err());
+        assert!(parse("").unwrap().is_empty());
+        // 纯数字的 # 视为普通词; 未闭合引号延伸到结尾
+        let q = parse("#42 \"open phrase")?;
+        assert!(q.tags.is_empty());
+        assert_eq!(q.text, ["#42", "\"open phrase\""]);
+
+        assert_eq!(parse("x")?.scopes(), [Scope::Local]);
+        assert_eq!(
+            parse("x in:history in:peers in:history")?.scopes(),
+            [Scope::History, Scope::Peers]
+        );
+        assert_eq!(parse("x in:all")?.scopes(), Scope::ALL);
+        assert!(parse("x in:trash").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+// crates/core/src/search/tokenizer.rs
+//! # 分词器 (Tokenizers)
+//!
+//! 注册到 tantivy 的两套分析器，按仓库配置 (`.deve/search.json`) 选择:
+//! - `latin`: 按非字母数字切分 + 小写 + 英文词干
+//! - `cjk` (默认): 中日韩连续字符切为重叠二元组 (bigram)，其余部分同 `latin`
+//!
+//! ## Invariants
+//! - 索引与查询使用同一分析器 (tantivy 按字段记录分词器名称)
+//! - 二元组位置连续，多字查询由 QueryParser 转为短语查询，等价于子串匹配
+//! - 单个 CJK 字符仅在其独立成段时可被检索 (二元组不含单字)
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tantivy::Index;
+use tantivy::tokenizer::{
+    Language, LowerCaser, RemoveLongFilter, SimpleTokenizer, Stemmer, TextAnalyzer, Token,
+    TokenStream, Tokenizer,
+};
+
+/// 过长的 token (如 base64 片段) 不进入索引
+const MAX_TOKEN_LEN: usize = 40;
+
+/// 仓库级分词策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenizerKind {
+    /// 仅西文 (词干化)
+    Latin,
+    /// 中日韩二元组 + 西文词干化
+    #[default]
+    Cjk,
+}
+
+impl TokenizerKind {
+    /// 注册到 tantivy 的分析器名称
+    pub fn name(self) -> &'static str {
+        match self {
+            TokenizerKind::Latin => "deve_latin",
+            TokenizerKind::Cjk => "deve_cjk",
+        }
+    }
+}
+
+/// 仓库搜索配置 (`.deve/search.json`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchConfig {
+    pub tokenizer: TokenizerKind,
+}
+
+impl SearchConfig {
+    /// 读取配置，文件不存在或无法解析时使用默认值
+    pub fn load(deve_dir: &Path) -> Self {
+        let path = deve_dir.join("search.json");
+        match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                tracing::warn!("Invalid {:?}, using defaults: {}", path, e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+}
+
+/// 在索引上注册全部分析器
+pub fn register(index: &Index) {
+    let manager = index.tokenizers();
+    manager.register(
+        TokenizerKind::Latin.name(),
+        TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(RemoveLongFilter::limit(MAX_TOKEN_LEN))
+            .filter(LowerCaser)
+            .filter(Stemmer::new(Language::English))
+            .build(),
+    );
+    manager.register(
+        TokenizerKind::Cjk.name(),
+        TextAnalyzer::builder(CjkBigramTokenizer)
+            .filter(RemoveLongFilter::limit(MAX_TOKEN_LEN))
+            .filter(LowerCaser)
+            .filter(Stemmer::new(Language::English))
+            .build(),
+    );
+}
+
+/// 是否为中日韩文字 (汉字、假名、谚文)
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'   // 平假名、片假名
+        | '\u{3400}'..='\u{4DBF}' // 扩展 A
+        | '\u{4E00}'..='\u{9FFF}' // 基本区
+        | '\u{AC00}'..='\u{D7AF}' // 谚文音节
+        | '\u{F900}'..='\u{FAFF}' // 兼容汉字
+        | '\u{20000}'..='\u{2EBEF}' // 扩展 B-F
+    )
+}
+
+/// CJK 二元组 + 西文单词切分
+#[derive(Clone, Default)]
+pub struct CjkBigramTokenizer;
+
+pub struct CjkTokenStream {
+    tokens: Vec<Token>,
+    index: usize,
+}
+
+impl Tokenizer for CjkBigramTokenizer {
+    type TokenStream<'a> = CjkTokenStream;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> CjkTokenStream {
+        let mut tokens = Vec::new();
+        let mut push = |from: usize, to: usize| {
+            tokens.push(Token {
+                offset_from: from,
+                offset_to: to,
+                position: tokens.len(),
+                text: text[from..to].to_string(),
+                position_length: 1,
+            });
+        };
+
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        let end_of = |i: usize| chars.get(i).map_or(text.len(), |(o, _)| *o);
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i].1;
+            if is_cjk(c) {
+                let start = i;
+                while i < chars.len() && is_cjk(chars[i].1) {
+                    i += 1;
+                }
+                if i - start == 1 {
+                    push(chars[start].0, end_of(i));
+                } else {
+                    for (j, (offset, _)) in chars.iter().enumerate().take(i - 1).skip(start) {
+                        push(*offset, end_of(j + 2));
+                    }
+                }
+            } else if c.is_alphanumeric() {
+                let start = i;
+                while i < chars.len() && chars[i].1.is_alphanumeric() && !is_cjk(chars[i].1) {
+                    i += 1;
+                }
+                push(chars[start].0, end_of(i));
+            } else {
+                i += 1;
+            }
+        }
+
+        CjkTokenStream {
+            tokens,
+            index: usize::MAX,
+        }
+    }
+}
+
+impl TokenStream for CjkTokenStream {
+    fn advance(&mut self) -> bool {
+        self.index = self.index.wrapping_add(1);
+        self.index < self.tokens.len()
+    }
+
+    fn token(&self) -> &Token {
+        &self.tokens[self.index]
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.tokens[self.index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(kind: TokenizerKind, text: &str) -> Vec<String> {
+        let index = Index::create_in_ram(tantivy::schema::Schema::builder().build());
+        register(&index);
+        let mut analyzer = index.tokenizers().get(kind.name()).unwrap();
+        let mut stream = analyzer.token_stream(text);
+        let mut out = Vec::new();
+        while let Some(t) = stream.next() {
+            out.push(t.text.clone());
+        }
+        out
+    }
+
+    #[test]
+    fn test_cjk_bigrams_with_latin_stemming() {
+        assert_eq!(
+            tokens(TokenizerKind::Cjk, "全文搜索 Indexing, 云"),
+            ["全文", "文搜", "搜索", "index", "云"]
+        );
+        assert_eq!(
+            tokens(TokenizerKind::Cjk, "Rust异步编程"),
+            ["rust", "异步", "步编", "编程"]
+        );
+        assert_eq!(
+            tokens(TokenizerKind::Latin, "Running tests"),
+            ["run", "test"]
+        );
+    }
+}
@@ -7,6 +7,7 @@
 //! - Token 有效期严格为 24 小时
 //! - `ver` 字段用于 Token Revocation（密码变更后递增）
 //! - 签名密钥来自环境变量 `AUTH_SECRET`，禁止硬编码
+//! - 双因素挑战 Token 使用派生密钥签名，无法冒充会话 Token
 
 use anyhow::{Result, anyhow};
 use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
@@ -14,12 +15,16 @@ use serde::{Deserialize, Serialize};
 
 /// JWT 有效期: 24 小时 (秒)
 const TOKEN_LIFETIME_SECS: i64 = 86_400;
+/// 双因素挑战 Token 有效期: 5 分钟 (秒)
+const CHALLENGE_LIFETIME_SECS: i64 = 300;
+/// 敏感操作要求的第二因素新鲜度: 10 分钟 (秒)
+pub const MFA_FRESHNESS_SECS: i64 = 600;
 
 /// JWT Payload (Claims)
 ///
 /// 遵循 `09_auth.md` 规范:
 /// ```json
-/// { "sub": "admin", "iat": ..., "exp": ..., "ver": 1 }
+/// { "sub": "admin", "iat": ..., "exp": ..., "ver": 1, "mfa_at": ... }
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Claims {
@@ -31,6 +36,17 @@ pub struct Claims {
     pub exp: i64,
     /// Token Version — 用于 Revocation
     pub ver: u32,
+    /// 第二因素最近一次校验时间 (Unix timestamp)，未校验为 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mfa_at: Option<i64>,
+}
+
+impl Claims {
+    /// 第二因素是否在新鲜度窗口内完成校验
+    pub fn has_recent_mfa(&self, now: i64) -> bool {
+        self.mfa_at
+            .is_some_and(|at| now - at >= 0 && now - at <= MFA_FRESHNESS_SECS)
+    }
 }
 
 /// 签发 JWT Token
@@ -42,15 +58,58 @@ pub struct Claims {
 /// # 后置条件
 /// - 返回的 Token 在 24 小时内有效
 pub fn issue_token(secret: &str, token_version: u32) -> Result<String> {
+    issue_token_with_mfa(secret, token_version, None)
+}
+
+/// 签发携带第二因素状态的 JWT Token
+///
+/// # 后置条件
+/// - `mfa_at` 写入 Claims，供敏感操作判断新鲜度
+pub fn issue_token_with_mfa(
+    secret: &str,
+    token_version: u32,
+    mfa_at: Option<i64>,
+) -> Result<String> {
     let now = chrono::Utc::now().timestamp();
     let claims = Claims {
         sub: "admin".into(),
         iat: now,
         exp: now + TOKEN_LIFETIME_SECS,
         ver: token_version,
+        mfa_at,
+    };
+    encode(secret, &claims)
+}
+
+/// 签发双因素挑战 Token (密码校验通过、等待 TOTP)
+///
+/// # 后置条件
+/// - 使用派生密钥签名，`validate_token` 必然拒绝
+/// - 5 分钟内有效
+pub fn issue_challenge(secret: &str, token_version: u32) -> Result<String> {
+    let now = chrono::Utc::now().timestamp();
+    let claims = Claims {
+        sub: "admin".into(),
+        iat: now,
+        exp: now + CHALLENGE_LIFETIME_SECS,
+        ver: token_version,
+        mfa_at: None,
     };
+    encode(&challenge_secret(secret), &claims)
+}
+
+/// 验证双因素挑战 Token
+pub fn validate_challenge(secret: &str, token: &str, current_version: u32) -> Result<Claims> {
+    validate_token(&challenge_secret(secret), token, current_version)
+}
+
+fn challenge_secret(secret: &str) -> String {
+    format!("{}:mfa-challenge", secret)
+}
+
+fn encode(secret: &str, claims: &Claims) -> Result<String> {
     let key = EncodingKey::from_secret(secret.as_bytes());
-    jsonwebtoken::encode(&Header::default(), &claims, &key)
+    jsonwebtoken::encode(&Header::default(), claims, &key)
         .map_err(|e| anyhow!("JWT encode failed: {}", e))
 }
 
@@ -104,4 +163,28 @@ mod tests {
         let result = validate_token("secret_b_32_bytes_long_xxxxxxxx!", &token, 1);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_challenge_not_a_session_token() {
+        let secret = "test_secret_key_at_least_32_bytes_long!";
+        let challenge = issue_challenge(secret, 1).unwrap();
+        assert!(validate_token(secret, &challenge, 1).is_err());
+        assert!(validate_challenge(secret, &challenge, 1).is_ok());
+
+        let session = issue_token(secret, 1).unwrap();
+        assert!(validate_challenge(secret, &session, 1).is_err());
+    }
+
+    #[test]
+    fn test_mfa_freshness() {
+        let secret = "test_secret_key_at_least_32_bytes_long!";
+        let now = chrono::Utc::now().timestamp();
+        let token = issue_token_with_mfa(secret, 1, Some(now)).unwrap();
+        let claims = validate_token(secret, &token, 1).unwrap();
+        assert!(claims.has_recent_mfa(now + 60));
+        assert!(!claims.has_recent_mfa(now + MFA_FRESHNESS_SECS + 1));
+
+        let plain = validate_token(secret, &issue_token(secret, 1).unwrap(), 1).unwrap();
+        assert!(!plain.has_recent_mfa(now));
+    }
 }
@@ -0,0 +1,258 @@
+// crates/core/src/security/auth/mfa.rs
+//! # 双因素认证存储 (MFA Store)
+//!
+//! 持久化 TOTP 密钥与恢复码，管理启用流程。
+//!
+//! ## 启用流程
+//! 1. `begin_enrolment`: 生成候选密钥 (仅内存)，返回 `otpauth://` URI
+//! 2. `confirm_enrolment`: 用户提交首个口令，校验通过后落盘并返回恢复码明文 (仅此一次)
+//!
+//! ## Invariants
+//! - 恢复码仅以 SHA256 哈希存储，使用后立即作废
+//! - 同一时间步的 TOTP 口令不可重复使用 (重放保护)
+//! - 存储文件权限为 0600 (Unix)
+
+use anyhow::{Result, anyhow};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::totp;
+use crate::security::hashing::sha256_hex;
+
+/// 恢复码数量
+const RECOVERY_CODE_COUNT: usize = 10;
+/// 恢复码长度 (字符)
+const RECOVERY_CODE_LEN: usize = 10;
+/// 配置 URI 中的签发者名称
+const ISSUER: &str = "Deve-Note";
+
+/// 落盘状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct MfaState {
+    /// TOTP 密钥 (hex)
+    secret: Option<String>,
+    /// 恢复码 SHA256 哈希
+    recovery_hashes: Vec<String>,
+    /// 最近一次成功使用的时间步
+    last_step: u64,
+}
+
+/// 启用流程返回给用户的配置信息
+#[derive(Debug, Clone, Serialize)]
+pub struct Enrolment {
+    /// Base32 编码的密钥 (手动输入用)
+    pub secret: String,
+    /// `otpauth://` URI (二维码内容)
+    pub uri: String,
+}
+
+/// 双因素认证存储
+pub struct MfaStore {
+    path: PathBuf,
+    state: Mutex<MfaState>,
+    pending_secret: Mutex<Option<Vec<u8>>>,
+}
+
+impl MfaStore {
+    /// 从文件加载 (文件不存在视为未启用)
+    pub fn load(path: &Path) -> Result<Self> {
+        let state = if path.exists() {
+            serde_json::from_slice(&std::fs::read(path)?)?
+        } else {
+            MfaState::default()
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            state: Mutex::new(state),
+            pending_secret: Mutex::new(None),
+        })
+    }
+
+    /// 是否已启用 TOTP
+    pub fn is_enabled(&self) -> bool {
+        self.lock_state().secret.is_some()
+    }
+
+    /// 剩余可用恢复码数量
+    pub fn recovery_codes_left(&self) -> usize {
+        self.lock_state().recovery_hashes.len()
+    }
+
+    /// 开始启用流程，生成候选密钥
+    pub fn begin_enrolment(&self, account: &str) -> Result<Enrolment> {
+        if self.is_enabled() {
+            return Err(anyhow!("TOTP already enabled"));
+        }
+        let secret = totp::generate_secret();
+        let enrolment = Enrolment {
+            secret: totp::base32_encode(&secret),
+            uri: totp::provisioning_uri(&secret, ISSUER, account),
+        };
+        *self
+            .pending_secret
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(secret);
+        Ok(enrolment)
+    }
+
+    /// 确认启用，返回恢复码明文
+    ///
+    /// # 前置条件
+    /// - 已调用 `begin_enrolment`
+    /// - `code` 为候选密钥生成的当前口令
+    pub fn confirm_enrolment(&self, code: &str, now: i64) -> Result<Vec<String>> {
+        let mut pending = self
+            .pending_secret
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let secret = pending
+            .as_ref()
+            .ok_or_else(|| anyhow!("No pending TOTP enrolment"))?;
+        let step = totp::verify_code(secret, code, now).ok_or_else(|| anyhow!("Invalid code"))?;
+
+        let codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+            .map(|_| generate_recovery_code())
+            .collect();
+        let new_state = MfaState {
+            secret: Some(hex::encode(secret)),
+            recovery_hashes: codes.iter().map(|c| hash_recovery_code(c)).collect(),
+            last_step: step,
+        };
+        self.persist(&new_state)?;
+        *self.lock_state() = new_state;
+        *pending = None;
+        Ok(codes)
+    }
+
+    /// 校验第二因素 (TOTP 口令或恢复码)
+    ///
+    /// # 后置条件
+    /// - 恢复码命中后立即作废并落盘
+    /// - 已用过的时间步再次提交返回 false
+    pub fn verify(&self, code: &str, now: i64) -> bool {
+        let mut state = self.lock_state();
+        let Some(secret) = state.secret.as_ref().and_then(|s| hex::decode(s).ok()) else {
+            return false;
+        };
+
+        if let Some(step) = totp::verify_code(&secret, code, now) {
+            if step <= state.last_step {
+                tracing::warn!("TOTP code replay rejected");
+                return false;
+            }
+            state.last_step = step;
+            if let Err(e) = self.persist(&state) {
+                tracing::error!("Failed to persist MFA state: {:?}", e);
+            }
+            return true;
+        }
+
+        let hash = hash_recovery_code(code);
+        if let Some(idx) = state.recovery_hashes.iter().position(|h| *h == hash) {
+            state.recovery_hashes.remove(idx);
+            if let Err(e) = self.persist(&state) {
+                tracing::error!("Failed to persist MFA state: {:?}", e);
+            }
+            tracing::info!("Recovery code used ({} left)", state.recovery_hashes.len());
+            return true;
+        }
+        false
+    }
+
+    /// 关闭 TOTP (需要有效的第二因素)
+    pub fn disable(&self, code: &str, now: i64) -> Result<()> {
+        if !self.verify(code, now) {
+            return Err(anyhow!("Invalid code"));
+        }
+        let cleared = MfaState::default();
+        self.persist(&cleared)?;
+        *self.lock_state() = cleared;
+        Ok(())
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, MfaState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn persist(&self, state: &MfaState) -> Result<()> {
+        std::fs::write(&self.path, serde_json::to_vec_pretty(state)?)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&self.path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(())
+    }
+}
+
+/// 恢复码: 去除易混淆字符的小写字母数字
+fn generate_recovery_code() -> String {
+    const CHARSET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+    let mut rng = rand::thread_rng();
+    (0..RECOVERY_CODE_LEN)
+        .map(|_| CHARSET[rng.gen_range(0..CHARSET.len())] as char)
+        .collect()
+}
+
+fn hash_recovery_code(code: &str) -> String {
+    sha256_hex(code.trim().to_lowercase().as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enrolled_store(dir: &Path, now: i64) -> (MfaStore, Vec<u8>, Vec<String>) {
+        let store = MfaStore::load(&dir.join("mfa.json")).unwrap();
+        store.begin_enrolment("admin").unwrap();
+        let secret = store.pending_secret.lock().unwrap().clone().unwrap();
+        let code = format!("{:06}", totp::code_at_step(&secret, totp::step_for(now)));
+        let codes = store.confirm_enrolment(&code, now).unwrap();
+        (store, secret, codes)
+    }
+
+    #[test]
+    fn test_enrolment_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, _, codes) = enrolled_store(dir.path(), 1_700_000_000);
+        assert!(store.is_enabled());
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+
+        let reloaded = MfaStore::load(&dir.path().join("mfa.json")).unwrap();
+        assert!(reloaded.is_enabled());
+        assert_eq!(reloaded.recovery_codes_left(), RECOVERY_CODE_COUNT);
+    }
+
+    #[test]
+    fn test_totp_replay_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = 1_700_000_000;
+        let (store, secret, _) = enrolled_store(dir.path(), now);
+        let later = now + totp::STEP_SECS * 2;
+        let code = format!("{:06}", totp::code_at_step(&secret, totp::step_for(later)));
+        assert!(store.verify(&code, later));
+        assert!(!store.verify(&code, later));
+    }
+
+    #[test]
+    fn test_recovery_code_single_use() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = 1_700_000_000;
+        let (store, _, codes) = enrolled_store(dir.path(), now);
+        assert!(store.verify(&codes[0], now));
+        assert!(!store.verify(&codes[0], now));
+        assert_eq!(store.recovery_codes_left(), RECOVERY_CODE_COUNT - 1);
+    }
+
+    #[test]
+    fn test_disable() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = 1_700_000_000;
+        let (store, _, codes) = enrolled_store(dir.path(), now);
+        assert!(store.disable("not-a-code", now).is_err());
+        store.disable(&codes[1], now).unwrap();
+        assert!(!store.is_enabled());
+    }
+}
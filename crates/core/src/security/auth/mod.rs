@@ -6,20 +6,29 @@
 //! - Argon2 密码哈希 (password)
 //! - HS256 JWT Token 签发/验证 (jwt)
 //! - 环境变量配置加载 (config)
+//! - RFC 6238 TOTP 双因素认证 (totp, mfa)
 //!
 //! ## 模块组织
 //! - `password`: Argon2 哈希生成与验证
 //! - `jwt`: JWT Claims 定义, Token 签发与验证
 //! - `config`: `AuthConfig` 环境变量加载
+//! - `totp`: TOTP 口令生成/校验与 `otpauth://` URI
+//! - `mfa`: TOTP 密钥与恢复码的持久化存储
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod config;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod jwt;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod mfa;
 pub mod password;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod totp;
 
 // Re-exports (server-only: JWT requires ring which needs C compiler)
 #[cfg(not(target_arch = "wasm32"))]
 pub use self::config::AuthConfig;
 #[cfg(not(target_arch = "wasm32"))]
 pub use self::jwt::Claims;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::mfa::MfaStore;
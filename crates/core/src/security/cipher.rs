@@ -8,11 +8,12 @@
 //! **设计**:
 //! - `RepoKey`: 32 字节对称密钥。
 //! - `EncryptedOp`: 加密后的操作载荷结构。
+//! - `EncryptedChunk`: 加密后的附件分块 (AAD 绑定哈希与分块序号，防止替换/重排)。
 
 use crate::models::{DocId, LedgerEntry};
 use aes_gcm::{
     Aes256Gcm, Key, Nonce,
-    aead::{Aead, AeadCore, KeyInit, OsRng},
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
 };
 use serde::{Deserialize, Serialize};
 
@@ -89,6 +90,59 @@ impl RepoKey {
         let entry: LedgerEntry = bincode::deserialize(&plaintext)?;
         Ok(entry)
     }
+
+    /// 加密附件分块
+    pub fn encrypt_chunk(
+        &self,
+        hash: &str,
+        index: u32,
+        total: u32,
+        bytes: &[u8],
+    ) -> anyhow::Result<EncryptedChunk> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let aad = chunk_aad(hash, index, total);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: bytes,
+                    aad: &aad,
+                },
+            )
+            .map_err(|e| anyhow::anyhow!("Encryption failed: {}", e))?;
+
+        Ok(EncryptedChunk {
+            hash: hash.to_string(),
+            index,
+            total,
+            ciphertext,
+            nonce: nonce.to_vec(),
+        })
+    }
+
+    /// 解密附件分块 (明文头部被篡改时失败)
+    pub fn decrypt_chunk(&self, chunk: &EncryptedChunk) -> anyhow::Result<Vec<u8>> {
+        let nonce = Nonce::from_slice(&chunk.nonce);
+        let aad = chunk_aad(&chunk.hash, chunk.index, chunk.total);
+        self.cipher
+            .decrypt(
+                nonce,
+                Payload {
+                    msg: &chunk.ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("Decryption failed (Bad Key or Tampered Data)"))
+    }
+}
+
+fn chunk_aad(hash: &str, index: u32, total: u32) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(hash.len() + 8);
+    aad.extend_from_slice(hash.as_bytes());
+    aad.extend_from_slice(&index.to_be_bytes());
+    aad.extend_from_slice(&total.to_be_bytes());
+    aad
 }
 
 /// 加密的操作载荷 (Envelope Body)
@@ -106,6 +160,21 @@ pub struct EncryptedOp {
     pub nonce: Vec<u8>,
 }
 
+/// 加密的附件分块
+///
+/// **结构**:
+/// - `hash` / `index` / `total`: 明文，用于路由与重组 (同时作为 AAD 参与认证)。
+/// - `ciphertext`: 密文 (分块原始字节)。
+/// - `nonce`: 用于 AES-GCM 解密的随机数。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedChunk {
+    pub hash: String,
+    pub index: u32,
+    pub total: u32,
+    pub ciphertext: Vec<u8>,
+    pub nonce: Vec<u8>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,4 +204,14 @@ mod tests {
         let dec = key.decrypt(&enc).unwrap();
         assert_eq!(dec.doc_id, entry.doc_id);
     }
+
+    #[test]
+    fn test_chunk_header_is_authenticated() {
+        let key = RepoKey::generate();
+        let mut chunk = key.encrypt_chunk("abc", 0, 2, b"payload").unwrap();
+        assert_eq!(key.decrypt_chunk(&chunk).unwrap(), b"payload");
+
+        chunk.index = 1;
+        assert!(key.decrypt_chunk(&chunk).is_err());
+    }
 }
@@ -20,6 +20,6 @@ pub mod permission;
 
 // Re-exports
 #[cfg(not(target_arch = "wasm32"))]
-pub use self::auth::{AuthConfig, Claims};
-pub use self::cipher::{EncryptedOp, RepoKey};
+pub use self::auth::{AuthConfig, Claims, MfaStore};
+pub use self::cipher::{EncryptedChunk, EncryptedOp, RepoKey};
 pub use self::keypair::IdentityKeyPair;
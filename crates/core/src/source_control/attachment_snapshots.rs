@@ -0,0 +1,59 @@
+// crates/core/src/source_control/attachment_snapshots.rs
+//! # Attachment Snapshot Index
+//!
+//! 附件的提交快照只记录 path -> 内容哈希，内容本身由 Blob Store 保留。
+
+use anyhow::Result;
+use redb::{Database, ReadableTable, TableDefinition};
+
+/// 附件快照表定义 (path -> blob hash)
+pub const ATTACHMENT_SNAPSHOTS_TABLE: TableDefinition<&str, &str> =
+    TableDefinition::new("commit_attachment_snapshots");
+
+pub fn init_table(db: &Database) -> Result<()> {
+    let write_txn = db.begin_write()?;
+    {
+        let _ = write_txn.open_table(ATTACHMENT_SNAPSHOTS_TABLE)?;
+    }
+    write_txn.commit()?;
+    Ok(())
+}
+
+pub fn save(db: &Database, path: &str, hash: &str) -> Result<()> {
+    let write_txn = db.begin_write()?;
+    {
+        let mut table = write_txn.open_table(ATTACHMENT_SNAPSHOTS_TABLE)?;
+        table.insert(path, hash)?;
+    }
+    write_txn.commit()?;
+    Ok(())
+}
+
+pub fn remove(db: &Database, path: &str) -> Result<()> {
+    let write_txn = db.begin_write()?;
+    {
+        let mut table = write_txn.open_table(ATTACHMENT_SNAPSHOTS_TABLE)?;
+        table.remove(path)?;
+    }
+    write_txn.commit()?;
+    Ok(())
+}
+
+/// 获取附件的已提交哈希
+pub fn get(db: &Database, path: &str) -> Result<Option<String>> {
+    let read_txn = db.begin_read()?;
+    let table = read_txn.open_table(ATTACHMENT_SNAPSHOTS_TABLE)?;
+    Ok(table.get(path)?.map(|v| v.value().to_string()))
+}
+
+/// 获取所有已提交附件 (path, hash)
+pub fn list(db: &Database) -> Result<Vec<(String, String)>> {
+    let read_txn = db.begin_read()?;
+    let table = read_txn.open_table(ATTACHMENT_SNAPSHOTS_TABLE)?;
+    let mut entries = Vec::new();
+    for row in table.iter()? {
+        let (path, hash) = row?;
+        entries.push((path.value().to_string(), hash.value().to_string()));
+    }
+    Ok(entries)
+}
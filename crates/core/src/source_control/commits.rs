@@ -95,3 +95,13 @@ pub fn list(db: &Database, limit: u32) -> Result<Vec<CommitInfo>> {
 
     Ok(commits)
 }
+
+/// 按 ID 获取提交
+pub fn get(db: &Database, commit_id: &str) -> Result<Option<CommitInfo>> {
+    let read_txn = db.begin_read()?;
+    let table = read_txn.open_table(COMMITS_TABLE)?;
+    match table.get(commit_id)? {
+        Some(json) => Ok(Some(serde_json::from_str(json.value())?)),
+        None => Ok(None),
+    }
+}
@@ -8,11 +8,14 @@
 //! - `staging`: 暂存区管理函数 [仅后端]
 //! - `commits`: 提交管理函数 [仅后端]
 //! - `changes`: 变更检测函数 [仅后端]
+//! - `attachment_snapshots`: 附件提交快照 (path -> hash) [仅后端]
 
 pub mod api;
 pub mod diff;
 pub mod types;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod attachment_snapshots;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod changes;
 #[cfg(not(target_arch = "wasm32"))]
@@ -36,4 +39,8 @@ pub enum SnapshotUpdate {
     },
     /// 删除快照 (表示文件被删除)
     Delete { doc_id: crate::models::DocId },
+    /// 保存附件的内容哈希
+    SaveAttachment { path: String, hash: String },
+    /// 删除附件快照
+    DeleteAttachment { path: String },
 }
@@ -4,15 +4,22 @@
 //! 本模块提供文档状态管理功能：
 //!
 //! - `reconstruct_content`: 从操作序列重建文档内容
+//! - `DocState`: 可逐条应用操作的文档状态 (供服务端缓存)
+//! - `ot`: 并发编辑的操作变换 (服务端 rebase 与客户端状态机)
+//! - `crdt`: 序列 CRDT 文档模型 (稳定字符标识，操作并集合并)
+//! - `text_ops_after`: 将含 CRDT 操作的日志转换为位置操作 (供编辑器回放)
 //! - `compute_diff`: 计算两个字符串之间的编辑操作差异
 //!
 //! 这些函数被后端（用于持久化）和前端（用于同步）共同使用。
 
 use crate::models::{LedgerEntry, Op};
+use crdt::{CrdtOp, OpId, SeqCrdt};
 use rope_utf16::Utf16IndexCache;
 use ropey::Rope;
 use utf16::{add_utf16_pos, utf16_len};
 
+pub mod crdt;
+pub mod ot;
 mod rope_utf16;
 mod utf16;
 // use anyhow::Result; // Not used currently
@@ -29,34 +36,157 @@ mod utf16;
 ///
 /// **注意**:
 /// - 所有位置都是 UTF-16 code unit 索引（非字节索引），与 JS/CodeMirror 一致。
-/// - 当前实现假设操作是线性有序的（Phase 0 简化假设）。
-/// - 在更复杂的 CRDT 场景中，此处应由 Loro 等库处理。
+/// - 位置操作按日志顺序线性应用；`Crdt` 操作按字符标识定位 (见 `state::crdt`)。
 pub fn reconstruct_content(ops: &[LedgerEntry]) -> String {
-    let mut content = Rope::new();
-    let mut total_utf16: u32 = 0;
-    let mut cache = Utf16IndexCache::new(adaptive_step(total_utf16));
-    let mut op_count = 0u32;
-
+    #[cfg(not(target_arch = "wasm32"))]
+    let _timer = crate::metrics::RECONSTRUCT_SECONDS.start_timer();
+    let mut state = DocState::new();
     for entry in ops {
-        op_count = op_count.wrapping_add(1);
-        match &entry.op {
+        state.apply(&entry.op);
+    }
+    state.content()
+}
+
+/// 可增量应用操作的文档状态 (Rope + UTF-16 索引缓存)
+///
+/// `reconstruct_content` 的逐条版本，供服务端缓存常驻文档、随追加操作推进。
+///
+/// ## Invariants
+/// - 依次 `apply` 一组操作后的 `content()` 与对同一组操作调用 `reconstruct_content` 相同
+///
+/// ## CRDT 文档
+/// 首个 `Op::Crdt` 操作将文档迁移为 CRDT 模型：此前由位置操作得到的文本被清空，
+/// 由迁移时写入的初始插入 (见 `migration_op`) 重新承载。迁移后的位置操作按匿名站点 0
+/// 转换为 CRDT 操作应用 (正常写入路径会先经 `to_crdt_ops` 转换)。
+#[derive(Clone)]
+pub struct DocState {
+    rope: Rope,
+    total_utf16: u32,
+    cache: Utf16IndexCache,
+    op_count: u32,
+    crdt: Option<SeqCrdt>,
+}
+
+impl Default for DocState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DocState {
+    pub fn new() -> Self {
+        Self {
+            rope: Rope::new(),
+            total_utf16: 0,
+            cache: Utf16IndexCache::new(adaptive_step(0)),
+            op_count: 0,
+            crdt: None,
+        }
+    }
+
+    /// 应用单个操作 (位置为 UTF-16 索引，越界时截断到文末)
+    pub fn apply(&mut self, op: &Op) {
+        if self.crdt.is_some() || matches!(op, Op::Crdt(_)) {
+            self.apply_effects(op);
+        } else {
+            self.apply_text(op);
+        }
+    }
+
+    /// 应用单个操作，返回其对文本的等价位置操作 (按顺序作用于应用前的文本)
+    pub fn apply_effects(&mut self, op: &Op) -> Vec<Op> {
+        let effects = match (op, &mut self.crdt) {
+            (Op::Crdt(op), Some(crdt)) => crdt.apply(op),
+            (Op::Crdt(op), None) => {
+                let mut crdt = SeqCrdt::new();
+                let mut effects = Vec::new();
+                if self.total_utf16 > 0 {
+                    effects.push(Op::Delete {
+                        pos: 0,
+                        len: self.total_utf16,
+                    });
+                }
+                effects.extend(crdt.apply(op));
+                self.crdt = Some(crdt);
+                effects
+            }
+            (_, Some(crdt)) => match crdt.local_op(op, 0) {
+                Some(op) => crdt.apply(&op),
+                None => Vec::new(),
+            },
+            (_, None) => vec![op.clone()],
+        };
+        for effect in &effects {
+            self.apply_text(effect);
+        }
+        effects
+    }
+
+    /// 文档是否已迁移为 CRDT 模型
+    pub fn is_crdt(&self) -> bool {
+        self.crdt.is_some()
+    }
+
+    /// CRDT 状态 (未迁移时为 `None`)
+    pub fn crdt(&self) -> Option<&SeqCrdt> {
+        self.crdt.as_ref()
+    }
+
+    /// 迁移为 CRDT 模型所需的初始插入 (已迁移或文本为空时为 `None`)
+    pub fn migration_op(&self, site: u64) -> Option<CrdtOp> {
+        (self.crdt.is_none() && self.total_utf16 > 0).then(|| CrdtOp::Insert {
+            id: OpId { counter: 1, site },
+            origin: None,
+            content: self.content().into(),
+        })
+    }
+
+    /// 将操作转换为站点 `site` 生成的 CRDT 操作序列 (必要时以迁移插入开头)
+    ///
+    /// 操作无实际效果时返回空。
+    pub fn to_crdt_ops(&self, op: &Op, site: u64) -> Vec<CrdtOp> {
+        if let Op::Crdt(op) = op {
+            return vec![op.clone()];
+        }
+        let mut ops = Vec::new();
+        let converted = match &self.crdt {
+            Some(crdt) => crdt.local_op(op, site),
+            None => {
+                let mut crdt = SeqCrdt::new();
+                if let Some(genesis) = self.migration_op(site) {
+                    crdt.apply(&genesis);
+                    ops.push(genesis);
+                }
+                crdt.local_op(op, site)
+            }
+        };
+        ops.extend(converted);
+        ops
+    }
+
+    fn apply_text(&mut self, op: &Op) {
+        self.op_count = self.op_count.wrapping_add(1);
+        let content = &mut self.rope;
+        match op {
             Op::Insert { pos, content: text } => {
-                let char_idx = cache.locate(&content, *pos);
+                let char_idx = self.cache.locate(content, *pos);
                 let utf16_delta = text.encode_utf16().count() as u32;
                 let char_delta = text.chars().count();
                 content.insert(char_idx, text);
-                total_utf16 = total_utf16.saturating_add(utf16_delta);
-                let next_step = adaptive_step(total_utf16);
-                if cache.update_after_insert(*pos, utf16_delta, char_delta)
-                    || cache.step() != next_step
+                self.total_utf16 = self.total_utf16.saturating_add(utf16_delta);
+                let next_step = adaptive_step(self.total_utf16);
+                if self
+                    .cache
+                    .update_after_insert(*pos, utf16_delta, char_delta)
+                    || self.cache.step() != next_step
                 {
-                    cache = Utf16IndexCache::build(&content, next_step);
+                    self.cache = Utf16IndexCache::build(content, next_step);
                 }
             }
             Op::Delete { pos, len } => {
                 let end_pos = pos.checked_add(*len).unwrap_or(u32::MAX);
-                let start_idx = cache.locate(&content, *pos);
-                let end_idx = cache.locate(&content, end_pos);
+                let start_idx = self.cache.locate(content, *pos);
+                let end_idx = self.cache.locate(content, end_pos);
                 if end_idx > start_idx {
                     let removed_slice = content.slice(start_idx..end_idx);
                     let mut removed_utf16 = 0u32;
@@ -66,23 +196,51 @@ pub fn reconstruct_content(ops: &[LedgerEntry]) -> String {
                         removed_chars += 1;
                     }
                     content.remove(start_idx..end_idx);
-                    total_utf16 = total_utf16.saturating_sub(removed_utf16);
-                    let next_step = adaptive_step(total_utf16);
-                    if cache.update_after_delete(*pos, removed_utf16, removed_utf16, removed_chars)
-                        || cache.step() != next_step
+                    self.total_utf16 = self.total_utf16.saturating_sub(removed_utf16);
+                    let next_step = adaptive_step(self.total_utf16);
+                    if self.cache.update_after_delete(
+                        *pos,
+                        removed_utf16,
+                        removed_utf16,
+                        removed_chars,
+                    ) || self.cache.step() != next_step
                     {
-                        cache = Utf16IndexCache::build(&content, next_step);
+                        self.cache = Utf16IndexCache::build(content, next_step);
                     }
                 }
             }
+            Op::Crdt(_) => {}
         }
 
-        if op_count.is_multiple_of(256) && !cache.validate_sample(&content) {
-            cache = Utf16IndexCache::build(&content, adaptive_step(total_utf16));
+        if self.op_count.is_multiple_of(256) && !self.cache.validate_sample(&self.rope) {
+            self.cache = Utf16IndexCache::build(&self.rope, adaptive_step(self.total_utf16));
         }
     }
 
-    content.to_string()
+    /// 当前文本
+    pub fn content(&self) -> String {
+        self.rope.to_string()
+    }
+
+    /// 文本的 UTF-8 字节数 (CRDT 文档另计墓碑与标识)
+    pub fn len_bytes(&self) -> usize {
+        self.rope.len_bytes() + self.crdt.as_ref().map_or(0, SeqCrdt::len_bytes)
+    }
+}
+
+/// 依序重放日志，返回序号大于 `after` 的操作对文本的等价位置操作
+///
+/// 用于向只理解位置操作的编辑器回放含 CRDT 操作的日志；不含 CRDT 操作时即原操作。
+pub fn text_ops_after(entries: &[(u64, LedgerEntry)], after: u64) -> Vec<(u64, Op)> {
+    let mut state = DocState::new();
+    let mut ops = Vec::new();
+    for (seq, entry) in entries {
+        let effects = state.apply_effects(&entry.op);
+        if *seq > after {
+            ops.extend(effects.into_iter().map(|op| (*seq, op)));
+        }
+    }
+    ops
 }
 
 fn adaptive_step(total_utf16: u32) -> u32 {
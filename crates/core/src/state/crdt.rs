@@ -0,0 +1,482 @@
+// crates/core/src/state/crdt.rs
+//! # 序列 CRDT (Sequence CRDT)
+//!
+//! RGA 风格的文本序列：每个字符拥有稳定标识 `OpId`，插入以左邻字符为锚点，
+//! 删除只留下墓碑。同一组操作以任意顺序、重复应用都得到相同文本，
+//! 因此不同节点的并发编辑可以直接取操作并集合并，无需三方合并。
+//!
+//! - `CrdtOp`: 写入 `LEDGER_OPS` 的操作 (由 `Op::Crdt` 承载，随 `EncryptedOp` 同步)
+//! - `SeqCrdt::apply`: 应用操作并返回等价的位置操作 (UTF-16)，供 Rope 与编辑器使用
+//! - `SeqCrdt::local_op`: 将基于位置的本地编辑转换为 `CrdtOp`
+//!
+//! ## 排序规则
+//! 同一锚点后的并发插入按 `OpId` 降序排列 (Lamport 计数大者在前，相同时比较站点)。
+//! 连续输入的字符合并为一个条目，锚点落在条目中间时拆分。
+//!
+//! ## 因果缺失
+//! 锚点或删除目标尚未出现的操作进入等待队列，依赖到达后自动应用。
+//!
+//! ## 复杂度
+//! 条目以数组存储，定位为 O(条目数)；连续输入合并后条目数远小于字符数。
+//!
+//! ## Invariants
+//! - 条目顺序只由已应用操作的集合决定，与应用顺序无关
+//! - 每个 `OpId` 至多对应一个字符，重复的插入被忽略
+
+use crate::models::Op;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+/// 字符标识 (Lamport 计数, 站点)
+///
+/// 站点为生成操作的本地库的随机标识 (见 `RepoManager::site_id`)，
+/// 计数大于生成时文档内已知的全部计数。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct OpId {
+    pub counter: u64,
+    pub site: u64,
+}
+
+impl OpId {
+    fn add(self, n: u64) -> Self {
+        Self {
+            counter: self.counter + n,
+            site: self.site,
+        }
+    }
+}
+
+/// 同一站点的连续标识区间 `[id, id + len)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdSpan {
+    pub id: OpId,
+    pub len: u32,
+}
+
+/// CRDT 操作
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CrdtOp {
+    /// 在 `origin` (左邻字符，`None` 为文档开头) 之后插入；第 i 个字符的标识为 `id + i`
+    Insert {
+        id: OpId,
+        origin: Option<OpId>,
+        content: SmolStr,
+    },
+    /// 删除标识区间内的字符
+    Delete { spans: Vec<IdSpan> },
+}
+
+#[derive(Debug, Clone)]
+struct Item {
+    /// 首字符标识 (其余字符依次递增)
+    id: OpId,
+    text: String,
+    chars: u32,
+    utf16: u32,
+    deleted: bool,
+}
+
+impl Item {
+    fn new(id: OpId, text: &str) -> Self {
+        Self {
+            id,
+            text: text.to_string(),
+            chars: text.chars().count() as u32,
+            utf16: text.encode_utf16().count() as u32,
+            deleted: false,
+        }
+    }
+
+    fn contains(&self, id: OpId) -> bool {
+        id.site == self.id.site
+            && id.counter >= self.id.counter
+            && id.counter - self.id.counter < u64::from(self.chars)
+    }
+
+    fn last_id(&self) -> OpId {
+        self.id.add(u64::from(self.chars) - 1)
+    }
+
+    fn visible(&self) -> u32 {
+        if self.deleted { 0 } else { self.utf16 }
+    }
+
+    /// 在第 `at` 个字符处拆分 (`0 < at < chars`)，自身保留左半部分
+    fn split_off(&mut self, at: u32) -> Item {
+        let byte = self
+            .text
+            .char_indices()
+            .nth(at as usize)
+            .map_or(self.text.len(), |(i, _)| i);
+        let mut right = Item::new(self.id.add(u64::from(at)), &self.text[byte..]);
+        right.deleted = self.deleted;
+        self.text.truncate(byte);
+        self.chars = at;
+        self.utf16 -= right.utf16;
+        right
+    }
+}
+
+/// 序列 CRDT 文档状态
+#[derive(Debug, Clone, Default)]
+pub struct SeqCrdt {
+    items: Vec<Item>,
+    /// 已知的最大 Lamport 计数
+    max_counter: u64,
+    /// 依赖尚未到达的操作
+    pending: Vec<CrdtOp>,
+}
+
+impl SeqCrdt {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 当前可见文本
+    pub fn text(&self) -> String {
+        self.items
+            .iter()
+            .filter(|item| !item.deleted)
+            .map(|item| item.text.as_str())
+            .collect()
+    }
+
+    /// 估算内存占用 (含墓碑)
+    pub fn len_bytes(&self) -> usize {
+        self.items
+            .iter()
+            .map(|item| item.text.len() + std::mem::size_of::<Item>())
+            .sum()
+    }
+
+    /// 标识为 `id` 的字符是否已存在 (含已删除)
+    pub fn contains(&self, id: OpId) -> bool {
+        self.find(id).is_some()
+    }
+
+    /// 操作是否已完全生效 (重复接收的操作)
+    pub fn is_redundant(&self, op: &CrdtOp) -> bool {
+        match op {
+            CrdtOp::Insert { id, .. } => {
+                self.contains(*id)
+                    || self
+                        .pending
+                        .iter()
+                        .any(|p| matches!(p, CrdtOp::Insert { id: q, .. } if q == id))
+            }
+            CrdtOp::Delete { spans } => spans.iter().all(|s| self.span_state(s) == Some(true)),
+        }
+    }
+
+    /// 应用操作，返回按顺序作用于当前文本的等价位置操作
+    ///
+    /// 依赖缺失的操作进入等待队列 (返回空)，依赖到达时随之应用。
+    pub fn apply(&mut self, op: &CrdtOp) -> Vec<Op> {
+        let mut effects = Vec::new();
+        if !self.integrate(op, &mut effects) {
+            if !self.is_redundant(op) {
+                self.pending.push(op.clone());
+            }
+            return effects;
+        }
+        while !self.pending.is_empty() {
+            let before = self.pending.len();
+            for op in std::mem::take(&mut self.pending) {
+                if !self.integrate(&op, &mut effects) {
+                    self.pending.push(op);
+                }
+            }
+            if self.pending.len() == before {
+                break;
+            }
+        }
+        effects
+    }
+
+    /// 将位置操作转换为站点 `site` 生成的 CRDT 操作 (无实际效果时返回 `None`)
+    pub fn local_op(&self, op: &Op, site: u64) -> Option<CrdtOp> {
+        match op {
+            Op::Crdt(op) => Some(op.clone()),
+            Op::Insert { pos, content } => (!content.is_empty()).then(|| CrdtOp::Insert {
+                id: OpId {
+                    counter: self.max_counter + 1,
+                    site,
+                },
+                origin: self.char_before(*pos),
+                content: content.clone(),
+            }),
+            Op::Delete { pos, len } => {
+                let spans = self.visible_spans(*pos, pos.saturating_add(*len));
+                (!spans.is_empty()).then_some(CrdtOp::Delete { spans })
+            }
+        }
+    }
+
+    fn find(&self, id: OpId) -> Option<(usize, u32)> {
+        let index = self.items.iter().position(|item| item.contains(id))?;
+        Some((index, (id.counter - self.items[index].id.counter) as u32))
+    }
+
+    /// 条目 `index` 之前的可见 UTF-16 长度
+    fn position(&self, index: usize) -> u32 {
+        self.items[..index].iter().map(Item::visible).sum()
+    }
+
+    /// 区间是否全部存在；存在时返回是否已全部删除
+    fn span_state(&self, span: &IdSpan) -> Option<bool> {
+        let end = span.id.counter + u64::from(span.len);
+        let mut cur = span.id;
+        let mut deleted = true;
+        while cur.counter < end {
+            let (index, offset) = self.find(cur)?;
+            let item = &self.items[index];
+            deleted &= item.deleted;
+            cur = cur.add(u64::from(item.chars - offset));
+        }
+        Some(deleted)
+    }
+
+    /// 返回 false 表示依赖缺失
+    fn integrate(&mut self, op: &CrdtOp, effects: &mut Vec<Op>) -> bool {
+        match op {
+            CrdtOp::Insert {
+                id,
+                origin,
+                content,
+            } => self.integrate_insert(*id, *origin, content, effects),
+            CrdtOp::Delete { spans } => self.integrate_delete(spans, effects),
+        }
+    }
+
+    fn integrate_insert(
+        &mut self,
+        id: OpId,
+        origin: Option<OpId>,
+        content: &str,
+        effects: &mut Vec<Op>,
+    ) -> bool {
+        if content.is_empty() || self.contains(id) {
+            return true;
+        }
+        let mut index = match origin {
+            None => 0,
+            Some(origin) => {
+                let Some((i, offset)) = self.find(origin) else {
+                    return false;
+                };
+                if offset + 1 < self.items[i].chars {
+                    let right = self.items[i].split_off(offset + 1);
+                    self.items.insert(i + 1, right);
+                }
+                i + 1
+            }
+        };
+        // 跳过锚点之后标识更大的条目 (并发插入及其后代)
+        while index < self.items.len() && self.items[index].id > id {
+            index += 1;
+        }
+
+        let item = Item::new(id, content);
+        self.max_counter = self.max_counter.max(id.counter + u64::from(item.chars) - 1);
+        effects.push(Op::Insert {
+            pos: self.position(index),
+            content: content.into(),
+        });
+        // 连续输入: 紧接同一站点上一个字符时并入该条目
+        if let Some(prev) = index.checked_sub(1).map(|i| &mut self.items[i])
+            && !prev.deleted
+            && Some(prev.last_id()) == origin
+            && prev.last_id().add(1) == id
+        {
+            prev.text.push_str(content);
+            prev.chars += item.chars;
+            prev.utf16 += item.utf16;
+        } else {
+            self.items.insert(index, item);
+        }
+        true
+    }
+
+    fn integrate_delete(&mut self, spans: &[IdSpan], effects: &mut Vec<Op>) -> bool {
+        if spans.iter().any(|s| self.span_state(s).is_none()) {
+            return false;
+        }
+        for span in spans {
+            let end = span.id.counter + u64::from(span.len);
+            let mut cur = span.id;
+            while cur.counter < end {
+                let Some((mut i, offset)) = self.find(cur) else {
+                    break;
+                };
+                if offset > 0 {
+                    let right = self.items[i].split_off(offset);
+                    self.items.insert(i + 1, right);
+                    i += 1;
+                }
+                if cur.counter + u64::from(self.items[i].chars) > end {
+                    let right = self.items[i].split_off((end - cur.counter) as u32);
+                    self.items.insert(i + 1, right);
+                }
+                cur = cur.add(u64::from(self.items[i].chars));
+                if !self.items[i].deleted {
+                    effects.push(Op::Delete {
+                        pos: self.position(i),
+                        len: self.items[i].utf16,
+                    });
+                    self.items[i].deleted = true;
+                }
+            }
+        }
+        true
+    }
+
+    /// 可见文本中结束位置不超过 UTF-16 位置 `pos` 的最后一个字符
+    fn char_before(&self, pos: u32) -> Option<OpId> {
+        let mut acc = 0u32;
+        let mut last = None;
+        for item in self.items.iter().filter(|item| !item.deleted) {
+            if acc + item.utf16 <= pos {
+                acc += item.utf16;
+                last = Some(item.last_id());
+                continue;
+            }
+            for (k, ch) in item.text.chars().enumerate() {
+                acc += ch.len_utf16() as u32;
+                if acc > pos {
+                    break;
+                }
+                last = Some(item.id.add(k as u64));
+            }
+            break;
+        }
+        last
+    }
+
+    /// 起始位置落在 `[start, end)` 内的可见字符，按文档顺序合并为区间
+    fn visible_spans(&self, start: u32, end: u32) -> Vec<IdSpan> {
+        let mut spans: Vec<IdSpan> = Vec::new();
+        let mut acc = 0u32;
+        for item in self.items.iter().filter(|item| !item.deleted) {
+            if acc >= end {
+                break;
+            }
+            if acc + item.utf16 <= start {
+                acc += item.utf16;
+                continue;
+            }
+            for (k, ch) in item.text.chars().enumerate() {
+                if acc >= end {
+                    break;
+                }
+                if acc >= start {
+                    let id = item.id.add(k as u64);
+                    match spans.last_mut() {
+                        Some(span) if span.id.add(u64::from(span.len)) == id => span.len += 1,
+                        _ => spans.push(IdSpan { id, len: 1 }),
+                    }
+                }
+                acc += ch.len_utf16() as u32;
+            }
+        }
+        spans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::DocState;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+
+    /// 本地编辑: 转换、应用，并把位置效果作用到编辑器文本 `view`
+    fn edit(doc: &mut SeqCrdt, view: &mut DocState, op: Op, site: u64) -> Option<CrdtOp> {
+        let op = doc.local_op(&op, site)?;
+        for effect in doc.apply(&op) {
+            view.apply(&effect);
+        }
+        Some(op)
+    }
+
+    fn insert(pos: u32, content: &str) -> Op {
+        Op::Insert {
+            pos,
+            content: content.into(),
+        }
+    }
+
+    #[test]
+    fn test_concurrent_inserts_converge() {
+        let (mut a, mut b) = (SeqCrdt::new(), SeqCrdt::new());
+        let (mut va, mut vb) = (DocState::new(), DocState::new());
+        let base = edit(&mut a, &mut va, insert(0, "ac"), 1).unwrap();
+        for effect in b.apply(&base) {
+            vb.apply(&effect);
+        }
+
+        // 同一位置的并发插入与跨越该位置的删除
+        let x = edit(&mut a, &mut va, insert(1, "X"), 1).unwrap();
+        let y = edit(&mut b, &mut vb, insert(1, "é中"), 2).unwrap();
+        let d = edit(&mut b, &mut vb, Op::Delete { pos: 0, len: 4 }, 2).unwrap();
+        assert_eq!(vb.content(), b.text());
+        for op in [&y, &d] {
+            a.apply(op);
+        }
+        b.apply(&x);
+        assert_eq!(a.text(), b.text());
+        assert_eq!(a.text(), "X");
+        // 重复应用无效果
+        assert!(a.apply(&x).is_empty());
+        assert!(a.is_redundant(&d));
+    }
+
+    /// 多站点随机编辑，操作以任意顺序 (含依赖缺失与重复) 投递后收敛
+    #[test]
+    fn test_random_delivery_converges() {
+        for seed in 0..40 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut sites: Vec<(SeqCrdt, DocState)> = (0..3).map(|_| Default::default()).collect();
+            let mut log: Vec<CrdtOp> = Vec::new();
+            for _ in 0..150 {
+                let s = rng.gen_range(0..sites.len());
+                let (doc, view) = &mut sites[s];
+                // 偶尔先接收一条已有操作，制造共享上下文
+                if rng.gen_bool(0.3) && !log.is_empty() {
+                    for effect in doc.apply(&log[rng.gen_range(0..log.len())]) {
+                        view.apply(&effect);
+                    }
+                }
+                let len = view.content().encode_utf16().count() as u32;
+                let op = if len > 0 && rng.gen_bool(0.35) {
+                    let pos = rng.gen_range(0..len);
+                    Op::Delete {
+                        pos,
+                        len: rng.gen_range(1..=(len - pos).min(5)),
+                    }
+                } else {
+                    let words = ["a", "bc", "中", "é", "\n"];
+                    insert(rng.gen_range(0..=len), words[rng.gen_range(0..words.len())])
+                };
+                if let Some(op) = edit(doc, view, op, s as u64 + 1) {
+                    assert_eq!(view.content(), doc.text(), "seed {seed}: effects diverge");
+                    log.push(op);
+                }
+            }
+
+            let mut expected = None;
+            for _ in 0..3 {
+                let mut ops = log.clone();
+                ops.shuffle(&mut rng);
+                ops.extend(log.iter().take(10).cloned());
+                let mut doc = SeqCrdt::new();
+                for op in &ops {
+                    doc.apply(op);
+                }
+                assert!(doc.pending.is_empty(), "seed {seed}");
+                let text = doc.text();
+                assert_eq!(expected.get_or_insert(text.clone()), &text, "seed {seed}");
+            }
+        }
+    }
+}
@@ -0,0 +1,361 @@
+// crates/core/src/state/ot.rs
+//! # 操作变换 (Operational Transformation)
+//!
+//! 浏览器编辑以 `base_seq` (客户端已应用的最后一个服务端操作序号) 为基准发送，
+//! 服务端将其变换到该序号之后的并发操作之上再写入账本。
+//!
+//! - `transform` / `transform_ops`: 单个/序列操作的包含变换 (位置均为 UTF-16 索引)
+//! - `rebase`: 服务端把传入操作变换到并发操作之后
+//! - `OtClient`: 客户端状态机 (同一时刻只有一个在途操作，其余缓冲)
+//!
+//! ## 平局规则
+//! 同位置插入时，已由服务端定序的操作在前，待定序的操作在后；
+//! 服务端 (`rebase`) 与客户端 (`OtClient::remote_op`) 使用同一规则，保证收敛。
+//!
+//! ## Invariants
+//! - TP1: 对任意 `a`、`b`，先 `b` 后 `transform(a, b)` 与先 `a` 后 `transform(b, a)`
+//!   (平局优先级相反) 得到相同文本
+
+use crate::models::Op;
+use std::collections::VecDeque;
+
+fn utf16_len(text: &str) -> u32 {
+    text.encode_utf16().count() as u32
+}
+
+/// 将 `op` 变换为在 `against` 之后应用的等价操作
+///
+/// 删除区间被插入切开时拆分为两段 (按顺序应用)；删除区间被完全删除时返回空。
+/// `wins_tie`: 同位置插入时 `op` 是否排在 `against` 之前。
+/// CRDT 操作按字符标识定位，无需变换 (调用方应先以 `text_ops_after` 转换为位置操作)。
+pub fn transform(op: &Op, against: &Op, wins_tie: bool) -> Vec<Op> {
+    match (op, against) {
+        (Op::Insert { pos, content }, Op::Insert { pos: q, content: t }) => {
+            let pos = if *pos < *q || (*pos == *q && wins_tie) {
+                *pos
+            } else {
+                pos.saturating_add(utf16_len(t))
+            };
+            vec![Op::Insert {
+                pos,
+                content: content.clone(),
+            }]
+        }
+        (Op::Insert { pos, content }, Op::Delete { pos: q, len }) => {
+            let end = q.saturating_add(*len);
+            let pos = if *pos <= *q {
+                *pos
+            } else if *pos >= end {
+                pos - len
+            } else {
+                *q
+            };
+            vec![Op::Insert {
+                pos,
+                content: content.clone(),
+            }]
+        }
+        (Op::Delete { pos, len }, Op::Insert { pos: q, content: t }) => {
+            let end = pos.saturating_add(*len);
+            let shift = utf16_len(t);
+            if *q <= *pos {
+                vec![Op::Delete {
+                    pos: pos.saturating_add(shift),
+                    len: *len,
+                }]
+            } else if *q >= end {
+                vec![op.clone()]
+            } else {
+                vec![
+                    Op::Delete {
+                        pos: *pos,
+                        len: q - pos,
+                    },
+                    Op::Delete {
+                        pos: pos.saturating_add(shift),
+                        len: end - q,
+                    },
+                ]
+            }
+        }
+        (Op::Delete { pos, len }, Op::Delete { pos: q, len: m }) => {
+            let (end, q_end) = (pos.saturating_add(*len), q.saturating_add(*m));
+            let overlap = end.min(q_end).saturating_sub(*pos.max(q));
+            let len = len - overlap;
+            if len == 0 {
+                return Vec::new();
+            }
+            let pos = if *pos <= *q {
+                *pos
+            } else if *pos >= q_end {
+                pos - m
+            } else {
+                *q
+            };
+            vec![Op::Delete { pos, len }]
+        }
+        (Op::Crdt(_), _) | (_, Op::Crdt(_)) => vec![op.clone()],
+    }
+}
+
+/// 序列间的变换: 返回 (`a` 在 `b` 之后的形式, `b` 在 `a` 之后的形式)
+///
+/// `a_wins_tie` 为 `a` 中插入在平局时的优先级 (`b` 取相反值)。
+pub fn transform_ops(a: &[Op], b: &[Op], a_wins_tie: bool) -> (Vec<Op>, Vec<Op>) {
+    match (a, b) {
+        ([], _) => (Vec::new(), b.to_vec()),
+        (_, []) => (a.to_vec(), Vec::new()),
+        ([x], [y]) => (transform(x, y, a_wins_tie), transform(y, x, !a_wins_tie)),
+        ([x, rest @ ..], _) if !rest.is_empty() => {
+            let (x, b) = transform_ops(std::slice::from_ref(x), b, a_wins_tie);
+            let (rest, b) = transform_ops(rest, &b, a_wins_tie);
+            ([x, rest].concat(), b)
+        }
+        (_, [y, rest @ ..]) => {
+            let (a, y) = transform_ops(a, std::slice::from_ref(y), a_wins_tie);
+            let (a, rest) = transform_ops(&a, rest, a_wins_tie);
+            (a, [y, rest].concat())
+        }
+    }
+}
+
+/// 服务端: 将基于旧版本的 `op` 变换到 `concurrent` (按序号升序) 之后
+pub fn rebase(op: &Op, concurrent: &[Op]) -> Vec<Op> {
+    transform_ops(std::slice::from_ref(op), concurrent, false).0
+}
+
+/// 客户端 OT 状态机
+///
+/// 本地操作先进入缓冲；无在途操作时取出一个发送，收到自己的 `Ack` 后再发下一个。
+/// 远端操作需与在途、缓冲操作互相变换后才能应用到编辑器。
+///
+/// ## Invariants
+/// - 编辑器文本 = 服务端 `version` 处文本 + `inflight` + `buffer`
+/// - 在途操作被远端操作完全抵消 (变换为空) 时仍须等待 `Ack`
+#[derive(Debug, Clone, Default)]
+pub struct OtClient {
+    version: u64,
+    /// 已发送未确认的操作 (变换后可能拆分或为空)
+    inflight: Option<Vec<Op>>,
+    buffer: VecDeque<Op>,
+}
+
+impl OtClient {
+    pub fn new(version: u64) -> Self {
+        Self {
+            version,
+            ..Default::default()
+        }
+    }
+
+    /// 已应用的最后一个服务端操作序号
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// 是否有尚未确认的本地操作
+    pub fn has_pending(&self) -> bool {
+        self.inflight.is_some() || !self.buffer.is_empty()
+    }
+
+    /// 记录已应用到编辑器的本地操作，返回此时应发送的 `(base_seq, op)`
+    pub fn local_edit(&mut self, op: Op) -> Option<(u64, Op)> {
+        self.buffer.push_back(op);
+        self.next_to_send()
+    }
+
+    /// 收到其他来源的操作，返回需应用到编辑器的变换结果
+    ///
+    /// 序号不大于 `version` 的重复消息返回空。
+    pub fn remote_op(&mut self, op: &Op, seq: u64) -> Vec<Op> {
+        if seq <= self.version {
+            return Vec::new();
+        }
+        self.version = seq;
+        let mut op = vec![op.clone()];
+        if let Some(inflight) = &mut self.inflight {
+            let (rebased, transformed) = transform_ops(inflight, &op, false);
+            *inflight = rebased;
+            op = transformed;
+        }
+        let buffer: Vec<Op> = self.buffer.drain(..).collect();
+        let (buffer, op) = transform_ops(&buffer, &op, false);
+        self.buffer = buffer.into();
+        op
+    }
+
+    /// 在途操作已被服务端以序号 `seq` 定序，返回下一个应发送的操作
+    pub fn ack(&mut self, seq: u64) -> Option<(u64, Op)> {
+        self.inflight = None;
+        self.version = self.version.max(seq);
+        self.next_to_send()
+    }
+
+    fn next_to_send(&mut self) -> Option<(u64, Op)> {
+        if self.inflight.is_some() {
+            return None;
+        }
+        let op = self.buffer.pop_front()?;
+        self.inflight = Some(vec![op.clone()]);
+        Some((self.version, op))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::DocState;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn apply(text: &str, ops: &[Op]) -> String {
+        let mut state = DocState::new();
+        state.apply(&Op::Insert {
+            pos: 0,
+            content: text.into(),
+        });
+        for op in ops {
+            state.apply(op);
+        }
+        state.content()
+    }
+
+    fn random_op(rng: &mut StdRng, text: &str) -> Op {
+        let len = utf16_len(text);
+        if len > 0 && rng.gen_bool(0.4) {
+            let pos = rng.gen_range(0..len);
+            let del = rng.gen_range(1..=(len - pos).min(4));
+            Op::Delete { pos, len: del }
+        } else {
+            // 不含代理对，随机删除不会切开字符
+            let words = ["a", "bc", "def", "é", "\n"];
+            Op::Insert {
+                pos: rng.gen_range(0..=len),
+                content: words[rng.gen_range(0..words.len())].into(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_transform_tp1() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..2000 {
+            let base = "0123456789abcdef";
+            let a = random_op(&mut rng, base);
+            let b = random_op(&mut rng, base);
+            let (a2, b2) = transform_ops(std::slice::from_ref(&a), std::slice::from_ref(&b), true);
+            assert_eq!(
+                apply(base, &[vec![b.clone()], a2].concat()),
+                apply(base, &[vec![a.clone()], b2].concat()),
+                "a={a:?} b={b:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_delete_split_by_insert() {
+        let del = Op::Delete { pos: 2, len: 4 };
+        let ins = Op::Insert {
+            pos: 4,
+            content: "XY".into(),
+        };
+        let ops = transform(&del, &ins, false);
+        assert_eq!(ops.len(), 2);
+        assert_eq!(apply("abcdefgh", &[vec![ins], ops].concat()), "abXYgh");
+    }
+
+    enum ToClient {
+        NewOp { seq: u64, op: Op, origin: usize },
+        Ack { seq: u64, client: usize },
+    }
+
+    /// 多客户端随机并发编辑、随机投递后与服务端收敛
+    #[test]
+    fn test_random_multi_client_convergence() {
+        const EDITS: usize = 120;
+        for seed in 0..64 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let initial = "hello world";
+            let clients_n = 3;
+            let mut server_text = initial.to_string();
+            let mut server_log: Vec<(u64, Op)> = Vec::new();
+            let mut seq = 10u64;
+            let mut clients: Vec<(OtClient, String)> = (0..clients_n)
+                .map(|_| (OtClient::new(seq), initial.to_string()))
+                .collect();
+            let mut up: Vec<VecDeque<(u64, Op)>> =
+                (0..clients_n).map(|_| VecDeque::new()).collect();
+            let mut down: Vec<VecDeque<ToClient>> =
+                (0..clients_n).map(|_| VecDeque::new()).collect();
+
+            let mut edits = 0;
+            loop {
+                let idle = up.iter().all(|q| q.is_empty()) && down.iter().all(|q| q.is_empty());
+                if edits >= EDITS && idle {
+                    break;
+                }
+                let c = rng.gen_range(0..clients_n);
+                match rng.gen_range(0..3) {
+                    0 if edits < EDITS => {
+                        edits += 1;
+                        let (client, text) = &mut clients[c];
+                        let op = random_op(&mut rng, text);
+                        *text = apply(text, std::slice::from_ref(&op));
+                        if let Some((base, op)) = client.local_edit(op) {
+                            up[c].push_back((base, op));
+                        }
+                    }
+                    1 => {
+                        let Some((base, op)) = up[c].pop_front() else {
+                            continue;
+                        };
+                        let concurrent: Vec<Op> = server_log
+                            .iter()
+                            .filter(|(s, _)| *s > base)
+                            .map(|(_, op)| op.clone())
+                            .collect();
+                        for op in rebase(&op, &concurrent) {
+                            seq += 1;
+                            server_text = apply(&server_text, std::slice::from_ref(&op));
+                            server_log.push((seq, op.clone()));
+                            for q in &mut down {
+                                q.push_back(ToClient::NewOp {
+                                    seq,
+                                    op: op.clone(),
+                                    origin: c,
+                                });
+                            }
+                        }
+                        for q in &mut down {
+                            q.push_back(ToClient::Ack { seq, client: c });
+                        }
+                    }
+                    _ => {
+                        let Some(msg) = down[c].pop_front() else {
+                            continue;
+                        };
+                        let (client, text) = &mut clients[c];
+                        match msg {
+                            ToClient::NewOp { origin, .. } if origin == c => {}
+                            ToClient::NewOp { seq, op, .. } => {
+                                let ops = client.remote_op(&op, seq);
+                                *text = apply(text, &ops);
+                            }
+                            ToClient::Ack { client: target, .. } if target != c => {}
+                            ToClient::Ack { seq, .. } => {
+                                if let Some((base, op)) = client.ack(seq) {
+                                    up[c].push_back((base, op));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            for (client, text) in &clients {
+                assert!(!client.has_pending());
+                assert_eq!(text, &server_text, "seed {seed}");
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use ropey::Rope;
 
+#[derive(Clone)]
 pub(super) struct Utf16IndexCache {
     checkpoints: Vec<(u32, usize)>,
     step: u32,
@@ -90,13 +91,12 @@ impl Utf16IndexCache {
         }
 
         let end = pos.saturating_add(len);
+        // 删除区间内部的检查点失效；恰在 `pos` 处的检查点不受影响
+        self.checkpoints.retain(|(u, _)| *u <= pos || *u >= end);
         for (u, c) in &mut self.checkpoints {
-            if *u >= end {
+            if *u >= end && *u > pos {
                 *u = u.saturating_sub(utf16_delta);
                 *c = c.saturating_sub(char_delta);
-            } else if *u >= pos {
-                *u = pos;
-                *c = c.saturating_sub(char_delta.min(*c));
             }
         }
         false
@@ -53,3 +53,41 @@ fn compute_diff_uses_utf16_positions() {
         _ => panic!("expected insert op"),
     }
 }
+
+/// 删除跨越索引检查点时缓存须保持正确 (与逐字符模型比对)
+#[test]
+fn doc_state_matches_naive_model() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut state = crate::state::DocState::new();
+    // 长文本先建立检查点，之后的小操作走增量更新路径
+    let mut model: Vec<char> = "é中\n".repeat(700).chars().collect();
+    state.apply(&Op::Insert {
+        pos: 0,
+        content: model.iter().collect::<String>().into(),
+    });
+    for _ in 0..3000 {
+        if !model.is_empty() && rng.gen_bool(0.5) {
+            let pos = rng.gen_range(0..model.len());
+            let len = rng.gen_range(1..=(model.len() - pos).min(10));
+            model.drain(pos..pos + len);
+            state.apply(&Op::Delete {
+                pos: pos as u32,
+                len: len as u32,
+            });
+        } else {
+            let pos = rng.gen_range(0..=model.len());
+            let text: String = (0..rng.gen_range(1..6))
+                .map(|_| ['a', 'é', '中', '\n'][rng.gen_range(0..4)])
+                .collect();
+            model.splice(pos..pos, text.chars());
+            state.apply(&Op::Insert {
+                pos: pos as u32,
+                content: text.into(),
+            });
+        }
+        assert_eq!(state.content(), model.iter().collect::<String>());
+    }
+}
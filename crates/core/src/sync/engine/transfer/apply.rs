@@ -1,4 +1,5 @@
 use super::SyncEngine;
+use super::record_sync_bytes;
 use crate::sync::protocol::SyncResponse;
 use anyhow::Result;
 use std::collections::HashSet;
@@ -10,6 +11,7 @@ impl SyncEngine {
             .repo_key
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("RepoKey not configured"))?;
+        record_sync_bytes(&response.peer_id, "received", &response.ops);
 
         let mut max_seq = 0u64;
         let mut reset_docs: HashSet<crate::models::DocId> = HashSet::new();
@@ -42,6 +44,7 @@ impl SyncEngine {
             .repo_key
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("RepoKey not configured, cannot decrypt ops"))?;
+        record_sync_bytes(&response.peer_id, "received", &response.ops);
 
         let mut max_seq = 0u64;
         for enc_op in response.ops {
@@ -0,0 +1,144 @@
+use super::SyncEngine;
+use crate::ledger::blob::{AttachmentRef, chunk_count};
+use crate::models::{PeerId, RepoId};
+use crate::security::EncryptedChunk;
+use anyhow::{Result, anyhow};
+use std::collections::BTreeSet;
+
+/// 附件清单条目 (path, hash, size)
+pub type ManifestEntry = (String, String, u64);
+
+impl SyncEngine {
+    /// 本地附件清单 (握手后发送给远端)
+    pub fn attachment_manifest(&self) -> Result<Vec<ManifestEntry>> {
+        Ok(self
+            .repo
+            .list_attachments()?
+            .into_iter()
+            .map(|(path, a)| (path, a.hash, a.size))
+            .collect())
+    }
+
+    /// 记录远端附件清单到影子库，返回本地缺失的 Blob 哈希 (去重)
+    pub fn apply_attachment_manifest(
+        &self,
+        peer_id: &PeerId,
+        repo_id: &RepoId,
+        entries: Vec<ManifestEntry>,
+    ) -> Result<Vec<String>> {
+        let refs: Vec<(String, AttachmentRef)> = entries
+            .into_iter()
+            .map(|(path, hash, size)| (path, AttachmentRef { hash, size }))
+            .collect();
+        self.repo
+            .apply_remote_attachments(peer_id, repo_id, &refs)?;
+
+        let mut missing = BTreeSet::new();
+        for (_, a) in refs {
+            if !self.repo.has_blob(&a.hash)? {
+                missing.insert(a.hash);
+            }
+        }
+        Ok(missing.into_iter().collect())
+    }
+
+    /// 将本地 Blob 切分并加密为分块 (用于响应 `SyncBlobRequest`)
+    ///
+    /// **安全**: 使用 `RepoKey` 加密，明文头部作为 AAD 参与认证。
+    pub fn get_blob_chunks_for_sync(&self, hash: &str) -> Result<Vec<EncryptedChunk>> {
+        let repo_key = self
+            .repo_key
+            .as_ref()
+            .ok_or_else(|| anyhow!("RepoKey not configured, cannot encrypt chunks"))?;
+        let meta = self
+            .repo
+            .blob_meta(hash)?
+            .ok_or_else(|| anyhow!("Blob not found: {}", hash))?;
+        debug_assert_eq!(meta.chunk_count, chunk_count(meta.size));
+
+        let mut chunks = Vec::with_capacity(meta.chunk_count as usize);
+        for index in 0..meta.chunk_count {
+            let bytes = self
+                .repo
+                .get_blob_chunk(hash, index)?
+                .ok_or_else(|| anyhow!("Blob {} is missing chunk {}", hash, index))?;
+            chunks.push(repo_key.encrypt_chunk(hash, index, meta.chunk_count, &bytes)?);
+        }
+        Ok(chunks)
+    }
+
+    /// 解密并写入远端分块，返回 Blob 是否已完整
+    pub fn apply_blob_chunk(&self, peer_id: &PeerId, chunk: &EncryptedChunk) -> Result<bool> {
+        let repo_key = self
+            .repo_key
+            .as_ref()
+            .ok_or_else(|| anyhow!("RepoKey not configured, cannot decrypt chunks"))?;
+        crate::metrics::SYNC_BYTES.observe(
+            &[peer_id.as_str(), "received"],
+            (chunk.ciphertext.len() + chunk.nonce.len()) as f64,
+        );
+        let bytes = repo_key.decrypt_chunk(chunk)?;
+        self.repo
+            .put_blob_chunk(&chunk.hash, chunk.index, chunk.total, &bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SyncMode;
+    use crate::ledger::RepoManager;
+    use crate::ledger::listing::RepoListing;
+    use crate::models::{NodeKind, RepoType};
+    use crate::security::RepoKey;
+    use std::sync::Arc;
+
+    fn engine(dir: &std::path::Path, peer: &str, key: &RepoKey) -> SyncEngine {
+        let repo = Arc::new(RepoManager::init(dir, 10, None, None).unwrap());
+        SyncEngine::new(PeerId::new(peer), repo, SyncMode::Auto, Some(key.clone()))
+    }
+
+    #[test]
+    fn test_attachment_transfer_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = RepoKey::generate();
+        let a = engine(&dir.path().join("a"), "peer-a", &key);
+        let b = engine(&dir.path().join("b"), "peer-b", &key);
+
+        let image: Vec<u8> = (0..300_000u32).map(|i| (i % 256) as u8).collect();
+        a.repo.store_attachment("assets/logo.png", &image).unwrap();
+
+        let repo_id = uuid::Uuid::nil();
+        let manifest = a.attachment_manifest().unwrap();
+        let missing = b
+            .apply_attachment_manifest(&a.local_peer_id, &repo_id, manifest.clone())
+            .unwrap();
+        assert_eq!(missing, vec![manifest[0].1.clone()]);
+
+        let chunks = a.get_blob_chunks_for_sync(&missing[0]).unwrap();
+        assert_eq!(chunks.len(), 2);
+        let done: Vec<bool> = chunks
+            .iter()
+            .rev()
+            .map(|c| b.apply_blob_chunk(&a.local_peer_id, c).unwrap())
+            .collect();
+        assert_eq!(done, [false, true]);
+        assert_eq!(b.repo.get_blob(&missing[0]).unwrap().unwrap(), image);
+
+        // 影子库中可见附件节点，再次交换清单无缺失
+        let nodes = b
+            .repo
+            .list_nodes(&RepoType::Remote(a.local_peer_id.clone(), repo_id))
+            .unwrap();
+        assert!(
+            nodes
+                .iter()
+                .any(|(_, m)| m.kind == NodeKind::Attachment && m.path == "assets/logo.png")
+        );
+        assert!(
+            b.apply_attachment_manifest(&a.local_peer_id, &repo_id, manifest)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -1,10 +1,14 @@
 use super::SyncEngine;
+use crate::security::EncryptedOp;
 use crate::sync::protocol::{SyncRequest, SyncResponse};
 use anyhow::Result;
 
 mod apply;
+mod blob;
 mod snapshot;
 
+pub use blob::ManifestEntry;
+
 impl SyncEngine {
     /// 从本地仓库获取指定范围的操作 (用于发送给远端)。
     ///
@@ -32,6 +36,8 @@ impl SyncEngine {
             encrypted_ops.push(repo_key.encrypt(&entry, seq)?);
         }
 
+        record_sync_bytes(&request.peer_id, "sent", &encrypted_ops);
+
         Ok(SyncResponse {
             peer_id: request.peer_id.clone(),
             repo_id: request.repo_id,
@@ -39,3 +45,12 @@ impl SyncEngine {
         })
     }
 }
+
+/// 记录一批同步密文的字节数 (ciphertext + nonce)
+fn record_sync_bytes(peer_id: &crate::models::PeerId, direction: &str, ops: &[EncryptedOp]) {
+    let bytes: usize = ops
+        .iter()
+        .map(|op| op.ciphertext.len() + op.nonce.len())
+        .sum();
+    crate::metrics::SYNC_BYTES.observe(&[peer_id.as_str(), direction], bytes as f64);
+}
@@ -139,6 +139,7 @@ impl<'a> FsEventHandler<'a> {
                 peer_id: crate::models::PeerId::new("local_watcher"),
                 seq: 0,
             };
+            let _guard = self.repo.lock_edits();
             self.repo.append_local_op(&entry)?;
             info!("Handler: Ingested initial content.");
         }
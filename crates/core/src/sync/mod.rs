@@ -56,19 +56,26 @@ impl SyncManager {
         scan::scan_vault(&self.repo, &self.vfs, &self.vault_root)
     }
 
+    /// 将磁盘上被外部修改的内容以修正操作写入账本，返回是否写入
     pub fn reconcile_doc(&self, doc_id: DocId) -> Result<bool> {
+        let _guard = self.repo.lock_edits();
+        self.reconcile_doc_locked(doc_id)
+    }
+
+    /// 同 `reconcile_doc`
+    ///
+    /// # 前置条件
+    /// - 调用方持有 `RepoManager::lock_edits`
+    pub fn reconcile_doc_locked(&self, doc_id: DocId) -> Result<bool> {
         if let Some(path_str) = self.repo.get_path_by_docid(doc_id)? {
             let file_path = self.vault_root.join(&path_str);
 
             if file_path.exists() {
                 let disk_content = std::fs::read_to_string(&file_path)?;
-                let ops = self.repo.get_local_ops(doc_id)?;
+                let ledger_content = self.repo.get_local_content(doc_id)?;
 
-                let fix_ops = reconcile::compute_reconcile_ops(
-                    doc_id,
-                    &ops.into_iter().map(|(_, e)| e).collect::<Vec<_>>(),
-                    &disk_content,
-                )?;
+                let fix_ops =
+                    reconcile::compute_reconcile_ops(doc_id, &ledger_content, &disk_content)?;
 
                 if !fix_ops.is_empty() {
                     info!(
@@ -126,12 +133,9 @@ impl SyncManager {
         persist: bool,
     ) -> Result<(u64, u64)> {
         // 1. Append Op to Ledger
-        let seqs = crate::ledger::ops::append_generated_op(
-            &self.repo.local_db,
-            doc_id,
-            peer_id,
-            op_entry_builder,
-        )?;
+        let seqs = self
+            .repo
+            .append_generated_op(doc_id, peer_id, op_entry_builder)?;
 
         // 2. Optional Persist
         if persist && let Err(e) = self.persist_doc(doc_id) {
@@ -153,4 +157,17 @@ impl SyncManager {
         let handler = handler::FsEventHandler::new(&self.repo, &self.vfs, &self.vault_root);
         handler.handle_event(path_str, self)
     }
+
+    /// 路径当前绑定的文档
+    pub fn doc_id_at(&self, path_str: &str) -> Result<Option<DocId>> {
+        self.repo.get_docid(path_str)
+    }
+
+    /// 处理附件文件事件，返回附件集合是否变化
+    pub fn handle_attachment_event(&self, path_str: &str) -> Result<bool> {
+        if !self.vault_root.join(path_str).is_file() {
+            return Ok(self.repo.remove_attachment(path_str)?.is_some());
+        }
+        scan::ingest_attachment(&self.repo, &self.vault_root, path_str)
+    }
 }
@@ -1,6 +1,5 @@
 use crate::ledger::RepoManager;
-use crate::ledger::ops;
-use crate::models::{DocId, LedgerEntry, Op, PeerId};
+use crate::models::DocId;
 use anyhow::Result;
 
 /// 重建结果：用于调用方决定是否保存快照、上报版本等。
@@ -10,48 +9,24 @@ pub(crate) struct RebuildResult {
     pub max_seq: u64,
 }
 
-/// 从“最新快照 + 增量操作”重建本地文档内容。
+/// 读取本地文档当前内容及其相对最新快照的位置。
 ///
-/// Invariants:
-/// - 对同一 `doc_id`，操作必须按 `seq` 升序应用。
-/// - 若存在快照，其 `seq` 必须小于等于随后应用的所有操作 `seq`。
-///
-/// Pre-conditions:
-/// - 快照内容（若存在）应对应快照序列号的真实状态。
+/// 内容来自 `RepoManager` 的文档状态缓存 (未命中时从最新快照起重放其后的操作)，
+/// `base_seq` 为最新快照序号，供快照策略计算增量。
 ///
 /// Post-conditions:
 /// - 返回内容等价于从空状态依次应用该文档全部操作后的结果。
+/// - `max_seq >= base_seq`。
 pub(crate) fn rebuild_local_doc(repo: &RepoManager, doc_id: DocId) -> Result<RebuildResult> {
-    let (base_seq, base_content) = match repo.load_latest_snapshot(doc_id)? {
-        Some((seq, content)) => (seq, content),
-        None => (0, String::new()),
-    };
-
-    let mut entries = Vec::new();
-    if !base_content.is_empty() {
-        entries.push(LedgerEntry {
-            doc_id,
-            op: Op::Insert {
-                pos: 0,
-                content: base_content.into(),
-            },
-            timestamp: 0,
-            peer_id: PeerId::new("snapshot"),
-            seq: base_seq,
-        });
-    }
-
-    let mut max_seq = base_seq;
-    let delta_entries = ops::get_ops_from_db_after(&repo.local_db, doc_id, base_seq)?;
-    for (seq, entry) in delta_entries {
-        max_seq = max_seq.max(seq);
-        entries.push(entry);
-    }
-
-    let content = crate::state::reconstruct_content(&entries);
+    let _timer = crate::metrics::SNAPSHOT_REBUILD_SECONDS.start_timer();
+    let base_seq = repo
+        .load_latest_snapshot(doc_id)?
+        .map(|(seq, _)| seq)
+        .unwrap_or(0);
+    let (content, max_seq) = repo.get_local_state(doc_id)?;
     Ok(RebuildResult {
         content,
         base_seq,
-        max_seq,
+        max_seq: max_seq.max(base_seq),
     })
 }
@@ -6,14 +6,12 @@ use tracing::info;
 
 /// Compares Ledger state with Disk content.
 /// Returns a list of Ops required to make the Ledger match the Disk.
-/// Returns None if content is identical.
+/// Returns an empty list if content is identical.
 pub fn compute_reconcile_ops(
     doc_id: DocId,
-    ledger_ops: &[LedgerEntry],
+    ledger_content: &str,
     disk_content: &str,
 ) -> Result<Vec<LedgerEntry>> {
-    let ledger_content = state::reconstruct_content(ledger_ops);
-
     // Normalize newlines for comparison
     let disk_norm = disk_content.replace("\r\n", "\n");
     let ledger_norm = ledger_content.replace("\r\n", "\n");
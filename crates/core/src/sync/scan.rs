@@ -1,5 +1,6 @@
 // crates\core\src\sync
 use crate::ledger::RepoManager;
+use crate::ledger::blob::MAX_ATTACHMENT_BYTES;
 use crate::ledger::listing::RepoListing;
 use crate::models::RepoType;
 use crate::utils::path::{path_to_forward_slash, to_forward_slash};
@@ -14,13 +15,15 @@ use walkdir::WalkDir;
 /// Performs a full scan of the vault.
 /// 1. Registers new files in Ledger.
 /// 2. Binds Inodes.
-/// 3. Removes ghost entries from Ledger.
+/// 3. Stores non-Markdown files as attachments (Blob Store).
+/// 4. Removes ghost entries (docs and attachments) from Ledger.
 pub fn scan_vault(repo: &Arc<RepoManager>, vfs: &Vfs, vault_root: &Path) -> Result<()> {
     info!("SyncScan: Starting full scan of {:?}", vault_root);
 
     // 1. Scan Disk -> Ledger
     let walker = WalkDir::new(vault_root).into_iter();
     let mut on_disk_paths = std::collections::HashSet::new();
+    let mut on_disk_attachments = std::collections::HashSet::new();
 
     for entry in walker.filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.')) {
         match entry {
@@ -49,6 +52,19 @@ pub fn scan_vault(repo: &Arc<RepoManager>, vfs: &Vfs, vault_root: &Path) -> Resu
                     if let Ok(Some(inode)) = vfs.get_inode(&path_str) {
                         let _ = repo.bind_inode(&inode, doc_id);
                     }
+                } else if entry.file_type().is_file()
+                    && let Ok(rel_path) = entry.path().strip_prefix(vault_root)
+                {
+                    let path_str = path_to_forward_slash(rel_path);
+                    if !is_attachment_path(&path_str) {
+                        continue;
+                    }
+                    match ingest_attachment(repo, vault_root, &path_str) {
+                        Ok(_) => {
+                            on_disk_attachments.insert(path_str);
+                        }
+                        Err(e) => warn!("SyncScan: 附件登记失败 {}: {:?}", path_str, e),
+                    }
                 }
             }
             Err(e) => warn!("Walk error: {:?}", e),
@@ -93,6 +109,43 @@ pub fn scan_vault(repo: &Arc<RepoManager>, vfs: &Vfs, vault_root: &Path) -> Resu
         }
     }
 
+    for (path, _) in repo.list_attachments()? {
+        if !on_disk_attachments.contains(&path) {
+            info!("SyncScan: 移除幽灵附件: {}", path);
+            repo.remove_attachment(&path)?;
+        }
+    }
+
     info!("SyncScan: Scan complete.");
     Ok(())
 }
+
+/// 是否作为附件收录 (非 Markdown、非隐藏、非编辑器临时文件)
+pub fn is_attachment_path(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let Some((stem, ext)) = name.rsplit_once('.') else {
+        return false;
+    };
+    let ext = ext.to_ascii_lowercase();
+    !stem.is_empty()
+        && !name.starts_with('.')
+        && !name.ends_with('~')
+        && !matches!(
+            ext.as_str(),
+            "md" | "tmp" | "swp" | "swx" | "part" | "crdownload" | "lock"
+        )
+}
+
+/// 读取磁盘文件并存入 Blob Store，返回内容是否变化
+///
+/// 超过 `MAX_ATTACHMENT_BYTES` 的文件忽略 (返回 false)。
+pub fn ingest_attachment(repo: &RepoManager, vault_root: &Path, path: &str) -> Result<bool> {
+    let file_path = vault_root.join(path);
+    if std::fs::metadata(&file_path)?.len() > MAX_ATTACHMENT_BYTES {
+        warn!("附件超过大小上限，已忽略: {}", path);
+        return Ok(false);
+    }
+    let bytes = std::fs::read(&file_path)?;
+    let (_, changed) = repo.store_attachment(path, &bytes)?;
+    Ok(changed)
+}
@@ -4,7 +4,7 @@
 //! 定义树的增量更新消息类型，用于 WebSocket 传输。
 
 use super::node::FileNode;
-use crate::models::{DocId, NodeId};
+use crate::models::{DocId, NodeId, NodeKind};
 use serde::{Deserialize, Serialize};
 
 /// 树增量更新
@@ -33,7 +33,9 @@ pub enum TreeDelta {
         name: String,
         /// 完整路径 (缓存)
         path: String,
-        /// 文档 ID (文件夹为 None)
+        /// 节点类型
+        kind: NodeKind,
+        /// 文档 ID (文件夹/附件为 None)
         doc_id: Option<DocId>,
     },
 
@@ -79,6 +81,7 @@ impl TreeDelta {
             parent_id,
             name,
             path,
+            kind: NodeKind::File,
             doc_id: Some(doc_id),
         }
     }
@@ -95,6 +98,24 @@ impl TreeDelta {
             parent_id,
             name,
             path,
+            kind: NodeKind::Dir,
+            doc_id: None,
+        }
+    }
+
+    /// 创建添加附件 Delta
+    pub fn add_attachment(
+        node_id: NodeId,
+        parent_id: Option<NodeId>,
+        name: String,
+        path: String,
+    ) -> Self {
+        Self::Add {
+            node_id,
+            parent_id,
+            name,
+            path,
+            kind: NodeKind::Attachment,
             doc_id: None,
         }
     }
@@ -7,7 +7,7 @@
 use super::delta::TreeDelta;
 use super::node::FileNode;
 use super::ops::{NodeInfo, build_subtree_iterative, remove_iterative};
-use crate::models::{DocId, NodeId, NodeMeta};
+use crate::models::{DocId, NodeId, NodeKind, NodeMeta};
 use crate::utils::path::to_forward_slash;
 use std::collections::HashMap;
 
@@ -48,6 +48,7 @@ impl TreeManager {
                     parent_id: meta.parent_id,
                     children_ids: Vec::new(),
                     path_cache: meta.path.clone(),
+                    kind: meta.kind,
                     doc_id: meta.doc_id,
                 },
             );
@@ -83,6 +84,7 @@ impl TreeManager {
                 parent_id,
                 children_ids: Vec::new(),
                 path_cache: path.clone(),
+                kind: NodeKind::File,
                 doc_id: Some(doc_id),
             };
             self.insert_node(info);
@@ -111,6 +113,7 @@ impl TreeManager {
             parent_id,
             children_ids: Vec::new(),
             path_cache: path.clone(),
+            kind: NodeKind::File,
             doc_id: Some(doc_id),
         });
         TreeDelta::add_file(node_id, parent_id, name, path, doc_id)
@@ -130,11 +133,32 @@ impl TreeManager {
             parent_id,
             children_ids: Vec::new(),
             path_cache: path.clone(),
+            kind: NodeKind::Dir,
             doc_id: None,
         });
         TreeDelta::add_folder(node_id, parent_id, name, path)
     }
 
+    /// 添加附件节点，返回 Delta
+    pub fn add_attachment(
+        &mut self,
+        node_id: NodeId,
+        path: String,
+        parent_id: Option<NodeId>,
+        name: String,
+    ) -> TreeDelta {
+        self.insert_node(NodeInfo {
+            node_id,
+            name: name.clone(),
+            parent_id,
+            children_ids: Vec::new(),
+            path_cache: path.clone(),
+            kind: NodeKind::Attachment,
+            doc_id: None,
+        });
+        TreeDelta::add_attachment(node_id, parent_id, name, path)
+    }
+
     /// 删除节点，返回 Delta
     pub fn remove(&mut self, node_id: NodeId) -> TreeDelta {
         remove_iterative(&mut self.nodes, node_id);
@@ -257,6 +281,7 @@ fn ensure_dir_nodes(
                 parent_id,
                 children_ids: Vec::new(),
                 path_cache: current.clone(),
+                kind: NodeKind::Dir,
                 doc_id: None,
             };
             nodes.insert(node_id, info);
@@ -3,7 +3,7 @@
 //!
 //! 定义文件树中的节点结构，用于表示文件和文件夹。
 
-use crate::models::{DocId, NodeId};
+use crate::models::{DocId, NodeId, NodeKind};
 use serde::{Deserialize, Serialize};
 
 /// 文件树节点
@@ -20,9 +20,12 @@ pub struct FileNode {
     /// 完整路径 (使用正斜杠格式)
     pub path: String,
 
+    /// 节点类型 (文件 / 文件夹 / 附件)
+    pub kind: NodeKind,
+
     /// 文档 ID
     /// - `Some(id)`: 文件节点
-    /// - `None`: 文件夹节点
+    /// - `None`: 文件夹或附件节点
     pub doc_id: Option<DocId>,
 
     /// 子节点列表
@@ -37,6 +40,7 @@ impl FileNode {
             node_id: NodeId::from_doc_id(doc_id),
             name,
             path,
+            kind: NodeKind::File,
             doc_id: Some(doc_id),
             children: Vec::new(),
         }
@@ -48,6 +52,19 @@ impl FileNode {
             node_id,
             name,
             path,
+            kind: NodeKind::Dir,
+            doc_id: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// 创建附件节点
+    pub fn attachment(node_id: NodeId, name: String, path: String) -> Self {
+        Self {
+            node_id,
+            name,
+            path,
+            kind: NodeKind::Attachment,
             doc_id: None,
             children: Vec::new(),
         }
@@ -55,12 +72,17 @@ impl FileNode {
 
     /// 判断是否为文件夹
     pub fn is_folder(&self) -> bool {
-        self.doc_id.is_none()
+        self.kind == NodeKind::Dir
     }
 
     /// 判断是否为文件
     pub fn is_file(&self) -> bool {
-        self.doc_id.is_some()
+        self.kind == NodeKind::File
+    }
+
+    /// 判断是否为附件
+    pub fn is_attachment(&self) -> bool {
+        self.kind == NodeKind::Attachment
     }
 
     /// 添加子节点
@@ -4,7 +4,7 @@
 //! 以 NodeId 为主键的迭代式树操作。
 
 use super::node::FileNode;
-use crate::models::{DocId, NodeId};
+use crate::models::{DocId, NodeId, NodeKind};
 use std::collections::HashMap;
 
 /// 节点信息 (内部使用)
@@ -15,6 +15,7 @@ pub struct NodeInfo {
     pub parent_id: Option<NodeId>,
     pub children_ids: Vec<NodeId>,
     pub path_cache: String,
+    pub kind: NodeKind,
     pub doc_id: Option<DocId>,
 }
 
@@ -80,6 +81,7 @@ pub fn build_subtree_iterative(
             node_id: info.node_id,
             name: info.name.clone(),
             path: info.path_cache.clone(),
+            kind: info.kind,
             doc_id: info.doc_id,
             children,
         };
@@ -0,0 +1,54 @@
+// crates\core\src\utils
+//! # 防抖批处理线程 (Debounced Batcher)
+//!
+//! 后台索引器共用的事件合并循环: 首个事件到达后开始计时，静默 `debounce`
+//! 或自首个事件起累计 `max_delay` 后，将窗口内的全部事件一次性交给 `flush`。
+//!
+//! ## Invariants
+//! - 每个窗口恰好调用一次 `flush`，事件按到达顺序排列
+//! - 丢弃全部 `Sender` 后，剩余事件 flush 完毕线程即退出
+
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+
+/// 默认防抖间隔: 最后一个事件后静默此时长再提交
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// 持续有事件时的最长等待 (避免索引长期滞后)
+pub const MAX_BATCH_DELAY: Duration = Duration::from_secs(5);
+
+/// 启动批处理线程，返回事件发送端
+pub fn spawn_batcher<E: Send + 'static>(
+    name: &str,
+    debounce: Duration,
+    max_delay: Duration,
+    mut flush: impl FnMut(Vec<E>) + Send + 'static,
+) -> Sender<E> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::Builder::new()
+        .name(name.into())
+        .spawn(move || {
+            while let Ok(first) = rx.recv() {
+                let mut batch = vec![first];
+                let deadline = Instant::now() + max_delay;
+                let mut closed = false;
+                loop {
+                    let wait = debounce.min(deadline.saturating_duration_since(Instant::now()));
+                    match rx.recv_timeout(wait) {
+                        Ok(event) => batch.push(event),
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => {
+                            closed = true;
+                            break;
+                        }
+                    }
+                }
+                flush(batch);
+                if closed {
+                    break;
+                }
+            }
+        })
+        .expect("failed to spawn batcher thread");
+    tx
+}
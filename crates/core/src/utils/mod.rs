@@ -1,3 +1,5 @@
 // crates\core\src\utils
+#[cfg(not(target_arch = "wasm32"))]
+pub mod debounce;
 pub mod hash;
 pub mod path;
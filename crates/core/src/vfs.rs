@@ -6,13 +6,14 @@
 //! ## 功能
 //!
 //! - `get_inode`: 获取跨平台文件标识符，用于重命名检测
-//! - `scan`: 同步 Ledger 与文件系统（添加新文件、清理幽灵条目）
+//! - `scan`: 同步 Ledger 与文件系统（添加新文件、收录附件、清理幽灵条目）
 //!
 //! VFS 层抽象了文件系统操作，提供在文件重命名后仍保持稳定的标识符。
 
 use crate::ledger::RepoManager;
 use crate::ledger::listing::RepoListing;
 use crate::models::{FileNodeId, RepoType};
+use crate::sync::scan::{ingest_attachment, is_attachment_path};
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
@@ -49,10 +50,14 @@ impl Vfs {
     }
 
     /// Scan the vault directory and ensure every markdown file has a DocId in the Ledger.
+    /// Non-markdown files outside hidden directories are stored as attachments (Blob Store).
     /// Also removes entries from the Ledger that no longer exist on disk.
+    ///
+    /// 返回新登记的文档数与新增/变化的附件数之和。
     pub fn scan(&self, repo: &RepoManager) -> Result<usize> {
         let mut count = 0;
         let mut on_disk_paths = std::collections::HashSet::new();
+        let mut on_disk_attachments = std::collections::HashSet::new();
 
         // 1. Scan Disk -> Upsert Ledger
         for entry in WalkDir::new(&self.root).into_iter().filter_map(|e| e.ok()) {
@@ -81,6 +86,20 @@ impl Vfs {
                         let _ = repo.bind_inode(&inode, doc_id);
                     }
                 }
+            } else if path.is_file()
+                && let Ok(rel_path) = path.strip_prefix(&self.root)
+            {
+                // 隐藏目录 (如 `.deve`) 中的文件不是附件
+                let path_str = crate::utils::path::to_forward_slash(&rel_path.to_string_lossy());
+                if path_str.split('/').any(|seg| seg.starts_with('.'))
+                    || !is_attachment_path(&path_str)
+                {
+                    continue;
+                }
+                if ingest_attachment(repo, &self.root, &path_str)? {
+                    count += 1;
+                }
+                on_disk_attachments.insert(path_str);
             }
         }
 
@@ -108,6 +127,12 @@ impl Vfs {
             }
         }
 
+        for (path, _) in repo.list_attachments()? {
+            if !on_disk_attachments.contains(&path) {
+                repo.remove_attachment(&path)?;
+            }
+        }
+
         if removed_count > 0 {
             // println!("Cleaned up {} ghost documents.", removed_count);
             // We can return total changes or just additions.
@@ -116,3 +141,40 @@ impl Vfs {
         Ok(count)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_registers_attachments() {
+        let vault = tempfile::tempdir().unwrap();
+        let ledger = tempfile::tempdir().unwrap();
+        std::fs::write(vault.path().join("note.md"), "# Note").unwrap();
+        std::fs::create_dir_all(vault.path().join("assets")).unwrap();
+        std::fs::write(vault.path().join("assets/pic.png"), [1u8, 2, 3]).unwrap();
+        std::fs::create_dir_all(vault.path().join(".deve")).unwrap();
+        std::fs::write(vault.path().join(".deve/state.json"), "{}").unwrap();
+
+        let repo = RepoManager::init(ledger.path(), 10, None, None).unwrap();
+        let vfs = Vfs::new(vault.path());
+        assert_eq!(vfs.scan(&repo).unwrap(), 2);
+        assert_eq!(
+            repo.read_attachment("assets/pic.png").unwrap(),
+            Some(vec![1, 2, 3])
+        );
+        let paths: Vec<String> = repo
+            .list_attachments()
+            .unwrap()
+            .into_iter()
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(paths, vec!["assets/pic.png".to_string()]);
+
+        // 未变化时不再计数；删除后清理附件
+        assert_eq!(vfs.scan(&repo).unwrap(), 0);
+        std::fs::remove_file(vault.path().join("assets/pic.png")).unwrap();
+        vfs.scan(&repo).unwrap();
+        assert!(repo.list_attachments().unwrap().is_empty());
+    }
+}
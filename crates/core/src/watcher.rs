@@ -24,6 +24,10 @@ pub enum FsEventType {
     DocChange(Vec<crate::protocol::ServerMessage>),
     /// 目录结构变更 (需要重新扫描树)
     DirChange,
+    /// 附件新增/修改/删除 (需要刷新树)
+    AttachmentChange,
+    /// 本批次中内容、路径或存在性发生变化的文档 (供搜索索引等增量消费)
+    DocsTouched(Vec<crate::models::DocId>),
 }
 
 pub struct Watcher {
@@ -76,6 +80,8 @@ impl Watcher {
         root: &std::path::Path,
     ) {
         let mut dir_changed = false;
+        let mut attachment_changed = false;
+        let mut touched = Vec::new();
 
         for event in events {
             let path = &event.path;
@@ -94,13 +100,27 @@ impl Watcher {
                     continue;
                 }
 
-                // 只处理 .md 文件
+                // 非 .md 文件作为附件处理
                 if !path_str.ends_with(".md") {
+                    if crate::sync::scan::is_attachment_path(&path_str) {
+                        match self.sync_manager.handle_attachment_event(&path_str) {
+                            Ok(changed) => attachment_changed |= changed,
+                            Err(e) => error!("Error handling attachment {}: {:?}", path_str, e),
+                        }
+                    }
                     continue;
                 }
 
                 match self.sync_manager.handle_fs_event(&path_str) {
                     Ok(msgs) if !msgs.is_empty() => {
+                        for msg in &msgs {
+                            if let crate::protocol::ServerMessage::DocDeleted { doc_id } = msg {
+                                touched.push(*doc_id);
+                            }
+                        }
+                        if let Ok(Some(doc_id)) = self.sync_manager.doc_id_at(&path_str) {
+                            touched.push(doc_id);
+                        }
                         if let Some(cb) = &self.on_event {
                             cb(FsEventType::DocChange(msgs));
                         }
@@ -111,6 +131,16 @@ impl Watcher {
             }
         }
 
+        if !touched.is_empty()
+            && let Some(cb) = &self.on_event
+        {
+            cb(FsEventType::DocsTouched(touched));
+        }
+
+        if attachment_changed && let Some(cb) = &self.on_event {
+            cb(FsEventType::AttachmentChange);
+        }
+
         // 目录结构变更，通知重新扫描
         if dir_changed && let Some(cb) = &self.on_event {
             cb(FsEventType::DirChange);